@@ -0,0 +1,41 @@
+use ceo::client::aoc::AoC;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const YEAR: i32 = 2023;
+const DAY: u8 = 7;
+
+// Global leaderboard page of the day with a hundred entries for each part, as served by AoC.
+fn global_leaderboard_html() -> String {
+    let entries = (1..=100)
+        .map(|rank| {
+            let (id, offset) = (8100 + rank, 300 + 7 * rank);
+            format!(
+                r#"<div class="leaderboard-entry" data-user-id="{id}"><span class="leaderboard-position">{rank:>3})</span> <span class="leaderboard-time">Dec {DAY:02}  {:02}:{:02}:{:02}</span> <a href="https://github.com/comet" target="_blank">Comet</a></div>"#,
+                offset / 3600,
+                (offset / 60) % 60,
+                offset % 60,
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        r#"<html><body><main>
+<p>First hundred users to get <span class="leaderboard-daydesc-both">both stars</span> on Day {DAY}:</p>
+{entries}
+<p>First hundred users to get the <span class="leaderboard-daydesc-first">first star</span> on Day {DAY}:</p>
+{entries}
+</main></body></html>"#
+    )
+}
+
+// Parsing of a complete global leaderboard, polled in a tight loop on release mornings.
+fn parse_global_leaderboard(c: &mut Criterion) {
+    let html = global_leaderboard_html();
+    c.bench_function("parse_global_leaderboard", |b| {
+        b.iter(|| AoC::parse_global_leaderboard(black_box(&html), YEAR, DAY).unwrap())
+    });
+}
+
+criterion_group!(benches, parse_global_leaderboard);
+criterion_main!(benches);
@@ -1,14 +1,14 @@
 use clap::Parser;
-use serde::Serialize;
 
-fn is_false(b: &bool) -> bool {
-    !b
-}
-
-#[derive(Debug, Parser, Serialize)]
+#[derive(Debug, Parser)]
 pub struct Cli {
     /// Whether to load the private leaderboard for all the previous AOC events
     #[arg(long)]
-    #[serde(skip_serializing_if = "is_false")]
     pub all_years: bool,
+    /// Log the messages instead of posting them, a copy being sent to the monitoring channel
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Check the settings against AoC and the messaging platform, report the problems and exit
+    #[arg(long)]
+    pub check: bool,
 }
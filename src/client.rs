@@ -1,2 +1,11 @@
 pub mod aoc;
+pub mod cooldown;
+pub mod debounce;
+pub mod dispatch;
+pub mod email;
+pub mod provider;
+pub mod quiet;
+pub mod retry;
+pub mod self_check;
 pub mod slack;
+pub mod telegram;
@@ -1,22 +1,33 @@
 use crate::{
+    client::provider::EventProvider,
     config,
-    core::leaderboard::{Entry, Identifier, Leaderboard, ProblemPart, ScrapedLeaderboard},
+    core::leaderboard::{
+        Entry, GlobalParseReport, Identifier, Leaderboard, MemberId, ProblemPart,
+        ScrapedLeaderboard,
+    },
     error::{BotError, BotResult},
 };
 use chrono::{TimeZone, Utc};
+use itertools::Itertools;
 use reqwest::{Client, StatusCode};
-use scraper::{Html, Selector};
+use scraper::{element_ref::ElementRef, CaseSensitivity::CaseSensitive, Html, Selector};
 use std::{collections::HashMap, fmt};
 
 enum Endpoint {
+    Event(i32),
     GlobalLeaderboard(i32, u8),
     DailyChallenge(i32, u8),
     PrivateLeaderboard(i32, u64),
+    PrivateLeaderboardPage(i32, u64),
+    PrivateLeaderboardJoin(i32),
 }
 
 impl fmt::Display for Endpoint {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Endpoint::Event(year) => {
+                write!(f, "/{}", year)
+            }
             Endpoint::GlobalLeaderboard(year, day) => {
                 write!(f, "/{}/leaderboard/day/{}", year, day)
             }
@@ -26,6 +37,12 @@ impl fmt::Display for Endpoint {
             Endpoint::PrivateLeaderboard(year, id) => {
                 write!(f, "/{}/leaderboard/private/view/{}.json", year, id)
             }
+            Endpoint::PrivateLeaderboardPage(year, id) => {
+                write!(f, "/{}/leaderboard/private/view/{}", year, id)
+            }
+            Endpoint::PrivateLeaderboardJoin(year) => {
+                write!(f, "/{}/leaderboard/private", year)
+            }
         }
     }
 }
@@ -47,47 +64,17 @@ impl AoC {
     pub fn new() -> Self {
         let settings = &config::SETTINGS;
         let http_client = Client::builder()
-            .timeout(std::time::Duration::new(settings.aoc_api_timeout_sec, 0))
+            .timeout(std::time::Duration::new(settings.aoc.api_timeout_sec, 0))
             .build()
             .unwrap();
         Self {
             http_client,
-            base_url: settings.aoc_base_url.clone(),
-            private_leaderboard_id: settings.aoc_private_leaderboard_id,
-            session_cookie: settings.aoc_session_cookie.clone(),
+            base_url: settings.aoc.base_url.clone(),
+            private_leaderboard_id: settings.aoc.private_leaderboard_id,
+            session_cookie: settings.aoc.session_cookie.clone(),
         }
     }
 
-    pub async fn global_leaderboard(&self, year: i32, day: u8) -> BotResult<ScrapedLeaderboard> {
-        let leaderboard_response = self.get_global_leaderboard(year, day).await?;
-        let leaderboard = AoC::parse_global_leaderboard(&leaderboard_response, year, day)?;
-        Ok(ScrapedLeaderboard {
-            timestamp: Utc::now(),
-            leaderboard,
-        })
-    }
-
-    pub async fn private_leaderboard(&self, year: i32) -> BotResult<ScrapedLeaderboard> {
-        let leaderboard_response = self.get_private_leaderboard(year).await?;
-        let leaderboard = AoC::parse_private_leaderboard(&leaderboard_response)?;
-        Ok(ScrapedLeaderboard {
-            timestamp: Utc::now(),
-            leaderboard,
-        })
-    }
-
-    pub async fn daily_challenge(&self, year: i32, day: u8) -> BotResult<String> {
-        let daily_challenge = self.get_daily_challenge(year, day).await?;
-        let title = AoC::parse_daily_challenge_title(&daily_challenge)?;
-        Ok(title)
-    }
-
-    pub fn daily_challenge_url(&self, year: i32, day: u8) -> String {
-        let endpoint = Endpoint::DailyChallenge(year, day);
-        let url = format!("{}{}", self.base_url, endpoint);
-        url
-    }
-
     async fn get(&self, endpoint: &Endpoint, session_cookie: Option<String>) -> BotResult<String> {
         let url = format!("{}{}", self.base_url, endpoint);
 
@@ -99,13 +86,12 @@ impl AoC {
         let response = request.send().await?;
 
         match response.status() {
-            StatusCode::OK => response.text().await.map_err(|_| BotError::Parse),
+            StatusCode::OK => Ok(response.text().await?),
             // AoC responds with INTERNAL_SERVER_ERROR when the session cookie is invalid.
-            StatusCode::INTERNAL_SERVER_ERROR => Err(BotError::Http(format!(
-                "{}. The session cookie might have expired.",
-                StatusCode::INTERNAL_SERVER_ERROR
-            ))),
-            _ => Err(BotError::Http(format!("{}", response.status()))),
+            StatusCode::INTERNAL_SERVER_ERROR => Err(BotError::AOC(
+                "Internal Server Error. The session cookie might have expired.",
+            )),
+            status => Err(BotError::HttpStatus(status)),
         }
     }
 
@@ -121,6 +107,12 @@ impl AoC {
         Ok(resp)
     }
 
+    async fn get_event_page(&self, year: i32) -> BotResult<String> {
+        let endpoint = Endpoint::Event(year);
+        let resp = self.get(&endpoint, None).await?;
+        Ok(resp)
+    }
+
     async fn get_private_leaderboard(&self, year: i32) -> BotResult<String> {
         let endpoint = Endpoint::PrivateLeaderboard(year, self.private_leaderboard_id);
         let resp = self
@@ -141,7 +133,24 @@ impl AoC {
         Ok(title.to_string())
     }
 
-    fn parse_global_leaderboard(leaderboard: &str, year: i32, day: u8) -> BotResult<Leaderboard> {
+    // Before an event is announced, AoC either does not find its page or redirects to the page
+    // of the last event, so the page is only the one of the event if its title has the year.
+    fn parse_event_page_is_live(page: &str, year: i32) -> bool {
+        let document = Html::parse_document(page);
+        let selector_title = Selector::parse("title").unwrap();
+        document
+            .select(&selector_title)
+            .next()
+            .is_some_and(|node| node.text().collect::<String>().contains(&year.to_string()))
+    }
+
+    /// Entries of both parts of the global leaderboard page of the day, with the report of the
+    /// entries that could not be parsed.
+    pub fn parse_global_leaderboard(
+        leaderboard: &str,
+        year: i32,
+        day: u8,
+    ) -> BotResult<(Leaderboard, GlobalParseReport)> {
         // The HTML document is organized like so:
         //
         //      <p>First hundred users to get <span class="leaderboard-daydesc-both">both stars</span> on Day XX:</p>
@@ -153,58 +162,85 @@ impl AoC {
         //
         // Instead of just selecting all the div and having some logic based on parsing to get the
         // entries associated with the first or second part, we will directly extract the part
-        // information based on the siblings of the <p> elements.
+        // information based on the siblings of the <p> elements, up to the next <p> element.
+        // If the spans are not found (AoC tweaking its DOM), the <p> elements are looked up on
+        // their text. The headers of both parts are found in a single pass over the <p> elements,
+        // as the document is parsed in the tight polling loop of the release mornings, the text of
+        // a <p> element only being collected when none of its spans identifies the part.
 
         let document = Html::parse_document(leaderboard);
-        let selector_first_part = Selector::parse(r#"span.leaderboard-daydesc-first"#).unwrap();
-        let selector_second_part = Selector::parse(r#"span.leaderboard-daydesc-both"#).unwrap();
-
-        // Entries first part. The selector will only give us the div below the p>span.leaderboard-daydesc-first element
-        let entries_first = document
-            .select(&selector_first_part)
-            .last()
-            .map_or(vec![], |span| {
-                span.parent().map_or(vec![], |p| {
-                    p.next_siblings()
-                        .filter_map(scraper::element_ref::ElementRef::wrap)
-                        .filter_map(|entry| Entry::from_html(entry, year, day, ProblemPart::FIRST))
-                        .collect::<Vec<Entry>>()
-                })
-            });
-
-        // Because the p>span.leaderboard-daydesc-both element is at the top, the selector will give us the entry divs for both parts.
-        // We will need to filter out entries already matched in first part.
-        let entries_second = document
-            .select(&selector_second_part)
-            .last()
-            .map_or(vec![], |span| {
-                span.parent().map_or(vec![], |p| {
-                    p.next_siblings()
-                        .filter_map(scraper::element_ref::ElementRef::wrap)
-                        .filter_map(|entry| Entry::from_html(entry, year, day, ProblemPart::SECOND))
-                        // Filter out entries of first part.
-                        .filter(|e| {
-                            !entries_first.contains(&Entry {
-                                id: e.id.clone(),
-                                timestamp: e.timestamp,
-                                rank: e.rank,
-                                day: e.day,
-                                year: e.year,
-                                part: ProblemPart::FIRST,
-                            })
-                        })
-                        .collect::<Vec<Entry>>()
-                })
-            });
-
+        let selector_paragraph = Selector::parse("p").unwrap();
+        let mut report = GlobalParseReport::default();
         let mut all_entries = Leaderboard::new();
-        all_entries.extend(entries_first);
-        all_entries.extend(entries_second);
 
-        Ok(all_entries)
+        // (part, class of the span, description) of the headers
+        let parts = [
+            (
+                ProblemPart::FIRST,
+                "leaderboard-daydesc-first",
+                "first star",
+            ),
+            (
+                ProblemPart::SECOND,
+                "leaderboard-daydesc-both",
+                "both stars",
+            ),
+        ];
+        // last header of each part, with whether it was found by its span, the headers found by
+        // their span taking precedence over the ones found by their text
+        let mut headers: [Option<(bool, ElementRef)>; 2] = [None, None];
+        for paragraph in document.select(&selector_paragraph) {
+            let mut text = None;
+            for ((_part, class, description), header) in parts.iter().zip(headers.iter_mut()) {
+                let by_span = paragraph.children().filter_map(ElementRef::wrap).any(|el| {
+                    el.value().name() == "span" && el.value().has_class(class, CaseSensitive)
+                });
+                if !by_span {
+                    if matches!(header, Some((true, _))) {
+                        continue;
+                    };
+                    let text = text.get_or_insert_with(|| paragraph.text().collect::<String>());
+                    if !text.contains(description) {
+                        continue;
+                    };
+                };
+                *header = Some((by_span, paragraph));
+            }
+        }
+
+        for ((part, _class, _description), header) in parts.into_iter().zip(headers) {
+            let Some((_by_span, header)) = header else {
+                continue;
+            };
+
+            let candidates = header
+                .next_siblings()
+                .filter_map(ElementRef::wrap)
+                .take_while(|el| el.value().name() != "p")
+                .filter(|el| {
+                    el.value().name() == "div" || el.value().attr("data-user-id").is_some()
+                })
+                .collect::<Vec<ElementRef>>();
+
+            let mut max_rank = 0;
+            for candidate in candidates.iter() {
+                match Entry::from_html(*candidate, year, day, part) {
+                    Ok(entry) => {
+                        max_rank = max_rank.max(entry.rank.unwrap_or_default() as usize);
+                        report.parsed += 1;
+                        all_entries.insert(entry);
+                    }
+                    Err(failure) => *report.failures.entry(failure).or_default() += 1,
+                }
+            }
+            // entries too altered to be recognized as such are still accounted for by the ranks
+            report.expected += max_rank.max(candidates.len());
+        }
+
+        Ok((all_entries, report))
     }
 
-    fn parse_private_leaderboard(leaderboard: &str) -> BotResult<Leaderboard> {
+    pub(crate) fn parse_private_leaderboard(leaderboard: &str) -> BotResult<Leaderboard> {
         // Response from AOC private leaderboard API.
         // Structs defined here as it is only used by this function.
         use serde::Deserialize;
@@ -231,11 +267,11 @@ impl AoC {
 
         #[derive(Debug, Deserialize)]
         struct AOCPrivateLeaderboardMemberEntry {
-            // star_index: u64,
+            star_index: u64,
             get_star_ts: i64,
         }
 
-        let parsed = serde_json::from_str::<AOCPrivateLeaderboardResponse>(leaderboard).unwrap();
+        let parsed = serde_json::from_str::<AOCPrivateLeaderboardResponse>(leaderboard)?;
         let mut earned_stars = Leaderboard::new();
 
         for (_, member) in parsed.members.iter() {
@@ -252,13 +288,14 @@ impl AoC {
                             .timestamp_opt(info.get_star_ts, 0)
                             .single()
                             .ok_or(BotError::Parse)?,
+                        star_index: Some(info.star_index),
                         year: parsed.event.parse().map_err(|_| BotError::Parse)?,
                         day: day.parse::<u8>().map_err(|_| BotError::Parse)?,
                         part: ProblemPart::from(star),
                         rank: None,
                         id: Identifier {
                             name: name.clone(),
-                            numeric: member.id,
+                            numeric: MemberId(member.id),
                         },
                     });
                 }
@@ -267,4 +304,227 @@ impl AoC {
 
         Ok(earned_stars)
     }
+
+    /// Members of the private leaderboard, including the ones without any star (who do not
+    /// appear in the parsed entries).
+    pub(crate) fn parse_private_leaderboard_members(
+        leaderboard: &str,
+    ) -> BotResult<Vec<Identifier>> {
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        struct AOCPrivateLeaderboardResponse {
+            members: HashMap<String, AOCPrivateLeaderboardMember>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct AOCPrivateLeaderboardMember {
+            name: Option<String>,
+            id: u64,
+        }
+
+        let parsed = serde_json::from_str::<AOCPrivateLeaderboardResponse>(leaderboard)?;
+        Ok(parsed
+            .members
+            .into_values()
+            .map(|member| Identifier {
+                name: member
+                    .name
+                    .unwrap_or_else(|| format!("anonymous user #{}", member.id)),
+                numeric: MemberId(member.id),
+            })
+            .sorted()
+            .collect())
+    }
+}
+
+impl EventProvider for AoC {
+    async fn fetch_private_leaderboard(&self, year: i32) -> BotResult<String> {
+        self.get_private_leaderboard(year).await
+    }
+
+    fn parse_private_leaderboard(response: &str) -> BotResult<(Leaderboard, Vec<Identifier>)> {
+        Ok((
+            AoC::parse_private_leaderboard(response)?,
+            AoC::parse_private_leaderboard_members(response)?,
+        ))
+    }
+
+    async fn global_leaderboard(
+        &self,
+        year: i32,
+        day: u8,
+    ) -> BotResult<(ScrapedLeaderboard, GlobalParseReport)> {
+        let leaderboard_response = self.get_global_leaderboard(year, day).await?;
+        let (leaderboard, report) =
+            AoC::parse_global_leaderboard(&leaderboard_response, year, day)?;
+        Ok((
+            ScrapedLeaderboard {
+                timestamp: Utc::now(),
+                leaderboard,
+            },
+            report,
+        ))
+    }
+
+    async fn daily_challenge(&self, year: i32, day: u8) -> BotResult<String> {
+        let daily_challenge = self.get_daily_challenge(year, day).await?;
+        let title = AoC::parse_daily_challenge_title(&daily_challenge)?;
+        Ok(title)
+    }
+
+    async fn event_is_live(&self, year: i32) -> BotResult<bool> {
+        match self.get_event_page(year).await {
+            Ok(page) => Ok(AoC::parse_event_page_is_live(&page, year)),
+            Err(BotError::HttpStatus(StatusCode::NOT_FOUND)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn daily_challenge_url(&self, year: i32, day: u8) -> String {
+        let endpoint = Endpoint::DailyChallenge(year, day);
+        format!("{}{}", self.base_url, endpoint)
+    }
+
+    fn private_leaderboard_url(&self, year: i32) -> String {
+        let endpoint = Endpoint::PrivateLeaderboardPage(year, self.private_leaderboard_id);
+        format!("{}{}", self.base_url, endpoint)
+    }
+
+    fn global_leaderboard_url(&self, year: i32, day: u8) -> String {
+        let endpoint = Endpoint::GlobalLeaderboard(year, day);
+        format!("{}{}", self.base_url, endpoint)
+    }
+
+    fn join_private_leaderboard_url(&self, year: i32) -> String {
+        let endpoint = Endpoint::PrivateLeaderboardJoin(year);
+        format!("{}{}", self.base_url, endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::EntryParseFailure,
+        fixtures::{
+            edge_case_members, global_leaderboard_html, huge_board_members,
+            private_leaderboard_json, MemberFixture, YEAR,
+        },
+    };
+    use chrono::Datelike;
+    use itertools::Itertools;
+
+    #[test]
+    fn anonymous_members_get_a_generated_name() {
+        let json = private_leaderboard_json(YEAR, &edge_case_members());
+        let leaderboard = AoC::parse_private_leaderboard(&json).unwrap();
+
+        let anonymous = leaderboard
+            .iter()
+            .filter(|e| e.id.numeric == MemberId(2))
+            .collect::<Vec<_>>();
+        assert_eq!(anonymous.len(), 3);
+        assert!(anonymous.iter().all(|e| e.id.name == "anonymous user #2"));
+    }
+
+    #[test]
+    fn members_without_stars_have_no_entries() {
+        let json = private_leaderboard_json(YEAR, &edge_case_members());
+        let leaderboard = AoC::parse_private_leaderboard(&json).unwrap();
+
+        assert!(!leaderboard.members_ids().contains(&MemberId(3)));
+        assert_eq!(leaderboard.members_ids().len(), 3);
+        // but are still members of the private leaderboard
+        let members = AoC::parse_private_leaderboard_members(&json).unwrap();
+        assert_eq!(members.len(), 4);
+        assert!(members.iter().any(|m| m.numeric == MemberId(3)));
+    }
+
+    #[test]
+    fn single_part_on_last_day_is_parsed() {
+        let json = private_leaderboard_json(YEAR, &edge_case_members());
+        let leaderboard = AoC::parse_private_leaderboard(&json).unwrap();
+
+        let last_day = leaderboard
+            .iter()
+            .filter(|e| e.id.numeric == MemberId(4) && e.day == 25)
+            .collect::<Vec<_>>();
+        assert_eq!(last_day.len(), 1);
+        assert_eq!(last_day[0].part, ProblemPart::FIRST);
+        assert_eq!(
+            last_day[0].duration_since_release().unwrap(),
+            chrono::Duration::seconds(3600)
+        );
+    }
+
+    #[test]
+    fn entries_carry_year_day_and_part() {
+        let members = vec![MemberFixture::new(7, "Dasher").with_day(3, 100, 50)];
+        let leaderboard =
+            AoC::parse_private_leaderboard(&private_leaderboard_json(YEAR, &members)).unwrap();
+
+        let mut entries = leaderboard.iter().sorted().collect::<Vec<_>>().into_iter();
+        let (p1, p2) = (entries.next().unwrap(), entries.next().unwrap());
+        assert_eq!((p1.year, p1.day, p1.part), (YEAR, 3, ProblemPart::FIRST));
+        assert_eq!((p2.year, p2.day, p2.part), (YEAR, 3, ProblemPart::SECOND));
+        assert_eq!(p2.timestamp - p1.timestamp, chrono::Duration::seconds(50));
+        assert!(p1.rank.is_none());
+    }
+
+    #[test]
+    fn huge_board_is_fully_parsed() {
+        let members = huge_board_members(200);
+        let n_stars = members.iter().map(|m| m.stars.len()).sum::<usize>();
+        let leaderboard =
+            AoC::parse_private_leaderboard(&private_leaderboard_json(YEAR, &members)).unwrap();
+
+        assert_eq!(leaderboard.len(), n_stars);
+    }
+
+    #[test]
+    fn altered_global_leaderboard_dom_is_parsed_and_reported() {
+        let html = global_leaderboard_html(
+            6,
+            &[(8001, Some("Vixen"), 300), (8002, None, 420)],
+            &[(8001, Some("Vixen"), 100), (8003, Some("Blitzen"), 150)],
+        )
+        // renamed classes, and the user id of an entry lost
+        .replace("leaderboard-daydesc-", "daydesc-")
+        .replace("leaderboard-position", "position")
+        .replace("leaderboard-time", "time")
+        .replace(r#" data-user-id="8003""#, "");
+
+        let (leaderboard, report) = AoC::parse_global_leaderboard(&html, YEAR, 6).unwrap();
+        let entries = leaderboard
+            .iter()
+            .map(|e| (e.id.numeric, e.part, e.rank))
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (MemberId(8001), ProblemPart::FIRST, Some(1)),
+                (MemberId(8001), ProblemPart::SECOND, Some(1)),
+                (MemberId(8002), ProblemPart::SECOND, Some(2)),
+            ]
+        );
+        assert!(leaderboard
+            .iter()
+            .all(|e| e.timestamp.date_naive().day() == 6));
+        assert_eq!(
+            leaderboard
+                .iter()
+                .find(|e| e.id.numeric == MemberId(8002))
+                .map(|e| e.id.name.as_str()),
+            Some("anonymous user #8002")
+        );
+
+        assert!(report.is_incomplete());
+        assert_eq!((report.parsed, report.expected), (3, 4));
+        assert_eq!(
+            report.failures,
+            HashMap::from([(EntryParseFailure::MissingId, 1)])
+        );
+    }
 }
@@ -0,0 +1,147 @@
+use crate::core::chat::{ChatId, UserId};
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+// Period over which the runs of a command are counted
+const COOLDOWN_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Requester {
+    User(UserId),
+    Channel(ChatId),
+}
+
+/// Command refused by the cooldown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throttled {
+    // first refusal, with the time to wait before the command can be run again
+    Notify(Duration),
+    // the requester was already told how long to wait
+    Silently,
+}
+
+/// Sliding window rate limiting of the commands, so a flood of commands does not trigger as many
+/// (possibly expensive) computations: a same command can be run at most `max_per_user` times by
+/// a user, and at most `max_per_channel` times in a channel, over any one minute window. The
+/// requester is only told once to wait, so a flood does not trigger as many replies either.
+pub struct CommandCooldown {
+    max_per_user: usize,
+    max_per_channel: usize,
+    runs: Mutex<HashMap<(Requester, &'static str), VecDeque<Instant>>>,
+    // end of the wait the requesters were told about
+    notified: Mutex<HashMap<(Requester, &'static str), Instant>>,
+}
+
+impl CommandCooldown {
+    pub fn new(max_per_user: usize, max_per_channel: usize) -> CommandCooldown {
+        CommandCooldown {
+            max_per_user,
+            max_per_channel,
+            runs: Mutex::new(HashMap::new()),
+            notified: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record the run of the command, unless the user or the channel ran it too often. Returns
+    /// whether the requester still has to be told how long to wait in that case.
+    pub fn try_run(
+        &self,
+        command: &'static str,
+        user: Option<&UserId>,
+        channel: &ChatId,
+        now: Instant,
+    ) -> Result<(), Throttled> {
+        let mut runs = self.runs.lock().unwrap();
+        // runs out of the window are forgotten, along with the requesters without recent runs
+        runs.retain(|_key, times| {
+            while times
+                .front()
+                .is_some_and(|t| now.duration_since(*t) >= COOLDOWN_WINDOW)
+            {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let limits = user
+            .map(|user| (Requester::User(user.clone()), self.max_per_user))
+            .into_iter()
+            .chain([(Requester::Channel(channel.clone()), self.max_per_channel)])
+            .collect::<Vec<(Requester, usize)>>();
+        let wait = limits
+            .iter()
+            .filter_map(|(requester, max)| {
+                let times = runs.get(&(requester.clone(), command))?;
+                (times.len() >= *max).then(|| {
+                    let wait = COOLDOWN_WINDOW - now.duration_since(times[times.len() - max]);
+                    (wait, requester)
+                })
+            })
+            .max_by_key(|(wait, _requester)| *wait);
+        if let Some((wait, requester)) = wait {
+            let mut notified = self.notified.lock().unwrap();
+            notified.retain(|_key, until| *until > now);
+            return match notified.entry((requester.clone(), command)) {
+                Entry::Occupied(_) => Err(Throttled::Silently),
+                Entry::Vacant(entry) => {
+                    entry.insert(now + wait);
+                    Err(Throttled::Notify(wait))
+                }
+            };
+        };
+
+        limits.into_iter().for_each(|(requester, _max)| {
+            runs.entry((requester, command)).or_default().push_back(now);
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_limited_per_user_and_per_channel() {
+        let cooldown = CommandCooldown::new(2, 3);
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let (comet, cupid) = (UserId("U0COMET".to_string()), UserId("U0CUPID".to_string()));
+        let general = ChatId("C0GENERAL".to_string());
+
+        assert!(cooldown
+            .try_run("board", Some(&comet), &general, at(0))
+            .is_ok());
+        assert!(cooldown
+            .try_run("board", Some(&comet), &general, at(10))
+            .is_ok());
+        assert_eq!(
+            cooldown.try_run("board", Some(&comet), &general, at(20)),
+            Err(Throttled::Notify(Duration::from_secs(40)))
+        );
+        // the user was already told to wait
+        assert_eq!(
+            cooldown.try_run("board", Some(&comet), &general, at(25)),
+            Err(Throttled::Silently)
+        );
+        // other commands are not affected
+        assert!(cooldown
+            .try_run("help", Some(&comet), &general, at(20))
+            .is_ok());
+        // the channel reaches its own limit
+        assert!(cooldown
+            .try_run("board", Some(&cupid), &general, at(30))
+            .is_ok());
+        assert_eq!(
+            cooldown.try_run("board", None, &general, at(40)),
+            Err(Throttled::Notify(Duration::from_secs(20)))
+        );
+        // the first run is out of the window
+        assert!(cooldown
+            .try_run("board", Some(&comet), &general, at(60))
+            .is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{client::dispatch::EventSink, core::events::Event, utils::DayHighlight};
+use std::{
+    mem,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Merge the highlights of the new entries announced within the debounce window into a single
+/// announcement, instead of a drip of small messages (one per scrape cycle), then deliver the
+/// events to the inner sink. The held highlights are posted at the first event (e.g. the end of
+/// a scrape cycle, or the periodic tick of the scheduler) received once the window, started by
+/// the first held highlight, has elapsed.
+pub struct DebounceSink<S> {
+    inner: S,
+    window: Option<Duration>,
+    // highlights held back, with the time the first one was received
+    held: Option<(Instant, Vec<DayHighlight>)>,
+}
+
+impl<S: EventSink + Send + Sync> DebounceSink<S> {
+    pub fn new(inner: S, window: Option<Duration>) -> Self {
+        DebounceSink {
+            inner,
+            window,
+            held: None,
+        }
+    }
+
+    // Events to deliver now.
+    fn route(&mut self, event: Event, now: Instant) -> Vec<Event> {
+        let Some(window) = self.window else {
+            return vec![event];
+        };
+        let event = match event {
+            Event::PrivateLeaderboardNewEntries(highlights) => {
+                info!("Holding back {} highlight(s).", highlights.len());
+                let (_, held) = self.held.get_or_insert_with(|| (now, vec![]));
+                held.extend(highlights);
+                None
+            }
+            // only there to post the held highlights without waiting for the next event
+            Event::HighlightsDebounceTick => None,
+            event => Some(event),
+        };
+        let elapsed = self
+            .held
+            .as_ref()
+            .is_some_and(|(start, _)| now.duration_since(*start) >= window);
+        let merged = match elapsed {
+            true => mem::take(&mut self.held)
+                .map(|(_, highlights)| Event::PrivateLeaderboardNewEntries(highlights)),
+            false => None,
+        };
+        merged.into_iter().chain(event).collect()
+    }
+}
+
+impl<S: EventSink + Send + Sync> EventSink for DebounceSink<S> {
+    async fn deliver(&mut self, event: Event) {
+        for event in self.route(event, Instant::now()) {
+            self.inner.deliver(event).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NullSink;
+
+    impl EventSink for NullSink {
+        async fn deliver(&mut self, _event: Event) {}
+    }
+
+    fn highlight(name: &str) -> DayHighlight {
+        DayHighlight {
+            parts_duration: vec!["00:10:00".to_string()],
+            year: 2005,
+            day: 1,
+            n_stars: 1,
+            name: name.to_string(),
+            delta: None,
+            delta_percentile: None,
+            new_points: 10,
+        }
+    }
+
+    #[test]
+    fn highlights_within_the_window_are_merged() {
+        let window = Duration::from_secs(30 * 60);
+        let mut sink = DebounceSink::new(NullSink, Some(window));
+        let labels = |events: Vec<Event>| {
+            events
+                .iter()
+                .map(<&'static str>::from)
+                .collect::<Vec<&str>>()
+        };
+        let start = Instant::now();
+        let cycle = |minutes: u64| start + Duration::from_secs(minutes * 60);
+
+        let entries = |name: &str| Event::PrivateLeaderboardNewEntries(vec![highlight(name)]);
+        assert!(sink.route(entries("Holly"), cycle(0)).is_empty());
+        assert_eq!(
+            labels(sink.route(Event::PrivateLeaderboardUpdated, cycle(0))),
+            vec!["PrivateLeaderboardUpdated"]
+        );
+        assert!(sink.route(entries("Ivy"), cycle(15)).is_empty());
+
+        match &sink.route(Event::PrivateLeaderboardUpdated, cycle(30))[..] {
+            [Event::PrivateLeaderboardNewEntries(highlights), Event::PrivateLeaderboardUpdated] => {
+                assert_eq!(
+                    highlights
+                        .iter()
+                        .map(|h| h.name.as_str())
+                        .collect::<Vec<_>>(),
+                    vec!["Holly", "Ivy"]
+                )
+            }
+            events => panic!(
+                "{} events delivered instead of the merged highlights",
+                events.len()
+            ),
+        };
+        assert_eq!(
+            labels(sink.route(Event::PrivateLeaderboardUpdated, cycle(45))),
+            vec!["PrivateLeaderboardUpdated"]
+        );
+
+        // the last burst is posted by the tick once the window has elapsed, without another event
+        assert!(sink.route(entries("Holly"), cycle(50)).is_empty());
+        assert!(sink
+            .route(Event::HighlightsDebounceTick, cycle(60))
+            .is_empty());
+        assert_eq!(
+            labels(sink.route(Event::HighlightsDebounceTick, cycle(80))),
+            vec!["PrivateLeaderboardNewEntries"]
+        );
+
+        let mut sink = DebounceSink::new(NullSink, None);
+        assert_eq!(
+            labels(sink.route(entries("Holly"), start)),
+            vec!["PrivateLeaderboardNewEntries"]
+        );
+    }
+}
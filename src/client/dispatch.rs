@@ -0,0 +1,252 @@
+use crate::core::events::Event;
+use std::{collections::VecDeque, future::Future, pin::Pin};
+use tokio::sync::mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender};
+use tracing::error;
+
+/// Posting of a message, along with the work depending on its outcome (e.g. continuing it in a
+/// thread).
+pub type Delivery = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Destination of the dispatched events (e.g. the Slack workspace).
+pub trait EventSink {
+    fn deliver(&mut self, event: Event) -> impl Future<Output = ()> + Send;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Lane {
+    // responses to users interactions (commands)
+    Interactive,
+    // everything else (highlights, summaries, monitoring, ...)
+    Announcement,
+}
+
+impl Lane {
+    pub fn of(event: &Event) -> Lane {
+        match event {
+            Event::CommandReceived(..) => Lane::Interactive,
+            _ => Lane::Announcement,
+        }
+    }
+}
+
+/// Pending events (or deliveries), split by lane. Each lane is first in, first out.
+pub struct PriorityLanes<T> {
+    interactive: VecDeque<T>,
+    announcements: VecDeque<T>,
+}
+
+impl<T> Default for PriorityLanes<T> {
+    fn default() -> Self {
+        PriorityLanes {
+            interactive: VecDeque::new(),
+            announcements: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> PriorityLanes<T> {
+    pub fn push(&mut self, lane: Lane, item: T) {
+        match lane {
+            Lane::Interactive => self.interactive.push_back(item),
+            Lane::Announcement => self.announcements.push_back(item),
+        }
+    }
+
+    /// Next item to deliver, interactive responses first.
+    pub fn pop(&mut self) -> Option<T> {
+        self.interactive
+            .pop_front()
+            .or_else(|| self.announcements.pop_front())
+    }
+}
+
+/// Outbound queue of the messages to post, delivered one after the other by a separate task, so
+/// a message being retried (e.g. while Slack is rate limiting the bot) does not hold up the
+/// dispatch of the events. Responses to commands jump ahead of the pending announcements.
+#[derive(Clone)]
+pub struct OutboundQueue {
+    tx: UnboundedSender<(Lane, Delivery)>,
+}
+
+impl OutboundQueue {
+    /// Start the task delivering the queued messages.
+    pub fn spawn() -> OutboundQueue {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(deliver_in_order(rx));
+        OutboundQueue { tx }
+    }
+
+    pub fn push(&self, lane: Lane, delivery: impl Future<Output = ()> + Send + 'static) {
+        if self.tx.send((lane, Box::pin(delivery))).is_err() {
+            error!("Outbound queue closed, message dropped.");
+        };
+    }
+}
+
+async fn deliver_in_order(mut rx: UnboundedReceiver<(Lane, Delivery)>) {
+    let mut lanes = PriorityLanes::default();
+    loop {
+        while let Ok((lane, delivery)) = rx.try_recv() {
+            lanes.push(lane, delivery);
+        }
+        match lanes.pop() {
+            Some(delivery) => delivery.await,
+            None => match rx.recv().await {
+                Some((lane, delivery)) => lanes.push(lane, delivery),
+                None => break,
+            },
+        }
+    }
+}
+
+/// Deliver the received events to the sink, interactive responses jumping ahead of the pending
+/// announcements. Returns once the channel is closed and every pending event has been delivered.
+pub async fn dispatch(mut rx: Receiver<Event>, sink: &mut impl EventSink) {
+    let mut lanes = PriorityLanes::default();
+    loop {
+        // Everything already waiting in the channel is queued, so that a command response sent
+        // after a burst of announcements does not wait for all of them to be delivered.
+        while let Ok(event) = rx.try_recv() {
+            lanes.push(Lane::of(&event), event);
+        }
+        match lanes.pop() {
+            Some(event) => sink.deliver(event).await,
+            None => match rx.recv().await {
+                Some(event) => lanes.push(Lane::of(&event), event),
+                None => break,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        chat::{ChatId, MessageId},
+        commands::Command,
+        events::Reply,
+    };
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use tokio::sync::mpsc::{self, Sender};
+
+    fn announcement(n: u64) -> Event {
+        Event::GlobalLeaderboardUpdateMessage(n, 0)
+    }
+
+    fn command(n: u64) -> Event {
+        Event::CommandReceived(
+            ChatId("C0".to_string()),
+            MessageId(n.to_string()),
+            Reply::Thread,
+            Command::Help,
+        )
+    }
+
+    /// Records the delivered events, optionally sending new events while delivering the first one.
+    #[derive(Default)]
+    struct MockSink {
+        delivered: Vec<String>,
+        on_first_delivery: Option<(Sender<Event>, Vec<Event>)>,
+    }
+
+    impl EventSink for MockSink {
+        async fn deliver(&mut self, event: Event) {
+            let label = match &event {
+                Event::GlobalLeaderboardUpdateMessage(n, _) => format!("a{n}"),
+                Event::CommandReceived(_, ts, _, _) => format!("c{}", ts.0),
+                _ => "other".to_string(),
+            };
+            self.delivered.push(label);
+            if let Some((tx, events)) = self.on_first_delivery.take() {
+                for event in events {
+                    tx.send(event).await.unwrap();
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn commands_are_delivered_before_pending_announcements() {
+        let (tx, rx) = mpsc::channel(64);
+        for event in [
+            announcement(1),
+            announcement(2),
+            command(1),
+            announcement(3),
+            command(2),
+        ] {
+            tx.send(event).await.unwrap();
+        }
+        drop(tx);
+
+        let mut sink = MockSink::default();
+        dispatch(rx, &mut sink).await;
+
+        assert_eq!(sink.delivered, vec!["c1", "c2", "a1", "a2", "a3"]);
+    }
+
+    #[tokio::test]
+    async fn commands_received_during_a_burst_jump_the_queue() {
+        let (tx, rx) = mpsc::channel(64);
+        for n in 1..=4 {
+            tx.send(announcement(n)).await.unwrap();
+        }
+
+        let mut sink = MockSink {
+            on_first_delivery: Some((tx.clone(), vec![announcement(5), command(1)])),
+            ..Default::default()
+        };
+        drop(tx);
+        dispatch(rx, &mut sink).await;
+
+        assert_eq!(sink.delivered, vec!["a1", "c1", "a2", "a3", "a4", "a5"]);
+    }
+
+    #[tokio::test]
+    async fn pending_events_are_drained_when_channel_closes() {
+        let (tx, rx) = mpsc::channel(64);
+        let events = (1..=10).map(|n| match n % 3 {
+            0 => command(n),
+            _ => announcement(n),
+        });
+        for event in events {
+            tx.send(event).await.unwrap();
+        }
+        drop(tx);
+
+        let mut sink = MockSink::default();
+        dispatch(rx, &mut sink).await;
+
+        assert_eq!(
+            sink.delivered,
+            vec!["c3", "c6", "c9", "a1", "a2", "a4", "a5", "a7", "a8", "a10"]
+        );
+    }
+
+    #[tokio::test]
+    async fn queued_deliveries_run_in_order_commands_first() {
+        let queue = OutboundQueue::spawn();
+        let delivered = Arc::new(Mutex::new(vec![]));
+        let deliver = |label: &'static str, delay: u64| {
+            let delivered = delivered.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                delivered.lock().unwrap().push(label);
+            }
+        };
+
+        // queued while the first (slow) delivery is running
+        queue.push(Lane::Announcement, deliver("a1", 50));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        queue.push(Lane::Announcement, deliver("a2", 0));
+        queue.push(Lane::Interactive, deliver("c1", 0));
+        queue.push(Lane::Announcement, deliver("a3", 0));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(*delivered.lock().unwrap(), vec!["a1", "c1", "a2", "a3"]);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{
+    client::dispatch::EventSink,
+    config::{self, EmailSettings, SmtpTls},
+    core::events::Event,
+    error::{BotError, BotResult},
+    storage::MemoryCache,
+};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use std::{sync::atomic::Ordering, time::Duration};
+use tracing::{error, info};
+
+// Time allowed for each command of the SMTP session
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTML email of a digest event.
+#[derive(Debug)]
+pub struct Email {
+    pub subject: String,
+    pub html: String,
+}
+
+impl Email {
+    /// Email of the digest events (daily summary, weekly recap), None for the other events. The
+    /// subject is the title of the HTML document, so it is translated along with the template.
+    pub fn of(event: &Event) -> Option<Email> {
+        let html = event.as_html()?;
+        let subject = html
+            .split_once("<title>")
+            .and_then(|(_head, rest)| rest.split_once("</title>"))
+            .map(|(title, _rest)| title.trim().to_string())
+            .unwrap_or_default();
+        Some(Email { subject, html })
+    }
+}
+
+/// SMTP client sending the emails to the mailing list, over an encrypted connection unless a
+/// plain relay is configured, and authenticated if credentials are set.
+#[derive(Clone)]
+pub struct SmtpClient {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    server: String,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl SmtpClient {
+    pub fn new(settings: &EmailSettings) -> BotResult<Self> {
+        let host = settings.smtp_host.as_str();
+        let invalid = |e: &dyn std::fmt::Display| BotError::Email(format!("{host}: {e}"));
+        let builder = match settings.smtp_tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| invalid(&e))?,
+            SmtpTls::Tls => {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(host).map_err(|e| invalid(&e))?
+            }
+        };
+        let mut builder = builder.port(settings.port()).timeout(Some(SMTP_TIMEOUT));
+        if let (Some(username), Some(password)) = (&settings.smtp_username, &settings.smtp_password)
+        {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        };
+        let mailbox = |address: &String| address.parse::<Mailbox>().map_err(|e| invalid(&e));
+        Ok(SmtpClient {
+            transport: builder.build(),
+            server: format!("{host}:{}", settings.port()),
+            from: mailbox(&settings.from)?,
+            to: settings.to.iter().map(mailbox).collect::<BotResult<_>>()?,
+        })
+    }
+
+    /// Send the email to every address of the mailing list, in a single SMTP session.
+    pub async fn send(&self, email: &Email) -> BotResult<()> {
+        self.transport
+            .send(self.message(email)?)
+            .await
+            .map(|_response| ())
+            .map_err(|e| BotError::Email(format!("could not send through {} ({e})", self.server)))
+    }
+
+    // The body is encoded by the builder (quoted-printable or base64), so its lines are short
+    // enough for any server.
+    fn message(&self, email: &Email) -> BotResult<Message> {
+        let builder = Message::builder()
+            .from(self.from.clone())
+            .subject(email.subject.clone())
+            .header(ContentType::TEXT_HTML);
+        self.to
+            .iter()
+            .fold(builder, |builder, to| builder.to(to.clone()))
+            .body(email.html.clone())
+            .map_err(|e| BotError::Email(format!("could not build the email ({e})")))
+    }
+}
+
+/// Email the digest events to the mailing list (if any), then deliver every event to the sink of
+/// the messaging platform.
+pub struct DigestSink<S> {
+    inner: S,
+    smtp: Option<SmtpClient>,
+    cache: MemoryCache,
+}
+
+impl<S: EventSink + Send + Sync> DigestSink<S> {
+    pub fn new(inner: S, cache: MemoryCache) -> Self {
+        DigestSink {
+            inner,
+            smtp: config::SETTINGS.email.as_ref().and_then(|settings| {
+                SmtpClient::new(settings)
+                    .inspect_err(|e| error!("{e} The digests are not emailed."))
+                    .ok()
+            }),
+            cache,
+        }
+    }
+
+    async fn email(&self, event: &Event) {
+        let Some(smtp) = &self.smtp else {
+            return;
+        };
+        let Some(email) = Email::of(event) else {
+            return;
+        };
+        if self.cache.muted.load(Ordering::Relaxed) {
+            info!("Announcements are muted, skipping email.");
+        } else if config::SETTINGS.features.dry_run {
+            info!(
+                "Dry run, would email '{}' to {} address(es).",
+                email.subject,
+                smtp.to.len()
+            );
+        } else {
+            match smtp.send(&email).await {
+                Ok(()) => info!(
+                    "'{}' emailed to {} address(es).",
+                    email.subject,
+                    smtp.to.len()
+                ),
+                Err(error) => error!("{error}"),
+            };
+        }
+    }
+}
+
+impl<S: EventSink + Send + Sync> EventSink for DigestSink<S> {
+    async fn deliver(&mut self, event: Event) {
+        self.email(&event).await;
+        self.inner.deliver(event).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::recap::WeeklyRecap;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    // Relay accepting every recipient but "nobody", returning the received data.
+    async fn relay(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        stream.write_all(b"220 relay ESMTP\r\n").await.unwrap();
+        let (mut data, mut in_data) = (String::new(), false);
+        loop {
+            let mut line = String::new();
+            if stream.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            };
+            let reply = match line.as_str() {
+                ".\r\n" if in_data => {
+                    in_data = false;
+                    "250 queued\r\n"
+                }
+                _ if in_data => {
+                    data.push_str(&line);
+                    continue;
+                }
+                l if l.starts_with("EHLO") => "250-relay\r\n250 8BITMIME\r\n",
+                l if l.starts_with("RCPT TO:<nobody") => "550 no such user\r\n",
+                l if l.starts_with("DATA") => {
+                    in_data = true;
+                    "354 end data with <CR><LF>.<CR><LF>\r\n"
+                }
+                l if l.starts_with("QUIT") => "221 bye\r\n",
+                _ => "250 ok\r\n",
+            };
+            stream.write_all(reply.as_bytes()).await.unwrap();
+        }
+        data
+    }
+
+    async fn client(to: &[&str]) -> (SmtpClient, TcpListener) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = SmtpClient::new(&EmailSettings {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: Some(listener.local_addr().unwrap().port()),
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            from: "ceo@example.com".to_string(),
+            to: to.iter().map(|to| to.to_string()).collect(),
+        })
+        .unwrap();
+        (client, listener)
+    }
+
+    #[test]
+    fn digest_email_is_titled_and_escaped() {
+        let event = Event::WeeklyRecap(WeeklyRecap {
+            year: 2014,
+            stars: vec![("<Rudolph>".to_string(), 4)],
+            climbs: vec![],
+            jerseys: vec![],
+            fastest_delta: None,
+        });
+        let email = Email::of(&event).unwrap();
+        assert_eq!(email.subject, "Weekly recap of the 2014 event");
+        assert!(email.html.contains("<b>&lt;Rudolph&gt;</b>: 4 stars"));
+        assert!(Email::of(&Event::PrivateLeaderboardUpdated).is_none());
+    }
+
+    #[tokio::test]
+    async fn email_is_relayed_to_the_mailing_list() {
+        let (client, listener) = client(&["elves@example.com", "santa@example.com"]).await;
+        let relay = tokio::spawn(relay(listener));
+        let email = Email {
+            subject: "Daily update ⭐".to_string(),
+            html: format!("<p>Crème brûlée</p>\n.\n<p>{}</p>", "x".repeat(100)),
+        };
+        client.send(&email).await.unwrap();
+
+        let data = relay.await.unwrap();
+        assert!(data.contains("To: elves@example.com, santa@example.com\r\n"));
+        assert!(data.contains("Subject: Daily update =?utf-8?b?4q2Q?=\r\n"));
+        assert!(data.contains("Content-Type: text/html; charset=utf-8\r\n"));
+        // the line with a single dot is escaped, not to end the data
+        assert!(data.contains("<p>Cr=C3=A8me br=C3=BBl=C3=A9e</p>\r\n..\r\n"));
+        assert!(data.lines().all(|line| line.len() <= 78));
+    }
+
+    #[tokio::test]
+    async fn rejected_recipient_fails_the_email() {
+        let (client, listener) = client(&["elves@example.com", "nobody@example.com"]).await;
+        tokio::spawn(relay(listener));
+        let email = Email {
+            subject: "Daily update".to_string(),
+            html: "<p>Hello</p>".to_string(),
+        };
+        let error = client.send(&email).await.unwrap_err().to_string();
+        assert!(
+            error.contains("could not send through 127.0.0.1:") && error.contains("550"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    core::leaderboard::{GlobalParseReport, Identifier, Leaderboard, ScrapedLeaderboard},
+    error::BotResult,
+};
+use chrono::Utc;
+use std::future::Future;
+
+/// Source of the leaderboards and puzzles of an advent-style event (e.g. Advent of Code). The
+/// scheduler jobs fetch from the provider they are given, so other events can be plugged in by
+/// mapping their API to the leaderboard types.
+pub trait EventProvider: Sync {
+    /// Raw private leaderboard of the year, parsed separately (on the blocking pool).
+    fn fetch_private_leaderboard(
+        &self,
+        year: i32,
+    ) -> impl Future<Output = BotResult<String>> + Send;
+
+    /// Stars of a raw private leaderboard, with all the members (with or without stars).
+    fn parse_private_leaderboard(response: &str) -> BotResult<(Leaderboard, Vec<Identifier>)>;
+
+    /// Global leaderboard of the day, with the outcome of its parsing.
+    fn global_leaderboard(
+        &self,
+        year: i32,
+        day: u8,
+    ) -> impl Future<Output = BotResult<(ScrapedLeaderboard, GlobalParseReport)>> + Send;
+
+    /// Title of the puzzle of the day.
+    fn daily_challenge(&self, year: i32, day: u8)
+        -> impl Future<Output = BotResult<String>> + Send;
+
+    /// Whether the page of the event of the year is live, i.e. the event was announced.
+    fn event_is_live(&self, year: i32) -> impl Future<Output = BotResult<bool>> + Send;
+
+    fn daily_challenge_url(&self, year: i32, day: u8) -> String;
+
+    fn private_leaderboard_url(&self, year: i32) -> String;
+
+    fn global_leaderboard_url(&self, year: i32, day: u8) -> String;
+
+    /// Page where the members enter the join code of the private leaderboard.
+    fn join_private_leaderboard_url(&self, year: i32) -> String;
+
+    /// Private leaderboard of the year, fetched and parsed.
+    fn private_leaderboard(
+        &self,
+        year: i32,
+    ) -> impl Future<Output = BotResult<ScrapedLeaderboard>> + Send {
+        async move {
+            let response = self.fetch_private_leaderboard(year).await?;
+            let (leaderboard, _members) = Self::parse_private_leaderboard(&response)?;
+            Ok(ScrapedLeaderboard {
+                timestamp: Utc::now(),
+                leaderboard,
+            })
+        }
+    }
+}
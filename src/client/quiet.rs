@@ -0,0 +1,173 @@
+use crate::{client::dispatch::EventSink, core::events::Event, storage::Store};
+use chrono::{Local, NaiveTime, Timelike};
+use std::mem;
+use tracing::info;
+
+/// Window of the day (local time of the bot) during which the announcements are held back, e.g.
+/// `23:00-07:00`. The window wraps around midnight if it ends before it starts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn parse(window: &str) -> Result<QuietHours, String> {
+        let invalid = || format!("'{window}' is not a window of the day like '23:00-07:00'");
+        let (start, end) = window.split_once('-').ok_or_else(invalid)?;
+        let time = |t: &str| NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| invalid());
+        let (start, end) = (time(start)?, time(end)?);
+        if start == end {
+            return Err(format!("'{window}' starts and ends at the same time"));
+        };
+        Ok(QuietHours { start, end })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match self.start < self.end {
+            true => self.start <= time && time < self.end,
+            false => self.start <= time || time < self.end,
+        }
+    }
+
+    /// Cron schedule of the end of the window, at which the held back announcements are posted.
+    pub fn end_schedule(&self) -> String {
+        format!("0 {} {} * * *", self.end.minute(), self.end.hour())
+    }
+}
+
+/// Hold back the deferrable announcements during the quiet hours, posting them as a single
+/// digest once the window ends, and drop the time-sensitive ones, then deliver the events to the
+/// sink of the messaging platform.
+pub struct QuietHoursSink<S> {
+    inner: S,
+    hours: Option<QuietHours>,
+    // messages of the announcements held back, kept across restarts
+    held: Store<Vec<String>>,
+}
+
+impl<S: EventSink + Send + Sync> QuietHoursSink<S> {
+    pub fn new(inner: S, hours: Option<QuietHours>, held: Store<Vec<String>>) -> Self {
+        QuietHoursSink { inner, hours, held }
+    }
+
+    // Events to deliver now, at the given local time.
+    fn route(&mut self, event: Event, now: NaiveTime) -> Vec<Event> {
+        let quiet = self.hours.is_some_and(|hours| hours.contains(now));
+        match event {
+            // the announcements held back are posted, even if the end of the window was missed
+            Event::QuietHoursEnded => self.flush().into_iter().collect(),
+            event if quiet && event.is_time_sensitive() => {
+                info!("Quiet hours, dropping time-sensitive event.");
+                vec![]
+            }
+            event if quiet && event.is_deferrable() => {
+                info!("Quiet hours, holding back event.");
+                self.held
+                    .update(|held| held.push(event.messages().join("\n")));
+                vec![]
+            }
+            event if quiet => vec![event],
+            event => self.flush().into_iter().chain([event]).collect(),
+        }
+    }
+
+    fn flush(&mut self) -> Option<Event> {
+        match self.held.read(Vec::is_empty) {
+            true => None,
+            false => Some(Event::QuietHoursDigest(self.held.update(mem::take))),
+        }
+    }
+}
+
+impl<S: EventSink + Send + Sync> EventSink for QuietHoursSink<S> {
+    async fn deliver(&mut self, event: Event) {
+        for event in self.route(event, Local::now().time()) {
+            self.inner.deliver(event).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        chat::{ChatId, MessageId},
+        commands::Command,
+        events::Reply,
+    };
+
+    struct NullSink;
+
+    impl EventSink for NullSink {
+        async fn deliver(&mut self, _event: Event) {}
+    }
+
+    fn at(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, "%H:%M").unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_wrap_around_midnight() {
+        let hours = QuietHours::parse("23:00-07:00").unwrap();
+        assert!(hours.contains(at("23:30")));
+        assert!(hours.contains(at("03:00")));
+        assert!(!hours.contains(at("07:00")));
+        assert!(!hours.contains(at("12:00")));
+        assert_eq!(hours.end_schedule(), "0 0 7 * * *");
+
+        let hours = QuietHours::parse("12:30 - 14:00").unwrap();
+        assert!(hours.contains(at("13:00")));
+        assert!(!hours.contains(at("23:30")));
+
+        assert!(QuietHours::parse("23:00").is_err());
+        assert!(QuietHours::parse("25:00-07:00").is_err());
+        assert!(QuietHours::parse("07:00-07:00").is_err());
+    }
+
+    #[test]
+    fn announcements_are_held_back_until_the_window_ends_or_dropped_if_stale() {
+        let hours = QuietHours::parse("23:00-07:00").ok();
+        let mut sink = QuietHoursSink::new(NullSink, hours, Store::default());
+        let command = Event::CommandReceived(
+            ChatId("C0".to_string()),
+            MessageId("1".to_string()),
+            Reply::Thread,
+            Command::Help,
+        );
+        let labels = |events: Vec<Event>| {
+            events
+                .iter()
+                .map(<&'static str>::from)
+                .collect::<Vec<&str>>()
+        };
+
+        let night = at("02:00");
+        assert!(sink
+            .route(Event::GlobalLeaderboardUpdateMessage(1, 0), night)
+            .is_empty());
+        assert!(sink.route(Event::Countdown(2, 10), night).is_empty());
+        assert!(sink
+            .route(
+                Event::DailyChallengeIsUp(2, "Title".into(), "url".into()),
+                night
+            )
+            .is_empty());
+        assert_eq!(labels(sink.route(command, night)), vec!["CommandReceived"]);
+        assert_eq!(
+            labels(sink.route(Event::DailySolutionsThreadToInitialize(2), night)),
+            vec!["DailySolutionsThreadToInitialize"]
+        );
+
+        match &sink.route(Event::QuietHoursEnded, at("07:00"))[..] {
+            // the countdown and the global leaderboard progress are stale by then, and dropped
+            [Event::QuietHoursDigest(messages)] => assert_eq!(messages.len(), 1),
+            events => panic!("{} events delivered instead of a digest", events.len()),
+        };
+        assert!(sink.route(Event::QuietHoursEnded, at("07:00")).is_empty());
+        assert_eq!(
+            labels(sink.route(Event::Countdown(3, 10), at("12:00"))),
+            vec!["Countdown"]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::{future::Future, time::Duration};
+
+/// How a failed attempt to post a message is handled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Failure {
+    // retried, after the given delay if any (e.g. `Retry-After` of a rate limited call)
+    Transient(Option<Duration>),
+    // not retried, the same call would fail again (e.g. unknown channel), or the message might
+    // have been posted anyway (e.g. timeout), posting a message not being idempotent
+    Permanent,
+}
+
+/// Retries of the calls failing with a transient error (rate limits, unavailable service), with
+/// an exponential backoff, so a short outage of the messaging platform does not drop an
+/// announcement.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before the attempt following the given (failed) one, starting at 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+
+    /// Delay before the next attempt, the one asked by the platform (if any) being capped like
+    /// the backoff.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after.map_or_else(
+            || self.backoff(attempt),
+            |delay| delay.min(self.max_backoff),
+        )
+    }
+
+    /// Run the call until it succeeds, fails with a permanent error or the attempts are
+    /// exhausted. Returns the outcome of the last attempt, along with the number of attempts.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        mut call: F,
+        classify: impl Fn(&E) -> Failure,
+    ) -> (Result<T, E>, u32)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match call().await {
+                Ok(response) => return (Ok(response), attempt),
+                Err(error) => error,
+            };
+            let delay = match classify(&error) {
+                Failure::Transient(_) if attempt >= self.max_attempts => None,
+                Failure::Transient(retry_after) => Some(self.delay(attempt, retry_after)),
+                Failure::Permanent => None,
+            };
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return (Err(error), attempt),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_attempts: 4,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(3),
+    };
+
+    fn classify(error: &&str) -> Failure {
+        match *error {
+            "rate_limited" => Failure::Transient(Some(Duration::from_millis(2))),
+            "server_error" => Failure::Transient(None),
+            _ => Failure::Permanent,
+        }
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let backoffs = (1..=4).map(|attempt| POLICY.backoff(attempt).as_millis());
+        assert_eq!(backoffs.collect::<Vec<_>>(), vec![1, 2, 3, 3]);
+        assert_eq!(
+            POLICY.delay(1, Some(Duration::from_secs(60))),
+            POLICY.max_backoff
+        );
+        assert_eq!(
+            POLICY.delay(1, Some(Duration::from_micros(10))),
+            Duration::from_micros(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_until_exhausted() {
+        let calls = Cell::new(0);
+        let outcomes = ["server_error", "rate_limited"];
+        let (result, attempts) = POLICY
+            .run(
+                || {
+                    calls.set(calls.get() + 1);
+                    let outcome = outcomes.get(calls.get() - 1).copied();
+                    async move { outcome.map_or(Ok("posted"), Err) }
+                },
+                classify,
+            )
+            .await;
+        assert_eq!((result, attempts), (Ok("posted"), 3));
+
+        let (result, attempts) = POLICY
+            .run(|| async { Err::<(), _>("server_error") }, classify)
+            .await;
+        assert_eq!((result, attempts), (Err("server_error"), 4));
+    }
+
+    #[tokio::test]
+    async fn permanent_failures_are_not_retried() {
+        let (result, attempts) = POLICY
+            .run(|| async { Err::<(), _>("channel_not_found") }, classify)
+            .await;
+        assert_eq!((result, attempts), (Err("channel_not_found"), 1));
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{
+    client::{
+        aoc::AoC, provider::EventProvider, slack::AoCSlackClient, telegram::AoCTelegramClient,
+    },
+    config::{self, MessagingBackend},
+    storage::MemoryCache,
+    utils::current_aoc_year_day,
+};
+
+/// Problems found by checking the settings against the services the bot relies on (rejected
+/// session cookie or tokens, unknown channels), reported like the settings validation. The
+/// settings themselves (e.g. the cron strings) are validated when they are loaded. The session
+/// cookie is only checked if asked, the private leaderboard being scraped right after startup.
+pub async fn run(cache: &MemoryCache, with_aoc: bool) -> Vec<String> {
+    let mut problems = vec![];
+    if with_aoc {
+        let (year, _day) = current_aoc_year_day();
+        problems.extend(check_aoc(&AoC::new(), year).await);
+    };
+    let messaging = match config::SETTINGS.messaging_backend {
+        MessagingBackend::Slack => match AoCSlackClient::new() {
+            Ok(client) => client.check_settings(cache).await,
+            Err(e) => vec![format!("slack: the client could not be initialized ({e})")],
+        },
+        MessagingBackend::Telegram => match AoCTelegramClient::new() {
+            Ok(client) => client.check_settings().await,
+            Err(e) => vec![format!(
+                "telegram: the client could not be initialized ({e})"
+            )],
+        },
+    };
+    problems.extend(messaging);
+    problems
+}
+
+/// Whether the private leaderboard of the year can be fetched with the session cookie.
+pub async fn check_aoc(aoc: &impl EventProvider, year: i32) -> Vec<String> {
+    match aoc.private_leaderboard(year).await {
+        Ok(_) => vec![],
+        Err(e) => vec![format!(
+            "aoc.session_cookie: could not fetch the {year} private leaderboard ({e})"
+        )],
+    }
+}
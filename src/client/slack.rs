@@ -1,25 +1,95 @@
 use crate::{
-    config,
-    core::{commands::Command, events::Event},
-    error::BotError,
-    storage::MemoryCache,
+    client::{
+        cooldown::{CommandCooldown, Throttled},
+        debounce::DebounceSink,
+        dispatch::{dispatch, EventSink, Lane, OutboundQueue},
+        email::DigestSink,
+        quiet::QuietHoursSink,
+        retry::{Failure, RetryPolicy},
+    },
+    config::{self, OutputFormat, ResponseMode},
+    core::{
+        chat::{ChatId, MessageId, UserId},
+        commands::{Command, BOARD_DETAILED_OPTION},
+        difficulty::{self, PollVotes, POLL_RATINGS},
+        events::{Event, Reply, ThreadActivity},
+        handler::{self, Requester},
+        home::app_home,
+        images::TextImage,
+        languages::{self, POLL_LANGUAGES},
+        names,
+        standings::Scoring,
+        votes::{self, SolutionReply},
+    },
+    error::{BotError, BotResult},
+    storage::{audit::PostedMessage, DifficultyPoll, MemoryCache, SolutionThread},
+    utils::{current_aoc_year_day, languages_mentioned},
 };
+use chrono::Utc;
 use http::StatusCode;
+use itertools::Itertools;
 use slack_morphism::{
-    api::SlackApiChatPostMessageRequest,
-    events::{SlackEventCallbackBody, SlackPushEventCallback},
-    hyper_tokio::{SlackClientHyperConnector, SlackHyperClient},
+    api::{
+        SlackApiAppsConnectionOpenRequest, SlackApiChatGetPermalinkRequest,
+        SlackApiChatPostEphemeralRequest, SlackApiChatPostMessageRequest,
+        SlackApiChatPostMessageResponse, SlackApiChatUpdateRequest,
+        SlackApiConversationsInfoRequest, SlackApiConversationsOpenRequest,
+        SlackApiConversationsRepliesRequest, SlackApiFilesComplete,
+        SlackApiFilesCompleteUploadExternalRequest, SlackApiFilesGetUploadUrlExternalRequest,
+        SlackApiFilesUploadViaUrlRequest, SlackApiPinsAddRequest, SlackApiReactionsAddRequest,
+        SlackApiReactionsGetRequest, SlackApiReactionsGetResponse, SlackApiViewsPublishRequest,
+    },
+    blocks::{
+        SlackActionBlockElement, SlackActionsBlock, SlackBlock, SlackBlockButtonElement,
+        SlackBlockChoiceItem, SlackBlockPlainTextOnly, SlackBlockStaticSelectElement,
+        SlackDividerBlock, SlackHomeView, SlackSectionBlock, SlackView,
+    },
+    errors::SlackClientError,
+    events::{
+        SlackEventCallbackBody, SlackInteractionBlockActionsEvent, SlackInteractionEvent,
+        SlackPushEventCallback,
+    },
+    hyper_tokio::{SlackClientHyperConnector, SlackClientHyperHttpsConnector, SlackHyperClient},
     listener::{SlackClientEventsListenerEnvironment, SlackClientEventsUserState},
-    SlackApiToken, SlackApiTokenValue, SlackChannelId, SlackClient, SlackClientSocketModeConfig,
-    SlackClientSocketModeListener, SlackMessageContent, SlackSocketModeListenerCallbacks,
+    md, SlackActionId, SlackApiToken, SlackApiTokenValue, SlackChannelId, SlackClient,
+    SlackClientSession, SlackClientSocketModeConfig, SlackClientSocketModeListener,
+    SlackMessageContent, SlackReactionName, SlackReactionsItem, SlackSocketModeListenerCallbacks,
+    SlackTs, SlackUserId,
+};
+use std::{
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
 };
-use std::sync::Arc;
 use tokio::sync::mpsc::{Receiver, Sender};
-use tracing::error;
+use tracing::{error, info};
+
+// Actions ids of the board interactive components
+const BOARD_PREVIOUS_PAGE_ACTION: &str = "board_previous_page";
+const BOARD_NEXT_PAGE_ACTION: &str = "board_next_page";
+const BOARD_YEAR_ACTION: &str = "board_year";
+// Delay between the messages of a batched announcement (Slack allows ~1 message/sec per channel)
+const BATCH_THROTTLE: Duration = Duration::from_secs(1);
+// Retries of the messages failing to be posted, before they are dead-lettered (~1 min overall)
+const POST_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    initial_backoff: Duration::from_secs(4),
+    max_backoff: Duration::from_secs(30),
+};
+// Errors returned by the Slack API when it is (temporarily) unable to handle the call, the
+// message not being posted (unlike `internal_error` or `fatal_error`, after which it might be).
+const TRANSIENT_API_ERRORS: [&str; 3] = ["ratelimited", "service_unavailable", "request_timeout"];
+// Errors returned when editing a live scoreboard that can no longer be edited (e.g. deleted)
+const SCOREBOARD_GONE_ERRORS: [&str; 3] = [
+    "message_not_found",
+    "cant_update_message",
+    "edit_window_closed",
+];
 
 struct MyEnvironment {
     sender: Arc<Sender<Event>>,
     cache: MemoryCache,
+    cooldown: CommandCooldown,
 }
 
 pub struct AoCSlackClient {
@@ -32,88 +102,122 @@ impl AoCSlackClient {
         Ok(Self { client })
     }
 
+    /// Problems with the Slack settings found by calling the API: rejected tokens, and channels
+    /// the bot can not see or is not a member of.
+    pub async fn check_settings(&self, cache: &MemoryCache) -> Vec<String> {
+        let settings = &config::SETTINGS;
+        let mut problems = vec![];
+        let app_token = SlackApiToken::new(settings.slack.app_token.to_string().into());
+        if let Err(e) = self
+            .client
+            .open_session(&app_token)
+            .apps_connections_open(&SlackApiAppsConnectionOpenRequest::new())
+            .await
+        {
+            problems.push(format!("slack.app_token: rejected by Slack ({e})"));
+        };
+        // the bot might only be installed through OAuth once started
+        let Some(token) = bot_token(cache) else {
+            return problems;
+        };
+        let session = self.client.open_session(&token);
+        if let Err(e) = session.auth_test().await {
+            problems.push(format!("slack.token: rejected by Slack ({e})"));
+            return problems;
+        };
+
+        let channels = [
+            (
+                "slack.default_channel",
+                Some(&settings.slack.default_channel),
+            ),
+            (
+                "slack.monitoring_channel",
+                settings.slack.monitoring_channel.as_ref(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, channel)| Some((name, channel?)))
+        .chain(
+            settings
+                .slack
+                .scoreboard_channels
+                .iter()
+                .flatten()
+                .map(|channel| ("slack.scoreboard_channels", channel)),
+        );
+        for (name, channel) in channels {
+            let request = SlackApiConversationsInfoRequest::new(SlackChannelId(channel.clone()));
+            match session.conversations_info(&request).await {
+                Ok(info) if info.channel.flags.is_member == Some(false) => problems.push(format!(
+                    "{name}: '{channel}' the bot must be invited in the channel"
+                )),
+                Ok(_) => (),
+                Err(e) => problems.push(format!("{name}: '{channel}' could not be found ({e})")),
+            };
+        }
+        problems
+    }
+
     pub async fn handle_messages_and_events(
         &self,
         cache: MemoryCache,
         tx: Sender<Event>,
         rx: Receiver<Event>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        self.listen_for_events(rx).await;
+        self.listen_for_events(cache.clone(), rx).await;
         self.start_slack_client_with_socket_mode(cache.clone(), tx)
             .await?;
         Ok(())
     }
 
-    // Spaw listener for events and post corresponding annoucements/messages
-    async fn listen_for_events(&self, mut rx: Receiver<Event>) {
-        let client = self.client.clone();
+    // Spawn listener for events and post corresponding annoucements/messages
+    async fn listen_for_events(&self, cache: MemoryCache, rx: Receiver<Event>) {
+        let settings = &config::SETTINGS;
+        if settings.features.dry_run {
+            info!("Dry run: messages are logged instead of being posted.");
+            let mut sink = DigestSink::new(
+                DebounceSink::new(
+                    QuietHoursSink::new(
+                        DryRunSink {
+                            client: self.client.clone(),
+                            cache: cache.clone(),
+                        },
+                        settings.quiet_hours(),
+                        cache.quiet_hours_held.clone(),
+                    ),
+                    settings.highlights_debounce(),
+                ),
+                cache,
+            );
+            tokio::spawn(async move {
+                dispatch(rx, &mut sink).await;
+            });
+            return;
+        };
 
-        tokio::spawn(async move {
-            let settings = &config::SETTINGS;
-            while let Some(event) = rx.recv().await {
-                let channel_id = SlackChannelId(settings.slack_default_channel.to_string());
-                let app_token_value: SlackApiTokenValue = settings.slack_token.to_string().into();
-                let app_token: SlackApiToken = SlackApiToken::new(app_token_value);
-                let session = client.open_session(&app_token);
-
-                let response_text = event.to_string();
-
-                let response = match &event {
-                    Event::PrivateLeaderboardUpdated => settings
-                        .slack_monitoring_channel
-                        .as_ref()
-                        .map(|channel_id| {
-                            SlackApiChatPostMessageRequest::new(
-                                SlackChannelId(channel_id.to_string()),
-                                SlackMessageContent::new().with_text(response_text),
-                            )
-                        }),
-                    Event::CommandReceived(channel_id, thread_ts, _cmd) => {
-                        // let data = cache.data.lock().unwrap();
-                        // // TODO: inject timestamp too
-                        // let ranking = data.leaderboard.standings_by_local_score();
-
-                        Some(
-                            SlackApiChatPostMessageRequest::new(
-                                channel_id.clone(),
-                                SlackMessageContent::new().with_text(response_text),
-                            )
-                            .with_thread_ts(thread_ts.clone()),
-                        )
-                    }
-                    _ => Some(SlackApiChatPostMessageRequest::new(
-                        channel_id.clone(),
-                        SlackMessageContent::new().with_text(response_text),
-                    )),
-                };
+        // The daily summary is rated before being emailed, so the email has the rating too.
+        let mut sink = CommunityRatingSink {
+            inner: DigestSink::new(
+                DebounceSink::new(
+                    QuietHoursSink::new(
+                        SlackEventSink {
+                            client: self.client.clone(),
+                            cache: cache.clone(),
+                            outbound: OutboundQueue::spawn(),
+                        },
+                        settings.quiet_hours(),
+                        cache.quiet_hours_held.clone(),
+                    ),
+                    settings.highlights_debounce(),
+                ),
+                cache.clone(),
+            ),
+            cache,
+        };
 
-                if let Some(response) = response {
-                    match session.chat_post_message(&response).await {
-                        Err(e) => {
-                            let error = BotError::Slack(e.to_string());
-                            error!("{error}");
-                        }
-                        Ok(res) => {
-                            // If Solution thread initialization, post a first message in thread
-                            if let Event::DailySolutionsThreadToInitialize(_day) = event {
-                                let thread_ts = res.ts;
-                                let message = ":warning: Last warning, spoiler ahead!".to_string();
-                                let first_thread_message = SlackApiChatPostMessageRequest::new(
-                                    channel_id,
-                                    SlackMessageContent::new().with_text(message),
-                                )
-                                .with_thread_ts(thread_ts);
-                                if let Err(e) =
-                                    session.chat_post_message(&first_thread_message).await
-                                {
-                                    let error = BotError::Slack(e.to_string());
-                                    error!("{error}");
-                                };
-                            }
-                        }
-                    }
-                }
-            }
+        tokio::spawn(async move {
+            dispatch(rx, &mut sink).await;
         });
     }
 
@@ -124,7 +228,8 @@ impl AoCSlackClient {
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let settings = &config::SETTINGS;
         let socket_mode_callbacks = SlackSocketModeListenerCallbacks::new()
-            .with_push_events(push_events_socket_mode_function);
+            .with_push_events(push_events_socket_mode_function)
+            .with_interaction_events(interaction_events_socket_mode_function);
 
         let listener_environment = Arc::new(
             SlackClientEventsListenerEnvironment::new(self.client.clone())
@@ -132,6 +237,10 @@ impl AoCSlackClient {
                 .with_user_state(MyEnvironment {
                     sender: Arc::new(tx),
                     cache,
+                    cooldown: CommandCooldown::new(
+                        settings.commands.max_per_user_per_minute,
+                        settings.commands.max_per_channel_per_minute,
+                    ),
                 }),
         );
 
@@ -141,7 +250,7 @@ impl AoCSlackClient {
             socket_mode_callbacks,
         );
 
-        let app_token_value: SlackApiTokenValue = settings.slack_app_token.to_string().into();
+        let app_token_value: SlackApiTokenValue = settings.slack.app_token.to_string().into();
         let app_token: SlackApiToken = SlackApiToken::new(app_token_value);
 
         socket_mode_listener.listen_for(&app_token).await?;
@@ -152,66 +261,1210 @@ impl AoCSlackClient {
     }
 }
 
+/// Rate the difficulty of the day in the daily summary from the votes of the poll of the day (if
+/// any), then deliver every event to the inner sink.
+struct CommunityRatingSink<S> {
+    inner: S,
+    cache: MemoryCache,
+}
+
+impl<S: EventSink + Send + Sync> EventSink for CommunityRatingSink<S> {
+    async fn deliver(&mut self, mut event: Event) {
+        if let Event::DailySummary(year, day, .., difficulty) = &mut event {
+            *difficulty = self.cache.difficulty_polls.read(|polls| {
+                polls
+                    .iter()
+                    .find(|p| p.year == *year && p.day == *day)
+                    .and_then(|p| difficulty::community_rating(&p.votes))
+            });
+        };
+        self.inner.deliver(event).await
+    }
+}
+
+/// Post the dispatched events to Slack.
+struct SlackEventSink {
+    client: Arc<SlackHyperClient>,
+    cache: MemoryCache,
+    outbound: OutboundQueue,
+}
+
+impl EventSink for SlackEventSink {
+    async fn deliver(&mut self, event: Event) {
+        let (client, cache) = (self.client.clone(), self.cache.clone());
+        let lane = Lane::of(&event);
+        self.outbound.push(
+            lane,
+            async move { post_event(&client, &cache, event).await },
+        );
+    }
+}
+
+/// Post the event to Slack, from the outbound queue.
+async fn post_event(client: &Arc<SlackHyperClient>, cache: &MemoryCache, event: Event) {
+    let settings = &config::SETTINGS;
+    let kind: &'static str = (&event).into();
+    cache.event_stream.publish(&event);
+    // Commands, monitoring and personal messages are still sent when announcements are muted.
+    if event.is_announcement() && cache.muted.load(Ordering::Relaxed) {
+        info!("Announcements are muted, skipping event.");
+        return;
+    };
+
+    let channel_id = SlackChannelId(settings.slack.default_channel.to_string());
+    let Some(app_token) = bot_token(cache) else {
+        error!("No Slack bot token, the bot must be installed in the workspace first.");
+        return;
+    };
+    let session = client.open_session(&app_token);
+
+    if let Event::LiveScoreboard(board) = &event {
+        refresh_live_scoreboards(&session, cache, kind, board).await;
+        return;
+    };
+
+    // Ephemeral responses can be neither continued in a thread nor updated (board navigation).
+    if let Event::CommandReceived(channel, _ts, Reply::Ephemeral(user), _cmd) = &event {
+        for message in event.messages() {
+            let request = SlackApiChatPostEphemeralRequest::new(
+                channel.into(),
+                user.into(),
+                SlackMessageContent::new().with_text(message),
+            );
+            if let Err(error) = post_ephemeral(&session, cache, kind, &request).await {
+                error!("{error}");
+            };
+        }
+        return;
+    };
+
+    // Text tables are uploaded as images if configured, the text being the fallback.
+    let image = match settings.features.output_format {
+        OutputFormat::Image => event.as_image(),
+        OutputFormat::Text => None,
+    };
+    if let Some(image) = image {
+        let (channel, thread_ts) = match &event {
+            Event::CommandReceived(channel, ts, reply, _cmd) => {
+                (channel.into(), reply_thread(ts, reply))
+            }
+            _ => (channel_id.clone(), None),
+        };
+        match upload_image(&session, channel.clone(), thread_ts, &image).await {
+            Ok(()) => {
+                let message = PostedMessage {
+                    at: Utc::now(),
+                    event: kind.to_string(),
+                    channel: channel.into(),
+                    ts: None,
+                    text: format!("*{}* (image)", image.title),
+                    error: None,
+                };
+                cache.audit_log.record(message);
+                return;
+            }
+            Err(error) => error!("{error} Falling back to text."),
+        }
+    };
+
+    // Long announcements are posted as several messages, throttled to respect Slack rate limits.
+    if let Event::PrivateLeaderboardNewEntries(_) = &event {
+        for (i, message) in event.messages().into_iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(BATCH_THROTTLE).await;
+            };
+            let request = SlackApiChatPostMessageRequest::new(
+                channel_id.clone(),
+                SlackMessageContent::new().with_text(message),
+            );
+            let context = "Could not post message.";
+            if let Err(error) = post_message(&session, cache, kind, &request, context).await {
+                error!("{error}");
+            };
+        }
+        return;
+    };
+
+    // Messages exceeding the Slack limit are continued in the thread of the first one.
+    let mut messages = event.messages().into_iter();
+    let response_text = messages.next().unwrap_or_default();
+    let continuations = messages.collect::<Vec<String>>();
+
+    let response = match &event {
+        Event::PersonalReminder(user, ..)
+        | Event::YearInReview(user, _)
+        | Event::PersonalSummary(user, _)
+        | Event::WatchAlerts(user, ..) => {
+            // Direct messages are posted in the conversation opened with the user
+            let request = SlackApiConversationsOpenRequest::new().with_users(vec![user.clone()]);
+            match session.conversations_open(&request).await {
+                Ok(conversation) => Some(SlackApiChatPostMessageRequest::new(
+                    conversation.channel.id,
+                    SlackMessageContent::new().with_text(response_text),
+                )),
+                Err(e) => {
+                    let error = BotError::slack("Could not open direct conversation.")(e);
+                    error!("{error} User: {user}.");
+                    None
+                }
+            }
+        }
+        Event::DailySolutionsThreadToSummarize(year, day) => {
+            let thread = cache.solution_threads.read(|threads| {
+                threads
+                    .iter()
+                    .find(|t| t.year == *year && t.day == *day)
+                    .cloned()
+            });
+            match thread {
+                Some(thread) => match thread_activity(&session, &thread).await {
+                    Ok(activity) => Some(SlackApiChatPostMessageRequest::new(
+                        channel_id.clone(),
+                        SlackMessageContent::new().with_text(
+                            Event::DailySolutionsThreadActivity(*day, activity).to_string(),
+                        ),
+                    )),
+                    Err(error) => {
+                        error!("{error}");
+                        None
+                    }
+                },
+                None => {
+                    info!("No solutions thread found for day {day} ({year}).");
+                    None
+                }
+            }
+        }
+        Event::PrivateLeaderboardUpdated
+        | Event::PrivateLeaderboardMembership(_)
+        | Event::SeasonChanged(..)
+        | Event::MonitoringAlert(..)
+        | Event::Heartbeat(_)
+        | Event::TemplatePreview(_) => {
+            settings
+                .slack
+                .monitoring_channel
+                .as_ref()
+                .map(|channel_id| {
+                    SlackApiChatPostMessageRequest::new(
+                        SlackChannelId(channel_id.to_string()),
+                        SlackMessageContent::new().with_text(response_text),
+                    )
+                })
+        }
+        Event::CommandReceived(
+            channel_id,
+            ts,
+            reply,
+            Command::LeaderboardDisplay(year, _board, _time, scoring, detailed, page, n_pages),
+        ) => {
+            let years = {
+                let data = cache.data.read().unwrap();
+                data.leaderboard.iter().map(|e| e.year).unique().collect()
+            };
+            let board = (*year, scoring, *detailed);
+            let blocks = board_blocks(&response_text, board, *page, *n_pages, years);
+            let mut request = SlackApiChatPostMessageRequest::new(
+                channel_id.into(),
+                SlackMessageContent::new()
+                    .with_text(response_text)
+                    .with_blocks(blocks),
+            );
+            request.thread_ts = reply_thread(ts, reply);
+            Some(request)
+        }
+        Event::CommandReceived(channel_id, ts, reply, _cmd) => {
+            // let data = cache.data.read().unwrap();
+            // // TODO: inject timestamp too
+            // let ranking = data.leaderboard.standings_by_local_score();
+
+            let mut request = SlackApiChatPostMessageRequest::new(
+                channel_id.into(),
+                SlackMessageContent::new().with_text(response_text),
+            );
+            request.thread_ts = reply_thread(ts, reply);
+            Some(request)
+        }
+        _ => Some(SlackApiChatPostMessageRequest::new(
+            channel_id.clone(),
+            SlackMessageContent::new().with_text(response_text),
+        )),
+    };
+
+    if let Some(response) = response {
+        let context = "Could not post message.";
+        match post_message(&session, cache, kind, &response, context).await {
+            Err(error) => {
+                error!("{error}");
+            }
+            Ok(res) => {
+                let thread_ts = response.thread_ts.clone().unwrap_or(res.ts.clone());
+                for message in continuations {
+                    tokio::time::sleep(BATCH_THROTTLE).await;
+                    let request = SlackApiChatPostMessageRequest::new(
+                        res.channel.clone(),
+                        SlackMessageContent::new().with_text(message),
+                    )
+                    .with_thread_ts(thread_ts.clone());
+                    let context = "Could not post message continuation.";
+                    if let Err(error) = post_message(&session, cache, kind, &request, context).await
+                    {
+                        error!("{error}");
+                    };
+                }
+
+                // Difficulty poll, voted on with the reactions added by the bot
+                if let Event::DifficultyPoll(year, day) = event {
+                    match add_poll_reactions(&session, &res, &POLL_RATINGS.map(|(r, _)| r)).await {
+                        Ok(()) => cache.difficulty_polls.update(|polls| {
+                            polls.retain(|p| !(p.year == year && p.day == day));
+                            polls.push(DifficultyPoll {
+                                year,
+                                day,
+                                channel: res.channel.clone(),
+                                ts: res.ts.clone(),
+                                votes: PollVotes::new(),
+                            });
+                        }),
+                        Err(error) => error!("{error}"),
+                    };
+                }
+
+                // If Solution thread initialization, post a first message in thread
+                if let Event::DailySolutionsThreadToInitialize(day) = event {
+                    let thread_ts = res.ts;
+                    // Keep track of the thread to summarize its activity later on
+                    let thread = SolutionThread {
+                        year: current_aoc_year_day().0,
+                        day,
+                        channel: channel_id.clone(),
+                        ts: thread_ts.clone(),
+                        poll_ts: None,
+                    };
+                    cache.solution_threads.update(|threads| {
+                        threads.retain(|t| !(t.year == thread.year && t.day == day));
+                        threads.push(thread);
+                    });
+                    let message = ":warning: Last warning, spoiler ahead!".to_string();
+                    let first_thread_message = SlackApiChatPostMessageRequest::new(
+                        channel_id.clone(),
+                        SlackMessageContent::new().with_text(message),
+                    )
+                    .with_thread_ts(thread_ts.clone());
+                    let context = "Could not post first thread message.";
+                    if let Err(error) =
+                        post_message(&session, cache, kind, &first_thread_message, context).await
+                    {
+                        error!("{error}");
+                    };
+
+                    // Languages poll, voted on with the reactions added by the bot
+                    match post_language_poll(&session, cache, kind, &channel_id, &thread_ts).await {
+                        Ok(poll_ts) => cache.solution_threads.update(|threads| {
+                            threads
+                                .iter_mut()
+                                .filter(|t| t.channel == channel_id && t.ts == thread_ts)
+                                .for_each(|t| t.poll_ts = Some(poll_ts.clone()))
+                        }),
+                        Err(error) => error!("{error}"),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Log the dispatched events instead of posting them, a copy being posted in the monitoring
+/// channel (if any) to review the messages in Slack.
+struct DryRunSink {
+    client: Arc<SlackHyperClient>,
+    cache: MemoryCache,
+}
+
+impl EventSink for DryRunSink {
+    async fn deliver(&mut self, event: Event) {
+        let settings = &config::SETTINGS;
+        self.cache.event_stream.publish(&event);
+        let destination = match &event {
+            Event::CommandReceived(channel, _ts, reply, _cmd) => match reply {
+                Reply::Channel => format!("<#{channel}>"),
+                Reply::Thread => format!("<#{channel}> (thread)"),
+                Reply::Ephemeral(user) => format!("<#{channel}> (only visible to <@{user}>)"),
+            },
+            Event::PersonalReminder(user, ..)
+            | Event::YearInReview(user, _)
+            | Event::PersonalSummary(user, _)
+            | Event::WatchAlerts(user, ..) => {
+                format!("<@{user}> (direct message)")
+            }
+            Event::PrivateLeaderboardUpdated
+            | Event::PrivateLeaderboardMembership(_)
+            | Event::SeasonChanged(..)
+            | Event::MonitoringAlert(..)
+            | Event::Heartbeat(_)
+            | Event::TemplatePreview(_) => "the monitoring channel".to_string(),
+            Event::LiveScoreboard(_) => "the pinned message of the scoreboard channels".to_string(),
+            _ => format!("<#{}>", settings.slack.default_channel),
+        };
+        let text = event.to_string();
+        info!("Dry run, would post in {destination}:\n{text}");
+
+        let (Some(monitoring_channel), Some(token)) = (
+            settings.slack.monitoring_channel.as_ref(),
+            bot_token(&self.cache),
+        ) else {
+            return;
+        };
+        let session = self.client.open_session(&token);
+        for text in event.messages() {
+            let request = SlackApiChatPostMessageRequest::new(
+                SlackChannelId(monitoring_channel.to_string()),
+                SlackMessageContent::new().with_text(format!(
+                    "🧪 *Dry run*, would post in {destination}:\n{text}"
+                )),
+            );
+            if let Err(e) = session.chat_post_message(&request).await {
+                let error = BotError::slack("Could not post message.")(e);
+                error!("{error}");
+            };
+        }
+    }
+}
+
+/// Whether a failed call is worth retrying: rate limits and server errors, the message not being
+/// posted. Network errors and timeouts are not retried, as the message might have been posted.
+fn slack_failure(error: &SlackClientError) -> Failure {
+    match error {
+        SlackClientError::RateLimitError(e) => Failure::Transient(e.retry_after),
+        SlackClientError::HttpError(e) if e.status_code.is_server_error() => {
+            Failure::Transient(None)
+        }
+        SlackClientError::ApiError(e) if TRANSIENT_API_ERRORS.contains(&e.code.as_str()) => {
+            Failure::Transient(None)
+        }
+        _ => Failure::Permanent,
+    }
+}
+
+/// Log the message that could not be posted, with its text so it can be posted by hand.
+fn dead_letter(event: &'static str, channel: &SlackChannelId, attempts: u32, text: &str) {
+    error!(
+        "Dead letter: {event} message to {channel} dropped after {attempts} attempt(s):\n{text}"
+    );
+}
+
+/// Post the message, retrying on transient errors, and recording it in the audit log whether
+/// it could be posted or not.
+async fn post_message(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    cache: &MemoryCache,
+    event: &'static str,
+    request: &SlackApiChatPostMessageRequest,
+    context: &'static str,
+) -> BotResult<SlackApiChatPostMessageResponse> {
+    let (result, attempts) = POST_RETRY
+        .run(|| session.chat_post_message(request), slack_failure)
+        .await;
+    let result = result.map_err(BotError::slack(context));
+    if result.is_err() {
+        let text = request.content.text.as_deref().unwrap_or_default();
+        dead_letter(event, &request.channel, attempts, text);
+    };
+    let message = PostedMessage {
+        at: Utc::now(),
+        event: event.to_string(),
+        channel: request.channel.clone().into(),
+        ts: result
+            .as_ref()
+            .ok()
+            .map(|response| response.ts.clone().into()),
+        text: request.content.text.clone().unwrap_or_default(),
+        error: result.as_ref().err().map(|error| error.to_string()),
+    };
+    cache.audit_log.record(message);
+    result
+}
+
+/// Post the ephemeral message, retrying on transient errors, and recording it in the audit log
+/// whether it could be posted or not.
+async fn post_ephemeral(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    cache: &MemoryCache,
+    event: &'static str,
+    request: &SlackApiChatPostEphemeralRequest,
+) -> BotResult<()> {
+    let (result, attempts) = POST_RETRY
+        .run(|| session.chat_post_ephemeral(request), slack_failure)
+        .await;
+    let result = result
+        .map(|_response| ())
+        .map_err(BotError::slack("Could not post ephemeral message."));
+    if result.is_err() {
+        let text = request.content.text.as_deref().unwrap_or_default();
+        dead_letter(event, &request.channel, attempts, text);
+    };
+    let message = PostedMessage {
+        at: Utc::now(),
+        event: event.to_string(),
+        channel: request.channel.clone().into(),
+        ts: None,
+        text: request.content.text.clone().unwrap_or_default(),
+        error: result.as_ref().err().map(|error| error.to_string()),
+    };
+    cache.audit_log.record(message);
+    result
+}
+
+/// Edit the board pinned in each scoreboard channel, the board being posted (and pinned) again
+/// if the channel has none yet or if it can no longer be edited.
+async fn refresh_live_scoreboards(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    cache: &MemoryCache,
+    event: &'static str,
+    board: &Command,
+) {
+    let Command::LeaderboardDisplay(year, _board, _time, scoring, detailed, page, n_pages) = board
+    else {
+        return;
+    };
+    let years = {
+        let data = cache.data.read().unwrap();
+        data.leaderboard.iter().map(|e| e.year).unique().collect()
+    };
+    let text = board.to_string();
+    let blocks = board_blocks(&text, (*year, scoring, *detailed), *page, *n_pages, years);
+    let content = SlackMessageContent::new()
+        .with_text(text)
+        .with_blocks(blocks);
+
+    for channel in config::SETTINGS.slack.scoreboard_channels.iter().flatten() {
+        let channel = SlackChannelId(channel.to_string());
+        let pinned = cache
+            .live_scoreboards
+            .read(|scoreboards| scoreboards.get(&channel).cloned());
+        if let Some(ts) = pinned {
+            let update = SlackApiChatUpdateRequest::new(channel.clone(), content.clone(), ts);
+            match session.chat_update(&update).await {
+                Ok(_) => continue,
+                Err(SlackClientError::ApiError(e))
+                    if SCOREBOARD_GONE_ERRORS.contains(&e.code.as_str()) =>
+                {
+                    info!(
+                        "Live scoreboard of {channel} can not be edited ({e}), posting it again."
+                    );
+                }
+                Err(e) => {
+                    let error = BotError::slack("Could not update live scoreboard.")(e);
+                    error!("{error}");
+                    continue;
+                }
+            };
+        };
+
+        let request = SlackApiChatPostMessageRequest::new(channel.clone(), content.clone());
+        let context = "Could not post live scoreboard.";
+        let ts = match post_message(session, cache, event, &request, context).await {
+            Ok(response) => response.ts,
+            Err(error) => {
+                error!("{error}");
+                continue;
+            }
+        };
+        cache
+            .live_scoreboards
+            .update(|scoreboards| scoreboards.insert(channel.clone(), ts.clone()));
+        if let Err(e) = session
+            .pins_add(&SlackApiPinsAddRequest::new(channel, ts))
+            .await
+        {
+            let error = BotError::slack("Could not pin live scoreboard.")(e);
+            error!("{error}");
+        };
+    }
+}
+
+/// Post the languages poll in the thread and add the reactions to vote with, returning the
+/// poll message ts.
+async fn post_language_poll(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    cache: &MemoryCache,
+    event: &'static str,
+    channel: &SlackChannelId,
+    thread_ts: &SlackTs,
+) -> BotResult<SlackTs> {
+    let request = SlackApiChatPostMessageRequest::new(
+        channel.clone(),
+        SlackMessageContent::new().with_text(languages::poll_message()),
+    )
+    .with_thread_ts(thread_ts.clone());
+    let context = "Could not post languages poll.";
+    let poll = post_message(session, cache, event, &request, context).await?;
+    add_poll_reactions(session, &poll, &POLL_LANGUAGES.map(|(r, _)| r)).await?;
+    Ok(poll.ts)
+}
+
+/// Add the reactions to vote with to a posted poll.
+async fn add_poll_reactions(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    poll: &SlackApiChatPostMessageResponse,
+    reactions: &[&str],
+) -> BotResult<()> {
+    for reaction in reactions {
+        let request = SlackApiReactionsAddRequest::new(
+            poll.channel.clone(),
+            SlackReactionName(reaction.to_string()),
+            poll.ts.clone(),
+        );
+        session
+            .reactions_add(&request)
+            .await
+            .map_err(BotError::slack("Could not add poll reaction."))?;
+    }
+    Ok(())
+}
+
+/// (reaction, count) of the languages polls, the polls whose reactions can not be
+/// retrieved being left out.
+async fn poll_reactions(
+    client: &SlackHyperClient,
+    cache: &MemoryCache,
+    polls: Vec<(SlackChannelId, SlackTs)>,
+) -> Vec<Vec<(String, usize)>> {
+    let Some(token) = bot_token(cache) else {
+        return vec![];
+    };
+    let session = client.open_session(&token);
+    let mut reactions = vec![];
+    for (channel, ts) in polls {
+        let request = SlackApiReactionsGetRequest::new()
+            .with_channel(channel)
+            .with_timestamp(ts)
+            .with_full(true);
+        match session.reactions_get(&request).await {
+            Ok(SlackApiReactionsGetResponse::Message(response)) => reactions.push(
+                response
+                    .message
+                    .content
+                    .reactions
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|r| (r.name.to_string(), r.count))
+                    .collect(),
+            ),
+            Ok(_) => (),
+            Err(e) => {
+                let error = BotError::slack("Could not retrieve poll reactions.")(e);
+                error!("{error}");
+            }
+        };
+    }
+    reactions
+}
+
+/// Thread of the command message to post the response in, None to post it in the channel.
+fn reply_thread(ts: &MessageId, reply: &Reply) -> Option<SlackTs> {
+    match reply {
+        Reply::Thread => Some(ts.into()),
+        Reply::Channel | Reply::Ephemeral(_) => None,
+    }
+}
+
+/// Upload the image in the channel (or thread), with its title as message.
+async fn upload_image(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    channel: SlackChannelId,
+    thread_ts: Option<SlackTs>,
+    image: &TextImage,
+) -> BotResult<()> {
+    let png = image.render_png()?;
+
+    let upload = session
+        .get_upload_url_external(
+            &SlackApiFilesGetUploadUrlExternalRequest::new("ceo.png".to_string(), png.len())
+                .with_alt_txt(image.title.clone()),
+        )
+        .await
+        .map_err(BotError::slack("Could not get image upload url."))?;
+    session
+        .files_upload_via_url(&SlackApiFilesUploadViaUrlRequest::new(
+            upload.upload_url,
+            png,
+            "image/png".to_string(),
+        ))
+        .await
+        .map_err(BotError::slack("Could not upload image."))?;
+    let mut request =
+        SlackApiFilesCompleteUploadExternalRequest::new(vec![SlackApiFilesComplete::new(
+            upload.file_id,
+        )
+        .with_title(image.title.clone())])
+        .with_channel_id(channel)
+        .with_initial_comment(format!("*{}*", image.title));
+    request.thread_ts = thread_ts;
+    session
+        .files_complete_upload_external(&request)
+        .await
+        .map_err(BotError::slack("Could not share uploaded image."))?;
+    Ok(())
+}
+
+/// Number of replies, languages mentioned and most reacted reply of a solutions thread.
+async fn thread_activity(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    thread: &SolutionThread,
+) -> BotResult<ThreadActivity> {
+    // the replies are listed page by page, following the cursor of the next page
+    let mut messages = vec![];
+    let mut cursor = None;
+    loop {
+        let request =
+            SlackApiConversationsRepliesRequest::new(thread.channel.clone(), thread.ts.clone())
+                .with_limit(200)
+                .opt_cursor(cursor);
+        let response = session
+            .conversations_replies(&request)
+            .await
+            .map_err(BotError::slack("Could not retrieve thread replies."))?;
+        messages.extend(response.messages);
+        cursor = response.response_metadata.and_then(|m| m.next_cursor);
+        if cursor.is_none() {
+            break;
+        };
+    }
+    let replies = messages
+        .into_iter()
+        // the first message is the thread parent message
+        .filter(|m| m.origin.ts != thread.ts)
+        // only replies from users are taken into account
+        .filter(|m| m.sender.bot_id.is_none())
+        .collect::<Vec<_>>();
+
+    let languages = languages_mentioned(replies.iter().filter_map(|m| m.content.text.as_deref()));
+
+    let most_reacted = replies
+        .iter()
+        .map(|m| {
+            let n_reactions = m
+                .content
+                .reactions
+                .as_ref()
+                .map_or(0, |reactions| reactions.iter().map(|r| r.count).sum());
+            (m, n_reactions)
+        })
+        .filter(|(_m, n_reactions)| *n_reactions > 0)
+        .max_by_key(|(_m, n_reactions)| *n_reactions);
+    let most_reacted = match most_reacted {
+        Some((message, n_reactions)) => {
+            let request = SlackApiChatGetPermalinkRequest::new(
+                thread.channel.clone(),
+                message.origin.ts.clone(),
+            );
+            let permalink = session
+                .chat_get_permalink(&request)
+                .await
+                .map_err(BotError::slack("Could not retrieve permalink."))?
+                .permalink;
+            Some((permalink.to_string(), n_reactions))
+        }
+        None => None,
+    };
+
+    Ok(ThreadActivity {
+        n_replies: replies.len(),
+        languages,
+        most_reacted,
+    })
+}
+
 async fn push_events_socket_mode_function(
     event: SlackPushEventCallback,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if let SlackEventCallbackBody::Message(message) = event.event {
-        // Only respond to messages from users (no bot_id) or allowed bots
-        let is_not_whitelisted_bot = message.sender.bot_id.and_then(|id| {
-            let settings = &config::SETTINGS;
-            match settings
-                .slack_bots_authorized_ids
-                .as_ref()
-                .is_some_and(|whitelisted| whitelisted.contains(&id.to_string()))
-            {
-                true => None,
-                false => Some("Bot id not whitelisted"),
-            }
-        });
-        if is_not_whitelisted_bot.is_none() {
-            // message from user, we will handle it if there is content and channel_id
-            if let (Some(content), Some(channel_id)) = (message.content, message.origin.channel) {
-                if let Some(t) = content.text {
-                    if Command::is_command(&t) {
-                        let states = states.read().await;
-                        let state: Option<&MyEnvironment> =
-                            states.get_user_state::<MyEnvironment>();
-                        if let Some(env) = state {
-                            let cache = env.cache.clone();
-                            let sender = env.sender.clone();
-
-                            let cmd = {
-                                let data = cache.data.lock().unwrap();
-                                // Safe unwrap as we already know it is a valid command
-                                Command::build_from(t, &data).unwrap()
-                            };
+    match event.event {
+        // Reactions on the replies of the solutions threads are counted as votes
+        SlackEventCallbackBody::ReactionAdded(reaction) => {
+            let (user, item_user) = (&reaction.user, reaction.item_user.as_ref());
+            record_vote(
+                &states,
+                reaction.item,
+                user,
+                &reaction.reaction.0,
+                item_user,
+                true,
+            )
+            .await;
+        }
+        SlackEventCallbackBody::ReactionRemoved(reaction) => {
+            let (user, item_user) = (&reaction.user, reaction.item_user.as_ref());
+            record_vote(
+                &states,
+                reaction.item,
+                user,
+                &reaction.reaction.0,
+                item_user,
+                false,
+            )
+            .await;
+        }
+        // The dashboard of the Home tab is refreshed each time the user opens it
+        SlackEventCallbackBody::AppHomeOpened(home) if home.tab.as_deref() == Some("home") => {
+            let cache = {
+                let states = states.read().await;
+                states
+                    .get_user_state::<MyEnvironment>()
+                    .map(|env| env.cache.clone())
+            };
+            if let Some(cache) = cache {
+                publish_app_home(&client, &cache, home.user).await;
+            };
+        }
+        SlackEventCallbackBody::Message(message) => {
+            // Replies of users in the solutions threads (commands excluded) can be upvoted
+            if let (Some(thread_ts), Some(channel), Some(user), None, None) = (
+                &message.origin.thread_ts,
+                &message.origin.channel,
+                &message.sender.user,
+                &message.sender.bot_id,
+                &message.subtype,
+            ) {
+                let is_command = message
+                    .content
+                    .as_ref()
+                    .and_then(|c| c.text.as_deref())
+                    .is_some_and(Command::is_command);
+                if !is_command {
+                    record_solution_reply(&states, channel, thread_ts, &message.origin.ts, user)
+                        .await;
+                };
+            };
+            // Only respond to messages from users (no bot_id) or allowed bots
+            let is_not_whitelisted_bot = message.sender.bot_id.and_then(|id| {
+                let settings = &config::SETTINGS;
+                match settings
+                    .slack
+                    .bots_authorized_ids
+                    .as_ref()
+                    .is_some_and(|whitelisted| whitelisted.contains(&id.to_string()))
+                {
+                    true => None,
+                    false => Some("Bot id not whitelisted"),
+                }
+            });
+            if is_not_whitelisted_bot.is_none() {
+                // message from user, we will handle it if there is content and channel_id
+                if let (Some(content), Some(channel_id)) = (message.content, message.origin.channel)
+                {
+                    if let Some(t) = content.text {
+                        if Command::is_command(&t) {
+                            let states = states.read().await;
+                            let state: Option<&MyEnvironment> =
+                                states.get_user_state::<MyEnvironment>();
+                            if let Some(env) = state {
+                                let cache = env.cache.clone();
+                                let sender = env.sender.clone();
+                                let chat = ChatId::from(channel_id);
+                                let thread_ts = MessageId::from(message.origin.ts); // to respond in thread
+                                let user = message.sender.user.clone().map(UserId::from);
+                                let mode = Command::name(&t)
+                                    .map(|name| config::SETTINGS.commands.response_mode(name))
+                                    .unwrap_or_default();
+                                // bots can not be sent ephemeral messages
+                                let reply = match (mode, &message.sender.user) {
+                                    (ResponseMode::Channel, _) => Reply::Channel,
+                                    (ResponseMode::Ephemeral, Some(user)) => {
+                                        Reply::Ephemeral(user.clone().into())
+                                    }
+                                    _ => Reply::Thread,
+                                };
+
+                                // Commands run too often are not computed again, the requester
+                                // being only told once to wait
+                                let throttled = Command::name(&t).and_then(|name| {
+                                    env.cooldown
+                                        .try_run(name, user.as_ref(), &chat, Instant::now())
+                                        .err()
+                                        .map(|throttled| (name, throttled))
+                                });
+                                if let Some((name, throttled)) = throttled {
+                                    info!("Command run too often, cooling down.");
+                                    if let Throttled::Notify(wait) = throttled {
+                                        let wait_sec = wait.as_secs_f64().ceil() as u64;
+                                        let cmd = Command::Cooldown(name, wait_sec);
+                                        if let Err(e) = sender
+                                            .send(Event::CommandReceived(
+                                                chat, thread_ts, reply, cmd,
+                                            ))
+                                            .await
+                                        {
+                                            error!("{}", e);
+                                        };
+                                    };
+                                    return Ok(());
+                                };
 
-                            let thread_ts = message.origin.ts; // to respond in thread
+                                let cmd = {
+                                    let data = cache.data.read().unwrap();
+                                    // Safe unwrap as we already know it is a valid command
+                                    Command::build_from(t, &data, &cache.precomputed).unwrap()
+                                };
 
-                            if let Err(e) = sender
-                                .send(Event::CommandReceived(channel_id, thread_ts, cmd))
-                                .await
-                            {
-                                error!("{}", e);
+                                let requester =
+                                    Requester {
+                                        user,
+                                        is_admin: message.sender.user.as_ref().is_some_and(
+                                            |user| config::SETTINGS.is_slack_admin(user),
+                                        ),
+                                    };
+                                // The commands relying on the Slack threads and reactions are resolved here, the others
+                                // being shared with the other messaging backends.
+                                let cmd = match cmd {
+                                    Command::BestSolutions(year, day, _) => {
+                                        let replies = cache.solution_replies.read(|replies| {
+                                            votes::best_solutions(replies, year, day)
+                                        });
+                                        let solutions = solution_permalinks(&client, &cache, replies).await;
+                                        Command::BestSolutions(year, day, solutions)
+                                    }
+                                    Command::Languages(year, _) => {
+                                        let polls = cache.solution_threads.read(|threads| {
+                                            threads
+                                                .iter()
+                                                .filter(|t| t.year == year)
+                                                .filter_map(|t| t.poll_ts.clone().map(|ts| (t.channel.clone(), ts)))
+                                                .collect()
+                                        });
+                                        let reactions = poll_reactions(&client, &cache, polls).await;
+                                        Command::Languages(year, languages::season_languages(&reactions))
+                                    }
+                                    Command::Preview(_)
+                                        if requester.is_admin
+                                            && config::SETTINGS.slack.monitoring_channel.is_none() =>
+                                    {
+                                        Command::NotValid(
+                                            "Previews are posted in the monitoring channel, which is not configured 📭."
+                                                .to_string(),
+                                        )
+                                    }
+                                    cmd => handler::handle(cmd, &requester, &cache, &sender).await,
+                                };
+
+                                if let Err(e) = sender
+                                    .send(Event::CommandReceived(chat, thread_ts, reply, cmd))
+                                    .await
+                                {
+                                    error!("{}", e);
+                                };
                             };
-                            // }
                         };
                     };
                 };
-            };
+            }
         }
+        _ => (),
     };
     Ok(())
 }
 
+// Keep track of the replies posted in the daily solutions threads, so they can be upvoted.
+async fn record_solution_reply(
+    states: &SlackClientEventsUserState,
+    channel: &SlackChannelId,
+    thread_ts: &SlackTs,
+    ts: &SlackTs,
+    author: &SlackUserId,
+) {
+    let states = states.read().await;
+    if let Some(env) = states.get_user_state::<MyEnvironment>() {
+        let thread = env.cache.solution_threads.read(|threads| {
+            threads
+                .iter()
+                .find(|t| t.channel == *channel && t.ts == *thread_ts)
+                .map(|t| (t.year, t.day))
+        });
+        if let Some((year, day)) = thread {
+            let reply = SolutionReply {
+                year,
+                day,
+                channel: channel.clone(),
+                ts: ts.clone(),
+                author: author.clone(),
+                voters: HashMap::new(),
+            };
+            env.cache
+                .solution_replies
+                .update(|replies| replies.push(reply));
+        };
+    };
+}
+
+async fn record_vote(
+    states: &SlackClientEventsUserState,
+    item: SlackReactionsItem,
+    user: &SlackUserId,
+    reaction: &str,
+    item_user: Option<&SlackUserId>,
+    added: bool,
+) {
+    if let SlackReactionsItem::Message(message) = item {
+        let states = states.read().await;
+        if let (Some(env), Some(channel)) = (
+            states.get_user_state::<MyEnvironment>(),
+            message.origin.channel,
+        ) {
+            env.cache.solution_replies.update(|replies| {
+                votes::record_reaction(replies, &channel, &message.origin.ts, user, added)
+            });
+            // the reactions added by the bot to open its poll are not votes
+            if item_user != Some(user) {
+                env.cache.difficulty_polls.update(|polls| {
+                    let poll = polls
+                        .iter_mut()
+                        .find(|p| p.channel == channel && p.ts == message.origin.ts);
+                    if let Some(poll) = poll {
+                        difficulty::record_poll_vote(&mut poll.votes, user, reaction, added);
+                    };
+                });
+            };
+        };
+    };
+}
+
+/// (permalink, author mention, votes) of the replies. Replies whose permalink can not be
+/// retrieved are left out.
+async fn solution_permalinks(
+    client: &SlackHyperClient,
+    cache: &MemoryCache,
+    replies: Vec<SolutionReply>,
+) -> Vec<(String, String, u32)> {
+    let Some(token) = bot_token(cache) else {
+        return vec![];
+    };
+    let session = client.open_session(&token);
+    let mut solutions = vec![];
+    for reply in replies {
+        let votes = reply.votes();
+        let request = SlackApiChatGetPermalinkRequest::new(reply.channel, reply.ts);
+        match session.chat_get_permalink(&request).await {
+            Ok(response) => solutions.push((
+                response.permalink.to_string(),
+                format!("<@{}>", reply.author),
+                votes,
+            )),
+            Err(e) => {
+                let error = BotError::slack("Could not retrieve permalink.")(e);
+                error!("{error}");
+            }
+        };
+    }
+    solutions
+}
+
+async fn interaction_events_socket_mode_function(
+    event: SlackInteractionEvent,
+    client: Arc<SlackHyperClient>,
+    states: SlackClientEventsUserState,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let SlackInteractionEvent::BlockActions(event) = event {
+        let states = states.read().await;
+        let state: Option<&MyEnvironment> = states.get_user_state::<MyEnvironment>();
+        if let Some(env) = state {
+            handle_board_actions(event, client, env.cache.clone()).await;
+        };
+    };
+    Ok(())
+}
+
+// Update the board message with the page/year requested through its interactive components
+async fn handle_board_actions(
+    event: SlackInteractionBlockActionsEvent,
+    client: Arc<SlackHyperClient>,
+    cache: MemoryCache,
+) {
+    let (Some(channel), Some(message), Some(actions)) =
+        (event.channel, event.message, event.actions)
+    else {
+        return;
+    };
+
+    let requested_board = actions.into_iter().find_map(|action| {
+        let value = match action.action_id.to_string().as_str() {
+            BOARD_PREVIOUS_PAGE_ACTION | BOARD_NEXT_PAGE_ACTION => action.value,
+            BOARD_YEAR_ACTION => action.selected_option.map(|option| option.value),
+            _ => None,
+        };
+        value.and_then(|v| parse_board_action_value(&v))
+    });
+
+    if let Some((year, scoring, detailed, page)) = requested_board {
+        let (cmd, years) = {
+            let data = cache.data.read().unwrap();
+            let years = data.leaderboard.iter().map(|e| e.year).unique().collect();
+            (
+                Command::board_page(&data, &cache.precomputed, year, scoring, detailed, page),
+                years,
+            )
+        };
+
+        if let Command::LeaderboardDisplay(year, _board, _time, scoring, detailed, page, n_pages) =
+            &cmd
+        {
+            let board = (*year, scoring, *detailed);
+            let blocks = board_blocks(&cmd.to_string(), board, *page, *n_pages, years);
+            let update = SlackApiChatUpdateRequest::new(
+                channel.id.clone(),
+                SlackMessageContent::new()
+                    .with_text(cmd.to_string())
+                    .with_blocks(blocks),
+                message.origin.ts.clone(),
+            );
+
+            let Some(app_token) = bot_token(&cache) else {
+                error!("No Slack bot token, the bot must be installed in the workspace first.");
+                return;
+            };
+            let session = client.open_session(&app_token);
+            if let Err(e) = session.chat_update(&update).await {
+                let error = BotError::slack("Could not update board message.")(e);
+                error!("{error}");
+            };
+        };
+    };
+}
+
+// Render the dashboard of the event (personal stats, board and jerseys) in the Home tab of the user
+async fn publish_app_home(client: &SlackHyperClient, cache: &MemoryCache, user: SlackUserId) {
+    let member = names::slack_users()
+        .into_iter()
+        .find(|(_member, linked)| *linked == user)
+        .map(|(member, _linked)| member);
+    let sections = {
+        let data = cache.data.read().unwrap();
+        let (year, _day) = current_aoc_year_day();
+        app_home(&data, &cache.precomputed, member, year).sections()
+    };
+
+    if config::SETTINGS.features.dry_run {
+        info!(
+            "Dry run, would publish in the App Home of {user}:\n{}",
+            sections.concat().join("\n")
+        );
+        return;
+    };
+    let Some(token) = bot_token(cache) else {
+        error!("No Slack bot token, the bot must be installed in the workspace first.");
+        return;
+    };
+    let view = SlackView::Home(SlackHomeView::new(home_blocks(sections)));
+    let session = client.open_session(&token);
+    if let Err(e) = session
+        .views_publish(&SlackApiViewsPublishRequest::new(user, view))
+        .await
+    {
+        let error = BotError::slack("Could not publish App Home.")(e);
+        error!("{error}");
+    };
+}
+
+/// Bot token, the configured one (e.g. injected from a secret store through `SLACK__TOKEN`)
+/// prevailing over the one obtained when the bot was installed through OAuth.
+fn bot_token(cache: &MemoryCache) -> Option<SlackApiToken> {
+    let settings = &config::SETTINGS;
+    let token = settings.slack.token.clone().map(|t| t.into()).or_else(|| {
+        cache
+            .slack_installation
+            .read(|installation| installation.as_ref().map(|i| i.bot_token.clone()))
+    })?;
+    Some(SlackApiToken::new(token))
+}
+
+/// Value attached to the board interactive components, describing the board to display.
+fn board_action_value((year, scoring, detailed): (i32, &Scoring, bool), page: usize) -> String {
+    match detailed {
+        true => format!("{year} {scoring} {page} {BOARD_DETAILED_OPTION}"),
+        false => format!("{year} {scoring} {page}"),
+    }
+}
+
+fn parse_board_action_value(value: &str) -> Option<(i32, Scoring, bool, usize)> {
+    let mut parts = value.split_whitespace();
+    let year = parts.next()?.parse::<i32>().ok()?;
+    let scoring = Scoring::from_string(parts.next()?)?;
+    let page = parts.next()?.parse::<usize>().ok()?;
+    let detailed = parts.next() == Some(BOARD_DETAILED_OPTION);
+    Some((year, scoring, detailed, page))
+}
+
+/// Board message as Block Kit, with navigation buttons between pages and a year selector.
+fn board_blocks(
+    text: &str,
+    board: (i32, &Scoring, bool),
+    page: usize,
+    n_pages: usize,
+    years: Vec<i32>,
+) -> Vec<SlackBlock> {
+    let (year, scoring, detailed) = board;
+    let mut elements = vec![];
+    if page > 0 {
+        elements.push(SlackActionBlockElement::Button(
+            SlackBlockButtonElement::new(SlackBlockPlainTextOnly::from("◀️ Previous"))
+                .with_action_id(SlackActionId::new(BOARD_PREVIOUS_PAGE_ACTION.to_string()))
+                .with_value(board_action_value(board, page - 1)),
+        ));
+    };
+    if page + 1 < n_pages {
+        elements.push(SlackActionBlockElement::Button(
+            SlackBlockButtonElement::new(SlackBlockPlainTextOnly::from("Next ▶️"))
+                .with_action_id(SlackActionId::new(BOARD_NEXT_PAGE_ACTION.to_string()))
+                .with_value(board_action_value(board, page + 1)),
+        ));
+    };
+    if years.len() > 1 {
+        let option = |y: i32| {
+            SlackBlockChoiceItem::new(
+                SlackBlockPlainTextOnly::from(y.to_string()),
+                board_action_value((y, scoring, detailed), 0),
+            )
+        };
+        elements.push(SlackActionBlockElement::StaticSelect(
+            SlackBlockStaticSelectElement::new()
+                .with_action_id(SlackActionId::new(BOARD_YEAR_ACTION.to_string()))
+                .with_options(years.into_iter().sorted().rev().map(option).collect())
+                .with_initial_option(option(year)),
+        ));
+    };
+
+    let mut blocks = vec![SlackBlock::Section(
+        SlackSectionBlock::new().with_text(md!(text)),
+    )];
+    if !elements.is_empty() {
+        blocks.push(SlackBlock::Actions(SlackActionsBlock::new(elements)));
+    };
+    blocks
+}
+
+/// Home tab as Block Kit, the sections being separated by dividers.
+fn home_blocks(sections: Vec<Vec<String>>) -> Vec<SlackBlock> {
+    sections
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, texts)| {
+            let divider = (i > 0).then(|| SlackBlock::Divider(SlackDividerBlock::new()));
+            divider.into_iter().chain(
+                texts
+                    .into_iter()
+                    .map(|text| SlackBlock::Section(SlackSectionBlock::new().with_text(md!(text)))),
+            )
+        })
+        .collect()
+}
+
 fn error_handler(
     err: Box<dyn std::error::Error + Send + Sync>,
     _client: Arc<SlackHyperClient>,
     _states: SlackClientEventsUserState,
 ) -> StatusCode {
-    let error = BotError::Slack(err.to_string());
+    let error = BotError::SlackListener(err.to_string());
     error!("{error}");
 
     // This return value should be OK if we want to return successful ack to the Slack server using Web-sockets
@@ -0,0 +1,717 @@
+use crate::{
+    client::{
+        cooldown::{CommandCooldown, Throttled},
+        debounce::DebounceSink,
+        dispatch::{dispatch, EventSink, Lane, OutboundQueue},
+        email::DigestSink,
+        quiet::QuietHoursSink,
+        retry::{Failure, RetryPolicy},
+    },
+    config::{self, ResponseMode, TelegramSettings},
+    core::{
+        chat::{ChatId, MessageId, UserId},
+        commands::Command,
+        events::{Event, Reply},
+        handler::{self, Requester},
+        names,
+    },
+    error::{BotError, BotResult},
+    storage::{audit::PostedMessage, MemoryCache},
+};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use slack_morphism::SlackUserId;
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{error, info};
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+// Time the Bot API holds a `getUpdates` request open while waiting for new messages
+const LONG_POLLING_TIMEOUT_SEC: u64 = 30;
+// Delay before polling again after a failed `getUpdates` request
+const POLLING_RETRY_DELAY: Duration = Duration::from_secs(5);
+// Delay between the messages of a batched announcement (Telegram allows ~20 messages/min per group)
+const BATCH_THROTTLE: Duration = Duration::from_secs(3);
+// Retries of the messages failing to be posted, before they are dead-lettered (~1 min overall)
+const POST_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    initial_backoff: Duration::from_secs(4),
+    max_backoff: Duration::from_secs(30),
+};
+// Unicode emojis of the Slack shortcodes used in the messages, the other shortcodes (e.g. the
+// custom emojis of a theme pack) being kept as is.
+const EMOJI_SHORTCODES: [(&str, &str); 18] = [
+    ("one", "1️⃣"),
+    ("two", "2️⃣"),
+    ("three", "3️⃣"),
+    ("four", "4️⃣"),
+    ("five", "5️⃣"),
+    ("rotating_light", "🚨"),
+    ("warning", "⚠️"),
+    ("snake", "🐍"),
+    ("tada", "🎉"),
+    ("star", "⭐"),
+    ("trophy", "🏆"),
+    ("first_place_medal", "🥇"),
+    ("second_place_medal", "🥈"),
+    ("third_place_medal", "🥉"),
+    ("christmas_tree", "🎄"),
+    ("mega", "📣"),
+    ("fire", "🔥"),
+    ("rocket", "🚀"),
+];
+
+// Slack mrkdwn syntax of the rendered messages
+static CODE_BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)```(.*?)```").unwrap());
+static INLINE_CODE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
+// mentions (`<@U0DASHER>`), channels (`<#C0GENERAL|general>`) and links (`<url|label>`)
+static SLACK_TOKEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<((?:[@#!]|https?://|mailto:)[^<>|\s]*)(?:\|([^<>]*))?>").unwrap());
+static EMOJI_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r":([a-z0-9_+-]+):").unwrap());
+// HTML fragments set aside while the text around them is converted
+static FRAGMENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x00(\d+)\x00").unwrap());
+static STYLE_REGEXES: Lazy<[(Regex, &str); 3]> = Lazy::new(|| {
+    [('*', "b"), ('_', "i"), ('~', "s")].map(|(delimiter, tag)| {
+        let d = regex::escape(&delimiter.to_string());
+        (
+            Regex::new(&format!(r"{d}(\S(?:[^{d}\n]*\S)?){d}")).unwrap(),
+            tag,
+        )
+    })
+});
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+    error_code: Option<u16>,
+    parameters: Option<ResponseParameters>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseParameters {
+    // seconds to wait before the call can be made again, when the bot is rate limited
+    retry_after: Option<u64>,
+}
+
+/// Failed Bot API call, along with whether it is worth retrying.
+#[derive(Debug)]
+struct CallError {
+    error: BotError,
+    failure: Failure,
+}
+
+impl<E: Into<BotError>> From<E> for CallError {
+    // Network errors are not retried, the message might have been posted, unless the
+    // connection could not be established.
+    fn from(error: E) -> Self {
+        let error = error.into();
+        let failure = match &error {
+            BotError::Http(e) if e.is_connect() => Failure::Transient(None),
+            _ => Failure::Permanent,
+        };
+        CallError { error, failure }
+    }
+}
+
+/// Whether a call answered with the error code is worth retrying: rate limits and server
+/// errors, the message not being posted.
+fn telegram_failure(error_code: Option<u16>, parameters: Option<ResponseParameters>) -> Failure {
+    match error_code {
+        Some(429) => Failure::Transient(
+            parameters
+                .and_then(|p| p.retry_after)
+                .map(Duration::from_secs),
+        ),
+        Some(code) if code >= 500 => Failure::Transient(None),
+        _ => Failure::Permanent,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Update {
+    update_id: i64,
+    message: Option<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    message_id: i64,
+    chat: Chat,
+    from: Option<User>,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    id: i64,
+    is_bot: bool,
+}
+
+/// Minimal client of the Telegram Bot API, for the calls made by the bot.
+#[derive(Clone)]
+struct BotApi {
+    http_client: Client,
+    base_url: String,
+}
+
+impl BotApi {
+    fn new(token: &str) -> BotResult<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(LONG_POLLING_TIMEOUT_SEC + 10))
+            .build()?;
+        Ok(Self {
+            http_client,
+            base_url: format!("{TELEGRAM_API_URL}/bot{token}"),
+        })
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: serde_json::Value,
+    ) -> BotResult<T> {
+        self.try_call(method, body).await.map_err(|e| e.error)
+    }
+
+    async fn try_call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: serde_json::Value,
+    ) -> Result<T, CallError> {
+        let response = self
+            .http_client
+            .post(format!("{}/{method}", self.base_url))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await?;
+        // Errors are reported in the body, along with a non OK status
+        let response = serde_json::from_str::<ApiResponse<T>>(&response.text().await?)?;
+        match response {
+            ApiResponse {
+                ok: true,
+                result: Some(result),
+                ..
+            } => Ok(result),
+            ApiResponse {
+                description,
+                error_code,
+                parameters,
+                ..
+            } => Err(CallError {
+                error: BotError::Telegram(
+                    description.unwrap_or_else(|| format!("{method} failed")),
+                ),
+                failure: telegram_failure(error_code, parameters),
+            }),
+        }
+    }
+
+    /// Messages received after the offset, waiting for new ones if there are none yet.
+    async fn get_updates(&self, offset: i64) -> BotResult<Vec<Update>> {
+        let body = json!({
+            "offset": offset,
+            "timeout": LONG_POLLING_TIMEOUT_SEC,
+            "allowed_updates": ["message"],
+        });
+        self.call("getUpdates", body).await
+    }
+
+    /// Post the HTML message in the chat, as a reply if a message id is given, retrying on
+    /// transient errors. Returns the id of the posted message, along with the number of attempts.
+    async fn send_message(
+        &self,
+        chat_id: i64,
+        reply_to: Option<i64>,
+        html: &str,
+    ) -> (BotResult<i64>, u32) {
+        let mut body = json!({
+            "chat_id": chat_id,
+            "text": html,
+            "parse_mode": "HTML",
+            "link_preview_options": { "is_disabled": true },
+        });
+        if let Some(message_id) = reply_to {
+            body["reply_parameters"] =
+                json!({ "message_id": message_id, "allow_sending_without_reply": true });
+        };
+        let (result, attempts) = POST_RETRY
+            .run(
+                || self.try_call::<Message>("sendMessage", body.clone()),
+                |e| e.failure,
+            )
+            .await;
+        (
+            result
+                .map(|message| message.message_id)
+                .map_err(|e| e.error),
+            attempts,
+        )
+    }
+
+    /// Whether the chat exists and is visible to the bot.
+    async fn get_chat(&self, chat_id: i64) -> BotResult<Chat> {
+        self.call("getChat", json!({ "chat_id": chat_id })).await
+    }
+}
+
+pub struct AoCTelegramClient {
+    api: BotApi,
+}
+
+impl AoCTelegramClient {
+    pub fn new() -> BotResult<Self> {
+        let settings = &config::SETTINGS;
+        Ok(Self {
+            api: BotApi::new(&settings.telegram.bot_token)?,
+        })
+    }
+
+    /// Problems with the Telegram settings found by calling the Bot API: a rejected token, and
+    /// groups the bot can not see.
+    pub async fn check_settings(&self) -> Vec<String> {
+        let telegram = &config::SETTINGS.telegram;
+        if let Err(e) = self.api.call::<User>("getMe", json!({})).await {
+            return vec![format!("telegram.bot_token: rejected by Telegram ({e})")];
+        };
+        let mut problems = vec![];
+        for (name, chat_id) in [
+            ("telegram.chat_id", Some(telegram.chat_id)),
+            ("telegram.monitoring_chat_id", telegram.monitoring_chat_id),
+        ] {
+            let Some(chat_id) = chat_id else {
+                continue;
+            };
+            if let Err(e) = self.api.get_chat(chat_id).await {
+                problems.push(format!("{name}: '{chat_id}' could not be found ({e})"));
+            };
+        }
+        problems
+    }
+
+    pub async fn handle_messages_and_events(
+        &self,
+        cache: MemoryCache,
+        tx: Sender<Event>,
+        rx: Receiver<Event>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut sink = DigestSink::new(
+            DebounceSink::new(
+                QuietHoursSink::new(
+                    TelegramEventSink {
+                        api: self.api.clone(),
+                        cache: cache.clone(),
+                        outbound: OutboundQueue::spawn(),
+                    },
+                    config::SETTINGS.quiet_hours(),
+                    cache.quiet_hours_held.clone(),
+                ),
+                config::SETTINGS.highlights_debounce(),
+            ),
+            cache.clone(),
+        );
+        tokio::spawn(async move {
+            dispatch(rx, &mut sink).await;
+        });
+        self.poll_commands(cache, tx).await;
+        Ok(())
+    }
+
+    // Long polling of the messages posted in the groups of the bot, the commands being answered
+    // like on Slack.
+    async fn poll_commands(&self, cache: MemoryCache, tx: Sender<Event>) {
+        let settings = &config::SETTINGS;
+        let cooldown = CommandCooldown::new(
+            settings.commands.max_per_user_per_minute,
+            settings.commands.max_per_channel_per_minute,
+        );
+        let mut offset = 0;
+        loop {
+            match self.api.get_updates(offset).await {
+                Ok(updates) => {
+                    for update in updates {
+                        // updates are acknowledged by asking for the following ones
+                        offset = update.update_id + 1;
+                        if let Some(message) = update.message {
+                            handle_message(message, &cache, &tx, &cooldown).await;
+                        };
+                    }
+                }
+                Err(error) => {
+                    error!("{error}");
+                    tokio::time::sleep(POLLING_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+}
+
+async fn handle_message(
+    message: Message,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+    cooldown: &CommandCooldown,
+) {
+    let settings = &config::SETTINGS;
+    let telegram = &settings.telegram;
+    // Only messages of users, in the groups of the bot, are answered
+    let (Some(text), Some(user)) = (message.text, message.from) else {
+        return;
+    };
+    let chats = [Some(telegram.chat_id), telegram.monitoring_chat_id];
+    if user.is_bot || !chats.contains(&Some(message.chat.id)) || !Command::is_command(&text) {
+        return;
+    };
+
+    let chat = ChatId(message.chat.id.to_string());
+    let message_id = MessageId(message.message_id.to_string());
+    let user = UserId(user.id.to_string());
+    // there are no ephemeral messages in Telegram groups
+    let reply = match Command::name(&text).map(|name| settings.commands.response_mode(name)) {
+        Some(ResponseMode::Channel) => Reply::Channel,
+        _ => Reply::Thread,
+    };
+
+    // Commands run too often are not computed again, the requester being only told once to wait
+    let throttled = Command::name(&text).and_then(|name| {
+        cooldown
+            .try_run(name, Some(&user), &chat, Instant::now())
+            .err()
+            .map(|throttled| (name, throttled))
+    });
+    let cmd = match throttled {
+        Some((name, Throttled::Notify(wait))) => {
+            info!("Command run too often, cooling down.");
+            Command::Cooldown(name, wait.as_secs_f64().ceil() as u64)
+        }
+        Some((_name, Throttled::Silently)) => {
+            info!("Command run too often, cooling down.");
+            return;
+        }
+        None => {
+            let cmd = {
+                let data = cache.data.read().unwrap();
+                // Safe unwrap as we already know it is a valid command
+                Command::build_from(text, &data, &cache.precomputed).unwrap()
+            };
+            resolve(cmd, user, cache, sender).await
+        }
+    };
+
+    if let Err(e) = sender
+        .send(Event::CommandReceived(chat, message_id, reply, cmd))
+        .await
+    {
+        error!("{}", e);
+    };
+}
+
+// Commands relying on the Slack threads, reactions or direct messages are not available, the
+// other ones are shared with Slack. The admins of the bot are Slack users.
+async fn resolve(
+    cmd: Command,
+    user: UserId,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+) -> Command {
+    match cmd {
+        Command::BestSolutions(..)
+        | Command::Languages(..)
+        | Command::Reminder(_)
+        | Command::Watch(..)
+        | Command::ReviewOptIn(_)
+        | Command::PersonalSummaryOptIn(_) => {
+            Command::NotValid("This command is only available on Slack for now 🙏.".to_string())
+        }
+        cmd => {
+            let requester = Requester {
+                user: Some(user),
+                is_admin: false,
+            };
+            handler::handle(cmd, &requester, cache, sender).await
+        }
+    }
+}
+
+/// Chat the event is posted in, and message replied to (if any). None for the events without
+/// Telegram counterpart (direct messages to Slack users, Slack threads).
+fn destination(event: &Event, telegram: &TelegramSettings) -> Option<(i64, Option<i64>)> {
+    match event {
+        Event::CommandReceived(chat, message_id, reply, _cmd) => {
+            let chat_id = chat.0.parse().ok()?;
+            let reply_to = match reply {
+                Reply::Channel => None,
+                _ => message_id.0.parse().ok(),
+            };
+            Some((chat_id, reply_to))
+        }
+        Event::PersonalReminder(..)
+        | Event::YearInReview(..)
+        | Event::PersonalSummary(..)
+        | Event::WatchAlerts(..)
+        | Event::DailySolutionsThreadToSummarize(..)
+        | Event::LiveScoreboard(_) => None,
+        Event::PrivateLeaderboardUpdated
+        | Event::PrivateLeaderboardMembership(_)
+        | Event::SeasonChanged(..)
+        | Event::MonitoringAlert(..)
+        | Event::Heartbeat(_)
+        | Event::TemplatePreview(_) => telegram.monitoring_chat_id.map(|chat_id| (chat_id, None)),
+        _ => Some((telegram.chat_id, None)),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Slack mention, channel or link, as HTML.
+fn slack_token_html(target: &str, label: Option<&str>) -> String {
+    if let Some(user) = target.strip_prefix('@') {
+        // Slack users can not be mentioned, the member linked to them is named instead
+        let name = names::linked_name(&SlackUserId(user.to_string()));
+        return escape_html(&name.unwrap_or_else(|| format!("@{user}")));
+    };
+    match (target.chars().next(), label) {
+        (Some('#'), Some(channel)) => escape_html(&format!("#{channel}")),
+        (Some('#' | '!'), None) => escape_html(target),
+        (Some('!'), Some(label)) => escape_html(label),
+        (_, label) => format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(target).replace('"', "&quot;"),
+            escape_html(label.unwrap_or(target))
+        ),
+    }
+}
+
+/// Message rendered for Slack (mrkdwn) as the HTML subset supported by Telegram: the code blocks
+/// keep the monospace layout of the boards, the mentions and links are rewritten and the emoji
+/// shortcodes replaced by their unicode emoji.
+fn to_telegram_html(text: &str) -> String {
+    let mut fragments: Vec<String> = vec![];
+    let mut set_aside = |html: String| {
+        fragments.push(html);
+        format!("\x00{}\x00", fragments.len() - 1)
+    };
+    let text = CODE_BLOCK_REGEX.replace_all(text, |c: &Captures| {
+        set_aside(format!(
+            "<pre>{}</pre>",
+            escape_html(c[1].trim_matches('\n'))
+        ))
+    });
+    let text = INLINE_CODE_REGEX.replace_all(&text, |c: &Captures| {
+        set_aside(format!("<code>{}</code>", escape_html(&c[1])))
+    });
+    let text = SLACK_TOKEN_REGEX.replace_all(&text, |c: &Captures| {
+        set_aside(slack_token_html(&c[1], c.get(2).map(|m| m.as_str())))
+    });
+
+    let mut html = escape_html(&text);
+    for (regex, tag) in STYLE_REGEXES.iter() {
+        html = regex
+            .replace_all(&html, |c: &Captures| {
+                // like on Slack, delimiters within words (e.g. snake_case names) are kept
+                let span = c.get(0).unwrap();
+                let within_word = html[..span.start()]
+                    .chars()
+                    .next_back()
+                    .into_iter()
+                    .chain(html[span.end()..].chars().next())
+                    .any(char::is_alphanumeric);
+                match within_word {
+                    true => c[0].to_string(),
+                    false => format!("<{tag}>{}</{tag}>", &c[1]),
+                }
+            })
+            .to_string();
+    }
+    let html = EMOJI_REGEX.replace_all(&html, |c: &Captures| {
+        EMOJI_SHORTCODES
+            .iter()
+            .find(|(shortcode, _emoji)| *shortcode == &c[1])
+            .map_or_else(|| c[0].to_string(), |(_shortcode, emoji)| emoji.to_string())
+    });
+    FRAGMENT_REGEX
+        .replace_all(&html, |c: &Captures| {
+            fragments[c[1].parse::<usize>().unwrap()].clone()
+        })
+        .to_string()
+}
+
+/// Post the dispatched events to the Telegram groups.
+struct TelegramEventSink {
+    api: BotApi,
+    cache: MemoryCache,
+    outbound: OutboundQueue,
+}
+
+impl EventSink for TelegramEventSink {
+    async fn deliver(&mut self, event: Event) {
+        let (api, cache) = (self.api.clone(), self.cache.clone());
+        let lane = Lane::of(&event);
+        self.outbound
+            .push(lane, async move { post_event(&api, &cache, event).await });
+    }
+}
+
+/// Post the event in the Telegram groups, from the outbound queue.
+async fn post_event(api: &BotApi, cache: &MemoryCache, event: Event) {
+    let settings = &config::SETTINGS;
+    let kind: &'static str = (&event).into();
+    cache.event_stream.publish(&event);
+    // Commands, monitoring and personal messages are still sent when announcements are muted.
+    if event.is_announcement() && cache.muted.load(Ordering::Relaxed) {
+        info!("Announcements are muted, skipping event.");
+        return;
+    };
+
+    let Some((chat_id, reply_to)) = destination(&event, &settings.telegram) else {
+        info!("No Telegram chat to post the {kind} event in.");
+        return;
+    };
+    if settings.features.dry_run {
+        info!("Dry run, would post in Telegram chat {chat_id}:\n{event}");
+        return;
+    };
+
+    for (i, text) in event.messages().into_iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(BATCH_THROTTLE).await;
+        };
+        let (result, attempts) = api
+            .send_message(chat_id, reply_to, &to_telegram_html(&text))
+            .await;
+        if result.is_err() {
+            error!("Dead letter: {kind} message to {chat_id} dropped after {attempts} attempt(s):\n{text}");
+        };
+        let message = PostedMessage {
+            at: Utc::now(),
+            event: kind.to_string(),
+            channel: ChatId(chat_id.to_string()),
+            ts: result.as_ref().ok().map(|id| MessageId(id.to_string())),
+            text,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        cache.audit_log.record(message);
+        if let Err(error) = result {
+            error!("{error}");
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{commands::Command, leaderboard::MemberId},
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn updates_are_parsed_from_the_bot_api_response() {
+        let body = r#"{"ok":true,"result":[
+            {"update_id":10,"message":{"message_id":7,"date":1701406800,"chat":{"id":-1001234567890,"type":"supergroup"},"from":{"id":42,"is_bot":false,"first_name":"Olive"},"text":"!board"}},
+            {"update_id":11,"edited_message":{"message_id":7,"date":1701406800,"chat":{"id":-1001234567890,"type":"supergroup"},"text":"!board 2022"}}
+        ]}"#;
+        let response = serde_json::from_str::<ApiResponse<Vec<Update>>>(body).unwrap();
+        let updates = response.result.unwrap();
+        assert_eq!(updates.len(), 2);
+        let message = updates[0].message.as_ref().unwrap();
+        assert_eq!(message.chat.id, -1001234567890);
+        assert_eq!(message.text.as_deref(), Some("!board"));
+        assert!(updates[1].message.is_none());
+
+        let error = r#"{"ok":false,"error_code":401,"description":"Unauthorized"}"#;
+        let response = serde_json::from_str::<ApiResponse<Vec<Update>>>(error).unwrap();
+        assert!(!response.ok);
+        assert_eq!(response.description.as_deref(), Some("Unauthorized"));
+        assert_eq!(
+            telegram_failure(response.error_code, response.parameters),
+            Failure::Permanent
+        );
+
+        let rate_limited = r#"{"ok":false,"error_code":429,"description":"Too Many Requests: retry after 7","parameters":{"retry_after":7}}"#;
+        let response = serde_json::from_str::<ApiResponse<Message>>(rate_limited).unwrap();
+        assert_eq!(
+            telegram_failure(response.error_code, response.parameters),
+            Failure::Transient(Some(Duration::from_secs(7)))
+        );
+    }
+
+    #[test]
+    fn events_are_posted_in_the_telegram_groups() {
+        let telegram = TelegramSettings {
+            bot_token: "123456789:token".to_string(),
+            chat_id: -100,
+            monitoring_chat_id: None,
+        };
+        let command = |reply| {
+            Event::CommandReceived(
+                ChatId("-200".to_string()),
+                MessageId("7".to_string()),
+                reply,
+                Command::Help,
+            )
+        };
+
+        assert_eq!(
+            destination(&command(Reply::Thread), &telegram),
+            Some((-200, Some(7)))
+        );
+        assert_eq!(
+            destination(&command(Reply::Channel), &telegram),
+            Some((-200, None))
+        );
+        assert_eq!(
+            destination(&Event::GlobalLeaderboardUpdateMessage(1, 0), &telegram),
+            Some((-100, None))
+        );
+        // no monitoring group configured
+        assert_eq!(
+            destination(&Event::PrivateLeaderboardUpdated, &telegram),
+            None
+        );
+    }
+
+    #[test]
+    fn slack_messages_are_converted_to_telegram_html() {
+        names::register_slack_users(HashMap::from([(
+            MemberId(3101),
+            SlackUserId("U3101".to_string()),
+        )]));
+        names::register(&private_leaderboard(
+            2000,
+            &[MemberFixture::new(3101, "Dancer").with_part1(1, 600)],
+        ));
+
+        assert_eq!(
+            to_telegram_html("🚨 *Spoilers Ahead* :rotating_light: for <@U3101> & <@U0UNKNOWN>"),
+            "🚨 <b>Spoilers Ahead</b> 🚨 for Dancer &amp; @U0UNKNOWN"
+        );
+        assert_eq!(
+            to_telegram_html("```\n 1) <Dasher>  *42*\n```\nsee `!board` in <#C0GENERAL|general>"),
+            "<pre> 1) &lt;Dasher&gt;  *42*</pre>\nsee <code>!board</code> in #general"
+        );
+        assert_eq!(
+            to_telegram_html("_day 3_ of <https://adventofcode.com/2023/day/3|AoC> by snake_case_elf :party-parrot:"),
+            "<i>day 3</i> of <a href=\"https://adventofcode.com/2023/day/3\">AoC</a> by snake_case_elf :party-parrot:"
+        );
+    }
+}
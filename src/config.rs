@@ -1,62 +1,417 @@
 use crate::cli::Cli;
+use crate::client::quiet::QuietHours;
+use crate::core::{
+    commands::{CommandSyntax, COMMANDS, DEFAULT_PREFIX},
+    faq::FaqEntry,
+    leaderboard::{Entry, MemberId, AOC_PUZZLE_UTC_STARTING_HOUR},
+    standings::JerseyPoints,
+    templates::{self, Language},
+    theme::Theme,
+};
+use crate::error::{BotError, BotResult};
+use chrono::{Datelike, Utc};
 use clap::Parser;
+use croner::Cron;
 use figment::{
     providers::{Env, Format, Serialized, Yaml},
     Figment,
 };
-use once_cell::sync::Lazy;
+use itertools::Itertools;
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+use reqwest::Url;
 use serde::Deserialize;
-use std::path::Path;
+use slack_morphism::SlackUserId;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+    time::Duration,
+};
 use tracing::Level;
 
 const TRACE_LEVELS: [&str; 5] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
 
-pub static SETTINGS: Lazy<Settings> = Lazy::new(Settings::new);
+static CHANNEL_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[CG][A-Z0-9]{8,}$").unwrap());
+static USER_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[UW][A-Z0-9]{8,}$").unwrap());
+static BOT_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^B[A-Z0-9]{8,}$").unwrap());
+static TELEGRAM_TOKEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9]+:[A-Za-z0-9_-]{30,}$").unwrap());
+static COMMAND_ALIAS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\w+$").unwrap());
+static EMAIL_ADDRESS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[^@\s<>]+@[^@\s<>]+\.[^@\s<>]+$").unwrap());
+
+// (previous name, current name) of the settings renamed when they were grouped in sections. The
+// previous names are still accepted, with a warning, so the existing deployments keep working.
+const RENAMED_SETTINGS: [(&str, &str); 16] = [
+    ("slack_token", "slack.token"),
+    ("slack_app_token", "slack.app_token"),
+    ("slack_default_channel", "slack.default_channel"),
+    ("slack_monitoring_channel", "slack.monitoring_channel"),
+    ("slack_bots_authorized_ids", "slack.bots_authorized_ids"),
+    ("aoc_slack_users", "slack.users"),
+    ("aoc_base_url", "aoc.base_url"),
+    ("aoc_api_timeout_sec", "aoc.api_timeout_sec"),
+    ("aoc_private_leaderboard_id", "aoc.private_leaderboard_id"),
+    ("aoc_session_cookie", "aoc.session_cookie"),
+    ("all_years", "aoc.all_years"),
+    (
+        "global_leaderboard_polling_interval_sec",
+        "scheduling.global_leaderboard_polling_interval_sec",
+    ),
+    ("summary_events_only", "features.summary_events_only"),
+    ("year_in_review", "features.year_in_review"),
+    ("http_server_port", "server.http_port"),
+    ("admin_api_token", "server.admin_api_token"),
+];
+
+static LOADED: OnceCell<Settings> = OnceCell::new();
+
+// Loaded by `main` before anything else happens, an invalid configuration stopping the bot.
+pub static SETTINGS: Lazy<&'static Settings> =
+    Lazy::new(|| LOADED.get().expect("settings are loaded at startup"));
+
+/// Load and validate the settings, once for the whole process.
+pub fn load() -> BotResult<&'static Settings> {
+    LOADED.get_or_try_init(Settings::new)
+}
 
 // Settings are a singleton generated at runtime. All settings may be
-// configured via environment variables. Example:
-// SLACK_TOKEN="xxx" would set slack_token to the xxx value.
-// Some settings are derived from other settings
+// configured via environment variables, sections being separated by a
+// double underscore. Example:
+// SLACK__TOKEN="xxx" would set slack.token to the xxx value.
 #[derive(Deserialize, Debug)]
 pub struct Settings {
     #[serde(default = "default_trace_level")]
     trace_level: String,
-    pub slack_token: String,
-    pub slack_app_token: String,
-    pub slack_default_channel: String,
+    // Directory where the bot state (reminders, ...) is persisted. Nothing is persisted if not set.
+    pub storage_dir: Option<String>,
+    // Directory of the templates overriding the embedded ones (reloaded when modified).
+    pub templates_dir: Option<String>,
+    // Language of the messages (`en`, `fr` or `de`).
+    #[serde(default)]
+    pub language: Language,
+    // Messaging platform the bot is connected to (`slack` or `telegram`).
+    #[serde(default)]
+    pub messaging_backend: MessagingBackend,
+    // Only required with the Slack backend.
+    #[serde(default)]
+    pub slack: SlackSettings,
+    // Only required with the Telegram backend.
+    #[serde(default)]
+    pub telegram: TelegramSettings,
+    // Mailing list receiving the daily summary and the weekly recap as HTML emails, if set.
+    pub email: Option<EmailSettings>,
+    pub aoc: AocSettings,
+    #[serde(default)]
+    pub scheduling: SchedulingSettings,
+    #[serde(default)]
+    pub features: FeaturesSettings,
+    #[serde(default)]
+    pub server: ServerSettings,
+    #[serde(default)]
+    pub commands: CommandsSettings,
+    // Points curves of the green and combative jerseys, and penalty of the yellow jersey.
+    #[serde(default)]
+    pub jerseys: JerseyPoints,
+    // Questions/answers available through the `!faq` command (prize rules, logistics, ...)
+    #[serde(default)]
+    pub faq: Vec<FaqEntry>,
+    // Warnings about the deprecated settings names found, logged once the logs are set up.
+    #[serde(skip)]
+    deprecations: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagingBackend {
+    #[default]
+    Slack,
+    Telegram,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct SlackSettings {
+    // Bot token. Not needed if the bot is installed in the workspace through OAuth.
+    pub token: Option<String>,
+    pub app_token: String,
+    pub default_channel: String,
     // Channel to reveive heartbeats and monitoring events
-    pub slack_monitoring_channel: Option<String>,
+    pub monitoring_channel: Option<String>,
+    // Channels where the bot keeps a pinned message with the current board, edited after each
+    // refresh of the private leaderboard.
+    pub scoreboard_channels: Option<Vec<String>>,
     // Will not treat any message from bots users, unless specifically authorized.
     // If using matterbridge you might want to leverage this option.
-    pub slack_bots_authorized_ids: Option<Vec<String>>,
-    #[serde(default = "default_global_leaderboard_polling_interval_sec")]
-    pub global_leaderboard_polling_interval_sec: u64,
+    pub bots_authorized_ids: Option<Vec<String>>,
+    // AoC member id => Slack user id, so announcements can mention the Slack users.
+    // Members can also link themselves with the `!iam` command.
+    users: Option<HashMap<String, String>>,
+    // Slack users allowed to run the admin commands (e.g. `!tdf absence`).
+    pub admins: Option<Vec<String>>,
+    // Slack app credentials, to install the bot in a workspace through OAuth instead of
+    // configuring its bot token.
+    pub oauth: Option<SlackOAuthSettings>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct TelegramSettings {
+    // Bot token, as given by @BotFather.
+    pub bot_token: String,
+    // Group receiving the announcements, e.g. -1001234567890. Commands are only answered in the
+    // announcements and monitoring groups.
+    pub chat_id: i64,
+    // Group to receive heartbeats and monitoring events
+    pub monitoring_chat_id: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EmailSettings {
+    pub smtp_host: String,
+    // defaults to the port of the encryption (25, 587 or 465)
+    pub smtp_port: Option<u16>,
+    #[serde(default)]
+    pub smtp_tls: SmtpTls,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+impl EmailSettings {
+    pub fn port(&self) -> u16 {
+        self.smtp_port.unwrap_or(match self.smtp_tls {
+            SmtpTls::None => 25,
+            SmtpTls::StartTls => 587,
+            SmtpTls::Tls => 465,
+        })
+    }
+}
+
+/// Encryption of the connection to the SMTP server.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    // plain connection, only for a relay on a trusted network (e.g. a postfix sidecar)
+    None,
+    // plain connection upgraded with STARTTLS, which the server must support
+    #[default]
+    StartTls,
+    // TLS from the start of the connection (SMTPS)
+    Tls,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SlackOAuthSettings {
+    pub client_id: String,
+    pub client_secret: String,
+    // Public URL of the `/slack/oauth/callback` endpoint, as registered in the Slack app.
+    pub redirect_url: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AocSettings {
     #[serde(default = "default_aoc_base_url")]
-    pub aoc_base_url: String,
+    pub base_url: String,
     #[serde(default = "default_aoc_api_timeout_sec")]
-    pub aoc_api_timeout_sec: u64,
-    pub aoc_private_leaderboard_id: u64,
-    pub aoc_session_cookie: String,
+    pub api_timeout_sec: u64,
+    pub private_leaderboard_id: u64,
+    pub session_cookie: String,
     // Whether to load the private leaderboard for all the previous AOC events
-    #[serde(default = "default_all_years")]
+    #[serde(default)]
     pub all_years: bool,
+    // Numeric ids of the members left out of the standings (e.g. bots, duplicate accounts),
+    // still listed at the bottom of the board
+    #[serde(default)]
+    pub excluded_members: Vec<u64>,
+    // Numeric ids of the members never named in the messages (e.g. who asked for privacy), their
+    // stars still counting in the standings
+    #[serde(default)]
+    pub anonymous_members: Vec<u64>,
+    // Code to join the private leaderboard, announced with the next event once its page is live
+    pub join_code: Option<String>,
+}
+
+// Cron schedules (with seconds) of the recurring jobs.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SchedulingSettings {
+    pub global_leaderboard_polling_interval_sec: u64,
+    pub daily_solutions_thread: String,
+    pub global_leaderboard: String,
+    pub daily_challenge: String,
+    pub daily_summary: String,
+    pub reminders: String,
+    pub solutions_thread_summary: String,
+    pub year_in_review: String,
+    pub weekly_recap: String,
+    pub awards_ceremony: String,
+    pub heartbeat: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct FeaturesSettings {
     // Whether to send only summary events.
     // By default every users stars collection is sent as an update. This can be
     // very verbose for very large leaderboards where lots of activity happens.
-    #[serde(default = "default_summary_events_only")]
     pub summary_events_only: bool,
+    // Whether to send a personal year in review to the members linked to a Slack user, once
+    // the event is over. Members can opt out with the `!review off` command.
+    pub year_in_review: bool,
+    // Whether to DM their own results of the day, after the daily summary, to the members linked
+    // to a Slack user who opted in with the `!mysummary on` command.
+    pub personal_summary: bool,
+    // Whether to post a recap of the week (stars collected, climbs, jerseys, fastest delta) on
+    // the Sundays of the event.
+    pub weekly_recap: bool,
+    // Whether to post an awards ceremony (jerseys, most stars, best delta, biggest comeback, ...)
+    // once the event is over.
+    pub awards_ceremony: bool,
+    // Whether to announce the winner of the day (first member to complete part 2) as soon as the
+    // private leaderboard is updated, instead of waiting for the daily summary.
+    pub stage_winner: bool,
+    // Whether to post a status line (cache age, members, last scrape, jobs) in the monitoring
+    // channel, so that a silent monitoring channel means the bot is down.
+    pub heartbeat: bool,
+    // Window of the day (local time of the bot, e.g. `23:00-07:00`) during which the
+    // announcements are held back, and posted as a single digest once it ends.
+    pub quiet_hours: Option<String>,
+    // Whether the daily summary and the board are posted as text or as images. Monospace text
+    // tables are not rendered well by some Slack clients (e.g. mobile).
+    pub output_format: OutputFormat,
+    // Theme pack (yaml file) overriding the emojis used in the messages, e.g. to use the
+    // custom emojis of the workspace.
+    pub theme_file: Option<String>,
+    // Whether to post a countdown (1 hour, 10 minutes and 1 minute) before each puzzle unlock.
+    pub countdown: bool,
+    // Minutes after each puzzle unlock at which a poll asks the members how hard the day felt,
+    // the community rating being part of the daily summary (Slack only). No poll if not set.
+    pub difficulty_poll_delay_min: Option<u32>,
+    // Minutes during which the highlights of the new entries are held back, to be merged into a
+    // single announcement instead of one message per scrape cycle. Announced right away if not set.
+    pub highlights_debounce_min: Option<u32>,
+    // Whether to scan, at startup, the past global leaderboards of the cached years (all the
+    // events in `aoc.all_years` mode) for the all-time appearances listed by `!heroes`.
+    pub heroes_history: bool,
+    // Whether to only log the messages (and copy them to the monitoring channel, if any) instead
+    // of posting them, to safely try a configuration.
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Image,
+}
+
+/// Where the response to a command is posted.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseMode {
+    // in the channel, as a new message
+    Channel,
+    // in the thread of the command message
+    #[default]
+    Thread,
+    // only visible to the user who sent the command
+    Ephemeral,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct ServerSettings {
+    // Port of the embedded HTTP server. The server is not started if not set.
+    pub http_port: Option<u16>,
+    // Bearer token required to call the admin endpoints. Admin API is disabled if not set.
+    pub admin_api_token: Option<String>,
+    // Bearer token required to post announcements through the bot API (e.g. from CI).
+    // Bot API is disabled if not set.
+    pub bot_api_token: Option<String>,
+    // Bearer token required to read the leaderboard data through the data API (e.g. to build
+    // dashboards). Data API is disabled if not set.
+    pub data_api_token: Option<String>,
+    // Maximum number of announcements accepted by the bot API over any one hour window.
+    pub bot_api_max_announcements_per_hour: usize,
+    // Age of the cached leaderboard above which `/healthz` reports the bot as unhealthy, while
+    // the private leaderboard is scraped.
+    pub healthcheck_max_age_sec: u64,
 }
 
-impl Default for Settings {
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct CommandsSettings {
+    // Written before the command names, e.g. `aoc ` (with a trailing space) for `aoc fast`.
+    pub prefix: String,
+    // Alternative names of the commands (without prefix), keyed by command name,
+    // e.g. `board: [lb]`.
+    pub aliases: HashMap<String, Vec<String>>,
+    // Maximum number of times a same command can be run by a user, and in a channel, over any
+    // one minute window. The commands run too often are answered with a cooldown message.
+    pub max_per_user_per_minute: usize,
+    pub max_per_channel_per_minute: usize,
+    // Where the responses are posted, keyed by command name (e.g. `board: ephemeral`). The
+    // responses of the other commands are posted in the thread of the command message.
+    pub responses: HashMap<String, ResponseMode>,
+}
+
+impl CommandsSettings {
+    /// Where the response to the command (aliases resolved) is posted.
+    pub fn response_mode(&self, command: &str) -> ResponseMode {
+        self.responses.get(command).copied().unwrap_or_default()
+    }
+}
+
+impl Default for CommandsSettings {
+    fn default() -> Self {
+        CommandsSettings {
+            prefix: DEFAULT_PREFIX.to_string(),
+            aliases: HashMap::new(),
+            max_per_user_per_minute: 3,
+            max_per_channel_per_minute: 6,
+            responses: HashMap::new(),
+        }
+    }
+}
+
+impl Default for SchedulingSettings {
     fn default() -> Self {
-        Self::new()
+        SchedulingSettings {
+            global_leaderboard_polling_interval_sec: 300,
+            daily_solutions_thread: "0 30 7 1-25 12 *".to_string(),
+            global_leaderboard: "0 0 5 1-25 12 *".to_string(),
+            daily_challenge: "1 0 5 1-25 12 *".to_string(),
+            daily_summary: "0 30 16 1-25 12 *".to_string(),
+            reminders: "0 0 21 1-25 12 *".to_string(),
+            solutions_thread_summary: "0 55 23 1-25 12 *".to_string(),
+            year_in_review: "0 0 17 26 12 *".to_string(),
+            weekly_recap: "0 0 18 2-31 12 SUN".to_string(),
+            awards_ceremony: "0 0 16 26 12 *".to_string(),
+            heartbeat: "0 0 */6 * * *".to_string(),
+        }
+    }
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        ServerSettings {
+            http_port: None,
+            admin_api_token: None,
+            bot_api_token: None,
+            data_api_token: None,
+            bot_api_max_announcements_per_hour: 10,
+            healthcheck_max_age_sec: 3600,
+        }
     }
 }
 
 impl Settings {
-    pub fn new() -> Self {
+    pub fn new() -> BotResult<Settings> {
         let local_settings_yaml_file = ".env.local.yaml";
-        let settings: Settings = match Path::new(local_settings_yaml_file).exists() {
+        let figment = match Path::new(local_settings_yaml_file).exists() {
             true => {
                 println!(
                     "\n######################################\n\
@@ -67,20 +422,725 @@ impl Settings {
                 );
                 Figment::new()
                     .merge(Yaml::file(local_settings_yaml_file))
-                    .merge(Env::raw())
-                    .merge(Serialized::defaults(Cli::parse()))
-                    .extract()
-                    .unwrap()
+                    .merge(Env::raw().split("__"))
+            }
+            false => Figment::new().merge(Env::raw().split("__")),
+        };
+
+        // CLI flags override the settings
+        let cli = Cli::parse();
+        let figment = match cli.all_years {
+            true => figment.merge(Serialized::default("aoc.all_years", true)),
+            false => figment,
+        };
+        let figment = match cli.dry_run {
+            true => figment.merge(Serialized::default("features.dry_run", true)),
+            false => figment,
+        };
+        Settings::from_figment(figment)
+    }
+
+    /// Extract and validate the settings, every problem found being reported in the error.
+    pub fn from_figment(figment: Figment) -> BotResult<Settings> {
+        let (figment, deprecations) = with_renamed_settings(figment);
+        let mut settings: Settings = figment.extract()?;
+        settings.deprecations = deprecations;
+        let problems = settings.validate();
+        match problems.is_empty() {
+            true => Ok(settings),
+            false => Err(BotError::Config(format!(
+                "invalid settings:\n  - {}",
+                problems.join("\n  - ")
+            ))),
+        }
+    }
+
+    pub fn deprecations(&self) -> &[String] {
+        &self.deprecations
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if !TRACE_LEVELS.contains(&self.trace_level.as_str()) {
+            problems.push(format!(
+                "trace_level: '{}' is not one of {}",
+                self.trace_level,
+                TRACE_LEVELS.join(", ")
+            ));
+        }
+
+        match self.messaging_backend {
+            MessagingBackend::Slack => problems.extend(self.validate_slack()),
+            MessagingBackend::Telegram => problems.extend(self.validate_telegram()),
+        }
+
+        if let Some(email) = &self.email {
+            if email.smtp_host.is_empty() {
+                problems.push("email.smtp_host: must not be empty".to_string());
+            }
+            if email.to.is_empty() {
+                problems.push("email.to: must not be empty".to_string());
+            }
+            if email.smtp_username.is_some() != email.smtp_password.is_some() {
+                problems.push(
+                    "email.smtp_username: must be set along with email.smtp_password".to_string(),
+                );
+            }
+            for (name, address) in std::iter::once(("email.from", &email.from))
+                .chain(email.to.iter().map(|address| ("email.to", address)))
+            {
+                if !EMAIL_ADDRESS_REGEX.is_match(address) {
+                    problems.push(format!("{name}: '{address}' is not a valid email address"));
+                }
+            }
+        }
+
+        match Url::parse(&self.aoc.base_url) {
+            Ok(url) if ["http", "https"].contains(&url.scheme()) => (),
+            Ok(_) => problems.push(format!(
+                "aoc.base_url: '{}' is not an http(s) url",
+                self.aoc.base_url
+            )),
+            Err(e) => problems.push(format!("aoc.base_url: '{}' {e}", self.aoc.base_url)),
+        }
+        if self.aoc.api_timeout_sec == 0 {
+            problems.push("aoc.api_timeout_sec: must be greater than 0".to_string());
+        }
+        if self
+            .aoc
+            .join_code
+            .as_ref()
+            .is_some_and(|code| code.trim().is_empty())
+        {
+            problems.push("aoc.join_code: must not be empty".to_string());
+        }
+
+        let scheduling = &self.scheduling;
+        if scheduling.global_leaderboard_polling_interval_sec == 0 {
+            problems.push(
+                "scheduling.global_leaderboard_polling_interval_sec: must be greater than 0"
+                    .to_string(),
+            );
+        }
+        for (name, schedule) in [
+            ("daily_solutions_thread", &scheduling.daily_solutions_thread),
+            ("global_leaderboard", &scheduling.global_leaderboard),
+            ("daily_challenge", &scheduling.daily_challenge),
+            ("daily_summary", &scheduling.daily_summary),
+            ("reminders", &scheduling.reminders),
+            (
+                "solutions_thread_summary",
+                &scheduling.solutions_thread_summary,
+            ),
+            ("year_in_review", &scheduling.year_in_review),
+            ("weekly_recap", &scheduling.weekly_recap),
+            ("awards_ceremony", &scheduling.awards_ceremony),
+            ("heartbeat", &scheduling.heartbeat),
+        ] {
+            // Same parsing as the scheduler, so a schedule accepted here is accepted there.
+            if let Err(e) = Cron::new(schedule)
+                .with_seconds_required()
+                .with_dom_and_dow()
+                .parse()
+            {
+                problems.push(format!("scheduling.{name}: '{schedule}' {e}"));
             }
-            false => Figment::new().merge(Env::raw()).extract().unwrap(),
+        }
+
+        let monitoring = match self.messaging_backend {
+            MessagingBackend::Slack => (
+                "slack.monitoring_channel",
+                self.slack.monitoring_channel.is_some(),
+            ),
+            MessagingBackend::Telegram => (
+                "telegram.monitoring_chat_id",
+                self.telegram.monitoring_chat_id.is_some(),
+            ),
         };
+        if self.features.heartbeat && !monitoring.1 {
+            problems.push(format!("features.heartbeat: requires {}", monitoring.0));
+        }
+        if let Some(Err(e)) = self.features.quiet_hours.as_deref().map(QuietHours::parse) {
+            problems.push(format!("features.quiet_hours: {e}"));
+        }
+        if let Some(delay) = self.features.difficulty_poll_delay_min {
+            // The poll is posted the day of the puzzle (UTC), before the next one unlocks.
+            let max_delay = (24 - AOC_PUZZLE_UTC_STARTING_HOUR) * 60;
+            if delay >= max_delay {
+                problems.push(format!(
+                    "features.difficulty_poll_delay_min: must be less than {max_delay}"
+                ));
+            }
+            if self.messaging_backend != MessagingBackend::Slack {
+                problems.push(
+                    "features.difficulty_poll_delay_min: requires the Slack messaging backend"
+                        .to_string(),
+                );
+            }
+            // The votes are tallied in the daily summary of the day, which must come after the poll.
+            let summary = Cron::new(&scheduling.daily_summary)
+                .with_seconds_required()
+                .with_dom_and_dow()
+                .parse();
+            if let (Ok(summary), Ok(unlock)) = (summary, Entry::puzzle_unlock(Utc::now().year(), 1))
+            {
+                let poll = unlock + chrono::Duration::minutes(delay.into());
+                if summary
+                    .find_next_occurrence(&unlock, true)
+                    .is_ok_and(|next| next <= poll)
+                {
+                    problems.push(
+                        "features.difficulty_poll_delay_min: the poll must be posted before scheduling.daily_summary"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+        if self.features.highlights_debounce_min == Some(0) {
+            problems.push("features.highlights_debounce_min: must be greater than 0".to_string());
+        }
+        if let Err(e) = self.get_theme() {
+            problems.push(format!("features.theme_file: {e}"));
+        }
+        if let Some(dir) = &self.templates_dir {
+            templates::check_dir(Path::new(dir))
+                .into_iter()
+                .for_each(|problem| problems.push(format!("templates_dir: {problem}")));
+        }
 
-        settings
+        if self.server.bot_api_token.is_some()
+            && self.server.bot_api_max_announcements_per_hour == 0
+        {
+            problems.push(
+                "server.bot_api_max_announcements_per_hour: must be greater than 0".to_string(),
+            );
+        }
+        if self.server.healthcheck_max_age_sec == 0 {
+            problems.push("server.healthcheck_max_age_sec: must be greater than 0".to_string());
+        }
+
+        if self.commands.prefix.trim().is_empty() {
+            problems.push("commands.prefix: must not be empty".to_string());
+        }
+        if self.commands.max_per_user_per_minute == 0 {
+            problems.push("commands.max_per_user_per_minute: must be greater than 0".to_string());
+        }
+        if self.commands.max_per_channel_per_minute == 0 {
+            problems
+                .push("commands.max_per_channel_per_minute: must be greater than 0".to_string());
+        }
+        let mut names = COMMANDS
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<HashSet<_>>();
+        for (command, aliases) in &self.commands.aliases {
+            if !COMMANDS.contains(&command.as_str()) {
+                problems.push(format!("commands.aliases: '{command}' is not a command"));
+            }
+            for alias in aliases {
+                if !COMMAND_ALIAS_REGEX.is_match(alias) {
+                    problems.push(format!("commands.aliases: '{alias}' must be a single word"));
+                } else if !names.insert(alias.to_string()) {
+                    problems.push(format!(
+                        "commands.aliases: '{alias}' is already a command or an alias"
+                    ));
+                }
+            }
+        }
+
+        for command in self.commands.responses.keys().sorted() {
+            if !COMMANDS.contains(&command.as_str()) {
+                problems.push(format!("commands.responses: '{command}' is not a command"));
+            }
+        }
+
+        for (name, problem) in self.jerseys.validate() {
+            problems.push(format!("jerseys.{name}: {problem}"));
+        }
+
+        let mut topics = HashSet::new();
+        for entry in &self.faq {
+            let topic = entry.topic.trim().to_lowercase();
+            if topic.is_empty() {
+                problems.push("faq: every entry must have a topic".to_string());
+            } else if !topics.insert(topic) {
+                problems.push(format!("faq: '{}' topic is defined twice", entry.topic));
+            }
+            if entry.answer.trim().is_empty() {
+                problems.push(format!("faq: '{}' has no answer", entry.topic));
+            }
+        }
+
+        problems
+    }
+
+    fn validate_slack(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        for (name, token, prefix) in [
+            ("slack.token", self.slack.token.as_ref(), "xoxb-"),
+            ("slack.app_token", Some(&self.slack.app_token), "xapp-"),
+        ] {
+            if token.is_some_and(|t| !t.starts_with(prefix)) {
+                problems.push(format!("{name}: expected a token starting with '{prefix}'"));
+            }
+        }
+        match &self.slack.oauth {
+            None if self.slack.token.is_none() => problems.push(
+                "slack.token: required, unless the bot is installed through slack.oauth"
+                    .to_string(),
+            ),
+            Some(oauth) => {
+                if let Err(e) = Url::parse(&oauth.redirect_url) {
+                    problems.push(format!(
+                        "slack.oauth.redirect_url: '{}' {e}",
+                        oauth.redirect_url
+                    ));
+                }
+                // The callback is served by the embedded HTTP server, and the installation
+                // tokens must survive a restart.
+                if self.server.http_port.is_none() {
+                    problems.push("slack.oauth: requires server.http_port".to_string());
+                }
+                if self.storage_dir.is_none() {
+                    problems.push("slack.oauth: requires storage_dir".to_string());
+                }
+                // Only the admins can start an installation.
+                if self.server.admin_api_token.is_none() {
+                    problems.push("slack.oauth: requires server.admin_api_token".to_string());
+                }
+            }
+            None => (),
+        }
+        for (name, channel) in [
+            ("slack.default_channel", Some(&self.slack.default_channel)),
+            (
+                "slack.monitoring_channel",
+                self.slack.monitoring_channel.as_ref(),
+            ),
+        ] {
+            if let Some(channel) = channel.filter(|c| !CHANNEL_ID_REGEX.is_match(c)) {
+                problems.push(format!("{name}: '{channel}' is not a valid channel id"));
+            }
+        }
+        for channel in self.slack.scoreboard_channels.iter().flatten() {
+            if !CHANNEL_ID_REGEX.is_match(channel) {
+                problems.push(format!(
+                    "slack.scoreboard_channels: '{channel}' is not a valid channel id"
+                ));
+            }
+        }
+        for id in self.slack.bots_authorized_ids.iter().flatten() {
+            if !BOT_ID_REGEX.is_match(id) {
+                problems.push(format!(
+                    "slack.bots_authorized_ids: '{id}' is not a valid bot id"
+                ));
+            }
+        }
+        for (member, user) in self.slack.users.iter().flatten() {
+            if member.parse::<u64>().is_err() {
+                problems.push(format!(
+                    "slack.users: '{member}' is not a valid AoC member id"
+                ));
+            }
+            if !USER_ID_REGEX.is_match(user) {
+                problems.push(format!("slack.users: '{user}' is not a valid user id"));
+            }
+        }
+        for user in self.slack.admins.iter().flatten() {
+            if !USER_ID_REGEX.is_match(user) {
+                problems.push(format!("slack.admins: '{user}' is not a valid user id"));
+            }
+        }
+        problems
+    }
+
+    fn validate_telegram(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if !TELEGRAM_TOKEN_REGEX.is_match(&self.telegram.bot_token) {
+            problems.push(
+                "telegram.bot_token: expected a token like '123456789:AAE...', from @BotFather"
+                    .to_string(),
+            );
+        }
+        if self.telegram.chat_id == 0 {
+            problems.push("telegram.chat_id: required".to_string());
+        }
+        problems
     }
 
     pub fn get_trace_level(&self) -> Level {
         get_trace_level(&self.trace_level)
     }
+
+    pub fn get_command_syntax(&self) -> CommandSyntax {
+        CommandSyntax::new(&self.commands.prefix, &self.commands.aliases)
+    }
+
+    pub fn get_theme(&self) -> BotResult<Theme> {
+        match &self.features.theme_file {
+            Some(path) => Theme::from_file(path),
+            None => Ok(Theme::default()),
+        }
+    }
+
+    /// Quiet hours of the announcements, validated at startup.
+    pub fn quiet_hours(&self) -> Option<QuietHours> {
+        self.features
+            .quiet_hours
+            .as_deref()
+            .and_then(|window| QuietHours::parse(window).ok())
+    }
+
+    /// Cron schedule of the daily difficulty poll, if enabled, e.g. `0 0 7 1-25 12 *` for a poll
+    /// 2 hours after the unlock.
+    pub fn difficulty_poll_schedule(&self) -> Option<String> {
+        self.features.difficulty_poll_delay_min.map(|delay| {
+            let minute_of_day = AOC_PUZZLE_UTC_STARTING_HOUR * 60 + delay;
+            format!("0 {} {} 1-25 12 *", minute_of_day % 60, minute_of_day / 60)
+        })
+    }
+
+    /// Window during which the highlights of the new entries are merged, if enabled.
+    pub fn highlights_debounce(&self) -> Option<Duration> {
+        self.features
+            .highlights_debounce_min
+            .map(|minutes| Duration::from_secs(u64::from(minutes) * 60))
+    }
+
+    /// Whether the Slack user is allowed to run the admin commands.
+    pub fn is_slack_admin(&self, user: &SlackUserId) -> bool {
+        self.slack
+            .admins
+            .iter()
+            .flatten()
+            .any(|admin| *admin == user.0)
+    }
+
+    pub fn get_aoc_slack_users(&self) -> HashMap<MemberId, SlackUserId> {
+        // ids are validated at startup
+        self.slack
+            .users
+            .iter()
+            .flatten()
+            .filter_map(|(member, user)| {
+                let id = member.parse::<u64>().ok()?;
+                Some((MemberId(id), SlackUserId(user.to_string())))
+            })
+            .collect()
+    }
+}
+
+/// Effective configuration, secrets redacted, for the startup diagnostics.
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (slack, telegram, aoc, scheduling, features, server) = (
+            &self.slack,
+            &self.telegram,
+            &self.aoc,
+            &self.scheduling,
+            &self.features,
+            &self.server,
+        );
+        writeln!(f, "trace_level: {}", self.trace_level)?;
+        writeln!(f, "storage_dir: {}", optional(&self.storage_dir))?;
+        writeln!(f, "templates_dir: {}", optional(&self.templates_dir))?;
+        writeln!(
+            f,
+            "language: {}",
+            format!("{:?}", self.language).to_lowercase()
+        )?;
+        writeln!(
+            f,
+            "messaging_backend: {}",
+            format!("{:?}", self.messaging_backend).to_lowercase()
+        )?;
+        writeln!(f, "slack:")?;
+        writeln!(
+            f,
+            "  token: {}",
+            optional(&slack.token.as_deref().map(redact))
+        )?;
+        writeln!(f, "  app_token: {}", redact(&slack.app_token))?;
+        writeln!(f, "  default_channel: {}", slack.default_channel)?;
+        writeln!(
+            f,
+            "  monitoring_channel: {}",
+            optional(&slack.monitoring_channel)
+        )?;
+        writeln!(
+            f,
+            "  scoreboard_channels: {}",
+            optional(&slack.scoreboard_channels.as_ref().map(|ids| ids.join(", ")))
+        )?;
+        match &slack.oauth {
+            Some(oauth) => {
+                writeln!(f, "  oauth:")?;
+                writeln!(f, "    client_id: {}", oauth.client_id)?;
+                writeln!(f, "    client_secret: {}", redact(&oauth.client_secret))?;
+                writeln!(f, "    redirect_url: {}", oauth.redirect_url)?;
+            }
+            None => writeln!(f, "  oauth: None")?,
+        };
+        writeln!(
+            f,
+            "  bots_authorized_ids: {}",
+            optional(&slack.bots_authorized_ids.as_ref().map(|ids| ids.join(", ")))
+        )?;
+        writeln!(
+            f,
+            "  users: {} linked",
+            slack.users.as_ref().map_or(0, |users| users.len())
+        )?;
+        writeln!(
+            f,
+            "  admins: {}",
+            optional(&slack.admins.as_ref().map(|admins| admins.join(", ")))
+        )?;
+        writeln!(f, "telegram:")?;
+        writeln!(f, "  bot_token: {}", redact(&telegram.bot_token))?;
+        writeln!(f, "  chat_id: {}", telegram.chat_id)?;
+        writeln!(
+            f,
+            "  monitoring_chat_id: {}",
+            optional(&telegram.monitoring_chat_id.map(|id| id.to_string()))
+        )?;
+        match &self.email {
+            Some(email) => {
+                writeln!(f, "email:")?;
+                writeln!(f, "  smtp: {}:{}", email.smtp_host, email.port())?;
+                writeln!(f, "  smtp_tls: {:?}", email.smtp_tls)?;
+                writeln!(f, "  smtp_username: {}", optional(&email.smtp_username))?;
+                writeln!(
+                    f,
+                    "  smtp_password: {}",
+                    optional(&email.smtp_password.as_deref().map(redact))
+                )?;
+                writeln!(f, "  from: {}", email.from)?;
+                writeln!(f, "  to: {} address(es)", email.to.len())?;
+            }
+            None => writeln!(f, "email: None")?,
+        };
+        writeln!(f, "aoc:")?;
+        writeln!(f, "  base_url: {}", aoc.base_url)?;
+        writeln!(f, "  api_timeout_sec: {}", aoc.api_timeout_sec)?;
+        writeln!(
+            f,
+            "  private_leaderboard_id: {}",
+            aoc.private_leaderboard_id
+        )?;
+        writeln!(f, "  session_cookie: {}", redact(&aoc.session_cookie))?;
+        writeln!(f, "  all_years: {}", aoc.all_years)?;
+        writeln!(
+            f,
+            "  excluded_members: {}",
+            aoc.excluded_members.iter().join(", ")
+        )?;
+        writeln!(
+            f,
+            "  anonymous_members: {}",
+            aoc.anonymous_members.iter().join(", ")
+        )?;
+        writeln!(f, "  join_code: {}", optional(&aoc.join_code))?;
+        writeln!(f, "scheduling:")?;
+        writeln!(
+            f,
+            "  global_leaderboard_polling_interval_sec: {}",
+            scheduling.global_leaderboard_polling_interval_sec
+        )?;
+        writeln!(
+            f,
+            "  daily_solutions_thread: {}",
+            scheduling.daily_solutions_thread
+        )?;
+        writeln!(f, "  global_leaderboard: {}", scheduling.global_leaderboard)?;
+        writeln!(f, "  daily_challenge: {}", scheduling.daily_challenge)?;
+        writeln!(f, "  daily_summary: {}", scheduling.daily_summary)?;
+        writeln!(f, "  reminders: {}", scheduling.reminders)?;
+        writeln!(
+            f,
+            "  solutions_thread_summary: {}",
+            scheduling.solutions_thread_summary
+        )?;
+        writeln!(f, "  year_in_review: {}", scheduling.year_in_review)?;
+        writeln!(f, "  weekly_recap: {}", scheduling.weekly_recap)?;
+        writeln!(f, "  awards_ceremony: {}", scheduling.awards_ceremony)?;
+        writeln!(f, "  heartbeat: {}", scheduling.heartbeat)?;
+        writeln!(f, "features:")?;
+        writeln!(f, "  summary_events_only: {}", features.summary_events_only)?;
+        writeln!(f, "  year_in_review: {}", features.year_in_review)?;
+        writeln!(f, "  personal_summary: {}", features.personal_summary)?;
+        writeln!(f, "  weekly_recap: {}", features.weekly_recap)?;
+        writeln!(f, "  awards_ceremony: {}", features.awards_ceremony)?;
+        writeln!(f, "  stage_winner: {}", features.stage_winner)?;
+        writeln!(f, "  heartbeat: {}", features.heartbeat)?;
+        writeln!(f, "  quiet_hours: {}", optional(&features.quiet_hours))?;
+        writeln!(
+            f,
+            "  output_format: {}",
+            format!("{:?}", features.output_format).to_lowercase()
+        )?;
+        writeln!(f, "  theme_file: {}", optional(&features.theme_file))?;
+        writeln!(f, "  countdown: {}", features.countdown)?;
+        writeln!(
+            f,
+            "  difficulty_poll_delay_min: {}",
+            optional(&features.difficulty_poll_delay_min.map(|d| d.to_string()))
+        )?;
+        writeln!(
+            f,
+            "  highlights_debounce_min: {}",
+            optional(&features.highlights_debounce_min.map(|d| d.to_string()))
+        )?;
+        writeln!(f, "  heroes_history: {}", features.heroes_history)?;
+        writeln!(f, "  dry_run: {}", features.dry_run)?;
+        writeln!(f, "server:")?;
+        writeln!(
+            f,
+            "  http_port: {}",
+            optional(&server.http_port.map(|p| p.to_string()))
+        )?;
+        writeln!(
+            f,
+            "  admin_api_token: {}",
+            optional(&server.admin_api_token.as_deref().map(redact))
+        )?;
+        writeln!(
+            f,
+            "  bot_api_token: {}",
+            optional(&server.bot_api_token.as_deref().map(redact))
+        )?;
+        writeln!(
+            f,
+            "  data_api_token: {}",
+            optional(&server.data_api_token.as_deref().map(redact))
+        )?;
+        writeln!(
+            f,
+            "  bot_api_max_announcements_per_hour: {}",
+            server.bot_api_max_announcements_per_hour
+        )?;
+        writeln!(
+            f,
+            "  healthcheck_max_age_sec: {}",
+            server.healthcheck_max_age_sec
+        )?;
+        writeln!(f, "commands:")?;
+        writeln!(f, "  prefix: '{}'", self.commands.prefix)?;
+        writeln!(
+            f,
+            "  aliases: {}",
+            match self.commands.aliases.is_empty() {
+                true => "None".to_string(),
+                false => self
+                    .commands
+                    .aliases
+                    .iter()
+                    .sorted()
+                    .map(|(command, aliases)| format!("{command} ({})", aliases.join(", ")))
+                    .join(", "),
+            }
+        )?;
+        writeln!(
+            f,
+            "  max_per_user_per_minute: {}",
+            self.commands.max_per_user_per_minute
+        )?;
+        writeln!(
+            f,
+            "  max_per_channel_per_minute: {}",
+            self.commands.max_per_channel_per_minute
+        )?;
+        writeln!(
+            f,
+            "  responses: {}",
+            match self.commands.responses.is_empty() {
+                true => "None".to_string(),
+                false => self
+                    .commands
+                    .responses
+                    .iter()
+                    .sorted_by_key(|(command, _mode)| *command)
+                    .map(|(command, mode)| format!("{command} ({mode:?})").to_lowercase())
+                    .join(", "),
+            }
+        )?;
+        writeln!(f, "jerseys:")?;
+        writeln!(
+            f,
+            "  green_points: {}",
+            self.jerseys.green_points.iter().join(", ")
+        )?;
+        writeln!(
+            f,
+            "  combative_max_points: {}",
+            self.jerseys.combative_max_points
+        )?;
+        writeln!(
+            f,
+            "  combative_decay_rate: {}",
+            self.jerseys.combative_decay_rate
+        )?;
+        writeln!(f, "  yellow_penalty: {}", self.jerseys.yellow_penalty)?;
+        writeln!(
+            f,
+            "  custom: {}",
+            match self.jerseys.custom.is_empty() {
+                true => "None".to_string(),
+                false => self
+                    .jerseys
+                    .custom
+                    .iter()
+                    .map(|j| format!("{} ({} {})", j.name, j.metric, j.aggregation))
+                    .join(", "),
+            }
+        )?;
+        write!(
+            f,
+            "faq: {}",
+            match self.faq.is_empty() {
+                true => "None".to_string(),
+                false => self
+                    .faq
+                    .iter()
+                    .map(|e| e.topic.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            }
+        )
+    }
+}
+
+// Values set under a previous name are used for the current one, unless it is set as well.
+fn with_renamed_settings(figment: Figment) -> (Figment, Vec<String>) {
+    let mut figment = figment;
+    let mut deprecations = vec![];
+    for (previous, current) in RENAMED_SETTINGS {
+        if let Ok(value) = figment.find_value(previous) {
+            deprecations.push(format!(
+                "setting `{previous}` ({}) is deprecated, use `{current}` ({}) instead",
+                previous.to_uppercase(),
+                current.to_uppercase().replace('.', "__")
+            ));
+            figment = figment.join(Serialized::default(current, value));
+        }
+    }
+    (figment, deprecations)
+}
+
+// Only the token type prefix (e.g. `xoxb-`) is kept
+fn redact(secret: &str) -> String {
+    let prefix = match secret.split_once('-') {
+        Some((prefix, _)) if prefix.len() <= 4 => format!("{prefix}-"),
+        _ => String::new(),
+    };
+    format!("{prefix}[redacted]")
+}
+
+fn optional(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("None")
 }
 
 fn get_trace_level(level_str: &str) -> Level {
@@ -99,10 +1159,6 @@ fn default_trace_level() -> String {
     "INFO".to_string()
 }
 
-fn default_global_leaderboard_polling_interval_sec() -> u64 {
-    300
-}
-
 fn default_aoc_api_timeout_sec() -> u64 {
     5
 }
@@ -111,10 +1167,270 @@ fn default_aoc_base_url() -> String {
     "https://adventofcode.com".to_string()
 }
 
-fn default_all_years() -> bool {
-    false
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_SETTINGS: &str = "
+slack:
+  token: xoxb-0000-1111
+  app_token: xapp-1-2222
+  default_channel: C000X9X0XX
+  users:
+    \"123456\": U000XX0X0X0
+aoc:
+  private_leaderboard_id: 424242
+  session_cookie: 53616c7465645f5f
+";
+
+    fn settings(overrides: &str) -> BotResult<Settings> {
+        Settings::from_figment(
+            Figment::new()
+                .merge(Yaml::string(VALID_SETTINGS))
+                .merge(Yaml::string(overrides)),
+        )
+    }
+
+    #[test]
+    fn defaults_are_applied_to_missing_sections() {
+        let settings = settings("").unwrap();
+
+        assert_eq!(settings.aoc.base_url, "https://adventofcode.com");
+        assert!(settings.aoc.excluded_members.is_empty());
+        assert!(settings.aoc.anonymous_members.is_empty());
+        assert_eq!(settings.scheduling.daily_summary, "0 30 16 1-25 12 *");
+        assert!(!settings.features.year_in_review);
+        assert!(!settings.features.personal_summary);
+        assert!(!settings.features.weekly_recap);
+        assert!(!settings.features.awards_ceremony);
+        assert!(!settings.features.stage_winner);
+        assert!(!settings.features.heartbeat);
+        assert!(settings.quiet_hours().is_none());
+        assert!(!settings.features.countdown);
+        assert_eq!(settings.difficulty_poll_schedule(), None);
+        assert_eq!(settings.highlights_debounce(), None);
+        assert!(!settings.features.heroes_history);
+        assert!(!settings.features.dry_run);
+        assert_eq!(settings.features.output_format, OutputFormat::Text);
+        assert_eq!(settings.language, Language::En);
+        assert_eq!(settings.messaging_backend, MessagingBackend::Slack);
+        assert_eq!(settings.server.http_port, None);
+        assert_eq!(settings.commands.prefix, "!");
+        assert_eq!(
+            settings.commands.response_mode("board"),
+            ResponseMode::Thread
+        );
+        assert_eq!(settings.jerseys, JerseyPoints::default());
+        assert_eq!(
+            settings.get_aoc_slack_users().get(&MemberId(123456)),
+            Some(&SlackUserId("U000XX0X0X0".to_string()))
+        );
+    }
+
+    #[test]
+    fn every_invalid_setting_is_reported() {
+        let error = settings(
+            "
+slack:
+  default_channel: general
+  scoreboard_channels: [C0123456789, pinned]
+  bots_authorized_ids: [B011111111, bot]
+  admins: [U000XX0X0X0, santa]
+  oauth: { client_id: '1234.5678', client_secret: s3cr3t, redirect_url: ceo/callback }
+email:
+  smtp_host: localhost
+  smtp_username: ceo
+  from: ceo@example.com
+  to: [elves@example.com, santa]
+aoc:
+  base_url: adventofcode.com
+  join_code: ' '
+scheduling:
+  daily_summary: 0 30 16 1-25 12
+templates_dir: templates/missing
+features:
+  theme_file: themes/missing.yaml
+  heartbeat: true
+  quiet_hours: 23h-7h
+  difficulty_poll_delay_min: 1200
+  highlights_debounce_min: 0
+commands:
+  prefix: ' '
+  aliases: { board: [lb, fast], standings: [st], wins: [top 10] }
+  responses: { board: ephemeral, ranking: channel }
+jerseys:
+  green_points: [20, 30, 10]
+  combative_max_points: 0
+  combative_decay_rate: 1.5
+  yellow_penalty: 7 days
+  custom:
+    - { name: green, metric: delta, aggregation: points, points: [5, 10] }
+faq:
+  - { topic: prizes, question: What can I win?, answer: A mug. }
+  - { topic: Prizes, question: Really?, answer: Yes. }
+",
+        )
+        .unwrap_err()
+        .to_string();
+
+        for expected in [
+            "slack.default_channel: 'general'",
+            "slack.scoreboard_channels: 'pinned'",
+            "email.to: 'santa' is not a valid email address",
+            "email.smtp_username: must be set along with email.smtp_password",
+            "slack.bots_authorized_ids: 'bot'",
+            "slack.admins: 'santa'",
+            "slack.oauth.redirect_url: 'ceo/callback'",
+            "slack.oauth: requires server.http_port",
+            "slack.oauth: requires storage_dir",
+            "slack.oauth: requires server.admin_api_token",
+            "aoc.base_url: 'adventofcode.com'",
+            "aoc.join_code: must not be empty",
+            "scheduling.daily_summary: '0 30 16 1-25 12'",
+            "features.heartbeat: requires slack.monitoring_channel",
+            "features.quiet_hours: '23h-7h' is not a window of the day",
+            "features.difficulty_poll_delay_min: must be less than 1140",
+            "features.highlights_debounce_min: must be greater than 0",
+            "features.theme_file: Configuration Error: theme pack 'themes/missing.yaml' not found",
+            "templates_dir: 'templates/missing'",
+            "faq: 'Prizes' topic is defined twice",
+            "commands.prefix: must not be empty",
+            "commands.aliases: 'fast' is already a command or an alias",
+            "commands.aliases: 'standings' is not a command",
+            "commands.aliases: 'top 10' must be a single word",
+            "commands.responses: 'ranking' is not a command",
+            "jerseys.green_points: must be in decreasing order",
+            "jerseys.yellow_penalty: '7 days' is not a duration like '7d' or '36h'",
+            "jerseys.combative_max_points: must be greater than 0",
+            "jerseys.combative_decay_rate: must be between 0 (included) and 1 (excluded)",
+            "jerseys.custom: 'green' is already a jersey or an option",
+            "jerseys.custom: 'green' points must not be empty and be in decreasing order",
+        ] {
+            assert!(error.contains(expected), "{expected} not in {error}");
+        }
+        assert!(!error.contains("B011111111"));
+        assert!(!error.contains("C0123456789"));
+        assert!(!error.contains("U000XX0X0X0"));
+    }
+
+    #[test]
+    fn custom_jerseys_typos_fail_the_loading() {
+        for (custom, typo) in [
+            ("{ name: polka, metric: p3, aggregation: sum }", "p3"),
+            (
+                "{ name: polka, metric: delta, aggregation: total }",
+                "total",
+            ),
+        ] {
+            let error = settings(&format!("jerseys: {{ custom: [{custom}] }}"))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains(typo), "{typo} not in {error}");
+        }
+    }
+
+    #[test]
+    fn previous_settings_names_are_still_accepted() {
+        let figment = Figment::new().merge(Yaml::string(
+            "
+slack_token: xoxb-0000-1111
+slack_app_token: xapp-1-2222
+slack_default_channel: C000X9X0XX
+aoc_private_leaderboard_id: 424242
+aoc_session_cookie: 53616c7465645f5f
+aoc:
+  session_cookie: 0123456789abcdef
+http_server_port: 8080
+",
+        ));
+        let settings = Settings::from_figment(figment).unwrap();
 
-fn default_summary_events_only() -> bool {
-    false
+        assert_eq!(settings.slack.app_token, "xapp-1-2222");
+        assert_eq!(settings.aoc.private_leaderboard_id, 424242);
+        // the current name takes precedence
+        assert_eq!(settings.aoc.session_cookie, "0123456789abcdef");
+        assert_eq!(settings.server.http_port, Some(8080));
+        assert_eq!(settings.deprecations().len(), 6);
+        assert!(settings.deprecations().contains(
+            &"setting `http_server_port` (HTTP_SERVER_PORT) is deprecated, use `server.http_port` (SERVER__HTTP_PORT) instead".to_string()
+        ));
+        assert!(self::settings("").unwrap().deprecations().is_empty());
+    }
+
+    #[test]
+    fn slack_settings_are_not_required_with_telegram() {
+        let figment = Figment::new().merge(Yaml::string(
+            "
+messaging_backend: telegram
+telegram:
+  bot_token: 123456789:AAE0rT1f2Z3y4X5w6V7u8T9s0R1q2P3o4N5
+  chat_id: -1001234567890
+aoc:
+  private_leaderboard_id: 424242
+  session_cookie: 53616c7465645f5f
+",
+        ));
+        let telegram = Settings::from_figment(figment).unwrap();
+        assert_eq!(telegram.messaging_backend, MessagingBackend::Telegram);
+        assert!(!telegram
+            .to_string()
+            .contains("AAE0rT1f2Z3y4X5w6V7u8T9s0R1q2P3o4N5"));
+
+        let error = settings(
+            "
+messaging_backend: telegram
+telegram: { bot_token: xoxb-0000-1111 }
+features: { heartbeat: true, difficulty_poll_delay_min: 120 }
+",
+        )
+        .unwrap_err()
+        .to_string();
+        for expected in [
+            "telegram.bot_token: expected a token",
+            "telegram.chat_id: required",
+            "features.heartbeat: requires telegram.monitoring_chat_id",
+            "features.difficulty_poll_delay_min: requires the Slack messaging backend",
+        ] {
+            assert!(error.contains(expected), "{expected} not in {error}");
+        }
+    }
+
+    #[test]
+    fn difficulty_poll_is_scheduled_after_the_unlock() {
+        let poll = settings("features: { difficulty_poll_delay_min: 135 }").unwrap();
+        assert_eq!(
+            poll.difficulty_poll_schedule().as_deref(),
+            Some("0 15 7 1-25 12 *")
+        );
+
+        // the daily summary is posted at 16:30 UTC by default
+        let error = settings("features: { difficulty_poll_delay_min: 690 }")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(
+            "features.difficulty_poll_delay_min: the poll must be posted before scheduling.daily_summary"
+        ));
+    }
+
+    #[test]
+    fn secrets_are_redacted_from_diagnostics() {
+        let diagnostics = settings(
+            "
+storage_dir: /var/lib/ceo
+slack:
+  oauth: { client_id: '1234.5678', client_secret: oauth-s3cr3t, redirect_url: 'https://ceo/callback' }
+server: { admin_api_token: s3cr3t, http_port: 8080 }
+",
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(diagnostics.contains("token: xoxb-[redacted]"));
+        assert!(diagnostics.contains("session_cookie: [redacted]"));
+        assert!(diagnostics.contains("admin_api_token: [redacted]"));
+        assert!(diagnostics.contains("client_secret: [redacted]"));
+        for secret in ["0000-1111", "2222", "53616c7465645f5f", "s3cr3t"] {
+            assert!(!diagnostics.contains(secret), "{secret} leaked");
+        }
+    }
 }
@@ -1,6 +1,35 @@
+pub mod absences;
+pub mod awards;
+pub mod chat;
 pub mod commands;
+pub mod completions;
+pub mod difficulty;
 pub mod display;
 pub mod events;
+pub mod exclusions;
+pub mod faq;
+pub mod gaps;
+pub mod handler;
+pub mod heatmap;
+pub mod heroes;
+pub mod home;
+pub mod images;
+pub mod languages;
+pub mod latency;
 pub mod leaderboard;
+pub mod membership;
+pub mod names;
+pub mod practice;
+pub mod predictions;
+pub mod preview;
+pub mod query;
+pub mod rank;
+pub mod recap;
+pub mod review;
+pub mod simulation;
+pub mod splits;
 pub mod standings;
 pub mod templates;
+pub mod theme;
+pub mod votes;
+pub mod watchlist;
@@ -0,0 +1,37 @@
+use crate::core::leaderboard::MemberId;
+use once_cell::sync::Lazy;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::RwLock,
+};
+
+/// Days of absence (year, day) declared for the members, keyed by numeric id.
+pub type Absences = HashMap<MemberId, BTreeSet<(i32, u8)>>;
+
+// Absences taken into account in the standings, registered from the persisted ones.
+static ABSENCES: Lazy<RwLock<Absences>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Add absence days to the ones already known for the members.
+pub fn register(absences: Absences) {
+    let mut registry = ABSENCES.write().unwrap();
+    absences.into_iter().for_each(|(member, days)| {
+        registry.entry(member).or_default().extend(days);
+    });
+}
+
+/// Forget the absences of the member.
+#[cfg(test)]
+pub fn unregister(member: MemberId) {
+    ABSENCES.write().unwrap().remove(&member);
+}
+
+/// Days of the year the member was declared absent.
+pub fn days(member: MemberId, year: i32) -> BTreeSet<u8> {
+    let registry = ABSENCES.read().unwrap();
+    registry.get(&member).map_or_else(BTreeSet::new, |days| {
+        days.iter()
+            .filter(|(y, _day)| *y == year)
+            .map(|(_year, day)| *day)
+            .collect()
+    })
+}
@@ -0,0 +1,153 @@
+use crate::{
+    core::{
+        leaderboard::{Entry, Leaderboard},
+        names,
+        standings::{jersey_points, overall_positions, Jersey, Scoring, Standing},
+    },
+    utils::format_duration,
+};
+use chrono::{Duration, Utc};
+use itertools::Itertools;
+use serde::Serialize;
+use std::{cmp::Reverse, collections::HashMap};
+
+// Number of members on the podium of the most stars collected
+const MOST_STARS_PODIUM: usize = 3;
+
+/// Awards of the ceremony closing an event, from the statistics of the whole season.
+#[derive(Debug, Serialize)]
+pub struct SeasonAwards {
+    pub year: i32,
+    // (jersey, winner), the yellow and green jerseys and the custom ones
+    pub jerseys: Vec<(String, String)>,
+    // (name, number of stars), most stars first
+    pub most_stars: Vec<(String, usize)>,
+    // (name, day, delta time) of the fastest part 2 of the event
+    pub best_delta: Option<(String, u8, String)>,
+    // (name, combative jersey points)
+    pub most_combative: Option<(String, i64)>,
+    // (name, worst position in the overall standings at the end of a day, final position)
+    pub comeback: Option<(String, usize, usize)>,
+}
+
+/// Awards of the event, None if no star was collected.
+pub fn season_awards(leaderboard: &Leaderboard, year: i32) -> Option<SeasonAwards> {
+    let mut season = Leaderboard::new();
+    season.extend(leaderboard.iter().filter(|e| e.year == year).cloned());
+    // no awards without any star collected
+    season.ranked_entries().next()?;
+
+    let standing = Standing::new(&season);
+    let winner = |jersey: &Jersey| {
+        standing
+            .tdf_season(jersey, year)
+            .first()
+            .map(|(id, value, _n_days)| (names::resolve(id), *value))
+    };
+    let jerseys = [Jersey::YELLOW, Jersey::GREEN]
+        .into_iter()
+        .chain(jersey_points().custom.iter().cloned().map(Jersey::CUSTOM))
+        .filter_map(|jersey| Some((jersey.to_string(), winner(&jersey)?.0)))
+        .collect();
+    let most_combative = winner(&Jersey::COMBATIVE).filter(|(_name, points)| *points > 0);
+
+    let most_stars = season
+        .ranked_entries()
+        .counts_by(|e| &e.id)
+        .into_iter()
+        .map(|(id, n_stars)| (names::resolve(id), n_stars))
+        .sorted_by_key(|(name, n_stars)| (Reverse(*n_stars), name.clone()))
+        .take(MOST_STARS_PODIUM)
+        .collect();
+
+    let best_delta = season
+        .entries_per_day_member_for_year(year)
+        .into_iter()
+        .filter_map(|((day, id), entries)| {
+            let (p1, p2) = entries.into_iter().sorted_unstable().collect_tuple()?;
+            Some((id, day, p2.timestamp - p1.timestamp))
+        })
+        .min_by_key(|(id, day, delta)| (*delta, *day, id.numeric))
+        .map(|(id, day, delta)| (names::resolve(id), day, format_duration(delta)));
+
+    // worst position of the members in the overall standings at the end of each day
+    let mut worst_positions = HashMap::new();
+    for day in 1..=25 {
+        let Ok(end_of_day) = Entry::puzzle_unlock(year, day).map(|t| t + Duration::days(1)) else {
+            continue;
+        };
+        if end_of_day > Utc::now() {
+            break;
+        };
+        let mut snapshot = Leaderboard::new();
+        snapshot.extend(season.iter().filter(|e| e.timestamp < end_of_day).cloned());
+        for (pos, id) in overall_positions(&Scoring::LOCAL, &snapshot, year)
+            .into_iter()
+            .enumerate()
+        {
+            let worst = worst_positions.entry(id.numeric).or_insert(pos + 1);
+            *worst = (*worst).max(pos + 1);
+        }
+    }
+    let comeback = overall_positions(&Scoring::LOCAL, &season, year)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(pos, id)| {
+            let worst = *worst_positions.get(&id.numeric)?;
+            (worst > pos + 1).then(|| (names::resolve(&id), worst, pos + 1))
+        })
+        .max_by_key(|(name, worst, current)| (worst - current, Reverse(name.clone())));
+
+    Some(SeasonAwards {
+        year,
+        jerseys,
+        most_stars,
+        best_delta,
+        most_combative,
+        comeback,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn awards_are_given_over_the_whole_event() {
+        let year = 2017;
+        let members = vec![
+            // fast start, then gone after the first week
+            (1..=7).fold(MemberFixture::new(4901, "Blitzen"), |m, day| {
+                m.with_day(day, 300, 600)
+            }),
+            // slow start, then every day of the event
+            (1..=25).fold(MemberFixture::new(4902, "Rudolph"), |m, day| {
+                let part1 = if day <= 7 { 7200 } else { 900 };
+                m.with_day(day, part1, 120)
+            }),
+            MemberFixture::new(4903, "Olive").with_day(3, 1800, 30),
+        ];
+        let leaderboard = private_leaderboard(year, &members);
+
+        let awards = season_awards(&leaderboard, year).unwrap();
+        assert_eq!(
+            awards.most_stars,
+            vec![
+                ("Rudolph".to_string(), 50),
+                ("Blitzen".to_string(), 14),
+                ("Olive".to_string(), 2)
+            ]
+        );
+        assert!(awards
+            .jerseys
+            .contains(&("yellow".to_string(), "Rudolph".to_string())));
+        assert_eq!(
+            awards.best_delta,
+            Some(("Olive".to_string(), 3, "00:00:30".to_string()))
+        );
+        assert_eq!(awards.comeback, Some(("Rudolph".to_string(), 2, 1)));
+
+        assert!(season_awards(&leaderboard, 2016).is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use slack_morphism::{SlackChannelId, SlackTs, SlackUserId};
+use std::fmt;
+
+/// Conversation of the messaging backend a message is posted in: a Slack channel id, or a
+/// Telegram chat id (e.g. `-1001234567890`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChatId(pub String);
+
+/// Message of a conversation: a Slack message timestamp, or a Telegram message id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MessageId(pub String);
+
+/// User of the messaging backend: a Slack user id, or a Telegram user id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserId(pub String);
+
+impl fmt::Display for ChatId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<SlackChannelId> for ChatId {
+    fn from(channel: SlackChannelId) -> Self {
+        ChatId(channel.0)
+    }
+}
+
+impl From<&ChatId> for SlackChannelId {
+    fn from(chat: &ChatId) -> Self {
+        SlackChannelId(chat.0.clone())
+    }
+}
+
+impl From<SlackTs> for MessageId {
+    fn from(ts: SlackTs) -> Self {
+        MessageId(ts.0)
+    }
+}
+
+impl From<&MessageId> for SlackTs {
+    fn from(message: &MessageId) -> Self {
+        SlackTs(message.0.clone())
+    }
+}
+
+impl From<SlackUserId> for UserId {
+    fn from(user: SlackUserId) -> Self {
+        UserId(user.0)
+    }
+}
+
+impl From<&UserId> for SlackUserId {
+    fn from(user: &UserId) -> Self {
+        SlackUserId(user.0.clone())
+    }
+}
@@ -1,43 +1,168 @@
 use crate::{
+    client::{aoc::AoC, provider::EventProvider},
+    config,
     core::{
+        completions::{full_completions, FullCompletion},
         display,
-        leaderboard::ScrapedLeaderboard,
-        standings::{standings_board, Jersey, Ranking, Scoring, Standing},
-        templates::invalid_year_day_message,
+        faq::{self, FaqReply},
+        gaps::{closest_fight, race_gaps, Race},
+        heatmap::{hours_heatmap, members_heatmap, HeatmapRows},
+        heroes::Hero,
+        languages::LanguageUsage,
+        latency::DetectionLatency,
+        leaderboard::{Entry, Identifier, ScrapedLeaderboard},
+        names,
+        practice::PracticePuzzle,
+        predictions::{guessed_day, parse_guess, Predictor},
+        preview::{self, TemplatePreview},
+        query::{Query, QueryResult},
+        rank::{member_rank, MemberRank},
+        simulation::{what_if, WhatIf},
+        splits::{season_splits, SplitsCount},
+        standings::{all_time_standings, Aggregate, Jersey, Ranking, Scoring, Standing},
+        templates::{invalid_year_day_message, MessageTemplate},
     },
+    scheduler::history::JobHistory,
+    storage::{audit::PostedMessage, precomputed::Precomputed},
     utils::current_aoc_year_day,
 };
 use chrono::{DateTime, Utc};
-use once_cell::sync::Lazy;
+use itertools::Itertools;
+use minijinja::context;
+use once_cell::sync::{Lazy, OnceCell};
 use regex::Regex;
 use std::{collections::HashMap, iter::Iterator};
+use strsim::damerau_levenshtein;
 
-const COMMANDS: [&str; 4] = ["!help", "!fast", "!board", "!tdf"];
-static REGEX_COMMANDS: Lazy<Regex> =
-    Lazy::new(|| {
-        let commands = COMMANDS.join(r"|^");
-        Regex::new(format!(
-            // <option> set at the end so all other matches have priority
-            r"(?<cmd>^{commands})|(?<year>\b\d{{4}}\b)|(?<day>\b\d{{1,2}}\b)|(?<option>\b[\S]+\b)"
-    ).as_str())
-    .unwrap()
-    });
+/// Names of the commands, to be written after the command prefix (e.g. `!fast`).
+pub const COMMANDS: [&str; 34] = [
+    "help",
+    "fast",
+    "board",
+    "tdf",
+    "remindme",
+    "iam",
+    "review",
+    "wins",
+    "faq",
+    "link",
+    "bestsolutions",
+    "alltime",
+    "mysummary",
+    "guess",
+    "whatif",
+    "jobs",
+    "missing",
+    "heroes",
+    "burndown",
+    "auditlog",
+    "practice",
+    "splits",
+    "languages",
+    "exclude",
+    "rank",
+    "delta",
+    "latency",
+    "optout",
+    "query",
+    "preview",
+    "heatmap",
+    "fifty",
+    "gap",
+    "watch",
+];
+pub const DEFAULT_PREFIX: &str = "!";
+// Maximum number of members displayed on a single page of the board
+pub const BOARD_PAGE_SIZE: usize = 20;
+// Option of the `board` command adding the solve times columns
+pub const BOARD_DETAILED_OPTION: &str = "detailed";
+// Number of posted messages listed by the `auditlog` command, by default and at most
+const AUDIT_LOG_DEFAULT_COUNT: usize = 10;
+const AUDIT_LOG_MAX_COUNT: usize = 50;
+// Number of days a member must have completed to be ranked by the `delta` command, by default
+const SEASON_DELTA_MIN_DAYS: usize = 5;
+// Options of the `alltime` and `heatmap` commands
+const ALL_TIME_OPTIONS: [&str; 2] = ["local", "stars"];
+const HEATMAP_OPTIONS: [&str; 2] = ["members", "hours"];
+// Maximum number of typos for an unknown option to be suggested the closest valid one
+const OPTION_MAX_TYPOS: usize = 2;
 
-#[derive(Debug, Clone)]
-pub enum Command {
-    Help,
-    Ranking(i32, u8, Vec<(String, String)>, DateTime<Utc>, Ranking),
-    StandingTdf(i32, Option<u8>, String, DateTime<Utc>, Jersey),
-    LeaderboardDisplay(i32, String, DateTime<Utc>, Scoring),
-    NotValid(String),
+// Commands served from memory between two scrapes, their results only depending on the
+// leaderboard and on the arguments written
+const MEMOIZED_COMMANDS: [&str; 11] = [
+    "fast", "board", "tdf", "wins", "alltime", "burndown", "splits", "delta", "heatmap", "fifty",
+    "gap",
+];
+
+// Option of the `tdf` command dropping the worst days of each member from the yellow jersey
+static DROP_WORST_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bdropworst=(?<days>\S*)").unwrap());
+
+// Year of the `rank` command, given as a separate argument since member names can end with digits
+static RANK_YEAR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:^|\s)year=(?<year>\S*)").unwrap());
+
+// Arguments of the `whatif` command, member names possibly containing spaces
+static WHAT_IF_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?<member>.+?)\s+completes\s+day\s+(?<day>\d{1,2})(?:\s+(?<year>\d{4}))?$")
+        .unwrap()
+});
+
+// Syntax of the commands, registered once at startup before any message is parsed.
+static SYNTAX: OnceCell<CommandSyntax> = OnceCell::new();
+
+/// Prefix and names (aliases included) the commands are recognized with, compiled in the regex
+/// used to parse the messages.
+pub struct CommandSyntax {
+    prefix: String,
+    // command name or alias => command name
+    names: HashMap<String, &'static str>,
+    regex: Regex,
 }
 
-impl Command {
-    pub fn parse_string(input: &str) -> HashMap<&str, &str> {
-        REGEX_COMMANDS
+impl Default for CommandSyntax {
+    fn default() -> Self {
+        CommandSyntax::new(DEFAULT_PREFIX, &HashMap::new())
+    }
+}
+
+impl CommandSyntax {
+    /// Aliases are alternative names of the commands (without prefix), keyed by command name.
+    /// Aliases of unknown commands are ignored (they are reported when validating the settings).
+    pub fn new(prefix: &str, aliases: &HashMap<String, Vec<String>>) -> CommandSyntax {
+        let names = COMMANDS
+            .iter()
+            .flat_map(|command| {
+                std::iter::once(command.to_string())
+                    .chain(aliases.get(*command).into_iter().flatten().cloned())
+                    .map(move |name| (name, *command))
+            })
+            .collect::<HashMap<String, &'static str>>();
+        // longest names first, so a name is not matched on another name it starts with
+        let alternatives = names
+            .keys()
+            .sorted_by_key(|name| (std::cmp::Reverse(name.len()), name.to_string()))
+            .map(|name| regex::escape(name))
+            .join("|");
+        let prefix_pattern = regex::escape(prefix);
+        let regex = Regex::new(format!(
+            // <option> set at the end so all other matches have priority
+            r"(?<cmd>^{prefix_pattern}(?:{alternatives})\b)|(?<year>\b\d{{4}}\b)|(?<range>\b\d{{1,2}}-\d{{1,2}}\b)|(?<day>\b\d{{1,2}}\b)|(?<option>\b[\S]+\b)"
+        ).as_str())
+        .unwrap();
+
+        CommandSyntax {
+            prefix: prefix.to_string(),
+            names,
+            regex,
+        }
+    }
+
+    fn parse<'a>(&'a self, input: &'a str) -> HashMap<&'a str, &'a str> {
+        self.regex
             .captures_iter(input)
             .flat_map(|caps| {
-                REGEX_COMMANDS
+                self.regex
                     .capture_names()
                     .filter_map(|o| o.and_then(|n| Some((n, caps.name(n)?.as_str()))))
                     .collect::<Vec<_>>()
@@ -48,22 +173,358 @@ impl Command {
             .rev()
             .collect()
     }
+
+    // Name of the command matched at the start of a message (with its prefix).
+    fn command(&self, matched: &str) -> Option<&'static str> {
+        let name = matched.strip_prefix(&self.prefix)?;
+        self.names.get(name).copied()
+    }
+}
+
+/// Use a command syntax for all the messages. Only the first registered syntax is kept.
+pub fn register(syntax: CommandSyntax) {
+    let _ = SYNTAX.set(syntax);
+}
+
+fn syntax() -> &'static CommandSyntax {
+    SYNTAX.get_or_init(CommandSyntax::default)
+}
+
+/// Prefix of the commands, e.g. to write them in the messages.
+pub fn prefix() -> &'static str {
+    &syntax().prefix
+}
+
+fn invalid_absence() -> String {
+    format!(
+        "Absences are declared with `{}tdf absence <name or id> <day | first day-last day> [year]` 🏖️.",
+        prefix()
+    )
+}
+
+fn invalid_exclusion() -> String {
+    format!(
+        "Members are excluded from the standings with `{prefix}exclude <name or id>`, and counted again with `{prefix}exclude off <name or id>` 🚫.",
+        prefix = prefix()
+    )
+}
+
+fn invalid_rank() -> String {
+    format!(
+        "Please tell me which member to rank (`{}rank <name or id> [year=<year>]`) 🏁.",
+        prefix()
+    )
+}
+
+fn invalid_season_delta() -> String {
+    format!(
+        "The minimum number of days is between 1 and 25, e.g. `{}delta median 10` 🧮.",
+        prefix()
+    )
+}
+
+fn invalid_what_if() -> String {
+    format!(
+        "Simulations are written as `{}whatif <name or id> completes day <day> [year]` 🔮.",
+        prefix()
+    )
+}
+
+fn invalid_drop_worst() -> String {
+    format!(
+        "The worst days can only be dropped from the yellow jersey standings of the year, \
+        e.g. `{}tdf yellow dropworst=3` (less than 25 days) 🟡.",
+        prefix()
+    )
+}
+
+/// Error message of an unknown option of a command, listing the valid options and suggesting
+/// the closest one (if close enough to be a typo).
+fn invalid_option(command: &str, option: &str, valid: &[&str]) -> String {
+    let suggestion = valid
+        .iter()
+        .map(|value| (damerau_levenshtein(option, value), *value))
+        .filter(|(typos, _value)| *typos <= OPTION_MAX_TYPOS)
+        .min_by_key(|(typos, _value)| *typos)
+        .map(|(_typos, value)| value);
+    MessageTemplate::InvalidOption
+        .get()
+        .render(context! {
+            command => command,
+            option => option,
+            suggestion => suggestion,
+            valid => valid,
+        })
+        .unwrap()
+}
+
+fn invalid_range() -> String {
+    format!(
+        "Days ranges are written as `<first day>-<last day>`, e.g. `{}fast avg 1-10` 🧮.",
+        prefix()
+    )
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Help,
+    Ranking(i32, u8, Vec<(String, String)>, DateTime<Utc>, Ranking),
+    // (year, (first day, last day), [(name, aggregated delta, number of days counted)], time, aggregate)
+    RangeRanking(
+        i32,
+        (u8, u8),
+        Vec<(String, String, usize)>,
+        DateTime<Utc>,
+        Aggregate,
+    ),
+    // (year, day, standings, time, jersey, worst days of each member dropped)
+    StandingTdf(i32, Option<u8>, String, DateTime<Utc>, Jersey, usize),
+    // (year, day, stage times, stage winner, time), yellow jersey stage of the day
+    StageTdf(i32, u8, String, Option<String>, DateTime<Utc>),
+    // (year, board page, time, scoring, detailed, page index, number of pages), the detailed
+    // board showing the median part 2 time and average delta of the members
+    LeaderboardDisplay(i32, String, DateTime<Utc>, Scoring, bool, usize, usize),
+    // (year, days won table, time)
+    Wins(i32, String, DateTime<Utc>),
+    // (years, all-time standings table, time, scoring)
+    AllTime(Vec<i32>, String, DateTime<Utc>, Scoring),
+    Faq(FaqReply),
+    // (year, day, puzzle url, private leaderboard url, global leaderboard url)
+    Link(i32, u8, String, String, String),
+    // (year, day, [(member, number of previous days with a star)]) of the members without any
+    // star on the day
+    Missing(i32, u8, Vec<(Identifier, usize)>),
+    // (year, day, [(permalink, author, votes)]), most upvoted replies of the solutions thread
+    BestSolutions(i32, u8, Vec<(String, String, u32)>),
+    // member to send reminders about, None to stop the reminders
+    Reminder(Option<Identifier>),
+    // (member, whether the Slack user sending the command watches them), None to stop watching
+    // all the members
+    Watch(Option<Identifier>, bool),
+    // member to link to the Slack user sending the command
+    Iam(Identifier),
+    // whether the Slack user sending the command wants to receive the year in review
+    ReviewOptIn(bool),
+    // whether the Slack user sending the command wants to receive a personal daily summary
+    PersonalSummaryOptIn(bool),
+    // whether the member linked to the Slack user sending the command is no longer named in the
+    // messages
+    OptOut(bool),
+    // (year, day, guess in seconds) of the global leaderboard cutoff of the next puzzle, submitted
+    // by the Slack user
+    Guess(i32, u8, i64),
+    // (year, standings) of the prediction game
+    Predictions(i32, Vec<Predictor>),
+    // projected standings if a member completed a missing day now
+    WhatIf(WhatIf),
+    // (job, history) of the scheduler jobs, requested by an admin
+    Jobs(Vec<(String, JobHistory)>),
+    // (number of messages requested, last messages posted), requested by an admin
+    AuditLog(usize, Vec<PostedMessage>),
+    // (year, day, stars remaining chart) over the first hours following the puzzle release, empty
+    // if no star was collected
+    Burndown(i32, u8, String),
+    // (number of global leaderboards recorded, all-time appearances of the members on them)
+    Heroes(usize, Vec<Hero>),
+    // (member, year, days) of absence declared by an admin, not penalized for the yellow jersey
+    Absence(Identifier, i32, Vec<u8>),
+    // (member, whether left out of the standings or counted again), requested by an admin
+    Exclusion(Identifier, bool),
+    // (year requested, None for all the years, puzzle suggested) of the past puzzles not
+    // completed by the Slack user, None if all of them are
+    Practice(Option<i32>, Option<PracticePuzzle>),
+    // (year, members with their number of negative splits) of the event
+    Splits(i32, Vec<SplitsCount>),
+    // standings of a single member for the year
+    Rank(MemberRank),
+    // (year, [(name, aggregated delta, number of days counted)], time, aggregate, minimum number
+    // of days) over the whole event
+    SeasonDelta(
+        i32,
+        Vec<(String, String, usize)>,
+        DateTime<Utc>,
+        Aggregate,
+        usize,
+    ),
+    // (year, languages used) of the event, from the votes of the daily polls
+    Languages(i32, Vec<LanguageUsage>),
+    // (year, delay between the completion of the stars and their detection by the bot, None if no
+    // star of the year was detected)
+    Latency(i32, Option<DetectionLatency>),
+    // stars of the private leaderboard matching the filters written by the member
+    Query(QueryResult),
+    // template to render with sample values, posted in the monitoring channel for an admin
+    Preview(TemplatePreview),
+    // (year, rows, heatmap of the stars collected per row and day, number of members with
+    // stars) of the event
+    Heatmap(i32, HeatmapRows, String, usize),
+    // (year, members having collected all the stars, in the order they completed the event)
+    FullCompletions(i32, Vec<FullCompletion>),
+    // (year, race, gaps of each member to the member ahead, (member ahead, member behind, gap) of
+    // the closest fight)
+    Gaps(i32, Race, String, Option<(Identifier, Identifier, i64)>),
+    // (command, seconds before it can be run again) of a command run too often
+    Cooldown(&'static str, u64),
+    NotValid(String),
+}
+
+impl Command {
+    pub fn parse_string(input: &str) -> HashMap<&str, &str> {
+        syntax().parse(input)
+    }
     pub fn is_command(input: &str) -> bool {
         Self::parse_string(input).contains_key("cmd")
     }
 
+    /// Name of the command (aliases resolved), if the input is a command.
+    pub fn name(input: &str) -> Option<&'static str> {
+        Self::parse_string(input)
+            .get("cmd")
+            .and_then(|cmd| syntax().command(cmd))
+    }
+
+    /// Command written in the input, the results of the expensive commands being memoized until
+    /// the next scrape (see `Precomputed::command`).
+    pub fn build_from(
+        input: String,
+        leaderboard: &ScrapedLeaderboard,
+        precomputed: &Precomputed,
+    ) -> Option<Command> {
+        let parsed = Self::parse_string(&input);
+        let memoized = parsed
+            .get("cmd")
+            .and_then(|cmd| syntax().command(cmd))
+            .is_some_and(|cmd| MEMOIZED_COMMANDS.contains(&cmd))
+            // absences are declared with the `tdf` command
+            && parsed.get("option") != Some(&"absence");
+        if !memoized {
+            return Self::build(input, leaderboard, precomputed);
+        };
+        let key = (current_aoc_year_day(), Self::memoization_key(&input));
+        precomputed.command(leaderboard, key, || {
+            Self::build(input, leaderboard, precomputed)
+        })
+    }
+
+    // Command (aliases resolved), year (the current one by default) and the other arguments of
+    // the input, sorted and without the default option of the command, so that the inputs
+    // building the same result (e.g. `!board` and `!board local`) share their memoized result.
+    fn memoization_key(input: &str) -> String {
+        let parsed = Self::parse_string(input);
+        let Some(cmd) = parsed.get("cmd").and_then(|cmd| syntax().command(cmd)) else {
+            return input.split_whitespace().join(" ");
+        };
+        let year = parsed
+            .get("year")
+            .and_then(|y| y.parse::<i32>().ok())
+            .unwrap_or_else(|| current_aoc_year_day().0);
+        let default_option = match cmd {
+            "fast" => Some(Ranking::get_default_str()),
+            "tdf" => Some(Jersey::get_default_str()),
+            "board" | "alltime" => Some(Scoring::get_default_str()),
+            "delta" => Some(Aggregate::options()[0]),
+            "heatmap" => Some(HEATMAP_OPTIONS[0]),
+            "gap" => Some(Jersey::get_default_str()),
+            _ => None,
+        };
+        let arguments = Self::argument(input, &parsed)
+            .split_whitespace()
+            .filter(|a| Some(a) != parsed.get("year") && Some(*a) != default_option)
+            .sorted()
+            .join(" ");
+        format!("{cmd} {year} {arguments}")
+    }
+
+    // Text following the command, the arguments of some commands (e.g. member names) being used
+    // as is.
+    fn argument<'a>(input: &'a str, parsed: &HashMap<&str, &str>) -> &'a str {
+        parsed
+            .get("cmd")
+            .and_then(|cmd| input.trim().strip_prefix(cmd))
+            .unwrap_or_default()
+            .trim()
+    }
+
     // Note that we call this command on matching command strings, so we know
     // input string is a command. We might want to return Option<Command> later on.
-    pub fn build_from(input: String, leaderboard: &ScrapedLeaderboard) -> Option<Command> {
+    fn build(
+        input: String,
+        leaderboard: &ScrapedLeaderboard,
+        precomputed: &Precomputed,
+    ) -> Option<Command> {
         let parsed = Self::parse_string(&input);
+        let argument = Self::argument(&input, &parsed);
+
+        match parsed.get("cmd").and_then(|cmd| syntax().command(cmd)) {
+            Some(cmd) if cmd == COMMANDS[0] => Some(Command::Help),
+            Some(cmd)
+                if cmd == COMMANDS[1]
+                    && (parsed.contains_key("range")
+                        || parsed
+                            .get("option")
+                            .is_some_and(|o| Aggregate::from_string(o).is_some())) =>
+            {
+                let aggregate = match parsed.get("option") {
+                    None => Aggregate::AVERAGE,
+                    Some(option) => match Aggregate::from_string(option) {
+                        Some(aggregate) => aggregate,
+                        None => {
+                            return Some(Command::NotValid(invalid_option(
+                                cmd,
+                                option,
+                                &Aggregate::options(),
+                            )))
+                        }
+                    },
+                };
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or_else(|| current_aoc_year_day().0);
+                // whole event (so far) by default
+                let (first, last) = match parsed.get("range") {
+                    Some(range) => match range.split_once('-').and_then(|(first, last)| {
+                        Some((first.parse::<u8>().ok()?, last.parse::<u8>().ok()?))
+                    }) {
+                        Some(days) => days,
+                        None => return Some(Command::NotValid(invalid_range())),
+                    },
+                    None => match current_aoc_year_day() {
+                        (current_year, current_day) if current_year == year => (1, current_day),
+                        _ => (1, 25),
+                    },
+                };
 
-        match parsed.get("cmd") {
-            Some(cmd) if cmd == &COMMANDS[0] => Some(Command::Help),
-            Some(cmd) if cmd == &COMMANDS[1] => {
+                if first > last {
+                    Some(Command::NotValid(invalid_range()))
+                } else if let Some(msg) = invalid_year_day_message(year, Some(first))
+                    .or_else(|| invalid_year_day_message(year, Some(last)))
+                {
+                    Some(Command::NotValid(msg))
+                } else {
+                    let data = Standing::new(&leaderboard.leaderboard).by_delta_over_days(
+                        &aggregate,
+                        year,
+                        first..=last,
+                    );
+
+                    Some(Command::RangeRanking(
+                        year,
+                        (first, last),
+                        data,
+                        leaderboard.timestamp,
+                        aggregate,
+                    ))
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[1] => {
                 let ranking_str = parsed
                     .get("option")
                     .map_or(Ranking::get_default_str(), |o| *o);
-                let ranking = Ranking::from_string(ranking_str).unwrap_or(Ranking::DELTA);
+                let Some(ranking) = Ranking::from_string(ranking_str) else {
+                    let valid = [Ranking::options(), Aggregate::options()].concat();
+                    return Some(Command::NotValid(invalid_option(cmd, ranking_str, &valid)));
+                };
                 let year = parsed
                     .get("year")
                     .and_then(|d| d.parse::<i32>().ok())
@@ -76,7 +537,7 @@ impl Command {
                 if let Some(msg) = invalid_year_day_message(year, Some(day)) {
                     Some(Command::NotValid(msg))
                 } else {
-                    let data = Standing::new(&leaderboard.leaderboard).by_time(&ranking, year, day);
+                    let data = precomputed.ranking(leaderboard, &ranking, year, day);
 
                     Some(Command::Ranking(
                         year,
@@ -87,11 +548,24 @@ impl Command {
                     ))
                 }
             }
-            Some(cmd) if cmd == &COMMANDS[2] => {
-                let scoring_str = parsed
-                    .get("option")
+            Some(cmd) if cmd == COMMANDS[2] => {
+                // the scoring and the detailed options can be given in any order
+                let options = argument.split_whitespace().collect::<Vec<&str>>();
+                let scoring_str = options
+                    .iter()
+                    .find(|o| Scoring::from_string(o).is_some())
                     .map_or(Scoring::get_default_str(), |o| *o);
                 let scoring = Scoring::from_string(scoring_str).unwrap_or(Scoring::LOCAL);
+                let detailed = options.contains(&BOARD_DETAILED_OPTION);
+                let unknown = options.iter().find(|o| {
+                    Scoring::from_string(o).is_none()
+                        && **o != BOARD_DETAILED_OPTION
+                        && o.parse::<i32>().is_err()
+                });
+                if let Some(unknown) = unknown {
+                    let valid = [Scoring::options(), vec![BOARD_DETAILED_OPTION]].concat();
+                    return Some(Command::NotValid(invalid_option(cmd, unknown, &valid)));
+                };
                 let year = parsed
                     .get("year")
                     .and_then(|d| d.parse::<i32>().ok())
@@ -100,36 +574,128 @@ impl Command {
                 if let Some(msg) = invalid_year_day_message(year, None) {
                     Some(Command::NotValid(msg))
                 } else {
-                    let data = standings_board(&scoring, &leaderboard.leaderboard, year);
-                    let formatted = display::board(data);
-                    Some(Command::LeaderboardDisplay(
+                    Some(Command::board_page(
+                        leaderboard,
+                        precomputed,
                         year,
-                        formatted,
-                        leaderboard.timestamp,
                         scoring,
+                        detailed,
+                        0,
                     ))
                 }
             }
-            Some(cmd) if cmd == &COMMANDS[3] => {
+            Some(cmd) if cmd == COMMANDS[3] && parsed.get("option") == Some(&"absence") => {
+                // member names can contain spaces, so everything before the days is the member
+                let argument = argument.trim_start_matches("absence");
+                let mut tokens = argument.split_whitespace().collect::<Vec<&str>>();
+                let year = match tokens.last() {
+                    Some(token) if token.len() == 4 => match token.parse::<i32>() {
+                        Ok(year) => {
+                            tokens.pop();
+                            year
+                        }
+                        Err(_) => return Some(Command::NotValid(invalid_absence())),
+                    },
+                    _ => current_aoc_year_day().0,
+                };
+                let days = tokens.pop().and_then(|days| match days.split_once('-') {
+                    Some((first, last)) => {
+                        Some((first.parse::<u8>().ok()?, last.parse::<u8>().ok()?))
+                    }
+                    None => days.parse::<u8>().ok().map(|day| (day, day)),
+                });
+                let member = tokens.join(" ");
+
+                // absences can be declared ahead, so days of the event to come are accepted
+                match days {
+                    Some((first, last))
+                        if 1 <= first && first <= last && last <= 25 && !member.is_empty() =>
+                    {
+                        if let Some(msg) = invalid_year_day_message(year, None) {
+                            Some(Command::NotValid(msg))
+                        } else {
+                            match leaderboard.leaderboard.find_member(&member) {
+                                Some(id) => {
+                                    Some(Command::Absence(id, year, (first..=last).collect()))
+                                }
+                                None => Some(Command::NotValid(format!(
+                                    "I could not find any member named *{member}* on the leaderboard 🔍."
+                                ))),
+                            }
+                        }
+                    }
+                    _ => Some(Command::NotValid(invalid_absence())),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[3] => {
                 let jersey_str = parsed
                     .get("option")
+                    // the worst days dropped are not a jersey
+                    .filter(|o| !DROP_WORST_REGEX.is_match(o))
                     .map_or(Jersey::get_default_str(), |o| *o);
-                let jersey = Jersey::from_string(jersey_str).unwrap_or(Jersey::YELLOW);
+                let Some(jersey) = Jersey::from_string(jersey_str) else {
+                    return Some(Command::NotValid(invalid_option(
+                        cmd,
+                        jersey_str,
+                        &Jersey::options(),
+                    )));
+                };
                 let year = parsed
                     .get("year")
                     .and_then(|d| d.parse::<i32>().ok())
                     .unwrap_or_else(|| current_aoc_year_day().0);
                 let day = parsed.get("day").and_then(|d| d.parse::<u8>().ok());
+                let drop_worst = DROP_WORST_REGEX
+                    .captures(argument)
+                    .map(|caps| caps["days"].parse::<usize>().ok().filter(|n| *n < 25));
 
                 if let Some(msg) = invalid_year_day_message(year, day) {
                     Some(Command::NotValid(msg))
+                } else if let Some(drop_worst) = drop_worst {
+                    match (drop_worst, &jersey, day) {
+                        (Some(drop_worst), Jersey::YELLOW, None) => {
+                            let standings = Standing::new(&leaderboard.leaderboard);
+                            Some(Command::StandingTdf(
+                                year,
+                                day,
+                                display::tdf_time_yearly(
+                                    &standings.yellow_season(year, drop_worst),
+                                ),
+                                leaderboard.timestamp,
+                                jersey,
+                                drop_worst,
+                            ))
+                        }
+                        _ => Some(Command::NotValid(invalid_drop_worst())),
+                    }
+                } else if let (Jersey::YELLOW, Some(day)) = (&jersey, day) {
+                    // stage of the day, based on part 2 completion time
+                    let standings = Standing::new(&leaderboard.leaderboard);
+                    let data = standings.tdf_stage(year, day);
+                    let winner = data
+                        .first()
+                        .filter(|(_id, time)| time.is_some())
+                        .map(|(id, _time)| names::resolve(id));
+
+                    Some(Command::StageTdf(
+                        year,
+                        day,
+                        display::tdf_stage_daily(&data),
+                        winner,
+                        leaderboard.timestamp,
+                    ))
                 } else {
                     let formatted = match (&jersey, day) {
                         // standing yearly, based on time
-                        (Jersey::YELLOW, None) => {
+                        (Jersey::YELLOW, None) => precomputed.yellow_jersey(leaderboard, year),
+                        // custom jerseys based on time
+                        (Jersey::CUSTOM(custom), None) if !custom.is_points() => {
                             let standings = Standing::new(&leaderboard.leaderboard);
-                            let data = standings.tdf_season(&jersey, year);
-                            display::tdf_time_yearly(&data)
+                            display::tdf_time_yearly(&standings.tdf_season(&jersey, year))
+                        }
+                        (Jersey::CUSTOM(custom), Some(day)) if !custom.is_points() => {
+                            let standings = Standing::new(&leaderboard.leaderboard);
+                            display::tdf_stage_daily(&standings.custom_stage(custom, year, day))
                         }
                         // standing yearly, based on points
                         (_, None) => {
@@ -137,12 +703,6 @@ impl Command {
                             let data = standings.tdf_season(&jersey, year);
                             display::tdf_points_yearly(&data)
                         }
-                        // santings daily, based on time
-                        (Jersey::YELLOW, Some(day)) => {
-                            let standings = Standing::new(&leaderboard.leaderboard);
-                            let data = standings.by_time(&Ranking::PART2, year, day);
-                            display::tdf_time_daily(&data)
-                        }
                         // standings daily, base on points
                         (_, Some(day)) => {
                             let standings = Standing::new(&leaderboard.leaderboard);
@@ -157,10 +717,923 @@ impl Command {
                         formatted,
                         leaderboard.timestamp,
                         jersey,
+                        0,
+                    ))
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[4] => {
+                // member names can contain spaces, so the whole argument is used
+                match argument {
+                    "" => Some(Command::NotValid(format!(
+                        "Please tell me who you are on the leaderboard (`{prefix}remindme <AoC name or id>`), \
+                        or use `{prefix}remindme off` to stop the reminders.",
+                        prefix = prefix()
+                    ))),
+                    "off" => Some(Command::Reminder(None)),
+                    member => match leaderboard.leaderboard.find_member(member) {
+                        Some(id) => Some(Command::Reminder(Some(id))),
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    },
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[5] => {
+                match argument {
+                    "" => Some(Command::NotValid(format!(
+                        "Please tell me who you are on the leaderboard (`{}iam <AoC name or id>`).",
+                        prefix()
+                    ))),
+                    member => match leaderboard.leaderboard.find_member(member) {
+                        Some(id) => Some(Command::Iam(id)),
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    },
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[6] => match parsed.get("option") {
+                Some(&"on") => Some(Command::ReviewOptIn(true)),
+                Some(&"off") => Some(Command::ReviewOptIn(false)),
+                _ => Some(Command::NotValid(format!(
+                    "Use `{prefix}review on` or `{prefix}review off` to receive (or not) your year in review.",
+                    prefix = prefix()
+                ))),
+            },
+            Some(cmd) if cmd == COMMANDS[7] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or_else(|| current_aoc_year_day().0);
+
+                if let Some(msg) = invalid_year_day_message(year, None) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    let standings = Standing::new(&leaderboard.leaderboard);
+                    let formatted = display::wins(&standings.wins(year));
+
+                    Some(Command::Wins(year, formatted, leaderboard.timestamp))
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[8] => {
+                // topics can contain spaces, so the whole argument is used
+                let settings = &config::SETTINGS;
+                Some(Command::Faq(faq::reply(&settings.faq, Some(argument))))
+            }
+            Some(cmd) if cmd == COMMANDS[9] => {
+                let (current_year, current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                let day = parsed
+                    .get("day")
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .unwrap_or(current_day);
+
+                if let Some(msg) = invalid_year_day_message(year, Some(day)) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    let aoc_client = AoC::new();
+                    Some(Command::Link(
+                        year,
+                        day,
+                        aoc_client.daily_challenge_url(year, day),
+                        aoc_client.private_leaderboard_url(year),
+                        aoc_client.global_leaderboard_url(year, day),
+                    ))
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[10] => {
+                let (current_year, current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                let day = parsed
+                    .get("day")
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .unwrap_or(current_day);
+
+                match invalid_year_day_message(year, Some(day)) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    // replies are looked up once the command is received from Slack
+                    None => Some(Command::BestSolutions(year, day, vec![])),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[11] => {
+                let scoring = match parsed.get("option") {
+                    Some(&"stars") => Scoring::STARS,
+                    None | Some(&"local") => Scoring::LOCAL,
+                    Some(option) => {
+                        return Some(Command::NotValid(invalid_option(
+                            cmd,
+                            option,
+                            &ALL_TIME_OPTIONS,
+                        )))
+                    }
+                };
+                let years = leaderboard
+                    .leaderboard
+                    .iter()
+                    .map(|e| e.year)
+                    .unique()
+                    .sorted()
+                    .collect();
+                let formatted =
+                    display::all_time(&all_time_standings(&scoring, &leaderboard.leaderboard));
+
+                Some(Command::AllTime(
+                    years,
+                    formatted,
+                    leaderboard.timestamp,
+                    scoring,
+                ))
+            }
+            Some(cmd) if cmd == COMMANDS[12] => {
+                let settings = &config::SETTINGS;
+                match (settings.features.personal_summary, parsed.get("option")) {
+                    (false, _) => Some(Command::NotValid(
+                        "The personal daily summaries are not enabled on this leaderboard 🤷.".to_string(),
+                    )),
+                    (true, Some(&"on")) => Some(Command::PersonalSummaryOptIn(true)),
+                    (true, Some(&"off")) => Some(Command::PersonalSummaryOptIn(false)),
+                    _ => Some(Command::NotValid(format!(
+                        "Use `{prefix}mysummary on` or `{prefix}mysummary off` to receive (or not) your personal daily summary.",
+                        prefix = prefix()
+                    ))),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[13] => {
+                let (current_year, _current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                match parse_guess(argument) {
+                    Some(guess_sec) => match guessed_day(current_year, Utc::now()) {
+                        Some(day) => Some(Command::Guess(current_year, day, guess_sec)),
+                        None => Some(Command::NotValid(
+                            "The guesses are only open during the event 📆.".to_string(),
+                        )),
+                    },
+                    // standings are computed once the command is received from Slack
+                    None if argument.is_empty() || parsed.get("year") == Some(&argument) => {
+                        match invalid_year_day_message(year, None) {
+                            Some(msg) => Some(Command::NotValid(msg)),
+                            None => Some(Command::Predictions(year, vec![])),
+                        }
+                    }
+                    None => Some(Command::NotValid(format!(
+                        "Use `{prefix}guess <mm:ss>` to guess the time of the 100th part 2 finisher of the global leaderboard of the next puzzle.",
+                        prefix = prefix()
+                    ))),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[14] => {
+                let Some(captures) = WHAT_IF_REGEX.captures(argument) else {
+                    return Some(Command::NotValid(invalid_what_if()));
+                };
+                let member = &captures["member"];
+                let day = captures["day"].parse::<u8>().ok()?;
+                let year = captures
+                    .name("year")
+                    .and_then(|y| y.as_str().parse::<i32>().ok())
+                    .unwrap_or_else(|| current_aoc_year_day().0);
+
+                if let Some(msg) = invalid_year_day_message(year, Some(day)) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    match leaderboard.leaderboard.find_member(member) {
+                        Some(id) => match what_if(&leaderboard.leaderboard, &id, year, day, Utc::now()) {
+                            Some(projection) => Some(Command::WhatIf(projection)),
+                            None => Some(Command::NotValid(format!(
+                                "*{}* already collected both stars of day {day} ⭐⭐, nothing to simulate.",
+                                names::resolve(&id)
+                            ))),
+                        },
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    }
+                }
+            }
+            // history is looked up once the command is received from Slack
+            Some(cmd) if cmd == COMMANDS[15] => Some(Command::Jobs(vec![])),
+            Some(cmd) if cmd == COMMANDS[16] => {
+                let (current_year, current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                let day = parsed
+                    .get("day")
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .unwrap_or(current_day);
+
+                match invalid_year_day_message(year, Some(day)) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    None => {
+                        let members = leaderboard
+                            .leaderboard
+                            .members_without_stars_for_year_day(year, day)
+                            .into_iter()
+                            .map(|(id, n_days)| (id.clone(), n_days))
+                            .collect();
+                        Some(Command::Missing(year, day, members))
+                    }
+                }
+            }
+            // appearances are looked up once the command is received from Slack
+            Some(cmd) if cmd == COMMANDS[17] => Some(Command::Heroes(0, vec![])),
+            Some(cmd) if cmd == COMMANDS[18] => {
+                let (current_year, current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|d| d.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                let day = parsed
+                    .get("day")
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .unwrap_or(current_day);
+
+                if let Some(msg) = invalid_year_day_message(year, Some(day)) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    let hours = leaderboard
+                        .leaderboard
+                        .stars_per_hour_for_year_day(year, day)
+                        .ok()?;
+                    // only the hours already elapsed for the ongoing day
+                    let elapsed = Utc::now() - Entry::puzzle_unlock(year, day).ok()?;
+                    let n_hours = (elapsed.num_hours() + 1).clamp(0, 24) as usize;
+                    let n_members = leaderboard
+                        .leaderboard
+                        .iter()
+                        .filter(|e| e.year == year)
+                        .map(|e| e.id.numeric)
+                        .unique()
+                        .count();
+
+                    let chart = match hours.iter().any(|(p1, p2)| p1 + p2 > 0) {
+                        true => display::burndown(&hours[..n_hours], 2 * n_members),
+                        false => String::new(),
+                    };
+                    Some(Command::Burndown(year, day, chart))
+                }
+            }
+            // messages are looked up once the command is received from Slack
+            Some(cmd) if cmd == COMMANDS[19] => {
+                let n = argument
+                    .parse::<usize>()
+                    .unwrap_or(AUDIT_LOG_DEFAULT_COUNT)
+                    .clamp(1, AUDIT_LOG_MAX_COUNT);
+                Some(Command::AuditLog(n, vec![]))
+            }
+            // puzzle is picked once the command is received from Slack
+            Some(cmd) if cmd == COMMANDS[20] => {
+                let year = parsed.get("year").and_then(|y| y.parse::<i32>().ok());
+                match year.and_then(|year| invalid_year_day_message(year, None)) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    None => Some(Command::Practice(year, None)),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[21] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                match invalid_year_day_message(year, None) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    None => Some(Command::Splits(
+                        year,
+                        season_splits(&leaderboard.leaderboard, year),
+                    )),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[22] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                match invalid_year_day_message(year, None) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    // votes are counted once the command is received from Slack
+                    None => Some(Command::Languages(year, vec![])),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[23] => {
+                // member names can contain spaces, so everything after `off` is the member
+                let (excluded, member) = match argument.split_once(char::is_whitespace) {
+                    Some(("off", member)) => (false, member.trim()),
+                    _ => (true, argument),
+                };
+                match member {
+                    "" | "off" => Some(Command::NotValid(invalid_exclusion())),
+                    member => match leaderboard.leaderboard.find_member(member) {
+                        Some(id) => Some(Command::Exclusion(id, excluded)),
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    },
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[24] => {
+                // member names can contain spaces and digits, so the year is only read from the
+                // `year=` argument and everything else is the member
+                let year = match RANK_YEAR_REGEX.captures(argument) {
+                    Some(captures) => captures["year"].parse::<i32>().ok(),
+                    None => Some(current_aoc_year_day().0),
+                };
+                let member = RANK_YEAR_REGEX
+                    .replace_all(argument, "")
+                    .split_whitespace()
+                    .join(" ");
+
+                let Some(year) = year.filter(|_| !member.is_empty()) else {
+                    return Some(Command::NotValid(invalid_rank()));
+                };
+                if let Some(msg) = invalid_year_day_message(year, None) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    match leaderboard.leaderboard.find_member(&member) {
+                        Some(id) => match member_rank(&leaderboard.leaderboard, &id, year) {
+                            Some(rank) => Some(Command::Rank(rank)),
+                            None => Some(Command::NotValid(format!(
+                                "*{}* has no star in the {year} event yet 🌑.",
+                                names::resolve(&id)
+                            ))),
+                        },
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    }
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[25] => {
+                let aggregate = match parsed.get("option") {
+                    None => Aggregate::AVERAGE,
+                    Some(option) => match Aggregate::from_string(option) {
+                        Some(aggregate) => aggregate,
+                        None => {
+                            return Some(Command::NotValid(invalid_option(
+                                cmd,
+                                option,
+                                &Aggregate::options(),
+                            )))
+                        }
+                    },
+                };
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                // no more days than unlocked so far
+                let min_days = match parsed.get("day").map(|d| d.parse::<usize>()) {
+                    Some(Ok(min_days)) => min_days,
+                    Some(Err(_)) => 0,
+                    None => match current_aoc_year_day() {
+                        (current_year, current_day) if current_year == year => {
+                            SEASON_DELTA_MIN_DAYS.min(current_day as usize)
+                        }
+                        _ => SEASON_DELTA_MIN_DAYS,
+                    },
+                };
+
+                if !(1..=25).contains(&min_days) {
+                    Some(Command::NotValid(invalid_season_delta()))
+                } else if let Some(msg) = invalid_year_day_message(year, None) {
+                    Some(Command::NotValid(msg))
+                } else {
+                    let data = Standing::new(&leaderboard.leaderboard)
+                        .by_season_delta(&aggregate, year, min_days);
+                    Some(Command::SeasonDelta(
+                        year,
+                        data,
+                        leaderboard.timestamp,
+                        aggregate,
+                        min_days,
                     ))
                 }
             }
+            Some(cmd) if cmd == COMMANDS[26] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                match invalid_year_day_message(year, None) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    // detections are looked up once the command is received from Slack
+                    None => Some(Command::Latency(year, None)),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[27] => match parsed.get("option") {
+                Some(&"on") => Some(Command::OptOut(true)),
+                Some(&"off") => Some(Command::OptOut(false)),
+                _ => Some(Command::NotValid(format!(
+                    "Use `{prefix}optout on` or `{prefix}optout off` to be anonymous (or named again) in the messages.",
+                    prefix = prefix()
+                ))),
+            },
+            Some(cmd) if cmd == COMMANDS[28] => {
+                let year = current_aoc_year_day().0;
+                match Query::parse(argument, &leaderboard.leaderboard, year) {
+                    Err(msg) => Some(Command::NotValid(format!(
+                        "{msg} Write the filters as in `{}query day=7 part=2 sort=time limit=10`.",
+                        prefix()
+                    ))),
+                    Ok(query) => match invalid_year_day_message(query.year, query.day) {
+                        Some(msg) => Some(Command::NotValid(msg)),
+                        None => Some(Command::Query(
+                            query.run(&leaderboard.leaderboard, argument),
+                        )),
+                    },
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[29] => {
+                let (name, sample) = argument.split_once(' ').unwrap_or((argument, ""));
+                let template = preview::find_template(name).ok_or_else(|| {
+                    format!(
+                        "`{name}` is not a template, write its file name (e.g. `{}preview summary`) 🧪.",
+                        prefix()
+                    )
+                });
+                match template.and_then(|t| Ok((t, preview::parse_sample(sample)?))) {
+                    Err(msg) => Some(Command::NotValid(msg)),
+                    Ok((template, sample)) => {
+                        Some(Command::Preview(TemplatePreview::new(template, sample)))
+                    }
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[30] => {
+                let (current_year, current_day) = current_aoc_year_day();
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_year);
+                if let Some(msg) = invalid_year_day_message(year, None) {
+                    return Some(Command::NotValid(msg));
+                };
+                // only the days already unlocked for the ongoing event
+                let n_days = match year == current_year {
+                    true => current_day as usize,
+                    false => 25,
+                };
+                let (members, n_members) = members_heatmap(&leaderboard.leaderboard, year);
+                let (rows, heatmap) = match parsed.get("option") {
+                    Some(&"hours") => (
+                        HeatmapRows::Hours,
+                        hours_heatmap(&leaderboard.leaderboard, year).ok()?,
+                    ),
+                    None | Some(&"members") => (HeatmapRows::Members, members),
+                    Some(option) => {
+                        return Some(Command::NotValid(invalid_option(
+                            cmd,
+                            option,
+                            &HEATMAP_OPTIONS,
+                        )))
+                    }
+                };
+                let chart = match n_members {
+                    0 => String::new(),
+                    _ => display::heatmap(&heatmap, n_days),
+                };
+                Some(Command::Heatmap(year, rows, chart, n_members))
+            }
+            Some(cmd) if cmd == COMMANDS[31] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                match invalid_year_day_message(year, None) {
+                    Some(msg) => Some(Command::NotValid(msg)),
+                    None => Some(Command::FullCompletions(
+                        year,
+                        full_completions(&leaderboard.leaderboard, year),
+                    )),
+                }
+            }
+            Some(cmd) if cmd == COMMANDS[32] => {
+                let year = parsed
+                    .get("year")
+                    .and_then(|y| y.parse::<i32>().ok())
+                    .unwrap_or(current_aoc_year_day().0);
+                if let Some(msg) = invalid_year_day_message(year, None) {
+                    return Some(Command::NotValid(msg));
+                };
+                let race = match parsed.get("option") {
+                    None => Race::Yellow,
+                    Some(option) => match (Jersey::from_string(option), Scoring::from_string(option)) {
+                        (Some(Jersey::YELLOW), _) => Race::Yellow,
+                        (_, Some(Scoring::LOCAL)) => Race::Local,
+                        _ => {
+                            return Some(Command::NotValid(invalid_option(
+                                cmd,
+                                option,
+                                &["yellow", "local"],
+                            )))
+                        }
+                    },
+                };
+                let gaps = race_gaps(&leaderboard.leaderboard, race, year);
+                let closest = closest_fight(&gaps).and_then(|(ahead, behind)| {
+                    Some((ahead.member.clone(), behind.member.clone(), behind.ahead?))
+                });
+                Some(Command::Gaps(year, race, display::gaps(&gaps, race), closest))
+            }
+            Some(cmd) if cmd == COMMANDS[33] => {
+                // member names can contain spaces, so the whole argument is used
+                let (member, watch) = match argument.strip_prefix("off") {
+                    Some(member) if member.is_empty() || member.starts_with(' ') => {
+                        (member.trim(), false)
+                    }
+                    _ => (argument, true),
+                };
+                match (member, watch) {
+                    ("", true) => Some(Command::NotValid(format!(
+                        "Please tell me who to watch on the leaderboard (`{prefix}watch <AoC name or id>`), \
+                        or use `{prefix}watch off [name]` to stop watching them.",
+                        prefix = prefix()
+                    ))),
+                    ("", false) => Some(Command::Watch(None, false)),
+                    (member, watch) => match leaderboard.leaderboard.find_member(member) {
+                        Some(id) => Some(Command::Watch(Some(id), watch)),
+                        None => Some(Command::NotValid(format!(
+                            "I could not find any member named *{member}* on the leaderboard 🔍."
+                        ))),
+                    },
+                }
+            }
             _ => None,
         }
     }
+
+    /// Board for the year, restricted to the requested page. The page index is clamped to the
+    /// number of available pages.
+    pub fn board_page(
+        leaderboard: &ScrapedLeaderboard,
+        precomputed: &Precomputed,
+        year: i32,
+        scoring: Scoring,
+        detailed: bool,
+        page: usize,
+    ) -> Command {
+        // Columns widths are computed on the full board, so they are consistent across pages.
+        let formatted = precomputed.board(leaderboard, year, &scoring, detailed);
+        let pages = formatted
+            .lines()
+            .chunks(BOARD_PAGE_SIZE)
+            .into_iter()
+            .map(|mut lines| lines.join("\n"))
+            .collect::<Vec<String>>();
+        let n_pages = pages.len().max(1);
+        let page = page.min(n_pages - 1);
+
+        Command::LeaderboardDisplay(
+            year,
+            pages.get(page).cloned().unwrap_or_default(),
+            leaderboard.timestamp,
+            scoring,
+            detailed,
+            page,
+            n_pages,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::MemberId,
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+
+    #[test]
+    fn memoization_keys_are_normalized() {
+        let key = Command::memoization_key;
+        let year = current_aoc_year_day().0;
+
+        assert_eq!(key("!board"), key("!board local"));
+        assert_eq!(key("!board"), key(&format!("!board   {year}")));
+        assert_eq!(key("!board stars detailed"), key("!board detailed stars"));
+        assert_eq!(key("!tdf"), key("!tdf yellow"));
+        assert_ne!(key("!board"), key("!board stars"));
+        assert_ne!(key("!board 2019"), key("!board 2020"));
+    }
+
+    #[test]
+    fn absences_are_declared_for_a_member_and_days() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2019,
+                &[MemberFixture::new(2201, "Jingle Bells").with_day(1, 600, 60)],
+            ),
+        };
+        let absence = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            absence("!tdf absence Jingle Bells 3-5 2019"),
+            Some(Command::Absence(id, 2019, days)) if id.numeric == MemberId(2201) && days == vec![3, 4, 5]
+        ));
+        assert!(matches!(
+            absence("!tdf absence 2201 7 2019"),
+            Some(Command::Absence(_, 2019, days)) if days == vec![7]
+        ));
+        assert!(matches!(
+            absence("!tdf absence Jingle Bells 2019"),
+            Some(Command::NotValid(_))
+        ));
+        assert!(matches!(
+            absence("!tdf absence Jingle Bells 20-26 2019"),
+            Some(Command::NotValid(_))
+        ));
+    }
+
+    #[test]
+    fn worst_days_are_dropped_from_the_yellow_jersey() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2018,
+                &[MemberFixture::new(2602, "Rudolph").with_day(1, 600, 60)],
+            ),
+        };
+        let tdf = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            tdf("!tdf yellow dropworst=3 2018"),
+            Some(Command::StandingTdf(2018, None, _, _, Jersey::YELLOW, 3))
+        ));
+        assert!(matches!(
+            tdf("!tdf dropworst=2 2018"),
+            Some(Command::StandingTdf(2018, None, _, _, Jersey::YELLOW, 2))
+        ));
+        for invalid in [
+            "!tdf green dropworst=3 2018",
+            "!tdf yellow dropworst=3 5 2018",
+            "!tdf yellow dropworst=25 2018",
+            "!tdf yellow dropworst=many 2018",
+        ] {
+            assert!(
+                matches!(tdf(invalid), Some(Command::NotValid(_))),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn members_are_excluded_by_name_or_id() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2019,
+                &[MemberFixture::new(2231, "Off Road Elf").with_day(1, 600, 60)],
+            ),
+        };
+        let exclusion = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            exclusion("!exclude Off Road Elf"),
+            Some(Command::Exclusion(id, true)) if id.numeric == MemberId(2231)
+        ));
+        assert!(matches!(
+            exclusion("!exclude off 2231"),
+            Some(Command::Exclusion(id, false)) if id.numeric == MemberId(2231)
+        ));
+        assert!(matches!(
+            exclusion("!exclude off"),
+            Some(Command::NotValid(_))
+        ));
+        assert!(matches!(
+            exclusion("!exclude Rudolph"),
+            Some(Command::NotValid(_))
+        ));
+    }
+
+    #[test]
+    fn members_are_ranked_by_name_and_year() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2019,
+                &[
+                    MemberFixture::new(2251, "Frosty The Elf").with_day(1, 600, 60),
+                    MemberFixture::new(2252, "Sleigh").with_part1(1, 900),
+                    MemberFixture::new(2253, "Santa 2024").with_part1(1, 1200),
+                ],
+            ),
+        };
+        let rank = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            rank("!rank Frosty The Elf year=2019"),
+            Some(Command::Rank(rank)) if rank.position == 1 && rank.behind.is_some()
+        ));
+        assert!(matches!(
+            rank("!rank 2252 year=2019"),
+            Some(Command::Rank(rank)) if rank.position == 2 && rank.n_stars == 1
+        ));
+        // a trailing number is part of the member name, not the year
+        assert!(matches!(
+            rank("!rank Santa 2024 year=2019"),
+            Some(Command::Rank(rank)) if rank.position == 3
+        ));
+        assert!(matches!(rank("!rank"), Some(Command::NotValid(_))));
+        assert!(matches!(
+            rank("!rank year=2019"),
+            Some(Command::NotValid(_))
+        ));
+        assert!(matches!(
+            rank("!rank Sleigh year=20x9"),
+            Some(Command::NotValid(_))
+        ));
+        assert!(matches!(
+            rank("!rank Rudolph year=2019"),
+            Some(Command::NotValid(_))
+        ));
+    }
+
+    #[test]
+    fn season_delta_takes_an_aggregate_and_a_minimum_of_days() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2019,
+                &[
+                    MemberFixture::new(2261, "Tinsel")
+                        .with_day(1, 600, 60)
+                        .with_day(2, 600, 30),
+                    MemberFixture::new(2262, "Garland").with_day(1, 600, 10),
+                ],
+            ),
+        };
+        let delta = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            delta("!delta median 2 2019"),
+            Some(Command::SeasonDelta(2019, data, _, Aggregate::MEDIAN, 2)) if data.len() == 1
+        ));
+        assert!(matches!(
+            delta("!delta 1 2019"),
+            Some(Command::SeasonDelta(_, data, _, Aggregate::AVERAGE, 1)) if data[0].0 == "Garland"
+        ));
+        assert!(matches!(
+            delta("!delta 30 2019"),
+            Some(Command::NotValid(_))
+        ));
+    }
+
+    #[test]
+    fn detailed_board_shows_the_solve_times() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2019,
+                &[
+                    MemberFixture::new(2401, "Tinsel")
+                        .with_day(1, 600, 60)
+                        .with_day(2, 1200, 180),
+                    MemberFixture::new(2402, "Garland").with_part1(1, 300),
+                ],
+            ),
+        };
+        let board = |input: &str| match Command::build_from(
+            input.to_string(),
+            &leaderboard,
+            &Precomputed::default(),
+        ) {
+            Some(Command::LeaderboardDisplay(2019, board, _, scoring, detailed, 0, 1)) => {
+                (board, scoring, detailed)
+            }
+            _ => panic!("{input} is not a board"),
+        };
+
+        let (detailed, scoring, is_detailed) = board("!board detailed stars 2019");
+        assert!(matches!(scoring, Scoring::STARS) && is_detailed);
+        let lines = detailed.lines().collect::<Vec<&str>>();
+        // median of 00:11:00 and 00:23:00, average of 00:01:00 and 00:03:00
+        assert!(lines[0].ends_with("  00:17:00 00:02:00"), "{}", lines[0]);
+        assert!(lines[1].ends_with(
+            "  [ □ - - - - - - - - - - - - - - - - - - - - - - - -]         -        -"
+        ));
+
+        let (board, _scoring, is_detailed) = board("!board 2019");
+        assert!(!is_detailed && !board.contains("00:17:00"));
+    }
+
+    #[test]
+    fn commands_are_recognized_with_the_configured_prefix_and_aliases() {
+        let syntax = CommandSyntax::new(
+            "aoc ",
+            &HashMap::from([("board".to_string(), vec!["b".to_string()])]),
+        );
+        let command = |input: &str| {
+            let parsed = syntax.parse(input);
+            parsed.get("cmd").and_then(|cmd| syntax.command(cmd))
+        };
+
+        assert_eq!(command("aoc board stars"), Some("board"));
+        assert_eq!(command("aoc b 2022"), Some("board"));
+        assert_eq!(command("aoc bestsolutions 3"), Some("bestsolutions"));
+        assert_eq!(command("aoc bx"), None);
+        assert_eq!(command("!board"), None);
+        assert_eq!(syntax.parse("aoc b stars").get("option"), Some(&"stars"));
+    }
+
+    #[test]
+    fn days_ranges_are_parsed_before_days() {
+        let parsed = Command::parse_string("!fast median 1-10 2023");
+
+        assert_eq!(parsed.get("cmd"), Some(&"!fast"));
+        assert_eq!(parsed.get("option"), Some(&"median"));
+        assert_eq!(parsed.get("range"), Some(&"1-10"));
+        assert_eq!(parsed.get("year"), Some(&"2023"));
+        assert_eq!(parsed.get("day"), None);
+    }
+
+    #[test]
+    fn unknown_options_are_rejected_with_a_suggestion() {
+        let leaderboard = ScrapedLeaderboard::new();
+        let build = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+        let not_valid = |input: &str| match build(input) {
+            Some(Command::NotValid(msg)) => msg,
+            command => panic!("{input} is valid: {command:?}"),
+        };
+
+        let msg = not_valid("!fast p3 2018");
+        assert!(msg.contains("did you mean `p1`?"), "{msg}");
+        assert!(
+            msg.contains("`delta`, `p1`, `p2`, `limit`, `avg`, `median`"),
+            "{msg}"
+        );
+        assert!(not_valid("!board sars 2018").contains("did you mean `stars`?"));
+        assert!(not_valid("!tdf yelow 2018").contains("did you mean `yellow`?"));
+        let msg = not_valid("!heatmap everyone 2018");
+        assert!(!msg.contains("did you mean"), "{msg}");
+        assert!(msg.contains("`members`, `hours`"), "{msg}");
+
+        assert!(matches!(
+            build("!fast p2 2018"),
+            Some(Command::Ranking(2018, _, _, _, Ranking::PART2))
+        ));
+        assert!(matches!(
+            build("!board stars detailed 2018"),
+            Some(Command::LeaderboardDisplay(
+                2018,
+                _,
+                _,
+                Scoring::STARS,
+                true,
+                ..
+            ))
+        ));
+    }
+
+    #[test]
+    fn simulations_are_written_with_a_member_and_a_day() {
+        let leaderboard = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2016,
+                &[
+                    MemberFixture::new(2301, "Holly Jolly").with_day(1, 600, 60),
+                    MemberFixture::new(2302, "Mistletoe").with_part1(2, 900),
+                ],
+            ),
+        };
+        let what_if = |input: &str| {
+            Command::build_from(input.to_string(), &leaderboard, &Precomputed::default())
+        };
+
+        assert!(matches!(
+            what_if("!whatif Holly Jolly completes day 2 2016"),
+            Some(Command::WhatIf(projection)) if projection.name == "Holly Jolly" && projection.n_stars == 2
+        ));
+        assert!(matches!(
+            what_if("!whatif 2302 completes day 2 2016"),
+            Some(Command::WhatIf(projection)) if projection.n_stars == 1
+        ));
+        assert!(matches!(
+            what_if("!whatif Holly Jolly completes day 1 2016"),
+            Some(Command::NotValid(_))
+        ));
+        assert!(matches!(
+            what_if("!whatif Holly Jolly 2"),
+            Some(Command::NotValid(_))
+        ));
+    }
 }
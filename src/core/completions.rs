@@ -0,0 +1,114 @@
+use crate::{
+    core::leaderboard::{Entry, Identifier, Leaderboard},
+    utils::{format_duration, format_duration_with_days},
+};
+use chrono::{DateTime, Duration, Utc};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+// Stars to collect to complete an event (2 parts for each of the 25 days)
+pub const EVENT_STARS: usize = 50;
+
+/// Member having collected all the stars of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullCompletion {
+    pub member: Identifier,
+    pub year: i32,
+    // order of completion among the members (1 based)
+    pub rank: usize,
+    pub completed_at: DateTime<Utc>,
+    // time to collect the last star, since the unlock of the last puzzle
+    pub time: Duration,
+}
+
+impl FullCompletion {
+    /// Time to collect the last star, with the days if it took more than a day.
+    pub fn formatted_time(&self) -> String {
+        match self.time < Duration::days(1) {
+            true => format_duration(self.time),
+            false => format_duration_with_days(self.time),
+        }
+    }
+}
+
+/// Members having collected all the stars of the year, in the order they completed the event.
+pub fn full_completions(leaderboard: &Leaderboard, year: i32) -> Vec<FullCompletion> {
+    leaderboard
+        .ranked_entries()
+        .filter(|e| e.year == year)
+        .into_group_map_by(|e| &e.id)
+        .into_iter()
+        .filter(|(_id, entries)| entries.len() == EVENT_STARS)
+        .filter_map(|(id, entries)| {
+            let last_star = entries.into_iter().max()?;
+            let time = last_star.timestamp - Entry::puzzle_unlock(year, 25).ok()?;
+            Some((id, last_star, time))
+        })
+        .sorted_unstable_by_key(|(_id, last_star, _time)| *last_star)
+        .enumerate()
+        .map(|(rank_minus_one, (id, last_star, time))| FullCompletion {
+            member: id.clone(),
+            year,
+            rank: rank_minus_one + 1,
+            completed_at: last_star.timestamp,
+            time,
+        })
+        .collect()
+}
+
+/// Members having collected all the stars of the year with the entries newly merged into the
+/// leaderboard.
+pub fn new_full_completions(
+    previous: &Leaderboard,
+    current: &Leaderboard,
+    year: i32,
+) -> Vec<FullCompletion> {
+    let completed = full_completions(previous, year)
+        .into_iter()
+        .map(|c| c.member.numeric)
+        .collect::<HashSet<_>>();
+    full_completions(current, year)
+        .into_iter()
+        .filter(|c| !completed.contains(&c.member.numeric))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn members_completing_the_event_are_detected_once() {
+        let year = 2002;
+        let complete = |id, name, delta| {
+            (1..=25).fold(MemberFixture::new(id, name), |m, day| {
+                m.with_day(day, 600, delta)
+            })
+        };
+        let almost = (1..=24)
+            .fold(MemberFixture::new(2702, "Cupid"), |m, day| {
+                m.with_day(day, 900, 60)
+            })
+            .with_part1(25, 900);
+        let mut completed = almost.clone();
+        completed.stars.push((25, 2, 960));
+        let previous = private_leaderboard(year, &[complete(2701, "Blitzen", 3600), almost]);
+        let current = private_leaderboard(year, &[complete(2701, "Blitzen", 3600), completed]);
+
+        let completions = full_completions(&current, year);
+        assert_eq!(
+            completions
+                .iter()
+                .map(|c| (c.member.name.as_str(), c.rank, c.time.num_seconds()))
+                .collect::<Vec<_>>(),
+            vec![("Cupid", 1, 960), ("Blitzen", 2, 4200)]
+        );
+
+        let new = new_full_completions(&previous, &current, year);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].member.name, "Cupid");
+        assert_eq!(new[0].formatted_time(), "00:16:00");
+        assert!(new_full_completions(&current, &current, year).is_empty());
+    }
+}
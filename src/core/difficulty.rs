@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use slack_morphism::SlackUserId;
+use std::collections::HashMap;
+
+// Minimum number of previous days recorded for the difficulty index to be meaningful
+const MIN_HISTORY_DAYS: usize = 10;
+// Share of the previous days that must have been easier for a day to be among the hardest
+const HARDEST_DAYS_PERCENTILE: f64 = 0.9;
+
+/// (reaction, rating) proposed in the daily difficulty poll, from 1 (easy) to 5 (very hard).
+pub const POLL_RATINGS: [(&str, u8); 5] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+];
+
+/// Rating of each user who voted in a difficulty poll.
+pub type PollVotes = HashMap<SlackUserId, u8>;
+
+/// Time taken by the last (100th) part 2 finisher of the global leaderboard of a day, recorded
+/// when the global leaderboard is complete.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlobalCompletion {
+    pub year: i32,
+    pub day: u8,
+    pub p2_slow_sec: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayDifficulty {
+    // completion time relative to the historical median (> 1 means harder than usual)
+    pub index: f64,
+    // share of the previous days that were completed faster
+    pub percentile: f64,
+    pub n_days: usize,
+}
+
+impl DayDifficulty {
+    pub fn is_among_hardest(&self) -> bool {
+        self.percentile >= HARDEST_DAYS_PERCENTILE
+    }
+}
+
+/// Difficulty of a day as felt by the members, from the votes of the daily poll.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DifficultyRating {
+    // average of the votes, between 1 and 5
+    pub average: f64,
+    pub n_votes: usize,
+}
+
+/// Difficulty of a day compared to the previously recorded days, None if not enough history.
+pub fn day_difficulty(p2_slow_sec: i64, history: &[GlobalCompletion]) -> Option<DayDifficulty> {
+    if history.len() < MIN_HISTORY_DAYS {
+        return None;
+    };
+    let mut times = history.iter().map(|c| c.p2_slow_sec).collect::<Vec<i64>>();
+    times.sort_unstable();
+
+    let n_days = times.len();
+    let median = match n_days % 2 {
+        0 => (times[n_days / 2 - 1] + times[n_days / 2]) as f64 / 2.0,
+        _ => times[n_days / 2] as f64,
+    };
+    let n_easier = times.iter().filter(|t| **t < p2_slow_sec).count();
+
+    Some(DayDifficulty {
+        index: p2_slow_sec as f64 / median.max(1.0),
+        percentile: n_easier as f64 / n_days as f64,
+        n_days,
+    })
+}
+
+/// Count a reaction added to (or removed from) the poll, the latest rating of a user replacing
+/// their previous one. Reactions that are not ratings are ignored.
+pub fn record_poll_vote(votes: &mut PollVotes, user: &SlackUserId, reaction: &str, added: bool) {
+    let Some((_reaction, rating)) = POLL_RATINGS.iter().find(|(r, _)| *r == reaction) else {
+        return;
+    };
+    match added {
+        true => {
+            votes.insert(user.clone(), *rating);
+        }
+        // the previous ratings of the user are not restored when the latest one is withdrawn
+        false if votes.get(user) == Some(rating) => {
+            votes.remove(user);
+        }
+        false => (),
+    };
+}
+
+/// Rating of the day from the votes of its poll, None if nobody voted.
+pub fn community_rating(votes: &PollVotes) -> Option<DifficultyRating> {
+    let n_votes = votes.len();
+    let total = votes
+        .values()
+        .map(|rating| usize::from(*rating))
+        .sum::<usize>();
+    (n_votes > 0).then(|| DifficultyRating {
+        average: total as f64 / n_votes as f64,
+        n_votes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(times_min: &[i64]) -> Vec<GlobalCompletion> {
+        times_min
+            .iter()
+            .enumerate()
+            .map(|(i, t)| GlobalCompletion {
+                year: 2022,
+                day: i as u8 + 1,
+                p2_slow_sec: t * 60,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn difficulty_is_relative_to_the_historical_median() {
+        let history = history(&[10, 12, 15, 18, 20, 22, 25, 30, 40, 60]);
+
+        let hard = day_difficulty(90 * 60, &history).unwrap();
+        assert_eq!(hard.index, 90.0 / 21.0);
+        assert_eq!(hard.percentile, 1.0);
+        assert!(hard.is_among_hardest());
+
+        let usual = day_difficulty(21 * 60, &history).unwrap();
+        assert_eq!(usual.index, 1.0);
+        assert_eq!(usual.percentile, 0.5);
+        assert!(!usual.is_among_hardest());
+    }
+
+    #[test]
+    fn community_rating_counts_the_latest_vote_of_each_user() {
+        let user = |id: &str| SlackUserId(id.to_string());
+        let mut votes = PollVotes::new();
+        for (id, reaction, added) in [
+            ("U0VIXEN", "two", true),
+            ("U0COMET", "one", true),
+            ("U0COMET", "four", true),
+            ("U0CUPID", "tada", true),
+            ("U0CUPID", "five", true),
+            ("U0CUPID", "three", false),
+            ("U0DASHER", "one", true),
+            ("U0DASHER", "one", false),
+        ] {
+            record_poll_vote(&mut votes, &user(id), reaction, added);
+        }
+        let rating = community_rating(&votes).unwrap();
+        assert_eq!(rating.n_votes, 3);
+        assert_eq!(rating.average, 11.0 / 3.0);
+
+        assert!(community_rating(&PollVotes::new()).is_none());
+    }
+
+    #[test]
+    fn difficulty_needs_enough_history() {
+        assert!(day_difficulty(3600, &history(&[10, 20, 30])).is_none());
+    }
+}
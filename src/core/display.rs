@@ -1,12 +1,111 @@
 use crate::{
     core::{
+        gaps::{Gap, Race},
         leaderboard::Identifier,
-        standings::{DailyStarsAndScores, PENALTY_UNFINISHED_DAY},
+        names,
+        standings::{penalty_unfinished_day, DailyStarsAndScores, SolveTimes},
     },
     utils::{format_duration, format_duration_with_days},
 };
 use chrono::Duration;
 use itertools::Itertools;
+use std::collections::HashMap;
+
+// Maximum width of the names in the displays, longer names being cut with an ellipsis
+pub const NAME_MAX_WIDTH: usize = 24;
+
+/// Name of the member as written in the displays: without emojis (their width depends on the
+/// font), and cut to the maximum width.
+pub fn display_name(id: &Identifier) -> String {
+    fit_name(&names::resolve(id), NAME_MAX_WIDTH)
+}
+
+fn fit_name(name: &str, max_width: usize) -> String {
+    let stripped = name
+        .chars()
+        .filter(|c| !is_emoji(*c))
+        .collect::<String>()
+        .split_whitespace()
+        .join(" ");
+    // names only made of emojis are kept as is
+    let name = match stripped.is_empty() {
+        true => name.to_string(),
+        false => stripped,
+    };
+    if text_width(&name) <= max_width {
+        return name;
+    };
+    let mut width = 0;
+    let cut = name
+        .chars()
+        .take_while(|c| {
+            width += char_width(*c);
+            // one column left for the ellipsis
+            width < max_width
+        })
+        .collect::<String>();
+    format!("{}…", cut.trim_end())
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        // pictographs, emoticons, transport, flags, skin tones, ...
+        0x1F000..=0x1FAFF
+            // miscellaneous symbols and dingbats
+            | 0x2600..=0x27BF
+            | 0x2300..=0x23FF
+            | 0x2B50..=0x2B55
+            // joiner, variation selectors, keycaps and tags of the emoji sequences
+            | 0x200D
+            | 0xFE0E..=0xFE0F
+            | 0x20E3
+            | 0xE0020..=0xE007F
+    )
+}
+
+// Number of columns taken by the character in a monospace font.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // combining marks and zero width characters
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        // east asian wide and fullwidth characters, and emojis
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Number of columns taken by the text in a monospace font.
+pub fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Text padded with spaces to the width, in columns of a monospace font.
+pub fn pad(text: &str, width: usize) -> String {
+    format!(
+        "{text}{}",
+        " ".repeat(width.saturating_sub(text_width(text)))
+    )
+}
 
 pub fn tdf_time_yearly(entries: &[(&Identifier, i64, i64)]) -> String {
     // calculate width for positions
@@ -17,16 +116,16 @@ pub fn tdf_time_yearly(entries: &[(&Identifier, i64, i64)]) -> String {
     // the length of the longest name, plus one for ':'
     let width_name = 1 + entries
         .iter()
-        .map(|(id, _, _)| id.name.len())
+        .map(|(id, _, _)| text_width(&display_name(id)))
         .max()
         .unwrap_or_default();
 
     // Max possible width for duration is all days above cutoff time
     let width_duration =
-        format_duration_with_days(Duration::seconds(*PENALTY_UNFINISHED_DAY * 25)).len();
+        format_duration_with_days(Duration::seconds(penalty_unfinished_day() * 25)).len();
     // Max possible width for delta duration is all days above cutoff time
     let width_delta_duration =
-        format_duration(Duration::seconds(*PENALTY_UNFINISHED_DAY * 25)).len() + 3;
+        format_duration(Duration::seconds(penalty_unfinished_day() * 25)).len() + 3;
     // Max possible width for penalties
     let width_penalties = "(25 stages out)".len() + 1;
 
@@ -42,10 +141,10 @@ pub fn tdf_time_yearly(entries: &[(&Identifier, i64, i64)]) -> String {
         .enumerate()
         .map(|(idx, (id, total_seconds, penalties))| {
             format!(
-                "{:>width_pos$}) {:<width_name$} {:>width_duration$} {:>width_delta_duration$} {:>width_penalties$}",
+                "{:>width_pos$}) {} {:>width_duration$} {:>width_delta_duration$} {:>width_penalties$}",
                 // idx is zero-based
                 idx + 1,
-                id.name,
+                pad(&display_name(id), width_name),
                 format_duration_with_days(Duration::seconds(*total_seconds)),
                 match idx == 0 {
                     true => "".to_string(),
@@ -73,7 +172,7 @@ pub fn tdf_points_yearly(entries: &[(&Identifier, i64, i64)]) -> String {
     // the length of the longest name, plus one for ':'
     let width_name = 1 + entries
         .iter()
-        .map(|(id, _, _)| id.name.len())
+        .map(|(id, _, _)| text_width(&display_name(id)))
         .max()
         .unwrap_or_default();
 
@@ -91,10 +190,10 @@ pub fn tdf_points_yearly(entries: &[(&Identifier, i64, i64)]) -> String {
         .enumerate()
         .map(|(idx, (id, total_points, scored_days))| {
             format!(
-                "{:>width_pos$}) {:<width_name$} {:>width_points$} {:>width_scored$}",
+                "{:>width_pos$}) {} {:>width_points$} {:>width_scored$}",
                 // idx is zero-based
                 idx + 1,
-                id.name,
+                pad(&display_name(id), width_name),
                 total_points,
                 format!("(scored {:0>2} days)", scored_days),
             )
@@ -112,7 +211,7 @@ pub fn tdf_points_daily(entries: &[(&Identifier, usize)]) -> String {
     // the length of the longest name, plus one for ':'
     let width_name = 1 + entries
         .iter()
-        .map(|(id, _)| id.name.len())
+        .map(|(id, _)| text_width(&display_name(id)))
         .max()
         .unwrap_or_default();
 
@@ -121,17 +220,17 @@ pub fn tdf_points_daily(entries: &[(&Identifier, usize)]) -> String {
         .enumerate()
         .map(|(idx, (id, points))| {
             format!(
-                "{:>width_pos$}) {:<width_name$} {points}",
+                "{:>width_pos$}) {} {points}",
                 // idx is zero-based
                 idx + 1,
-                id.name,
+                pad(&display_name(id), width_name),
             )
         })
         .join("\n")
 }
 
-// Daily times
-pub fn tdf_time_daily(entries: &[(String, String)]) -> String {
+// Daily yellow jersey stage
+pub fn tdf_stage_daily(entries: &[(&Identifier, Option<Duration>)]) -> String {
     // calculate width for positions
     // the width of the maximum position to be displayed, plus one for ')'
     let width_pos = entries.len().to_string().len();
@@ -140,26 +239,71 @@ pub fn tdf_time_daily(entries: &[(String, String)]) -> String {
     // the length of the longest name, plus one for ':'
     let width_name = 1 + entries
         .iter()
-        .map(|(name, _)| name.len())
+        .map(|(id, _)| text_width(&display_name(id)))
         .max()
         .unwrap_or_default();
 
+    // Max possible width for the stage time is the cutoff time
+    let width_duration =
+        format_duration_with_days(Duration::seconds(penalty_unfinished_day())).len();
+    let width_delta_duration =
+        format_duration_with_days(Duration::seconds(penalty_unfinished_day())).len() + 4;
+
+    // Stage winner time
+    let fastest = entries
+        .first()
+        .and_then(|(_id, time)| *time)
+        .unwrap_or_else(Duration::zero);
+
     entries
         .iter()
         .enumerate()
-        .map(|(idx, (name, time))| {
+        .map(|(idx, (id, time))| {
+            let (time, gap, annotation) = match time {
+                Some(time) => (
+                    format_duration_with_days(*time),
+                    match idx == 0 {
+                        true => "".to_string(),
+                        false => format!("(+ {})", format_duration_with_days(*time - fastest)),
+                    },
+                    // counted as the penalty time in the yellow jersey standings
+                    match time.num_seconds() > penalty_unfinished_day() {
+                        true => "(over cutoff)",
+                        false => "",
+                    },
+                ),
+                None => ("DNF".to_string(), "".to_string(), "(stage out)"),
+            };
             format!(
-                "{:>width_pos$}) {:<width_name$} {time}",
+                "{:>width_pos$}) {} {:>width_duration$} {:>width_delta_duration$} {}",
                 // idx is zero-based
                 idx + 1,
-                name,
+                pad(&display_name(id), width_name),
+                time,
+                gap,
+                annotation,
             )
+            .trim_end()
+            .to_string()
         })
         .join("\n")
 }
 
-// Display board from given entries
-pub fn board(entries: Vec<(&Identifier, DailyStarsAndScores, usize)>) -> String {
+// Days won per member, with a header naming the columns
+pub fn wins(entries: &[(&Identifier, usize, usize, usize, usize)]) -> String {
+    table_with_header(
+        &format!("{:>3} {:>3} {:>5} {:>5}", "p1", "p2", "delta", "total"),
+        entries
+            .iter()
+            .map(|(id, p1, p2, delta, total)| {
+                (*id, format!("{p1:>3} {p2:>3} {delta:>5} {total:>5}"))
+            })
+            .collect(),
+    )
+}
+
+// Gaps of a race to the member ahead, and to the leader in parentheses
+pub fn gaps(entries: &[Gap], race: Race) -> String {
     // calculate width for positions
     // the width of the maximum position to be displayed, plus one for ')'
     let width_pos = entries.len().to_string().len();
@@ -168,28 +312,147 @@ pub fn board(entries: Vec<(&Identifier, DailyStarsAndScores, usize)>) -> String
     // the length of the longest name, plus one for ':'
     let width_name = 1 + entries
         .iter()
-        .map(|(id, _scores, _total)| id.name.len())
+        .map(|gap| text_width(&display_name(&gap.member)))
+        .max()
+        .unwrap_or_default();
+
+    let width = |format: &dyn Fn(&Gap) -> String| {
+        entries
+            .iter()
+            .map(|gap| format(gap).len())
+            .max()
+            .unwrap_or_default()
+    };
+    let width_total = width(&|gap| race.format_total(gap.total));
+    let width_ahead = width(&|gap| gap.ahead.map(|g| race.format_gap(g)).unwrap_or_default());
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(idx, gap)| {
+            let leader = match gap.ahead {
+                // the gap to the leader is the same as to the member ahead for the 2nd
+                Some(_) if idx > 1 => format!("({})", race.format_gap(gap.leader)),
+                _ => String::new(),
+            };
+            format!(
+                "{:>width_pos$}) {} {:>width_total$} {:>width_ahead$} {leader}",
+                // idx is zero-based
+                idx + 1,
+                pad(&display_name(&gap.member), width_name),
+                race.format_total(gap.total),
+                gap.ahead.map(|g| race.format_gap(g)).unwrap_or_default(),
+            )
+            .trim_end()
+            .to_string()
+        })
+        .join("\n")
+}
+
+// All-time standings, with a header naming the columns
+pub fn all_time(entries: &[(&Identifier, usize, usize, usize)]) -> String {
+    table_with_header(
+        &format!("{:>5} {:>5} {:>6}", "stars", "score", "events"),
+        entries
+            .iter()
+            .map(|(id, stars, score, n_years)| (*id, format!("{stars:>5} {score:>5} {n_years:>6}")))
+            .collect(),
+    )
+}
+
+// Ranked members followed by their columns, formatted to the widths of the columns names of the
+// header.
+fn table_with_header(header: &str, rows: Vec<(&Identifier, String)>) -> String {
+    // the width of the maximum position to be displayed, the ')' being written after it
+    let width_pos = rows.len().to_string().len();
+
+    // the length of the longest name, plus one for the space before the columns
+    let width_name = 1 + rows
+        .iter()
+        .map(|(id, _columns)| text_width(&display_name(id)))
+        .max()
+        .unwrap_or_default();
+
+    std::iter::once(format!("{:>width_pos$}  {:<width_name$} {header}", "", ""))
+        .chain(rows.iter().enumerate().map(|(idx, (id, columns))| {
+            format!(
+                "{:>width_pos$}) {} {columns}",
+                // idx is zero-based
+                idx + 1,
+                pad(&display_name(id), width_name),
+            )
+        }))
+        .join(
+            "
+",
+        )
+}
+
+// Display board from given entries, with the median part 2 time and average delta columns if the
+// solve times are given (detailed board). The members excluded from the standings are listed
+// last, without position nor score.
+pub fn board(
+    entries: Vec<(&Identifier, DailyStarsAndScores, usize)>,
+    excluded: Vec<(&Identifier, DailyStarsAndScores)>,
+    solve_times: Option<&HashMap<&Identifier, SolveTimes>>,
+) -> String {
+    let rows = entries
+        .into_iter()
+        .enumerate()
+        // idx is zero-based
+        .map(|(idx, (id, scores, total))| ((idx + 1).to_string(), id, scores, total.to_string()))
+        .chain(
+            excluded
+                .into_iter()
+                .map(|(id, scores)| ("-".to_string(), id, scores, "-".to_string())),
+        )
+        .collect::<Vec<(String, &Identifier, DailyStarsAndScores, String)>>();
+
+    // calculate width for positions
+    // the width of the maximum position to be displayed, plus one for ')'
+    let width_pos = rows
+        .iter()
+        .map(|(pos, _id, _scores, _total)| pos.len())
+        .max()
+        .unwrap_or(1);
+
+    // calculate width for names
+    // the length of the longest name, plus one for ':'
+    let width_name = 1 + rows
+        .iter()
+        .map(|(_pos, id, _scores, _total)| text_width(&display_name(id)))
         .max()
         .unwrap_or_default();
 
     // calculate width for scores
     // the width of the maximum score, formatted to two decimal places
-    let width_score = entries
+    let width_score = rows
         .iter()
-        .map(|(_id, _scores, total)| total)
+        .map(|(_pos, _id, _scores, total)| 1 + total.len())
         .max()
-        .map(|s| 1 + s.to_string().len())
         .unwrap_or_default();
 
-    entries
+    // median part 2 time and average delta of the member, `-` for the missing times
+    let times = |id: &Identifier| {
+        let format = |time: Option<Duration>| time.map_or("-".to_string(), format_duration);
+        solve_times.map(|solve_times| {
+            let times = solve_times.get(id).copied().unwrap_or_default();
+            (format(times.median_p2), format(times.average_delta))
+        })
+    };
+    let width_times = rows
         .iter()
-        .enumerate()
-        .map(|(idx, (id, scores, total))| {
+        .filter_map(|(_pos, id, _scores, _total)| times(id))
+        .map(|(median_p2, average_delta)| median_p2.len().max(average_delta.len()))
+        .max()
+        .unwrap_or_default();
+
+    rows.iter()
+        .map(|(pos, id, scores, total)| {
             format!(
-                "{:>width_pos$}) {:<width_name$} {:>width_score$}  [{}]",
-                // idx is zero-based
-                idx + 1,
-                id.name,
+                "{:>width_pos$}) {} {:>width_score$}  [{}]{}",
+                pos,
+                pad(&display_name(id), width_name),
                 total,
                 scores
                     .iter()
@@ -199,8 +462,135 @@ pub fn board(entries: Vec<(&Identifier, DailyStarsAndScores, usize)>) -> String
                         2 => " ■",
                         _ => unreachable!(),
                     })
-                    .collect::<String>()
+                    .collect::<String>(),
+                times(id).map_or(String::new(), |(median_p2, average_delta)| format!(
+                    "  {median_p2:>width_times$} {average_delta:>width_times$}"
+                ))
             )
         })
         .join("\n")
 }
+
+// Stars remaining to collect at the end of each hour following the puzzle release, with a
+// header naming the columns
+pub fn burndown(hours: &[(usize, usize)], n_stars: usize) -> String {
+    // width of the bar of the stars remaining at the release
+    const WIDTH_BAR: usize = 20;
+    let width_count = n_stars.to_string().len().max("left".len());
+
+    let header = format!(
+        "{:>4} {:>width_count$} {:>width_count$} {:>width_count$}",
+        "", "p1", "p2", "left"
+    );
+
+    std::iter::once(header)
+        .chain(
+            hours
+                .iter()
+                .scan(n_stars, |left, (p1, p2)| {
+                    *left = left.saturating_sub(p1 + p2);
+                    Some((*p1, *p2, *left))
+                })
+                .enumerate()
+                .map(|(idx, (p1, p2, left))| {
+                    let bar = match n_stars {
+                        0 => 0,
+                        n => (left * WIDTH_BAR).div_ceil(n),
+                    };
+                    format!(
+                        "+{:0>2}h {p1:>width_count$} {p2:>width_count$} {left:>width_count$} {}",
+                        // idx is zero-based, the row is the end of the hour
+                        idx + 1,
+                        "█".repeat(bar),
+                    )
+                }),
+        )
+        .join("\n")
+}
+
+// Stars collected per row (member or hour) and day, as intensity levels relative to the busiest
+// cell, with a header naming the days
+pub fn heatmap(rows: &[(String, [usize; 25])], n_days: usize) -> String {
+    const LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+    let max = rows
+        .iter()
+        .flat_map(|(_, days)| days[..n_days].iter())
+        .max()
+        .copied()
+        .unwrap_or_default();
+    let width_label = rows
+        .iter()
+        .map(|(label, _)| text_width(label))
+        .max()
+        .unwrap_or_default();
+
+    let header = format!(
+        "{:width_label$}{}",
+        "",
+        (1..=n_days).map(|day| format!("{day:>3}")).join("")
+    );
+
+    std::iter::once(header)
+        .chain(rows.iter().map(|(label, days)| {
+            let cells = days[..n_days]
+                .iter()
+                .map(|count| {
+                    let level = match (count, max) {
+                        (0, _) | (_, 0) => 0,
+                        (count, max) => (count * (LEVELS.len() - 1)).div_ceil(max),
+                    };
+                    format!(" {}", LEVELS[level].to_string().repeat(2))
+                })
+                .join("");
+            format!("{}{cells}", pad(label, width_label))
+        }))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::leaderboard::MemberId;
+
+    #[test]
+    fn names_are_stripped_of_emojis_and_cut() {
+        assert_eq!(fit_name("🎄 Jingle 🔔 Bells 👨‍👩‍👧", 24), "Jingle Bells");
+        assert_eq!(fit_name("🎅🤶", 24), "🎅🤶");
+        assert_eq!(
+            fit_name("Sugarplum Mary of the North Pole", 24),
+            "Sugarplum Mary of the N…"
+        );
+        assert_eq!(
+            text_width(&fit_name("Sugarplum Mary of the North Pole", 24)),
+            24
+        );
+        // wide characters take two columns
+        assert_eq!(text_width("圣诞老人"), 8);
+        assert_eq!(fit_name("圣诞老人的小精灵们", 8), "圣诞老…");
+        assert_eq!(text_width("Noe\u{0308}l"), 4);
+        assert_eq!(pad("圣诞", 6), "圣诞  ");
+    }
+
+    #[test]
+    fn wins_columns_are_aligned_with_the_header() {
+        let (elf, santa) = (
+            Identifier {
+                name: "Elf".to_string(),
+                numeric: MemberId(4001),
+            },
+            Identifier {
+                name: "Santa".to_string(),
+                numeric: MemberId(4002),
+            },
+        );
+        assert_eq!(
+            wins(&[(&santa, 3, 2, 1, 6), (&elf, 0, 1, 0, 1)]),
+            [
+                "           p1  p2 delta total",
+                "1) Santa    3   2     1     6",
+                "2) Elf      0   1     0     1",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,29 +1,54 @@
 use crate::{
+    client::{aoc::AoC, provider::EventProvider},
     core::{
+        awards::SeasonAwards,
+        chat::{ChatId, MessageId, UserId},
         commands::Command,
-        leaderboard::{LeaderboardStatistics, ProblemPart},
-        standings::Ranking,
+        completions::FullCompletion,
+        difficulty::{DayDifficulty, DifficultyRating},
+        faq::FaqReply,
+        gaps::Race,
+        heatmap::{HeatmapRows, HEATMAP_MAX_MEMBERS},
+        images::{Line, TextImage},
+        leaderboard::{Identifier, LeaderboardStatistics, ProblemPart},
+        membership::{MembershipChange, PRIVATE_LEADERBOARD_MAX_MEMBERS},
+        names,
+        predictions::Guess,
+        preview::TemplatePreview,
+        recap::WeeklyRecap,
+        review::{DayReview, YearReview},
+        splits::NegativeSplit,
+        standings::{RankChange, Ranking, Scoring, StageWinner},
         templates::MessageTemplate,
+        theme,
+        watchlist::WatchAlert,
     },
+    error::BotError,
+    scheduler::heartbeat::Heartbeat,
     utils::{
         current_aoc_year_day, format_duration, format_rank, ordinal_number_suffix, DayHighlight,
+        Season,
     },
 };
-use chrono::{Datelike, Local};
+use chrono::{DateTime, Datelike, Duration, Local, Utc};
 use itertools::Itertools;
 use minijinja::context;
-use slack_morphism::{SlackChannelId, SlackTs};
+use slack_morphism::SlackUserId;
 use std::fmt;
+use strum::IntoStaticStr;
 use text_to_ascii_art::to_art;
 
-const MEDALS: [&str; 3] = ["🥇", "🥈", "🥉"];
-const TROPHIES: [&str; 5] = ["🏆", "🥈", "🥉", "🍫", "🍬"];
+// Longer messages are split, as recommended by Slack for `chat.postMessage`
+const MESSAGE_MAX_LENGTH: usize = 4000;
+// Length of the excerpts of the posted messages listed by the `auditlog` command
+const AUDIT_EXCERPT_MAX_LENGTH: usize = 80;
 
-fn symbols_prefix<'a>(symbols: &'a [&'static str]) -> impl Iterator<Item = String> + 'a {
+fn symbols_prefix(symbols: &[String]) -> impl Iterator<Item = String> + '_ {
     let num = symbols.len();
     symbols
         .iter()
-        .chain([" "].iter().cycle())
+        .map(String::as_str)
+        .chain(std::iter::repeat(" "))
         .enumerate()
         .map(move |(i, s)| match i + 1 {
             n if (1..=num).contains(&n) => format!("{s} "),
@@ -32,29 +57,402 @@ fn symbols_prefix<'a>(symbols: &'a [&'static str]) -> impl Iterator<Item = Strin
         })
 }
 
-#[derive(Debug)]
+/// New completions grouped by day, today's completions first, each day under its own header.
+fn new_entries_sections(entries: &[DayHighlight]) -> Vec<String> {
+    let (year, today) = current_aoc_year_day();
+
+    entries
+        .iter()
+        .into_group_map_by(|h| (h.year, h.day))
+        .into_iter()
+        .sorted_by_key(|((y, d), _entries)| (!(*y == year && *d == today), *y, *d))
+        .map(|((y, d), day_entries)| {
+            let template = match y == year && d == today {
+                true => MessageTemplate::NewEntriesToday,
+                false => MessageTemplate::NewEntriesLate,
+            };
+            template
+                .get()
+                .render(context! {
+                    year => y,
+                    day => d,
+                    current_year => y == year,
+                    completions => day_entries,
+                })
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Sections packed in as few messages as possible, none exceeding the maximum length. A section
+/// too long for a single message is split on its lines, its header being repeated.
+fn batch_sections(sections: Vec<String>, max_len: usize) -> Vec<String> {
+    let mut messages = vec![];
+    let mut current = String::new();
+    for chunk in sections.iter().flat_map(|s| split_section(s, max_len)) {
+        if !current.is_empty() && current.len() + 1 + chunk.len() > max_len {
+            messages.push(std::mem::take(&mut current));
+        };
+        if !current.is_empty() {
+            current.push('\n');
+        };
+        current.push_str(&chunk);
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    };
+    messages
+}
+
+fn split_section(section: &str, max_len: usize) -> Vec<String> {
+    if section.len() <= max_len {
+        return vec![section.to_string()];
+    };
+    let mut lines = section.lines();
+    let header = lines.next().unwrap_or_default();
+    let continued = format!("{header} (continued)");
+
+    let mut chunks = vec![];
+    let mut chunk = header.to_string();
+    for line in lines {
+        if chunk.len() + 1 + line.len() > max_len && chunk != header && chunk != continued {
+            chunks.push(std::mem::replace(&mut chunk, continued.clone()));
+        };
+        chunk.push('\n');
+        chunk.push_str(line);
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+/// Message split on its lines in chunks not exceeding the maximum length. A code block cut between
+/// two chunks is closed and reopened, its text table keeping the column widths computed on the
+/// whole table.
+pub fn split_message(message: &str, max_len: usize) -> Vec<String> {
+    if message.len() <= max_len {
+        return vec![message.to_string()];
+    };
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    let mut in_code_block = false;
+    for line in message.lines() {
+        // room kept to close the code block
+        let closing = if in_code_block { 4 } else { 0 };
+        if !chunk.is_empty() && chunk.len() + 1 + line.len() + closing > max_len {
+            if in_code_block {
+                chunk.push_str("\n```");
+            };
+            chunks.push(std::mem::take(&mut chunk));
+            if in_code_block {
+                chunk.push_str("```");
+            };
+        };
+        if !chunk.is_empty() {
+            chunk.push('\n');
+        };
+        chunk.push_str(line);
+        in_code_block ^= line.matches("```").count() % 2 == 1;
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+/// Context of the daily summary templates (message and email), the rankings being prefixed
+/// with the trophies of the theme.
+fn daily_summary_context(
+    year: &i32,
+    day: &u8,
+    p1_data: &[(String, String)],
+    p2_data: &[(String, String)],
+    delta_data: &[(String, String)],
+    splits: &[NegativeSplit],
+    difficulty: &Option<DifficultyRating>,
+) -> minijinja::Value {
+    // Prefix with medal or ranking
+    let prefixed = |data: &[(String, String)]| {
+        data.iter()
+            .zip(symbols_prefix(&theme::current().trophies))
+            .map(|((name, score), prefix)| (prefix, name.clone(), format!("{:>9}", score)))
+            .take(5)
+            .collect::<Vec<(String, String, String)>>()
+    };
+    context! {
+        year => year,
+        day => format!("{day}{}", ordinal_number_suffix((*day).into())),
+        ranking_p1 => prefixed(p1_data),
+        ranking_p2 => prefixed(p2_data),
+        ranking_delta => prefixed(delta_data),
+        negative_splits => splits
+            .iter()
+            .map(|split| (
+                names::resolve(&split.member),
+                format_duration(split.part1),
+                format_duration(split.delta),
+            ))
+            .collect::<Vec<(String, String, String)>>(),
+        difficulty => difficulty.as_ref().map(|rating| context! {
+            average => format!("{:.1}", rating.average),
+            n_votes => rating.n_votes,
+        }),
+    }
+}
+
+/// First line of a posted message, shortened and without backticks to be quoted as inline code.
+fn audit_excerpt(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().replace('`', "'");
+    match line.chars().count() > AUDIT_EXCERPT_MAX_LENGTH {
+        true => format!(
+            "{}…",
+            line.chars()
+                .take(AUDIT_EXCERPT_MAX_LENGTH)
+                .collect::<String>()
+        ),
+        false => line,
+    }
+}
+
+/// Statistics on the replies posted in a daily solutions thread.
+#[derive(Debug, Default)]
+pub struct ThreadActivity {
+    pub n_replies: usize,
+    // (language, number of replies mentioning it)
+    pub languages: Vec<(String, usize)>,
+    // (permalink, number of reactions)
+    pub most_reacted: Option<(String, usize)>,
+}
+
+/// Where the response to a command is posted, following the `commands.responses` settings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Channel,
+    Thread,
+    // only visible to the user who sent the command
+    Ephemeral(UserId),
+}
+
+#[derive(Debug, IntoStaticStr)]
 pub enum Event {
-    GlobalLeaderboardComplete((u8, LeaderboardStatistics)),
+    GlobalLeaderboardComplete((u8, LeaderboardStatistics, Option<DayDifficulty>)),
+    WeeklyRecap(WeeklyRecap),
+    // grand finale posted once the event is over
+    SeasonAwards(SeasonAwards),
+    // (day, actual cutoff in seconds, closest guesses, number of guesses)
+    GuessResults(u8, i64, Vec<Guess>, usize),
     GlobalLeaderboardHeroFound((String, ProblemPart, u8)),
     GlobalLeaderboardUpdateMessage(u64, u64),
     DailyChallengeIsUp(u8, String, String),
+    // (day, minutes before the puzzle unlock)
+    Countdown(u8, u32),
+    // (year, day) of the puzzle rated with the reactions added by the messaging service
+    DifficultyPoll(i32, u8),
+    // (year, day, part 1 ranking, part 2 ranking, delta ranking, negative splits of the day,
+    // community difficulty tallied from the poll of the day by the messaging service)
     DailySummary(
         i32,
         u8,
         Vec<(String, String)>,
         Vec<(String, String)>,
         Vec<(String, String)>,
+        Vec<NegativeSplit>,
+        Option<DifficultyRating>,
     ),
     PrivateLeaderboardNewEntries(Vec<DayHighlight>),
-    PrivateLeaderboardNewMembers(Vec<String>),
+    PrivateLeaderboardNewMembers(Vec<Identifier>),
+    PrivateLeaderboardOvertakes(Vec<(Identifier, Identifier, u8)>),
+    // (year, members whose overall rank changed with late completions of past days)
+    PrivateLeaderboardShuffle(i32, Vec<RankChange>),
+    // first member to complete part 2 of the current day
+    StageWinner(StageWinner),
+    // member having collected all the stars of the year
+    FullCompletion(FullCompletion),
+    // (year, join code of the private leaderboard, url to enter it), the page of the event being live
+    NextEventAnnounced(i32, String, String),
     DailySolutionsThreadToInitialize(u8),
-    CommandReceived(SlackChannelId, SlackTs, Command),
+    // The activity is gathered from the thread replies by the messaging service.
+    DailySolutionsThreadToSummarize(i32, u8),
+    DailySolutionsThreadActivity(u8, ThreadActivity),
+    // (channel, command message, where to reply, command)
+    CommandReceived(ChatId, MessageId, Reply, Command),
+    // (user, year, [(day, number of missing stars)]), sent as a direct message
+    PersonalReminder(SlackUserId, i32, Vec<(u8, usize)>),
+    // sent as a direct message
+    YearInReview(SlackUserId, YearReview),
+    // sent as a direct message after the daily summary
+    PersonalSummary(SlackUserId, DayReview),
+    // (user, year, news about the members watched by the user), sent as a direct message
+    WatchAlerts(SlackUserId, i32, Vec<WatchAlert>),
+    // (message, emoji), posted through the bot API (e.g. from CI)
+    CustomAnnouncement(String, Option<String>),
+    // board of the current year, edited in place in the scoreboard channels after each refresh
+    LiveScoreboard(Command),
     // Debug/Monitoring events
     PrivateLeaderboardUpdated,
+    // members joining or leaving the private leaderboard, and its cap getting close
+    PrivateLeaderboardMembership(MembershipChange),
+    // (new season, year of the current or upcoming event)
+    SeasonChanged(Season, i32),
+    // (context, error)
+    MonitoringAlert(&'static str, BotError),
+    // periodic status of the bot
+    Heartbeat(Heartbeat),
+    // template rendered with sample values, requested by an admin
+    TemplatePreview(TemplatePreview),
+    // end of the quiet hours, the announcements held back being posted
+    QuietHoursEnded,
+    // messages of the announcements held back during the quiet hours
+    QuietHoursDigest(Vec<String>),
+    // periodic check of the highlights held back, posted once their debounce window has elapsed
+    HighlightsDebounceTick,
     // GlobalLeaderboardPollingStarted,
     // FailedFetching,
 }
 
+impl Event {
+    /// Messages to post for the event, long announcements being batched in several messages
+    /// and other messages exceeding the Slack limit being split.
+    pub fn messages(&self) -> Vec<String> {
+        match self {
+            Event::PrivateLeaderboardNewEntries(entries) => {
+                batch_sections(new_entries_sections(entries), MESSAGE_MAX_LENGTH)
+            }
+            // boards are paginated, with navigation buttons attached to the message
+            Event::CommandReceived(_, _, _, Command::LeaderboardDisplay(..))
+            | Event::LiveScoreboard(_) => vec![self.to_string()],
+            _ => split_message(&self.to_string(), MESSAGE_MAX_LENGTH),
+        }
+    }
+
+    /// Whether the event is announced in the default channel, as opposed to the commands
+    /// responses, the monitoring messages and the direct messages.
+    pub fn is_announcement(&self) -> bool {
+        !matches!(
+            self,
+            Event::CommandReceived(..)
+                | Event::PrivateLeaderboardUpdated
+                | Event::PrivateLeaderboardMembership(_)
+                | Event::SeasonChanged(..)
+                | Event::MonitoringAlert(..)
+                | Event::Heartbeat(_)
+                | Event::TemplatePreview(_)
+                | Event::QuietHoursEnded
+                | Event::HighlightsDebounceTick
+                | Event::PersonalReminder(..)
+                | Event::YearInReview(..)
+                | Event::PersonalSummary(..)
+                | Event::WatchAlerts(..)
+        )
+    }
+
+    /// Whether the announcement can be held back during the quiet hours. The messages edited in
+    /// place, the solutions threads and the announcements posted through the bot API are not.
+    pub fn is_deferrable(&self) -> bool {
+        self.is_announcement()
+            && !matches!(
+                self,
+                Event::LiveScoreboard(_)
+                    | Event::DailySolutionsThreadToInitialize(_)
+                    | Event::DailySolutionsThreadToSummarize(..)
+                    | Event::DifficultyPoll(..)
+                    | Event::CustomAnnouncement(..)
+                    | Event::QuietHoursDigest(_)
+            )
+    }
+
+    /// Whether the announcement is only relevant when posted right away (e.g. the countdown to
+    /// the puzzle release), being dropped instead of held back during the quiet hours.
+    pub fn is_time_sensitive(&self) -> bool {
+        matches!(
+            self,
+            Event::Countdown(..) | Event::GlobalLeaderboardUpdateMessage(..)
+        )
+    }
+
+    /// Image version of the events displaying a text table (daily summary, board).
+    pub fn as_image(&self) -> Option<TextImage> {
+        match self {
+            Event::DailySummary(year, day, p1_data, p2_data, delta_data, splits, difficulty) => {
+                let mut lines = vec![];
+                for (heading, data) in [
+                    ("Top 5 to finish PART 1", p1_data),
+                    ("Top 5 to finish PART 2", p2_data),
+                    ("Top 5 DELTA", delta_data),
+                ] {
+                    lines.push(Line::Heading(heading.to_string()));
+                    lines.extend(data.iter().take(5).enumerate().map(|(i, (name, time))| {
+                        Line::Text(format!("{}) {:>9}  {name}", i + 1, time))
+                    }));
+                    if data.is_empty() {
+                        lines.push(Line::Text("Nobody yet".to_string()));
+                    };
+                }
+                if !splits.is_empty() {
+                    lines.push(Line::Heading(
+                        "Negative splits (part 2 faster than part 1)".to_string(),
+                    ));
+                    lines.extend(splits.iter().map(|split| {
+                        Line::Text(format!(
+                            "{} / {}  {}",
+                            format_duration(split.part1),
+                            format_duration(split.delta),
+                            names::resolve(&split.member)
+                        ))
+                    }));
+                };
+                if let Some(rating) = difficulty {
+                    lines.push(Line::Heading("Community difficulty".to_string()));
+                    lines.push(Line::Text(format!(
+                        "{:.1}/5 ({} votes)",
+                        rating.average, rating.n_votes
+                    )));
+                };
+                Some(TextImage {
+                    title: format!(
+                        "December, {day}{} {year} - Daily update",
+                        ordinal_number_suffix((*day).into())
+                    ),
+                    lines,
+                })
+            }
+            Event::CommandReceived(
+                _channel_id,
+                _ts,
+                _reply,
+                Command::LeaderboardDisplay(year, board, _time, scoring, _detailed, page, n_pages),
+            ) => {
+                let scoring = match scoring {
+                    Scoring::LOCAL => "local score",
+                    Scoring::STARS => "number of stars",
+                    Scoring::PERCENTILE => "percentile score",
+                };
+                let pages = match n_pages {
+                    1 => String::new(),
+                    n => format!(" (page {}/{n})", page + 1),
+                };
+                Some(TextImage {
+                    title: format!("{year} leaderboard by {scoring}{pages}"),
+                    lines: board.lines().map(|l| Line::Text(l.to_string())).collect(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// HTML version of the digest events (daily summary, weekly recap), sent to the mailing list.
+    pub fn as_html(&self) -> Option<String> {
+        let (template, ctx) = match self {
+            Event::DailySummary(year, day, p1_data, p2_data, delta_data, splits, difficulty) => (
+                MessageTemplate::DailySummaryEmail,
+                daily_summary_context(year, day, p1_data, p2_data, delta_data, splits, difficulty),
+            ),
+            Event::WeeklyRecap(recap) => {
+                (MessageTemplate::WeeklyRecapEmail, context! {recap => recap})
+            }
+            _ => return None,
+        };
+        Some(template.get().render(ctx).unwrap())
+    }
+}
+
 impl fmt::Display for Event {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -68,6 +466,27 @@ impl fmt::Display for Event {
                         .unwrap()
                 )
             }
+            Event::DailySolutionsThreadToSummarize(year, day) => {
+                write!(
+                    f,
+                    "Summarizing the day {day} ({year}) solutions thread activity."
+                )
+            }
+            Event::DailySolutionsThreadActivity(day, activity) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::DailySolutionThreadActivity
+                        .get()
+                        .render(context! {
+                            day => day,
+                            n_replies => activity.n_replies,
+                            languages => activity.languages,
+                            most_reacted => activity.most_reacted,
+                        })
+                        .unwrap()
+                )
+            }
             Event::DailyChallengeIsUp(day, title, challenge_url) => {
                 let day = format!("Day {day}");
                 let header = match to_art(day.to_string(), "small", 0, 0, 0) {
@@ -83,39 +502,35 @@ impl fmt::Display for Event {
                         .unwrap()
                 )
             }
-            Event::DailySummary(year, day, p1_data, p2_data, delta_data) => {
-                // Prefix with medal or ranking
-                let prefixed_p1 = p1_data
-                    .iter()
-                    .zip(symbols_prefix(&TROPHIES))
-                    .map(|((name, score), prefix)| (prefix, name, format!("{:>9}", score)))
-                    .take(5)
-                    .collect::<Vec<(String, &String, String)>>();
-                let prefixed_p2 = p2_data
-                    .iter()
-                    .zip(symbols_prefix(&TROPHIES))
-                    .map(|((name, score), prefix)| (prefix, name, format!("{:>9}", score)))
-                    .take(5)
-                    .collect::<Vec<(String, &String, String)>>();
-                let prefixed_delta = delta_data
-                    .iter()
-                    .zip(symbols_prefix(&TROPHIES))
-                    .map(|((name, score), prefix)| (prefix, name, format!("{:>9}", score)))
-                    .take(5)
-                    .collect::<Vec<(String, &String, String)>>();
-
+            Event::DifficultyPoll(_year, day) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::DifficultyPoll
+                        .get()
+                        .render(context! { day => day })
+                        .unwrap()
+                )
+            }
+            Event::Countdown(day, minutes) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Countdown
+                        .get()
+                        .render(context! { day => day, minutes => minutes })
+                        .unwrap()
+                )
+            }
+            Event::DailySummary(year, day, p1_data, p2_data, delta_data, splits, difficulty) => {
                 write!(
                     f,
                     "{}",
                     MessageTemplate::DailySummary
                         .get()
-                        .render(context! {
-                            year => year,
-                            day => format!("{day}{}", ordinal_number_suffix(*day)),
-                            ranking_p1 => prefixed_p1,
-                            ranking_p2 => prefixed_p2,
-                            ranking_delta => prefixed_delta,
-                        })
+                        .render(daily_summary_context(
+                            year, day, p1_data, p2_data, delta_data, splits, difficulty
+                        ))
                         .unwrap()
                 )
             }
@@ -133,7 +548,7 @@ impl fmt::Display for Event {
                         .unwrap()
                 )
             }
-            Event::GlobalLeaderboardComplete((day, statistics)) => {
+            Event::GlobalLeaderboardComplete((day, statistics, difficulty)) => {
                 write!(
                     f,
                     "{}",
@@ -146,12 +561,15 @@ impl fmt::Display for Event {
                             p2_slow => statistics.p2_slow.map_or("N/A".to_string(), format_duration),
                             delta_fast => statistics.delta_fast.map_or("N/A".to_string(), |(d, rank)| {
                                 let rank = rank.unwrap_or_default();
-                                format!("*{}* ({})", format_duration(d), format_rank(rank))
+                                format!("*{}* ({})", format_duration(d), format_rank(rank.into()))
                             }),
                             delta_slow => statistics.delta_slow.map_or("N/A".to_string(), |(d, rank)| {
                                 let rank = rank.unwrap_or_default();
-                                format!("*{}* ({})", format_duration(d), format_rank(rank))
+                                format!("*{}* ({})", format_duration(d), format_rank(rank.into()))
                             }),
+                            difficulty => difficulty.as_ref().map(|d| format!("{:.2}", d.index)),
+                            n_days => difficulty.as_ref().map(|d| d.n_days),
+                            is_among_hardest => difficulty.as_ref().is_some_and(|d| d.is_among_hardest()),
                         })
                         .unwrap()
                 )
@@ -165,7 +583,7 @@ impl fmt::Display for Event {
                         .render(context! {
                             name => hero,
                             part => part.to_string(),
-                            rank => format_rank(*rank)
+                            rank => format_rank((*rank).into())
                         })
                         .unwrap()
                 )
@@ -181,36 +599,10 @@ impl fmt::Display for Event {
                 )
             }
             Event::PrivateLeaderboardNewEntries(entries) => {
-                let (year, today) = current_aoc_year_day();
-
-                let is_today_entries = entries
-                    .iter()
-                    .into_group_map_by(|h| h.year == year && h.day == today);
-
-                let mut output = String::new();
-                if let Some(today_entries) = is_today_entries.get(&true) {
-                    output.push_str(
-                        &MessageTemplate::NewEntriesToday
-                            .get()
-                            .render(context! {completions => today_entries})
-                            .unwrap(),
-                    );
-                };
-                if let Some(late_entries) = is_today_entries.get(&false) {
-                    if !output.is_empty() {
-                        output.push('\n');
-                    };
-                    output.push_str(
-                        &MessageTemplate::NewEntriesLate
-                            .get()
-                            .render(context! {completions => late_entries})
-                            .unwrap(),
-                    );
-                };
-
-                write!(f, "{}", output)
+                write!(f, "{}", new_entries_sections(entries).join("\n"))
             }
             Event::PrivateLeaderboardNewMembers(members) => {
+                let members = members.iter().map(names::resolve).collect::<Vec<String>>();
                 write!(
                     f,
                     "{}",
@@ -220,91 +612,1043 @@ impl fmt::Display for Event {
                         .unwrap()
                 )
             }
-            Event::CommandReceived(_channel_id, _ts, cmd) => {
-                match cmd {
-                    Command::NotValid(reason) => {
-                        write!(
-                            f,
-                            "{}",
-                            MessageTemplate::CustomMessage
-                                .get()
-                                .render(context! {
-                                message => reason})
-                                .unwrap()
-                        )
-                    }
-                    Command::Help => {
-                        write!(f, "{}", MessageTemplate::Help.get().render(()).unwrap())
-                    }
-                    Command::Ranking(year, day, data, time, method) => {
-                        let now = time.with_timezone(&Local);
-                        let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
-
-                        // Prefix with medal or ranking
-                        let prefixed_data = data
-                            .iter()
-                            .zip(symbols_prefix(&MEDALS))
-                            .map(|((name, score), prefix)| (prefix, name, format!("{:>9}", score)))
-                            .collect::<Vec<(String, &String, String)>>();
-
-                        write!(
-                            f,
-                            "{}",
-                            MessageTemplate::Ranking
-                                .get()
-                                .render(context! {
-                                    year => year,
-                                    day => day,
-                                    current_day => year == &now.year() && *day as u32 == now.day(),
-                                    timestamp => timestamp,
-                                    ranking => prefixed_data,
-                                    ranking_method => method.to_string(),
-                                    is_limit => matches!(method, Ranking::LIMIT),
-                                })
-                                .unwrap()
-                        )
-                    }
-                    Command::LeaderboardDisplay(year, board, time, method) => {
-                        let now = time.with_timezone(&Local);
-                        let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
-
-                        write!(
-                            f,
-                            "{}",
-                            MessageTemplate::LeaderboardDisplay
-                                .get()
-                                .render(context! {
-                                    year => year,
-                                    current_year => year == &now.year(),
-                                    timestamp => timestamp,
-                                    leaderboard => board,
-                                    scoring_method => method.to_string()
-                                })
-                                .unwrap()
+            Event::PrivateLeaderboardOvertakes(overtakes) => {
+                let overtakes = overtakes
+                    .iter()
+                    .map(|(overtaker, overtaken, position)| {
+                        (
+                            names::resolve(overtaker),
+                            names::resolve(overtaken),
+                            format_rank((*position).into()),
                         )
-                    }
-                    Command::StandingTdf(year, day, standings, time, jersey) => {
-                        let now = time.with_timezone(&Local);
-                        let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
-
-                        write!(
-                            f,
-                            "{}",
-                            MessageTemplate::TdfStandings
-                                .get()
-                                .render(context! {
-                                    year => year,
-                                    day => day,
-                                    current_year => year == &now.year(),
-                                    timestamp => timestamp,
-                                    standings => standings,
-                                    jersey => jersey.to_string()
-                                })
-                                .unwrap()
+                    })
+                    .collect::<Vec<(String, String, String)>>();
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Overtakes
+                        .get()
+                        .render(context! {overtakes => overtakes})
+                        .unwrap()
+                )
+            }
+            Event::PrivateLeaderboardShuffle(year, changes) => {
+                let changes = changes
+                    .iter()
+                    .map(|change| {
+                        (
+                            names::resolve(&change.member),
+                            format_rank(change.previous),
+                            format_rank(change.current),
+                            change.current < change.previous,
                         )
-                    }
-                }
+                    })
+                    .collect::<Vec<(String, String, String, bool)>>();
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::StandingsShuffle
+                        .get()
+                        .render(context! {year => year, changes => changes})
+                        .unwrap()
+                )
             }
-        }
+            Event::StageWinner(winner) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::StageWinner
+                        .get()
+                        .render(context! {
+                            name => names::resolve(&winner.member),
+                            day => winner.day,
+                            time => format_duration(winner.time),
+                            delta => format_duration(winner.delta),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::FullCompletion(completion) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::FullCompletion
+                        .get()
+                        .render(context! {
+                            name => names::resolve(&completion.member),
+                            year => completion.year,
+                            time => completion.formatted_time(),
+                            rank => format_rank(completion.rank),
+                            first => completion.rank == 1,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::NextEventAnnounced(year, join_code, url) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::NextEvent
+                        .get()
+                        .render(context! {
+                            year => year,
+                            join_code => join_code,
+                            url => url,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::PersonalReminder(_user, year, missing) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::PersonalReminder
+                        .get()
+                        .render(context! {year => year, missing => missing})
+                        .unwrap()
+                )
+            }
+            Event::WatchAlerts(_user, year, alerts) => {
+                // (name, day, is first star, new position of the overtaking member)
+                let alerts = alerts
+                    .iter()
+                    .map(|alert| match alert {
+                        WatchAlert::Star(member, day, part) => (
+                            names::resolve(member),
+                            *day,
+                            *part == ProblemPart::FIRST,
+                            None,
+                        ),
+                        WatchAlert::Overtake(member, position) => (
+                            names::resolve(member),
+                            0,
+                            false,
+                            Some(format_rank(*position)),
+                        ),
+                    })
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::WatchAlerts
+                        .get()
+                        .render(context! {year => year, alerts => alerts})
+                        .unwrap()
+                )
+            }
+            Event::YearInReview(_user, review) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::YearInReview
+                        .get()
+                        .render(context! {
+                            review => review,
+                            rank => format_rank(review.rank),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::WeeklyRecap(recap) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::WeeklyRecap
+                        .get()
+                        .render(context! {recap => recap})
+                        .unwrap()
+                )
+            }
+            Event::SeasonAwards(awards) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::AwardsCeremony
+                        .get()
+                        .render(context! {awards => awards})
+                        .unwrap()
+                )
+            }
+            Event::GuessResults(day, p2_slow_sec, winners, n_guesses) => {
+                let guess_error = |g: &Guess| Duration::seconds((g.guess_sec - p2_slow_sec).abs());
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::GuessResults
+                        .get()
+                        .render(context! {
+                            day => day,
+                            cutoff => format_duration(Duration::seconds(*p2_slow_sec)),
+                            winners => winners
+                                .iter()
+                                .map(|g| (
+                                    format!("<@{}>", g.user),
+                                    format_duration(Duration::seconds(g.guess_sec)),
+                                    format_duration(guess_error(g)),
+                                ))
+                                .collect::<Vec<(String, String, String)>>(),
+                            n_guesses => n_guesses,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::PersonalSummary(_user, review) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::PersonalSummary
+                        .get()
+                        .render(context! {
+                            review => review,
+                            rank => format_rank(review.rank),
+                            previous_rank => review.previous_rank.map(format_rank),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::CustomAnnouncement(message, emoji) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::CustomMessage
+                        .get()
+                        .render(context! {
+                            message => message,
+                            emoji => emoji.as_deref().unwrap_or(&theme::current().announcement),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::PrivateLeaderboardMembership(change) => {
+                let names = |members: &[Identifier]| {
+                    members.iter().map(names::resolve).collect::<Vec<String>>()
+                };
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::PrivateLeaderboardMembership
+                        .get()
+                        .render(context! {
+                            count => change.count,
+                            cap => PRIVATE_LEADERBOARD_MAX_MEMBERS,
+                            trend => change.trend,
+                            joined => names(&change.joined),
+                            left => names(&change.left),
+                            spots_left => change.cap_warning,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::SeasonChanged(season, year) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::SeasonChanged
+                        .get()
+                        .render(context! {
+                            is_event => *season == Season::Event,
+                            year => year,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::MonitoringAlert(context, error) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::MonitoringAlert
+                        .get()
+                        .render(context! {
+                            code => error.code(),
+                            context => context,
+                            error => error.to_string(),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::Heartbeat(status) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Heartbeat
+                        .get()
+                        .render(context! {
+                            cache_age => format_duration(Duration::seconds(status.cache_age_sec)),
+                            n_members => status.n_members,
+                            scrape_sec => status
+                                .last_scrape
+                                .map(|s| format!("{:.1}", s.duration_ms as f64 / 1000.0)),
+                            scrape_failed => status.last_scrape.is_some_and(|s| !s.succeeded),
+                            n_jobs => status.n_jobs,
+                            failing_jobs => status.failing_jobs,
+                        })
+                        .unwrap()
+                )
+            }
+            Event::TemplatePreview(preview) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::TemplatePreview
+                        .get()
+                        .render(context! {
+                            template => preview.template.name(),
+                            rendered => preview.rendered.as_ref().and_then(|r| r.as_ref().ok()),
+                            error => preview.rendered.as_ref().and_then(|r| r.as_ref().err()),
+                        })
+                        .unwrap()
+                )
+            }
+            Event::QuietHoursEnded | Event::HighlightsDebounceTick => Ok(()),
+            Event::QuietHoursDigest(messages) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::QuietHoursDigest
+                        .get()
+                        .render(context! {messages => messages})
+                        .unwrap()
+                )
+            }
+            Event::CommandReceived(_channel_id, _ts, _reply, cmd) => write!(f, "{}", cmd),
+            Event::LiveScoreboard(board) => write!(f, "{}", board),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::NotValid(reason) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::CustomMessage
+                        .get()
+                        .render(context! {
+                        message => reason})
+                        .unwrap()
+                )
+            }
+            Command::Cooldown(command, wait_sec) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::CommandCooldown
+                        .get()
+                        .render(context! {
+                            command => command,
+                            wait_sec => wait_sec,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Reminder(member) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::ReminderRegistration
+                        .get()
+                        .render(context! {member => member.as_ref().map(names::resolve)})
+                        .unwrap()
+                )
+            }
+            Command::Watch(member, watching) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::WatchRegistration
+                        .get()
+                        .render(context! {
+                            member => member.as_ref().map(names::resolve),
+                            watching => watching,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Iam(member) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::MemberLinked
+                        .get()
+                        .render(context! {member => names::resolve(member)})
+                        .unwrap()
+                )
+            }
+            Command::BestSolutions(year, day, solutions) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::BestSolutions
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            solutions => solutions,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Absence(member, year, days) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Absence
+                        .get()
+                        .render(context! {
+                            member => names::resolve(member),
+                            year => year,
+                            days => days,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Exclusion(member, excluded) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Exclusion
+                        .get()
+                        .render(context! {
+                            member => names::resolve(member),
+                            excluded => excluded,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Guess(_year, day, guess_sec) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Guess
+                        .get()
+                        .render(context! {
+                            day => day,
+                            guess => format_duration(Duration::seconds(*guess_sec)),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Predictions(year, standings) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Predictions
+                        .get()
+                        .render(context! {
+                            year => year,
+                            standings => standings
+                                .iter()
+                                .map(|p| (
+                                    format!("<@{}>", p.user),
+                                    p.wins,
+                                    p.n_guesses,
+                                    format_duration(Duration::seconds(p.average_error_sec)),
+                                ))
+                                .collect::<Vec<(String, usize, usize, String)>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::WhatIf(projection) => {
+                let position = |rank: Option<usize>| rank.map(format_rank);
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::WhatIf
+                        .get()
+                        .render(context! {
+                            projection => projection,
+                            rank => format_rank(projection.rank.0),
+                            new_rank => format_rank(projection.rank.1),
+                            jerseys => projection
+                                .jerseys
+                                .iter()
+                                .map(|(jersey, before, after)| (jersey, position(*before), position(*after)))
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Jobs(jobs) => {
+                let time = |at: &DateTime<Utc>| {
+                    format!("{}", at.with_timezone(&Local).format("%d/%m %H:%M:%S"))
+                };
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Jobs
+                        .get()
+                        .render(context! {
+                            jobs => jobs
+                                .iter()
+                                .map(|(job, history)| context! {
+                                    name => job,
+                                    runs => history.runs,
+                                    failed_runs => history.failed_runs,
+                                    running_since => history.running_since.as_ref().map(time),
+                                    last_run_at => history.last_run.as_ref().map(|run| time(&run.at)),
+                                    last_run_ms => history.last_run.map(|run| run.duration_ms),
+                                    succeeded => history.last_run.is_some_and(|run| run.succeeded()),
+                                    last_error_at => history.last_error.as_ref().map(|(at, _)| time(at)),
+                                    last_error => history.last_error.as_ref().map(|(_, error)| error),
+                                })
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::AuditLog(_n, messages) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::AuditLog
+                        .get()
+                        .render(context! {
+                            messages => messages
+                                .iter()
+                                .map(|m| context! {
+                                    at => format!("{}", m.at.with_timezone(&Local).format("%d/%m %H:%M:%S")),
+                                    event => m.event,
+                                    channel => m.channel.to_string(),
+                                    ts => m.ts.as_ref().map(|ts| ts.to_string()),
+                                    // quoted as code, so the mentions of the message do not notify again
+                                    excerpt => audit_excerpt(&m.text),
+                                    error => m.error,
+                                })
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::PersonalSummaryOptIn(opt_in) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::PersonalSummaryOptIn
+                        .get()
+                        .render(context! {opt_in => opt_in})
+                        .unwrap()
+                )
+            }
+            Command::Query(result) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Query
+                        .get()
+                        .render(context! {result => result})
+                        .unwrap()
+                )
+            }
+            Command::Preview(preview) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Preview
+                        .get()
+                        .render(context! {
+                            template => preview.template.name(),
+                            failed => matches!(preview.rendered, Some(Err(_))),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::OptOut(opt_out) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::OptOut
+                        .get()
+                        .render(context! {opt_out => opt_out})
+                        .unwrap()
+                )
+            }
+            Command::ReviewOptIn(opt_in) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::YearInReviewOptIn
+                        .get()
+                        .render(context! {opt_in => opt_in})
+                        .unwrap()
+                )
+            }
+            Command::Help => {
+                write!(f, "{}", MessageTemplate::Help.get().render(()).unwrap())
+            }
+            Command::Ranking(year, day, data, time, method) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                // Prefix with medal or ranking
+                let prefixed_data = data
+                    .iter()
+                    .zip(symbols_prefix(&theme::current().medals))
+                    .map(|((name, score), prefix)| (prefix, name, format!("{:>9}", score)))
+                    .collect::<Vec<(String, &String, String)>>();
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Ranking
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            current_day => year == &now.year() && *day as u32 == now.day(),
+                            timestamp => timestamp,
+                            ranking => prefixed_data,
+                            ranking_method => method.to_string(),
+                            is_limit => matches!(method, Ranking::LIMIT),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::RangeRanking(year, (first, last), data, time, aggregate) => {
+                let timestamp =
+                    format!("{}", time.with_timezone(&Local).format("%d/%m/%Y %H:%M:%S"));
+
+                // Prefix with medal or ranking
+                let prefixed_data = data
+                    .iter()
+                    .zip(symbols_prefix(&theme::current().medals))
+                    .map(|((name, time, n_days), prefix)| {
+                        (prefix, name, format!("{:>9}", time), n_days)
+                    })
+                    .collect::<Vec<(String, &String, String, &usize)>>();
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::RangeRanking
+                        .get()
+                        .render(context! {
+                            year => year,
+                            first => first,
+                            last => last,
+                            n_total => last - first + 1,
+                            timestamp => timestamp,
+                            ranking => prefixed_data,
+                            aggregate => aggregate.to_string(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::SeasonDelta(year, data, time, aggregate, min_days) => {
+                let timestamp =
+                    format!("{}", time.with_timezone(&Local).format("%d/%m/%Y %H:%M:%S"));
+
+                // Prefix with medal or ranking
+                let prefixed_data = data
+                    .iter()
+                    .zip(symbols_prefix(&theme::current().medals))
+                    .map(|((name, time, n_days), prefix)| {
+                        (prefix, name, format!("{:>9}", time), n_days)
+                    })
+                    .collect::<Vec<(String, &String, String, &usize)>>();
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::SeasonDelta
+                        .get()
+                        .render(context! {
+                            year => year,
+                            min_days => min_days,
+                            timestamp => timestamp,
+                            ranking => prefixed_data,
+                            aggregate => aggregate.to_string(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::LeaderboardDisplay(year, board, time, method, detailed, page, n_pages) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::LeaderboardDisplay
+                        .get()
+                        .render(context! {
+                            year => year,
+                            current_year => year == &now.year(),
+                            timestamp => timestamp,
+                            leaderboard => board,
+                            scoring_method => method.to_string(),
+                            detailed => detailed,
+                            page => page + 1,
+                            n_pages => n_pages,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::AllTime(years, standings, time, scoring) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::AllTime
+                        .get()
+                        .render(context! {
+                            years => years,
+                            timestamp => timestamp,
+                            scoring => scoring.to_string(),
+                            standings => standings,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Wins(year, wins, time) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Wins
+                        .get()
+                        .render(context! {
+                            year => year,
+                            current_year => year == &now.year(),
+                            timestamp => timestamp,
+                            wins => wins,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Faq(reply) => {
+                let context = match reply {
+                    FaqReply::List(entries) => context! { entries => entries },
+                    FaqReply::Answer(entry) => context! { answer => entry },
+                    FaqReply::Suggestions(requested, entries) => {
+                        context! { requested => requested, entries => entries }
+                    }
+                };
+                write!(f, "{}", MessageTemplate::Faq.get().render(context).unwrap())
+            }
+            Command::Link(year, day, puzzle, private_leaderboard, global_leaderboard) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Link
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            puzzle => puzzle,
+                            private_leaderboard => private_leaderboard,
+                            global_leaderboard => global_leaderboard,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Missing(year, day, members) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Missing
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            current_year => year == &current_aoc_year_day().0,
+                            members => members
+                                .iter()
+                                .map(|(id, n_days)| (names::resolve(id), n_days))
+                                .collect::<Vec<(String, &usize)>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Practice(year, puzzle) => {
+                let difficulty = puzzle.as_ref().and_then(|p| p.difficulty.as_ref());
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Practice
+                        .get()
+                        .render(context! {
+                            year => year,
+                            puzzle_year => puzzle.as_ref().map(|p| p.year),
+                            day => puzzle.as_ref().map(|p| p.day),
+                            missing_stars => puzzle.as_ref().map(|p| p.missing_stars),
+                            url => puzzle.as_ref().map(|p| AoC::new().daily_challenge_url(p.year, p.day)),
+                            difficulty => difficulty.map(|d| format!("{:.2}", d.index)),
+                            percentile => difficulty.map(|d| (d.percentile * 100.0).round() as u32),
+                            n_days => difficulty.map(|d| d.n_days),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Languages(year, usage) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Languages
+                        .get()
+                        .render(context! {
+                            year => year,
+                            languages => usage
+                                .iter()
+                                .map(|u| (&u.language, u.votes, u.n_days))
+                                .collect::<Vec<(&String, usize, usize)>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Latency(year, latency) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Latency
+                        .get()
+                        .render(context! {year => year, latency => latency})
+                        .unwrap()
+                )
+            }
+            Command::Rank(rank) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Rank
+                        .get()
+                        .render(context! {
+                            rank => rank,
+                            position => format_rank(rank.position),
+                            jerseys => rank
+                                .jerseys
+                                .iter()
+                                .map(|(jersey, position)| (jersey, position.map(format_rank)))
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Splits(year, counts) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Splits
+                        .get()
+                        .render(context! {
+                            year => year,
+                            members => counts
+                                .iter()
+                                .map(|c| (names::resolve(&c.member), c.n_splits, c.n_days))
+                                .collect::<Vec<(String, usize, usize)>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Heatmap(year, rows, chart, n_members) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Heatmap
+                        .get()
+                        .render(context! {
+                            year => year,
+                            current_year => year == &current_aoc_year_day().0,
+                            hours => rows == &HeatmapRows::Hours,
+                            chart => chart,
+                            n_members => n_members,
+                            max_members => HEATMAP_MAX_MEMBERS,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Burndown(year, day, chart) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Burndown
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            current_year => year == &current_aoc_year_day().0,
+                            chart => chart,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Gaps(year, race, gaps, closest) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Gaps
+                        .get()
+                        .render(context! {
+                            year => year,
+                            yellow => race == &Race::Yellow,
+                            gaps => gaps,
+                            closest => closest.as_ref().map(|(ahead, behind, gap)| context! {
+                                ahead => names::resolve(ahead),
+                                behind => names::resolve(behind),
+                                gap => race.format_gap(*gap).trim_start_matches(['+', '-']),
+                                points => gap,
+                            }),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::FullCompletions(year, completions) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::FullCompletions
+                        .get()
+                        .render(context! {
+                            year => year,
+                            completions => completions
+                                .iter()
+                                .map(|c| {
+                                    context! {
+                                        name => names::resolve(&c.member),
+                                        time => c.formatted_time(),
+                                        date => c.completed_at.format("%Y-%m-%d").to_string(),
+                                    }
+                                })
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::Heroes(n_scanned, heroes) => {
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::Heroes
+                        .get()
+                        .render(context! {
+                            n_scanned => n_scanned,
+                            heroes => heroes
+                                .iter()
+                                .map(|hero| {
+                                    let (rank, year, day, part) = hero.best;
+                                    context! {
+                                        name => names::resolve(&hero.member),
+                                        appearances => hero.appearances,
+                                        years => hero.years,
+                                        rank => format_rank(rank.into()),
+                                        year => year,
+                                        day => day,
+                                        part => part.to_string(),
+                                    }
+                                })
+                                .collect::<Vec<_>>(),
+                        })
+                        .unwrap()
+                )
+            }
+            Command::StageTdf(year, day, stage, winner, time) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::TdfStage
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            current_day => year == &now.year() && *day as u32 == now.day(),
+                            timestamp => timestamp,
+                            stage => stage,
+                            winner => winner,
+                        })
+                        .unwrap()
+                )
+            }
+            Command::StandingTdf(year, day, standings, time, jersey, drop_worst) => {
+                let now = time.with_timezone(&Local);
+                let timestamp = format!("{}", now.format("%d/%m/%Y %H:%M:%S"));
+
+                write!(
+                    f,
+                    "{}",
+                    MessageTemplate::TdfStandings
+                        .get()
+                        .render(context! {
+                            year => year,
+                            day => day,
+                            current_year => year == &now.year(),
+                            timestamp => timestamp,
+                            standings => standings,
+                            jersey => jersey.to_string(),
+                            drop_worst => drop_worst,
+                        })
+                        .unwrap()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_announcements_are_batched_by_day() {
+        let section = |day: u8, n_lines: usize| {
+            std::iter::once(format!("*Day {day}*"))
+                .chain((0..n_lines).map(|i| format!("completion {i:02}")))
+                .join("\n")
+        };
+
+        // small sections are packed together
+        let messages = batch_sections(vec![section(1, 2), section(2, 2)], 100);
+        assert_eq!(
+            messages,
+            vec![format!("{}\n{}", section(1, 2), section(2, 2))]
+        );
+
+        // a section too long for one message is split, its header being repeated
+        let messages = batch_sections(vec![section(3, 10), section(4, 1)], 60);
+        assert!(messages.iter().all(|m| m.len() <= 60));
+        assert!(messages[1].starts_with("*Day 3* (continued)\ncompletion"));
+        assert_eq!(messages.join("\n").matches("completion").count(), 10 + 1);
+        assert!(messages.last().unwrap().ends_with(&section(4, 1)));
+    }
+
+    #[test]
+    fn long_messages_are_split_with_their_code_block_reopened() {
+        let table = (1..=12).map(|i| format!("{i:>2}) member {i:02}   {:>4}", i * 10));
+        let message = std::iter::once("*Standings*\n```".to_string())
+            .chain(table)
+            .chain(std::iter::once("```\nLast update".to_string()))
+            .join("\n");
+
+        let messages = split_message(&message, 120);
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|m| m.len() <= 120));
+        assert!(messages.iter().all(|m| m.matches("```").count() % 2 == 0));
+        assert!(messages[1].starts_with("```\n"));
+        // rows keep the same width in every chunk
+        let rows = messages
+            .iter()
+            .flat_map(|m| m.lines())
+            .filter(|l| l.contains("member"))
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 12);
+        assert!(rows.iter().all(|l| l.len() == rows[0].len()));
+
+        assert_eq!(split_message("short", 120), vec!["short".to_string()]);
+    }
+
+    #[test]
+    fn countdown_gets_more_pressing() {
+        assert!(Event::Countdown(3, 60).to_string().contains("*1 hour*"));
+        assert!(Event::Countdown(3, 10).to_string().contains("*10 minutes*"));
+        let last_minute = Event::Countdown(3, 1).to_string();
+        assert!(last_minute.contains("*1 minute*") && last_minute.contains("day 3"));
     }
 }
@@ -0,0 +1,24 @@
+use crate::core::leaderboard::MemberId;
+use once_cell::sync::Lazy;
+use std::{collections::BTreeSet, sync::RwLock};
+
+/// Members excluded from the standings (e.g. bots, duplicate accounts), keyed by numeric id.
+pub type Exclusions = BTreeSet<MemberId>;
+
+// Members left out of the standings computations, registered from the settings and from the
+// persisted exclusions.
+static EXCLUDED: Lazy<RwLock<Exclusions>> = Lazy::new(|| RwLock::new(BTreeSet::new()));
+
+/// Add members to the ones already excluded.
+pub fn register(members: impl IntoIterator<Item = MemberId>) {
+    EXCLUDED.write().unwrap().extend(members);
+}
+
+/// Take the member into account in the standings again.
+pub fn unregister(member: MemberId) {
+    EXCLUDED.write().unwrap().remove(&member);
+}
+
+pub fn is_excluded(member: MemberId) -> bool {
+    EXCLUDED.read().unwrap().contains(&member)
+}
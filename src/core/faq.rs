@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use strsim::damerau_levenshtein;
+
+// Maximum number of typos tolerated when matching a topic, relative to the topic length
+const TYPOS_PER_CHARS: usize = 4;
+
+/// Question/answer entry defined by the operators in the settings (prize rules, logistics, ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FaqEntry {
+    pub topic: String,
+    pub question: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaqReply {
+    List(Vec<FaqEntry>),
+    Answer(FaqEntry),
+    // (requested topic, close topics), no close topic if nothing matched
+    Suggestions(String, Vec<FaqEntry>),
+}
+
+/// Answer to the requested topic, matched on the topic (exactly, then allowing typos) and then on
+/// the question. All the entries are listed if no topic is requested.
+pub fn reply(entries: &[FaqEntry], requested: Option<&str>) -> FaqReply {
+    let requested = match requested.map(|r| r.trim().to_lowercase()) {
+        Some(requested) if !requested.is_empty() => requested,
+        _ => return FaqReply::List(entries.to_vec()),
+    };
+
+    if let Some(entry) = entries.iter().find(|e| e.topic.to_lowercase() == requested) {
+        return FaqReply::Answer(entry.clone());
+    };
+
+    let close_topics = entries
+        .iter()
+        .filter(|e| {
+            let topic = e.topic.to_lowercase();
+            topic.contains(&requested)
+                || damerau_levenshtein(&topic, &requested) <= (topic.len() / TYPOS_PER_CHARS).max(1)
+        })
+        .collect::<Vec<&FaqEntry>>();
+    let candidates = match close_topics.is_empty() {
+        true => entries
+            .iter()
+            .filter(|e| e.question.to_lowercase().contains(&requested))
+            .collect(),
+        false => close_topics,
+    };
+
+    match candidates[..] {
+        [entry] => FaqReply::Answer(entry.clone()),
+        _ => FaqReply::Suggestions(requested, candidates.into_iter().cloned().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<FaqEntry> {
+        [
+            ("prizes", "What can I win?", "A golden mug for the top 3."),
+            (
+                "prize-rules",
+                "How are the prizes attributed?",
+                "Local score on Dec 26th.",
+            ),
+            (
+                "badges",
+                "Where can I get a conference badge?",
+                "At the front desk.",
+            ),
+        ]
+        .into_iter()
+        .map(|(topic, question, answer)| FaqEntry {
+            topic: topic.to_string(),
+            question: question.to_string(),
+            answer: answer.to_string(),
+        })
+        .collect()
+    }
+
+    fn topics(reply: FaqReply) -> Vec<String> {
+        match reply {
+            FaqReply::List(entries) | FaqReply::Suggestions(_, entries) => {
+                entries.into_iter().map(|e| e.topic).collect()
+            }
+            FaqReply::Answer(entry) => vec![entry.topic],
+        }
+    }
+
+    #[test]
+    fn topics_are_matched_exactly_then_fuzzily() {
+        let entries = entries();
+
+        assert!(matches!(reply(&entries, None), FaqReply::List(all) if all.len() == 3));
+        assert!(
+            matches!(reply(&entries, Some("Prizes")), FaqReply::Answer(e) if e.topic == "prizes")
+        );
+        assert!(
+            matches!(reply(&entries, Some("bagdes")), FaqReply::Answer(e) if e.topic == "badges")
+        );
+        // matched on the question
+        assert!(
+            matches!(reply(&entries, Some("front desk")), FaqReply::Suggestions(_, e) if e.is_empty())
+        );
+        assert!(
+            matches!(reply(&entries, Some("conference")), FaqReply::Answer(e) if e.topic == "badges")
+        );
+
+        assert_eq!(
+            topics(reply(&entries, Some("prize"))),
+            vec!["prizes", "prize-rules"]
+        );
+        assert!(topics(reply(&entries, Some("parking"))).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    core::{
+        leaderboard::{Identifier, Leaderboard},
+        standings::{standings_board, Scoring, Standing},
+    },
+    utils::{format_duration, format_duration_with_days},
+};
+use chrono::Duration;
+
+/// Race whose gaps between consecutive positions are shown by the `gap` command.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Race {
+    // total time of the yellow jersey, the lowest leading
+    Yellow,
+    // local score, the highest leading
+    Local,
+}
+
+/// Member of a race, with the gaps to the member ahead and to the leader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap {
+    pub member: Identifier,
+    // total time (in seconds) or points
+    pub total: i64,
+    // none for the leader
+    pub ahead: Option<i64>,
+    pub leader: i64,
+}
+
+impl Race {
+    /// Total of a member, as displayed in the race.
+    pub fn format_total(&self, total: i64) -> String {
+        match self {
+            Race::Yellow => format_duration_with_days(Duration::seconds(total)),
+            Race::Local => total.to_string(),
+        }
+    }
+
+    /// Gap between two members, as displayed in the race.
+    pub fn format_gap(&self, gap: i64) -> String {
+        match self {
+            Race::Yellow => format!("+{}", format_duration(Duration::seconds(gap))),
+            Race::Local => format!("-{gap}"),
+        }
+    }
+}
+
+/// Members of the race of the year in position order, with their gaps.
+pub fn race_gaps(leaderboard: &Leaderboard, race: Race, year: i32) -> Vec<Gap> {
+    let standing = Standing::new(leaderboard);
+    let totals = match race {
+        Race::Yellow => standing
+            .yellow_season(year, 0)
+            .into_iter()
+            .map(|(id, time, _over_cutoff)| (id, time))
+            .collect::<Vec<_>>(),
+        Race::Local => standings_board(&Scoring::LOCAL, leaderboard, year)
+            .into_iter()
+            .map(|(id, _scores, points)| (id, points as i64))
+            .collect(),
+    };
+    let gap = |ahead: i64, behind: i64| (behind - ahead).abs();
+    let leader = totals
+        .first()
+        .map(|(_id, total)| *total)
+        .unwrap_or_default();
+    totals
+        .iter()
+        .enumerate()
+        .map(|(idx, (id, total))| Gap {
+            member: (*id).clone(),
+            total: *total,
+            ahead: idx.checked_sub(1).map(|ahead| gap(totals[ahead].1, *total)),
+            leader: gap(leader, *total),
+        })
+        .collect()
+}
+
+/// (member ahead, member behind) of the closest fight of the race.
+pub fn closest_fight(gaps: &[Gap]) -> Option<(&Gap, &Gap)> {
+    gaps.windows(2)
+        .min_by_key(|pair| pair[1].ahead)
+        .map(|pair| (&pair[0], &pair[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn gaps_are_measured_to_the_member_ahead() {
+        let year = 2001;
+        let leaderboard = private_leaderboard(
+            year,
+            &[
+                MemberFixture::new(2901, "Olive").with_day(1, 600, 60),
+                MemberFixture::new(2902, "Clove").with_day(1, 900, 60),
+                MemberFixture::new(2903, "Mace").with_day(1, 3600, 60),
+            ],
+        );
+
+        let yellow = race_gaps(&leaderboard, Race::Yellow, year);
+        assert_eq!(
+            yellow
+                .iter()
+                .map(|g| (g.member.name.as_str(), g.ahead, g.leader))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Olive", None, 0),
+                ("Clove", Some(300), 300),
+                ("Mace", Some(2700), 3000)
+            ]
+        );
+        let (ahead, behind) = closest_fight(&yellow).unwrap();
+        assert_eq!(
+            (ahead.member.name.as_str(), behind.member.name.as_str()),
+            ("Olive", "Clove")
+        );
+        assert_eq!(Race::Yellow.format_gap(300), "+00:05:00");
+
+        let local = race_gaps(&leaderboard, Race::Local, year);
+        assert_eq!(
+            local.iter().map(|g| (g.total, g.ahead)).collect::<Vec<_>>(),
+            vec![(6, None), (4, Some(2)), (2, Some(2))]
+        );
+        assert_eq!(Race::Local.format_gap(2), "-2");
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    core::{
+        absences,
+        chat::UserId,
+        commands::{prefix, Command},
+        events::Event,
+        exclusions, latency,
+        leaderboard::Entry,
+        names, practice,
+        predictions::{self, Guess},
+    },
+    storage::MemoryCache,
+    utils::current_aoc_year_day,
+};
+use chrono::Utc;
+use itertools::Itertools;
+use slack_morphism::SlackUserId;
+use std::collections::HashMap;
+use tokio::sync::mpsc::Sender;
+use tracing::error;
+
+/// Author of a command, as identified by the messaging backend.
+pub struct Requester {
+    // None for the bots allowed to send commands
+    pub user: Option<UserId>,
+    pub is_admin: bool,
+}
+
+/// Run the side effects of the command (links, reminders, admin commands, ...) for the user who
+/// sent it, whatever the messaging backend. Returns the command to reply with, the commands not
+/// allowed for the user being replaced by an explanation.
+pub async fn handle(
+    cmd: Command,
+    requester: &Requester,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+) -> Command {
+    // the users are stored with the Slack type, whatever the messaging backend
+    let user = requester.user.as_ref().map(SlackUserId::from);
+    match (cmd, &user) {
+        (Command::Reminder(member), Some(user)) => {
+            cache.reminders.update(|reminders| match &member {
+                Some(id) => reminders.insert(user.clone(), id.numeric),
+                None => reminders.remove(user),
+            });
+            Command::Reminder(member)
+        }
+        (Command::Watch(member, watch), Some(user)) => {
+            cache.watchlists.update(|watchlists| {
+                let watched = watchlists.entry(user.clone()).or_default();
+                match (&member, watch) {
+                    (Some(id), true) => watched.insert(id.numeric),
+                    (Some(id), false) => watched.remove(&id.numeric),
+                    (None, _) => {
+                        watched.clear();
+                        true
+                    }
+                };
+                watchlists.retain(|_user, watched| !watched.is_empty());
+            });
+            Command::Watch(member, watch)
+        }
+        // a member can only be claimed by a single user
+        (Command::Iam(member), Some(user))
+            if names::slack_users()
+                .get(&member.numeric)
+                .is_some_and(|linked| linked != user) =>
+        {
+            Command::NotValid("This member is already linked to another user 🔒.".to_string())
+        }
+        (Command::Iam(member), Some(user)) => {
+            let link = HashMap::from([(member.numeric, user.clone())]);
+            // the previous link of the user is replaced
+            cache.slack_users.update(|users| {
+                users.retain(|_member, linked| linked != user);
+                users.extend(link.clone())
+            });
+            names::register_slack_users(link);
+            Command::Iam(member)
+        }
+        (Command::ReviewOptIn(opt_in), Some(user)) => {
+            cache.review_opt_outs.update(|opt_outs| match opt_in {
+                true => opt_outs.remove(user),
+                false => opt_outs.insert(user.clone()),
+            });
+            Command::ReviewOptIn(opt_in)
+        }
+        (Command::PersonalSummaryOptIn(true), Some(user))
+            if !names::slack_users().values().contains(user) =>
+        {
+            Command::NotValid(format!(
+                "Link yourself to a member of the leaderboard with `{}iam <name>` first 🔗.",
+                prefix()
+            ))
+        }
+        (Command::PersonalSummaryOptIn(opt_in), Some(user)) => {
+            cache
+                .personal_summary_opt_ins
+                .update(|opt_ins| match opt_in {
+                    true => opt_ins.insert(user.clone()),
+                    false => opt_ins.remove(user),
+                });
+            Command::PersonalSummaryOptIn(opt_in)
+        }
+        (Command::OptOut(opt_out), Some(user)) => {
+            let linked = names::slack_users()
+                .into_iter()
+                .find(|(_member, linked)| linked == user);
+            match linked {
+                Some((member, _user)) if !opt_out && names::is_anonymous(member) => {
+                    Command::NotValid(
+                        "You are kept anonymous by the admins of the bot 🕶️.".to_string(),
+                    )
+                }
+                Some((member, _user)) => {
+                    cache.opt_outs.update(|opt_outs| match opt_out {
+                        true => opt_outs.insert(member),
+                        false => opt_outs.remove(&member),
+                    });
+                    match opt_out {
+                        true => names::register_opt_outs([member]),
+                        false => names::unregister_opt_out(member),
+                    };
+                    // the names of the board have to be resolved again
+                    cache.precomputed.invalidate();
+                    Command::OptOut(opt_out)
+                }
+                None => Command::NotValid(format!(
+                    "Link yourself to a member of the leaderboard with `{}iam <name>` first 🔗.",
+                    prefix()
+                )),
+            }
+        }
+        (Command::Practice(year, _), Some(user)) => {
+            let linked = names::slack_users()
+                .into_iter()
+                .find(|(_member, linked)| linked == user);
+            match linked {
+                Some((member, _user)) => {
+                    let completions = cache.global_completions.read(|c| c.clone());
+                    let data = cache.data.read().unwrap();
+                    let years = match year {
+                        Some(year) => vec![year],
+                        None => data.leaderboard.iter().map(|e| e.year).unique().collect(),
+                    };
+                    let puzzle = practice::suggest_puzzle(
+                        &data.leaderboard,
+                        member,
+                        &years,
+                        current_aoc_year_day(),
+                        &completions,
+                    );
+                    Command::Practice(year, puzzle)
+                }
+                None => Command::NotValid(format!(
+                    "Link yourself to a member of the leaderboard with `{}iam <name>` first 🔗.",
+                    prefix()
+                )),
+            }
+        }
+        (Command::Absence(member, year, days), Some(_)) if requester.is_admin => {
+            let declared = HashMap::from([(
+                member.numeric,
+                days.iter().map(|day| (year, *day)).collect(),
+            )]);
+            cache.absences.update(|absences| {
+                declared
+                    .iter()
+                    .for_each(|(id, days)| absences.entry(*id).or_default().extend(days))
+            });
+            absences::register(declared);
+            // the yellow jersey standings have to be computed again
+            cache.precomputed.invalidate();
+            Command::Absence(member, year, days)
+        }
+        (Command::Exclusion(member, excluded), Some(_)) if requester.is_admin => {
+            let id = member.numeric;
+            cache.exclusions.update(|exclusions| match excluded {
+                true => exclusions.insert(id),
+                false => exclusions.remove(&id),
+            });
+            match excluded {
+                true => exclusions::register([id]),
+                false => exclusions::unregister(id),
+            };
+            // the standings have to be computed again
+            cache.precomputed.invalidate();
+            Command::Exclusion(member, excluded)
+        }
+        (Command::Guess(year, day, guess_sec), Some(user)) => {
+            let is_unlocked =
+                Entry::puzzle_unlock(year, day).is_ok_and(|unlock| unlock <= Utc::now());
+            match is_unlocked {
+                true => Command::NotValid(format!(
+                    "The puzzle of day {day} is already unlocked, the guesses are closed 🔒."
+                )),
+                false => {
+                    let guess = Guess {
+                        year,
+                        day,
+                        user: user.clone(),
+                        guess_sec,
+                    };
+                    cache
+                        .guesses
+                        .update(|guesses| predictions::record_guess(guesses, guess));
+                    Command::Guess(year, day, guess_sec)
+                }
+            }
+        }
+        (Command::Predictions(year, _), _) => {
+            let completions = cache.global_completions.read(|c| c.clone());
+            let standings = cache
+                .guesses
+                .read(|guesses| predictions::season_standings(guesses, &completions, year));
+            Command::Predictions(year, standings)
+        }
+        (Command::Jobs(_), Some(_)) if requester.is_admin => {
+            let jobs = cache.jobs_history.lock().unwrap();
+            Command::Jobs(
+                jobs.iter()
+                    .map(|(job, history)| (job.to_string(), history.clone()))
+                    .collect(),
+            )
+        }
+        (Command::Heroes(..), _) => cache
+            .global_heroes
+            .read(|heroes| Command::Heroes(heroes.scanned.len(), heroes.heroes())),
+        (Command::Latency(year, _), _) => cache.detections.read(|detections| {
+            Command::Latency(year, latency::detection_latency(detections, year))
+        }),
+        (Command::Absence(..), _) => {
+            Command::NotValid("Only the bot admins can declare absences 👮.".to_string())
+        }
+        (Command::AuditLog(n, _), Some(_)) if requester.is_admin => {
+            let messages = cache.audit_log.last(n);
+            Command::AuditLog(n, messages)
+        }
+        (Command::Preview(preview), Some(_)) if requester.is_admin => {
+            // only rendered once the admin is checked
+            let preview = preview.render();
+            if let Err(e) = sender.send(Event::TemplatePreview(preview.clone())).await {
+                error!("{}", e);
+            };
+            Command::Preview(preview)
+        }
+        (Command::Preview(_), _) => {
+            Command::NotValid("Only the bot admins can preview the templates 👮.".to_string())
+        }
+        (Command::Exclusion(..), _) => Command::NotValid(
+            "Only the bot admins can exclude members from the standings 👮.".to_string(),
+        ),
+        (Command::Jobs(_), _) => {
+            Command::NotValid("Only the bot admins can see the history of the jobs 👮.".to_string())
+        }
+        (Command::AuditLog(..), _) => {
+            Command::NotValid("Only the bot admins can see the posted messages 👮.".to_string())
+        }
+        (
+            Command::Reminder(_)
+            | Command::Watch(..)
+            | Command::Iam(_)
+            | Command::ReviewOptIn(_)
+            | Command::PersonalSummaryOptIn(_)
+            | Command::OptOut(_)
+            | Command::Practice(..),
+            None,
+        ) => Command::NotValid("Only users can be reminded or linked to a member.".to_string()),
+        (Command::Guess(..), None) => {
+            Command::NotValid("Only users can take part in the prediction game 🔮.".to_string())
+        }
+        (cmd, _) => cmd,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::leaderboard::{Identifier, MemberId};
+    use crate::fixtures::aoc_server::mock_aoc;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn commands_are_handled_for_any_backend_user() {
+        mock_aoc();
+        let cache = MemoryCache::new();
+        let (tx, _rx) = mpsc::channel(8);
+        // e.g. a Telegram user
+        let requester = Requester {
+            user: Some(UserId("7301".to_string())),
+            is_admin: false,
+        };
+        let member = Identifier {
+            name: "Blitzen".to_string(),
+            numeric: MemberId(7301),
+        };
+
+        let cmd = handle(Command::Iam(member.clone()), &requester, &cache, &tx).await;
+        assert!(matches!(cmd, Command::Iam(_)));
+        assert_eq!(
+            names::slack_users().get(&MemberId(7301)),
+            Some(&SlackUserId("7301".to_string()))
+        );
+
+        let cmd = handle(Command::Exclusion(member, true), &requester, &cache, &tx).await;
+        assert!(matches!(cmd, Command::NotValid(_)));
+        assert!(!exclusions::is_excluded(MemberId(7301)));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    core::{
+        display,
+        leaderboard::{Entry, Leaderboard},
+    },
+    error::BotResult,
+};
+use itertools::Itertools;
+
+// Members with the most stars listed as rows of the heatmap
+pub const HEATMAP_MAX_MEMBERS: usize = 30;
+// Rows of the hours heatmap, the stars collected after the first day sharing the last one
+const HEATMAP_HOURS: usize = 25;
+
+/// Rows of a heatmap, its columns being the days of the event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeatmapRows {
+    // stars collected by each member
+    Members,
+    // stars collected during each hour following the puzzle release
+    Hours,
+}
+
+/// (row label, stars collected on each day) of the members with the most stars of the year,
+/// with the total number of members with stars.
+pub fn members_heatmap(
+    leaderboard: &Leaderboard,
+    year: i32,
+) -> (Vec<(String, [usize; 25])>, usize) {
+    let members = leaderboard
+        .iter()
+        .filter(|e| e.year == year)
+        .into_group_map_by(|e| &e.id);
+    let n_members = members.len();
+    let rows = members
+        .into_iter()
+        .map(|(id, entries)| {
+            let days = entries.iter().fold([0; 25], |mut days, e| {
+                days[(e.day - 1) as usize] += 1;
+                days
+            });
+            (display::display_name(id), days)
+        })
+        .sorted_by(|(a_name, a_days), (b_name, b_days)| {
+            let total = |days: &[usize; 25]| days.iter().sum::<usize>();
+            total(b_days)
+                .cmp(&total(a_days))
+                .then_with(|| a_name.to_lowercase().cmp(&b_name.to_lowercase()))
+        })
+        .take(HEATMAP_MAX_MEMBERS)
+        .collect();
+    (rows, n_members)
+}
+
+/// (row label, stars collected on each day) of each hour following the puzzle release, the stars
+/// collected after the first 24 hours being counted in a last `24h+` row.
+pub fn hours_heatmap(
+    leaderboard: &Leaderboard,
+    year: i32,
+) -> BotResult<Vec<(String, [usize; 25])>> {
+    let mut hours = [[0; 25]; HEATMAP_HOURS];
+    for e in leaderboard.iter().filter(|e| e.year == year) {
+        let hour = (e.timestamp - Entry::puzzle_unlock(year, e.day)?).num_hours();
+        let hour = usize::try_from(hour)
+            .unwrap_or_default()
+            .min(HEATMAP_HOURS - 1);
+        hours[hour][(e.day - 1) as usize] += 1;
+    }
+    Ok(hours
+        .into_iter()
+        .enumerate()
+        .map(|(hour, days)| {
+            let label = match hour {
+                h if h == HEATMAP_HOURS - 1 => format!("{h}h+"),
+                h => format!("+{h:0>2}h"),
+            };
+            (label, days)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn members_are_ranked_by_stars() {
+        let year = 2006;
+        let leaderboard = private_leaderboard(
+            year,
+            &[
+                MemberFixture::new(5501, "Tinsel").with_part1(1, 600),
+                MemberFixture::new(5502, "Garland")
+                    .with_day(1, 300, 60)
+                    .with_part1(3, 1200),
+            ],
+        );
+
+        let (rows, n_members) = members_heatmap(&leaderboard, year);
+        assert_eq!(n_members, 2);
+        assert_eq!(
+            rows.iter()
+                .map(|(name, days)| (name.as_str(), days[0], days[1], days[2]))
+                .collect::<Vec<_>>(),
+            vec![("Garland", 2, 0, 1), ("Tinsel", 1, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn late_stars_share_the_last_hour() {
+        let year = 2006;
+        let leaderboard = private_leaderboard(
+            year,
+            &[MemberFixture::new(5503, "Bauble")
+                .with_day(2, 30 * 60, 2 * 3600)
+                .with_part1(4, 3 * 24 * 3600)],
+        );
+
+        let rows = hours_heatmap(&leaderboard, year).unwrap();
+        assert_eq!(rows.len(), 25);
+        assert_eq!((rows[0].0.as_str(), rows[0].1[1]), ("+00h", 1));
+        assert_eq!(rows[2].1[1], 1);
+        assert_eq!((rows[24].0.as_str(), rows[24].1[3]), ("24h+", 1));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::core::leaderboard::{Entry, Identifier, ProblemPart};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+};
+
+/// Appearance of a member of the private leaderboard on the global leaderboard of a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlobalAppearance {
+    pub member: Identifier,
+    pub year: i32,
+    pub day: u8,
+    pub part: ProblemPart,
+    pub rank: u8,
+}
+
+/// Global leaderboard appearances of the members, recorded by the global leaderboard watch and
+/// by the scan of the past global leaderboards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeroesHistory {
+    // (year, day) of the complete global leaderboards already recorded
+    pub scanned: BTreeSet<(i32, u8)>,
+    pub appearances: Vec<GlobalAppearance>,
+    // (year, day, entry key) of the appearances already announced for the day being watched, so
+    // a restart of the bot does not announce them again
+    #[serde(default)]
+    pub announced: BTreeSet<(i32, u8, String)>,
+}
+
+/// All-time global leaderboard appearances of a member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hero {
+    pub member: Identifier,
+    pub appearances: usize,
+    // (rank, year, day, part) of the best appearance, the earliest one in case of a tie
+    pub best: (u8, i32, u8, ProblemPart),
+    pub years: Vec<i32>,
+}
+
+impl HeroesHistory {
+    /// Record the entries of the members found on the global leaderboard of the day. Returns
+    /// the number of appearances not recorded yet.
+    pub fn record(&mut self, year: i32, day: u8, entries: &[&Entry], is_complete: bool) -> usize {
+        let new_appearances = entries
+            .iter()
+            .filter_map(|e| {
+                let appearance = GlobalAppearance {
+                    member: e.id.clone(),
+                    year,
+                    day,
+                    part: e.part,
+                    rank: e.rank?,
+                };
+                (!self.appearances.iter().any(|a| {
+                    a.member == appearance.member
+                        && (a.year, a.day, a.part) == (year, day, appearance.part)
+                }))
+                .then_some(appearance)
+            })
+            .collect::<Vec<GlobalAppearance>>();
+        let n_new = new_appearances.len();
+        self.appearances.extend(new_appearances);
+        if is_complete {
+            self.scanned.insert((year, day));
+        };
+        n_new
+    }
+
+    /// Whether the entry of the global leaderboard of the day was already announced.
+    pub fn is_announced(&self, year: i32, day: u8, key: &str) -> bool {
+        self.announced.contains(&(year, day, key.to_string()))
+    }
+
+    /// Register the announcement of an entry of the global leaderboard of the day, forgetting
+    /// the announcements of the previous days.
+    pub fn mark_announced(&mut self, year: i32, day: u8, key: String) {
+        self.announced
+            .retain(|(y, d, _key)| (*y, *d) == (year, day));
+        self.announced.insert((year, day, key));
+    }
+
+    /// Days of the years not recorded yet, up to the day before the current day for the ongoing
+    /// event.
+    pub fn days_to_scan(
+        &self,
+        years: &[i32],
+        (current_year, current_day): (i32, u8),
+    ) -> Vec<(i32, u8)> {
+        years
+            .iter()
+            .sorted()
+            .flat_map(|year| {
+                let last_day = match *year == current_year {
+                    true => current_day.saturating_sub(1),
+                    false => 25,
+                };
+                (1..=last_day).map(move |day| (*year, day))
+            })
+            .filter(|year_day| !self.scanned.contains(year_day))
+            .collect()
+    }
+
+    /// All-time global leaderboard appearances per member, most appearances first and then
+    /// best rank first.
+    pub fn heroes(&self) -> Vec<Hero> {
+        self.appearances
+            .iter()
+            .fold(HashMap::new(), |mut acc, a| {
+                acc.entry(a.member.numeric).or_insert_with(Vec::new).push(a);
+                acc
+            })
+            .into_values()
+            .map(|appearances| {
+                let best = appearances
+                    .iter()
+                    .map(|a| (a.rank, a.year, a.day, a.part))
+                    .min()
+                    .unwrap();
+                Hero {
+                    member: appearances[0].member.clone(),
+                    appearances: appearances.len(),
+                    best,
+                    years: appearances
+                        .iter()
+                        .map(|a| a.year)
+                        .sorted()
+                        .dedup()
+                        .collect(),
+                }
+            })
+            .sorted_by_key(|h| (Reverse(h.appearances), h.best, h.member.name.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::leaderboard::MemberId;
+    use chrono::{TimeZone, Utc};
+
+    fn global_entry(id: u64, name: &str, year: i32, day: u8, part: ProblemPart, rank: u8) -> Entry {
+        Entry {
+            timestamp: Utc
+                .with_ymd_and_hms(year, 12, day as u32, 5, 10, 0)
+                .unwrap(),
+            star_index: None,
+            year,
+            day,
+            part,
+            id: Identifier {
+                name: name.to_string(),
+                numeric: MemberId(id),
+            },
+            rank: Some(rank),
+        }
+    }
+
+    #[test]
+    fn appearances_are_recorded_once_and_ranked_per_member() {
+        let mut history = HeroesHistory::default();
+        let day_3 = [
+            global_entry(2601, "Blitzen", 2019, 3, ProblemPart::FIRST, 42),
+            global_entry(2601, "Blitzen", 2019, 3, ProblemPart::SECOND, 17),
+            global_entry(2602, "Cupid", 2019, 3, ProblemPart::SECOND, 88),
+        ];
+        let entries = day_3.iter().collect::<Vec<&Entry>>();
+        assert_eq!(history.record(2019, 3, &entries[..1], false), 1);
+        assert_eq!(history.record(2019, 3, &entries, true), 2);
+        let later = global_entry(2602, "Cupid", 2021, 7, ProblemPart::FIRST, 5);
+        assert_eq!(history.record(2021, 7, &[&later], true), 1);
+
+        assert_eq!(
+            history.days_to_scan(&[2021, 2019], (2021, 8)).len(),
+            25 + 7 - 2
+        );
+        assert!(!history
+            .days_to_scan(&[2019], (2021, 8))
+            .contains(&(2019, 3)));
+
+        let heroes = history.heroes();
+        assert_eq!(heroes.len(), 2);
+        // same number of appearances, Cupid has the best rank
+        assert_eq!(heroes[0].member.numeric, MemberId(2602));
+        assert_eq!(heroes[0].best, (5, 2021, 7, ProblemPart::FIRST));
+        assert_eq!(heroes[0].years, vec![2019, 2021]);
+        assert_eq!(heroes[1].best, (17, 2019, 3, ProblemPart::SECOND));
+
+        history.mark_announced(2019, 3, day_3[0].to_key());
+        assert!(history.is_announced(2019, 3, &day_3[0].to_key()));
+        assert!(!history.is_announced(2019, 3, &day_3[1].to_key()));
+        history.mark_announced(2021, 7, later.to_key());
+        assert_eq!(history.announced.len(), 1);
+    }
+}
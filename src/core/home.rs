@@ -0,0 +1,131 @@
+use crate::{
+    core::{
+        commands::Command,
+        events::split_message,
+        leaderboard::{MemberId, ScrapedLeaderboard},
+        names,
+        review::{year_review, YearReview},
+        standings::{Jersey, Scoring, Standing},
+        templates::MessageTemplate,
+    },
+    storage::precomputed::Precomputed,
+    utils::{format_duration, format_rank},
+};
+use chrono::Duration;
+use minijinja::context;
+
+// Number of leaders of each jersey displayed in the App Home tab
+const HOME_JERSEY_LEADERS: usize = 3;
+// Maximum length of the text of a Block Kit section
+const HOME_SECTION_MAX_LENGTH: usize = 3000;
+
+/// Dashboard displayed in the App Home tab of a Slack user.
+#[derive(Debug)]
+pub struct AppHome {
+    pub year: i32,
+    // whether the Slack user is linked to a member of the leaderboard
+    pub linked: bool,
+    // stats of the linked member, None if not linked or without any star during the event
+    pub review: Option<YearReview>,
+    // first page of the board of the event
+    pub board: Command,
+    // (jersey, [(name, time or points)]) of the leaders of each jersey
+    pub jerseys: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// Dashboard of the event for the Slack user linked to `member` (if any).
+pub fn app_home(
+    data: &ScrapedLeaderboard,
+    precomputed: &Precomputed,
+    member: Option<MemberId>,
+    year: i32,
+) -> AppHome {
+    let standings = Standing::new(&data.leaderboard);
+    let jerseys = [Jersey::YELLOW, Jersey::GREEN, Jersey::COMBATIVE]
+        .iter()
+        .map(|jersey| {
+            let leaders = standings
+                .tdf_season(jersey, year)
+                .into_iter()
+                // members without any point are not leading anything
+                .filter(|(_id, value, _n_days)| matches!(jersey, Jersey::YELLOW) || *value > 0)
+                .take(HOME_JERSEY_LEADERS)
+                .map(|(id, value, _n_days)| {
+                    let value = match jersey {
+                        Jersey::YELLOW => format_duration(Duration::seconds(value)),
+                        _ => format!("{value} pts"),
+                    };
+                    (names::resolve(id), value)
+                })
+                .collect();
+            (jersey.to_string(), leaders)
+        })
+        .collect();
+
+    AppHome {
+        year,
+        linked: member.is_some(),
+        review: member.and_then(|member| year_review(&data.leaderboard, member, year)),
+        board: Command::board_page(data, precomputed, year, Scoring::LOCAL, false, 0),
+        jerseys,
+    }
+}
+
+impl AppHome {
+    /// Texts of the sections of the tab (personal stats and jerseys, then board), split to fit
+    /// in Block Kit sections.
+    pub fn sections(&self) -> Vec<Vec<String>> {
+        let overview = MessageTemplate::AppHome
+            .get()
+            .render(context! {
+                year => self.year,
+                linked => self.linked,
+                review => self.review,
+                rank => self.review.as_ref().map(|r| format_rank(r.rank)),
+                jerseys => self.jerseys,
+            })
+            .unwrap();
+        [overview, self.board.to_string()]
+            .iter()
+            .map(|section| split_message(section, HOME_SECTION_MAX_LENGTH))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::ScrapedLeaderboard,
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+
+    #[test]
+    fn home_shows_the_stats_of_the_linked_member_and_the_jerseys_leaders() {
+        let year = 2019;
+        let members = vec![
+            MemberFixture::new(2701, "Comet")
+                .with_day(1, 300, 60)
+                .with_day(2, 300, 60),
+            MemberFixture::new(2702, "Vixen").with_day(1, 600, 60),
+        ];
+        let mut data = ScrapedLeaderboard::new();
+        data.leaderboard = private_leaderboard(year, &members);
+        let precomputed = Precomputed::default();
+
+        let home = app_home(&data, &precomputed, Some(MemberId(2702)), year);
+        assert!(home.linked);
+        let review = home.review.as_ref().unwrap();
+        assert_eq!((review.n_stars, review.rank), (2, 2));
+        for (jersey, leaders) in &home.jerseys {
+            match jersey.as_str() {
+                "combative" => assert!(leaders.is_empty()),
+                _ => assert_eq!(leaders[0].0, "Comet"),
+            }
+        }
+        assert!(matches!(home.board, Command::LeaderboardDisplay(..)));
+
+        let anonymous = app_home(&data, &precomputed, None, year);
+        assert!(!anonymous.linked && anonymous.review.is_none());
+    }
+}
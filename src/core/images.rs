@@ -0,0 +1,142 @@
+use crate::error::{BotError, BotResult};
+use once_cell::sync::Lazy;
+use plotters::{
+    prelude::*,
+    style::{register_font, FontStyle},
+};
+
+const FONT_FAMILY: &str = "ceo-monospace";
+const FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono.ttf");
+const TEXT_SIZE: f64 = 22.0;
+const HEADING_SIZE: f64 = 26.0;
+const LINE_SPACING: f64 = 1.5;
+const MARGIN: u32 = 24;
+const BACKGROUND: RGBColor = RGBColor(15, 15, 35);
+const TEXT_COLOR: RGBColor = RGBColor(204, 204, 204);
+const HEADING_COLOR: RGBColor = RGBColor(0, 204, 0);
+
+static FONT_REGISTERED: Lazy<bool> =
+    Lazy::new(|| register_font(FONT_FAMILY, FontStyle::Normal, FONT).is_ok());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Heading(String),
+    Text(String),
+}
+
+impl Line {
+    fn style(&self) -> TextStyle<'static> {
+        match self {
+            Line::Heading(_) => (FONT_FAMILY, HEADING_SIZE)
+                .into_font()
+                .color(&HEADING_COLOR),
+            Line::Text(_) => (FONT_FAMILY, TEXT_SIZE).into_font().color(&TEXT_COLOR),
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Line::Heading(text) | Line::Text(text) => text,
+        }
+    }
+
+    fn height(&self) -> u32 {
+        let size = match self {
+            Line::Heading(_) => HEADING_SIZE,
+            Line::Text(_) => TEXT_SIZE,
+        };
+        (size * LINE_SPACING) as u32
+    }
+}
+
+/// Text table rendered as an image, monospace alignment being kept whatever the Slack client.
+#[derive(Debug)]
+pub struct TextImage {
+    // also used as the message accompanying the image
+    pub title: String,
+    pub lines: Vec<Line>,
+}
+
+impl TextImage {
+    pub fn render_png(&self) -> BotResult<Vec<u8>> {
+        if !*FONT_REGISTERED {
+            return Err(BotError::Compute("Could not load the image font."));
+        };
+        let lines = std::iter::once(Line::Heading(self.title.clone()))
+            .chain(self.lines.iter().cloned())
+            .collect::<Vec<Line>>();
+
+        let mut width = 0;
+        for line in &lines {
+            let (line_width, _height) = line
+                .style()
+                .font
+                .box_size(line.text())
+                .map_err(BotError::render("Could not measure text for the image."))?;
+            width = width.max(line_width);
+        }
+        let width = width + 2 * MARGIN;
+        let height = lines.iter().map(|l| l.height()).sum::<u32>() + 2 * MARGIN;
+
+        let mut buffer = vec![0u8; (width * height * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (width, height)).into_drawing_area();
+            root.fill(&BACKGROUND)
+                .map_err(BotError::render("Could not draw the image."))?;
+            let mut y = MARGIN;
+            for line in &lines {
+                root.draw_text(line.text(), &line.style(), (MARGIN as i32, y as i32))
+                    .map_err(BotError::render("Could not draw the image."))?;
+                y += line.height();
+            }
+            root.present()
+                .map_err(BotError::render("Could not draw the image."))?;
+        }
+
+        encode_png(&buffer, width, height)
+    }
+}
+
+fn encode_png(rgb: &[u8], width: u32, height: u32) -> BotResult<Vec<u8>> {
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(BotError::render("Could not encode the image."))?;
+    writer
+        .write_image_data(rgb)
+        .map_err(BotError::render("Could not encode the image."))?;
+    writer
+        .finish()
+        .map_err(BotError::render("Could not encode the image."))?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_rendered_as_png() {
+        let image = TextImage {
+            title: "Leaderboard".to_string(),
+            lines: vec![
+                Line::Heading("Top 5".to_string()),
+                Line::Text("1) Santa   100  [ ■ ■ □ -]".to_string()),
+                Line::Text("2) Rudolph  42  [ ■ □ - -]".to_string()),
+            ],
+        };
+
+        let png = image.render_png().unwrap();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let decoder = png::Decoder::new(png.as_slice());
+        let info = decoder.read_info().unwrap().info().clone();
+        let heights =
+            (HEADING_SIZE * LINE_SPACING) as u32 * 2 + (TEXT_SIZE * LINE_SPACING) as u32 * 2;
+        assert_eq!(info.height, heights + 2 * MARGIN);
+        assert!(info.width > 2 * MARGIN);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::core::templates::MessageTemplate;
+use itertools::Itertools;
+use minijinja::context;
+use std::cmp::Reverse;
+
+/// (reaction, language) proposed in the poll of the daily solutions thread.
+pub const POLL_LANGUAGES: [(&str, &str); 12] = [
+    ("crab", "Rust"),
+    ("snake", "Python"),
+    ("racing_car", "Go"),
+    ("coffee", "Java"),
+    ("camel", "OCaml"),
+    ("lizard", "Zig"),
+    ("gem", "Ruby"),
+    ("large_blue_square", "TypeScript"),
+    ("large_yellow_square", "JavaScript"),
+    ("gear", "C / C++"),
+    ("bar_chart", "Excel"),
+    ("jigsaw", "Other"),
+];
+
+/// Usage of a language over an event, from the votes of the daily polls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageUsage {
+    pub language: String,
+    pub votes: usize,
+    // days with at least one vote for the language
+    pub n_days: usize,
+}
+
+/// Message of the daily poll, the members voting with the reactions added by the bot.
+pub fn poll_message() -> String {
+    MessageTemplate::LanguagePoll
+        .get()
+        .render(context! {
+            languages => POLL_LANGUAGES
+                .iter()
+                .map(|(reaction, language)| (format!(":{reaction}:"), *language))
+                .collect::<Vec<(String, &str)>>(),
+        })
+        .unwrap()
+}
+
+/// Languages used over the event, most voted first, from the (reaction, count) of each daily
+/// poll. The reactions added by the bot to open the polls are not counted as votes.
+pub fn season_languages(polls: &[Vec<(String, usize)>]) -> Vec<LanguageUsage> {
+    polls
+        .iter()
+        .flat_map(|reactions| {
+            reactions.iter().filter_map(|(reaction, count)| {
+                let (_reaction, language) = POLL_LANGUAGES.iter().find(|(r, _)| r == reaction)?;
+                let votes = count.saturating_sub(1);
+                (votes > 0).then_some((*language, votes))
+            })
+        })
+        .into_group_map()
+        .into_iter()
+        .map(|(language, votes)| LanguageUsage {
+            language: language.to_string(),
+            votes: votes.iter().sum(),
+            n_days: votes.len(),
+        })
+        .sorted_by_key(|usage| {
+            (
+                Reverse(usage.votes),
+                Reverse(usage.n_days),
+                usage.language.clone(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_members_votes_are_counted() {
+        let reaction = |name: &str, count: usize| (name.to_string(), count);
+        let polls = vec![
+            vec![
+                reaction("crab", 4),
+                reaction("snake", 2),
+                reaction("excel", 1),
+            ],
+            vec![
+                reaction("crab", 1),
+                reaction("snake", 3),
+                reaction("tada", 5),
+            ],
+            vec![reaction("gem", 3)],
+        ];
+
+        let usage = season_languages(&polls)
+            .into_iter()
+            .map(|u| (u.language, u.votes, u.n_days))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usage,
+            vec![
+                ("Python".to_string(), 3, 2),
+                ("Rust".to_string(), 3, 1),
+                ("Ruby".to_string(), 2, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    core::leaderboard::{Leaderboard, MemberId, ProblemPart},
+    scheduler::PRIVATE_LEADERBOARD_REFRESH_MIN,
+    utils::format_duration,
+};
+use chrono::{DateTime, Duration, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// Star seen for the first time by the bot, in a scrape of the private leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    pub year: i32,
+    pub day: u8,
+    pub part: ProblemPart,
+    pub member: MemberId,
+    // completion time given by AoC
+    pub completed: DateTime<Utc>,
+    // time of the scrape the star was first seen in
+    pub detected: DateTime<Utc>,
+}
+
+/// Delay between the completion of the stars of an event and their detection by the bot.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectionLatency {
+    pub n_stars: usize,
+    pub average: String,
+    pub median: String,
+    pub max: String,
+    // share (in %) of the stars detected within the refresh interval of the private leaderboard
+    pub within_refresh: usize,
+    pub refresh_min: u32,
+}
+
+/// Stars newly merged into the cached leaderboard (see `ScrapedLeaderboard::merge_with`),
+/// detected at the scrape time. Only the stars completed since the previous scrape are
+/// detections: the older ones (e.g. of a member joining the leaderboard) were not missed by the
+/// previous scrape.
+pub fn new_detections(
+    new_entries: &Leaderboard,
+    previous_scrape: DateTime<Utc>,
+    detected: DateTime<Utc>,
+) -> Vec<Detection> {
+    new_entries
+        .iter()
+        .filter(|e| e.timestamp >= previous_scrape)
+        .map(|e| Detection {
+            year: e.year,
+            day: e.day,
+            part: e.part,
+            member: e.id.numeric,
+            completed: e.timestamp,
+            detected,
+        })
+        .collect()
+}
+
+/// Latency of the detections of the year, None if no star of the year was detected.
+pub fn detection_latency(detections: &[Detection], year: i32) -> Option<DetectionLatency> {
+    let delays = detections
+        .iter()
+        .filter(|d| d.year == year)
+        // the clocks of AoC and the bot might drift slightly
+        .map(|d| (d.detected - d.completed).max(Duration::zero()))
+        .sorted()
+        .collect::<Vec<Duration>>();
+    let max = *delays.last()?;
+    let total = delays.iter().fold(Duration::zero(), |total, d| total + *d);
+    let refresh = Duration::minutes(PRIVATE_LEADERBOARD_REFRESH_MIN as i64);
+    let n_within = delays.iter().filter(|d| **d <= refresh).count();
+    Some(DetectionLatency {
+        n_stars: delays.len(),
+        average: format_duration(total / delays.len() as i32),
+        median: format_duration(delays[delays.len() / 2]),
+        max: format_duration(max),
+        within_refresh: n_within * 100 / delays.len(),
+        refresh_min: PRIVATE_LEADERBOARD_REFRESH_MIN,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::{Entry, ScrapedLeaderboard},
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+
+    #[test]
+    fn only_stars_completed_since_the_previous_scrape_are_detected() {
+        let year = 2009;
+        let previous_scrape = Entry::puzzle_unlock(year, 2).unwrap();
+        let mut cached = ScrapedLeaderboard {
+            timestamp: previous_scrape,
+            leaderboard: private_leaderboard(
+                year,
+                &[MemberFixture::new(5301, "Holly").with_day(1, 600, 60)],
+            ),
+        };
+        let scraped = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                year,
+                &[
+                    MemberFixture::new(5301, "Holly")
+                        .with_day(1, 600, 60)
+                        .with_part1(2, 300),
+                    // joining the leaderboard after the first day
+                    MemberFixture::new(5302, "Ivy")
+                        .with_part1(1, 1200)
+                        .with_part1(2, 900),
+                ],
+            ),
+        };
+        let detected = scraped.timestamp;
+
+        let new_entries = cached.merge_with(scraped);
+        let detections = new_detections(&new_entries, previous_scrape, detected);
+        assert_eq!(
+            detections
+                .iter()
+                .map(|d| (d.member.0, d.day, d.detected))
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![(5301, 2, detected), (5302, 2, detected)]
+        );
+    }
+
+    #[test]
+    fn latency_is_measured_from_the_completion() {
+        let completed = Utc::now();
+        let detection = |year: i32, minutes: i64| Detection {
+            year,
+            day: 1,
+            part: ProblemPart::FIRST,
+            member: MemberId(5303),
+            completed,
+            detected: completed + Duration::minutes(minutes),
+        };
+        let detections = vec![
+            detection(2009, 2),
+            detection(2009, 14),
+            detection(2009, 20),
+            detection(2008, 90),
+        ];
+
+        let latency = detection_latency(&detections, 2009).unwrap();
+        assert_eq!(latency.n_stars, 3);
+        assert_eq!(
+            (latency.average, latency.median, latency.max),
+            (
+                "00:12:00".to_string(),
+                "00:14:00".to_string(),
+                "00:20:00".to_string()
+            )
+        );
+        assert_eq!(latency.within_refresh, 66);
+        assert!(detection_latency(&detections, 2007).is_none());
+    }
+}
@@ -1,29 +1,93 @@
-use crate::error::{BotError, BotResult};
+use crate::{
+    core::{exclusions, names},
+    error::{BotError, BotResult},
+};
 use chrono::{naive::NaiveDateTime, DateTime, Duration, TimeZone, Utc};
 use itertools::{Itertools, MinMaxResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use scraper::{Node, Selector};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
+    cmp::{Ordering, Reverse},
     collections::{HashMap, HashSet},
     fmt,
+    hash::{Hash, Hasher},
     iter::Iterator,
     ops::{Deref, DerefMut},
 };
 
-static AOC_PUZZLE_UTC_STARTING_HOUR: u32 = 5;
+pub static AOC_PUZZLE_UTC_STARTING_HOUR: u32 = 5;
 static AOC_MONTH: u32 = 12;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize)]
+// Fallbacks of the global leaderboard parsing, on the text of the entries
+static ANONYMOUS_ID_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"anonymous user #(\d+)").unwrap());
+static RANK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(\d+)\)").unwrap());
+static TIME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Z][a-z]{2})\s+(\d{1,2})\s+(\d{2}:\d{2}:\d{2})").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ProblemPart {
     FIRST,
     SECOND,
 }
 
+/// Field of a global leaderboard entry that could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryParseFailure {
+    MissingId,
+    MissingRank,
+    MissingTime,
+}
+
+/// Outcome of the parsing of a global leaderboard page.
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct GlobalParseReport {
+    pub parsed: usize,
+    // entries displayed on the page, based on the ranks of each part
+    pub expected: usize,
+    pub failures: HashMap<EntryParseFailure, usize>,
+}
+
+impl GlobalParseReport {
+    /// Whether some of the entries displayed on the page could not be parsed.
+    pub fn is_incomplete(&self) -> bool {
+        self.parsed < self.expected
+    }
+}
+
+impl fmt::Display for GlobalParseReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} global leaderboard entries parsed",
+            self.parsed, self.expected
+        )?;
+        if !self.failures.is_empty() {
+            let failures = self
+                .failures
+                .iter()
+                .sorted_by_key(|(_failure, count)| Reverse(**count))
+                .map(|(failure, count)| format!("{failure:?}: {count}"))
+                .join(", ");
+            write!(f, " ({failures})")?;
+        }
+        Ok(())
+    }
+}
+
 // Leaderboard entry parsed from AoC API.
 // Year and day fields match corresponding components of DateTime<Utc>.
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize)]
+// Entries are ordered chronologically, the AoC star index breaking ties between stars earned
+// during the same second.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub timestamp: DateTime<Utc>,
+    // only provided by the private leaderboard API (snapshots prior to it do not have it)
+    #[serde(default)]
+    pub star_index: Option<u64>,
     pub year: i32,
     pub day: u8,
     pub part: ProblemPart,
@@ -31,19 +95,24 @@ pub struct Entry {
     pub rank: Option<u8>,
 }
 
-// unique identifier for a participant on this leaderboard
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize)]
+// AoC numeric id of a participant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MemberId(pub u64);
+
+// unique identifier for a participant on this leaderboard.
+// Members are identified by their numeric id only, as names can change over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Identifier {
     pub name: String,
-    pub numeric: u64,
+    pub numeric: MemberId,
 }
 
 type Entries = HashSet<Entry>;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Leaderboard(Entries);
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ScrapedLeaderboard {
     pub timestamp: chrono::DateTime<Utc>,
     pub leaderboard: Leaderboard,
@@ -73,6 +142,38 @@ impl fmt::Display for ProblemPart {
     }
 }
 
+impl fmt::Display for MemberId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq for Identifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.numeric == other.numeric
+    }
+}
+
+impl Eq for Identifier {}
+
+impl Hash for Identifier {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.numeric.hash(state);
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numeric.cmp(&other.numeric)
+    }
+}
+
 impl ProblemPart {
     pub fn from(input: usize) -> Self {
         match input {
@@ -85,89 +186,93 @@ impl ProblemPart {
 }
 
 impl Entry {
-    /// Parsing of global leaderboard HTML nodes.
+    /// Parsing of global leaderboard HTML nodes. Each field is looked up with the selectors of the
+    /// current AoC layout first, then with fallbacks on the text of the entry, so a small change
+    /// of the DOM does not silently drop the entries.
     pub fn from_html(
         entry: scraper::element_ref::ElementRef,
         year: i32,
         day: u8,
         part: ProblemPart,
-    ) -> Option<Self> {
+    ) -> Result<Self, EntryParseFailure> {
         let rank_selector = Selector::parse(r#".leaderboard-position"#).unwrap();
         let time_selector = Selector::parse(r#".leaderboard-time"#).unwrap();
+        let text = entry.text().collect::<String>();
 
         let id = entry
             .value()
             .attr("data-user-id")
-            .and_then(|id| id.parse::<u64>().ok());
+            .and_then(|id| id.trim().parse::<u64>().ok())
+            // anonymous users are displayed with their id
+            .or_else(|| {
+                ANONYMOUS_ID_REGEX
+                    .captures(&text)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+            })
+            .ok_or(EntryParseFailure::MissingId)?;
 
         // Depending on whether users have declared their github, are sponsors, etc ... the name
         // will be nested in different possible DOM hierarchy layouts.
         let name = entry
             .children()
             .filter_map(|node| match node.value() {
-                Node::Text(text) => Some(text.trim()),
-                Node::Element(el) => match el.name() {
-                    // Name wrapped into <a> tags to link to user's github.
-                    "a" => {
-                        let text = node.last_child().unwrap().value();
-                        let text = text.as_text().unwrap().trim();
-                        // We ignore <a> tags related to (AoC++) or (Sponsor) labels.
-                        match (text.starts_with('('), text.ends_with(')')) {
-                            (false, false) => Some(text),
-                            (_, _) => None,
-                        }
+                Node::Text(text) => Some(text.trim().to_string()),
+                // Name wrapped into <a> tags to link to user's github.
+                Node::Element(el) if el.name() == "a" => {
+                    let text = scraper::element_ref::ElementRef::wrap(node)?
+                        .text()
+                        .collect::<String>();
+                    let text = text.trim();
+                    // We ignore <a> tags related to (AoC++) or (Sponsor) labels.
+                    match (text.starts_with('('), text.ends_with(')')) {
+                        (false, false) => Some(text.to_string()),
+                        (_, _) => None,
                     }
-                    _ => None,
-                },
+                }
                 _ => None,
             })
             .filter(|text| !text.is_empty())
             .last();
 
-        let rank = match entry.select(&rank_selector).next() {
-            Some(text) => match text.text().next() {
-                Some(t) => t
-                    .split(')')
-                    .next()
-                    .and_then(|rank| rank.trim().parse::<u8>().ok()),
-                None => None,
-            },
-            None => None,
-        };
+        let rank = entry
+            .select(&rank_selector)
+            .next()
+            .map_or(text.clone(), |node| node.text().collect::<String>());
+        let rank = RANK_REGEX
+            .captures(&rank)
+            .and_then(|c| c[1].parse::<u8>().ok())
+            .ok_or(EntryParseFailure::MissingRank)?;
 
-        let timestamp = match entry.select(&time_selector).next() {
-            Some(t) => t
-                .text()
-                .filter_map(|time| {
-                    let with_year = format!("{} {}", year, time);
-                    NaiveDateTime::parse_from_str(&with_year, "%Y %b %d  %H:%M:%S").ok()
-                })
-                .map(|d| {
-                    // Global leaderboard entries are starting at 00:00:00, so we need to offset by
-                    // 5 hours to get real UTC time.
-                    Utc.from_utc_datetime(&d) + Duration::hours(AOC_PUZZLE_UTC_STARTING_HOUR.into())
-                })
-                .last(),
-            None => None,
-        };
+        let time = entry
+            .select(&time_selector)
+            .next()
+            .map_or(text.clone(), |node| node.text().collect::<String>());
+        let timestamp = TIME_REGEX
+            .captures(&time)
+            .and_then(|c| {
+                let with_year = format!("{} {} {} {}", year, &c[1], &c[2], &c[3]);
+                NaiveDateTime::parse_from_str(&with_year, "%Y %b %d %H:%M:%S").ok()
+            })
+            .map(|d| {
+                // Global leaderboard entries are starting at 00:00:00, so we need to offset by
+                // 5 hours to get real UTC time.
+                Utc.from_utc_datetime(&d) + Duration::hours(AOC_PUZZLE_UTC_STARTING_HOUR.into())
+            })
+            .ok_or(EntryParseFailure::MissingTime)?;
 
-        match (id, name, rank, timestamp) {
-            (Some(id), _, Some(rank), Some(timestamp)) => Some(Entry {
-                id: Identifier {
-                    // Name of anonymous user will be None
-                    name: name
-                        .map_or(format!("anonymous user #{}", id), |n| n.to_string())
-                        .to_string(),
-                    numeric: id,
-                },
-                rank: Some(rank),
-                part,
-                year,
-                day,
-                timestamp,
-            }),
-            _ => None,
-        }
+        Ok(Entry {
+            id: Identifier {
+                // Name of anonymous user will be None
+                name: name.map_or(format!("anonymous user #{}", id), |n| n.to_string()),
+                numeric: MemberId(id),
+            },
+            rank: Some(rank),
+            star_index: None,
+            part,
+            year,
+            day,
+            timestamp,
+        })
     }
 
     /// Time of the release of the corresponding puzzle.
@@ -185,6 +290,12 @@ impl Entry {
         .ok_or(BotError::Parse)
     }
 
+    /// Whether the star is left out of the standings. Exclusions only apply to the private
+    /// leaderboard: the global leaderboard entries, which carry their global rank, are all kept.
+    pub fn is_excluded(&self) -> bool {
+        self.rank.is_none() && exclusions::is_excluded(self.id.numeric)
+    }
+
     /// generate key from entry
     pub fn to_key(&self) -> String {
         format!(
@@ -218,6 +329,12 @@ impl Leaderboard {
         Leaderboard(Entries::new())
     }
 
+    /// Entries counted in the standings, the private leaderboard ones of the excluded members
+    /// being left out.
+    pub fn ranked_entries(&self) -> impl Iterator<Item = &Entry> {
+        self.iter().filter(|e| !e.is_excluded())
+    }
+
     pub fn is_global_complete(&self) -> bool {
         // 100 entries for each part, so completion of global leaderboard
         // for a specific day is 2*100
@@ -230,7 +347,7 @@ impl Leaderboard {
         year: i32,
         day: u8,
     ) -> HashMap<&Identifier, Vec<&Entry>> {
-        self.iter()
+        self.ranked_entries()
             .filter(|s| s.year == year && s.day == day)
             .into_group_map_by(|e| &e.id)
     }
@@ -238,7 +355,7 @@ impl Leaderboard {
     /// (year, id) => [score per day for that year]
     pub fn daily_scores_per_year_member(&self) -> HashMap<(i32, &Identifier), [usize; 25]> {
         // Max point earned for each star is number of members in leaderboard
-        let members_solutions = self.iter().into_group_map_by(|a| (a.year, &a.id));
+        let members_solutions = self.ranked_entries().into_group_map_by(|a| (a.year, &a.id));
         let n_members_per_year = members_solutions
             .iter()
             .map(|((y, id), _)| (y, id))
@@ -269,8 +386,20 @@ impl Leaderboard {
         year: i32,
     ) -> HashMap<&Identifier, [(u8, usize); 25]> {
         // Max point earned for each star is number of members in leaderboard
+        self.daily_stars_and_points_per_member_for_year(year, |rank_minus_one, n_members| {
+            n_members - rank_minus_one
+        })
+    }
+
+    /// id => [(number of stars, points) per day for that year], the points of each star being
+    /// given by its rank (0 based) among the number of members of the year.
+    pub fn daily_stars_and_points_per_member_for_year(
+        &self,
+        year: i32,
+        points: impl Fn(usize, usize) -> usize,
+    ) -> HashMap<&Identifier, [(u8, usize); 25]> {
         let members_solutions = self
-            .iter()
+            .ranked_entries()
             .filter(|e| e.year == year)
             .into_group_map_by(|a| &a.id);
         let n_members = members_solutions.len();
@@ -283,7 +412,7 @@ impl Leaderboard {
                     .iter()
                     .enumerate()
                     .for_each(|(rank_minus_one, id)| {
-                        let star_score = n_members - rank_minus_one;
+                        let star_score = points(rank_minus_one, n_members);
                         let day_stars_scores = acc.entry(id).or_insert([(0, 0); 25]);
                         day_stars_scores[(*day - 1) as usize].0 += 1;
                         day_stars_scores[(*day - 1) as usize].1 += star_score;
@@ -368,7 +497,7 @@ impl Leaderboard {
     ) -> HashMap<(u8, ProblemPart), (DateTime<Utc>, DateTime<Utc>)> {
         // Compute max time for each part, in order to infer deltas for members who only scored
         // one part of the global leaderboard that day.
-        self.iter()
+        self.ranked_entries()
             .filter(|s| s.year == year)
             .into_group_map_by(|s| (s.day, s.part))
             .into_iter()
@@ -384,18 +513,12 @@ impl Leaderboard {
 
     pub fn statistics_for_year_day(&self, year: i32, day: u8) -> BotResult<LeaderboardStatistics> {
         let minmax_by_part = self.min_max_times_for_year_day(year, day);
-        let (p1_fast, p1_slow) =
-            minmax_by_part
-                .get(&ProblemPart::FIRST)
-                .ok_or(BotError::Compute(
-                    "Could not retrieve minmax for part 1".to_string(),
-                ))?;
-        let (p2_fast, p2_slow) =
-            minmax_by_part
-                .get(&ProblemPart::SECOND)
-                .ok_or(BotError::Compute(
-                    "Could not retrieve minmax for part 2".to_string(),
-                ))?;
+        let (p1_fast, p1_slow) = minmax_by_part
+            .get(&ProblemPart::FIRST)
+            .ok_or(BotError::Compute("Could not retrieve minmax for part 1"))?;
+        let (p2_fast, p2_slow) = minmax_by_part
+            .get(&ProblemPart::SECOND)
+            .ok_or(BotError::Compute("Could not retrieve minmax for part 2"))?;
 
         let sorted_deltas = self.standings_by_delta_for_year_day(year, day)?;
         let mut sorted_deltas_iter = sorted_deltas.iter();
@@ -423,11 +546,12 @@ impl Leaderboard {
 
     /// (year, day, part) => (unordered) stars
     fn entries_per_year_day_part(&self) -> HashMap<(i32, u8, ProblemPart), Vec<&Entry>> {
-        self.iter().into_group_map_by(|a| (a.year, a.day, a.part))
+        self.ranked_entries()
+            .into_group_map_by(|a| (a.year, a.day, a.part))
     }
 
     fn entries_per_day_part_for_year(&self, year: i32) -> HashMap<(u8, ProblemPart), Vec<&Entry>> {
-        self.iter()
+        self.ranked_entries()
             .filter(|e| e.year == year)
             .into_group_map_by(|a| (a.day, a.part))
     }
@@ -436,16 +560,117 @@ impl Leaderboard {
         &self,
         year: i32,
     ) -> HashMap<(u8, &Identifier), Vec<&Entry>> {
-        self.iter()
+        self.ranked_entries()
             .filter(|e| e.year == year)
             .into_group_map_by(|a| (a.day, &a.id))
     }
 
+    /// (member, [(number of stars, no score) for the 25 days]) of the members excluded from the
+    /// standings who collected stars during the year
+    pub fn excluded_stars_for_year(&self, year: i32) -> Vec<(&Identifier, [(u8, usize); 25])> {
+        self.iter()
+            .filter(|e| e.year == year && e.is_excluded())
+            .into_group_map_by(|e| &e.id)
+            .into_iter()
+            .map(|(id, entries)| {
+                let stars = entries.iter().fold([(0, 0); 25], |mut acc, e| {
+                    acc[(e.day - 1) as usize].0 += 1;
+                    acc
+                });
+                (id, stars)
+            })
+            .sorted_by_key(|(id, _stars)| names::resolve(id))
+            .collect()
+    }
+
     /// all members ids
-    fn members_ids(&self) -> HashSet<u64> {
+    pub fn members_ids(&self) -> HashSet<MemberId> {
         self.iter().map(|e| e.id.numeric).collect()
     }
 
+    /// member matching the AoC numeric id or name (case insensitive), the members who opted out
+    /// only being found by id, so their name can not be checked against the leaderboard
+    pub fn find_member(&self, id_or_name: &str) -> Option<Identifier> {
+        let numeric = id_or_name.parse::<u64>().ok().map(MemberId);
+        self.iter()
+            .map(|e| &e.id)
+            .find(|id| {
+                Some(id.numeric) == numeric
+                    || (id.name.eq_ignore_ascii_case(id_or_name)
+                        && !names::is_opted_out(id.numeric))
+            })
+            .cloned()
+    }
+
+    /// (day, number of missing stars) for the member, for the days of the year up to `last_day`
+    pub fn missing_stars_for_member(
+        &self,
+        member: MemberId,
+        year: i32,
+        last_day: u8,
+    ) -> Vec<(u8, usize)> {
+        let stars_per_day = self
+            .iter()
+            .filter(|e| e.year == year && e.id.numeric == member)
+            .counts_by(|e| e.day);
+        (1..=last_day)
+            .map(|day| {
+                (
+                    day,
+                    2 - stars_per_day.get(&day).copied().unwrap_or(0).min(2),
+                )
+            })
+            .filter(|(_day, missing)| *missing > 0)
+            .collect()
+    }
+
+    /// (member, number of previous days of the year with a star) for the members (of any year)
+    /// without any star on the day, the most regular participants first
+    pub fn members_without_stars_for_year_day(
+        &self,
+        year: i32,
+        day: u8,
+    ) -> Vec<(&Identifier, usize)> {
+        let started = self.entries_per_member_for_year_day(year, day);
+        let participation = self
+            .ranked_entries()
+            .filter(|e| e.year == year && e.day < day)
+            .map(|e| (e.id.numeric, e.day))
+            .unique()
+            .counts_by(|(member, _day)| member);
+
+        self.ranked_entries()
+            .map(|e| &e.id)
+            .unique_by(|id| id.numeric)
+            .filter(|id| !started.keys().any(|s| s.numeric == id.numeric))
+            .map(|id| (id, participation.get(&id.numeric).copied().unwrap_or(0)))
+            .sorted_by_key(|(id, n_days)| (Reverse(*n_days), id.name.clone()))
+            .collect()
+    }
+
+    /// (part 1, part 2) stars collected during each of the first 24 hours following the puzzle
+    /// release of the day
+    pub fn stars_per_hour_for_year_day(
+        &self,
+        year: i32,
+        day: u8,
+    ) -> BotResult<[(usize, usize); 24]> {
+        let release = Entry::puzzle_unlock(year, day)?;
+        Ok(self.iter().filter(|e| e.year == year && e.day == day).fold(
+            [(0, 0); 24],
+            |mut acc, e| {
+                let hour = (e.timestamp - release).num_hours();
+                if let Some(stars) = usize::try_from(hour).ok().and_then(|h| acc.get_mut(h)) {
+                    match e.part {
+                        ProblemPart::FIRST => stars.0 += 1,
+                        ProblemPart::SECOND => stars.1 += 1,
+                    };
+                };
+                acc
+            },
+        ))
+    }
+
     /// (year, day, part) => [ordered members]
     fn ranked_members_per_year_day_part(
         &self,
@@ -457,7 +682,7 @@ impl Leaderboard {
                     challenge,
                     entries
                         .into_iter()
-                        // sort solutions chronologically by timestamp
+                        // sort solutions chronologically by timestamp, then by star index
                         .sorted_unstable()
                         // retrieve author of the solution
                         .map(|s| &s.id)
@@ -479,7 +704,7 @@ impl Leaderboard {
                     challenge,
                     entries
                         .into_iter()
-                        // sort solutions chronologically by timestamp
+                        // sort solutions chronologically by timestamp, then by star index
                         .sorted_unstable()
                         // retrieve author of the solution
                         .map(|s| &s.id)
@@ -496,7 +721,7 @@ impl Leaderboard {
     ) -> HashMap<ProblemPart, (DateTime<Utc>, DateTime<Utc>)> {
         // Compute max time for each part, in order to infer deltas for members who only scored
         // one part of the global leaderboard that day.
-        self.iter()
+        self.ranked_entries()
             .filter(|s| s.year == year && s.day == day)
             .into_group_map_by(|s| s.part)
             .iter()
@@ -522,9 +747,7 @@ impl Leaderboard {
             .min_max_times_for_year_day(year, day)
             .get(&ProblemPart::FIRST)
             .map(|(_p1_fast, p1_slow)| *p1_slow)
-            .ok_or(BotError::Compute(
-                "MinMax times could not be computed".to_string(),
-            ))?;
+            .ok_or(BotError::Compute("MinMax times could not be computed"))?;
 
         let standings = self
             .entries_per_member_for_year_day(year, day)
@@ -589,13 +812,357 @@ impl ScrapedLeaderboard {
         }
     }
 
-    pub fn merge_with(&mut self, other: ScrapedLeaderboard) {
+    /// Merge the other leaderboard, only inserting its entries not known yet. Returns these new
+    /// entries.
+    pub fn merge_with(&mut self, other: ScrapedLeaderboard) -> Leaderboard {
         self.timestamp = other.timestamp;
-        // TODO: if a member changes his/her name, this will be flagged as a new member ...
-        // We need to handle this by checking on unique id. Maybe replace the full year with updated
-        // leaderboard if we find duplicates for same id ?
+        // Entries are identified by member numeric id, so a member changing his/her name is not
+        // duplicated. We only need to keep track of the latest name for display purpose.
+        names::register(&other.leaderboard);
+
+        // Entries restored from snapshots taken before the star index was recorded are replaced
+        // by their indexed counterpart, so the same star is not counted twice (nor as new).
+        let indexed = other
+            .leaderboard
+            .iter()
+            .filter(|e| e.star_index.is_some())
+            .map(|e| (e.year, e.day, e.part, e.id.numeric))
+            .collect::<HashSet<(i32, u8, ProblemPart, MemberId)>>();
+        let mut replaced = HashSet::new();
+        if !indexed.is_empty() {
+            self.leaderboard.retain(|e| {
+                let star = (e.year, e.day, e.part, e.id.numeric);
+                let is_replaced = e.star_index.is_none() && indexed.contains(&star);
+                if is_replaced {
+                    replaced.insert(star);
+                };
+                !is_replaced
+            });
+        };
+
+        let mut new_entries = Leaderboard::new();
+        for entry in other.leaderboard.0 {
+            if self.leaderboard.contains(&entry) {
+                continue;
+            };
+            self.leaderboard.insert(entry.clone());
+            if !replaced.contains(&(entry.year, entry.day, entry.part, entry.id.numeric)) {
+                new_entries.insert(entry);
+            };
+        }
+        new_entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{
+        edge_case_members, private_leaderboard, random_board, MemberFixture, RANDOM_BOARDS, YEAR,
+    };
+    use proptest::prelude::*;
+
+    fn scraped(members: &[MemberFixture]) -> ScrapedLeaderboard {
+        ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(YEAR, members),
+        }
+    }
+
+    #[test]
+    fn merging_same_leaderboard_does_not_duplicate_entries() {
+        let mut cache = ScrapedLeaderboard::new();
+        let new_entries = cache.merge_with(scraped(&edge_case_members()));
+        let n_entries = cache.leaderboard.len();
+        assert_eq!(new_entries.len(), n_entries);
+        let new_entries = cache.merge_with(scraped(&edge_case_members()));
+
+        assert_eq!(cache.leaderboard.len(), n_entries);
+        assert!(new_entries.is_empty());
+    }
+
+    #[test]
+    fn merging_renamed_member_keeps_entries_once() {
+        let mut cache = ScrapedLeaderboard::new();
+        cache.merge_with(scraped(&[
+            MemberFixture::new(1001, "Comet").with_day(1, 100, 10)
+        ]));
+        cache.merge_with(scraped(&[MemberFixture::new(1001, "Comet the Great")
+            .with_day(1, 100, 10)
+            .with_part1(2, 200)]));
+
+        assert_eq!(cache.leaderboard.len(), 3);
+        assert_eq!(cache.leaderboard.members_ids().len(), 1);
+        let id = &cache.leaderboard.iter().next().unwrap().id;
+        assert_eq!(names::resolve(id), "Comet the Great");
+    }
+
+    #[test]
+    fn excluded_members_are_left_out_of_the_scores() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(4801, "Bot").with_day(1, 10, 1),
+                MemberFixture::new(4802, "Human").with_day(1, 600, 60),
+            ],
+        );
+        // the same star of the member on the global leaderboard
+        let mut global = leaderboard
+            .iter()
+            .find(|e| e.id.numeric == MemberId(4801))
+            .unwrap()
+            .clone();
+        global.rank = Some(1);
+
+        exclusions::register([MemberId(4801)]);
+        let scores = leaderboard.daily_stars_and_scores_per_member_for_year(YEAR);
+        let excluded = leaderboard.excluded_stars_for_year(YEAR);
+        let global_excluded = global.is_excluded();
+        // the registry is shared by all the tests, so it is restored before any assertion
+        exclusions::unregister(MemberId(4801));
+
+        assert_eq!(scores.len(), 1);
+        // the only ranked member gets all the points
+        assert!(scores.values().all(|days| days[0] == (2, 2)));
+        assert_eq!(leaderboard.members_ids().len(), 2);
+        assert!(!global_excluded);
+
+        assert_eq!(excluded.len(), 1);
+        assert_eq!(excluded[0].0.numeric, MemberId(4801));
+        assert_eq!(excluded[0].1[0], (2, 0));
+    }
+
+    #[test]
+    fn local_scores_follow_star_ranks() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1, "Fast").with_day(1, 100, 10),
+                MemberFixture::new(2, "Slow").with_day(1, 200, 100),
+                MemberFixture::new(3, "Half").with_part1(1, 50),
+            ],
+        );
+        let scores = leaderboard.daily_stars_and_scores_per_member_for_year(YEAR);
+        let score_of = |id: u64| {
+            scores
+                .iter()
+                .find(|(member, _)| member.numeric == MemberId(id))
+                .map(|(_, days)| days[0])
+                .unwrap()
+        };
+
+        // 3 members: part 1 ranks Half, Fast, Slow; part 2 ranks Fast, Slow.
+        assert_eq!(score_of(1), (2, 2 + 3));
+        assert_eq!(score_of(2), (2, 1 + 2));
+        assert_eq!(score_of(3), (1, 3));
+    }
 
-        // Cloning the leaderboard is expensive, but this operation is only done every 15min
-        self.leaderboard.extend(other.leaderboard.clone());
+    #[test]
+    fn star_index_breaks_ties_of_same_second_stars() {
+        // same timestamps, the first listed member earning the lowest star indexes
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1102, "Dasher").with_day(1, 100, 10),
+                MemberFixture::new(1101, "Dancer").with_day(1, 100, 10),
+            ],
+        );
+        let ranked = leaderboard.ranked_members_per_day_part_for_year(YEAR);
+
+        for part in [ProblemPart::FIRST, ProblemPart::SECOND] {
+            let ids = ranked[&(1, part)]
+                .iter()
+                .map(|id| id.numeric)
+                .collect::<Vec<MemberId>>();
+            assert_eq!(ids, vec![MemberId(1102), MemberId(1101)]);
+        }
+    }
+
+    #[test]
+    fn merging_indexed_entries_replaces_unindexed_ones() {
+        let mut cache = scraped(&edge_case_members());
+        let n_entries = cache.leaderboard.len();
+        let unindexed = cache
+            .leaderboard
+            .drain()
+            .map(|e| Entry {
+                star_index: None,
+                ..e
+            })
+            .collect::<HashSet<Entry>>();
+        cache.leaderboard.extend(unindexed);
+
+        let new_entries = cache.merge_with(scraped(&edge_case_members()));
+
+        assert_eq!(cache.leaderboard.len(), n_entries);
+        assert!(cache.leaderboard.iter().all(|e| e.star_index.is_some()));
+        // the same stars, only indexed, are not new
+        assert!(new_entries.is_empty());
+    }
+
+    #[test]
+    fn deltas_are_only_computed_for_completed_days() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+        let deltas = leaderboard.daily_delta_and_scores_per_member_for_year(YEAR);
+        let rudolph = deltas
+            .iter()
+            .find(|(member, _)| member.numeric == MemberId(4))
+            .map(|(_, days)| days)
+            .unwrap();
+
+        assert_eq!(rudolph[0].0, Duration::seconds(600));
+        // Only first part completed on day 25
+        assert_eq!(rudolph[24], (Duration::seconds(0), 0, 0));
+    }
+
+    #[test]
+    fn missing_stars_lists_unfinished_days() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+
+        // anonymous member: day 1 complete, only part 1 of day 2
+        assert_eq!(
+            leaderboard.missing_stars_for_member(MemberId(2), YEAR, 4),
+            vec![(2, 1), (3, 2), (4, 2)]
+        );
+        assert!(leaderboard
+            .missing_stars_for_member(MemberId(1), YEAR, 25)
+            .is_empty());
+    }
+
+    #[test]
+    fn members_without_stars_are_listed_by_participation() {
+        let members = vec![
+            MemberFixture::new(2501, "Dasher")
+                .with_day(1, 300, 60)
+                .with_day(2, 300, 60),
+            MemberFixture::new(2502, "Dancer")
+                .with_part1(1, 900)
+                .with_part1(3, 900),
+            MemberFixture::new(2503, "Prancer").with_day(1, 600, 60),
+        ];
+        let leaderboard = private_leaderboard(2016, &members);
+
+        let missing = leaderboard
+            .members_without_stars_for_year_day(2016, 3)
+            .into_iter()
+            .map(|(id, n_days)| (id.name.as_str(), n_days))
+            .collect::<Vec<_>>();
+        assert_eq!(missing, vec![("Dasher", 2), ("Prancer", 1)]);
+        assert!(leaderboard
+            .members_without_stars_for_year_day(2016, 1)
+            .is_empty());
+    }
+
+    #[test]
+    fn members_are_found_by_name_or_id() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+
+        let by_name = leaderboard.find_member("rudolph").map(|id| id.numeric);
+        let by_id = leaderboard.find_member("4").map(|id| id.numeric);
+        assert_eq!(by_name, Some(MemberId(4)));
+        assert_eq!(by_id, Some(MemberId(4)));
+        assert!(leaderboard.find_member("Grinch").is_none());
+
+        // members who opted out are only found by id
+        let tinsel = MemberFixture::new(5401, "Tinsel").with_part1(1, 600);
+        let leaderboard = private_leaderboard(YEAR, &[tinsel]);
+        names::register_opt_outs([MemberId(5401)]);
+        assert!(leaderboard.find_member("tinsel").is_none());
+        assert!(leaderboard.find_member("5401").is_some());
+        names::unregister_opt_out(MemberId(5401));
+    }
+
+    #[test]
+    fn stars_are_counted_per_hour_after_the_release() {
+        let members = vec![
+            MemberFixture::new(2801, "Prancer").with_day(3, 600, 3600),
+            MemberFixture::new(2802, "Donner").with_day(3, 1800, 60),
+            // collected the next day, out of the first 24 hours
+            MemberFixture::new(2803, "Dancer").with_day(3, 90000, 60),
+        ];
+        let leaderboard = private_leaderboard(2020, &members);
+        let hours = leaderboard.stars_per_hour_for_year_day(2020, 3).unwrap();
+
+        assert_eq!(hours[0], (2, 1));
+        assert_eq!(hours[1], (0, 1));
+        assert_eq!(hours.iter().map(|(p1, p2)| p1 + p2).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_entries() {
+        let leaderboard = scraped(&edge_case_members());
+        let snapshot = serde_json::to_string(&leaderboard).unwrap();
+        let restored = serde_json::from_str::<ScrapedLeaderboard>(&snapshot).unwrap();
+
+        assert_eq!(restored.timestamp, leaderboard.timestamp);
+        assert_eq!(*restored.leaderboard, *leaderboard.leaderboard);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(RANDOM_BOARDS))]
+
+        #[test]
+        fn random_boards_scores_are_consistent(mut members in random_board(15)) {
+            let year = 2003;
+            let total = |scores: &HashMap<&Identifier, [(u8, usize); 25]>, id: u64| {
+                scores
+                    .iter()
+                    .find(|(member, _)| member.numeric == MemberId(id))
+                    .map_or(0, |(_, days)| days.iter().map(|(_, points)| points).sum())
+            };
+
+            let leaderboard = private_leaderboard(year, &members);
+            let scores = leaderboard.daily_stars_and_scores_per_member_for_year(year);
+            let n_members = members.iter().filter(|m| !m.stars.is_empty()).count();
+            prop_assert_eq!(scores.len(), n_members);
+
+            for (id, days) in scores.iter() {
+                let member = members
+                    .iter()
+                    .find(|m| MemberId(m.id) == id.numeric)
+                    .unwrap();
+                for (day, (stars, points)) in (1..=25).zip(days) {
+                    let n_stars = member.stars.iter().filter(|(d, ..)| *d == day).count();
+                    prop_assert_eq!(*stars as usize, n_stars);
+                    // each star scores between 1 point (last) and the number of members (first)
+                    prop_assert!(n_stars <= *points && *points <= n_stars * n_members);
+                }
+            }
+            // the points of each star are given once per rank
+            for day in 1..=25 {
+                let day_points = scores.values().map(|days| days[day - 1].1).sum::<usize>();
+                let expected = (1..=2)
+                    .map(|part| {
+                        let finishers = members
+                            .iter()
+                            .filter(|m| m.stars.iter().any(|s| (s.0, s.1) == (day as u8, part)))
+                            .count();
+                        (0..finishers).map(|rank| n_members - rank).sum::<usize>()
+                    })
+                    .sum::<usize>();
+                prop_assert_eq!(day_points, expected, "day {}", day);
+            }
+
+            // completing the second part of a day only adds points to the member
+            let single_part = members.iter().enumerate().find_map(|(i, m)| {
+                m.stars.iter().find_map(|(day, _, offset)| {
+                    let n_stars = m.stars.iter().filter(|(d, ..)| d == day).count();
+                    (n_stars == 1).then_some((i, *day, *offset))
+                })
+            });
+            let Some((i, day, offset)) = single_part else {
+                return Ok(());
+            };
+            members[i].stars.push((day, 2, offset + 60));
+            let completed = private_leaderboard(year, &members);
+            let new_scores = completed.daily_stars_and_scores_per_member_for_year(year);
+            for member in members.iter() {
+                let (before, after) = (total(&scores, member.id), total(&new_scores, member.id));
+                match member.id == members[i].id {
+                    true => prop_assert!(after > before),
+                    false => prop_assert!(after <= before),
+                }
+            }
+        }
     }
 }
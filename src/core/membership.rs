@@ -0,0 +1,152 @@
+use crate::core::leaderboard::{Identifier, MemberId};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Maximum number of members of an AoC private leaderboard.
+pub const PRIVATE_LEADERBOARD_MAX_MEMBERS: usize = 200;
+// Spots left before the cap at which the monitoring channel is warned, each level once
+const CAP_WARNING_SPOTS: [usize; 4] = [20, 10, 5, 0];
+// Period over which the evolution of the number of members is reported
+const MEMBERS_TREND_DAYS: i64 = 7;
+
+/// Members of the private leaderboard (with or without stars), as listed by the AoC API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MembershipHistory {
+    pub members: Vec<Identifier>,
+    // (date, number of members) each time the number of members changed
+    pub counts: Vec<(DateTime<Utc>, usize)>,
+    // tightest level of spots left the monitoring channel was warned about, reset once back
+    // above all the levels
+    pub warned_spots: Option<usize>,
+}
+
+/// Changes of the members of the private leaderboard since the previous update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MembershipChange {
+    pub count: usize,
+    // evolution of the number of members over the last days
+    pub trend: Option<i64>,
+    pub joined: Vec<Identifier>,
+    pub left: Vec<Identifier>,
+    // spots left before the cap, if a new warning level was reached
+    pub cap_warning: Option<usize>,
+}
+
+impl MembershipHistory {
+    /// Record the current members, without reporting the changes (e.g. at startup).
+    pub fn track(&mut self, members: Vec<Identifier>, at: DateTime<Utc>) {
+        if self.counts.last().map(|(_at, count)| *count) != Some(members.len()) {
+            self.counts.push((at, members.len()));
+        };
+        self.members = members;
+    }
+
+    /// Record the current members and return the changes worth reporting, if any. Joins and
+    /// leaves are detected by numeric id, nothing being reported for the first members recorded.
+    pub fn update(
+        &mut self,
+        members: Vec<Identifier>,
+        at: DateTime<Utc>,
+    ) -> Option<MembershipChange> {
+        let is_first = self.counts.is_empty();
+        let (joined, left) = match is_first {
+            true => (vec![], vec![]),
+            false => (
+                diff_by_id(&members, &self.members),
+                diff_by_id(&self.members, &members),
+            ),
+        };
+        self.track(members, at);
+
+        let count = self.members.len();
+        let spots = PRIVATE_LEADERBOARD_MAX_MEMBERS.saturating_sub(count);
+        let reached = CAP_WARNING_SPOTS
+            .iter()
+            .filter(|level| spots <= **level)
+            .min()
+            .copied();
+        let cap_warning = match (reached, self.warned_spots) {
+            (Some(level), Some(warned)) if level >= warned => None,
+            (Some(_level), _) => Some(spots),
+            (None, _) => None,
+        };
+        self.warned_spots = reached;
+
+        (!joined.is_empty() || !left.is_empty() || cap_warning.is_some()).then(|| {
+            MembershipChange {
+                count,
+                trend: self
+                    .count_at(at - Duration::days(MEMBERS_TREND_DAYS))
+                    .map(|before| count as i64 - before as i64),
+                joined,
+                left,
+                cap_warning,
+            }
+        })
+    }
+
+    /// Number of members at the given date, if already tracked then.
+    pub fn count_at(&self, at: DateTime<Utc>) -> Option<usize> {
+        self.counts
+            .iter()
+            .take_while(|(changed_at, _count)| *changed_at <= at)
+            .last()
+            .map(|(_changed_at, count)| *count)
+    }
+}
+
+// Members of `members` whose id is not in `others`.
+fn diff_by_id(members: &[Identifier], others: &[Identifier]) -> Vec<Identifier> {
+    let others = others
+        .iter()
+        .map(|m| m.numeric)
+        .collect::<HashSet<MemberId>>();
+    members
+        .iter()
+        .filter(|m| !others.contains(&m.numeric))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(ids: impl Iterator<Item = u64>) -> Vec<Identifier> {
+        ids.map(|id| Identifier {
+            name: format!("Elf #{id}"),
+            numeric: MemberId(id),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn churn_and_cap_warnings_are_reported_once() {
+        let start = Utc::now() - Duration::days(10);
+        let mut history = MembershipHistory::default();
+        assert_eq!(history.update(members(3001..3171), start), None);
+
+        // one member leaves, 15 join: 184 members, 16 spots left
+        let change = history
+            .update(members(3002..3186), start + Duration::days(1))
+            .unwrap();
+        assert_eq!(change.count, 184);
+        assert_eq!(change.left, members(3001..3002));
+        assert_eq!(change.joined.len(), 15);
+        assert_eq!(change.cap_warning, Some(16));
+        assert_eq!(change.trend, None);
+
+        // the same level is not warned again
+        let change = history
+            .update(members(3002..3188), start + Duration::days(2))
+            .unwrap();
+        assert_eq!(change.cap_warning, None);
+        // until the next level is reached
+        let change = history.update(members(3002..3202), Utc::now()).unwrap();
+        assert_eq!((change.count, change.cap_warning), (200, Some(0)));
+        assert_eq!(change.trend, Some(200 - 186));
+        assert_eq!(history.update(members(3002..3202), Utc::now()), None);
+        assert_eq!(history.count_at(start + Duration::hours(36)), Some(184));
+    }
+}
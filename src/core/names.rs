@@ -0,0 +1,181 @@
+use crate::core::{
+    leaderboard::{Identifier, Leaderboard, MemberId},
+    templates::{self, Language},
+};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use slack_morphism::SlackUserId;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::RwLock,
+};
+
+/// Members who opted out of being named in the messages, keyed by numeric id.
+pub type OptOuts = BTreeSet<MemberId>;
+
+// Central registry of the latest known name of each member, keyed by numeric id.
+static REGISTRY: Lazy<RwLock<HashMap<MemberId, String>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// Slack user of each member who is known to the bot, keyed by numeric id.
+static SLACK_USERS: Lazy<RwLock<HashMap<MemberId, SlackUserId>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// Members never named in the messages, registered from the persisted opt-outs.
+static OPTED_OUT: Lazy<RwLock<OptOuts>> = Lazy::new(|| RwLock::new(BTreeSet::new()));
+
+// Members anonymous in the settings, kept apart as they can not opt in again by themselves.
+static ANONYMOUS: Lazy<RwLock<OptOuts>> = Lazy::new(|| RwLock::new(BTreeSet::new()));
+
+/// Register the names of the members of a freshly scraped leaderboard.
+pub fn register(leaderboard: &Leaderboard) {
+    let mut registry = REGISTRY.write().unwrap();
+    leaderboard.iter().for_each(|e| {
+        registry.insert(e.id.numeric, e.id.name.clone());
+    });
+}
+
+/// Name to display for a member. Falls back to the name the identifier was scraped with if the
+/// member is not (yet) known, members who opted out being anonymous.
+pub fn resolve(id: &Identifier) -> String {
+    if is_opted_out(id.numeric) {
+        return anonymous_name().to_string();
+    };
+    let registry = REGISTRY.read().unwrap();
+    registry
+        .get(&id.numeric)
+        .map_or_else(|| id.name.clone(), |name| name.clone())
+}
+
+/// Add members to the ones never named in the messages.
+pub fn register_opt_outs(members: impl IntoIterator<Item = MemberId>) {
+    OPTED_OUT.write().unwrap().extend(members);
+}
+
+/// Name the member in the messages again, unless they are anonymous in the settings.
+pub fn unregister_opt_out(member: MemberId) {
+    OPTED_OUT.write().unwrap().remove(&member);
+}
+
+/// Add members to the ones anonymous in the settings.
+pub fn register_anonymous_members(members: impl IntoIterator<Item = MemberId>) {
+    ANONYMOUS.write().unwrap().extend(members);
+}
+
+pub fn is_anonymous(member: MemberId) -> bool {
+    ANONYMOUS.read().unwrap().contains(&member)
+}
+
+pub fn is_opted_out(member: MemberId) -> bool {
+    is_anonymous(member) || OPTED_OUT.read().unwrap().contains(&member)
+}
+
+// Name of the members who opted out, in the language of the messages.
+fn anonymous_name() -> &'static str {
+    match templates::language() {
+        Language::En => "anonymous elf",
+        Language::Fr => "lutin anonyme",
+        Language::De => "anonymer Elf",
+    }
+}
+
+/// Link members to their Slack users, replacing any previous link of the members and of the
+/// users, so a Slack user is linked to a single member.
+pub fn register_slack_users(users: HashMap<MemberId, SlackUserId>) {
+    let mut slack_users = SLACK_USERS.write().unwrap();
+    slack_users.retain(|_member, linked| !users.values().contains(linked));
+    slack_users.extend(users);
+}
+
+/// Members linked to a Slack user.
+pub fn slack_users() -> HashMap<MemberId, SlackUserId> {
+    SLACK_USERS.read().unwrap().clone()
+}
+
+/// Name of the member linked to the Slack user, to name them where Slack users can not be
+/// mentioned.
+pub fn linked_name(user: &SlackUserId) -> Option<String> {
+    let member = SLACK_USERS
+        .read()
+        .unwrap()
+        .iter()
+        .find_map(|(member, linked)| (linked == user).then_some(*member))?;
+    let name = REGISTRY.read().unwrap().get(&member).cloned()?;
+    Some(resolve(&Identifier {
+        name,
+        numeric: member,
+    }))
+}
+
+/// Name to use in announcements: a mention of the Slack user if the member is linked to one (and
+/// did not opt out), the member name otherwise.
+pub fn mention(id: &Identifier) -> String {
+    let slack_users = SLACK_USERS.read().unwrap();
+    slack_users
+        .get(&id.numeric)
+        .filter(|_| !is_opted_out(id.numeric))
+        .map_or_else(|| resolve(id), |user| format!("<@{}>", user.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_members_are_mentioned() {
+        let linked = Identifier {
+            name: "Dasher".to_string(),
+            numeric: MemberId(3001),
+        };
+        let unlinked = Identifier {
+            name: "Dancer".to_string(),
+            numeric: MemberId(3002),
+        };
+        register_slack_users(HashMap::from([(
+            MemberId(3001),
+            SlackUserId("U0DASHER".to_string()),
+        )]));
+
+        assert_eq!(mention(&linked), "<@U0DASHER>");
+        assert_eq!(mention(&unlinked), "Dancer");
+    }
+
+    #[test]
+    fn users_are_linked_to_a_single_member() {
+        let user = SlackUserId("U0PRANCER".to_string());
+        register_slack_users(HashMap::from([(MemberId(3005), user.clone())]));
+        register_slack_users(HashMap::from([(MemberId(3006), user.clone())]));
+
+        let linked = slack_users()
+            .into_iter()
+            .filter(|(_member, linked)| *linked == user)
+            .collect::<Vec<_>>();
+        assert_eq!(linked, vec![(MemberId(3006), user)]);
+    }
+
+    #[test]
+    fn opted_out_members_are_anonymous() {
+        let member = Identifier {
+            name: "Comet".to_string(),
+            numeric: MemberId(3003),
+        };
+        register_slack_users(HashMap::from([(
+            MemberId(3003),
+            SlackUserId("U0COMET".to_string()),
+        )]));
+
+        register_opt_outs([MemberId(3003)]);
+        assert_eq!(resolve(&member), "anonymous elf");
+        assert_eq!(mention(&member), "anonymous elf");
+
+        unregister_opt_out(MemberId(3003));
+        assert_eq!(resolve(&member), "Comet");
+        assert_eq!(mention(&member), "<@U0COMET>");
+
+        // the members anonymous in the settings stay anonymous
+        register_anonymous_members([MemberId(3004)]);
+        register_opt_outs([MemberId(3004)]);
+        unregister_opt_out(MemberId(3004));
+        assert!(is_opted_out(MemberId(3004)));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::core::{
+    difficulty::{day_difficulty, DayDifficulty, GlobalCompletion},
+    leaderboard::{Leaderboard, MemberId},
+};
+use itertools::Itertools;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// Past puzzle suggested to a member for practice.
+#[derive(Debug, Clone)]
+pub struct PracticePuzzle {
+    pub year: i32,
+    pub day: u8,
+    // stars of the puzzle the member still has to collect
+    pub missing_stars: usize,
+    // difficulty of the day on the global leaderboard, if it was recorded
+    pub difficulty: Option<DayDifficulty>,
+}
+
+/// (year, day, missing stars) of the puzzles of the given years not completed by the member, up
+/// to the day before the current day for the ongoing event.
+pub fn unsolved_puzzles(
+    leaderboard: &Leaderboard,
+    member: MemberId,
+    years: &[i32],
+    (current_year, current_day): (i32, u8),
+) -> Vec<(i32, u8, usize)> {
+    years
+        .iter()
+        .sorted()
+        .dedup()
+        .flat_map(|year| {
+            let last_day = match *year == current_year {
+                true => current_day.saturating_sub(1),
+                false => 25,
+            };
+            leaderboard
+                .missing_stars_for_member(member, *year, last_day)
+                .into_iter()
+                // the last star of day 25 is only missing because of the stars of the other days
+                .filter(|(day, missing)| !(*day == 25 && *missing == 1))
+                .map(move |(day, missing)| (*year, day, missing))
+        })
+        .collect()
+}
+
+/// Puzzle picked at random among the ones not completed by the member, None if there is none.
+pub fn suggest_puzzle(
+    leaderboard: &Leaderboard,
+    member: MemberId,
+    years: &[i32],
+    today: (i32, u8),
+    completions: &[GlobalCompletion],
+) -> Option<PracticePuzzle> {
+    let puzzles = unsolved_puzzles(leaderboard, member, years, today);
+    if puzzles.is_empty() {
+        return None;
+    };
+    // randomly seeded by the standard library, good enough to vary the suggestions
+    let index = RandomState::new().build_hasher().finish() as usize % puzzles.len();
+    let (year, day, missing_stars) = puzzles[index];
+
+    // difficulty compared to all the other recorded days
+    let difficulty = completions
+        .iter()
+        .find(|c| c.year == year && c.day == day)
+        .and_then(|completion| {
+            let others = completions
+                .iter()
+                .filter(|c| !(c.year == year && c.day == day))
+                .cloned()
+                .collect::<Vec<GlobalCompletion>>();
+            day_difficulty(completion.p2_slow_sec, &others)
+        });
+
+    Some(PracticePuzzle {
+        year,
+        day,
+        missing_stars,
+        difficulty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn only_unsolved_past_puzzles_are_suggested() {
+        let mut leaderboard = private_leaderboard(
+            2021,
+            &[
+                (1..=25).fold(MemberFixture::new(3501, "Blitzen"), |m, day| {
+                    m.with_day(day, 600, 60)
+                }),
+            ],
+        );
+        let ongoing = (1..=3)
+            .fold(MemberFixture::new(3501, "Blitzen"), |m, day| {
+                m.with_day(day, 600, 60)
+            })
+            .with_part1(4, 900);
+        leaderboard.extend(private_leaderboard(2022, &[ongoing]).iter().cloned());
+
+        let today = (2022, 6);
+        // days 4 (second part) and 5 of the ongoing event, the 6th being today
+        assert_eq!(
+            unsolved_puzzles(&leaderboard, MemberId(3501), &[2022, 2021], today),
+            vec![(2022, 4, 1), (2022, 5, 2)]
+        );
+        assert!(suggest_puzzle(&leaderboard, MemberId(3501), &[2021], today, &[]).is_none());
+
+        let completions = (1..=12)
+            .map(|day| GlobalCompletion {
+                year: 2022,
+                day,
+                p2_slow_sec: day as i64 * 600,
+            })
+            .collect::<Vec<GlobalCompletion>>();
+        let puzzle = suggest_puzzle(
+            &leaderboard,
+            MemberId(3501),
+            &[2022],
+            (2022, 5),
+            &completions,
+        )
+        .unwrap();
+        assert_eq!(
+            (puzzle.year, puzzle.day, puzzle.missing_stars),
+            (2022, 4, 1)
+        );
+        assert!(puzzle.difficulty.is_some_and(|d| d.n_days == 11));
+    }
+}
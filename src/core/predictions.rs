@@ -0,0 +1,182 @@
+use crate::core::{difficulty::GlobalCompletion, leaderboard::Entry};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use slack_morphism::SlackUserId;
+
+static GUESS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{1,3}):([0-5]\d)$").unwrap());
+
+/// Guess of a user of the time taken by the last (100th) part 2 finisher of the global
+/// leaderboard of a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: i32,
+    pub day: u8,
+    pub user: SlackUserId,
+    pub guess_sec: i64,
+}
+
+/// Prediction game results of a user over an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predictor {
+    pub user: SlackUserId,
+    // number of days the user made the closest guess
+    pub wins: usize,
+    pub n_guesses: usize,
+    pub average_error_sec: i64,
+}
+
+/// Parse a `mm:ss` guess, in seconds.
+pub fn parse_guess(input: &str) -> Option<i64> {
+    let captures = GUESS_REGEX.captures(input.trim())?;
+    let minutes = captures[1].parse::<i64>().ok()?;
+    let seconds = captures[2].parse::<i64>().ok()?;
+    Some(minutes * 60 + seconds)
+}
+
+/// Day of the next puzzle to unlock in the event of the year, the guesses being closed once the
+/// puzzle is unlocked.
+pub fn guessed_day(year: i32, now: DateTime<Utc>) -> Option<u8> {
+    (1..=25).find(|day| Entry::puzzle_unlock(year, *day).is_ok_and(|unlock| unlock > now))
+}
+
+/// Record the guess, replacing the previous guess of the user for the same day.
+pub fn record_guess(guesses: &mut Vec<Guess>, guess: Guess) {
+    guesses.retain(|g| !(g.year == guess.year && g.day == guess.day && g.user == guess.user));
+    guesses.push(guess);
+}
+
+/// Closest guesses of the day to the actual completion time, several in case of a tie.
+pub fn closest_guesses(guesses: &[Guess], year: i32, day: u8, p2_slow_sec: i64) -> Vec<Guess> {
+    guesses
+        .iter()
+        .filter(|g| g.year == year && g.day == day)
+        .min_set_by_key(|g| (g.guess_sec - p2_slow_sec).abs())
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+/// Season-long standings of the prediction game, ranked on the number of days won and then on the
+/// average error. Only the days with a complete global leaderboard are counted.
+pub fn season_standings(
+    guesses: &[Guess],
+    completions: &[GlobalCompletion],
+    year: i32,
+) -> Vec<Predictor> {
+    let results = completions
+        .iter()
+        .filter(|c| c.year == year)
+        .flat_map(|c| {
+            let winners = closest_guesses(guesses, year, c.day, c.p2_slow_sec)
+                .into_iter()
+                .map(|g| g.user)
+                .collect::<Vec<SlackUserId>>();
+            guesses
+                .iter()
+                .filter(move |g| g.year == year && g.day == c.day)
+                .map(move |g| {
+                    (
+                        g.user.clone(),
+                        winners.contains(&g.user),
+                        (g.guess_sec - c.p2_slow_sec).abs(),
+                    )
+                })
+        })
+        .into_group_map_by(|(user, _won, _error)| user.clone());
+
+    results
+        .into_iter()
+        .map(|(user, days)| Predictor {
+            user,
+            wins: days.iter().filter(|(_user, won, _error)| *won).count(),
+            n_guesses: days.len(),
+            average_error_sec: days.iter().map(|(_user, _won, error)| error).sum::<i64>()
+                / days.len() as i64,
+        })
+        .sorted_by_key(|p| {
+            (
+                std::cmp::Reverse(p.wins),
+                p.average_error_sec,
+                p.user.0.clone(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn guess(day: u8, user: &str, guess: &str) -> Guess {
+        Guess {
+            year: 2021,
+            day,
+            user: SlackUserId(user.to_string()),
+            guess_sec: parse_guess(guess).unwrap(),
+        }
+    }
+
+    #[test]
+    fn guesses_are_made_on_the_next_puzzle_to_unlock() {
+        let at = |month, day, hour| Utc.with_ymd_and_hms(2021, month, day, hour, 0, 0).unwrap();
+        assert_eq!(guessed_day(2021, at(11, 20, 12)), Some(1));
+        assert_eq!(guessed_day(2021, at(12, 7, 4)), Some(7));
+        assert_eq!(guessed_day(2021, at(12, 7, 5)), Some(8));
+        assert_eq!(guessed_day(2021, at(12, 25, 5)), None);
+    }
+
+    #[test]
+    fn closest_guesses_win_the_prediction_game() {
+        assert_eq!(parse_guess("12:34"), Some(754));
+        assert_eq!(parse_guess("125:00"), Some(7500));
+        assert_eq!(parse_guess("12:60"), None);
+        assert_eq!(parse_guess("soon"), None);
+
+        let mut guesses = vec![];
+        [
+            guess(1, "U0COMET", "10:00"),
+            guess(1, "U0CUPID", "15:00"),
+            guess(1, "U0COMET", "14:00"),
+            guess(2, "U0COMET", "30:00"),
+            guess(2, "U0CUPID", "20:00"),
+            guess(2, "U0DONNER", "30:00"),
+            guess(3, "U0CUPID", "42:00"),
+        ]
+        .into_iter()
+        .for_each(|g| record_guess(&mut guesses, g));
+        assert_eq!(guesses.len(), 6);
+
+        let completions = [(1, 14 * 60 + 20), (2, 26 * 60)]
+            .into_iter()
+            .map(|(day, p2_slow_sec)| GlobalCompletion {
+                year: 2021,
+                day,
+                p2_slow_sec,
+            })
+            .collect::<Vec<_>>();
+
+        let winners = closest_guesses(&guesses, 2021, 2, 26 * 60)
+            .into_iter()
+            .map(|g| g.user.0)
+            .collect::<Vec<_>>();
+        assert_eq!(winners, vec!["U0COMET", "U0DONNER"]);
+
+        // the guess of the day 3 is not counted until its global leaderboard is complete
+        let standings = season_standings(&guesses, &completions, 2021)
+            .into_iter()
+            .map(|p| (p.user.0, p.wins, p.n_guesses, p.average_error_sec))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            standings,
+            vec![
+                ("U0COMET".to_string(), 2, 2, 130),
+                ("U0DONNER".to_string(), 1, 1, 240),
+                ("U0CUPID".to_string(), 0, 2, 200),
+            ]
+        );
+    }
+}
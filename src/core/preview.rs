@@ -0,0 +1,261 @@
+use crate::core::templates::MessageTemplate;
+use minijinja::{
+    value::{Enumerator, Object, ObjectRepr, Value as TemplateValue},
+    ErrorKind,
+};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+};
+use strum::IntoEnumIterator;
+
+// Number of items of the sample lists iterated by the templates
+const SAMPLE_ITEMS: usize = 2;
+// Number of elements of the sample tuples unpacked by the templates, at most
+const SAMPLE_MAX_ITEMS: usize = 8;
+// Nesting of the sample values, deeper attributes being rendered as placeholders
+const SAMPLE_MAX_DEPTH: usize = 5;
+// Sample value of the numbers, greater than 1 for the plurals to be rendered
+const SAMPLE_NUMBER: u64 = 2;
+// Globals of the templating engine, not part of the context
+const GLOBALS: [&str; 5] = ["theme", "command_prefix", "jersey_points", "loop", "range"];
+
+/// Template to render with sample data for an admin, posted in the monitoring channel.
+#[derive(Debug, Clone)]
+pub struct TemplatePreview {
+    pub template: MessageTemplate,
+    // sample values given in the command, taking precedence over the generated ones
+    sample: Map<String, Value>,
+    // rendered message, or the rendering error, once rendered
+    pub rendered: Option<Result<String, String>>,
+}
+
+// Shapes of the sample values learnt from the errors of the previous renderings of the template.
+#[derive(Debug, Default)]
+struct Shapes {
+    // variables used as numbers (e.g. `day - 1`)
+    numbers: Mutex<HashSet<String>>,
+    // number of items of the lists unpacked in tuples, by shape of the list
+    lengths: Mutex<HashMap<String, usize>>,
+    // shape of the list enumerated last, the one failing to be unpacked if its length differs
+    enumerated: Mutex<Option<String>>,
+}
+
+// Sample value of a variable of the template, taking the shape the template expects: it is
+// rendered as a `<path>` placeholder, has sample attributes and can be iterated.
+#[derive(Debug)]
+struct Sample {
+    // e.g. `ranking.0.name`
+    path: String,
+    // path without the positions of the items, e.g. `ranking[].name`
+    shape: String,
+    depth: usize,
+    shapes: Arc<Shapes>,
+}
+
+impl Sample {
+    fn value(path: String, shape: String, depth: usize, shapes: &Arc<Shapes>) -> TemplateValue {
+        let is_number = shapes.numbers.lock().unwrap().contains(&shape);
+        match (is_number, depth > SAMPLE_MAX_DEPTH) {
+            (true, _) => TemplateValue::from(SAMPLE_NUMBER),
+            (false, true) => TemplateValue::from(format!("<{path}>")),
+            (false, false) => TemplateValue::from_object(Sample {
+                path,
+                shape,
+                depth,
+                shapes: shapes.clone(),
+            }),
+        }
+    }
+
+    fn item(&self, position: usize) -> TemplateValue {
+        Sample::value(
+            format!("{}.{position}", self.path),
+            format!("{}[]", self.shape),
+            self.depth + 1,
+            &self.shapes,
+        )
+    }
+}
+
+impl Object for Sample {
+    fn repr(self: &Arc<Self>) -> ObjectRepr {
+        ObjectRepr::Seq
+    }
+
+    fn get_value(self: &Arc<Self>, key: &TemplateValue) -> Option<TemplateValue> {
+        match (key.as_str(), key.as_usize()) {
+            (Some(attribute), _) => Some(Sample::value(
+                format!("{}.{attribute}", self.path),
+                format!("{}.{attribute}", self.shape),
+                self.depth + 1,
+                &self.shapes,
+            )),
+            (None, Some(position)) => Some(self.item(position)),
+            (None, None) => None,
+        }
+    }
+
+    fn enumerate(self: &Arc<Self>) -> Enumerator {
+        *self.shapes.enumerated.lock().unwrap() = Some(self.shape.clone());
+        let length = self
+            .shapes
+            .lengths
+            .lock()
+            .unwrap()
+            .get(&self.shape)
+            .copied();
+        Enumerator::Values(
+            (0..length.unwrap_or(SAMPLE_ITEMS))
+                .map(|position| self.item(position))
+                .collect(),
+        )
+    }
+
+    fn render(self: &Arc<Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.path)
+    }
+}
+
+impl TemplatePreview {
+    pub fn new(template: MessageTemplate, sample: Map<String, Value>) -> TemplatePreview {
+        TemplatePreview {
+            template,
+            sample,
+            rendered: None,
+        }
+    }
+
+    /// Render the template (with its override, if any) with sample values for all its variables
+    /// (found by the templating engine), the given sample values taking precedence. The shapes
+    /// of the sample values are adjusted to the rendering errors, until the template renders.
+    pub fn render(self) -> TemplatePreview {
+        let loaded = self.template.get();
+        let rendered = loaded
+            .source_and_variables()
+            .map_err(|e| e.to_string())
+            .and_then(|(source, variables)| {
+                let names = variables
+                    .iter()
+                    .filter_map(|v| v.split('.').next())
+                    .filter(|v| !GLOBALS.contains(v))
+                    .collect::<HashSet<&str>>();
+                let shapes = Arc::new(Shapes::default());
+                loop {
+                    let mut context = names
+                        .iter()
+                        .map(|name| {
+                            let sample =
+                                Sample::value(name.to_string(), name.to_string(), 0, &shapes);
+                            (name.to_string(), sample)
+                        })
+                        .collect::<BTreeMap<String, TemplateValue>>();
+                    context.extend(
+                        self.sample
+                            .iter()
+                            .map(|(k, v)| (k.clone(), TemplateValue::from_serialize(v))),
+                    );
+                    let error = match loaded.render(context) {
+                        Ok(rendered) => return Ok(rendered),
+                        Err(e) => e,
+                    };
+                    if !shapes.adjust(&error, &source, &variables) {
+                        return Err(error.to_string());
+                    };
+                }
+            });
+        TemplatePreview {
+            rendered: Some(rendered),
+            ..self
+        }
+    }
+}
+
+impl Shapes {
+    // Adjust the shapes to the rendering error, if it can be fixed.
+    fn adjust(&self, error: &minijinja::Error, source: &str, variables: &HashSet<String>) -> bool {
+        if error.kind() == ErrorKind::CannotUnpack {
+            let enumerated = self.enumerated.lock().unwrap().take();
+            if let Some(shape) = enumerated {
+                let mut lengths = self.lengths.lock().unwrap();
+                let length = lengths.entry(shape).or_insert(SAMPLE_ITEMS);
+                *length += 1;
+                return *length <= SAMPLE_MAX_ITEMS;
+            };
+        };
+        // the variables of the failing expression are used as numbers
+        let failing = error
+            .range()
+            .and_then(|range| source.get(range))
+            .unwrap_or_default();
+        let numbers = failing
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .filter(|token| {
+                variables.contains(*token)
+                    || variables
+                        .iter()
+                        .any(|v| v.starts_with(&format!("{token}.")))
+            })
+            .map(|token| token.to_string())
+            .collect::<Vec<String>>();
+        let mut known = self.numbers.lock().unwrap();
+        let n_known = known.len();
+        known.extend(numbers);
+        known.len() > n_known
+    }
+}
+
+/// Template matching a name, with or without its extension (e.g. `summary` or `summary.txt`).
+pub fn find_template(name: &str) -> Option<MessageTemplate> {
+    let name = name.trim().to_lowercase();
+    MessageTemplate::iter()
+        .find(|t| t.name() == name || t.name().strip_suffix(".txt") == Some(name.as_str()))
+}
+
+/// Sample values written in the arguments of the command, Slack quotes being replaced.
+pub fn parse_sample(argument: &str) -> Result<Map<String, Value>, String> {
+    if argument.trim().is_empty() {
+        return Ok(Map::new());
+    };
+    let argument = argument.replace(['“', '”'], "\"");
+    serde_json::from_str::<Map<String, Value>>(&argument)
+        .map_err(|e| format!("The sample values are not a JSON object ({e})."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_template_renders_with_sample_values() {
+        for template in MessageTemplate::iter() {
+            let preview = TemplatePreview::new(template, Map::new()).render();
+            assert!(
+                matches!(preview.rendered, Some(Ok(_))),
+                "{} {:?}",
+                preview.template.name(),
+                preview.rendered
+            );
+        }
+    }
+
+    #[test]
+    fn sample_values_follow_the_template_variables() {
+        let template = find_template("summary").unwrap();
+        let sample = parse_sample(r#"{“year”: 2006}"#).unwrap();
+        let rendered = TemplatePreview::new(template, sample)
+            .render()
+            .rendered
+            .unwrap()
+            .unwrap();
+        assert!(rendered.contains("2006"));
+        // names of the members unpacked from the rankings
+        assert!(rendered.contains("*<ranking_p1.0.1>*"));
+
+        assert!(find_template("summary.txt").is_some());
+        assert!(find_template("sumary").is_none());
+        assert!(parse_sample("year=2006").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    core::{
+        leaderboard::{Entry, Identifier, Leaderboard, ProblemPart},
+        names,
+    },
+    utils::format_duration,
+};
+use chrono::Duration;
+use itertools::Itertools;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+// Number of stars listed by the `query` command, by default and at most
+const QUERY_DEFAULT_LIMIT: usize = 10;
+const QUERY_MAX_LIMIT: usize = 50;
+const QUERY_KEYS: [&str; 6] = ["year", "day", "part", "member", "sort", "limit"];
+
+/// Order of the stars listed by a query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuerySort {
+    // time to complete the part, since the puzzle unlock
+    Time,
+    // completion date
+    Date,
+    Name,
+}
+
+/// Slice of the stars of the private leaderboard, written as `key=value` filters
+/// (e.g. `day=7 part=2 sort=time limit=10`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub year: i32,
+    pub day: Option<u8>,
+    pub part: Option<ProblemPart>,
+    pub member: Option<Identifier>,
+    pub sort: QuerySort,
+    pub descending: bool,
+    pub limit: usize,
+}
+
+/// Star matching a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryRow {
+    pub name: String,
+    pub day: u8,
+    pub part: u8,
+    pub time: String,
+}
+
+/// Stars matching a query, the first ones only being listed.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResult {
+    // query as written by the member
+    pub query: String,
+    pub year: i32,
+    pub n_matches: usize,
+    pub rows: Vec<QueryRow>,
+}
+
+impl Query {
+    /// Query written in the arguments of the command, the year defaulting to the given one.
+    /// Members (name or numeric id, names with spaces only by id) are looked up in the leaderboard.
+    pub fn parse(argument: &str, leaderboard: &Leaderboard, year: i32) -> Result<Query, String> {
+        let mut query = Query {
+            year,
+            day: None,
+            part: None,
+            member: None,
+            sort: QuerySort::Time,
+            descending: false,
+            limit: QUERY_DEFAULT_LIMIT,
+        };
+        for filter in argument.split_whitespace() {
+            let Some((key, value)) = filter.split_once('=') else {
+                return Err(format!("`{filter}` is not a `key=value` filter."));
+            };
+            let invalid = || format!("`{value}` is not a valid value for `{key}`.");
+            match key.to_lowercase().as_str() {
+                "year" => query.year = value.parse::<i32>().map_err(|_| invalid())?,
+                "day" => {
+                    query.day = Some(
+                        value
+                            .parse::<u8>()
+                            .ok()
+                            .filter(|day| (1..=25).contains(day))
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                "part" => {
+                    query.part = Some(match value {
+                        "1" => ProblemPart::FIRST,
+                        "2" => ProblemPart::SECOND,
+                        _ => return Err(invalid()),
+                    })
+                }
+                "member" => {
+                    query.member = Some(leaderboard.find_member(value).ok_or_else(|| {
+                        format!(
+                            "I could not find any member named *{value}* on the leaderboard 🔍."
+                        )
+                    })?)
+                }
+                "sort" => {
+                    let (descending, sort) = match value.strip_prefix('-') {
+                        Some(sort) => (true, sort),
+                        None => (false, value),
+                    };
+                    query.descending = descending;
+                    query.sort = match sort {
+                        "time" => QuerySort::Time,
+                        "date" => QuerySort::Date,
+                        "name" => QuerySort::Name,
+                        _ => return Err(invalid()),
+                    };
+                }
+                "limit" => {
+                    query.limit = value
+                        .parse::<usize>()
+                        .map_err(|_| invalid())?
+                        .clamp(1, QUERY_MAX_LIMIT)
+                }
+                _ => {
+                    return Err(format!(
+                        "`{key}` is not a known filter (use {}).",
+                        QUERY_KEYS.iter().map(|key| format!("`{key}`")).join(", ")
+                    ))
+                }
+            };
+        }
+        Ok(query)
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        entry.year == self.year
+            && self.day.is_none_or(|day| entry.day == day)
+            && self.part.is_none_or(|part| entry.part == part)
+            && self
+                .member
+                .as_ref()
+                .is_none_or(|member| entry.id.numeric == member.numeric)
+    }
+
+    fn compare(&self, a: &(&Entry, Duration), b: &(&Entry, Duration)) -> Ordering {
+        let ordering = match self.sort {
+            QuerySort::Time => a.1.cmp(&b.1),
+            QuerySort::Date => a.0.cmp(b.0),
+            QuerySort::Name => names::resolve(&a.0.id)
+                .to_lowercase()
+                .cmp(&names::resolve(&b.0.id).to_lowercase()),
+        };
+        // ties broken by completion date
+        let ordering = ordering.then_with(|| a.0.cmp(b.0));
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+
+    /// Stars of the leaderboard matching the query, sorted and limited.
+    pub fn run(&self, leaderboard: &Leaderboard, query: &str) -> QueryResult {
+        let matches = leaderboard
+            .iter()
+            .filter(|e| self.matches(e))
+            .filter_map(|e| Some((e, e.duration_since_release().ok()?)))
+            .sorted_by(|a, b| self.compare(a, b))
+            .collect::<Vec<(&Entry, Duration)>>();
+        QueryResult {
+            query: query.to_string(),
+            year: self.year,
+            n_matches: matches.len(),
+            rows: matches
+                .iter()
+                .take(self.limit)
+                .map(|(e, time)| QueryRow {
+                    name: names::resolve(&e.id),
+                    day: e.day,
+                    part: match e.part {
+                        ProblemPart::FIRST => 1,
+                        ProblemPart::SECOND => 2,
+                    },
+                    time: format_duration(*time),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn stars_are_filtered_sorted_and_limited() {
+        let year = 2007;
+        let leaderboard = private_leaderboard(
+            year,
+            &[
+                MemberFixture::new(5401, "Pepper Minstix")
+                    .with_day(7, 900, 300)
+                    .with_day(8, 600, 60),
+                MemberFixture::new(5402, "Bushy").with_day(7, 600, 120),
+                MemberFixture::new(5403, "Shinny").with_part1(7, 300),
+            ],
+        );
+
+        let query = Query::parse("day=7 part=2 sort=time limit=10", &leaderboard, year).unwrap();
+        let result = query.run(&leaderboard, "day=7 part=2 sort=time limit=10");
+        assert_eq!(result.n_matches, 2);
+        assert_eq!(
+            result
+                .rows
+                .iter()
+                .map(|r| (r.name.as_str(), r.time.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("Bushy", "00:12:00"), ("Pepper Minstix", "00:20:00")]
+        );
+
+        let query = Query::parse("member=5401 sort=-time limit=1", &leaderboard, year).unwrap();
+        let result = query.run(&leaderboard, "");
+        assert_eq!(result.n_matches, 4);
+        assert_eq!((result.rows[0].day, result.rows[0].part), (7, 2));
+    }
+
+    #[test]
+    fn invalid_filters_are_reported() {
+        let leaderboard = private_leaderboard(2007, &[]);
+        let error = |argument: &str| Query::parse(argument, &leaderboard, 2007).unwrap_err();
+
+        assert!(error("day=26").contains("`day`"));
+        assert!(error("part=3").contains("`part`"));
+        assert!(error("sort=speed").contains("`sort`"));
+        assert!(error("color=red").contains("not a known filter"));
+        assert!(error("day").contains("key=value"));
+        assert!(error("member=Rudolph").contains("Rudolph"));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::core::{
+    leaderboard::{Identifier, Leaderboard},
+    names,
+    standings::{standings_board, Jersey, Scoring, Standing},
+};
+use serde::Serialize;
+
+/// Position of a member in the standings of an event, with the gaps to the neighbours.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberRank {
+    pub name: String,
+    pub year: i32,
+    // position in the local score standings, among the members with stars
+    pub position: usize,
+    pub n_members: usize,
+    pub score: usize,
+    pub n_stars: usize,
+    // (name, points to catch up) of the member right ahead
+    pub ahead: Option<(String, usize)>,
+    // (name, points of margin) over the member right behind
+    pub behind: Option<(String, usize)>,
+    // (jersey, position), None if not classified
+    pub jerseys: Vec<(String, Option<usize>)>,
+}
+
+/// Standings of the member for the year, None if the member has no star for the year.
+pub fn member_rank(
+    leaderboard: &Leaderboard,
+    member: &Identifier,
+    year: i32,
+) -> Option<MemberRank> {
+    let stars = standings_board(&Scoring::STARS, leaderboard, year);
+    let standings = standings_board(&Scoring::LOCAL, leaderboard, year);
+    let position = standings
+        .iter()
+        .position(|(id, _days, _score)| id.numeric == member.numeric)?;
+    let score = standings[position].2;
+    let neighbour = |pos: Option<usize>| {
+        pos.and_then(|pos| standings.get(pos))
+            .map(|(id, _days, other)| (names::resolve(id), score.abs_diff(*other)))
+    };
+
+    let standing = Standing::new(leaderboard);
+    let jerseys = [Jersey::YELLOW, Jersey::GREEN, Jersey::COMBATIVE]
+        .iter()
+        .map(|jersey| {
+            let position = standing
+                .tdf_season(jersey, year)
+                .iter()
+                .position(|(id, _value, _n_days)| id.numeric == member.numeric)
+                .map(|pos| pos + 1);
+            (jersey.to_string(), position)
+        })
+        .collect();
+
+    Some(MemberRank {
+        name: names::resolve(member),
+        year,
+        position: position + 1,
+        n_members: standings.len(),
+        score,
+        n_stars: stars
+            .iter()
+            .find(|(id, _days, _n_stars)| id.numeric == member.numeric)
+            .map_or(0, |(_id, _days, n_stars)| *n_stars),
+        ahead: neighbour(position.checked_sub(1)),
+        behind: neighbour(Some(position + 1)),
+        jerseys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn member_is_ranked_with_the_gaps_to_the_neighbours() {
+        let year = 2018;
+        let members = vec![
+            MemberFixture::new(5101, "Cinnamon")
+                .with_day(1, 300, 60)
+                .with_day(2, 300, 60),
+            MemberFixture::new(5102, "Nutmeg").with_day(1, 600, 60),
+            MemberFixture::new(5103, "Clove").with_part1(1, 900),
+        ];
+        let leaderboard = private_leaderboard(year, &members);
+
+        let nutmeg = leaderboard.find_member("Nutmeg").unwrap();
+        let rank = member_rank(&leaderboard, &nutmeg, year).unwrap();
+        assert_eq!((rank.position, rank.n_members), (2, 3));
+        // second on both parts of day 1, with 3 members
+        assert_eq!((rank.score, rank.n_stars), (4, 2));
+        // 12 points for the fastest member of both days
+        assert_eq!(rank.ahead, Some(("Cinnamon".to_string(), 8)));
+        assert_eq!(rank.behind, Some(("Clove".to_string(), 3)));
+        assert!(rank.jerseys.contains(&("yellow".to_string(), Some(2))));
+
+        let cinnamon = leaderboard.find_member("Cinnamon").unwrap();
+        assert!(member_rank(&leaderboard, &cinnamon, year)
+            .unwrap()
+            .ahead
+            .is_none());
+        assert!(member_rank(&leaderboard, &cinnamon, 2017).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    core::{
+        leaderboard::{Entry, Identifier, Leaderboard, ProblemPart},
+        names,
+        standings::{overall_positions, Jersey, Scoring, Standing},
+    },
+    utils::format_duration,
+};
+use chrono::{DateTime, Duration, Utc};
+use itertools::Itertools;
+use serde::Serialize;
+use std::{cmp::Reverse, collections::HashMap};
+
+// Number of climbs in the overall standings reported in the recap
+const RECAP_CLIMBS_COUNT: usize = 3;
+
+/// What happened on the leaderboard over a week of the event.
+#[derive(Debug, Serialize)]
+pub struct WeeklyRecap {
+    pub year: i32,
+    // (name, stars collected during the week), most stars first
+    pub stars: Vec<(String, usize)>,
+    // (name, position at the start of the week, position at the end of the week)
+    pub climbs: Vec<(String, usize, usize)>,
+    // (jersey, holder at the start of the week, holder at the end of the week)
+    pub jerseys: Vec<(String, Option<String>, String)>,
+    // (name, day, delta time), among the stars collected during the week
+    pub fastest_delta: Option<(String, u8, String)>,
+}
+
+/// Recap of the stars collected in [start, end), None if no star was collected.
+pub fn weekly_recap(
+    leaderboard: &Leaderboard,
+    year: i32,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Option<WeeklyRecap> {
+    let snapshot = |until: DateTime<Utc>| {
+        let mut snapshot = Leaderboard::new();
+        snapshot.extend(
+            leaderboard
+                .iter()
+                .filter(|e| e.year == year && e.timestamp < until)
+                .cloned(),
+        );
+        snapshot
+    };
+    let (before, after) = (snapshot(start), snapshot(end));
+    let week_entries = after
+        .iter()
+        .filter(|e| e.timestamp >= start)
+        .collect::<Vec<&Entry>>();
+    if week_entries.is_empty() {
+        return None;
+    };
+
+    let stars = week_entries
+        .iter()
+        .counts_by(|e| &e.id)
+        .into_iter()
+        .map(|(id, n_stars)| (names::resolve(id), n_stars))
+        .sorted_by_key(|(name, n_stars)| (Reverse(*n_stars), name.clone()))
+        .collect();
+
+    let previous_positions = overall_positions(&Scoring::LOCAL, &before, year)
+        .into_iter()
+        .enumerate()
+        .map(|(pos, id)| (id.numeric, pos + 1))
+        .collect::<HashMap<_, _>>();
+    let climbs = overall_positions(&Scoring::LOCAL, &after, year)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(pos, id)| {
+            let previous = *previous_positions.get(&id.numeric)?;
+            (previous > pos + 1).then(|| (names::resolve(&id), previous, pos + 1))
+        })
+        .sorted_by_key(|(name, previous, current)| (Reverse(previous - current), name.clone()))
+        .take(RECAP_CLIMBS_COUNT)
+        .collect();
+
+    let holder = |leaderboard: &Leaderboard, jersey: &Jersey| {
+        Standing::new(leaderboard)
+            .tdf_season(jersey, year)
+            .first()
+            .map(|(id, _value, _n_days)| names::resolve(id))
+    };
+    let jerseys = [Jersey::YELLOW, Jersey::GREEN, Jersey::COMBATIVE]
+        .iter()
+        .filter_map(|jersey| {
+            Some((
+                jersey.to_string(),
+                holder(&before, jersey),
+                holder(&after, jersey)?,
+            ))
+        })
+        .collect();
+
+    // deltas of the days whose part 2 was completed during the week
+    let fastest_delta = week_entries
+        .iter()
+        .filter(|e| e.part == ProblemPart::SECOND)
+        .filter_map(|p2| {
+            let p1 = after.iter().find(|e| {
+                e.id.numeric == p2.id.numeric && e.day == p2.day && e.part == ProblemPart::FIRST
+            })?;
+            Some((&p2.id, p2.day, p2.timestamp - p1.timestamp))
+        })
+        .min_by_key(|(_id, day, delta)| (*delta, *day))
+        .map(|(id, day, delta): (&Identifier, u8, Duration)| {
+            (names::resolve(id), day, format_duration(delta))
+        });
+
+    Some(WeeklyRecap {
+        year,
+        stars,
+        climbs,
+        jerseys,
+        fastest_delta,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn weekly_recap_covers_the_stars_of_the_week() {
+        let year = 2018;
+        let members = vec![
+            (1..=3).fold(MemberFixture::new(41, "Jingle"), |m, day| {
+                m.with_day(day, 600, 600)
+            }),
+            (1..=8).fold(MemberFixture::new(42, "Jangle"), |m, day| {
+                // slower than Jingle on the delta before the week
+                let delta = if day <= 3 { 900 } else { 60 * day as i64 };
+                m.with_day(day, 3600, delta)
+            }),
+            MemberFixture::new(43, "Tinsel").with_part1(9, 7200),
+        ];
+        let leaderboard = private_leaderboard(year, &members);
+        let start = Entry::puzzle_unlock(year, 4).unwrap();
+        let end = Entry::puzzle_unlock(year, 11).unwrap();
+
+        let recap = weekly_recap(&leaderboard, year, start, end).unwrap();
+        assert_eq!(
+            recap.stars,
+            vec![("Jangle".to_string(), 10), ("Tinsel".to_string(), 1)]
+        );
+        assert_eq!(recap.climbs, vec![("Jangle".to_string(), 2, 1)]);
+        assert!(recap.jerseys.contains(&(
+            "green".to_string(),
+            Some("Jingle".to_string()),
+            "Jangle".to_string()
+        )));
+        assert_eq!(
+            recap.fastest_delta,
+            Some(("Jangle".to_string(), 4, "00:04:00".to_string()))
+        );
+
+        assert!(weekly_recap(&leaderboard, year, end, end + Duration::days(7)).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{
+    core::{
+        leaderboard::{Identifier, Leaderboard, MemberId, ProblemPart},
+        names,
+    },
+    utils::{format_duration, format_rank},
+};
+use chrono::Duration;
+use itertools::Itertools;
+use serde::Serialize;
+use std::{collections::HashMap, fmt};
+
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// Minimum number of consecutive days fully completed to earn the streak badge
+const STREAK_BADGE_MIN_DAYS: usize = 7;
+// Both parts completed within this time after the puzzle release
+const EARLY_BIRD_MAX_MIN: i64 = 60;
+// Star collected at least this number of days after the puzzle release
+const NEVER_GIVE_UP_MIN_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Badge {
+    AllStars,
+    Podium(usize),
+    Streak(usize),
+    EarlyBird,
+    NeverGiveUp,
+}
+
+impl fmt::Display for Badge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Badge::AllStars => write!(f, "🌟 *All stars*: every single star of the event collected"),
+            Badge::Podium(rank) => write!(
+                f,
+                "🏆 *Podium*: finished {} of the leaderboard",
+                format_rank(*rank)
+            ),
+            Badge::Streak(days) => write!(f, "🔥 *On fire*: {days} days in a row fully completed"),
+            Badge::EarlyBird => write!(
+                f,
+                "⏰ *Early bird*: both parts of a day completed within {EARLY_BIRD_MAX_MIN} minutes"
+            ),
+            Badge::NeverGiveUp => write!(
+                f,
+                "🐢 *Never give up*: caught up on a star more than {NEVER_GIVE_UP_MIN_DAYS} days late"
+            ),
+        }
+    }
+}
+
+/// Personal summary of the event for a member.
+#[derive(Debug, Serialize)]
+pub struct YearReview {
+    pub year: i32,
+    pub name: String,
+    pub n_stars: usize,
+    pub score: usize,
+    pub rank: usize,
+    pub n_members: usize,
+    // (day, points earned that day)
+    pub best_day: Option<(u8, usize)>,
+    pub average_delta: Option<String>,
+    // overall rank at the end of each day, best ranks being the highest bars
+    pub rank_chart: String,
+    pub badges: Vec<String>,
+}
+
+/// Year in review of the member, None if the member did not take part in the event.
+pub fn year_review(leaderboard: &Leaderboard, member: MemberId, year: i32) -> Option<YearReview> {
+    let scores = leaderboard.daily_stars_and_scores_per_member_for_year(year);
+    let (id, daily) = scores.iter().find(|(id, _daily)| id.numeric == member)?;
+    let n_members = scores.len();
+
+    // overall rank of the member at the end of each day with activity
+    let last_day = scores
+        .values()
+        .filter_map(|daily| daily.iter().rposition(|(stars, _score)| *stars > 0))
+        .max()
+        .unwrap_or_default();
+    let ranks = (0..=last_day)
+        .map(|day| rank_at_end_of_day(&scores, daily, day))
+        .collect::<Vec<usize>>();
+    let rank = ranks.last().copied().unwrap_or(n_members);
+
+    let n_stars = daily.iter().map(|(stars, _score)| *stars as usize).sum();
+    let score = daily.iter().map(|(_stars, score)| score).sum();
+    let best_day = daily
+        .iter()
+        .enumerate()
+        .filter(|(_day, (_stars, score))| *score > 0)
+        // earliest day wins ties
+        .max_by_key(|(day, (_stars, score))| (*score, -(*day as i32)))
+        .map(|(day, (_stars, score))| (day as u8 + 1, *score));
+
+    let member_entries = leaderboard
+        .iter()
+        .filter(|e| e.year == year && e.id.numeric == member)
+        .into_group_map_by(|e| e.day);
+    let deltas = member_entries
+        .values()
+        .filter_map(|entries| {
+            let part = |p: ProblemPart| entries.iter().find(|e| e.part == p).map(|e| e.timestamp);
+            Some(part(ProblemPart::SECOND)? - part(ProblemPart::FIRST)?)
+        })
+        .collect::<Vec<Duration>>();
+    let average_delta = match deltas.len() {
+        0 => None,
+        n => Some(format_duration(deltas.iter().sum::<Duration>() / n as i32)),
+    };
+
+    let mut badges = vec![];
+    if n_stars == 50 {
+        badges.push(Badge::AllStars);
+    }
+    if rank <= 3 {
+        badges.push(Badge::Podium(rank));
+    }
+    let longest_streak = daily
+        .iter()
+        .chunk_by(|(stars, _score)| *stars == 2)
+        .into_iter()
+        .filter(|(complete, _days)| *complete)
+        .map(|(_complete, days)| days.count())
+        .max()
+        .unwrap_or_default();
+    if longest_streak >= STREAK_BADGE_MIN_DAYS {
+        badges.push(Badge::Streak(longest_streak));
+    }
+    let durations = member_entries
+        .values()
+        .flatten()
+        .filter_map(|e| Some((e, e.duration_since_release().ok()?)))
+        .collect::<Vec<_>>();
+    if durations.iter().any(|(e, duration)| {
+        e.part == ProblemPart::SECOND && *duration <= Duration::minutes(EARLY_BIRD_MAX_MIN)
+    }) {
+        badges.push(Badge::EarlyBird);
+    }
+    if durations
+        .iter()
+        .any(|(_e, duration)| *duration >= Duration::days(NEVER_GIVE_UP_MIN_DAYS))
+    {
+        badges.push(Badge::NeverGiveUp);
+    }
+
+    Some(YearReview {
+        year,
+        name: names::resolve(id),
+        n_stars,
+        score,
+        rank,
+        n_members,
+        best_day,
+        average_delta,
+        rank_chart: rank_sparkline(&ranks, n_members),
+        badges: badges.iter().map(|b| b.to_string()).collect(),
+    })
+}
+
+/// Personal summary of a day for a member.
+#[derive(Debug, Serialize)]
+pub struct DayReview {
+    pub year: i32,
+    pub day: u8,
+    pub name: String,
+    // completion times since the puzzle release
+    pub p1_time: Option<String>,
+    pub p2_time: Option<String>,
+    pub delta: Option<String>,
+    pub points: usize,
+    // overall rank at the end of the previous day, None for the first day
+    pub previous_rank: Option<usize>,
+    pub rank: usize,
+    pub n_members: usize,
+}
+
+/// Review of the day for the member, None if the member did not collect any star of the day.
+pub fn day_review(
+    leaderboard: &Leaderboard,
+    member: MemberId,
+    year: i32,
+    day: u8,
+) -> Option<DayReview> {
+    let scores = leaderboard.daily_stars_and_scores_per_member_for_year(year);
+    let (id, daily) = scores.iter().find(|(id, _daily)| id.numeric == member)?;
+    let (stars, points) = daily[(day - 1) as usize];
+    if stars == 0 {
+        return None;
+    };
+
+    let entries = leaderboard
+        .iter()
+        .filter(|e| e.year == year && e.day == day && e.id.numeric == member)
+        .collect::<Vec<_>>();
+    let time = |p: ProblemPart| {
+        entries
+            .iter()
+            .find(|e| e.part == p)
+            .and_then(|e| e.duration_since_release().ok())
+    };
+    let (p1, p2) = (time(ProblemPart::FIRST), time(ProblemPart::SECOND));
+
+    let day_idx = (day - 1) as usize;
+    Some(DayReview {
+        year,
+        day,
+        name: names::resolve(id),
+        p1_time: p1.map(format_duration),
+        p2_time: p2.map(format_duration),
+        delta: p1.zip(p2).map(|(p1, p2)| format_duration(p2 - p1)),
+        points,
+        previous_rank: day_idx
+            .checked_sub(1)
+            .map(|previous| rank_at_end_of_day(&scores, daily, previous)),
+        rank: rank_at_end_of_day(&scores, daily, day_idx),
+        n_members: scores.len(),
+    })
+}
+
+// Overall rank of the member at the end of the day (zero-based), on the points earned so far.
+fn rank_at_end_of_day(
+    scores: &HashMap<&Identifier, [(u8, usize); 25]>,
+    daily: &[(u8, usize); 25],
+    day_idx: usize,
+) -> usize {
+    let total = |daily: &[(u8, usize); 25]| -> usize {
+        daily
+            .iter()
+            .take(day_idx + 1)
+            .map(|(_stars, score)| score)
+            .sum()
+    };
+    let member_total = total(daily);
+    1 + scores
+        .values()
+        .filter(|other| total(other) > member_total)
+        .count()
+}
+
+/// Sparkline of the ranks evolution, the first rank being the highest bar.
+fn rank_sparkline(ranks: &[usize], n_members: usize) -> String {
+    let n_levels = SPARKLINE_LEVELS.len();
+    ranks
+        .iter()
+        .map(|rank| {
+            let level = match n_members {
+                0 | 1 => n_levels - 1,
+                n => (n - rank) * (n_levels - 1) / (n - 1),
+            };
+            SPARKLINE_LEVELS[level]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{edge_case_members, private_leaderboard, MemberFixture, YEAR};
+
+    #[test]
+    fn completionist_gets_all_the_badges_they_deserve() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+        let review = year_review(&leaderboard, MemberId(1), YEAR).unwrap();
+
+        assert_eq!(review.n_stars, 50);
+        assert_eq!(review.rank, 1);
+        assert_eq!(review.average_delta, Some("00:05:00".to_string()));
+        assert_eq!(review.rank_chart.chars().count(), 25);
+        assert!(review.rank_chart.chars().all(|c| c == '█'));
+        assert_eq!(
+            review.badges,
+            vec![
+                Badge::AllStars.to_string(),
+                Badge::Podium(1).to_string(),
+                Badge::Streak(25).to_string(),
+                Badge::EarlyBird.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rank_evolution_follows_the_overall_standings() {
+        // Blitzen leads the first day, then stops playing
+        let members = vec![
+            MemberFixture::new(1, "Blitzen").with_day(1, 60, 60),
+            (1..=3).fold(MemberFixture::new(2, "Vixen"), |m, day| {
+                m.with_day(day, 3600, 600)
+            }),
+            MemberFixture::new(3, "Cupid").with_part1(2, 86_400 * 10),
+        ];
+        let leaderboard = private_leaderboard(YEAR, &members);
+
+        let vixen = year_review(&leaderboard, MemberId(2), YEAR).unwrap();
+        assert_eq!(vixen.rank, 1);
+        assert_eq!(vixen.best_day, Some((2, 6)));
+        assert_eq!(vixen.rank_chart, "▄██");
+
+        let cupid = year_review(&leaderboard, MemberId(3), YEAR).unwrap();
+        assert_eq!(cupid.rank, 3);
+        assert_eq!(cupid.average_delta, None);
+        assert_eq!(
+            cupid.badges,
+            vec![Badge::Podium(3).to_string(), Badge::NeverGiveUp.to_string()]
+        );
+
+        assert!(year_review(&leaderboard, MemberId(42), YEAR).is_none());
+    }
+
+    #[test]
+    fn day_review_reports_times_points_and_rank_change() {
+        let members = vec![
+            MemberFixture::new(1, "Blitzen").with_day(1, 60, 60),
+            MemberFixture::new(2, "Vixen")
+                .with_day(1, 3600, 600)
+                .with_day(2, 3600, 600),
+            MemberFixture::new(3, "Cupid").with_part1(2, 86_400 * 10),
+        ];
+        let leaderboard = private_leaderboard(YEAR, &members);
+
+        let vixen = day_review(&leaderboard, MemberId(2), YEAR, 2).unwrap();
+        assert_eq!(vixen.p1_time, Some("01:00:00".to_string()));
+        assert_eq!(vixen.p2_time, Some("01:10:00".to_string()));
+        assert_eq!(vixen.delta, Some("00:10:00".to_string()));
+        assert_eq!(vixen.points, 6);
+        assert_eq!((vixen.previous_rank, vixen.rank), (Some(2), 1));
+        assert_eq!(vixen.n_members, 3);
+
+        let cupid = day_review(&leaderboard, MemberId(3), YEAR, 2).unwrap();
+        assert_eq!(cupid.p2_time, None);
+        assert_eq!(cupid.delta, None);
+        assert_eq!(cupid.points, 2);
+
+        assert!(day_review(&leaderboard, MemberId(3), YEAR, 1).is_none());
+        assert!(day_review(&leaderboard, MemberId(1), YEAR, 2).is_none());
+    }
+}
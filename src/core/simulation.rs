@@ -0,0 +1,165 @@
+use crate::core::{
+    leaderboard::{Entry, Identifier, Leaderboard, ProblemPart},
+    names,
+    standings::{standings_board, Jersey, Scoring, Standing},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Projected standings if a member completed the missing parts of a day at a given time.
+#[derive(Debug, Clone, Serialize)]
+pub struct WhatIf {
+    pub name: String,
+    pub year: i32,
+    pub day: u8,
+    pub n_stars: usize,
+    // local points earned by the missing parts
+    pub points: usize,
+    // (position before, position after) in the local score standings
+    pub rank: (usize, usize),
+    // members passed in the local score standings
+    pub overtaken: Vec<String>,
+    // (jersey, position before, position after), None if not classified
+    pub jerseys: Vec<(String, Option<usize>, Option<usize>)>,
+}
+
+/// Simulation of the member completing the missing parts of the day at `at`. None if both parts
+/// of the day are already completed.
+pub fn what_if(
+    leaderboard: &Leaderboard,
+    member: &Identifier,
+    year: i32,
+    day: u8,
+    at: DateTime<Utc>,
+) -> Option<WhatIf> {
+    let mut before = Leaderboard::new();
+    before.extend(leaderboard.iter().filter(|e| e.year == year).cloned());
+    let completed = before
+        .iter()
+        .filter(|e| e.id.numeric == member.numeric && e.day == day)
+        .map(|e| e.part)
+        .collect::<Vec<ProblemPart>>();
+
+    let mut after = Leaderboard::new();
+    after.extend(before.iter().cloned());
+    let missing = [ProblemPart::FIRST, ProblemPart::SECOND]
+        .into_iter()
+        .filter(|part| !completed.contains(part))
+        .map(|part| Entry {
+            id: member.clone(),
+            rank: None,
+            star_index: None,
+            part,
+            year,
+            day,
+            timestamp: at,
+        })
+        .collect::<Vec<Entry>>();
+    if missing.is_empty() {
+        return None;
+    };
+    let n_stars = missing.len();
+    after.extend(missing);
+
+    let local_standings = |leaderboard: &Leaderboard| {
+        standings_board(&Scoring::LOCAL, leaderboard, year)
+            .into_iter()
+            .map(|(id, _days, score)| (id.numeric, score))
+            .collect::<Vec<_>>()
+    };
+    let (standings_before, standings_after) = (local_standings(&before), local_standings(&after));
+    let position = |standings: &[(_, usize)]| {
+        standings
+            .iter()
+            .position(|(id, _score)| *id == member.numeric)
+    };
+    let score = |standings: &[(_, usize)]| {
+        standings
+            .iter()
+            .find(|(id, _score)| *id == member.numeric)
+            .map_or(0, |(_id, score)| *score)
+    };
+    // members without any star of the year are not in the standings yet
+    let rank_before = position(&standings_before).unwrap_or(standings_before.len());
+    let rank_after = position(&standings_after).unwrap_or_default();
+
+    let overtaken = standings_after
+        .iter()
+        .skip(rank_after + 1)
+        .filter(|(id, _score)| {
+            standings_before
+                .iter()
+                .position(|(other, _score)| other == id)
+                .is_some_and(|pos| pos < rank_before)
+        })
+        .filter_map(|(id, _score)| {
+            before
+                .iter()
+                .find(|e| e.id.numeric == *id)
+                .map(|e| names::resolve(&e.id))
+        })
+        .collect();
+
+    let jersey_position = |leaderboard: &Leaderboard, jersey: &Jersey| {
+        Standing::new(leaderboard)
+            .tdf_season(jersey, year)
+            .iter()
+            .position(|(id, _value, _n_days)| id.numeric == member.numeric)
+            .map(|pos| pos + 1)
+    };
+    let jerseys = [Jersey::YELLOW, Jersey::GREEN, Jersey::COMBATIVE]
+        .iter()
+        .map(|jersey| {
+            (
+                jersey.to_string(),
+                jersey_position(&before, jersey),
+                jersey_position(&after, jersey),
+            )
+        })
+        .collect();
+
+    Some(WhatIf {
+        name: names::resolve(member),
+        year,
+        day,
+        n_stars,
+        points: score(&standings_after) - score(&standings_before),
+        rank: (rank_before + 1, rank_after + 1),
+        overtaken,
+        jerseys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn completing_a_missing_day_projects_the_new_standings() {
+        let year = 2017;
+        let members = vec![
+            MemberFixture::new(51, "Sugarplum")
+                .with_day(1, 300, 60)
+                .with_day(2, 300, 60),
+            MemberFixture::new(52, "Snowball").with_day(1, 600, 60),
+            MemberFixture::new(53, "Pepper").with_day(1, 900, 60),
+        ];
+        let leaderboard = private_leaderboard(year, &members);
+        let pepper = leaderboard.find_member("Pepper").unwrap();
+        let at = Entry::puzzle_unlock(year, 2).unwrap() + chrono::Duration::hours(2);
+
+        let projection = what_if(&leaderboard, &pepper, year, 2, at).unwrap();
+        assert_eq!(projection.n_stars, 2);
+        // second to complete each part of the day
+        assert_eq!(projection.points, 4);
+        assert_eq!(projection.rank, (3, 2));
+        assert_eq!(projection.overtaken, vec!["Snowball".to_string()]);
+        assert!(projection
+            .jerseys
+            .contains(&("yellow".to_string(), Some(3), Some(2))));
+
+        let sugarplum = leaderboard.find_member("Sugarplum").unwrap();
+        assert!(what_if(&leaderboard, &sugarplum, year, 2, at).is_none());
+    }
+}
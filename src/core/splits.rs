@@ -0,0 +1,134 @@
+use crate::core::leaderboard::{Identifier, Leaderboard};
+use chrono::Duration;
+use itertools::Itertools;
+use std::cmp::Reverse;
+
+// Part 1 time above which a day is not considered, the part 1 of a day started late being
+// obviously slower than its part 2
+const NEGATIVE_SPLIT_MAX_PART1_HOURS: i64 = 24;
+
+/// Day completed with a part 2 faster than the part 1: the time between the two stars is shorter
+/// than the time taken to collect the first star after the puzzle release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegativeSplit {
+    pub member: Identifier,
+    pub day: u8,
+    pub part1: Duration,
+    pub delta: Duration,
+}
+
+/// Number of negative splits of a member over an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitsCount {
+    pub member: Identifier,
+    pub n_splits: usize,
+    // days with both parts completed
+    pub n_days: usize,
+}
+
+/// Negative splits of the event, by day and then from the biggest to the smallest margin.
+pub fn negative_splits(leaderboard: &Leaderboard, year: i32) -> Vec<NegativeSplit> {
+    completed_days(leaderboard, year)
+        .into_iter()
+        .filter(|split| {
+            split.part1 <= Duration::hours(NEGATIVE_SPLIT_MAX_PART1_HOURS)
+                && split.delta < split.part1
+        })
+        .sorted_by_key(|split| (split.day, Reverse(split.part1 - split.delta)))
+        .collect()
+}
+
+/// Members with at least one negative split during the event, most negative splits first.
+pub fn season_splits(leaderboard: &Leaderboard, year: i32) -> Vec<SplitsCount> {
+    let n_days = completed_days(leaderboard, year)
+        .into_iter()
+        .counts_by(|split| split.member);
+    negative_splits(leaderboard, year)
+        .into_iter()
+        .counts_by(|split| split.member)
+        .into_iter()
+        .map(|(member, n_splits)| SplitsCount {
+            n_days: n_days.get(&member).copied().unwrap_or_default(),
+            member,
+            n_splits,
+        })
+        .sorted_by_key(|count| {
+            (
+                Reverse(count.n_splits),
+                count.n_days,
+                count.member.name.clone(),
+            )
+        })
+        .collect()
+}
+
+// (part 1 time, delta) of the days with both parts completed.
+fn completed_days(leaderboard: &Leaderboard, year: i32) -> Vec<NegativeSplit> {
+    leaderboard
+        .entries_per_day_member_for_year(year)
+        .into_iter()
+        .filter(|(_key, entries)| entries.len() == 2)
+        .filter_map(|((day, id), entries)| {
+            let (p1, p2) = entries.into_iter().sorted_unstable().collect_tuple()?;
+            Some(NegativeSplit {
+                member: id.clone(),
+                day,
+                part1: p1.duration_since_release().ok()?,
+                delta: p2.timestamp - p1.timestamp,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::MemberId,
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+
+    #[test]
+    fn negative_splits_are_counted_over_the_event() {
+        let leaderboard = private_leaderboard(
+            2020,
+            &[
+                MemberFixture::new(3801, "Dasher")
+                    .with_day(1, 900, 300)
+                    .with_day(2, 600, 1200)
+                    .with_day(3, 1800, 60),
+                MemberFixture::new(3802, "Vixen")
+                    .with_day(1, 600, 120)
+                    .with_part1(2, 300),
+                // started late, part 1 collected two days after the release
+                MemberFixture::new(3803, "Prancer").with_day(1, 2 * 86400, 60),
+            ],
+        );
+
+        let splits = negative_splits(&leaderboard, 2020);
+        assert_eq!(
+            splits
+                .iter()
+                .map(|s| (s.day, s.member.numeric))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, MemberId(3801)),
+                (1, MemberId(3802)),
+                (3, MemberId(3801))
+            ]
+        );
+        assert_eq!(splits[0].delta, Duration::seconds(300));
+
+        let counts = season_splits(&leaderboard, 2020);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(
+            (
+                counts[0].member.numeric,
+                counts[0].n_splits,
+                counts[0].n_days
+            ),
+            (MemberId(3801), 2, 3)
+        );
+        assert_eq!((counts[1].n_splits, counts[1].n_days), (1, 1));
+    }
+}
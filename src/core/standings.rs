@@ -1,29 +1,195 @@
 use crate::{
-    core::leaderboard::{Entry, Identifier, Leaderboard},
-    utils::{exponential_decay, format_duration},
+    core::{
+        absences,
+        leaderboard::{Entry, Identifier, Leaderboard, ProblemPart},
+        names,
+    },
+    utils::{exponential_decay, format_duration, parse_duration},
 };
 use chrono::{Datelike, Duration, Utc};
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use std::{cmp::Reverse, collections::HashMap, fmt};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::HashMap, fmt, iter, ops::RangeInclusive};
 
-// Time penalty added for TDF rankings if a day is not finished
-pub static PENALTY_UNFINISHED_DAY: Lazy<i64> = Lazy::new(|| Duration::days(7).num_seconds());
+// Time penalty added for TDF rankings if a day is not finished, unless set in the settings
+const PENALTY_UNFINISHED_DAY: &str = "7d";
 const JERSEY_COLORS: [&str; 3] = ["yellow", "green", "combative"];
-const SCORING_METHODS: [&str; 2] = ["local", "stars"];
+const SCORING_METHODS: [&str; 3] = ["local", "stars", "percentile"];
 const RANKING_METHODS: [&str; 4] = ["delta", "p1", "p2", "limit"];
+const AGGREGATE_METHODS: [&str; 2] = ["avg", "median"];
+const JERSEY_AGGREGATIONS: [&str; 2] = ["sum", "points"];
+// Option of the `tdf` command not to be used as a custom jersey name
+const RESERVED_JERSEY_NAMES: [&str; 1] = ["absence"];
 
 // see https://en.wikipedia.org/wiki/Points_classification_in_the_Tour_de_France#Current
-const GREEN_JERSEY_POINTS: [u8; 15] = [50, 30, 20, 18, 16, 14, 12, 10, 8, 7, 6, 5, 4, 3, 2];
+const GREEN_JERSEY_POINTS: [usize; 15] = [50, 30, 20, 18, 16, 14, 12, 10, 8, 7, 6, 5, 4, 3, 2];
 const COMBATIVE_JERSEY_MAX_POINTS: f32 = 500.0;
 const COMBATIVE_JERSEY_POINTS_DECAY_RATE: f32 = 0.005;
+// (share of the members ahead in %, exclusive upper bound, points) of the bands of the percentile
+// scoring, so a few seconds do not cost as many points as with the local score
+const PERCENTILE_BANDS: [(usize, usize); 5] = [(10, 10), (25, 8), (50, 6), (75, 4), (100, 2)];
+// Minimum number of members who completed a day to rank a delta time among the others
+const DELTA_PERCENTILE_MIN_MEMBERS: usize = 4;
+
+// Points curves of the jerseys, registered once at startup before any standing is computed.
+static JERSEY_POINTS: OnceCell<JerseyPoints> = OnceCell::new();
 
 pub type DailyStarsAndScores = [(u8, usize); 25];
 
-#[derive(Debug, Clone)]
+/// Points awarded each day for the green and combative jerseys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JerseyPoints {
+    // green jersey points of the fastest members on the delta time, by position
+    pub green_points: Vec<usize>,
+    // combative jersey points for a day finished right before the next puzzle unlock
+    pub combative_max_points: f32,
+    // decrease of the combative jersey points for each minute left before the next unlock
+    pub combative_decay_rate: f32,
+    // time counted for the yellow jersey for a day not finished or finished in a longer time,
+    // e.g. `7d` or `36h`
+    pub yellow_penalty: String,
+    // jerseys defined by the operators, along with the Tour de France inspired ones
+    pub custom: Vec<CustomJersey>,
+}
+
+/// Jersey defined in the settings, ranking the members on a time of each day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomJersey {
+    // name of the jersey, used as option of the `tdf` command
+    pub name: String,
+    // time of the day the members are ranked on: `delta`, `p1`, `p2` or `limit`
+    pub metric: Ranking,
+    // `sum` of the times of the days, or `points` earned each day by position on the time
+    pub aggregation: JerseyAggregation,
+    // points of the fastest members each day, by position (`points` aggregation)
+    #[serde(default)]
+    pub points: Vec<usize>,
+    // time counted for a day without time or with a longer time (`sum` aggregation)
+    #[serde(default = "default_penalty_sec")]
+    pub penalty_sec: i64,
+}
+
+fn default_penalty_sec() -> i64 {
+    parse_duration(PENALTY_UNFINISHED_DAY)
+        .unwrap_or_default()
+        .num_seconds()
+}
+
+impl CustomJersey {
+    /// Time the members are ranked on each day.
+    pub fn ranking(&self) -> Ranking {
+        self.metric.clone()
+    }
+
+    pub fn is_points(&self) -> bool {
+        self.aggregation == JerseyAggregation::POINTS
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let name = &self.name;
+        let mut problems = vec![];
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            problems.push(format!("'{name}' must be a single word"));
+        } else if JERSEY_COLORS.contains(&name.as_str())
+            || RESERVED_JERSEY_NAMES.contains(&name.as_str())
+        {
+            problems.push(format!("'{name}' is already a jersey or an option"));
+        }
+        if self.is_points()
+            && (self.points.is_empty() || self.points.windows(2).any(|w| w[0] < w[1]))
+        {
+            problems.push(format!(
+                "'{name}' points must not be empty and be in decreasing order"
+            ));
+        }
+        if self.penalty_sec <= 0 {
+            problems.push(format!("'{name}' penalty_sec must be greater than 0"));
+        }
+        problems
+    }
+}
+
+impl Default for JerseyPoints {
+    fn default() -> Self {
+        JerseyPoints {
+            green_points: GREEN_JERSEY_POINTS.to_vec(),
+            combative_max_points: COMBATIVE_JERSEY_MAX_POINTS,
+            combative_decay_rate: COMBATIVE_JERSEY_POINTS_DECAY_RATE,
+            yellow_penalty: PENALTY_UNFINISHED_DAY.to_string(),
+            custom: vec![],
+        }
+    }
+}
+
+impl JerseyPoints {
+    /// Problems of the points curves, as (field, problem).
+    pub fn validate(&self) -> Vec<(&'static str, String)> {
+        let mut problems = vec![];
+        if self.green_points.is_empty() {
+            problems.push(("green_points", "must not be empty".to_string()));
+        } else if self.green_points.windows(2).any(|w| w[0] < w[1]) {
+            problems.push(("green_points", "must be in decreasing order".to_string()));
+        }
+        if !(self.combative_max_points.is_finite() && self.combative_max_points > 0.0) {
+            problems.push(("combative_max_points", "must be greater than 0".to_string()));
+        }
+        if !(0.0..1.0).contains(&self.combative_decay_rate) {
+            problems.push((
+                "combative_decay_rate",
+                "must be between 0 (included) and 1 (excluded)".to_string(),
+            ));
+        }
+        match parse_duration(&self.yellow_penalty) {
+            Some(penalty) if penalty > Duration::zero() => (),
+            Some(_) => problems.push(("yellow_penalty", "must be greater than 0".to_string())),
+            None => problems.push((
+                "yellow_penalty",
+                format!(
+                    "'{}' is not a duration like '7d' or '36h'",
+                    self.yellow_penalty
+                ),
+            )),
+        }
+        for jersey in &self.custom {
+            problems.extend(jersey.validate().into_iter().map(|p| ("custom", p)));
+        }
+        let names = self.custom.iter().map(|j| &j.name).counts();
+        for name in names
+            .iter()
+            .filter(|(_, n)| **n > 1)
+            .map(|(name, _)| name)
+            .sorted()
+        {
+            problems.push(("custom", format!("'{name}' is defined twice")));
+        }
+        problems
+    }
+}
+
+/// Time penalty (in seconds) of the yellow jersey for a day not finished.
+pub fn penalty_unfinished_day() -> i64 {
+    parse_duration(&jersey_points().yellow_penalty)
+        .map_or_else(default_penalty_sec, |penalty| penalty.num_seconds())
+}
+
+/// Use points curves for all the jerseys standings. Only the first registered curves are kept.
+pub fn register_jersey_points(points: JerseyPoints) {
+    let _ = JERSEY_POINTS.set(points);
+}
+
+/// Points curves of the jerseys, the Tour de France inspired ones if none were registered.
+pub fn jersey_points() -> &'static JerseyPoints {
+    JERSEY_POINTS.get_or_init(JerseyPoints::default)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Scoring {
     LOCAL,
     STARS,
+    // points by percentile band of the rank, rather than by rank
+    PERCENTILE,
 }
 
 #[derive(Debug, Clone)]
@@ -31,27 +197,53 @@ pub enum Jersey {
     YELLOW,
     GREEN,
     COMBATIVE,
+    CUSTOM(CustomJersey),
 }
 
-#[derive(Debug, Clone)]
+// Serialized as the values of the ranking option (`RANKING_METHODS`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Ranking {
+    #[serde(rename = "delta")]
     DELTA,
+    #[serde(rename = "p1")]
     PART1,
+    #[serde(rename = "p2")]
     PART2,
+    #[serde(rename = "limit")]
     LIMIT,
 }
 
+// How the times of the days are aggregated for a custom jersey
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JerseyAggregation {
+    SUM,
+    POINTS,
+}
+
+// How the delta times of a member are aggregated over a range of days
+#[derive(Debug, Clone)]
+pub enum Aggregate {
+    AVERAGE,
+    MEDIAN,
+}
+
 impl Scoring {
     pub fn from_string(s: &str) -> Option<Self> {
         match s {
             method if method == SCORING_METHODS[0] => Some(Scoring::LOCAL),
             method if method == SCORING_METHODS[1] => Some(Scoring::STARS),
+            method if method == SCORING_METHODS[2] => Some(Scoring::PERCENTILE),
             _ => None,
         }
     }
     pub fn get_default_str() -> &'static str {
         SCORING_METHODS[0]
     }
+    /// Values of the option of the commands, listed when an unknown one is used.
+    pub fn options() -> Vec<&'static str> {
+        SCORING_METHODS.to_vec()
+    }
 }
 
 impl fmt::Display for Scoring {
@@ -63,6 +255,9 @@ impl fmt::Display for Scoring {
             Scoring::STARS => {
                 write!(f, "{}", SCORING_METHODS[1])
             }
+            Scoring::PERCENTILE => {
+                write!(f, "{}", SCORING_METHODS[2])
+            }
         }
     }
 }
@@ -73,12 +268,30 @@ impl Jersey {
             color if color == JERSEY_COLORS[0] => Some(Jersey::YELLOW),
             color if color == JERSEY_COLORS[1] => Some(Jersey::GREEN),
             color if color == JERSEY_COLORS[2] => Some(Jersey::COMBATIVE),
-            _ => None,
+            name => jersey_points()
+                .custom
+                .iter()
+                .find(|jersey| jersey.name == name)
+                .map(|jersey| Jersey::CUSTOM(jersey.clone())),
         }
     }
     pub fn get_default_str() -> &'static str {
         JERSEY_COLORS[0]
     }
+    /// Values of the option of the `tdf` command (custom jerseys included), listed when an
+    /// unknown one is used.
+    pub fn options() -> Vec<&'static str> {
+        JERSEY_COLORS
+            .into_iter()
+            .chain(
+                jersey_points()
+                    .custom
+                    .iter()
+                    .map(|jersey| jersey.name.as_str()),
+            )
+            .chain(RESERVED_JERSEY_NAMES)
+            .collect()
+    }
 }
 
 impl fmt::Display for Jersey {
@@ -93,6 +306,9 @@ impl fmt::Display for Jersey {
             Jersey::COMBATIVE => {
                 write!(f, "{}", JERSEY_COLORS[2])
             }
+            Jersey::CUSTOM(jersey) => {
+                write!(f, "{}", jersey.name)
+            }
         }
     }
 }
@@ -110,6 +326,23 @@ impl Ranking {
     pub fn get_default_str() -> &'static str {
         RANKING_METHODS[0]
     }
+    /// Values of the option of the commands, listed when an unknown one is used.
+    pub fn options() -> Vec<&'static str> {
+        RANKING_METHODS.to_vec()
+    }
+}
+
+impl fmt::Display for JerseyAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JerseyAggregation::SUM => {
+                write!(f, "{}", JERSEY_AGGREGATIONS[0])
+            }
+            JerseyAggregation::POINTS => {
+                write!(f, "{}", JERSEY_AGGREGATIONS[1])
+            }
+        }
+    }
 }
 
 impl fmt::Display for Ranking {
@@ -131,6 +364,48 @@ impl fmt::Display for Ranking {
     }
 }
 
+impl Aggregate {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s {
+            method if method == AGGREGATE_METHODS[0] => Some(Aggregate::AVERAGE),
+            method if method == AGGREGATE_METHODS[1] => Some(Aggregate::MEDIAN),
+            _ => None,
+        }
+    }
+    /// Values of the option of the commands, listed when an unknown one is used.
+    pub fn options() -> Vec<&'static str> {
+        AGGREGATE_METHODS.to_vec()
+    }
+
+    fn aggregate(&self, mut seconds: Vec<i64>) -> i64 {
+        let n = seconds.len() as i64;
+        match self {
+            Aggregate::AVERAGE => seconds.iter().sum::<i64>() / n,
+            Aggregate::MEDIAN => {
+                seconds.sort_unstable();
+                let mid = seconds.len() / 2;
+                match seconds.len() % 2 {
+                    0 => (seconds[mid - 1] + seconds[mid]) / 2,
+                    _ => seconds[mid],
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Aggregate::AVERAGE => {
+                write!(f, "{}", AGGREGATE_METHODS[0])
+            }
+            Aggregate::MEDIAN => {
+                write!(f, "{}", AGGREGATE_METHODS[1])
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Standing<'a> {
     leaderboard: &'a Leaderboard,
@@ -148,10 +423,11 @@ impl Standing<'_> {
         day: u8,
     ) -> Vec<(&'b Identifier, usize)> {
         match jersey {
-            Jersey::GREEN => GREEN_JERSEY_POINTS
+            Jersey::GREEN => jersey_points()
+                .green_points
                 .iter()
                 .zip(self.ranked_times_for_year_day(&Ranking::DELTA, year, day))
-                .map(|(points, (id, _duration))| (id, *points as usize))
+                .map(|(points, (id, _duration))| (id, *points))
                 .collect::<Vec<_>>(),
             Jersey::COMBATIVE => self
                 .ranked_times_for_year_day(&Ranking::LIMIT, year, day)
@@ -162,15 +438,235 @@ impl Standing<'_> {
                     )
                 })
                 .collect::<Vec<_>>(),
+            Jersey::CUSTOM(jersey) if jersey.is_points() => jersey
+                .points
+                .iter()
+                .zip(self.ranked_times_for_year_day(&jersey.ranking(), year, day))
+                .map(|(points, (id, _duration))| (id, *points))
+                .collect::<Vec<_>>(),
             _ => vec![],
         }
     }
 
+    /// ordered vec of (id, time) of the day for the time the custom jersey ranks the members on.
+    pub fn custom_stage(
+        &self,
+        jersey: &CustomJersey,
+        year: i32,
+        day: u8,
+    ) -> Vec<(&Identifier, Option<Duration>)> {
+        self.ranked_times_for_year_day(&jersey.ranking(), year, day)
+            .map(|(id, duration)| (id, Some(duration)))
+            .collect()
+    }
+
     pub fn by_time(&self, ranking_type: &Ranking, year: i32, day: u8) -> Vec<(String, String)> {
         self.ranked_times_for_year_day(ranking_type, year, day)
-            .map(|(id, duration)| (id.name.clone(), format_duration(duration)))
+            .map(|(id, duration)| (names::resolve(id), format_duration(duration)))
             .collect::<Vec<_>>()
     }
+    /// ordered vec of (name, aggregated delta time, number of days counted) over a range of days.
+    /// Only the days with both parts completed are counted, so members having completed more days
+    /// are ranked first, then by aggregated delta time.
+    pub fn by_delta_over_days(
+        &self,
+        aggregate: &Aggregate,
+        year: i32,
+        days: RangeInclusive<u8>,
+    ) -> Vec<(String, String, usize)> {
+        self.aggregated_deltas(aggregate, year, days)
+            .into_iter()
+            .sorted_unstable_by_key(|(_id, n_days, seconds)| (Reverse(*n_days), *seconds))
+            .map(|(id, n_days, seconds)| {
+                (
+                    names::resolve(id),
+                    format_duration(Duration::seconds(seconds)),
+                    n_days,
+                )
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// ordered vec of (name, aggregated delta time, number of days counted) over the whole event,
+    /// of the members having completed both parts of at least `min_days` days. Unlike the range
+    /// ranking, members are only ranked by aggregated delta time, i.e. by consistency.
+    pub fn by_season_delta(
+        &self,
+        aggregate: &Aggregate,
+        year: i32,
+        min_days: usize,
+    ) -> Vec<(String, String, usize)> {
+        self.aggregated_deltas(aggregate, year, 1..=25)
+            .into_iter()
+            .filter(|(_id, n_days, _seconds)| *n_days >= min_days)
+            .sorted_unstable_by_key(|(_id, n_days, seconds)| (*seconds, Reverse(*n_days)))
+            .map(|(id, n_days, seconds)| {
+                (
+                    names::resolve(id),
+                    format_duration(Duration::seconds(seconds)),
+                    n_days,
+                )
+            })
+            .collect::<Vec<_>>()
+    }
+
+    // (member, number of days with both parts completed, aggregated delta in seconds) over the
+    // range of days.
+    fn aggregated_deltas(
+        &self,
+        aggregate: &Aggregate,
+        year: i32,
+        days: RangeInclusive<u8>,
+    ) -> Vec<(&Identifier, usize, i64)> {
+        self.leaderboard
+            .entries_per_day_member_for_year(year)
+            .into_iter()
+            .filter(|((day, _id), _entries)| days.contains(day))
+            .filter_map(|((_day, id), entries_for_day)| {
+                Self::compute_delta(&entries_for_day).map(|delta| (id, delta.num_seconds()))
+            })
+            .into_group_map()
+            .into_iter()
+            .map(|(id, deltas)| (id, deltas.len(), aggregate.aggregate(deltas)))
+            .collect()
+    }
+
+    /// ordered vec of (id, part 1 wins, part 2 wins, delta wins, total wins) for the year. A day is
+    /// won by the fastest member for the ranking.
+    pub fn wins(&self, year: i32) -> Vec<(&Identifier, usize, usize, usize, usize)> {
+        (1..=25)
+            .flat_map(|day| {
+                [Ranking::PART1, Ranking::PART2, Ranking::DELTA]
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(move |(idx, ranking)| {
+                        self.day_winner(&ranking, year, day).map(|id| (id, idx))
+                    })
+            })
+            .fold(HashMap::new(), |mut acc, (id, idx)| {
+                let wins = acc.entry(id).or_insert([0; 3]);
+                wins[idx] += 1;
+                acc
+            })
+            .into_iter()
+            .map(|(id, [p1, p2, delta])| (id, p1, p2, delta, p1 + p2 + delta))
+            // sort by total wins descending, then by part 2 wins descending
+            .sorted_unstable_by_key(|(id, _p1, p2, _delta, total)| {
+                (Reverse(*total), Reverse(*p2), id.numeric)
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn day_winner(&self, ranking_type: &Ranking, year: i32, day: u8) -> Option<&Identifier> {
+        self.ranked_times_for_year_day(ranking_type, year, day)
+            .next()
+            .map(|(id, _duration)| id)
+    }
+
+    /// ordered vec of (id, stage time) for the yellow jersey stage of the day. The stage time is
+    /// the part 2 completion time, members who only completed part 1 (did not finish the stage)
+    /// being ranked last without stage time.
+    pub fn tdf_stage(&self, year: i32, day: u8) -> Vec<(&Identifier, Option<Duration>)> {
+        self.leaderboard
+            .entries_per_member_for_year_day(year, day)
+            .into_iter()
+            .map(|(id, entries_for_day)| {
+                (
+                    id,
+                    Self::get_time_for_part(&entries_for_day, Ranking::PART2),
+                )
+            })
+            .sorted_unstable_by_key(|(id, time)| (time.is_none(), *time, id.numeric))
+            .collect::<Vec<_>>()
+    }
+
+    /// ordered vec of (id, total time in secs, number of days over stage cutoff) of the yellow
+    /// jersey, the `drop_worst` days of each member with the longest times not being counted.
+    pub fn yellow_season(&self, year: i32, drop_worst: usize) -> Vec<(&Identifier, i64, i64)> {
+        let max_n_days = Self::days_to_score(year);
+        let penalty = penalty_unfinished_day();
+
+        let data = self.leaderboard.entries_per_day_member_for_year(year);
+        let finished_days_per_member = data
+            .into_iter()
+            .filter_map(|((day, id), entries_for_day)| {
+                Standing::get_time_for_part(&entries_for_day, Ranking::PART2)
+                    .map(|duration| (day, id, duration))
+            })
+            .fold(HashMap::new(), |mut acc, (day, id, duration)| {
+                // (day, time counted, whether the day is over the cutoff)
+                let finished_days: &mut Vec<(u8, i64, bool)> = acc.entry(id).or_default();
+                // we do not want to be unfair with members having finished a day in a time that exceed
+                // the time penalty for finishing a day inflicted to members not having finished a day.
+                let absent = absences::days(id.numeric, year).contains(&day);
+                finished_days.push(match (duration.num_seconds() < penalty, absent) {
+                    // time to complete is not greater than max time penalty
+                    (true, _) => (day, duration.num_seconds(), false),
+                    // declared days of absence finished over the cutoff are not penalized
+                    (false, true) => (day, penalty, false),
+                    (false, false) => (day, 2 * penalty, true),
+                });
+                acc
+            });
+
+        finished_days_per_member
+            .into_iter()
+            .map(|(id, finished_days)| {
+                // declared days of absence are not penalized, unless the member finished them
+                // anyway
+                let absences = absences::days(id.numeric, year);
+                let absent_days = absences
+                    .iter()
+                    .filter(|day| (**day as i64) <= max_n_days)
+                    .count() as i64;
+                let absent_finished_days = finished_days
+                    .iter()
+                    .filter(|(day, _time, _over)| absences.contains(day))
+                    .count() as i64;
+                let absent_unfinished_days = absent_days - absent_finished_days;
+                let unfinished_days =
+                    max_n_days - finished_days.len() as i64 - absent_unfinished_days;
+
+                // the unfinished days of absence count the median time of the finished days, so
+                // an absence neither improves nor worsens the standing of the member
+                let neutral_time = finished_days
+                    .iter()
+                    .map(|(_day, time, _over)| (*time).min(penalty))
+                    .sorted_unstable()
+                    .collect::<Vec<i64>>();
+                let neutral_time = match neutral_time.len() {
+                    0 => penalty,
+                    n => neutral_time[n / 2],
+                };
+
+                // penalty for every challenge not completed
+                let counted_days = finished_days
+                    .iter()
+                    .map(|(_day, time, over_cutoff)| (*time, *over_cutoff))
+                    .chain(iter::repeat_n(
+                        (neutral_time, false),
+                        absent_unfinished_days.max(0) as usize,
+                    ))
+                    .chain(iter::repeat_n(
+                        (penalty, true),
+                        unfinished_days.max(0) as usize,
+                    ))
+                    // worst days first, to be dropped
+                    .sorted_unstable_by_key(|day| Reverse(*day))
+                    .skip(drop_worst)
+                    .collect::<Vec<(i64, bool)>>();
+                let total_duration = counted_days.iter().map(|(time, _)| time).sum::<i64>();
+                let days_over_cutoff = counted_days.iter().filter(|(_, over)| *over).count() as i64;
+                (id, total_duration, days_over_cutoff)
+            })
+            // sort by total time ascending, then by number of penalties ascendings
+            .sorted_unstable_by(|a, b| match a.1 == b.1 {
+                true => a.2.cmp(&b.2),
+                false => a.1.cmp(&b.1),
+            })
+            .collect::<Vec<(&Identifier, i64, i64)>>()
+    }
+
     /// ordered vec of (id, time/points of interests, number of days of interest)
     pub fn tdf_season<'a: 'b, 'b>(
         &'a self,
@@ -181,79 +677,7 @@ impl Standing<'_> {
         // see if we can refactor a bit and simplify
         match jersey {
             // returns Vec<(id, total time in secs, number of days over stage cutoff)>
-            Jersey::YELLOW => {
-                // NOTE: here we cannot use utils::current_year_day() to get the current year.
-                // Using current_year_day() would return the last aoc year, not necessarily the
-                // actual current year (if we are in the first 11 months of the year following the
-                // last AOC). So we would incorrectly compute the max_n_days below, since it would
-                // comparecompare it to "year", which is already computed using the current_year_day
-                // function, and as such would incorrectly use the current day.
-                let now = Utc::now();
-
-                // how many possible days to score for yellow jersey
-                let current_day = now.day();
-                let max_n_days = match year == now.year() {
-                    false => 25,
-                    true => {
-                        // Ensure correct output from 26/12 to 31/12 of ongoing AOC event
-                        if current_day <= 25 {
-                            current_day as i64
-                        } else {
-                            25
-                        }
-                    }
-                };
-
-                let data = self.leaderboard.entries_per_day_member_for_year(year);
-                let duration_sum_per_member = data
-                    .into_iter()
-                    .filter_map(|((_day, id), entries_for_day)| {
-                        Standing::get_time_for_part(&entries_for_day, Ranking::PART2)
-                            .map(|duration| (id, duration))
-                    })
-                    .fold(HashMap::new(), |mut acc, (id, duration)| {
-                        // (total duration, finished days, finished days below cutoff)
-                        let duration_sum_and_count = acc.entry(id).or_insert((0, 0, 0));
-                        // we do not want to be unfair with members having finished a day in a time that exceed
-                        // the time penalty for finishing a day inflicted to members not having finished a day.
-                        let (duration_to_add, finished_before_cutoff) =
-                            match (*PENALTY_UNFINISHED_DAY - duration.num_seconds()) > 0 {
-                                // time to complete is not greater than max time penalty
-                                true => (duration.num_seconds(), 1),
-                                false => (*PENALTY_UNFINISHED_DAY, 0),
-                            };
-                        *duration_sum_and_count = (
-                            duration_sum_and_count.0 + duration_to_add,
-                            duration_sum_and_count.1 + 1,
-                            duration_sum_and_count.2 + finished_before_cutoff,
-                        );
-                        acc
-                    });
-
-                let standings = duration_sum_per_member
-                    .iter()
-                    .map(
-                        |(id, (total_duration, _finished_days, finished_before_cutoff_days))| {
-                            let days_over_cutoff = max_n_days - finished_before_cutoff_days;
-                            match days_over_cutoff {
-                                0 => (*id, *total_duration, days_over_cutoff),
-                                diff => {
-                                    // penalty for every challenge not completed
-                                    let total_duration =
-                                        total_duration + diff * (*PENALTY_UNFINISHED_DAY);
-                                    (*id, total_duration, days_over_cutoff)
-                                }
-                            }
-                        },
-                    )
-                    // sort by total time ascending, then by number of penalties ascendings
-                    .sorted_unstable_by(|a, b| match a.1 == b.1 {
-                        true => a.2.cmp(&b.2),
-                        false => a.1.cmp(&b.1),
-                    })
-                    .collect::<Vec<(&Identifier, i64, i64)>>();
-                standings
-            }
+            Jersey::YELLOW => self.yellow_season(year, 0),
             // returns Vec<(id, total earned points, number of stages with earned points)>
             Jersey::GREEN => {
                 let data = self.leaderboard.entries_per_day_member_for_year(year);
@@ -271,9 +695,9 @@ impl Standing<'_> {
                         .map(|(_day, id, delta)| (id, delta))
                         // sort by delta time ascending
                         .sorted_unstable_by(|a, b| a.1.cmp(&b.1))
-                        .zip(GREEN_JERSEY_POINTS)
-                        .map(|((id, _delta), points)| (id, day, points))
-                        .collect::<Vec<(&Identifier, u8, u8)>>()
+                        .zip(&jersey_points().green_points)
+                        .map(|((id, _delta), points)| (id, day, *points))
+                        .collect::<Vec<(&Identifier, u8, usize)>>()
                 });
 
                 daily_points
@@ -329,6 +753,87 @@ impl Standing<'_> {
                     .collect::<Vec<(&Identifier, i64, i64)>>();
                 standings
             }
+            // returns Vec<(id, total earned points, number of stages with earned points)>
+            Jersey::CUSTOM(jersey) if jersey.is_points() => {
+                let ranking = jersey.ranking();
+                (1..=25)
+                    .flat_map(|day| {
+                        jersey
+                            .points
+                            .iter()
+                            .zip(self.ranked_times_for_year_day(&ranking, year, day))
+                            .map(|(points, (id, _duration))| (id, *points as i64))
+                            .collect::<Vec<(&Identifier, i64)>>()
+                    })
+                    .fold(HashMap::new(), |mut acc, (id, points)| {
+                        let total_points_and_days = acc.entry(id).or_insert((0, 0));
+                        *total_points_and_days = (
+                            total_points_and_days.0 + points,
+                            total_points_and_days.1 + 1,
+                        );
+                        acc
+                    })
+                    .into_iter()
+                    .map(|(id, (total_points, n_days))| (id, total_points, n_days))
+                    // sort by total points descending, then by number of scored days descending
+                    .sorted_unstable_by_key(|(id, points, n_days)| {
+                        (Reverse(*points), Reverse(*n_days), id.numeric)
+                    })
+                    .collect::<Vec<(&Identifier, i64, i64)>>()
+            }
+            // returns Vec<(id, total time in secs, number of penalized days)>
+            Jersey::CUSTOM(jersey) => {
+                let (ranking, penalty) = (jersey.ranking(), jersey.penalty_sec);
+                let max_n_days = Self::days_to_score(year);
+                let times = (1..=max_n_days as u8)
+                    .flat_map(|day| self.ranked_times_for_year_day(&ranking, year, day))
+                    .map(|(id, duration)| (id, duration.num_seconds()))
+                    .into_group_map();
+                // members without any time for the metric are penalized every day
+                self.leaderboard
+                    .iter()
+                    .filter(|e| e.year == year)
+                    .map(|e| &e.id)
+                    .unique()
+                    .map(|id| {
+                        let seconds = times.get(id).cloned().unwrap_or_default();
+                        let n_scored = seconds.iter().filter(|s| **s < penalty).count() as i64;
+                        let total = seconds.iter().map(|s| (*s).min(penalty)).sum::<i64>()
+                            + (max_n_days - seconds.len() as i64) * penalty;
+                        (id, total, max_n_days - n_scored)
+                    })
+                    // sort by total time ascending, then by number of penalties ascending
+                    .sorted_unstable_by_key(|(id, total, penalties)| {
+                        (*total, *penalties, id.numeric)
+                    })
+                    .collect::<Vec<(&Identifier, i64, i64)>>()
+            }
+        }
+    }
+
+    // Number of days to score for the time based jerseys: every day of the past events, up to the
+    // current day for the ongoing one.
+    fn days_to_score(year: i32) -> i64 {
+        // NOTE: here we cannot use utils::current_year_day() to get the current year.
+        // Using current_year_day() would return the last aoc year, not necessarily the
+        // actual current year (if we are in the first 11 months of the year following the
+        // last AOC). So we would incorrectly compute the max_n_days below, since it would
+        // comparecompare it to "year", which is already computed using the current_year_day
+        // function, and as such would incorrectly use the current day.
+        let now = Utc::now();
+
+        // how many possible days to score
+        let current_day = now.day();
+        match year == now.year() {
+            false => 25,
+            true => {
+                // Ensure correct output from 26/12 to 31/12 of ongoing AOC event
+                if current_day <= 25 {
+                    current_day as i64
+                } else {
+                    25
+                }
+            }
         }
     }
 
@@ -420,9 +925,10 @@ impl Standing<'_> {
     }
 
     fn compute_combative_points(remaining_time: i32) -> usize {
+        let points = jersey_points();
         exponential_decay(
-            COMBATIVE_JERSEY_MAX_POINTS,
-            COMBATIVE_JERSEY_POINTS_DECAY_RATE,
+            points.combative_max_points,
+            points.combative_decay_rate,
             remaining_time,
         )
     }
@@ -432,13 +938,188 @@ impl Standing<'_> {
 /// TOTAL SCORE/STARS
 ////////////////////////////////////////////////
 
+/// Members ordered by position in the overall standings of the year.
+pub fn overall_positions(
+    score_type: &Scoring,
+    leaderboard: &Leaderboard,
+    year: i32,
+) -> Vec<Identifier> {
+    standings_board(score_type, leaderboard, year)
+        .into_iter()
+        .map(|(id, _scores, _total)| id.clone())
+        .collect()
+}
+
+/// Overtakes that happened in the `top` first positions between two snapshots of the overall
+/// standings, as (overtaker, overtaken, new position of the overtaker).
+/// For each member climbing up, only the best placed member that was overtaken is reported.
+pub fn overtakes_between(
+    previous: &[Identifier],
+    current: &[Identifier],
+    top: usize,
+) -> Vec<(Identifier, Identifier, u8)> {
+    let previous_positions = previous
+        .iter()
+        .enumerate()
+        .map(|(pos, id)| (id, pos))
+        .collect::<HashMap<&Identifier, usize>>();
+    let current_positions = current
+        .iter()
+        .enumerate()
+        .map(|(pos, id)| (id, pos))
+        .collect::<HashMap<&Identifier, usize>>();
+
+    current
+        .iter()
+        .take(top)
+        .enumerate()
+        .filter_map(|(pos, id)| {
+            // new members are not considered as overtaking anyone
+            let previous_pos = previous_positions.get(id)?;
+            previous
+                .iter()
+                .take(*previous_pos)
+                .find(|other| {
+                    current_positions
+                        .get(other)
+                        .is_some_and(|other_pos| *other_pos > pos)
+                })
+                .map(|overtaken| (id.clone(), overtaken.clone(), (pos + 1) as u8))
+        })
+        .collect()
+}
+
+// Completions made at least this long after the release of the puzzle are late, the daily
+// summary of the day being already posted.
+const LATE_ENTRY_MIN_HOURS: i64 = 24;
+
+/// Change of the rank of a member in the overall standings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankChange {
+    pub member: Identifier,
+    pub previous: usize,
+    pub current: usize,
+}
+
+/// Changes of the overall standings (local score) caused by the late completions of past days
+/// among the entries newly merged into the leaderboard, best new ranks first. Only the members
+/// already ranked before the merge are listed.
+pub fn late_entries_shuffle(
+    previous: &Leaderboard,
+    current: &Leaderboard,
+    year: i32,
+) -> Vec<RankChange> {
+    let late_entries = current
+        .iter()
+        .filter(|e| e.year == year && !previous.contains(e))
+        .filter(|e| {
+            e.duration_since_release()
+                .is_ok_and(|d| d >= Duration::hours(LATE_ENTRY_MIN_HOURS))
+        })
+        .cloned()
+        .collect::<Vec<Entry>>();
+    if late_entries.is_empty() {
+        return vec![];
+    };
+
+    let mut before = Leaderboard::new();
+    before.extend(previous.iter().filter(|e| e.year == year).cloned());
+    let mut after = Leaderboard::new();
+    after.extend(before.iter().cloned().chain(late_entries));
+
+    let previous_ranks = overall_ranks(&before, year);
+    overall_ranks(&after, year)
+        .into_iter()
+        .filter_map(|(member, current)| {
+            let previous = *previous_ranks.get(&member)?;
+            (previous != current).then_some(RankChange {
+                member,
+                previous,
+                current,
+            })
+        })
+        .sorted_by_key(|change| (change.current, change.member.name.clone()))
+        .collect()
+}
+
+/// First member to complete both parts of a day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageWinner {
+    pub member: Identifier,
+    pub day: u8,
+    // time to complete part 2, since the puzzle unlock
+    pub time: Duration,
+    pub delta: Duration,
+}
+
+/// Winner of the day, if the part 2 of the day was completed for the first time among the
+/// entries newly merged into the leaderboard.
+pub fn new_stage_winner(
+    previous: &Leaderboard,
+    current: &Leaderboard,
+    year: i32,
+    day: u8,
+) -> Option<StageWinner> {
+    let completed = |leaderboard: &Leaderboard| {
+        leaderboard
+            .iter()
+            .any(|e| e.year == year && e.day == day && e.part == ProblemPart::SECOND)
+    };
+    if completed(previous) || !completed(current) {
+        return None;
+    };
+
+    let standing = Standing::new(current);
+    let (member, time) = standing
+        .ranked_times_for_year_day(&Ranking::PART2, year, day)
+        .next()?;
+    let delta = standing
+        .ranked_times_for_year_day(&Ranking::DELTA, year, day)
+        .find_map(|(id, delta)| (id == member).then_some(delta))?;
+    Some(StageWinner {
+        member: member.clone(),
+        day,
+        time,
+        delta,
+    })
+}
+
+// Rank of the members in the overall standings (local score, then stars), tied members sharing
+// the same rank.
+fn overall_ranks(leaderboard: &Leaderboard, year: i32) -> HashMap<Identifier, usize> {
+    let totals = leaderboard
+        .daily_stars_and_scores_per_member_for_year(year)
+        .into_iter()
+        .map(|(id, scores)| {
+            let total = scores
+                .iter()
+                .fold((0, 0), |acc, s| (acc.0 + s.1, acc.1 + s.0 as usize));
+            (id.clone(), total)
+        })
+        .collect::<Vec<(Identifier, (usize, usize))>>();
+    totals
+        .iter()
+        .map(|(id, total)| {
+            let rank = 1 + totals.iter().filter(|(_id, other)| other > total).count();
+            (id.clone(), rank)
+        })
+        .collect()
+}
+
 /// ordered vec of (id, [(n_stars, daily score) for the 25 days], total_stars or total_score)
 pub fn standings_board<'a>(
     score_type: &Scoring,
     leaderboard: &'a Leaderboard,
     year: i32,
 ) -> Vec<(&'a Identifier, DailyStarsAndScores, usize)> {
-    let scores = leaderboard.daily_stars_and_scores_per_member_for_year(year);
+    let scores = match score_type {
+        Scoring::PERCENTILE => {
+            leaderboard.daily_stars_and_points_per_member_for_year(year, percentile_points)
+        }
+        Scoring::LOCAL | Scoring::STARS => {
+            leaderboard.daily_stars_and_scores_per_member_for_year(year)
+        }
+    };
     let entries = scores
         .into_iter()
         .map(|(id, scores)| {
@@ -454,16 +1135,731 @@ pub fn standings_board<'a>(
         })
         .sorted_unstable_by_key(|entry| match score_type {
             // sort by score descending, then by number of stars descending
-            Scoring::LOCAL => (Reverse(entry.2 .1), Reverse(entry.2 .0)),
+            Scoring::LOCAL | Scoring::PERCENTILE => (Reverse(entry.2 .1), Reverse(entry.2 .0)),
             // sort by number of stars descending, then by score descending
             Scoring::STARS => (Reverse(entry.2 .0), Reverse(entry.2 .1)),
         })
         .map(
             |(id, scores, (total_stars, total_score))| match score_type {
-                Scoring::LOCAL => (id, scores, total_score),
+                Scoring::LOCAL | Scoring::PERCENTILE => (id, scores, total_score),
                 Scoring::STARS => (id, scores, total_stars),
             },
         )
         .collect::<Vec<_>>();
     entries
 }
+
+/// Points of a star in the percentile scoring, by band of the share of the members who got it
+/// before (rank 0 based).
+pub fn percentile_points(rank_minus_one: usize, n_members: usize) -> usize {
+    let ahead = 100 * rank_minus_one / n_members.max(1);
+    PERCENTILE_BANDS
+        .iter()
+        .find(|(bound, _points)| ahead < *bound)
+        .map_or(0, |(_bound, points)| *points)
+}
+
+/// Median part 2 time and average delta of a member over the days of an event, the days without
+/// the time being left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SolveTimes {
+    pub median_p2: Option<Duration>,
+    pub average_delta: Option<Duration>,
+}
+
+/// Solve times of the members for the year, shown in the detailed board.
+pub fn solve_times(leaderboard: &Leaderboard, year: i32) -> HashMap<&Identifier, SolveTimes> {
+    leaderboard
+        .entries_per_day_member_for_year(year)
+        .into_iter()
+        .map(|((_day, id), entries_for_day)| {
+            let p2 = Standing::get_time_for_part(&entries_for_day, Ranking::PART2);
+            (id, (p2, Standing::compute_delta(&entries_for_day)))
+        })
+        .into_group_map()
+        .into_iter()
+        .map(|(id, times)| {
+            let aggregate = |aggregate: Aggregate, durations: Vec<Duration>| {
+                let seconds = durations.iter().map(|d| d.num_seconds()).collect_vec();
+                (!seconds.is_empty()).then(|| Duration::seconds(aggregate.aggregate(seconds)))
+            };
+            let solve_times = SolveTimes {
+                median_p2: aggregate(
+                    Aggregate::MEDIAN,
+                    times.iter().filter_map(|(p2, _delta)| *p2).collect(),
+                ),
+                average_delta: aggregate(
+                    Aggregate::AVERAGE,
+                    times.iter().filter_map(|(_p2, delta)| *delta).collect(),
+                ),
+            };
+            (id, solve_times)
+        })
+        .collect()
+}
+
+/// All-time standings over every cached year, as (id, total stars, total normalized score,
+/// number of events played). Scores are normalized per event (the best score of an event being
+/// worth 100 points), so the events with more members do not weigh more.
+pub fn all_time_standings<'a>(
+    score_type: &Scoring,
+    leaderboard: &'a Leaderboard,
+) -> Vec<(&'a Identifier, usize, usize, usize)> {
+    let yearly_scores = leaderboard
+        .daily_scores_per_year_member()
+        .into_iter()
+        .map(|((year, id), scores)| (year, id, scores.iter().sum::<usize>()))
+        .collect::<Vec<_>>();
+    let best_scores = yearly_scores
+        .iter()
+        .into_grouping_map_by(|(year, _id, _score)| *year)
+        .fold(0, |best, _year, (_, _, score)| best.max(*score));
+    let stars = leaderboard.ranked_entries().counts_by(|e| e.id.numeric);
+
+    yearly_scores
+        .into_iter()
+        .fold(HashMap::new(), |mut acc, (year, id, score)| {
+            let normalized = 100.0 * score as f64 / best_scores[&year].max(1) as f64;
+            // (identifier of the most recent event, normalized score, events played)
+            let member = acc.entry(id.numeric).or_insert((year, id, 0.0, 0));
+            if year > member.0 {
+                (member.0, member.1) = (year, id);
+            };
+            member.2 += normalized;
+            member.3 += 1;
+            acc
+        })
+        .into_iter()
+        .map(|(numeric, (_year, id, score, n_years))| {
+            (id, stars[&numeric], score.round() as usize, n_years)
+        })
+        .sorted_unstable_by_key(|(id, stars, score, _n_years)| match score_type {
+            // sort by score descending, then by number of stars descending
+            Scoring::LOCAL | Scoring::PERCENTILE => (Reverse(*score), Reverse(*stars), id.numeric),
+            // sort by number of stars descending, then by score descending
+            Scoring::STARS => (Reverse(*stars), Reverse(*score), id.numeric),
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Percentile rank of the value among the values, i.e. the percentage of the values lower than
+/// or equal to it, rounded up. None if there is no value.
+pub fn percentile_rank<T: PartialOrd>(value: &T, values: &[T]) -> Option<usize> {
+    let n_below = values.iter().filter(|v| *v <= value).count();
+    (!values.is_empty()).then(|| (100 * n_below).div_ceil(values.len()))
+}
+
+/// Delta times of the members who completed both parts of the day.
+pub fn deltas_for_year_day(leaderboard: &Leaderboard, year: i32, day: u8) -> Vec<Duration> {
+    leaderboard
+        .entries_per_member_for_year_day(year, day)
+        .into_values()
+        .filter(|entries| entries.len() == 2)
+        .map(|entries| {
+            let (first, last) = entries
+                .iter()
+                .map(|e| e.timestamp)
+                .minmax()
+                .into_option()
+                // safe unwrap since len == 2
+                .unwrap();
+            last - first
+        })
+        .collect()
+}
+
+/// Share (in percent) of the deltas of the day at least as fast as the delta, e.g. 10 for one of
+/// the 10% fastest deltas. None if too few members completed the day to be meaningful.
+pub fn delta_percentile(deltas: &[Duration], delta: &Duration) -> Option<usize> {
+    if deltas.len() < DELTA_PERCENTILE_MIN_MEMBERS {
+        return None;
+    };
+    percentile_rank(delta, deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::MemberId,
+        fixtures::{
+            edge_case_members, huge_board_members, private_leaderboard, random_board,
+            MemberFixture, RANDOM_BOARDS, YEAR,
+        },
+    };
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn board_is_sorted_by_score_then_stars() {
+        let leaderboard = private_leaderboard(YEAR, &huge_board_members(50));
+        let board = standings_board(&Scoring::LOCAL, &leaderboard, YEAR);
+
+        assert!(board.windows(2).all(|w| w[0].2 >= w[1].2));
+    }
+
+    #[test]
+    fn board_by_stars_counts_stars() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+        let board = standings_board(&Scoring::STARS, &leaderboard, YEAR);
+        let stars = board
+            .iter()
+            .map(|(id, _scores, total)| (id.numeric, *total))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            stars,
+            vec![(MemberId(1), 50), (MemberId(4), 49), (MemberId(2), 3)]
+        );
+    }
+
+    #[test]
+    fn delta_ranking_ignores_single_part_days() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+        let standing = Standing::new(&leaderboard);
+
+        let delta = standing.by_time(&Ranking::DELTA, YEAR, 25);
+        assert_eq!(delta, vec![("Santa".to_string(), "00:05:00".to_string())]);
+
+        let p1 = standing.by_time(&Ranking::PART1, YEAR, 25);
+        assert_eq!(p1.len(), 2);
+    }
+
+    #[test]
+    fn range_ranking_favors_members_completing_more_days() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1901, "Steady")
+                    .with_day(1, 100, 60)
+                    .with_day(2, 100, 120)
+                    .with_day(3, 100, 600),
+                MemberFixture::new(1902, "Sprinter")
+                    .with_day(1, 100, 10)
+                    .with_day(2, 100, 20)
+                    .with_part1(3, 100),
+                // outside of the range
+                MemberFixture::new(1903, "Late").with_day(4, 100, 1),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+
+        let average = standing.by_delta_over_days(&Aggregate::AVERAGE, YEAR, 1..=3);
+        assert_eq!(
+            average,
+            vec![
+                ("Steady".to_string(), "00:04:20".to_string(), 3),
+                ("Sprinter".to_string(), "00:00:15".to_string(), 2),
+            ]
+        );
+
+        let median = standing.by_delta_over_days(&Aggregate::MEDIAN, YEAR, 1..=3);
+        assert_eq!(median[0].1, "00:02:00");
+    }
+
+    #[test]
+    fn season_delta_ranks_the_members_with_enough_days_by_consistency() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1951, "Steady")
+                    .with_day(1, 100, 60)
+                    .with_day(2, 100, 120)
+                    .with_day(3, 100, 600),
+                MemberFixture::new(1952, "Sprinter")
+                    .with_day(1, 100, 10)
+                    .with_day(2, 100, 20),
+                // a single lucky day
+                MemberFixture::new(1953, "Lucky").with_day(4, 100, 1),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+
+        let average = standing.by_season_delta(&Aggregate::AVERAGE, YEAR, 2);
+        assert_eq!(
+            average,
+            vec![
+                ("Sprinter".to_string(), "00:00:15".to_string(), 2),
+                ("Steady".to_string(), "00:04:20".to_string(), 3),
+            ]
+        );
+
+        let median = standing.by_season_delta(&Aggregate::MEDIAN, YEAR, 1);
+        assert_eq!(median[0], ("Lucky".to_string(), "00:00:01".to_string(), 1));
+        assert!(standing
+            .by_season_delta(&Aggregate::MEDIAN, YEAR, 4)
+            .is_empty());
+    }
+
+    #[test]
+    fn wins_are_counted_per_ranking() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2001, "Early")
+                    .with_day(1, 10, 500)
+                    .with_day(2, 10, 500),
+                MemberFixture::new(2002, "Steady")
+                    .with_day(1, 100, 20)
+                    .with_day(2, 100, 20),
+                MemberFixture::new(2003, "Lazy").with_part1(3, 1000),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+        let wins = standing
+            .wins(YEAR)
+            .into_iter()
+            .map(|(id, p1, p2, delta, total)| (id.numeric, p1, p2, delta, total))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            wins,
+            vec![
+                (MemberId(2002), 0, 2, 2, 4),
+                (MemberId(2001), 2, 0, 0, 2),
+                (MemberId(2003), 1, 0, 0, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn yellow_jersey_penalizes_unfinished_days() {
+        let leaderboard = private_leaderboard(YEAR, &edge_case_members());
+        let standing = Standing::new(&leaderboard);
+        let yellow = standing.tdf_season(&Jersey::YELLOW, YEAR);
+
+        let (leader, _time, penalties) = yellow[0];
+        assert_eq!(leader.numeric, MemberId(1));
+        assert_eq!(penalties, 0);
+
+        let (_id, time, penalties) = yellow
+            .iter()
+            .find(|(id, _, _)| id.numeric == MemberId(4))
+            .unwrap();
+        assert_eq!(*penalties, 1);
+        assert_eq!(*time, 24 * 1800 + penalty_unfinished_day());
+    }
+
+    #[test]
+    fn yellow_jersey_can_drop_the_worst_days() {
+        let member = (1..=23).fold(MemberFixture::new(2601, "Sleigher"), |m, day| {
+            m.with_day(day, 600 + i64::from(day), 300)
+        });
+        let leaderboard = private_leaderboard(YEAR, &[member]);
+        let standing = Standing::new(&leaderboard);
+        let total = |drop_worst| standing.yellow_season(YEAR, drop_worst)[0].1;
+        let penalties = |drop_worst| standing.yellow_season(YEAR, drop_worst)[0].2;
+
+        let finished = (1..=23).map(|day| 900 + day).sum::<i64>();
+        assert_eq!(total(0), finished + 2 * penalty_unfinished_day());
+        assert_eq!(penalties(0), 2);
+        // the unfinished days are the worst ones, then the slowest finished days
+        assert_eq!(total(2), finished);
+        assert_eq!(penalties(2), 0);
+        assert_eq!(total(3), finished - (900 + 23));
+    }
+
+    #[test]
+    fn all_time_scores_are_normalized_per_event() {
+        // small event: Early wins, large event: Steady wins against many members
+        let mut leaderboard = private_leaderboard(
+            2016,
+            &[
+                MemberFixture::new(2301, "Early").with_day(1, 10, 10),
+                MemberFixture::new(2302, "Steady").with_day(1, 20, 10),
+            ],
+        );
+        let large_event = (2303..2310)
+            .map(|id| MemberFixture::new(id, "Crowd").with_part1(1, 100))
+            .chain([MemberFixture::new(2302, "Steady").with_day(1, 10, 10)])
+            .collect::<Vec<_>>();
+        leaderboard.extend(private_leaderboard(2017, &large_event).iter().cloned());
+
+        let by_score = all_time_standings(&Scoring::LOCAL, &leaderboard)
+            .into_iter()
+            .map(|(id, stars, score, n_years)| (id.numeric.0, stars, score, n_years))
+            .collect::<Vec<_>>();
+        assert_eq!(by_score[0], (2302, 4, 150, 2));
+        assert_eq!(by_score[1], (2301, 2, 100, 1));
+
+        let by_stars = all_time_standings(&Scoring::STARS, &leaderboard);
+        assert_eq!(by_stars[0].0.numeric, MemberId(2302));
+        assert_eq!(by_stars.len(), 9);
+    }
+
+    #[test]
+    fn declared_absences_are_neither_penalized_nor_rewarded() {
+        let on_holiday = (1..=25)
+            .filter(|day| ![10, 11].contains(day))
+            .fold(MemberFixture::new(2191, "Traveller"), |m, day| {
+                m.with_day(day, 600, 300)
+            })
+            .with_day(11, 600, 300);
+        // solved every day, a bit faster than the usual time of the traveller on day 10
+        let steady = (1..=25).fold(MemberFixture::new(2192, "Steady"), |m, day| match day {
+            10 => m.with_day(day, 600, 200),
+            _ => m.with_day(day, 600, 300),
+        });
+        let leaderboard = private_leaderboard(YEAR, &[on_holiday, steady]);
+        absences::register(HashMap::from([(
+            MemberId(2191),
+            BTreeSet::from([(YEAR, 10), (YEAR, 11), (YEAR - 1, 3)]),
+        )]));
+        let standing = Standing::new(&leaderboard);
+        let yellow = standing
+            .tdf_season(&Jersey::YELLOW, YEAR)
+            .into_iter()
+            .map(|(id, time, penalties)| (id.numeric, time, penalties))
+            .collect::<Vec<_>>();
+        absences::unregister(MemberId(2191));
+
+        // the absence day counts the median time of the traveller, without penalty
+        assert_eq!(
+            yellow,
+            vec![
+                (MemberId(2192), 24 * 900 + 800, 0),
+                (MemberId(2191), 25 * 900, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn yellow_stage_ranks_finishers_by_part2_time() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2101, "Climber").with_day(5, 100, 200),
+                MemberFixture::new(2102, "Sprinter").with_day(5, 50, 100),
+                MemberFixture::new(2103, "Dropout").with_part1(5, 10),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+        let stage = standing
+            .tdf_stage(YEAR, 5)
+            .into_iter()
+            .map(|(id, time)| (id.numeric, time.map(|t| t.num_seconds())))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            stage,
+            vec![
+                (MemberId(2102), Some(150)),
+                (MemberId(2101), Some(300)),
+                (MemberId(2103), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn green_jersey_points_follow_delta_ranks() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1, "Fast").with_day(1, 100, 10),
+                MemberFixture::new(2, "Slow").with_day(1, 50, 100),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+        let points = standing
+            .by_points(&Jersey::GREEN, YEAR, 1)
+            .into_iter()
+            .map(|(id, points)| (id.numeric, points))
+            .collect::<Vec<_>>();
+
+        assert_eq!(points, vec![(MemberId(1), 50), (MemberId(2), 30)]);
+    }
+
+    #[test]
+    fn custom_jerseys_aggregate_their_metric() {
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2201, "Steady")
+                    .with_day(1, 100, 60)
+                    .with_day(2, 100, 60),
+                MemberFixture::new(2202, "Sprinter")
+                    .with_day(1, 50, 30)
+                    .with_day(2, 50, 900),
+                MemberFixture::new(2203, "Dropout").with_part1(1, 10),
+            ],
+        );
+        let standing = Standing::new(&leaderboard);
+        let jersey = |aggregation: JerseyAggregation| CustomJersey {
+            name: "polka".to_string(),
+            metric: Ranking::DELTA,
+            aggregation,
+            points: vec![5, 2],
+            penalty_sec: 600,
+        };
+
+        let sum = standing
+            .tdf_season(&Jersey::CUSTOM(jersey(JerseyAggregation::SUM)), YEAR)
+            .into_iter()
+            .map(|(id, total, penalties)| (id.numeric, total, penalties))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sum,
+            vec![
+                (MemberId(2201), 120 + 23 * 600, 23),
+                (MemberId(2202), 30 + 24 * 600, 24),
+                (MemberId(2203), 25 * 600, 25),
+            ]
+        );
+
+        let points = standing
+            .tdf_season(&Jersey::CUSTOM(jersey(JerseyAggregation::POINTS)), YEAR)
+            .into_iter()
+            .map(|(id, points, n_days)| (id.numeric, points, n_days))
+            .collect::<Vec<_>>();
+        assert_eq!(points[0], (MemberId(2201), 7, 2));
+        assert_eq!(points[1], (MemberId(2202), 7, 2));
+    }
+
+    #[test]
+    fn overtakes_report_best_placed_overtaken_member() {
+        let id = |n: u64| Identifier {
+            name: format!("Elf #{n}"),
+            numeric: MemberId(n),
+        };
+        let previous = vec![id(1), id(2), id(3), id(4)];
+        let current = vec![id(3), id(1), id(2), id(4)];
+
+        let overtakes = overtakes_between(&previous, &current, 5);
+        assert_eq!(overtakes, vec![(id(3), id(1), 1)]);
+    }
+
+    #[test]
+    fn late_entries_shuffle_the_overall_standings() {
+        let previous = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2951, "Comet")
+                    .with_day(1, 600, 60)
+                    .with_day(2, 600, 60),
+                MemberFixture::new(2952, "Cupid").with_day(1, 900, 60),
+                MemberFixture::new(2953, "Donner").with_day(1, 1200, 60),
+            ],
+        );
+        let mut current = Leaderboard::new();
+        current.extend(previous.iter().cloned());
+        // Donner catches up on day 2 three days late, Cupid completes day 3 on time
+        current.extend(
+            private_leaderboard(
+                YEAR,
+                &[
+                    MemberFixture::new(2953, "Donner").with_day(2, 3 * 86400, 60),
+                    MemberFixture::new(2952, "Cupid").with_day(3, 600, 60),
+                ],
+            )
+            .iter()
+            .cloned(),
+        );
+
+        let shuffle = late_entries_shuffle(&previous, &current, YEAR)
+            .into_iter()
+            .map(|c| (c.member.numeric, c.previous, c.current))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shuffle,
+            vec![(MemberId(2953), 3, 2), (MemberId(2952), 2, 3)]
+        );
+
+        // no late entry, no shuffle
+        assert!(late_entries_shuffle(&current, &current, YEAR).is_empty());
+    }
+
+    #[test]
+    fn percentile_scoring_rewards_bands_of_ranks() {
+        assert_eq!(
+            (0..10)
+                .map(|rank| percentile_points(rank, 10))
+                .collect::<Vec<_>>(),
+            vec![10, 8, 8, 6, 6, 4, 4, 4, 2, 2]
+        );
+
+        // a few seconds separate the first two members, who get the same points
+        let leaderboard = private_leaderboard(
+            YEAR,
+            &(0..40)
+                .map(|n| MemberFixture::new(6101 + n, "Elf").with_part1(6, 600 + n as i64))
+                .collect::<Vec<_>>(),
+        );
+        let board = standings_board(&Scoring::PERCENTILE, &leaderboard, YEAR);
+        assert_eq!(board[0].2, 10);
+        assert_eq!(board[1].2, 10);
+        assert_eq!(board[39].2, 2);
+        assert_eq!(
+            Scoring::from_string("percentile"),
+            Some(Scoring::PERCENTILE)
+        );
+    }
+
+    #[test]
+    fn stage_winner_is_announced_once() {
+        let previous = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2961, "Blitzen").with_part1(4, 300),
+                MemberFixture::new(2962, "Vixen").with_part1(4, 200),
+            ],
+        );
+        let mut current = Leaderboard::new();
+        current.extend(
+            private_leaderboard(
+                YEAR,
+                &[
+                    MemberFixture::new(2961, "Blitzen").with_day(4, 300, 120),
+                    MemberFixture::new(2962, "Vixen").with_day(4, 200, 400),
+                ],
+            )
+            .iter()
+            .cloned(),
+        );
+
+        let winner = new_stage_winner(&previous, &current, YEAR, 4).unwrap();
+        assert_eq!(
+            (winner.member.numeric, winner.day, winner.time, winner.delta),
+            (
+                MemberId(2961),
+                4,
+                Duration::seconds(420),
+                Duration::seconds(120)
+            )
+        );
+
+        // part 2 already completed before the merge, or not completed yet
+        assert!(new_stage_winner(&current, &current, YEAR, 4).is_none());
+        assert!(new_stage_winner(&previous, &previous, YEAR, 4).is_none());
+    }
+
+    #[test]
+    fn deltas_are_ranked_among_the_deltas_of_the_day() {
+        let members = (1..=5)
+            .map(|n| MemberFixture::new(2900 + n, "Elf").with_day(1, 600, 60 * n as i64))
+            .chain(std::iter::once(
+                MemberFixture::new(2906, "Half Elf").with_part1(1, 300),
+            ))
+            .collect::<Vec<_>>();
+        let leaderboard = private_leaderboard(YEAR, &members);
+        let deltas = deltas_for_year_day(&leaderboard, YEAR, 1);
+
+        assert_eq!(deltas.len(), 5);
+        assert_eq!(delta_percentile(&deltas, &Duration::seconds(60)), Some(20));
+        assert_eq!(delta_percentile(&deltas, &Duration::seconds(180)), Some(60));
+        assert_eq!(delta_percentile(&deltas[..2], &Duration::seconds(60)), None);
+        assert_eq!(percentile_rank(&1, &[1, 2, 3]), Some(34));
+        assert_eq!(percentile_rank::<u8>(&1, &[]), None);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(RANDOM_BOARDS))]
+
+        #[test]
+        fn random_boards_jerseys_are_consistent(
+            members in random_board(15),
+            absent_days in prop::collection::vec(prop::collection::btree_set(1..=25u8, 0..4), 15),
+        ) {
+            let year = 2003;
+            let penalty = penalty_unfinished_day();
+            let absences = members
+                .iter()
+                .zip(absent_days)
+                .map(|(m, days)| (m.id, days))
+                .collect::<HashMap<u64, BTreeSet<u8>>>();
+            absences::register(
+                absences
+                    .iter()
+                    .map(|(id, days)| (MemberId(*id), days.iter().map(|d| (year, *d)).collect()))
+                    .collect(),
+            );
+
+            let leaderboard = private_leaderboard(year, &members);
+            let standing = Standing::new(&leaderboard);
+
+            // single part days are ranked without panicking
+            for jersey in [Jersey::YELLOW, Jersey::GREEN, Jersey::COMBATIVE] {
+                standing.tdf_season(&jersey, year);
+            }
+            let mut stages = vec![];
+            for day in 1..=25 {
+                for ranking in [
+                    Ranking::DELTA,
+                    Ranking::PART1,
+                    Ranking::PART2,
+                    Ranking::LIMIT,
+                ] {
+                    standing.by_time(&ranking, year, day);
+                }
+                stages.push(standing.tdf_stage(year, day));
+            }
+            let yellow = |drop_worst| {
+                standing
+                    .yellow_season(year, drop_worst)
+                    .into_iter()
+                    .map(|(id, time, over_cutoff)| (id.numeric, (time, over_cutoff)))
+                    .collect::<HashMap<MemberId, (i64, i64)>>()
+            };
+            let seasons = (0..4).map(yellow).collect::<Vec<_>>();
+            let sorted = standing.yellow_season(year, 0);
+            members.iter().for_each(|m| absences::unregister(MemberId(m.id)));
+
+            for stage in stages {
+                prop_assert!(stage
+                    .windows(2)
+                    .all(|w| w[0].1.is_some() || w[1].1.is_none()));
+            }
+
+            // the finished stages count their time, the unfinished ones the penalty, and the
+            // unfinished days of absence the median time of the finished stages
+            for member in members.iter() {
+                let absent = &absences[&member.id];
+                let finished = member
+                    .stars
+                    .iter()
+                    .filter(|(_, part, _)| *part == 2)
+                    .map(|(day, _, offset)| match (*offset < penalty, absent.contains(day)) {
+                        (true, _) => (*offset, 0),
+                        (false, true) => (penalty, 0),
+                        (false, false) => (2 * penalty, 1),
+                    })
+                    .collect::<Vec<(i64, i64)>>();
+                let finished_days = member
+                    .stars
+                    .iter()
+                    .filter(|(_, part, _)| *part == 2)
+                    .map(|(day, ..)| *day)
+                    .collect::<BTreeSet<u8>>();
+                let absent_unfinished = absent.difference(&finished_days).count() as i64;
+                let unfinished = 25 - finished.len() as i64 - absent_unfinished;
+                let mut times = finished
+                    .iter()
+                    .map(|(time, _)| (*time).min(penalty))
+                    .collect::<Vec<i64>>();
+                times.sort_unstable();
+                let median = times.get(times.len() / 2).copied().unwrap_or(penalty);
+
+                let expected = (
+                    finished.iter().map(|(time, _)| time).sum::<i64>()
+                        + absent_unfinished * median
+                        + unfinished * penalty,
+                    finished.iter().map(|(_, over)| over).sum::<i64>() + unfinished,
+                );
+                match seasons[0].get(&MemberId(member.id)) {
+                    Some(season) => prop_assert_eq!(*season, expected),
+                    // members without any finished stage are not ranked
+                    None => prop_assert!(finished.is_empty()),
+                }
+            }
+            prop_assert!(sorted.windows(2).all(|w| w[0].1 <= w[1].1));
+
+            // dropping more days never costs time nor days over the cutoff
+            for drop_worst in 0..3 {
+                let (kept, dropped) = (&seasons[drop_worst], &seasons[drop_worst + 1]);
+                for (id, (time, over_cutoff)) in dropped.iter() {
+                    let (kept_time, kept_over_cutoff) = kept[id];
+                    prop_assert!(*time <= kept_time && *over_cutoff <= kept_over_cutoff);
+                }
+            }
+        }
+    }
+}
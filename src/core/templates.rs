@@ -1,34 +1,186 @@
 use crate::{
-    core::leaderboard::Entry,
+    core::{commands, leaderboard::Entry, standings, theme},
     utils::{current_aoc_year_day, format_rank},
 };
 use chrono::{Duration, Utc};
-use minijinja::{Environment, Template};
-use once_cell::sync::Lazy;
+use minijinja::{Environment, Value};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, RwLock},
+    time::{Duration as StdDuration, Instant, SystemTime},
+};
 use strum::{EnumIter, IntoEnumIterator};
-use tracing::info;
+use tracing::{error, info};
+
+mod de;
+mod fr;
+
+// Language of the messages, registered once at startup before anything is rendered.
+static LANGUAGE: OnceCell<Language> = OnceCell::new();
+// Directory of the operator templates overriding the embedded ones, registered at startup.
+static TEMPLATES_DIR: OnceCell<PathBuf> = OnceCell::new();
+// Override file currently loaded and last time it was checked, per template name.
+static OVERRIDES: Lazy<Mutex<HashMap<&'static str, Override>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+// Minimum time between two checks of the override file of a template, so rendering a message
+// does not hit the file system each time.
+const OVERRIDE_CHECK_INTERVAL: StdDuration = StdDuration::from_secs(10);
 
-static TEMPLATES_ENVIRONMENT: Lazy<Environment> = Lazy::new(|| {
+static TEMPLATES_ENVIRONMENT: Lazy<RwLock<Environment<'static>>> = Lazy::new(|| {
     info!("Initializing templating engine environment.");
+    let env = environment();
+    info!("Templates loaded in templating engine environment.");
+    RwLock::new(env)
+});
+
+// Embedded templates only, to render the messages whose override fails to render.
+static EMBEDDED_ENVIRONMENT: Lazy<Environment<'static>> = Lazy::new(environment);
+
+struct Override {
+    // None if the template is not overridden
+    modified: Option<SystemTime>,
+    checked: Instant,
+}
+
+// Environment with the embedded templates and the templating engine globals.
+fn environment() -> Environment<'static> {
     let mut env = Environment::new();
 
+    // Emojis of the theme are available to every template.
+    env.add_global("theme", Value::from_serialize(theme::current()));
+    // Commands are written with the configured prefix.
+    env.add_global("command_prefix", commands::prefix());
+    // Help describes the configured jerseys points.
+    env.add_global(
+        "jersey_points",
+        Value::from_serialize(standings::jersey_points()),
+    );
+
     // Use strum to iterate over the variants of the enum.
     for template in MessageTemplate::iter() {
-        env.add_template(template.name(), template.template())
+        env.add_template(template.name(), template.localized())
             .unwrap();
     }
+    env
+}
 
-    info!("Templates loaded in templating engine environment.");
+/// Language of the embedded templates. The commands (names and arguments) are not translated.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, EnumIter)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Fr,
+    De,
+}
 
-    env
-});
+/// Use a language for all the messages. Only the first registered language is kept, the
+/// templates environment loading the templates of the language the first time a message is
+/// rendered.
+pub fn register_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
 
-#[derive(EnumIter)]
+/// Language of the messages, english if none was registered.
+pub fn language() -> Language {
+    *LANGUAGE.get_or_init(Language::default)
+}
+
+/// Register the directory of the templates overriding the embedded ones, loading the overrides.
+/// A file named after a template (e.g. `summary.txt`) is used instead of the embedded template,
+/// and reloaded when modified.
+pub fn register_dir(dir: &str) {
+    if TEMPLATES_DIR.set(PathBuf::from(dir)).is_err() {
+        error!("Templates directory already registered.");
+        return;
+    }
+    MessageTemplate::iter().for_each(|template| template.reload_override(Instant::now()));
+}
+
+/// Problems (unknown template, syntax error) of the override files of the directory.
+pub fn check_dir(dir: &Path) -> Vec<String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return vec![format!("'{}' {e}", dir.display())],
+    };
+    let names = MessageTemplate::iter()
+        .map(|t| t.name())
+        .collect::<Vec<&str>>();
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !names.contains(&name.as_str()) {
+                return Some(format!("'{name}' does not override any template"));
+            };
+            let source = fs::read_to_string(entry.path()).ok()?;
+            Environment::new()
+                .add_template_owned(name.clone(), source)
+                .err()
+                .map(|e| format!("'{name}' {e}"))
+        })
+        .collect()
+}
+
+// Render the template of the environment, or the one of the embedded environment if the template
+// is overridden and fails to render.
+fn render_with_fallback<S: Serialize>(
+    env: &Environment,
+    embedded: &Environment,
+    name: &str,
+    ctx: S,
+    is_overridden: bool,
+) -> Result<String, minijinja::Error> {
+    let ctx = Value::from_serialize(ctx);
+    match env.get_template(name)?.render(&ctx) {
+        Err(e) if is_overridden => {
+            error!("Could not render the {name} override, using the embedded template. {e}");
+            embedded.get_template(name)?.render(&ctx)
+        }
+        rendered => rendered,
+    }
+}
+
+/// Template of the environment, rendered with the templating engine globals.
+pub struct LoadedTemplate(&'static str);
+
+impl LoadedTemplate {
+    /// Render the template, falling back to the embedded template if its override fails to
+    /// render (e.g. with an unknown filter).
+    pub fn render<S: Serialize>(&self, ctx: S) -> Result<String, minijinja::Error> {
+        let is_overridden = {
+            let overrides = OVERRIDES.lock().unwrap();
+            overrides.get(self.0).is_some_and(|o| o.modified.is_some())
+        };
+        let env = TEMPLATES_ENVIRONMENT.read().unwrap();
+        render_with_fallback(&env, &EMBEDDED_ENVIRONMENT, self.0, ctx, is_overridden)
+    }
+
+    /// Source of the template, with the variables it expects in its context.
+    pub fn source_and_variables(&self) -> Result<(String, HashSet<String>), minijinja::Error> {
+        let env = TEMPLATES_ENVIRONMENT.read().unwrap();
+        let template = env.get_template(self.0)?;
+        Ok((
+            template.source().to_string(),
+            template.undeclared_variables(true),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, EnumIter)]
 pub enum MessageTemplate {
     Help,
     CustomMessage,
     DailyChallenge,
+    Countdown,
+    DifficultyPoll,
     DailySolutionThread,
+    DailySolutionThreadActivity,
     DailySummary,
     GlobalStatistics,
     HardChallenge,
@@ -37,9 +189,67 @@ pub enum MessageTemplate {
     NewEntriesToday,
     NewEntriesLate,
     TdfStandings,
+    TdfStage,
     Ranking,
+    RangeRanking,
+    SeasonDelta,
+    Wins,
+    AllTime,
+    Faq,
+    Link,
+    BestSolutions,
     LeaderboardDisplay,
     Hero,
+    Overtakes,
+    StandingsShuffle,
+    StageWinner,
+    FullCompletion,
+    NextEvent,
+    ReminderRegistration,
+    WatchRegistration,
+    MemberLinked,
+    Absence,
+    Exclusion,
+    YearInReview,
+    YearInReviewOptIn,
+    PersonalSummary,
+    PersonalSummaryOptIn,
+    OptOut,
+    WeeklyRecap,
+    DailySummaryEmail,
+    WeeklyRecapEmail,
+    AwardsCeremony,
+    Guess,
+    GuessResults,
+    Predictions,
+    WhatIf,
+    Jobs,
+    Missing,
+    Heroes,
+    AppHome,
+    Burndown,
+    Heatmap,
+    FullCompletions,
+    Gaps,
+    AuditLog,
+    Practice,
+    Splits,
+    Rank,
+    LanguagePoll,
+    Languages,
+    Latency,
+    Query,
+    Preview,
+    TemplatePreview,
+    CommandCooldown,
+    InvalidOption,
+    PersonalReminder,
+    WatchAlerts,
+    PrivateLeaderboardMembership,
+    SeasonChanged,
+    MonitoringAlert,
+    Heartbeat,
+    QuietHoursDigest,
 }
 
 impl MessageTemplate {
@@ -48,7 +258,10 @@ impl MessageTemplate {
             MessageTemplate::Help => "help.txt",
             MessageTemplate::CustomMessage => "custom.txt",
             MessageTemplate::DailyChallenge => "challenge.txt",
+            MessageTemplate::Countdown => "countdown.txt",
+            MessageTemplate::DifficultyPoll => "difficulty_poll.txt",
             MessageTemplate::DailySolutionThread => "solution_thread.txt",
+            MessageTemplate::DailySolutionThreadActivity => "solution_thread_activity.txt",
             MessageTemplate::DailySummary => "summary.txt",
             MessageTemplate::PrivateLeaderboardUpdated => "private_leaderboard_updated.txt",
             MessageTemplate::LeaderboardMemberJoin => "private_leaderboard_new_members.txt",
@@ -57,14 +270,132 @@ impl MessageTemplate {
             MessageTemplate::GlobalStatistics => "global_leaderboard_statistics.txt",
             MessageTemplate::HardChallenge => "hard_challenge.txt",
             MessageTemplate::Ranking => "ranking.txt",
+            MessageTemplate::RangeRanking => "range_ranking.txt",
+            MessageTemplate::SeasonDelta => "season_delta.txt",
+            MessageTemplate::Wins => "wins.txt",
+            MessageTemplate::AllTime => "alltime.txt",
+            MessageTemplate::Faq => "faq.txt",
+            MessageTemplate::Link => "link.txt",
+            MessageTemplate::BestSolutions => "best_solutions.txt",
             MessageTemplate::TdfStandings => "tdf.txt",
+            MessageTemplate::TdfStage => "tdf_stage.txt",
             MessageTemplate::LeaderboardDisplay => "leaderboard.txt",
             MessageTemplate::Hero => "hero.txt",
+            MessageTemplate::Overtakes => "overtakes.txt",
+            MessageTemplate::StandingsShuffle => "standings_shuffle.txt",
+            MessageTemplate::StageWinner => "stage_winner.txt",
+            MessageTemplate::FullCompletion => "full_completion.txt",
+            MessageTemplate::NextEvent => "next_event.txt",
+            MessageTemplate::ReminderRegistration => "reminder_registration.txt",
+            MessageTemplate::WatchRegistration => "watch_registration.txt",
+            MessageTemplate::MemberLinked => "member_linked.txt",
+            MessageTemplate::Absence => "absence.txt",
+            MessageTemplate::Exclusion => "exclusion.txt",
+            MessageTemplate::YearInReview => "year_in_review.txt",
+            MessageTemplate::YearInReviewOptIn => "year_in_review_opt_in.txt",
+            MessageTemplate::PersonalSummary => "personal_summary.txt",
+            MessageTemplate::PersonalSummaryOptIn => "personal_summary_opt_in.txt",
+            MessageTemplate::OptOut => "opt_out.txt",
+            MessageTemplate::WeeklyRecap => "weekly_recap.txt",
+            MessageTemplate::DailySummaryEmail => "summary.html",
+            MessageTemplate::WeeklyRecapEmail => "weekly_recap.html",
+            MessageTemplate::AwardsCeremony => "awards_ceremony.txt",
+            MessageTemplate::Guess => "guess.txt",
+            MessageTemplate::GuessResults => "guess_results.txt",
+            MessageTemplate::Predictions => "predictions.txt",
+            MessageTemplate::WhatIf => "what_if.txt",
+            MessageTemplate::Jobs => "jobs.txt",
+            MessageTemplate::Missing => "missing.txt",
+            MessageTemplate::Heroes => "heroes.txt",
+            MessageTemplate::AppHome => "app_home.txt",
+            MessageTemplate::Burndown => "burndown.txt",
+            MessageTemplate::Heatmap => "heatmap.txt",
+            MessageTemplate::FullCompletions => "full_completions.txt",
+            MessageTemplate::Gaps => "gaps.txt",
+            MessageTemplate::AuditLog => "audit_log.txt",
+            MessageTemplate::Practice => "practice.txt",
+            MessageTemplate::Splits => "splits.txt",
+            MessageTemplate::Rank => "rank.txt",
+            MessageTemplate::LanguagePoll => "language_poll.txt",
+            MessageTemplate::Languages => "languages.txt",
+            MessageTemplate::Latency => "latency.txt",
+            MessageTemplate::Query => "query.txt",
+            MessageTemplate::Preview => "preview.txt",
+            MessageTemplate::TemplatePreview => "template_preview.txt",
+            MessageTemplate::CommandCooldown => "command_cooldown.txt",
+            MessageTemplate::InvalidOption => "invalid_option.txt",
+            MessageTemplate::PersonalReminder => "personal_reminder.txt",
+            MessageTemplate::WatchAlerts => "watch_alerts.txt",
+            MessageTemplate::PrivateLeaderboardMembership => "private_leaderboard_membership.txt",
+            MessageTemplate::SeasonChanged => "season_changed.txt",
+            MessageTemplate::MonitoringAlert => "monitoring_alert.txt",
+            MessageTemplate::Heartbeat => "heartbeat.txt",
+            MessageTemplate::QuietHoursDigest => "quiet_hours_digest.txt",
         }
     }
 
-    pub fn get(&self) -> Template<'_, '_> {
-        TEMPLATES_ENVIRONMENT.get_template(self.name()).unwrap()
+    pub fn get(&self) -> LoadedTemplate {
+        self.reload_override(Instant::now());
+        LoadedTemplate(self.name())
+    }
+
+    // Load the override file of the template if it changed since it was last checked, falling
+    // back to the embedded template if the file was removed or is not valid.
+    fn reload_override(&self, now: Instant) {
+        let Some(dir) = TEMPLATES_DIR.get() else {
+            return;
+        };
+        let name = self.name();
+        let mut overrides = OVERRIDES.lock().unwrap();
+        if overrides
+            .get(name)
+            .is_some_and(|o| now.duration_since(o.checked) < OVERRIDE_CHECK_INTERVAL)
+        {
+            return;
+        };
+        let path = dir.join(name);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let loaded = overrides.insert(
+            name,
+            Override {
+                modified,
+                checked: now,
+            },
+        );
+        if loaded.and_then(|o| o.modified) == modified {
+            return;
+        };
+        let mut env = TEMPLATES_ENVIRONMENT.write().unwrap();
+        let loaded = modified
+            .and_then(|_| fs::read_to_string(&path).ok())
+            .map(|source| env.add_template_owned(name, source));
+        match loaded {
+            Some(Ok(())) => info!("Template {name} loaded from {}.", path.display()),
+            Some(Err(e)) => {
+                error!(
+                    "Could not load template {}, using the embedded one. {e}",
+                    path.display()
+                );
+                env.add_template(name, self.localized()).unwrap();
+            }
+            None => {
+                info!("Template {name} is not overridden anymore, using the embedded one.");
+                env.add_template(name, self.localized()).unwrap();
+            }
+        };
+    }
+
+    // Embedded template in the registered language.
+    fn localized(&self) -> &'static str {
+        self.translation(language())
+    }
+
+    fn translation(&self, language: Language) -> &'static str {
+        match language {
+            Language::En => self.template(),
+            Language::Fr => fr::template(self),
+            Language::De => de::template(self),
+        }
     }
 
     pub fn template(&self) -> &'static str {
@@ -77,33 +408,162 @@ impl MessageTemplate {
                 Note that the command arguments parsing system is a marvel of regex engineering, and as such \
                 the order of the optional arguments passed to a command does not (or at least should not...) matter.\n\n\
                 👉 🆘 *How to*\n\
-                ```!help```\n\
+                ```{{ command_prefix }}help```\n\
                 List and explains the bot commands. You're currently reading this.\n\n\
                 👉 🏎️ *Fastest of the West!*\n\
-                ```!fast [ranking method] [day] [year]```\n\
+                ```{{ command_prefix }}fast [ranking method] [day] [year]```\n\
                 Fastest time(s) for the day. By default, the ranking is based on the `delta` time for the day, \
                 but individual `p1` and `p2` rankings are also available. Note that you can also access the \
                 ranking of the closest finishes before cuttoff (i.e.: the least amount of time before the next puzzle release) \
-                with the `limit` method (those times are used to attribute points for the `!tdf combative` jersey). \
-                If no day and/or year is set, the current day/or year is automatically defined.`\n\n\
+                with the `limit` method (those times are used to attribute points for the `{{ command_prefix }}tdf combative` jersey). \
+                If no day and/or year is set, the current day/or year is automatically defined.`\n\
+                ```{{ command_prefix }}fast <avg | median> [first day-last day] [year]```\n\
+                Best `avg` (average) or `median` delta time over a range of days (e.g. `{{ command_prefix }}fast avg 1-10`), the whole event by default. \
+                Only the days with both parts completed are counted, members having completed more days of the range being ranked first.\n\n\
+                👉 ⚖️ *Steady hands*\n\
+                ```{{ command_prefix }}delta [avg | median] [minimum days] [year]```\n\
+                Most consistent two-part solvers of the event: members ranked by their `avg` (default) or `median` delta time over \
+                all the days with both parts completed, only the members having completed at least the minimum number of days \
+                (5 by default) being ranked.\n\n\
                 👉 📊 *Show me the board!*\n\
-                ```!board [ranking method] [year]```\n\
+                ```{{ command_prefix }}board [ranking method] [detailed] [year]```\n\
                 Current score and stars completion for the year, shown as a neat ascii board. Default is ranking by `local` \
-                score for the current year, but ranking by number of `stars` is also available.`\n\n\
+                score for the current year, but ranking by number of `stars` is also available, as well as by `percentile` score \
+                (10, 8, 6, 4 or 2 points per star depending on the share of the members who got it first: top 10%, 25%, 50%, 75% \
+                or the others), less punishing than the local score on large leaderboards. The `detailed` board adds the \
+                median part 2 time and the average delta of each member. Large boards are paginated, \
+                use the buttons below the board to navigate between pages or to select another year.`\n\n\
                 👉 🚴 *The long haul!*\n\
-                ```!tdf [jersey color] [day] [year]```\n\
+                ```{{ command_prefix }}tdf [jersey color] [day] [year]```\n\
                 Tour de France alternative standings! Come join the peloton and compete to earn `yellow` jersey credentials, \
                 or accumulate points for the coveted `green` or `combative` jerseys. \
                 Default is ranking for the Yellow jersey for the current year.\n\
                 - `yellow` jersey ranking is based on the accumulated time for the full (part 2) solve each day (a penalty of \
-                7 days is applied for every day not fully solved, or any day taking longer to solve than the penalty time).\n\
-                - `green` jersey points are earned each day by going full blast between part 1 and part 2 ! The points attributed are \
-                based on the official Tour de France green jersey points.\n\
+                `{{ jersey_points.yellow_penalty }}` is applied for every day not fully solved, or any day taking longer to solve than the penalty time). \
+                Add `dropworst=<N>` (e.g. `{{ command_prefix }}tdf yellow dropworst=3`) to leave out the N worst days of each member. \
+                Admins can declare the days a member is away (e.g. on holiday) with `{{ command_prefix }}tdf absence <name or id> <day | first day-last day> [year]`, \
+                those days counting the member's median time instead of a penalty if not solved. \
+                Set a day to see the results of that stage, ranked on the part 2 completion time.\n\
+                - `green` jersey points are earned each day by going full blast between part 1 and part 2 ! The \
+                {{ jersey_points.green_points|length }} fastest members earn {{ jersey_points.green_points|join(', ') }} points.\n\
                 - `combative` jersey points are attributed each day to the brave soul showing grit by not throwing the towel too early and keeping \
-                their focus on finishing a day before the next one starts ... The closer to the cutoff, the more points earned !"
+                their focus on finishing a day before the next one starts ... The closer to the cutoff, the more points earned \
+                (up to {{ jersey_points.combative_max_points|int }}) !\n\
+                {% if jersey_points.custom %}- custom jerseys of this leaderboard: {% for custom in jersey_points.custom %}`{{ custom.name }}` ({{ custom.metric }} {{ 'points' if custom.aggregation == 'points' else 'time' }}){{ ', ' if not loop.last }}{% endfor %}.\n{% endif %}\n\
+                👉 🏅 *Who rules the days?*\n\
+                ```{{ command_prefix }}wins [year]```\n\
+                Number of days each member won, i.e. was the fastest on part 1 (`p1`), on part 2 (`p2`) or on the `delta` time. \
+                If no year is set, the current year is automatically defined.\n\n\
+                👉 🏛️ *Hall of legends*\n\
+                ```{{ command_prefix }}alltime [score | stars]```\n\
+                All-time standings over all the events known to the bot, ranked by `score` (default) or by number of `stars`. \
+                The scores are normalized per event (the best score of each event being worth 100 points), so the events \
+                with more members do not weigh more.\n\n\
+                👉 📚 *Frequently asked questions*\n\
+                ```{{ command_prefix }}faq [topic]```\n\
+                Answers to the questions about this event (prizes, rules, ...). Lists the available topics if no topic is set.\n\n\
+                👉 🔗 *Where is it?*\n\
+                ```{{ command_prefix }}link [day] [year]```\n\
+                Links to the puzzle, the private leaderboard and the global leaderboard of the day. \
+                If no day and/or year is set, the current day/or year is automatically defined.\n\n\
+                👉 🗳️ *Hall of fame*\n\
+                ```{{ command_prefix }}bestsolutions [day] [year]```\n\
+                Most upvoted replies of the daily solutions thread, based on the reactions they received. \
+                If no day and/or year is set, the current day/or year is automatically defined.\n\n\
+                👉 ⏰ *Don't let me forget!*\n\
+                ```{{ command_prefix }}remindme <name or id | off>```\n\
+                Get a direct message each evening of the event listing the days you still have stars to collect. \
+                Use your AoC name (or numeric id) as shown on the leaderboard, or `off` to stop the reminders.\n\n\
+                👉 👀 *Keep an eye on them*\n\
+                ```{{ command_prefix }}watch [off] <name or id>```\n\
+                Get a direct message whenever the member collects a star, or overtakes you in the standings if you are linked \
+                to a member with `{{ command_prefix }}iam`. Use `off` to stop watching the member, or `off` alone to stop watching everyone.\n\n\
+                👉 🪪 *Who am I?*\n\
+                ```{{ command_prefix }}iam <name or id>```\n\
+                Tell me which leaderboard member you are, so I can mention you when you earn stars or make it \
+                to the global leaderboard. A member already linked to another user can not be claimed.\n\n\
+                👉 🎁 *That's a wrap!*\n\
+                ```{{ command_prefix }}review <on | off>```\n\
+                Once the event is over, members linked with `{{ command_prefix }}iam` receive a personal year in review (if enabled on this bot). \
+                Use `off` to opt out, or `on` to opt back in.\n\n\
+                👉 📬 *How did I do?*\n\
+                ```{{ command_prefix }}mysummary <on | off>```\n\
+                Get a direct message after each daily summary with your own times, points earned and rank change \
+                of the day (if enabled on this bot). You need to be linked to a member with `{{ command_prefix }}iam` first.\n\n\
+                👉 🥷 *Incognito*\n\
+                ```{{ command_prefix }}optout <on | off>```\n\
+                Appear as an anonymous elf in the messages instead of your name, your stars still counting in the standings. \
+                You need to be linked to a member with `{{ command_prefix }}iam` first.\n\n\
+                👉 🔮 *Crystal ball*\n\
+                ```{{ command_prefix }}guess [mm:ss | year]```\n\
+                Guess the time of the 100th part 2 finisher of the global leaderboard of the next puzzle, before it unlocks. \
+                The closest guess is announced with the global leaderboard statistics. Without a time, shows the prediction game \
+                standings of the event (number of days won, then average error).\n\n\
+                👉 🧮 *What if...*\n\
+                ```{{ command_prefix }}whatif <name or id> completes day <day> [year]```\n\
+                Projected points, position and jerseys standings if the member completed the missing parts of the day right now. \
+                Nothing like seeing the places to gain to get back to a puzzle!\n\n\
+                👉 🏁 *Where do I stand?*\n\
+                ```{{ command_prefix }}rank <name or id> [year=<year>]```\n\
+                Position, score and stars of a single member, with the points to catch up on the member ahead, the margin \
+                over the member behind and the jerseys positions. If no year is set, the current year is used.\n\n\
+                👉 🐢 *Who is missing?*\n\
+                ```{{ command_prefix }}missing [day] [year]```\n\
+                Members without any star on the day, the ones who usually play first, so they can get a friendly nudge. \
+                Without day and/or year, the current day and/or year are used.\n\n\
+                👉 🦸 *Global heroes*\n\
+                ```{{ command_prefix }}heroes```\n\
+                All-time appearances of our members on the global leaderboards recorded by the bot, with their best rank.\n\n\
+                👉 📉 *Burn-down*\n\
+                ```{{ command_prefix }}burndown [day] [year]```\n\
+                Stars left to collect hour by hour over the first 24 hours after the puzzle release. \
+                Without day and/or year, the current day and/or year are used.\n\n\
+                👉 🌡️ *Heatmap*\n\
+                ```{{ command_prefix }}heatmap [hours] [year]```\n\
+                Stars collected on each day of the event by the members with the most stars, or with `hours` during each hour \
+                following the puzzle release, to spot the days that broke the leaderboard. If no year is set, the current year is used.\n\n\
+                👉 🌟 *Hall of fame*\n\
+                ```{{ command_prefix }}fifty [year]```\n\
+                Members who collected all the 50 stars of the event, in the order they completed it, with the time they took \
+                after the last puzzle unlocked. If no year is set, the current year is used.\n\n\
+                👉 ⏱️ *Time gaps*\n\
+                ```{{ command_prefix }}gap [yellow | local] [year]```\n\
+                Gap between each member and the one ahead (and the leader), on the yellow jersey total time or on the local score, \
+                to see how close the race is at each position. If no year is set, the current year is used.\n\n\
+                👉 🎯 *Practice makes perfect*\n\
+                ```{{ command_prefix }}practice [year]```\n\
+                A random past puzzle you have not completed yet, with its link and how hard it was on the global leaderboard. \
+                You need to be linked to a member with `{{ command_prefix }}iam` first. Without year, all the recorded events are used.\n\n\
+                👉 ⚡ *Negative splits*\n\
+                ```{{ command_prefix }}splits [year]```\n\
+                Members who solved the part 2 of a day faster than its part 1 (days started more than 24 hours late are not counted), \
+                with their number of negative splits over the event. If no year is set, the current year is used.\n\n\
+                👉 🗳️ *Language of the season*\n\
+                ```{{ command_prefix }}languages [year]```\n\
+                Languages used over the event, from the votes of the poll posted every day in the solutions thread. \
+                If no year is set, the current year is used.\n\n\
+                👉 🔎 *Power users*\n\
+                ```{{ command_prefix }}query [key=value ...]```\n\
+                Stars of the leaderboard matching the filters, e.g. `{{ command_prefix }}query day=7 part=2 sort=time limit=10`. \
+                Filters: `year`, `day`, `part` (1 or 2), `member` (name or id), `sort` (`time`, `date` or `name`, `-` first for a \
+                descending order) and `limit` (10 by default, at most 50). If no year is set, the current year is used.\n\n\
+                👉 ⚙️ *Under the hood*\n\
+                ```{{ command_prefix }}jobs```\n\
+                Last runs, durations and errors of the scheduled jobs of the bot (admins only).\n\
+                ```{{ command_prefix }}latency [year]```\n\
+                Average delay between the completion of the stars and their detection by the bot, to check the refresh of the \
+                private leaderboard keeps up. If no year is set, the current year is used.\n\
+                ```{{ command_prefix }}auditlog [n]```\n\
+                Last `n` messages posted by the bot (10 by default, at most 50), to track down missing or duplicated messages (admins only).\n\
+                ```{{ command_prefix }}exclude [off] <name or id>```\n\
+                Leave a member (e.g. a bot or a duplicate account) out of the standings, still listed at the bottom of the board, \
+                or count them again with `off` (admins only).\n\
+                ```{{ command_prefix }}preview <template> [sample values]```\n\
+                Render a template (e.g. `summary`) with sample values, posted in the monitoring channel to check its customization \
+                without waiting for the event. Sample values are given as a JSON object, e.g. `{\"year\": 2023}` (admins only)."
             },
             MessageTemplate::CustomMessage => {
-                "🙅 {{message}}"
+                "{{emoji or '🙅'}} {{message}}"
             },
             MessageTemplate::HardChallenge => {
                 "😱 *{{minutes}} minutes* went by already and there are still some spots to grab in the global leaderboard ...\n\
@@ -119,15 +579,41 @@ impl MessageTemplate {
             },
             MessageTemplate::DailyChallenge => {
                 "```{{header}}```\n\
-                🎉 Today's challenge is up! (<{{url}}|link>)\n\
+                {{theme.celebration}} Today's challenge is up! (<{{url}}|link>)\n\
                     \x20 *{{title}}*\n\
                 🔫 Go after it and get some fun, ⏱️ time is ticking !"
             },
+            MessageTemplate::Countdown => {
+                "{%- if minutes >= 60 -%}
+                    ⏳ *{{minutes // 60}} hour{{ 's' if minutes >= 120 }}* before day {{day}} unlocks! Time to grab a coffee ☕ and warm up the keyboard.
+                {%- elif minutes > 1 -%}
+                    ⌛ *{{minutes}} minutes* before day {{day}} unlocks! Editors open, inputs folders ready? 🧑‍💻
+                {%- else -%}
+                    🚨 *1 minute* before day {{day}} unlocks! Fingers on the keyboard... 🚀
+                {%- endif %}"
+            },
+            MessageTemplate::DifficultyPoll => {
+                "🌡️ How hard did the *day {{day}}* puzzle feel? Rate it with the reactions below, \
+                from :one: (a walk in the park) to :five: (my brain melted)."
+            },
             MessageTemplate::DailySolutionThread => {
                 "👇 *Daily discussion thread for day {{day}}*\n\
                     \x20   Refrain yourself to open until you complete part 2!\n\
                  🚨 *Spoilers Ahead* :rotating_light:"
             },
+            MessageTemplate::DailySolutionThreadActivity => {
+                "{%- if n_replies == 0 -%}
+                    🦗 Nobody shared anything in the *day {{day}}* discussion thread today... Don't be shy, the thread is still open!
+                {%- else -%}
+                    💬 The *day {{day}}* discussion thread gathered *{{n_replies}}* repl{{ 'ies' if n_replies > 1 else 'y' }} today!
+                    {%- if languages %}\n\
+                        \x20 • Languages spotted: {% for (language, count) in languages %}{{language}} ({{count}}){{ ', ' if not loop.last }}{% endfor %}
+                    {%- endif %}
+                    {%- if most_reacted %}\n\
+                        \x20 • Most reacted message (<{{most_reacted[0]}}|link>) with *{{most_reacted[1]}}* reaction{{ 's' if most_reacted[1] > 1 }}
+                    {%- endif %}
+                {%- endif %}"
+            },
             MessageTemplate::DailySummary => {
                 "🗓️ *December, {{day}} {{year}}*\n\
                 ----- 🥁 *Daily update* 🗞️ -----\n\
@@ -146,25 +632,70 @@ impl MessageTemplate {
                 Top 5 *DELTA* 🏁\n\
                 {%- for (prefix, name, time) in ranking_delta %}\n\
                     {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
-                {%- endfor %}"
+                {%- endfor %}
+                {%- if negative_splits %}\n\
+                    ___________________________________________________________________\n\
+                    ⚡ *Negative splits* (part 2 faster than part 1)
+                    {%- for (name, part1, delta) in negative_splits %}\n\
+                        \x20 • *{{name}}*: part 1 in {{part1}}, part 2 in {{delta}}
+                    {%- endfor %}
+                {%- endif %}
+                {%- if difficulty %}\n\
+                    ___________________________________________________________________\n\
+                    🌡️ *Community difficulty*: {{difficulty.average}}/5 ({{difficulty.n_votes}} vote{{ 's' if difficulty.n_votes > 1 }})
+                {%- endif %}"
             },
             MessageTemplate::PrivateLeaderboardUpdated => {
                 "🔁 Private Leaderboard successfully updated!"
             },
+            MessageTemplate::PrivateLeaderboardMembership => {
+                "👥 *Private leaderboard*: {{ count }}/{{ cap }} members
+                {%- if trend is not none %} ({{ '+' if trend >= 0 }}{{ trend }} over the last 7 days){% endif %}
+                {%- if joined %}\n\
+                    \x20 ➕ Joined: {{ joined|join(', ') }}
+                {%- endif %}
+                {%- if left %}\n\
+                    \x20 ➖ Left: {{ left|join(', ') }}
+                {%- endif %}
+                {%- if spots_left is not none %}\n\
+                    ⚠️ {% if spots_left == 0 %}The private leaderboard is full, nobody else can join until members leave.{% else %}Only {{ spots_left }} spot{{ 's' if spots_left != 1 }} left before the cap of {{ cap }} members.{% endif %}
+                {%- endif %}"
+            },
+            MessageTemplate::SeasonChanged => {
+                "{% if is_event -%}
+                    🎄 The window of the {{ year }} event is open: the private leaderboard is scraped again and the scheduled jobs are back.
+                {%- else -%}
+                    💤 Off-season until the {{ year }} event: AoC is not scraped anymore, the commands are answered from the cached leaderboard.
+                {%- endif %}"
+            },
+            MessageTemplate::MonitoringAlert => {
+                "🚨 `{{code}}` {{context}}\n```{{error}}```"
+            },
+            MessageTemplate::Heartbeat => {
+                "💓 Cache {{ cache_age }} old · {{ n_members }} member{{ 's' if n_members != 1 }} · \
+                {%- if scrape_sec is none %} no scrape yet{% else %} last scrape {{ scrape_sec }}s{{ ' (failed)' if scrape_failed }}{% endif %} · \
+                {%- if failing_jobs %} ⚠️ failing job{{ 's' if failing_jobs|length > 1 }}: {{ failing_jobs|join(', ') }}{% else %} {{ n_jobs }} job{{ 's' if n_jobs != 1 }} healthy{% endif %}"
+            },
+            MessageTemplate::QuietHoursDigest => {
+                "🌅 Good morning! Here is what happened during the quiet hours:
+                {%- for message in messages %}\n\n{{ message }}{% endfor %}"
+            },
             MessageTemplate::LeaderboardMemberJoin => {
                 "{%- for name in members %}\n\
                     🕺 A new player has joined the christmas arena ! Happy to have you on board *{{name}}* !
                  {%- endfor %}"
             },
             MessageTemplate::NewEntriesToday => {
-                "{%- for entry in completions %}\n\
-                    {% with both = entry.parts_duration|length > 1, double = '⭐⭐', single = '⭐' %}\
-                    📣 {{entry.name}} just earned *{{entry.n_stars}}* more star{{ 's' if entry.n_stars > 1 }} for day {{entry.day}} ({{[double, '*<->', entry.delta, '*']|join(' ') if both else single}}) +{{entry.new_points}}pts
+                "🗓️ *Day {{day}}*\
+                {%- for entry in completions %}\n\
+                    {% with both = entry.parts_duration|length > 1, double = theme.star ~ theme.star, single = theme.star %}\
+                    {{theme.announcement}} {{entry.name}} just earned *{{entry.n_stars}}* more star{{ 's' if entry.n_stars > 1 }} for day {{entry.day}} ({{[double, '*<->', entry.delta, '*']|join(' ') if both else single}}{{ ', top ' ~ entry.delta_percentile ~ '% of the day' if both and entry.delta_percentile }}) +{{entry.new_points}}pts
                     {%- endwith %}
-                 {%- endfor %}\n"
+                 {%- endfor %}"
             },
             MessageTemplate::NewEntriesLate => {
-                "{%- for entry in completions %}\n\
+                "🗓️ *Day {{day}}*{{ ' of the ' ~ year ~ ' event' if not current_year }} (catching up)\
+                {%- for entry in completions %}\n\
                     {% with both = entry.parts_duration|length > 1, double = '🤩', single = '✔️' %}\
                     🚂  {{entry.name}} just caught up on *{{entry.n_stars}}* more star{{ 's' if entry.n_stars > 1 }} for day {{entry.day}} ({{ [double, 'both parts completed!', '*<->', entry.delta, '*']|join(' ')  if both else single }}) +{{entry.new_points}}pts
                     {%- endwith %}
@@ -174,7 +705,13 @@ impl MessageTemplate {
                 "🌍 Global Leaderboard is complete for *day {{day}}*! Here is how it went for the big dogs:\n\
                     \x20 • Part 1 finish time range: 🔥 *{{p1_fast}}* - *{{p1_slow}}* ❄️\n\
                     \x20 • Part 2 finish time range: 🔥 *{{p2_fast}}* - *{{p2_slow}}* ❄️\n\
-                    \x20 • Delta times range: 🏃‍♀️ {{delta_fast}} - {{delta_slow}} 🚶‍♀️"
+                    \x20 • Delta times range: 🏃‍♀️ {{delta_fast}} - {{delta_slow}} 🚶‍♀️
+                {%- if difficulty %}\n\
+                    \x20 • Difficulty index: *{{difficulty}}* (100th part 2 finish time vs. the median of the {{n_days}} previous days)
+                {%- endif %}
+                {%- if is_among_hardest %}\n\
+                    🥵 Today was a *top-10% hardest day*, well done to everyone who made it through!
+                {%- endif %}"
             }
             MessageTemplate::Ranking => {
                 "{%- if current_day -%}
@@ -186,27 +723,579 @@ impl MessageTemplate {
                     {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
                 {%- endfor %}"
             }
+            MessageTemplate::RangeRanking => {
+                "Best *{{ 'average' if aggregate == 'avg' else 'median' }} delta time* for days {{ first }}-{{ last }} of the {{ year }} event (as of {{timestamp}}):\n\
+                {%- for (prefix, name, time, n_days) in ranking %}\n\
+                    {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*{{ ' (' ~ n_days ~ '/' ~ n_total ~ ' days)' if n_days < n_total }}
+                {%- else %}\n\
+                    Nobody completed both parts of any of these days yet.
+                {%- endfor %}"
+            }
+            MessageTemplate::SeasonDelta => {
+                "⚖️ Most consistent two-part solvers of the {{ year }} event, by *{{ 'average' if aggregate == 'avg' else 'median' }} delta time* \
+                over at least {{ min_days }} day{{ 's' if min_days > 1 }} (as of {{timestamp}}):\n\
+                {%- for (prefix, name, time, n_days) in ranking %}\n\
+                    {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}* ({{ n_days }} day{{ 's' if n_days > 1 }})
+                {%- else %}\n\
+                    Nobody completed both parts of {{ min_days }} day{{ 's' if min_days > 1 }} yet.
+                {%- endfor %}"
+            }
             MessageTemplate::Hero => {
-                "🎉 🥳 Our very own *{{ name }}* made it to the global leaderboard on part *{{ part }}*! (*{{ rank }}*) 🙌"
+                "{{ theme.celebration }} 🥳 Our very own *{{ name }}* made it to the global leaderboard on part *{{ part }}*! (*{{ rank }}*) 🙌"
+            },
+            MessageTemplate::Overtakes => {
+                "{%- for (overtaker, overtaken, position) in overtakes %}\n\
+                    🏎️ *{{ overtaker }}* overtakes *{{ overtaken }}* for *{{ position }}* place!
+                 {%- endfor %}"
+            },
+            MessageTemplate::StandingsShuffle => {
+                "🔀 *Standings shuffle* in the {{ year }} event: late completions of past days changed the overall standings of the daily summaries
+                {%- for (name, previous, current, is_up) in changes %}\n\
+                    \x20 • *{{ name }}*: {{ previous }} → *{{ current }}* {{ '⬆️' if is_up else '⬇️' }}
+                {%- endfor %}"
+            },
+            MessageTemplate::StageWinner => {
+                "🏁 *Stage {{ day }} winner*: *{{ name }}* is the first to collect both stars of the day, in {{ time }} (delta {{ delta }})! 🏆"
+            },
+            MessageTemplate::FullCompletion => {
+                "🌟 *{{ name }}* collected all the 50 stars of {{ year }}, {{ time }} after the last puzzle unlocked! \
+                {%- if first %} The first of us to complete the event! 🏆{% else %} {{ rank }} member to complete the event. 🎉{% endif %}"
+            },
+            MessageTemplate::NextEvent => {
+                "📣 *Advent of Code {{ year }} is coming!* Join our private leaderboard with the code `{{ join_code }}` on {{ url }} and get ready for the 1st of December! 🎄"
+            },
+            MessageTemplate::ReminderRegistration => {
+                "{%- if member -%}
+                    ⏰ Noted! I will send you a direct message each evening with the days *{{ member }}* still has stars to collect.
+                {%- else -%}
+                    🔕 Alright, no more reminders for you. Enjoy the peace and quiet!
+                {%- endif %}"
+            },
+            MessageTemplate::WatchRegistration => {
+                "{%- if watching -%}
+                    👀 Noted! I will send you a direct message whenever *{{ member }}* collects a star or overtakes you in the standings.
+                {%- elif member -%}
+                    🙈 Alright, no more news about *{{ member }}*.
+                {%- else -%}
+                    🙈 Alright, no more news about the members you watch.
+                {%- endif %}"
+            },
+            MessageTemplate::MemberLinked => {
+                "🤝 Nice to meet you *{{ member }}*! I will make sure to mention you when you shine."
+            },
+            MessageTemplate::Absence => {
+                "🏖️ Enjoy the break *{{ member }}*! \
+                {% if days|length == 1 %}Day *{{ days|first }}*{% else %}Days *{{ days|first }}* to *{{ days|last }}*{% endif %} \
+                of the {{ year }} event will not be penalized for the 🟡 yellow jersey."
+            },
+            MessageTemplate::Exclusion => {
+                "{% if excluded -%}
+                    🚫 *{{ member }}* is now left out of the standings, and only listed at the bottom of the board.
+                {%- else -%}
+                    ✅ *{{ member }}* is counted in the standings again.
+                {%- endif %}"
+            },
+            MessageTemplate::YearInReview => {
+                "🎁 *{{ review.year }} in review* for *{{ review.name }}*\n\
+                The {{ review.year }} event is over, here is how it went for you:\n\
+                    \x20 • {{ theme.star }} *{{ review.n_stars }}* stars collected, for a total of *{{ review.score }}* points\n\
+                    \x20 • 🏁 Final position: *{{ rank }}* out of {{ review.n_members }}
+                {%- if review.best_day %}\n\
+                    \x20 • 🚀 Best day: *day {{ review.best_day[0] }}*, with *{{ review.best_day[1] }}* points earned
+                {%- endif %}
+                {%- if review.average_delta %}\n\
+                    \x20 • ⏱️ Average delta time: *{{ review.average_delta }}*
+                {%- endif %}\n\
+                    \x20 • 📈 Rank evolution: `{{ review.rank_chart }}`
+                {%- if review.badges %}\n\
+                    Badges earned:
+                    {%- for badge in review.badges %}\n\
+                        \x20 {{ badge }}
+                    {%- endfor %}
+                {%- endif %}\n\
+                See you next year! {{ theme.season }}"
+            },
+            MessageTemplate::YearInReviewOptIn => {
+                "{%- if opt_in -%}
+                    🎁 Great, you will receive your personal year in review once the event is over!
+                {%- else -%}
+                    🙈 No problem, your year in review will stay secret, even to you.
+                {%- endif %}"
+            },
+            MessageTemplate::PersonalSummary => {
+                "📬 *Day {{ review.day }}* of {{ review.year }} for *{{ review.name }}*\n\
+                {%- if review.p1_time %}\n\
+                    \x20 • {{ theme.star }} Part 1 in *{{ review.p1_time }}*
+                {%- endif %}
+                {%- if review.p2_time %}\n\
+                    \x20 • {{ theme.star }}{{ theme.star }} Part 2 in *{{ review.p2_time }}*
+                {%- endif %}
+                {%- if review.delta %}\n\
+                    \x20 • ⏱️ Delta time: *{{ review.delta }}*
+                {%- endif %}\n\
+                    \x20 • 🎯 *{{ review.points }}* points earned today\n\
+                    \x20 • 🏁 Position: *{{ rank }}* out of {{ review.n_members }}
+                {%- if previous_rank %}
+                {%- if review.rank < review.previous_rank %} (📈 up from {{ previous_rank }})
+                {%- elif review.rank > review.previous_rank %} (📉 down from {{ previous_rank }})
+                {%- else %} (unchanged)
+                {%- endif %}
+                {%- endif %}"
+            },
+            MessageTemplate::PersonalSummaryOptIn => {
+                "{%- if opt_in -%}
+                    📬 Deal! You will receive your own results of the day after each daily summary.
+                {%- else -%}
+                    🔕 Got it, no more personal daily summaries for you.
+                {%- endif %}"
+            },
+            MessageTemplate::OptOut => {
+                "{%- if opt_out -%}
+                    🥷 Understood! Your stars still count, but you will only appear as an *anonymous elf* in the messages.
+                {%- else -%}
+                    📣 Welcome back to the spotlight! You will be named in the messages again.
+                {%- endif %}"
+            },
+            MessageTemplate::WeeklyRecap => {
+                "🗞️ *Weekly recap* of the {{ recap.year }} event, here is what happened over the last 7 days:\n\
+                {{ theme.star }} Stars collected:
+                {%- for (name, n_stars) in recap.stars %}\n\
+                    \x20 • *{{ name }}*: {{ n_stars }} star{{ 's' if n_stars > 1 }}
+                {%- endfor %}
+                {%- if recap.climbs %}\n\
+                    📈 Biggest climbs in the overall standings:
+                    {%- for (name, previous, current) in recap.climbs %}\n\
+                        \x20 • *{{ name }}*: from position {{ previous }} to position {{ current }}
+                    {%- endfor %}
+                {%- endif %}
+                {%- if recap.jerseys %}\n\
+                    🚴 Jerseys:
+                    {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                        \x20 • {{ jersey|capitalize }} jersey: *{{ holder }}*
+                        {%- if previous and previous != holder %} (taken from {{ previous }})
+                        {%- elif previous %} (kept)
+                        {%- endif %}
+                    {%- endfor %}
+                {%- endif %}
+                {%- if recap.fastest_delta %}\n\
+                    ⚡ Fastest delta of the week: *{{ recap.fastest_delta[0] }}* on day {{ recap.fastest_delta[1] }}, in *{{ recap.fastest_delta[2] }}*
+                {%- endif %}"
+            },
+            MessageTemplate::DailySummaryEmail => {
+                "<!DOCTYPE html>\n\
+                <html>\n\
+                <head><meta charset='utf-8'><title>December, {{day}} {{year}} - Daily update</title></head>\n\
+                <body style='font-family: sans-serif;'>\n\
+                <h2>🥁 Daily update of December, {{day}} {{year}}</h2>\n\
+                <p>Here is how things went down at the front of the pack today:</p>\n\
+                {%- for (heading, ranking) in [('Top 5 to finish part 1', ranking_p1), ('Top 5 to finish part 2', ranking_p2), ('Top 5 delta', ranking_delta)] %}\n\
+                    <h3>{{heading}} 🏁</h3>\n\
+                    <table>\n\
+                    {%- for (prefix, name, time) in ranking %}\n\
+                        <tr><td>{{prefix}}</td><td style='font-family: monospace;'>{{time|trim}}</td><td><b>{{name}}</b></td></tr>\n\
+                    {%- else %}\n\
+                        <tr><td>Nobody yet</td></tr>\n\
+                    {%- endfor %}\n\
+                    </table>\n\
+                {%- endfor %}\n\
+                {%- if negative_splits %}\n\
+                    <h3>⚡ Negative splits (part 2 faster than part 1)</h3>\n\
+                    <ul>\n\
+                    {%- for (name, part1, delta) in negative_splits %}\n\
+                        <li><b>{{name}}</b>: part 1 in {{part1}}, part 2 in {{delta}}</li>\n\
+                    {%- endfor %}\n\
+                    </ul>\n\
+                {%- endif %}\n\
+                </body>\n\
+                </html>"
+            },
+            MessageTemplate::WeeklyRecapEmail => {
+                "<!DOCTYPE html>\n\
+                <html>\n\
+                <head><meta charset='utf-8'><title>Weekly recap of the {{ recap.year }} event</title></head>\n\
+                <body style='font-family: sans-serif;'>\n\
+                <h2>🗞️ Weekly recap of the {{ recap.year }} event</h2>\n\
+                <p>Here is what happened over the last 7 days.</p>\n\
+                <h3>⭐ Stars collected</h3>\n\
+                <ul>\n\
+                {%- for (name, n_stars) in recap.stars %}\n\
+                    <li><b>{{ name }}</b>: {{ n_stars }} star{{ 's' if n_stars > 1 }}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+                {%- if recap.climbs %}\n\
+                    <h3>📈 Biggest climbs in the overall standings</h3>\n\
+                    <ul>\n\
+                    {%- for (name, previous, current) in recap.climbs %}\n\
+                        <li><b>{{ name }}</b>: from position {{ previous }} to position {{ current }}</li>\n\
+                    {%- endfor %}\n\
+                    </ul>\n\
+                {%- endif %}\n\
+                {%- if recap.jerseys %}\n\
+                    <h3>🚴 Jerseys</h3>\n\
+                    <ul>\n\
+                    {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                        <li>{{ jersey|capitalize }} jersey: <b>{{ holder }}</b>\n\
+                        {%- if previous and previous != holder %} (taken from {{ previous }})\n\
+                        {%- elif previous %} (kept)\n\
+                        {%- endif %}</li>\n\
+                    {%- endfor %}\n\
+                    </ul>\n\
+                {%- endif %}\n\
+                {%- if recap.fastest_delta %}\n\
+                    <p>⚡ Fastest delta of the week: <b>{{ recap.fastest_delta[0] }}</b> on day {{ recap.fastest_delta[1] }}, in <b>{{ recap.fastest_delta[2] }}</b></p>\n\
+                {%- endif %}\n\
+                </body>\n\
+                </html>"
+            },
+            MessageTemplate::AwardsCeremony => {
+                "🏆 *Awards ceremony* of the {{ awards.year }} event! The curtain falls on the puzzles, time to celebrate the champions of the season:
+                {%- if awards.jerseys %}\n\
+                    🚴 Jerseys:
+                    {%- for (jersey, winner) in awards.jerseys %}\n\
+                        \x20 • {{ jersey|capitalize }} jersey: *{{ winner }}*
+                    {%- endfor %}
+                {%- endif %}
+                {%- if awards.most_combative %}\n\
+                    🥋 Most combative: *{{ awards.most_combative[0] }}*, with {{ awards.most_combative[1] }} points earned right before the next unlocks
+                {%- endif %}\n\
+                {{ theme.star }} Most stars:
+                {%- for (name, n_stars) in awards.most_stars %}\n\
+                    \x20 {{ ['🥇', '🥈', '🥉'][loop.index0] }} *{{ name }}*: {{ n_stars }} star{{ 's' if n_stars > 1 }}
+                {%- endfor %}
+                {%- if awards.best_delta %}\n\
+                    ⚡ Best delta of the season: *{{ awards.best_delta[0] }}* on day {{ awards.best_delta[1] }}, in *{{ awards.best_delta[2] }}*
+                {%- endif %}
+                {%- if awards.comeback %}\n\
+                    📈 Biggest comeback: *{{ awards.comeback[0] }}*, from position {{ awards.comeback[1] }} to position {{ awards.comeback[2] }} in the overall standings
+                {%- endif %}\n\
+                Thank you all for playing, see you next December 🎄!"
+            },
+            MessageTemplate::Guess => {
+                "🔮 Guess registered! You bet on *{{ guess }}* for the 100th part 2 finisher of the global leaderboard \
+                of *day {{ day }}*. You can change your mind until the puzzle unlocks."
+            },
+            MessageTemplate::GuessResults => {
+                "🔮 The 100th part 2 finisher of the global leaderboard of *day {{ day }}* made it in *{{ cutoff }}*. \
+                Closest guess{{ 'es' if winners|length > 1 }} out of {{ n_guesses }}:
+                {%- for (user, guess, error) in winners %}\n\
+                    \x20 • {{ user }} with *{{ guess }}* ({{ error }} off)
+                {%- endfor %}"
+            },
+            MessageTemplate::Predictions => {
+                "{%- if standings -%}
+                    🔮 Prediction game standings of the {{ year }} event:
+                    {%- for (user, wins, n_guesses, average_error) in standings %}\n\
+                        \x20 {{ loop.index }}. {{ user }}: *{{ wins }}* win{{ 's' if wins != 1 }} \
+                        out of {{ n_guesses }} guess{{ 'es' if n_guesses > 1 }}, {{ average_error }} off on average
+                    {%- endfor %}
+                {%- else -%}
+                    🤷 Nobody played the prediction game of the {{ year }} event yet. \
+                    Guess the time of the 100th part 2 finisher of the global leaderboard with `{{ command_prefix }}guess <mm:ss>`!
+                {%- endif %}"
+            },
+            MessageTemplate::WhatIf => {
+                "🧮 What if *{{ projection.name }}* completed day {{ projection.day }} of the {{ projection.year }} event right now?\n\
+                    \x20 • {{ theme.star }} *{{ projection.n_stars }}* more star{{ 's' if projection.n_stars > 1 }}, worth *{{ projection.points }}* points\n\
+                    \x20 • 🏁 Position: {% if projection.rank[0] == projection.rank[1] %}*{{ new_rank }}* (unchanged){% else %}from {{ rank }} to *{{ new_rank }}*{% endif %}
+                {%- if projection.overtaken %}, overtaking {{ projection.overtaken|join(', ') }}{% endif %}
+                {%- for (jersey, before, after) in jerseys %}{% if after %}\n\
+                    \x20 • 🚴 {{ jersey|capitalize }} jersey: {% if before and before != after %}from {{ before }} to *{{ after }}*{% elif before %}*{{ after }}* (unchanged){% else %}*{{ after }}*{% endif %}
+                {%- endif %}{% endfor %}"
+            },
+            MessageTemplate::Jobs => {
+                "⚙️ *Scheduler jobs*
+                {%- for job in jobs %}\n\
+                    \x20 • {% if job.running_since %}⏳{% elif job.succeeded %}✅{% else %}❌{% endif %} `{{ job.name }}`: \
+                    {{ job.runs }} run{{ 's' if job.runs != 1 }} ({{ job.failed_runs }} failed)
+                    {%- if job.last_run_at %}, last one at {{ job.last_run_at }} in {{ job.last_run_ms }}ms{% endif %}
+                    {%- if job.running_since %}, running since {{ job.running_since }}{% endif %}
+                    {%- if job.last_error %}\n\
+                        \x20     ⚠️ Last error at {{ job.last_error_at }}: `{{ job.last_error }}`
+                    {%- endif %}
+                {%- else %}\n\
+                    💤 No job ran since the bot started.
+                {%- endfor %}"
+            },
+            MessageTemplate::Missing => {
+                "{%- if members -%}
+                    🐢 *{{ members|length }}* member{{ 's' if members|length > 1 }} without any star on day {{ day }}{% if not current_year %} of {{ year }}{% endif %}, \
+                    the usual players first:
+                    {%- for (name, n_days) in members %}\n\
+                        \x20 • {{ name }}{% if day > 1 %} ({{ n_days }}/{{ day - 1 }} previous day{{ 's' if day > 2 }} played){% endif %}
+                    {%- endfor %}
+                {%- else -%}
+                    🎉 Everybody collected at least one star on day {{ day }}{% if not current_year %} of {{ year }}{% endif %}!
+                {%- endif %}"
+            },
+            MessageTemplate::Heroes => {
+                "{%- if heroes -%}
+                    🦸 All-time global leaderboard appearances of our members, \
+                    over the {{ n_scanned }} global leaderboard{{ 's' if n_scanned != 1 }} recorded:
+                    {%- for hero in heroes %}\n\
+                        \x20 {{ loop.index }}. *{{ hero.name }}*: *{{ hero.appearances }}* appearance{{ 's' if hero.appearances > 1 }} \
+                        in {{ hero.years|length }} event{{ 's' if hero.years|length > 1 }}, \
+                        best rank *{{ hero.rank }}* (part {{ hero.part }} of day {{ hero.day }} {{ hero.year }})
+                    {%- endfor %}
+                {%- else -%}
+                    🦸 None of our members appeared on the {{ n_scanned }} global leaderboard{{ 's' if n_scanned != 1 }} recorded yet.
+                {%- endif %}"
+            },
+            MessageTemplate::AppHome => {
+                "🎄 *Advent of Code {{ year }}*\n\
+                {%- if review %}\n\
+                    Your event so far, *{{ review.name }}*:\n\
+                    \x20 • {{ theme.star }} *{{ review.n_stars }}* stars collected, for a total of *{{ review.score }}* points\n\
+                    \x20 • 🏁 Position: *{{ rank }}* out of {{ review.n_members }}
+                    {%- if review.best_day %}\n\
+                        \x20 • 🚀 Best day: *day {{ review.best_day[0] }}*, with *{{ review.best_day[1] }}* points earned
+                    {%- endif %}
+                    {%- if review.average_delta %}\n\
+                        \x20 • ⏱️ Average delta time: *{{ review.average_delta }}*
+                    {%- endif %}\n\
+                    \x20 • 📈 Rank evolution: `{{ review.rank_chart }}`
+                {%- elif linked %}\n\
+                    You did not collect any star during the {{ year }} event yet, the puzzles are waiting for you! {{ theme.star }}
+                {%- else %}\n\
+                    🔗 Link yourself to a member of the leaderboard with `{{ command_prefix }}iam <name>` to see your own stats here.
+                {%- endif %}\n\n\
+                🚴 *Jerseys*
+                {%- for (jersey, leaders) in jerseys %}\n\
+                    \x20 • {{ jersey|capitalize }} jersey: {% for (name, value) in leaders %}{{ loop.index }}. *{{ name }}* ({{ value }}){{ ', ' if not loop.last }}{% else %}nobody yet{% endfor %}
+                {%- endfor %}"
+            },
+            MessageTemplate::Burndown => {
+                "{%- if chart -%}
+                    📉 Stars left to collect on day {{ day }}{% if not current_year %} of {{ year }}{% endif %}, \
+                    hour by hour after the puzzle release:\n\
+                    ```{{ chart }}```
+                {%- else -%}
+                    🤷 Nobody collected any star within 24 hours of the release of day {{ day }}{% if not current_year %} of {{ year }}{% endif %}.
+                {%- endif %}"
+            },
+            MessageTemplate::Heatmap => {
+                "{%- if chart -%}
+                    🌡️ Stars collected on each day of the {{ year }} event, \
+                    {%- if hours %} per hour after the puzzle release
+                    {%- elif n_members > max_members %} by the {{ max_members }} members with the most stars
+                    {%- else %} per member{% endif %} (darker is busier):\n\
+                    ```{{ chart }}```
+                {%- else -%}
+                    🤷 Nobody collected any star in the {{ year }} event yet.
+                {%- endif %}"
+            },
+            MessageTemplate::FullCompletions => {
+                "{%- if completions -%}
+                    🌟 *Hall of fame {{ year }}*, the members who collected all the 50 stars:
+                    {%- for c in completions %}\n\
+                        \x20 {{ loop.index }}. *{{ c.name }}*, {{ c.time }} after the last unlock ({{ c.date }})
+                    {%- endfor %}
+                {%- else -%}
+                    🤷 Nobody collected all the 50 stars of {{ year }} yet.
+                {%- endif %}"
+            },
+            MessageTemplate::Gaps => {
+                "{%- if gaps -%}
+                    ⏱️ *Gaps of the {{ 'yellow jersey' if yellow else 'local score' }} {{ year }}*, to the member ahead (and to the leader):\n\
+                    ```{{ gaps }}```
+                    {%- if closest %}\n\
+                        🔥 Closest fight: *{{ closest.behind }}* is \
+                        {%- if yellow %} {{ closest.gap }}{% else %} {{ closest.points }} point{{ 's' if closest.points != 1 }}{% endif %} behind *{{ closest.ahead }}*.
+                    {%- endif %}
+                {%- else -%}
+                    🤷 Nobody is in the race of {{ year }} yet.
+                {%- endif %}"
+            },
+            MessageTemplate::AuditLog => {
+                "📜 *Last {{ messages|length }} message{{ 's' if messages|length != 1 }} posted*, most recent first
+                {%- for m in messages %}\n\
+                    \x20 • {{ '✅' if m.ts else ('🖼️' if not m.error else '❌') }} {{ m.at }} `{{ m.event }}` in <#{{ m.channel }}>{% if m.ts %} (ts {{ m.ts }}){% endif %}: `{{ m.excerpt }}`
+                    {%- if m.error %}\n\
+                        \x20     ⚠️ `{{ m.error }}`
+                    {%- endif %}
+                {%- else %}\n\
+                    📭 No message posted since the audit log was started.
+                {%- endfor %}"
+            },
+            MessageTemplate::Practice => {
+                "{%- if day -%}
+                    🎯 How about *day {{ day }} of {{ puzzle_year }}*? {{ 'Both stars are' if missing_stars == 2 else 'The second star is' }} still waiting for you: {{ url }}
+                    {%- if difficulty %}\n\
+                        \x20 • Difficulty index: *{{ difficulty }}*, harder than {{ percentile }}% of the {{ n_days }} other days recorded on the global leaderboard
+                    {%- endif %}
+                {%- else -%}
+                    🏆 You completed all the puzzles{{ ' of ' ~ year if year }} released so far, nothing left to practice on!
+                {%- endif %}"
+            },
+            MessageTemplate::Splits => {
+                "⚡ *Negative splits* of the {{ year }} event, part 2 solved faster than part 1
+                {%- for (name, n_splits, n_days) in members %}\n\
+                    {{ loop.index }}. *{{ name }}*: {{ n_splits }} negative split{{ 's' if n_splits > 1 }} out of {{ n_days }} completed day{{ 's' if n_days > 1 }}
+                {%- else %}\n\
+                    Nobody solved a part 2 faster than its part 1 yet.
+                {%- endfor %}"
+            },
+            MessageTemplate::Rank => {
+                "🏁 *{{ rank.name }}* in the {{ rank.year }} event: *{{ position }}* out of {{ rank.n_members }}, \
+                with *{{ rank.score }}* points and {{ rank.n_stars }} {{ theme.star }}
+                {%- if rank.ahead %}\n\
+                    \x20 • ⬆️ {{ rank.ahead[1] }} point{{ 's' if rank.ahead[1] != 1 }} behind *{{ rank.ahead[0] }}*
+                {%- endif %}
+                {%- if rank.behind %}\n\
+                    \x20 • ⬇️ {{ rank.behind[1] }} point{{ 's' if rank.behind[1] != 1 }} ahead of *{{ rank.behind[0] }}*
+                {%- endif %}
+                {%- for (jersey, position) in jerseys %}{% if position %}\n\
+                    \x20 • 🚴 {{ jersey|capitalize }} jersey: *{{ position }}*
+                {%- endif %}{% endfor %}"
+            },
+            MessageTemplate::LanguagePoll => {
+                "🗳️ Which language did you use today? Vote with the reactions below:
+                {%- for (reaction, language) in languages %} {{ reaction }} {{ language }}{{ ',' if not loop.last }}{% endfor %}"
+            },
+            MessageTemplate::Languages => {
+                "🗳️ *Languages* of the {{ year }} event, from the daily polls of the solutions threads
+                {%- for (language, votes, n_days) in languages %}\n\
+                    {{ loop.index }}. *{{ language }}*: {{ votes }} vote{{ 's' if votes > 1 }} over {{ n_days }} day{{ 's' if n_days > 1 }}
+                {%- else %}\n\
+                    Nobody voted in the polls yet.
+                {%- endfor %}"
+            },
+            MessageTemplate::Latency => {
+                "{%- if latency -%}
+                    ⏱️ *{{ latency.n_stars }}* star{{ 's' if latency.n_stars > 1 }} of the {{ year }} event detected by the bot, on average *{{ latency.average }}* after their completion (median {{ latency.median }}, at most {{ latency.max }}).\n\
+                    {{ latency.within_refresh }}% of them were detected within {{ latency.refresh_min }} minutes, the interval between two refreshes of the private leaderboard.
+                {%- else -%}
+                    ⏱️ No star of the {{ year }} event was detected by the bot yet.
+                {%- endif %}"
+            },
+            MessageTemplate::Query => {
+                "🔎 *{{ result.n_matches }}* star{{ 's' if result.n_matches != 1 }} of the {{ result.year }} event\
+                {%- if result.query %} matching `{{ result.query }}`{% endif %}\
+                {%- if result.rows|length < result.n_matches %} (first {{ result.rows|length }} listed){% endif %}{{ ':' if result.rows }}
+                {%- for row in result.rows %}\n\
+                    \x20 {{ loop.index }}. *{{ row.name }}*: day {{ row.day }} part {{ row.part }} in {{ row.time }}
+                {%- endfor %}"
+            },
+            MessageTemplate::Preview => {
+                "🧪 Preview of `{{ template }}` posted in the monitoring channel\
+                {%- if failed %}, it could not be rendered though ⚠️{% endif %}."
+            },
+            MessageTemplate::TemplatePreview => {
+                "{%- if error -%}
+                    ⚠️ `{{ template }}` could not be rendered with the sample values:\n```{{ error }}```
+                {%- else -%}
+                    🧪 Preview of `{{ template }}` with sample values:\n{{ rendered }}
+                {%- endif %}"
+            },
+            MessageTemplate::CommandCooldown => {
+                "⏳ Easy there! `{{ command_prefix }}{{ command }}` was asked a lot in the last minute, please try again in {{ wait_sec }} second{{ 's' if wait_sec != 1 }} 🙏."
+            },
+            MessageTemplate::InvalidOption => {
+                "🤔 `{{ option }}` is not an option of `{{ command_prefix }}{{ command }}`\
+                {%- if suggestion %}, did you mean `{{ suggestion }}`?{% else %}.{% endif %} \
+                The valid options are {% for value in valid %}`{{ value }}`{{ ', ' if not loop.last }}{% endfor %}."
+            },
+            MessageTemplate::PersonalReminder => {
+                "⏰ Friendly reminder from your favorite elf, there are still stars waiting for you in the {{ year }} event:\n\
+                {%- for (day, n_missing) in missing %}\n\
+                    \x20 • Day {{ day }}: {{ n_missing }} star{{ 's' if n_missing > 1 }} to collect
+                {%- endfor %}"
+            },
+            MessageTemplate::WatchAlerts => {
+                "👀 News from the members you watch in the {{ year }} event:\n\
+                {%- for (name, day, is_first, position) in alerts %}\n\
+                    \x20 • {% if position %}🏎️ *{{ name }}* overtook you in the standings, now {{ position }}\
+                    {%- else %}⭐ *{{ name }}* collected the {{ 'first' if is_first else 'second' }} star of day {{ day }}{% endif %}
+                {%- endfor %}"
             },
             MessageTemplate::LeaderboardDisplay => {
                 "{%- if current_year -%}
-                    📓 Current Leaderboard by {{ '*local score*' if scoring_method == 'local' else '*number of stars*' }} as of {{timestamp}}:
+                    📓 Current Leaderboard by {% if scoring_method == 'local' %}*local score*{% elif scoring_method == 'percentile' %}*percentile score*{% else %}*number of stars*{% endif %} as of {{timestamp}}{{ ' (page ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }}:
                 {%- else -%}
-                    📓 Learderboard by {{ '*local score*' if scoring_method == 'local' else '*number of stars*' }} from the {{ year }} event:
+                    📓 Learderboard by {% if scoring_method == 'local' %}*local score*{% elif scoring_method == 'percentile' %}*percentile score*{% else %}*number of stars*{% endif %} from the {{ year }} event{{ ' (page ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }}:
+                {%- endif %}
+                {%- if detailed %}\n\
+                    _Last columns: median part 2 time and average delta over the event._
                 {%- endif %}\n\
                 ```{{ leaderboard }}```"
             }
+            MessageTemplate::Wins => {
+                "{%- if current_year -%}
+                    🏅 Days won by each member as of {{timestamp}}:
+                {%- else -%}
+                    🏅 Days won by each member during the *{{year}}* event:
+                {%- endif %}\n\
+                ```{{ wins }}```"
+            }
+            MessageTemplate::AllTime => {
+                "🏛️ All-time standings over the {{ years|length }} cached event{{ 's' if years|length > 1 }}\
+                {% if years %} ({{ years|first }}-{{ years|last }}){% endif %}, ranked by {{ 'stars' if scoring == 'stars' else 'score' }} as of {{timestamp}}:\n\
+                ```{{ standings }}```\n\
+                _Scores are normalized per event, the best score of each event being worth 100 points._"
+            }
+            MessageTemplate::Faq => {
+                "{%- if answer -%}
+                    ❓ *{{ answer.question }}*\n\
+                    {{ answer.answer }}
+                {%- elif requested -%}
+                    🤷 I could not find any answer about *{{ requested }}*
+                    {%- if entries %}, did you mean one of these?
+                        {%- for entry in entries %}\n\
+                            \x20 • `{{ entry.topic }}`: {{ entry.question }}
+                        {%- endfor %}
+                    {%- else %}. Use `{{ command_prefix }}faq` to list the available topics.
+                    {%- endif %}
+                {%- elif entries -%}
+                    📚 Here is what I can help you with (`{{ command_prefix }}faq <topic>`):
+                    {%- for entry in entries %}\n\
+                        \x20 • `{{ entry.topic }}`: {{ entry.question }}
+                    {%- endfor %}
+                {%- else -%}
+                    📚 No FAQ has been configured for this event yet.
+                {%- endif %}"
+            }
+            MessageTemplate::Link => {
+                "🔗 Everything you need for *day {{day}}* of the *{{year}}* event:\n\
+                    \x20 • 🧩 <{{puzzle}}|Puzzle>\n\
+                    \x20 • 🎄 <{{private_leaderboard}}|Private leaderboard>\n\
+                    \x20 • 🌍 <{{global_leaderboard}}|Global leaderboard>"
+            }
+            MessageTemplate::BestSolutions => {
+                "{%- if solutions -%}
+                    🗳️ Most upvoted replies of the *day {{day}}* solutions thread ({{year}} event):
+                    {%- for (permalink, author, votes) in solutions %}\n\
+                        \x20 {{ loop.index }}. <{{ permalink }}|Solution> by {{ author }} ({{ votes }} reaction{{ 's' if votes > 1 }})
+                    {%- endfor %}
+                {%- else -%}
+                    🤷 No reply of the *day {{day}}* solutions thread ({{year}} event) got any reaction yet. \
+                    React to the solutions you like to upvote them!
+                {%- endif %}"
+            }
             MessageTemplate::TdfStandings => {
                 "{%- if current_year and not day -%}
-                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else '⚫Combative 🥋')}} Jersey current standings as of {{timestamp}}:
+                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else ('⚫Combative 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Jersey current standings as of {{timestamp}}:
                 {%- elif not day -%}
-                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else '⚫Combative 🥋')}} Jersey standings from the *{{year}}* event:
+                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else ('⚫Combative 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Jersey standings from the *{{year}}* event:
                 {%- else -%}
-                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else '⚫Combative 🥋')}} Jersey standings for *day {{day}}* of the {{year}} event:
-                {%- endif %}\n\
+                    🚴 {{ '🟡 Yellow 🛵' if jersey=='yellow' else ('🟢 Green 🍏' if jersey=='green' else ('⚫Combative 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Jersey standings for *day {{day}}* of the {{year}} event:
+                {%- endif %}
+                {%- if drop_worst %} _(the {{drop_worst}} worst day{{ 's' if drop_worst > 1 }} of each member dropped)_{% endif %}\n\
                 ```{{ standings }}```"
             }
+            MessageTemplate::TdfStage => {
+                "{%- if current_day -%}
+                    🚴 🟡 Yellow 🛵 Jersey *stage {{day}}* results as of {{timestamp}}:
+                {%- else -%}
+                    🚴 🟡 Yellow 🛵 Jersey *stage {{day}}* results of the {{year}} event:
+                {%- endif %}
+                {%- if winner %}\n\
+                    🏆 Stage winner: *{{ winner }}*
+                {%- endif %}
+                {%- if stage %}\n\
+                    ```{{ stage }}```
+                {%- else %}\n\
+                    Nobody took the start of this stage yet.
+                {%- endif %}"
+            }
         }
     }
 }
@@ -272,7 +1361,7 @@ pub fn invalid_year_day_message(year: i32, day: Option<u8>) -> Option<String> {
                 "I know the suspense is unbearable, but I can't go faster than the music 🎶...\n\
                 *Come back in {delta} day{}* to see what's happening on December {}.",
                 potential_s,
-                format_rank(day)
+                format_rank(day.into())
             ))
         }
         // it's today, make sure AOC puzzle was released
@@ -293,3 +1382,52 @@ pub fn invalid_year_day_message(year: i32, day: Option<u8>) -> Option<String> {
         (_, _, _) => None, // any other combination is valid
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minijinja::context;
+
+    #[test]
+    fn overrides_failing_to_render_fall_back_to_the_embedded_template() {
+        let mut embedded = Environment::new();
+        embedded.add_template("link.txt", "Day {{ day }}").unwrap();
+        let mut env = Environment::new();
+        env.add_template("link.txt", "Day {{ day|shout }}").unwrap();
+
+        let rendered =
+            render_with_fallback(&env, &embedded, "link.txt", context! { day => 3 }, true);
+        assert_eq!(rendered.unwrap(), "Day 3");
+        // errors of the embedded templates are still reported
+        let rendered =
+            render_with_fallback(&env, &embedded, "link.txt", context! { day => 3 }, false);
+        assert!(rendered.is_err());
+    }
+
+    #[test]
+    fn every_template_is_translated_in_every_language() {
+        let mut env = Environment::new();
+        for language in Language::iter() {
+            for template in MessageTemplate::iter() {
+                let name = format!("{language:?}/{}", template.name());
+                let source = template.translation(language);
+                assert!(
+                    env.add_template_owned(name.clone(), source).is_ok(),
+                    "{name} does not compile"
+                );
+            }
+        }
+
+        let absence = |language: Language| {
+            let env = Environment::new();
+            env.render_str(
+                MessageTemplate::Absence.translation(language),
+                context! { member => "Cupid", days => vec![3, 4, 5], year => 2022 },
+            )
+            .unwrap()
+        };
+        assert!(absence(Language::En).contains("Days *3* to *5*"));
+        assert!(absence(Language::Fr).contains("Les jours *3* à *5*"));
+        assert!(absence(Language::De).contains("Die Tage *3* bis *5*"));
+    }
+}
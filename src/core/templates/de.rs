@@ -0,0 +1,898 @@
+use super::MessageTemplate;
+
+// Same variables as the embedded (english) templates. Command names and arguments are not
+// translated, as the commands parser only understands them in english.
+pub fn template(template: &MessageTemplate) -> &'static str {
+    match template {
+        MessageTemplate::Help => {
+            "🗒️ Gut gemacht, du hast das *Handbuch der CEO-Befehle* gefunden.\n\
+            Das Parsen der Befehlsargumente ist ein Meisterwerk der Regex-Ingenieurskunst, \
+            die Reihenfolge der optionalen Argumente eines Befehls spielt also keine (oder sollte zumindest keine...) Rolle.\n\n\
+            👉 🆘 *Anleitung*\n\
+            ```{{ command_prefix }}help```\n\
+            Listet und erklärt die Befehle des Bots. Du liest das gerade.\n\n\
+            👉 🏎️ *Die Schnellsten im Westen!*\n\
+            ```{{ command_prefix }}fast [ranking method] [day] [year]```\n\
+            Schnellste Zeit(en) des Tages. Standardmäßig basiert die Rangliste auf der `delta`-Zeit des Tages, \
+            aber auch die Ranglisten für `p1` und `p2` sind verfügbar. Mit der Methode `limit` gibt es die Rangliste der \
+            knappsten Abschlüsse vor dem Stichzeitpunkt (d.h. der kürzesten Zeit vor dem nächsten Rätsel), diese Zeiten \
+            bringen die Punkte für das Trikot `{{ command_prefix }}tdf combative`. \
+            Ohne Tag und/oder Jahr werden der aktuelle Tag und/oder das aktuelle Jahr verwendet.\n\
+            ```{{ command_prefix }}fast <avg | median> [first day-last day] [year]```\n\
+            Beste durchschnittliche (`avg`) oder mittlere (`median`) Delta-Zeit über einen Zeitraum (z.B. `{{ command_prefix }}fast avg 1-10`), \
+            standardmäßig über das ganze Event. Nur Tage mit beiden gelösten Teilen zählen, wobei Mitglieder mit mehr gelösten \
+            Tagen des Zeitraums zuerst gereiht werden.\n\n\
+            👉 ⚖️ *Ruhige Hand*\n\
+            ```{{ command_prefix }}delta [avg | median] [minimum days] [year]```\n\
+            Die beständigsten Löser beider Teile: Rangliste nach durchschnittlicher (`avg`, Standard) oder mittlerer (`median`) \
+            Delta-Zeit über alle Tage mit beiden gelösten Teilen, wobei nur Mitglieder mit mindestens der Mindestanzahl an \
+            Tagen (standardmäßig 5) gereiht werden.\n\n\
+            👉 📊 *Zeig mir die Tabelle!*\n\
+            ```{{ command_prefix }}board [ranking method] [detailed] [year]```\n\
+            Aktuelle Punkte und Sterne des Jahres als hübsche ASCII-Tabelle. Standardmäßig nach `local`-Punkten des aktuellen \
+            Jahres sortiert, die Sortierung nach Anzahl der Sterne (`stars`) ist ebenfalls verfügbar, sowie nach Perzentil-Punkten \
+            (`percentile`: 10, 8, 6, 4 oder 2 Punkte pro Stern, je nach Anteil der Mitglieder, die ihn vorher hatten: Top 10 %, 25 %, \
+            50 %, 75 % oder die anderen), weniger streng als die lokalen Punkte bei großen Ranglisten. Die ausführliche Tabelle \
+            (`detailed`) zeigt zusätzlich die mittlere Zeit für Teil 2 (Median) und das durchschnittliche Delta jedes Mitglieds. Große Tabellen werden \
+            auf mehrere Seiten verteilt, mit den Knöpfen unter der Tabelle kannst du blättern oder ein anderes Jahr wählen.\n\n\
+            👉 🚴 *Die lange Etappe!*\n\
+            ```{{ command_prefix }}tdf [jersey color] [day] [year]```\n\
+            Alternative Wertungen nach Art der Tour de France! Schließ dich dem Peloton an, um das Gelbe Trikot (`yellow`) \
+            zu erobern, oder sammle Punkte für das begehrte Grüne (`green`) oder Kämpfer-Trikot (`combative`). \
+            Standardmäßig die Wertung des Gelben Trikots für das aktuelle Jahr.\n\
+            - die Wertung des `yellow`-Trikots basiert auf der aufsummierten Zeit für die vollständige Lösung (Teil 2) jedes Tages \
+            (eine Strafe von `{{ jersey_points.yellow_penalty }}` gilt für jeden nicht vollständig gelösten Tag oder jeden Tag, der länger als die Strafzeit gedauert hat). \
+            Mit `dropworst=<N>` (z.B. `{{ command_prefix }}tdf yellow dropworst=3`) werden die N schlechtesten Tage jedes Mitglieds nicht gezählt. \
+            Admins können die Abwesenheitstage eines Mitglieds (z.B. im Urlaub) mit \
+            `{{ command_prefix }}tdf absence <name or id> <day | first day-last day> [year]` eintragen, diese Tage zählen die Medianzeit des Mitglieds statt einer Strafe, wenn sie ungelöst bleiben. \
+            Gib einen Tag an, um die Ergebnisse dieser Etappe zu sehen, sortiert nach der Zeit für Teil 2.\n\
+            - Punkte für das `green`-Trikot gibt es jeden Tag für Vollgas zwischen Teil 1 und Teil 2! Die \
+            {{ jersey_points.green_points|length }} schnellsten Mitglieder erhalten {{ jersey_points.green_points|join(', ') }} Punkte.\n\
+            - Punkte für das `combative`-Trikot gehen jeden Tag an die tapferen Seelen, die nicht zu früh das Handtuch werfen und \
+            einen Tag noch vor dem Start des nächsten abschließen... Je knapper vor dem Stichzeitpunkt, desto mehr Punkte \
+            (bis zu {{ jersey_points.combative_max_points|int }})!\n\
+            {% if jersey_points.custom %}- eigene Trikots dieses Leaderboards: {% for custom in jersey_points.custom %}`{{ custom.name }}` ({{ 'Punkte' if custom.aggregation == 'points' else 'Zeit' }} {{ custom.metric }}){{ ', ' if not loop.last }}{% endfor %}.\n{% endif %}\n\
+            👉 🏅 *Wer beherrscht die Tage?*\n\
+            ```{{ command_prefix }}wins [year]```\n\
+            Anzahl der Tage, die jedes Mitglied gewonnen hat, d.h. am schnellsten in Teil 1 (`p1`), Teil 2 (`p2`) oder bei der `delta`-Zeit. \
+            Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🏛️ *Ruhmeshalle der Legenden*\n\
+            ```{{ command_prefix }}alltime [score | stars]```\n\
+            Ewige Rangliste über alle dem Bot bekannten Events, nach Punkten (`score`, Standard) oder Anzahl der Sterne (`stars`). \
+            Die Punkte werden pro Event normiert (die beste Punktzahl jedes Events ist 100 Punkte wert), damit Events \
+            mit mehr Mitgliedern nicht mehr Gewicht haben.\n\n\
+            👉 📚 *Häufig gestellte Fragen*\n\
+            ```{{ command_prefix }}faq [topic]```\n\
+            Antworten auf Fragen zu diesem Event (Preise, Regeln, ...). Listet die verfügbaren Themen auf, wenn kein Thema angegeben ist.\n\n\
+            👉 🔗 *Wo ist das?*\n\
+            ```{{ command_prefix }}link [day] [year]```\n\
+            Links zum Rätsel, zur privaten und zur globalen Rangliste des Tages. \
+            Ohne Tag und/oder Jahr werden der aktuelle Tag und/oder das aktuelle Jahr verwendet.\n\n\
+            👉 🗳️ *Ruhmeshalle*\n\
+            ```{{ command_prefix }}bestsolutions [day] [year]```\n\
+            Die beliebtesten Antworten im Lösungs-Thread des Tages, basierend auf den erhaltenen Reaktionen. \
+            Ohne Tag und/oder Jahr werden der aktuelle Tag und/oder das aktuelle Jahr verwendet.\n\n\
+            👉 ⏰ *Lass mich nicht vergessen!*\n\
+            ```{{ command_prefix }}remindme <name or id | off>```\n\
+            Erhalte an jedem Abend des Events eine Direktnachricht mit den Tagen, an denen dir noch Sterne fehlen. \
+            Verwende deinen AoC-Namen (oder deine numerische ID) wie in der Rangliste angezeigt, oder `off`, um die Erinnerungen zu beenden.\n\n\
+            👉 👀 *Im Blick behalten*\n\
+            ```{{ command_prefix }}watch [off] <name or id>```\n\
+            Erhalte eine Direktnachricht, sobald das Mitglied einen Stern sammelt oder dich in der Gesamtwertung überholt, wenn du \
+            mit `{{ command_prefix }}iam` mit einem Mitglied verknüpft bist. Verwende `off`, um dem Mitglied nicht mehr zu folgen, oder nur `off`, um niemandem mehr zu folgen.\n\n\
+            👉 🪪 *Wer bin ich?*\n\
+            ```{{ command_prefix }}iam <name or id>```\n\
+            Sag mir, welches Mitglied der Rangliste du bist, damit ich dich erwähnen kann, wenn du Sterne sammelst oder \
+            es in die globale Rangliste schaffst. Ein Mitglied, das schon mit einem anderen Benutzer verknüpft ist, kann nicht gewählt werden.\n\n\
+            👉 🎁 *Das war's!*\n\
+            ```{{ command_prefix }}review <on | off>```\n\
+            Nach dem Ende des Events erhalten die mit `{{ command_prefix }}iam` verknüpften Mitglieder einen persönlichen Jahresrückblick \
+            (falls in diesem Bot aktiviert). Mit `off` kannst du ihn abbestellen, mit `on` wieder anfordern.\n\n\
+            👉 📬 *Wie lief es bei mir?*\n\
+            ```{{ command_prefix }}mysummary <on | off>```\n\
+            Erhalte nach jeder täglichen Zusammenfassung eine Direktnachricht mit deinen Zeiten, den verdienten Punkten und \
+            der Veränderung deiner Platzierung (falls in diesem Bot aktiviert). Du musst zuerst mit `{{ command_prefix }}iam` mit einem Mitglied verknüpft sein.\n\n\
+            👉 🥷 *Inkognito*\n\
+            ```{{ command_prefix }}optout <on | off>```\n\
+            Erscheine in den Nachrichten als anonymer Elf statt mit deinem Namen, deine Sterne zählen weiterhin in der Wertung. \
+            Du musst zuerst mit `{{ command_prefix }}iam` mit einem Mitglied verknüpft sein.\n\n\
+            👉 🔮 *Kristallkugel*\n\
+            ```{{ command_prefix }}guess [mm:ss | year]```\n\
+            Tippe die Zeit des 100. Teilnehmers, der Teil 2 in der globalen Rangliste des nächsten Rätsels abschließt, bevor es freigeschaltet wird. \
+            Der knappste Tipp wird mit den Statistiken der globalen Rangliste verkündet. Ohne Zeit wird die Wertung des \
+            Tippspiels des Events angezeigt (Anzahl gewonnener Tage, dann durchschnittliche Abweichung).\n\n\
+            👉 🧮 *Was wäre, wenn...*\n\
+            ```{{ command_prefix }}whatif <name or id> completes day <day> [year]```\n\
+            Voraussichtliche Punkte, Platzierung und Trikotwertungen, wenn das Mitglied die fehlenden Teile des Tages jetzt lösen würde. \
+            Nichts motiviert mehr für ein Rätsel, als die Plätze zu sehen, die es zu gewinnen gibt!\n\n\
+            👉 🏁 *Wo stehe ich?*\n\
+            ```{{ command_prefix }}rank <name or id> [year=<year>]```\n\
+            Platzierung, Punkte und Sterne eines einzelnen Mitglieds, mit dem Rückstand auf das Mitglied davor, dem Vorsprung \
+            auf das Mitglied dahinter und den Platzierungen in den Trikotwertungen. Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🐢 *Wer fehlt noch?*\n\
+            ```{{ command_prefix }}missing [day] [year]```\n\
+            Mitglieder ohne einen einzigen Stern am Tag, die Stammspieler zuerst, um ihnen einen freundlichen Schubs zu geben. \
+            Ohne Tag und/oder Jahr werden der aktuelle Tag und/oder das aktuelle Jahr verwendet.\n\n\
+            👉 🦸 *Globale Helden*\n\
+            ```{{ command_prefix }}heroes```\n\
+            Alle Auftritte unserer Mitglieder in den vom Bot erfassten globalen Bestenlisten, mit ihrem besten Rang.\n\n\
+            👉 📉 *Burn-down*\n\
+            ```{{ command_prefix }}burndown [day] [year]```\n\
+            Noch zu sammelnde Sterne, Stunde für Stunde während der ersten 24 Stunden nach der Veröffentlichung des Rätsels. \
+            Ohne Tag und/oder Jahr werden der aktuelle Tag und/oder das aktuelle Jahr verwendet.\n\n\
+            👉 🌡️ *Heatmap*\n\
+            ```{{ command_prefix }}heatmap [hours] [year]```\n\
+            Gesammelte Sterne an jedem Tag des Events durch die Mitglieder mit den meisten Sternen, oder mit `hours` in jeder Stunde \
+            nach der Veröffentlichung des Rätsels, um die Tage zu erkennen, die das Leaderboard gesprengt haben. Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🌟 *Ruhmeshalle*\n\
+            ```{{ command_prefix }}fifty [year]```\n\
+            Mitglieder, die alle 50 Sterne des Events gesammelt haben, in der Reihenfolge ihres Abschlusses, mit der benötigten Zeit \
+            nach der Veröffentlichung des letzten Rätsels. Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 ⏱️ *Zeitabstände*\n\
+            ```{{ command_prefix }}gap [yellow | local] [year]```\n\
+            Abstand jedes Mitglieds zum Vordermann (und zum Führenden), bei der Gesamtzeit des Gelben Trikots oder beim lokalen Punktestand, \
+            um zu sehen, wie eng das Rennen auf jeder Position ist. Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🎯 *Übung macht den Meister*\n\
+            ```{{ command_prefix }}practice [year]```\n\
+            Ein zufälliges vergangenes Rätsel, das du noch nicht gelöst hast, mit Link und seiner Schwierigkeit auf der globalen Rangliste. \
+            Du musst dich zuerst mit `{{ command_prefix }}iam` mit einem Mitglied verknüpfen. Ohne Jahr werden alle erfassten Events verwendet.\n\n\
+            👉 ⚡ *Negative Splits*\n\
+            ```{{ command_prefix }}splits [year]```\n\
+            Mitglieder, die den Teil 2 eines Tages schneller gelöst haben als den Teil 1 (mehr als 24 Stunden zu spät begonnene Tage zählen nicht), \
+            mit ihrer Anzahl an Negative Splits im Event. Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🗳️ *Die Sprache der Saison*\n\
+            ```{{ command_prefix }}languages [year]```\n\
+            Die im Event verwendeten Sprachen, nach den Stimmen der täglich im Lösungs-Thread veröffentlichten Umfrage. \
+            Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 🔎 *Für Profis*\n\
+            ```{{ command_prefix }}query [key=value ...]```\n\
+            Die Sterne der Rangliste, die zu den Filtern passen, z. B. `{{ command_prefix }}query day=7 part=2 sort=time limit=10`. \
+            Filter: `year`, `day`, `part` (1 oder 2), `member` (Name oder ID), `sort` (`time`, `date` oder `name`, mit vorangestelltem `-` \
+            für eine absteigende Reihenfolge) und `limit` (standardmäßig 10, höchstens 50). Ohne Jahr wird das aktuelle Jahr verwendet.\n\n\
+            👉 ⚙️ *Unter der Haube*\n\
+            ```{{ command_prefix }}jobs```\n\
+            Letzte Ausführungen, Dauer und Fehler der geplanten Aufgaben des Bots (nur für Admins).\n\
+            ```{{ command_prefix }}latency [year]```\n\
+            Durchschnittliche Verzögerung zwischen dem Lösen der Sterne und ihrer Erkennung durch den Bot, um zu prüfen, ob die \
+            Aktualisierung des privaten Leaderboards mithält. Ohne Jahr wird das aktuelle Jahr verwendet.\n\
+            ```{{ command_prefix }}auditlog [n]```\n\
+            Die letzten `n` vom Bot gesendeten Nachrichten (standardmäßig 10, höchstens 50), um fehlende oder doppelte Nachrichten aufzuspüren (nur für Admins).\n\
+            ```{{ command_prefix }}exclude [off] <Name oder ID>```\n\
+            Ein Mitglied (z. B. einen Bot oder ein doppeltes Konto) aus den Wertungen nehmen, es bleibt unten im Board aufgeführt, \
+            oder es mit `off` wieder mitzählen (nur für Admins).\n\
+            ```{{ command_prefix }}preview <template> [Beispielwerte]```\n\
+            Ein Template (z. B. `summary`) mit Beispielwerten darstellen, im Monitoring-Kanal gepostet, um seine Anpassung zu prüfen, \
+            ohne auf das Ereignis zu warten. Beispielwerte werden als JSON-Objekt angegeben, z. B. `{\"year\": 2023}` (nur für Admins)."
+        }
+        MessageTemplate::CustomMessage => "{{emoji or '🙅'}} {{message}}",
+        MessageTemplate::HardChallenge => {
+            "😱 *{{minutes}} Minuten* sind schon vergangen und es gibt noch freie Plätze in der globalen Rangliste...\n\
+            {% if cycle == 5 -%}
+                Ich weiß nicht, wie es dir geht, aber es fühlt sich an, als würde die Temperatur 🤒 plötzlich steigen...
+            {% elif cycle == 8 -%}
+                Jetzt wäre wohl ein guter Moment, ein Taschentuch bereitzulegen, falls du weinen musst 😭.
+            {% elif cycle == 11 -%}
+                Keine Sorge, heute ist der Drang, einen Freund anzurufen ☎️  und um Hilfe 🆘 zu bitten, völlig normal.
+            {% else -%}
+                Oje, Zeit die Flagge der Hoffnung zu hissen 🏴... Ich kann dir nur viel Glück wünschen 🤞, das wirst du heute brauchen...
+            {% endif %}"
+        }
+        MessageTemplate::DailyChallenge => {
+            "```{{header}}```\n\
+            {{theme.celebration}} Die Aufgabe des Tages ist online! (<{{url}}|Link>)\n\
+                \x20 *{{title}}*\n\
+            🔫 Ran an die Arbeit und viel Spaß, ⏱️ die Zeit läuft!"
+        }
+        MessageTemplate::Countdown => {
+            "{%- if minutes >= 60 -%}
+                ⏳ *{{minutes // 60}} Stunde{{ 'n' if minutes >= 120 }}* bis Tag {{day}} freigeschaltet wird! Zeit für einen Kaffee ☕ und zum Aufwärmen der Tastatur.
+            {%- elif minutes > 1 -%}
+                ⌛ *{{minutes}} Minuten* bis Tag {{day}} freigeschaltet wird! Editoren offen, Input-Ordner bereit? 🧑‍💻
+            {%- else -%}
+                🚨 *1 Minute* bis Tag {{day}} freigeschaltet wird! Finger auf die Tastatur... 🚀
+            {%- endif %}"
+        }
+        MessageTemplate::DifficultyPoll => {
+            "🌡️ Wie schwer fühlte sich das Rätsel von *Tag {{day}}* an? Bewerte es mit den Reaktionen unten, \
+            von :one: (ein Spaziergang) bis :five: (mein Gehirn ist geschmolzen)."
+        }
+        MessageTemplate::DailySolutionThread => {
+            "👇 *Diskussions-Thread für Tag {{day}}*\n\
+                \x20   Nicht öffnen, bevor du Teil 2 gelöst hast!\n\
+             🚨 *Achtung Spoiler* :rotating_light:"
+        }
+        MessageTemplate::DailySolutionThreadActivity => {
+            "{%- if n_replies == 0 -%}
+                🦗 Heute hat niemand etwas im Diskussions-Thread von *Tag {{day}}* geteilt... Nur keine Scheu, der Thread ist noch offen!
+            {%- else -%}
+                💬 Der Diskussions-Thread von *Tag {{day}}* hat heute *{{n_replies}}* Antwort{{ 'en' if n_replies > 1 }} gesammelt!
+                {%- if languages %}\n\
+                    \x20 • Gesichtete Sprachen: {% for (language, count) in languages %}{{language}} ({{count}}){{ ', ' if not loop.last }}{% endfor %}
+                {%- endif %}
+                {%- if most_reacted %}\n\
+                    \x20 • Nachricht mit den meisten Reaktionen (<{{most_reacted[0]}}|Link>) mit *{{most_reacted[1]}}* Reaktion{{ 'en' if most_reacted[1] > 1 }}
+                {%- endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::DailySummary => {
+            "🗓️ *{{day}} Dezember {{year}}*\n\
+            ----- 🥁 *Tägliches Update* 🗞️ -----\n\
+            So lief es heute an der Spitze des Feldes:\n\
+            ___________________________________________________________________\n\
+            Top 5 bei *TEIL 1* 🏁\n\
+            {%- for (prefix, name, time) in ranking_p1 %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}\n\
+            ___________________________________________________________________\n\
+            Top 5 bei *TEIL 2* 🏁\n\
+            {%- for (prefix, name, time) in ranking_p2 %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}\n\
+            ___________________________________________________________________\n\
+            Top 5 beim *DELTA* 🏁\n\
+            {%- for (prefix, name, time) in ranking_delta %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}
+            {%- if negative_splits %}\n\
+                ___________________________________________________________________\n\
+                ⚡ *Negative Splits* (Teil 2 schneller als Teil 1)
+                {%- for (name, part1, delta) in negative_splits %}\n\
+                    \x20 • *{{name}}*: Teil 1 in {{part1}}, Teil 2 in {{delta}}
+                {%- endfor %}
+            {%- endif %}
+            {%- if difficulty %}\n\
+                ___________________________________________________________________\n\
+                🌡️ *Gefühlte Schwierigkeit*: {{difficulty.average}}/5 ({{difficulty.n_votes}} Stimme{{ 'n' if difficulty.n_votes > 1 }})
+            {%- endif %}"
+        }
+        MessageTemplate::PrivateLeaderboardUpdated => {
+            "🔁 Private Rangliste erfolgreich aktualisiert!"
+        }
+        MessageTemplate::PrivateLeaderboardMembership => {
+            "👥 *Privates Leaderboard*: {{ count }}/{{ cap }} Mitglieder
+            {%- if trend is not none %} ({{ '+' if trend >= 0 }}{{ trend }} in den letzten 7 Tagen){% endif %}
+            {%- if joined %}\n\
+                \x20 ➕ Beigetreten: {{ joined|join(', ') }}
+            {%- endif %}
+            {%- if left %}\n\
+                \x20 ➖ Ausgetreten: {{ left|join(', ') }}
+            {%- endif %}
+            {%- if spots_left is not none %}\n\
+                ⚠️ {% if spots_left == 0 %}Das private Leaderboard ist voll, niemand kann mehr beitreten, bis Mitglieder es verlassen.{% else %}Nur noch {{ spots_left }} {{ 'freier Platz' if spots_left == 1 else 'freie Plätze' }} bis zur Grenze von {{ cap }} Mitgliedern.{% endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::SeasonChanged => {
+            "{% if is_event -%}
+                🎄 Der Zeitraum des Events {{ year }} hat begonnen: das private Leaderboard wird wieder abgerufen und die geplanten Aufgaben laufen wieder.
+            {%- else -%}
+                💤 Nebensaison bis zum Event {{ year }}: AoC wird nicht mehr abgerufen, die Befehle werden aus dem zwischengespeicherten Leaderboard beantwortet.
+            {%- endif %}"
+        }
+        MessageTemplate::MonitoringAlert => "🚨 `{{code}}` {{context}}\n```{{error}}```",
+        MessageTemplate::Heartbeat => {
+            "💓 Cache {{ cache_age }} alt · {{ n_members }} Mitglied{{ 'er' if n_members != 1 }} · \
+            {%- if scrape_sec is none %} noch kein Scraping{% else %} letztes Scraping {{ scrape_sec }} s{{ ' (fehlgeschlagen)' if scrape_failed }}{% endif %} · \
+            {%- if failing_jobs %} ⚠️ fehlgeschlagene{{ 'r' if failing_jobs|length == 1 }} Job{{ 's' if failing_jobs|length > 1 }}: {{ failing_jobs|join(', ') }}{% else %} {{ n_jobs }} Job{{ 's' if n_jobs != 1 }} gesund{% endif %}"
+        }
+        MessageTemplate::QuietHoursDigest => {
+            "🌅 Guten Morgen! Das ist während der Ruhezeit passiert:
+            {%- for message in messages %}\n\n{{ message }}{% endfor %}"
+        }
+        MessageTemplate::LeaderboardMemberJoin => {
+            "{%- for name in members %}\n\
+                🕺 Ein neuer Spieler hat die Weihnachtsarena betreten! Schön, dass du dabei bist, *{{name}}*!
+             {%- endfor %}"
+        }
+        MessageTemplate::NewEntriesToday => {
+            "🗓️ *Tag {{day}}*\
+            {%- for entry in completions %}\n\
+                {% with both = entry.parts_duration|length > 1, double = theme.star ~ theme.star, single = theme.star %}\
+                {{theme.announcement}} {{entry.name}} hat gerade *{{entry.n_stars}}* weitere{{ 'n' if entry.n_stars == 1 }} Stern{{ 'e' if entry.n_stars > 1 }} für Tag {{entry.day}} verdient ({{[double, '*<->', entry.delta, '*']|join(' ') if both else single}}{{ ', Top ' ~ entry.delta_percentile ~ ' % des Tages' if both and entry.delta_percentile }}) +{{entry.new_points}}pts
+                {%- endwith %}
+             {%- endfor %}"
+        }
+        MessageTemplate::NewEntriesLate => {
+            "🗓️ *Tag {{day}}*{{ ' des Events ' ~ year if not current_year }} (Nachzügler)\
+            {%- for entry in completions %}\n\
+                {% with both = entry.parts_duration|length > 1, double = '🤩', single = '✔️' %}\
+                🚂  {{entry.name}} hat gerade *{{entry.n_stars}}* weitere{{ 'n' if entry.n_stars == 1 }} Stern{{ 'e' if entry.n_stars > 1 }} für Tag {{entry.day}} nachgeholt ({{ [double, 'beide Teile gelöst!', '*<->', entry.delta, '*']|join(' ')  if both else single }}) +{{entry.new_points}}pts
+                {%- endwith %}
+             {%- endfor %}"
+        }
+        MessageTemplate::GlobalStatistics => {
+            "🌍 Die globale Rangliste für *Tag {{day}}* ist vollständig! So lief es für die großen Fische:\n\
+                \x20 • Zielzeiten Teil 1: 🔥 *{{p1_fast}}* - *{{p1_slow}}* ❄️\n\
+                \x20 • Zielzeiten Teil 2: 🔥 *{{p2_fast}}* - *{{p2_slow}}* ❄️\n\
+                \x20 • Delta-Zeiten: 🏃‍♀️ {{delta_fast}} - {{delta_slow}} 🚶‍♀️
+            {%- if difficulty %}\n\
+                \x20 • Schwierigkeitsindex: *{{difficulty}}* (Zeit des 100. bei Teil 2 im Vergleich zum Median der {{n_days}} vorherigen Tage)
+            {%- endif %}
+            {%- if is_among_hardest %}\n\
+                🥵 Heute war einer der *schwersten 10% aller Tage*, Glückwunsch an alle, die es geschafft haben!
+            {%- endif %}"
+        }
+        MessageTemplate::Ranking => {
+            "{%- if current_day -%}
+                {{'Schnellste' if not is_limit else 'Knappste'}} *{{ ranking_method }}-Zeit* des Tages (Stand {{timestamp}}):
+            {%- else -%}
+                {{'Schnellste' if not is_limit else 'Knappste'}} *{{ ranking_method }}-Zeit* für den {{ day }}.12.{{ year }}:
+            {%- endif %}\n\
+            {%- for (prefix, name, time) in ranking %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}"
+        }
+        MessageTemplate::RangeRanking => {
+            "Beste *{{ 'durchschnittliche' if aggregate == 'avg' else 'mittlere' }} Delta-Zeit* für die Tage {{ first }}-{{ last }} des Events {{ year }} (Stand {{timestamp}}):\n\
+            {%- for (prefix, name, time, n_days) in ranking %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}*{{ ' (' ~ n_days ~ '/' ~ n_total ~ ' Tage)' if n_days < n_total }}
+            {%- else %}\n\
+                Bisher hat niemand beide Teile eines dieser Tage gelöst.
+            {%- endfor %}"
+        }
+        MessageTemplate::SeasonDelta => {
+            "⚖️ Die beständigsten Löser beider Teile im Event {{ year }}, nach *{{ 'durchschnittlicher' if aggregate == 'avg' else 'mittlerer' }} Delta-Zeit* \
+            über mindestens {{ min_days }} Tag{{ 'e' if min_days > 1 }} (Stand {{timestamp}}):\n\
+            {%- for (prefix, name, time, n_days) in ranking %}\n\
+                {{prefix}} in ⏱️ {{time}} 👉🏻 *{{name}}* ({{ n_days }} Tag{{ 'e' if n_days > 1 }})
+            {%- else %}\n\
+                Bisher hat niemand beide Teile von {{ min_days }} Tag{{ 'en' if min_days > 1 }} gelöst.
+            {%- endfor %}"
+        }
+        MessageTemplate::Hero => {
+            "{{ theme.celebration }} 🥳 Unser eigenes Mitglied *{{ name }}* hat es bei Teil *{{ part }}* in die globale Rangliste geschafft! (*{{ rank }}*) 🙌"
+        }
+        MessageTemplate::Overtakes => {
+            "{%- for (overtaker, overtaken, position) in overtakes %}\n\
+                🏎️ *{{ overtaker }}* überholt *{{ overtaken }}* und ist jetzt auf Platz *{{ position }}*!
+             {%- endfor %}"
+        }
+        MessageTemplate::StandingsShuffle => {
+            "🔀 *Umstellung der Gesamtwertung* des Events {{ year }}: verspätete Lösungen vergangener Tage haben die Gesamtwertung der Tageszusammenfassungen verändert
+            {%- for (name, previous, current, is_up) in changes %}\n\
+                \x20 • *{{ name }}*: {{ previous }} → *{{ current }}* {{ '⬆️' if is_up else '⬇️' }}
+            {%- endfor %}"
+        }
+        MessageTemplate::StageWinner => {
+            "🏁 *Etappensieger des Tages {{ day }}*: *{{ name }}* hat als Erstes beide Sterne des Tages gesammelt, in {{ time }} (Delta {{ delta }})! 🏆"
+        }
+        MessageTemplate::FullCompletion => {
+            "🌟 *{{ name }}* hat alle 50 Sterne von {{ year }} gesammelt, {{ time }} nach der Veröffentlichung des letzten Rätsels! \
+            {%- if first %} Das erste Mitglied, das das Event abgeschlossen hat! 🏆{% else %} {{ rank }} Mitglied, das das Event abgeschlossen hat. 🎉{% endif %}"
+        }
+        MessageTemplate::NextEvent => {
+            "📣 *Advent of Code {{ year }} kommt!* Tritt unserem privaten Leaderboard mit dem Code `{{ join_code }}` auf {{ url }} bei und mach dich bereit für den 1. Dezember! 🎄"
+        }
+        MessageTemplate::ReminderRegistration => {
+            "{%- if member -%}
+                ⏰ Notiert! Ich schicke dir jeden Abend eine Direktnachricht mit den Tagen, an denen *{{ member }}* noch Sterne fehlen.
+            {%- else -%}
+                🔕 Alles klar, keine Erinnerungen mehr für dich. Genieß die Ruhe!
+            {%- endif %}"
+        }
+        MessageTemplate::WatchRegistration => {
+            "{%- if watching -%}
+                👀 Notiert! Ich schicke dir eine Direktnachricht, sobald *{{ member }}* einen Stern sammelt oder dich in der Gesamtwertung überholt.
+            {%- elif member -%}
+                🙈 Alles klar, keine Neuigkeiten mehr über *{{ member }}*.
+            {%- else -%}
+                🙈 Alles klar, keine Neuigkeiten mehr über die Mitglieder, denen du folgst.
+            {%- endif %}"
+        }
+        MessageTemplate::MemberLinked => {
+            "🤝 Freut mich, *{{ member }}*! Ich werde dich erwähnen, wenn du glänzt."
+        }
+        MessageTemplate::Absence => {
+            "🏖️ Schöne Pause, *{{ member }}*! \
+            {% if days|length == 1 %}Tag *{{ days|first }}*{% else %}Die Tage *{{ days|first }}* bis *{{ days|last }}*{% endif %} \
+            des Events {{ year }} {{ 'wird' if days|length == 1 else 'werden' }} für das 🟡 Gelbe Trikot nicht bestraft."
+        }
+        MessageTemplate::Exclusion => {
+            "{% if excluded -%}
+                🚫 *{{ member }}* wird ab jetzt nicht mehr gewertet und nur noch unten im Board aufgeführt.
+            {%- else -%}
+                ✅ *{{ member }}* wird wieder gewertet.
+            {%- endif %}"
+        }
+        MessageTemplate::YearInReview => {
+            "🎁 *Rückblick {{ review.year }}* für *{{ review.name }}*\n\
+            Das Event {{ review.year }} ist vorbei, so lief es für dich:\n\
+                \x20 • {{ theme.star }} *{{ review.n_stars }}* Sterne gesammelt, insgesamt *{{ review.score }}* Punkte\n\
+                \x20 • 🏁 Endplatzierung: *{{ rank }}* von {{ review.n_members }}
+            {%- if review.best_day %}\n\
+                \x20 • 🚀 Bester Tag: *Tag {{ review.best_day[0] }}*, mit *{{ review.best_day[1] }}* verdienten Punkten
+            {%- endif %}
+            {%- if review.average_delta %}\n\
+                \x20 • ⏱️ Durchschnittliche Delta-Zeit: *{{ review.average_delta }}*
+            {%- endif %}\n\
+                \x20 • 📈 Verlauf der Platzierung: `{{ review.rank_chart }}`
+            {%- if review.badges %}\n\
+                Verdiente Abzeichen:
+                {%- for badge in review.badges %}\n\
+                    \x20 {{ badge }}
+                {%- endfor %}
+            {%- endif %}\n\
+            Bis nächstes Jahr! {{ theme.season }}"
+        }
+        MessageTemplate::YearInReviewOptIn => {
+            "{%- if opt_in -%}
+                🎁 Super, du erhältst deinen persönlichen Jahresrückblick, sobald das Event vorbei ist!
+            {%- else -%}
+                🙈 Kein Problem, dein Jahresrückblick bleibt geheim, sogar vor dir.
+            {%- endif %}"
+        }
+        MessageTemplate::PersonalSummary => {
+            "📬 *Tag {{ review.day }}* von {{ review.year }} für *{{ review.name }}*\n\
+            {%- if review.p1_time %}\n\
+                \x20 • {{ theme.star }} Teil 1 in *{{ review.p1_time }}*
+            {%- endif %}
+            {%- if review.p2_time %}\n\
+                \x20 • {{ theme.star }}{{ theme.star }} Teil 2 in *{{ review.p2_time }}*
+            {%- endif %}
+            {%- if review.delta %}\n\
+                \x20 • ⏱️ Delta-Zeit: *{{ review.delta }}*
+            {%- endif %}\n\
+                \x20 • 🎯 *{{ review.points }}* Punkte heute verdient\n\
+                \x20 • 🏁 Platzierung: *{{ rank }}* von {{ review.n_members }}
+            {%- if previous_rank %}
+            {%- if review.rank < review.previous_rank %} (📈 hoch von {{ previous_rank }})
+            {%- elif review.rank > review.previous_rank %} (📉 runter von {{ previous_rank }})
+            {%- else %} (unverändert)
+            {%- endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::PersonalSummaryOptIn => {
+            "{%- if opt_in -%}
+                📬 Abgemacht! Du erhältst nach jeder täglichen Zusammenfassung deine eigenen Ergebnisse des Tages.
+            {%- else -%}
+                🔕 Verstanden, keine persönlichen täglichen Zusammenfassungen mehr für dich.
+            {%- endif %}"
+        }
+        MessageTemplate::OptOut => {
+            "{%- if opt_out -%}
+                🥷 Verstanden! Deine Sterne zählen weiterhin, aber in den Nachrichten erscheinst du nur noch als *anonymer Elf*.
+            {%- else -%}
+                📣 Zurück im Rampenlicht! Dein Name erscheint wieder in den Nachrichten.
+            {%- endif %}"
+        }
+        MessageTemplate::WeeklyRecap => {
+            "🗞️ *Wochenrückblick* des Events {{ recap.year }}, das ist in den letzten 7 Tagen passiert:\n\
+            {{ theme.star }} Gesammelte Sterne:
+            {%- for (name, n_stars) in recap.stars %}\n\
+                \x20 • *{{ name }}*: {{ n_stars }} Stern{{ 'e' if n_stars > 1 }}
+            {%- endfor %}
+            {%- if recap.climbs %}\n\
+                📈 Größte Aufsteiger in der Gesamtwertung:
+                {%- for (name, previous, current) in recap.climbs %}\n\
+                    \x20 • *{{ name }}*: von Platz {{ previous }} auf Platz {{ current }}
+                {%- endfor %}
+            {%- endif %}
+            {%- if recap.jerseys %}\n\
+                🚴 Trikots:
+                {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                    \x20 • {{ {'yellow': 'Gelbes', 'green': 'Grünes', 'combative': 'Kämpfer-'}[jersey] }}{{ ' ' if jersey != 'combative' }}Trikot: *{{ holder }}*
+                    {%- if previous and previous != holder %} ({{ previous }} abgenommen)
+                    {%- elif previous %} (verteidigt)
+                    {%- endif %}
+                {%- endfor %}
+            {%- endif %}
+            {%- if recap.fastest_delta %}\n\
+                ⚡ Schnellstes Delta der Woche: *{{ recap.fastest_delta[0] }}* an Tag {{ recap.fastest_delta[1] }}, in *{{ recap.fastest_delta[2] }}*
+            {%- endif %}"
+        }
+        MessageTemplate::DailySummaryEmail => {
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head><meta charset='utf-8'><title>{{day}} Dezember {{year}} - Tägliches Update</title></head>\n\
+            <body style='font-family: sans-serif;'>\n\
+            <h2>🥁 Tägliches Update vom {{day}} Dezember {{year}}</h2>\n\
+            <p>So lief es heute an der Spitze des Feldes:</p>\n\
+            {%- for (heading, ranking) in [('Top 5 bei Teil 1', ranking_p1), ('Top 5 bei Teil 2', ranking_p2), ('Top 5 beim Delta', ranking_delta)] %}\n\
+                <h3>{{heading}} 🏁</h3>\n\
+                <table>\n\
+                {%- for (prefix, name, time) in ranking %}\n\
+                    <tr><td>{{prefix}}</td><td style='font-family: monospace;'>{{time|trim}}</td><td><b>{{name}}</b></td></tr>\n\
+                {%- else %}\n\
+                    <tr><td>Noch niemand</td></tr>\n\
+                {%- endfor %}\n\
+                </table>\n\
+            {%- endfor %}\n\
+            {%- if negative_splits %}\n\
+                <h3>⚡ Negative Splits (Teil 2 schneller als Teil 1)</h3>\n\
+                <ul>\n\
+                {%- for (name, part1, delta) in negative_splits %}\n\
+                    <li><b>{{name}}</b>: Teil 1 in {{part1}}, Teil 2 in {{delta}}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            </body>\n\
+            </html>"
+        }
+        MessageTemplate::WeeklyRecapEmail => {
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head><meta charset='utf-8'><title>Wochenrückblick des Events {{ recap.year }}</title></head>\n\
+            <body style='font-family: sans-serif;'>\n\
+            <h2>🗞️ Wochenrückblick des Events {{ recap.year }}</h2>\n\
+            <p>Das ist in den letzten 7 Tagen passiert.</p>\n\
+            <h3>⭐ Gesammelte Sterne</h3>\n\
+            <ul>\n\
+            {%- for (name, n_stars) in recap.stars %}\n\
+                <li><b>{{ name }}</b>: {{ n_stars }} Stern{{ 'e' if n_stars > 1 }}</li>\n\
+            {%- endfor %}\n\
+            </ul>\n\
+            {%- if recap.climbs %}\n\
+                <h3>📈 Größte Aufsteiger in der Gesamtwertung</h3>\n\
+                <ul>\n\
+                {%- for (name, previous, current) in recap.climbs %}\n\
+                    <li><b>{{ name }}</b>: von Platz {{ previous }} auf Platz {{ current }}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            {%- if recap.jerseys %}\n\
+                <h3>🚴 Trikots</h3>\n\
+                <ul>\n\
+                {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                    <li>{{ {'yellow': 'Gelbes', 'green': 'Grünes', 'combative': 'Kämpfer-'}[jersey] }}{{ ' ' if jersey != 'combative' }}Trikot: <b>{{ holder }}</b>\n\
+                    {%- if previous and previous != holder %} ({{ previous }} abgenommen)\n\
+                    {%- elif previous %} (verteidigt)\n\
+                    {%- endif %}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            {%- if recap.fastest_delta %}\n\
+                <p>⚡ Schnellstes Delta der Woche: <b>{{ recap.fastest_delta[0] }}</b> an Tag {{ recap.fastest_delta[1] }}, in <b>{{ recap.fastest_delta[2] }}</b></p>\n\
+            {%- endif %}\n\
+            </body>\n\
+            </html>"
+        }
+        MessageTemplate::AwardsCeremony => {
+            "🏆 *Siegerehrung* des Events {{ awards.year }}! Der Vorhang fällt über den Rätseln, Zeit, die Champions der Saison zu feiern:
+            {%- if awards.jerseys %}\n\
+                🚴 Trikots:
+                {%- for (jersey, winner) in awards.jerseys %}\n\
+                    \x20 • {% if jersey == 'yellow' %}Gelbes Trikot{% elif jersey == 'green' %}Grünes Trikot{% else %}Trikot {{ jersey }}{% endif %}: *{{ winner }}*
+                {%- endfor %}
+            {%- endif %}
+            {%- if awards.most_combative %}\n\
+                🥋 Kämpferischste Person: *{{ awards.most_combative[0] }}*, mit {{ awards.most_combative[1] }} Punkten kurz vor den nächsten Freischaltungen
+            {%- endif %}\n\
+            {{ theme.star }} Die meisten Sterne:
+            {%- for (name, n_stars) in awards.most_stars %}\n\
+                \x20 {{ ['🥇', '🥈', '🥉'][loop.index0] }} *{{ name }}*: {{ n_stars }} Stern{{ 'e' if n_stars > 1 }}
+            {%- endfor %}
+            {%- if awards.best_delta %}\n\
+                ⚡ Bestes Delta der Saison: *{{ awards.best_delta[0] }}* an Tag {{ awards.best_delta[1] }}, in *{{ awards.best_delta[2] }}*
+            {%- endif %}
+            {%- if awards.comeback %}\n\
+                📈 Größte Aufholjagd: *{{ awards.comeback[0] }}*, von Platz {{ awards.comeback[1] }} auf Platz {{ awards.comeback[2] }} der Gesamtwertung
+            {%- endif %}\n\
+            Danke an alle fürs Mitspielen, bis zum nächsten Dezember 🎄!"
+        }
+        MessageTemplate::Guess => {
+            "🔮 Tipp registriert! Du setzt auf *{{ guess }}* für den 100. Teilnehmer, der Teil 2 in der globalen Rangliste \
+            von *Tag {{ day }}* abschließt. Du kannst deine Meinung ändern, bis das Rätsel freigeschaltet wird."
+        }
+        MessageTemplate::GuessResults => {
+            "🔮 Der 100. Teilnehmer, der Teil 2 in der globalen Rangliste von *Tag {{ day }}* abgeschlossen hat, brauchte *{{ cutoff }}*. \
+            {{ 'Knappste Tipps' if winners|length > 1 else 'Knappster Tipp' }} von {{ n_guesses }}:
+            {%- for (user, guess, error) in winners %}\n\
+                \x20 • {{ user }} mit *{{ guess }}* ({{ error }} daneben)
+            {%- endfor %}"
+        }
+        MessageTemplate::Predictions => {
+            "{%- if standings -%}
+                🔮 Wertung des Tippspiels des Events {{ year }}:
+                {%- for (user, wins, n_guesses, average_error) in standings %}\n\
+                    \x20 {{ loop.index }}. {{ user }}: *{{ wins }}* Sieg{{ 'e' if wins != 1 }} \
+                    bei {{ n_guesses }} Tipp{{ 's' if n_guesses > 1 }}, im Schnitt {{ average_error }} daneben
+                {%- endfor %}
+            {%- else -%}
+                🤷 Noch niemand hat beim Tippspiel des Events {{ year }} mitgespielt. \
+                Tippe die Zeit des 100. Teilnehmers, der Teil 2 in der globalen Rangliste abschließt, mit `{{ command_prefix }}guess <mm:ss>`!
+            {%- endif %}"
+        }
+        MessageTemplate::WhatIf => {
+            "🧮 Was wäre, wenn *{{ projection.name }}* Tag {{ projection.day }} des Events {{ projection.year }} jetzt lösen würde?\n\
+                \x20 • {{ theme.star }} *{{ projection.n_stars }}* weitere{{ 'r' if projection.n_stars == 1 }} Stern{{ 'e' if projection.n_stars > 1 }}, *{{ projection.points }}* Punkte wert\n\
+                \x20 • 🏁 Platzierung: {% if projection.rank[0] == projection.rank[1] %}*{{ new_rank }}* (unverändert){% else %}von {{ rank }} auf *{{ new_rank }}*{% endif %}
+            {%- if projection.overtaken %} und überholt {{ projection.overtaken|join(', ') }}{% endif %}
+            {%- for (jersey, before, after) in jerseys %}{% if after %}\n\
+                \x20 • 🚴 {{ {'yellow': 'Gelbes', 'green': 'Grünes', 'combative': 'Kämpfer-'}[jersey] }}{{ ' ' if jersey != 'combative' }}Trikot: {% if before and before != after %}von {{ before }} auf *{{ after }}*{% elif before %}*{{ after }}* (unverändert){% else %}*{{ after }}*{% endif %}
+            {%- endif %}{% endfor %}"
+        }
+        MessageTemplate::Jobs => {
+            "⚙️ *Geplante Aufgaben*
+            {%- for job in jobs %}\n\
+                \x20 • {% if job.running_since %}⏳{% elif job.succeeded %}✅{% else %}❌{% endif %} `{{ job.name }}`: \
+                {{ job.runs }} Ausführung{{ 'en' if job.runs != 1 }} ({{ job.failed_runs }} fehlgeschlagen)
+                {%- if job.last_run_at %}, zuletzt am {{ job.last_run_at }} in {{ job.last_run_ms }}ms{% endif %}
+                {%- if job.running_since %}, läuft seit {{ job.running_since }}{% endif %}
+                {%- if job.last_error %}\n\
+                    \x20     ⚠️ Letzter Fehler am {{ job.last_error_at }}: `{{ job.last_error }}`
+                {%- endif %}
+            {%- else %}\n\
+                💤 Seit dem Start des Bots wurde keine Aufgabe ausgeführt.
+            {%- endfor %}"
+        }
+        MessageTemplate::Missing => {
+            "{%- if members -%}
+                🐢 *{{ members|length }}* Mitglied{{ 'er' if members|length > 1 }} ohne einen einzigen Stern an Tag {{ day }}{% if not current_year %} von {{ year }}{% endif %}, \
+                die Stammspieler zuerst:
+                {%- for (name, n_days) in members %}\n\
+                    \x20 • {{ name }}{% if day > 1 %} (an {{ n_days }} von {{ day - 1 }} Vortag{{ 'en' if day > 2 }} gespielt){% endif %}
+                {%- endfor %}
+            {%- else -%}
+                🎉 Alle haben an Tag {{ day }}{% if not current_year %} von {{ year }}{% endif %} mindestens einen Stern gesammelt!
+            {%- endif %}"
+        }
+        MessageTemplate::Heroes => {
+            "{%- if heroes -%}
+                🦸 Auftritte unserer Mitglieder in der globalen Bestenliste, \
+                über {{ n_scanned }} erfasste globale Bestenliste{{ 'n' if n_scanned != 1 }}:
+                {%- for hero in heroes %}\n\
+                    \x20 {{ loop.index }}. *{{ hero.name }}*: *{{ hero.appearances }}* Auftritt{{ 'e' if hero.appearances > 1 }} \
+                    in {{ hero.years|length }} Event{{ 's' if hero.years|length > 1 }}, \
+                    bester Rang *{{ hero.rank }}* (Teil {{ hero.part }} von Tag {{ hero.day }} {{ hero.year }})
+                {%- endfor %}
+            {%- else -%}
+                🦸 Bisher ist keines unserer Mitglieder in den {{ n_scanned }} erfassten globalen Bestenliste{{ 'n' if n_scanned != 1 }} aufgetaucht.
+            {%- endif %}"
+        }
+        MessageTemplate::AppHome => {
+            "🎄 *Advent of Code {{ year }}*\n\
+            {%- if review %}\n\
+                Dein bisheriges Event, *{{ review.name }}*:\n\
+                \x20 • {{ theme.star }} *{{ review.n_stars }}* Sterne gesammelt, insgesamt *{{ review.score }}* Punkte\n\
+                \x20 • 🏁 Position: *{{ rank }}* von {{ review.n_members }}
+                {%- if review.best_day %}\n\
+                    \x20 • 🚀 Bester Tag: *Tag {{ review.best_day[0] }}*, mit *{{ review.best_day[1] }}* Punkten
+                {%- endif %}
+                {%- if review.average_delta %}\n\
+                    \x20 • ⏱️ Durchschnittliche Delta-Zeit: *{{ review.average_delta }}*
+                {%- endif %}\n\
+                \x20 • 📈 Platzierungsverlauf: `{{ review.rank_chart }}`
+            {%- elif linked %}\n\
+                Du hast im Event {{ year }} noch keinen Stern gesammelt, die Rätsel warten auf dich! {{ theme.star }}
+            {%- else %}\n\
+                🔗 Verknüpfe dich mit `{{ command_prefix }}iam <name>` mit einem Mitglied der Bestenliste, um hier deine eigenen Statistiken zu sehen.
+            {%- endif %}\n\n\
+            🚴 *Trikots*
+            {%- for (jersey, leaders) in jerseys %}\n\
+                \x20 • {{ {'yellow': 'Gelbes', 'green': 'Grünes', 'combative': 'Kämpfer-'}[jersey] }}{{ ' ' if jersey != 'combative' }}Trikot: {% for (name, value) in leaders %}{{ loop.index }}. *{{ name }}* ({{ value }}){{ ', ' if not loop.last }}{% else %}noch niemand{% endfor %}
+            {%- endfor %}"
+        }
+        MessageTemplate::Burndown => {
+            "{%- if chart -%}
+                📉 Noch zu sammelnde Sterne an Tag {{ day }}{% if not current_year %} von {{ year }}{% endif %}, \
+                Stunde für Stunde nach der Veröffentlichung des Rätsels:\n\
+                ```{{ chart }}```
+            {%- else -%}
+                🤷 Niemand hat innerhalb von 24 Stunden nach der Veröffentlichung von Tag {{ day }}{% if not current_year %} von {{ year }}{% endif %} einen Stern gesammelt.
+            {%- endif %}"
+        }
+        MessageTemplate::Heatmap => {
+            "{%- if chart -%}
+                🌡️ Gesammelte Sterne an jedem Tag des Events {{ year }}, \
+                {%- if hours %} pro Stunde nach der Veröffentlichung des Rätsels
+                {%- elif n_members > max_members %} durch die {{ max_members }} Mitglieder mit den meisten Sternen
+                {%- else %} pro Mitglied{% endif %} (je dunkler, desto mehr los):\n\
+                ```{{ chart }}```
+            {%- else -%}
+                🤷 Im Event {{ year }} hat noch niemand einen Stern gesammelt.
+            {%- endif %}"
+        }
+        MessageTemplate::FullCompletions => {
+            "{%- if completions -%}
+                🌟 *Ruhmeshalle {{ year }}*, die Mitglieder, die alle 50 Sterne gesammelt haben:
+                {%- for c in completions %}\n\
+                    \x20 {{ loop.index }}. *{{ c.name }}*, {{ c.time }} nach der letzten Veröffentlichung ({{ c.date }})
+                {%- endfor %}
+            {%- else -%}
+                🤷 Im Event {{ year }} hat noch niemand alle 50 Sterne gesammelt.
+            {%- endif %}"
+        }
+        MessageTemplate::Gaps => {
+            "{%- if gaps -%}
+                ⏱️ *Abstände {{ 'des Gelben Trikots' if yellow else 'des lokalen Punktestands' }} {{ year }}*, zum Vordermann (und zum Führenden):\n\
+                ```{{ gaps }}```
+                {%- if closest %}\n\
+                    🔥 Engstes Duell: *{{ closest.behind }}* liegt \
+                    {%- if yellow %} {{ closest.gap }}{% else %} {{ closest.points }} Punkt{{ 'e' if closest.points != 1 }}{% endif %} hinter *{{ closest.ahead }}*.
+                {%- endif %}
+            {%- else -%}
+                🤷 Im Rennen von {{ year }} ist noch niemand.
+            {%- endif %}"
+        }
+        MessageTemplate::AuditLog => {
+            "📜 *Letzte {{ messages|length }} gesendete Nachricht{{ 'en' if messages|length != 1 }}*, die neueste zuerst
+            {%- for m in messages %}\n\
+                \x20 • {{ '✅' if m.ts else ('🖼️' if not m.error else '❌') }} {{ m.at }} `{{ m.event }}` in <#{{ m.channel }}>{% if m.ts %} (ts {{ m.ts }}){% endif %}: `{{ m.excerpt }}`
+                {%- if m.error %}\n\
+                    \x20     ⚠️ `{{ m.error }}`
+                {%- endif %}
+            {%- else %}\n\
+                📭 Seit dem Start des Protokolls wurde keine Nachricht gesendet.
+            {%- endfor %}"
+        }
+        MessageTemplate::Practice => {
+            "{%- if day -%}
+                🎯 Wie wäre es mit *Tag {{ day }} von {{ puzzle_year }}*? {{ 'Beide Sterne warten' if missing_stars == 2 else 'Der zweite Stern wartet' }} noch auf dich: {{ url }}
+                {%- if difficulty %}\n\
+                    \x20 • Schwierigkeitsindex: *{{ difficulty }}*, schwerer als {{ percentile }} % der {{ n_days }} anderen auf der globalen Rangliste erfassten Tage
+                {%- endif %}
+            {%- else -%}
+                🏆 Du hast alle bisher veröffentlichten Rätsel{{ ' von ' ~ year if year }} gelöst, nichts mehr zum Üben!
+            {%- endif %}"
+        }
+        MessageTemplate::Splits => {
+            "⚡ *Negative Splits* des Events {{ year }}, Teil 2 schneller gelöst als Teil 1
+            {%- for (name, n_splits, n_days) in members %}\n\
+                {{ loop.index }}. *{{ name }}*: {{ n_splits }} Negative Split{{ 's' if n_splits > 1 }} bei {{ n_days }} abgeschlossenen Tag{{ 'en' if n_days > 1 }}
+            {%- else %}\n\
+                Bisher hat niemand einen Teil 2 schneller gelöst als den Teil 1.
+            {%- endfor %}"
+        }
+        MessageTemplate::Rank => {
+            "🏁 *{{ rank.name }}* im Event {{ rank.year }}: *{{ position }}* von {{ rank.n_members }}, \
+            mit *{{ rank.score }}* Punkten und {{ rank.n_stars }} {{ theme.star }}
+            {%- if rank.ahead %}\n\
+                \x20 • ⬆️ {{ rank.ahead[1] }} Punkt{{ 'e' if rank.ahead[1] != 1 }} hinter *{{ rank.ahead[0] }}*
+            {%- endif %}
+            {%- if rank.behind %}\n\
+                \x20 • ⬇️ {{ rank.behind[1] }} Punkt{{ 'e' if rank.behind[1] != 1 }} vor *{{ rank.behind[0] }}*
+            {%- endif %}
+            {%- for (jersey, position) in jerseys %}{% if position %}\n\
+                \x20 • 🚴 {{ {'yellow': 'Gelbes', 'green': 'Grünes', 'combative': 'Kämpfer-'}[jersey] }}{{ ' ' if jersey != 'combative' }}Trikot: *{{ position }}*
+            {%- endif %}{% endfor %}"
+        }
+        MessageTemplate::LanguagePoll => {
+            "🗳️ Welche Sprache hast du heute verwendet? Stimme mit den Reaktionen unten ab:
+            {%- for (reaction, language) in languages %} {{ reaction }} {{ language }}{{ ',' if not loop.last }}{% endfor %}"
+        }
+        MessageTemplate::Languages => {
+            "🗳️ *Sprachen* des Events {{ year }}, nach den täglichen Umfragen der Lösungs-Threads
+            {%- for (language, votes, n_days) in languages %}\n\
+                {{ loop.index }}. *{{ language }}*: {{ votes }} Stimme{{ 'n' if votes > 1 }} an {{ n_days }} Tag{{ 'en' if n_days > 1 }}
+            {%- else %}\n\
+                Bisher hat niemand in den Umfragen abgestimmt.
+            {%- endfor %}"
+        }
+        MessageTemplate::Latency => {
+            "{%- if latency -%}
+                ⏱️ *{{ latency.n_stars }}* Stern{{ 'e' if latency.n_stars > 1 }} des Events {{ year }} vom Bot erkannt, im Schnitt *{{ latency.average }}* nach dem Lösen (Median {{ latency.median }}, höchstens {{ latency.max }}).\n\
+                {{ latency.within_refresh }} % davon wurden innerhalb von {{ latency.refresh_min }} Minuten erkannt, dem Intervall zwischen zwei Aktualisierungen des privaten Leaderboards.
+            {%- else -%}
+                ⏱️ Der Bot hat noch keinen Stern des Events {{ year }} erkannt.
+            {%- endif %}"
+        }
+        MessageTemplate::Query => {
+            "🔎 *{{ result.n_matches }}* Stern{{ 'e' if result.n_matches != 1 }} des Events {{ result.year }}\
+            {%- if result.query %} passend zu `{{ result.query }}`{% endif %}\
+            {%- if result.rows|length < result.n_matches %} (die ersten {{ result.rows|length }} aufgelistet){% endif %}{{ ':' if result.rows }}
+            {%- for row in result.rows %}\n\
+                \x20 {{ loop.index }}. *{{ row.name }}*: Tag {{ row.day }} Teil {{ row.part }} in {{ row.time }}
+            {%- endfor %}"
+        }
+        MessageTemplate::Preview => {
+            "🧪 Vorschau von `{{ template }}` im Monitoring-Kanal gepostet\
+            {%- if failed %}, sie konnte aber nicht dargestellt werden ⚠️{% endif %}."
+        }
+        MessageTemplate::TemplatePreview => {
+            "{%- if error -%}
+                ⚠️ `{{ template }}` konnte mit den Beispielwerten nicht dargestellt werden:\n```{{ error }}```
+            {%- else -%}
+                🧪 Vorschau von `{{ template }}` mit Beispielwerten:\n{{ rendered }}
+            {%- endif %}"
+        }
+        MessageTemplate::CommandCooldown => {
+            "⏳ Immer mit der Ruhe! `{{ command_prefix }}{{ command }}` wurde in der letzten Minute oft angefragt, bitte versuch es in {{ wait_sec }} Sekunde{{ 'n' if wait_sec != 1 }} noch einmal 🙏."
+        }
+        MessageTemplate::InvalidOption => {
+            "🤔 `{{ option }}` ist keine Option von `{{ command_prefix }}{{ command }}`\
+            {%- if suggestion %}, meintest du `{{ suggestion }}`?{% else %}.{% endif %} \
+            Gültige Optionen sind {% for value in valid %}`{{ value }}`{{ ', ' if not loop.last }}{% endfor %}."
+        }
+        MessageTemplate::PersonalReminder => {
+            "⏰ Freundliche Erinnerung von deinem Lieblingswichtel, im Event {{ year }} warten noch Sterne auf dich:\n\
+            {%- for (day, n_missing) in missing %}\n\
+                \x20 • Tag {{ day }}: {{ n_missing }} Stern{{ 'e' if n_missing > 1 }} zu sammeln
+            {%- endfor %}"
+        }
+        MessageTemplate::WatchAlerts => {
+            "👀 Neuigkeiten von den Mitgliedern, denen du im Event {{ year }} folgst:\n\
+            {%- for (name, day, is_first, position) in alerts %}\n\
+                \x20 • {% if position %}🏎️ *{{ name }}* hat dich in der Gesamtwertung überholt, jetzt {{ position }}\
+                {%- else %}⭐ *{{ name }}* hat den {{ 'ersten' if is_first else 'zweiten' }} Stern von Tag {{ day }} gesammelt{% endif %}
+            {%- endfor %}"
+        }
+        MessageTemplate::LeaderboardDisplay => {
+            "{%- if current_year -%}
+                📓 Aktuelle Rangliste nach {% if scoring_method == 'local' %}*lokalen Punkten*{% elif scoring_method == 'percentile' %}*Perzentil-Punkten*{% else %}*Anzahl der Sterne*{% endif %}, Stand {{timestamp}}{{ ' (Seite ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }}:
+            {%- else -%}
+                📓 Rangliste nach {% if scoring_method == 'local' %}*lokalen Punkten*{% elif scoring_method == 'percentile' %}*Perzentil-Punkten*{% else %}*Anzahl der Sterne*{% endif %} des Events {{ year }}{{ ' (Seite ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }}:
+            {%- endif %}
+            {%- if detailed %}\n\
+                _Letzte Spalten: Median der Zeit für Teil 2 und durchschnittliches Delta im Event._
+            {%- endif %}\n\
+            ```{{ leaderboard }}```"
+        }
+        MessageTemplate::Wins => {
+            "{%- if current_year -%}
+                🏅 Von jedem Mitglied gewonnene Tage, Stand {{timestamp}}:
+            {%- else -%}
+                🏅 Von jedem Mitglied gewonnene Tage während des Events *{{year}}*:
+            {%- endif %}\n\
+            ```{{ wins }}```"
+        }
+        MessageTemplate::AllTime => {
+            "🏛️ Ewige Rangliste über {{ years|length }} gespeicherte{{ 's' if years|length == 1 }} Event{{ 's' if years|length > 1 }}\
+            {% if years %} ({{ years|first }}-{{ years|last }}){% endif %}, nach {{ 'Sternen' if scoring == 'stars' else 'Punkten' }}, Stand {{timestamp}}:\n\
+            ```{{ standings }}```\n\
+            _Die Punkte werden pro Event normiert, die beste Punktzahl jedes Events ist 100 Punkte wert._"
+        }
+        MessageTemplate::Faq => {
+            "{%- if answer -%}
+                ❓ *{{ answer.question }}*\n\
+                {{ answer.answer }}
+            {%- elif requested -%}
+                🤷 Ich konnte keine Antwort zu *{{ requested }}* finden
+                {%- if entries %}, meintest du eines davon?
+                    {%- for entry in entries %}\n\
+                        \x20 • `{{ entry.topic }}`: {{ entry.question }}
+                    {%- endfor %}
+                {%- else %}. Mit `{{ command_prefix }}faq` werden die verfügbaren Themen aufgelistet.
+                {%- endif %}
+            {%- elif entries -%}
+                📚 Dabei kann ich dir helfen (`{{ command_prefix }}faq <topic>`):
+                {%- for entry in entries %}\n\
+                    \x20 • `{{ entry.topic }}`: {{ entry.question }}
+                {%- endfor %}
+            {%- else -%}
+                📚 Für dieses Event wurden noch keine FAQ eingerichtet.
+            {%- endif %}"
+        }
+        MessageTemplate::Link => {
+            "🔗 Alles, was du für *Tag {{day}}* des Events *{{year}}* brauchst:\n\
+                \x20 • 🧩 <{{puzzle}}|Rätsel>\n\
+                \x20 • 🎄 <{{private_leaderboard}}|Private Rangliste>\n\
+                \x20 • 🌍 <{{global_leaderboard}}|Globale Rangliste>"
+        }
+        MessageTemplate::BestSolutions => {
+            "{%- if solutions -%}
+                🗳️ Die beliebtesten Antworten im Lösungs-Thread von *Tag {{day}}* (Event {{year}}):
+                {%- for (permalink, author, votes) in solutions %}\n\
+                    \x20 {{ loop.index }}. <{{ permalink }}|Lösung> von {{ author }} ({{ votes }} Reaktion{{ 'en' if votes > 1 }})
+                {%- endfor %}
+            {%- else -%}
+                🤷 Keine Antwort im Lösungs-Thread von *Tag {{day}}* (Event {{year}}) hat bisher eine Reaktion erhalten. \
+                Reagiere auf die Lösungen, die dir gefallen, um für sie zu stimmen!
+            {%- endif %}"
+        }
+        MessageTemplate::TdfStandings => {
+            "{%- if current_year and not day -%}
+                🚴 Aktuelle Wertung des {{ '🟡 Gelben 🛵' if jersey=='yellow' else ('🟢 Grünen 🍏' if jersey=='green' else ('⚫Kämpfer 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Trikots, Stand {{timestamp}}:
+            {%- elif not day -%}
+                🚴 Wertung des {{ '🟡 Gelben 🛵' if jersey=='yellow' else ('🟢 Grünen 🍏' if jersey=='green' else ('⚫Kämpfer 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Trikots des Events *{{year}}*:
+            {%- else -%}
+                🚴 Wertung des {{ '🟡 Gelben 🛵' if jersey=='yellow' else ('🟢 Grünen 🍏' if jersey=='green' else ('⚫Kämpfer 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} Trikots für *Tag {{day}}* des Events {{year}}:
+            {%- endif %}
+            {%- if drop_worst %} _(ohne die {{drop_worst}} schlechtesten Tag{{ 'e' if drop_worst > 1 }} jedes Mitglieds)_{% endif %}\n\
+            ```{{ standings }}```"
+        }
+        MessageTemplate::TdfStage => {
+            "{%- if current_day -%}
+                🚴 Ergebnisse der *Etappe {{day}}* des 🟡 Gelben 🛵 Trikots, Stand {{timestamp}}:
+            {%- else -%}
+                🚴 Ergebnisse der *Etappe {{day}}* des 🟡 Gelben 🛵 Trikots des Events {{year}}:
+            {%- endif %}
+            {%- if winner %}\n\
+                🏆 Etappensieger: *{{ winner }}*
+            {%- endif %}
+            {%- if stage %}\n\
+                ```{{ stage }}```
+            {%- else %}\n\
+                Noch niemand ist zu dieser Etappe gestartet.
+            {%- endif %}"
+        }
+    }
+}
@@ -0,0 +1,896 @@
+use super::MessageTemplate;
+
+// Same variables as the embedded (english) templates. Command names and arguments are not
+// translated, as the commands parser only understands them in english.
+pub fn template(template: &MessageTemplate) -> &'static str {
+    match template {
+        MessageTemplate::Help => {
+            "🗒️ Bien joué, vous avez trouvé le *manuel des commandes du CEO*.\n\
+            Le système d'analyse des arguments des commandes est une merveille d'ingénierie regex, \
+            l'ordre des arguments optionnels d'une commande n'a donc pas (ou du moins ne devrait pas avoir...) d'importance.\n\n\
+            👉 🆘 *Mode d'emploi*\n\
+            ```{{ command_prefix }}help```\n\
+            Liste et explique les commandes du bot. Vous êtes en train de le lire.\n\n\
+            👉 🏎️ *Les plus rapides de l'Ouest !*\n\
+            ```{{ command_prefix }}fast [ranking method] [day] [year]```\n\
+            Temps le(s) plus rapide(s) du jour. Par défaut, le classement est basé sur le temps `delta` du jour, \
+            mais les classements `p1` et `p2` sont aussi disponibles. La méthode `limit` donne le classement des fins \
+            les plus proches de la limite (c.-à-d. le moins de temps avant la sortie du puzzle suivant), ces temps \
+            attribuant les points du maillot `{{ command_prefix }}tdf combative`. \
+            Sans jour et/ou année, le jour et/ou l'année en cours sont utilisés.\n\
+            ```{{ command_prefix }}fast <avg | median> [first day-last day] [year]```\n\
+            Meilleur temps delta moyen (`avg`) ou médian (`median`) sur une plage de jours (p. ex. `{{ command_prefix }}fast avg 1-10`), \
+            tout l'événement par défaut. Seuls les jours dont les deux parties sont terminées comptent, les membres ayant \
+            terminé le plus de jours de la plage étant classés en premier.\n\n\
+            👉 ⚖️ *Mains sûres*\n\
+            ```{{ command_prefix }}delta [avg | median] [minimum days] [year]```\n\
+            Les membres les plus réguliers entre les deux parties : classement par temps delta moyen (`avg`, par défaut) ou \
+            médian (`median`) sur tous les jours dont les deux parties sont terminées, seuls les membres ayant terminé au moins \
+            le nombre minimum de jours (5 par défaut) étant classés.\n\n\
+            👉 📊 *Montrez-moi le tableau !*\n\
+            ```{{ command_prefix }}board [ranking method] [detailed] [year]```\n\
+            Score et étoiles de l'année, sous forme d'un joli tableau ascii. Le classement par défaut est basé sur le score \
+            `local` de l'année en cours, mais le classement par nombre d'étoiles (`stars`) est aussi disponible, ainsi que par \
+            score par percentile (`percentile` : 10, 8, 6, 4 ou 2 points par étoile selon la part des membres l'ayant obtenue avant : \
+            top 10 %, 25 %, 50 %, 75 % ou les autres), moins sévère que le score local sur les grands classements. Le tableau \
+            détaillé (`detailed`) ajoute le temps médian de la partie 2 et le delta moyen de chaque membre. Les grands \
+            tableaux sont paginés, utilisez les boutons sous le tableau pour changer de page ou d'année.\n\n\
+            👉 🚴 *La longue échappée !*\n\
+            ```{{ command_prefix }}tdf [jersey color] [day] [year]```\n\
+            Les classements façon Tour de France ! Rejoignez le peloton pour décrocher le maillot jaune (`yellow`), \
+            ou accumulez les points des convoités maillots vert (`green`) et combatif (`combative`). \
+            Par défaut, classement du maillot jaune de l'année en cours.\n\
+            - le classement du maillot `yellow` est basé sur le temps cumulé de la résolution complète (partie 2) de chaque jour \
+            (une pénalité de `{{ jersey_points.yellow_penalty }}` est appliquée pour chaque jour non résolu, ou résolu en plus de temps que la pénalité). \
+            Ajoutez `dropworst=<N>` (p. ex. `{{ command_prefix }}tdf yellow dropworst=3`) pour ne pas compter les N pires jours de chaque membre. \
+            Les admins peuvent déclarer les jours d'absence d'un membre (p. ex. en vacances) avec \
+            `{{ command_prefix }}tdf absence <name or id> <day | first day-last day> [year]`, ces jours comptant le temps médian du membre au lieu d'une pénalité s'ils ne sont pas résolus. \
+            Précisez un jour pour voir les résultats de l'étape, classés sur le temps de la partie 2.\n\
+            - les points du maillot `green` se gagnent chaque jour en fonçant entre la partie 1 et la partie 2 ! Les \
+            {{ jersey_points.green_points|length }} membres les plus rapides gagnent {{ jersey_points.green_points|join(', ') }} points.\n\
+            - les points du maillot `combative` récompensent chaque jour les âmes courageuses qui ne jettent pas l'éponge trop tôt \
+            et terminent un jour avant le début du suivant... Plus c'est proche de la limite, plus on gagne de points \
+            (jusqu'à {{ jersey_points.combative_max_points|int }}) !\n\
+            {% if jersey_points.custom %}- maillots personnalisés de ce classement : {% for custom in jersey_points.custom %}`{{ custom.name }}` ({{ 'points' if custom.aggregation == 'points' else 'temps' }} {{ custom.metric }}){{ ', ' if not loop.last }}{% endfor %}.\n{% endif %}\n\
+            👉 🏅 *Qui domine les jours ?*\n\
+            ```{{ command_prefix }}wins [year]```\n\
+            Nombre de jours gagnés par chaque membre, c.-à-d. le plus rapide sur la partie 1 (`p1`), la partie 2 (`p2`) ou le temps `delta`. \
+            Sans année, l'année en cours est utilisée.\n\n\
+            👉 🏛️ *Panthéon des légendes*\n\
+            ```{{ command_prefix }}alltime [score | stars]```\n\
+            Classement de tous les temps sur tous les événements connus du bot, par `score` (par défaut) ou par nombre d'étoiles (`stars`). \
+            Les scores sont normalisés par événement (le meilleur score de chaque événement valant 100 points), pour que les \
+            événements avec plus de membres ne pèsent pas plus.\n\n\
+            👉 📚 *Foire aux questions*\n\
+            ```{{ command_prefix }}faq [topic]```\n\
+            Réponses aux questions sur l'événement (lots, règles, ...). Liste les sujets disponibles si aucun sujet n'est précisé.\n\n\
+            👉 🔗 *Où est-ce ?*\n\
+            ```{{ command_prefix }}link [day] [year]```\n\
+            Liens vers le puzzle, le classement privé et le classement mondial du jour. \
+            Sans jour et/ou année, le jour et/ou l'année en cours sont utilisés.\n\n\
+            👉 🗳️ *Temple de la renommée*\n\
+            ```{{ command_prefix }}bestsolutions [day] [year]```\n\
+            Réponses les plus appréciées du fil des solutions du jour, selon les réactions reçues. \
+            Sans jour et/ou année, le jour et/ou l'année en cours sont utilisés.\n\n\
+            👉 ⏰ *Ne me laissez pas oublier !*\n\
+            ```{{ command_prefix }}remindme <name or id | off>```\n\
+            Recevez un message privé chaque soir de l'événement listant les jours où il vous reste des étoiles à récolter. \
+            Utilisez votre nom AoC (ou identifiant numérique) tel qu'affiché sur le classement, ou `off` pour arrêter les rappels.\n\n\
+            👉 👀 *À l'œil*\n\
+            ```{{ command_prefix }}watch [off] <name or id>```\n\
+            Recevez un message privé chaque fois que le membre récolte une étoile, ou vous dépasse au classement si vous êtes associé \
+            à un membre avec `{{ command_prefix }}iam`. Utilisez `off` pour ne plus suivre le membre, ou `off` seul pour ne plus suivre personne.\n\n\
+            👉 🪪 *Qui suis-je ?*\n\
+            ```{{ command_prefix }}iam <name or id>```\n\
+            Dites-moi quel membre du classement vous êtes, pour que je vous mentionne quand vous gagnez des étoiles ou \
+            entrez dans le classement mondial. Un membre déjà lié à un autre utilisateur ne peut pas être choisi.\n\n\
+            👉 🎁 *C'est dans la boîte !*\n\
+            ```{{ command_prefix }}review <on | off>```\n\
+            Une fois l'événement terminé, les membres liés avec `{{ command_prefix }}iam` reçoivent leur bilan personnel de l'année \
+            (si activé sur ce bot). Utilisez `off` pour ne pas le recevoir, ou `on` pour le recevoir à nouveau.\n\n\
+            👉 📬 *Comment je m'en suis sorti ?*\n\
+            ```{{ command_prefix }}mysummary <on | off>```\n\
+            Recevez un message privé après chaque résumé quotidien avec vos temps, les points gagnés et l'évolution de votre \
+            position du jour (si activé sur ce bot). Vous devez d'abord être lié à un membre avec `{{ command_prefix }}iam`.\n\n\
+            👉 🥷 *Incognito*\n\
+            ```{{ command_prefix }}optout <on | off>```\n\
+            Apparaissez comme un lutin anonyme dans les messages au lieu de votre nom, vos étoiles comptant toujours dans le classement. \
+            Vous devez d'abord être lié à un membre avec `{{ command_prefix }}iam`.\n\n\
+            👉 🔮 *Boule de cristal*\n\
+            ```{{ command_prefix }}guess [mm:ss | year]```\n\
+            Pariez sur le temps du 100e finisseur de la partie 2 du classement mondial de la prochaine énigme, avant son ouverture. \
+            Le pari le plus proche est annoncé avec les statistiques du classement mondial. Sans temps, affiche le classement \
+            du jeu de pronostics de l'événement (nombre de jours gagnés, puis erreur moyenne).\n\n\
+            👉 🧮 *Et si...*\n\
+            ```{{ command_prefix }}whatif <name or id> completes day <day> [year]```\n\
+            Points, position et classements des maillots projetés si le membre terminait les parties manquantes du jour maintenant. \
+            Rien de tel que de voir les places à gagner pour se remettre à un puzzle !\n\n\
+            👉 🏁 *Où en suis-je ?*\n\
+            ```{{ command_prefix }}rank <name or id> [year=<year>]```\n\
+            Position, score et étoiles d'un seul membre, avec les points à rattraper sur le membre devant, l'avance \
+            sur le membre derrière et les positions dans les maillots. Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 🐢 *Qui manque à l'appel ?*\n\
+            ```{{ command_prefix }}missing [day] [year]```\n\
+            Membres sans aucune étoile le jour donné, les habitués en premier, pour pouvoir leur donner un petit coup de pouce. \
+            Sans jour et/ou année, le jour et/ou l'année en cours sont utilisés.\n\n\
+            👉 🦸 *Héros mondiaux*\n\
+            ```{{ command_prefix }}heroes```\n\
+            Toutes les apparitions de nos membres dans les classements mondiaux enregistrés par le bot, avec leur meilleur rang.\n\n\
+            👉 📉 *Burn-down*\n\
+            ```{{ command_prefix }}burndown [day] [year]```\n\
+            Étoiles restant à récolter heure par heure pendant les 24 heures suivant la publication du puzzle. \
+            Sans jour et/ou année, le jour et/ou l'année en cours sont utilisés.\n\n\
+            👉 🌡️ *Carte de chaleur*\n\
+            ```{{ command_prefix }}heatmap [hours] [year]```\n\
+            Étoiles récoltées chaque jour de l'événement par les membres ayant le plus d'étoiles, ou avec `hours` pendant chaque heure \
+            suivant la publication du puzzle, pour repérer les jours qui ont cassé le leaderboard. Sans année, l'année en cours est utilisée.\n\n\
+            👉 🌟 *Panthéon*\n\
+            ```{{ command_prefix }}fifty [year]```\n\
+            Les membres ayant récolté les 50 étoiles de l'événement, dans l'ordre où ils l'ont terminé, avec le temps mis \
+            après la publication du dernier puzzle. Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 ⏱️ *Écarts*\n\
+            ```{{ command_prefix }}gap [yellow | local] [year]```\n\
+            Écart entre chaque membre et celui qui le précède (et le leader), sur le temps total du maillot jaune ou sur le score local, \
+            pour voir à quel point la course est serrée à chaque position. Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 🎯 *C'est en forgeant...*\n\
+            ```{{ command_prefix }}practice [year]```\n\
+            Un puzzle passé que vous n'avez pas encore terminé, tiré au hasard, avec son lien et sa difficulté sur le classement mondial. \
+            Vous devez d'abord être associé à un membre avec `{{ command_prefix }}iam`. Sans année, tous les événements enregistrés sont utilisés.\n\n\
+            👉 ⚡ *Negative splits*\n\
+            ```{{ command_prefix }}splits [year]```\n\
+            Les membres ayant résolu la partie 2 d'un jour plus vite que sa partie 1 (les jours commencés plus de 24 heures en retard ne comptent pas), \
+            avec leur nombre de negative splits sur l'événement. Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 🗳️ *Le langage de la saison*\n\
+            ```{{ command_prefix }}languages [year]```\n\
+            Les langages utilisés pendant l'événement, d'après les votes du sondage publié chaque jour dans le fil des solutions. \
+            Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 🔎 *Pour les experts*\n\
+            ```{{ command_prefix }}query [key=value ...]```\n\
+            Les étoiles du classement correspondant aux filtres, par exemple `{{ command_prefix }}query day=7 part=2 sort=time limit=10`. \
+            Filtres : `year`, `day`, `part` (1 ou 2), `member` (nom ou id), `sort` (`time`, `date` ou `name`, précédé de `-` pour \
+            un ordre décroissant) et `limit` (10 par défaut, au plus 50). Si l'année n'est pas définie, l'année en cours est utilisée.\n\n\
+            👉 ⚙️ *Sous le capot*\n\
+            ```{{ command_prefix }}jobs```\n\
+            Dernières exécutions, durées et erreurs des tâches planifiées du bot (admins uniquement).\n\
+            ```{{ command_prefix }}latency [year]```\n\
+            Délai moyen entre l'obtention des étoiles et leur détection par le bot, pour vérifier que l'actualisation du \
+            leaderboard privé suit le rythme. Sans année, l'année en cours est utilisée.\n\
+            ```{{ command_prefix }}auditlog [n]```\n\
+            Les `n` derniers messages publiés par le bot (10 par défaut, 50 au plus), pour retrouver les messages manquants ou en double (admins uniquement).\n\
+            ```{{ command_prefix }}exclude [off] <nom ou id>```\n\
+            Retirer un membre (par exemple un bot ou un compte en double) des classements, en le laissant en bas du tableau, \
+            ou le compter à nouveau avec `off` (admins uniquement).\n\
+            ```{{ command_prefix }}preview <template> [valeurs d'exemple]```\n\
+            Afficher un template (par exemple `summary`) avec des valeurs d'exemple, publié dans le canal de monitoring pour vérifier \
+            sa personnalisation sans attendre l'événement. Les valeurs d'exemple sont un objet JSON, par exemple `{\"year\": 2023}` (admins uniquement)."
+        }
+        MessageTemplate::CustomMessage => "{{emoji or '🙅'}} {{message}}",
+        MessageTemplate::HardChallenge => {
+            "😱 *{{minutes}} minutes* sont déjà passées et il reste des places à prendre dans le classement mondial...\n\
+            {% if cycle == 5 -%}
+                Je ne sais pas vous, mais j'ai l'impression que la température 🤒 monte d'un coup...
+            {% elif cycle == 8 -%}
+                C'est peut-être le moment de garder un mouchoir à portée de main au cas où il faudrait pleurer 😭.
+            {% elif cycle == 11 -%}
+                Pas d'inquiétude, ressentir l'envie de téléphoner ☎️  à un ami pour appeler à l'aide 🆘 est normal aujourd'hui.
+            {% else -%}
+                Eh bien, il est temps de hisser le drapeau de l'espoir 🏴... Je ne peux que vous souhaiter bonne chance 🤞, vous en aurez besoin aujourd'hui...
+            {% endif %}"
+        }
+        MessageTemplate::DailyChallenge => {
+            "```{{header}}```\n\
+            {{theme.celebration}} Le défi du jour est en ligne ! (<{{url}}|lien>)\n\
+                \x20 *{{title}}*\n\
+            🔫 Foncez et amusez-vous, ⏱️ le temps presse !"
+        }
+        MessageTemplate::Countdown => {
+            "{%- if minutes >= 60 -%}
+                ⏳ *{{minutes // 60}} heure{{ 's' if minutes >= 120 }}* avant le déblocage du jour {{day}} ! Le temps de prendre un café ☕ et de chauffer le clavier.
+            {%- elif minutes > 1 -%}
+                ⌛ *{{minutes}} minutes* avant le déblocage du jour {{day}} ! Éditeurs ouverts, dossiers des inputs prêts ? 🧑‍💻
+            {%- else -%}
+                🚨 *1 minute* avant le déblocage du jour {{day}} ! Les doigts sur le clavier... 🚀
+            {%- endif %}"
+        }
+        MessageTemplate::DifficultyPoll => {
+            "🌡️ Quelle a été la difficulté du puzzle du *jour {{day}}* ? Notez-la avec les réactions ci-dessous, \
+            de :one: (une promenade de santé) à :five: (mon cerveau a fondu)."
+        }
+        MessageTemplate::DailySolutionThread => {
+            "👇 *Fil de discussion du jour {{day}}*\n\
+                \x20   Retenez-vous de l'ouvrir avant d'avoir terminé la partie 2 !\n\
+             🚨 *Attention spoilers* :rotating_light:"
+        }
+        MessageTemplate::DailySolutionThreadActivity => {
+            "{%- if n_replies == 0 -%}
+                🦗 Personne n'a rien partagé dans le fil de discussion du *jour {{day}}* aujourd'hui... Ne soyez pas timides, le fil est toujours ouvert !
+            {%- else -%}
+                💬 Le fil de discussion du *jour {{day}}* a réuni *{{n_replies}}* réponse{{ 's' if n_replies > 1 }} aujourd'hui !
+                {%- if languages %}\n\
+                    \x20 • Langages repérés : {% for (language, count) in languages %}{{language}} ({{count}}){{ ', ' if not loop.last }}{% endfor %}
+                {%- endif %}
+                {%- if most_reacted %}\n\
+                    \x20 • Message avec le plus de réactions (<{{most_reacted[0]}}|lien>) avec *{{most_reacted[1]}}* réaction{{ 's' if most_reacted[1] > 1 }}
+                {%- endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::DailySummary => {
+            "🗓️ *{{day}} décembre {{year}}*\n\
+            ----- 🥁 *Résumé du jour* 🗞️ -----\n\
+            Voici comment les choses se sont passées en tête du peloton aujourd'hui :\n\
+            ___________________________________________________________________\n\
+            Top 5 de la *PARTIE 1* 🏁\n\
+            {%- for (prefix, name, time) in ranking_p1 %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}\n\
+            ___________________________________________________________________\n\
+            Top 5 de la *PARTIE 2* 🏁\n\
+            {%- for (prefix, name, time) in ranking_p2 %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}\n\
+            ___________________________________________________________________\n\
+            Top 5 du *DELTA* 🏁\n\
+            {%- for (prefix, name, time) in ranking_delta %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}
+            {%- if negative_splits %}\n\
+                ___________________________________________________________________\n\
+                ⚡ *Negative splits* (partie 2 plus rapide que la partie 1)
+                {%- for (name, part1, delta) in negative_splits %}\n\
+                    \x20 • *{{name}}* : partie 1 en {{part1}}, partie 2 en {{delta}}
+                {%- endfor %}
+            {%- endif %}
+            {%- if difficulty %}\n\
+                ___________________________________________________________________\n\
+                🌡️ *Difficulté ressentie* : {{difficulty.average}}/5 ({{difficulty.n_votes}} vote{{ 's' if difficulty.n_votes > 1 }})
+            {%- endif %}"
+        }
+        MessageTemplate::PrivateLeaderboardUpdated => "🔁 Classement privé mis à jour avec succès !",
+        MessageTemplate::PrivateLeaderboardMembership => {
+            "👥 *Leaderboard privé* : {{ count }}/{{ cap }} membres
+            {%- if trend is not none %} ({{ '+' if trend >= 0 }}{{ trend }} sur les 7 derniers jours){% endif %}
+            {%- if joined %}\n\
+                \x20 ➕ Arrivée{{ 's' if joined|length > 1 }} : {{ joined|join(', ') }}
+            {%- endif %}
+            {%- if left %}\n\
+                \x20 ➖ Départ{{ 's' if left|length > 1 }} : {{ left|join(', ') }}
+            {%- endif %}
+            {%- if spots_left is not none %}\n\
+                ⚠️ {% if spots_left == 0 %}Le leaderboard privé est complet, plus personne ne peut le rejoindre tant que des membres ne le quittent pas.{% else %}Plus que {{ spots_left }} place{{ 's' if spots_left != 1 }} avant la limite de {{ cap }} membres.{% endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::SeasonChanged => {
+            "{% if is_event -%}
+                🎄 La période de l'édition {{ year }} est ouverte : le leaderboard privé est de nouveau récupéré et les tâches planifiées reprennent.
+            {%- else -%}
+                💤 Intersaison jusqu'à l'édition {{ year }} : AoC n'est plus interrogé, les commandes sont traitées à partir du leaderboard en cache.
+            {%- endif %}"
+        }
+        MessageTemplate::MonitoringAlert => "🚨 `{{code}}` {{context}}\n```{{error}}```",
+        MessageTemplate::Heartbeat => {
+            "💓 Cache vieux de {{ cache_age }} · {{ n_members }} membre{{ 's' if n_members > 1 }} · \
+            {%- if scrape_sec is none %} aucun scraping encore{% else %} dernier scraping {{ scrape_sec }} s{{ ' (échoué)' if scrape_failed }}{% endif %} · \
+            {%- if failing_jobs %} ⚠️ tâche{{ 's' if failing_jobs|length > 1 }} en échec : {{ failing_jobs|join(', ') }}{% else %} {{ n_jobs }} tâche{{ 's' if n_jobs > 1 }} en bonne santé{% endif %}"
+        }
+        MessageTemplate::QuietHoursDigest => {
+            "🌅 Bonjour ! Voici ce qui s'est passé pendant les heures calmes :
+            {%- for message in messages %}\n\n{{ message }}{% endfor %}"
+        }
+        MessageTemplate::LeaderboardMemberJoin => {
+            "{%- for name in members %}\n\
+                🕺 Un nouveau joueur a rejoint l'arène de Noël ! Ravi de vous avoir à bord *{{name}}* !
+             {%- endfor %}"
+        }
+        MessageTemplate::NewEntriesToday => {
+            "🗓️ *Jour {{day}}*\
+            {%- for entry in completions %}\n\
+                {% with both = entry.parts_duration|length > 1, double = theme.star ~ theme.star, single = theme.star %}\
+                {{theme.announcement}} {{entry.name}} vient de gagner *{{entry.n_stars}}* étoile{{ 's' if entry.n_stars > 1 }} de plus pour le jour {{entry.day}} ({{[double, '*<->', entry.delta, '*']|join(' ') if both else single}}{{ ', top ' ~ entry.delta_percentile ~ ' % du jour' if both and entry.delta_percentile }}) +{{entry.new_points}}pts
+                {%- endwith %}
+             {%- endfor %}"
+        }
+        MessageTemplate::NewEntriesLate => {
+            "🗓️ *Jour {{day}}*{{ ' de l’événement ' ~ year if not current_year }} (rattrapage)\
+            {%- for entry in completions %}\n\
+                {% with both = entry.parts_duration|length > 1, double = '🤩', single = '✔️' %}\
+                🚂  {{entry.name}} vient de rattraper *{{entry.n_stars}}* étoile{{ 's' if entry.n_stars > 1 }} de plus pour le jour {{entry.day}} ({{ [double, 'les deux parties terminées !', '*<->', entry.delta, '*']|join(' ')  if both else single }}) +{{entry.new_points}}pts
+                {%- endwith %}
+             {%- endfor %}"
+        }
+        MessageTemplate::GlobalStatistics => {
+            "🌍 Le classement mondial est complet pour le *jour {{day}}* ! Voici comment ça s'est passé pour les cadors :\n\
+                \x20 • Temps de fin de la partie 1 : 🔥 *{{p1_fast}}* - *{{p1_slow}}* ❄️\n\
+                \x20 • Temps de fin de la partie 2 : 🔥 *{{p2_fast}}* - *{{p2_slow}}* ❄️\n\
+                \x20 • Temps delta : 🏃‍♀️ {{delta_fast}} - {{delta_slow}} 🚶‍♀️
+            {%- if difficulty %}\n\
+                \x20 • Indice de difficulté : *{{difficulty}}* (temps du 100e de la partie 2 par rapport à la médiane des {{n_days}} jours précédents)
+            {%- endif %}
+            {%- if is_among_hardest %}\n\
+                🥵 Aujourd'hui fait partie des *10% des jours les plus difficiles*, bravo à tous ceux qui en sont venus à bout !
+            {%- endif %}"
+        }
+        MessageTemplate::Ranking => {
+            "{%- if current_day -%}
+                Temps *{{ ranking_method }}* {{'le plus rapide' if not is_limit else 'le plus proche de la limite'}} du jour (au {{timestamp}}) :
+            {%- else -%}
+                Temps *{{ ranking_method }}* {{'le plus rapide' if not is_limit else 'le plus proche de la limite'}} du {{ day }}/12/{{ year }} :
+            {%- endif %}\n\
+            {%- for (prefix, name, time) in ranking %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}*
+            {%- endfor %}"
+        }
+        MessageTemplate::RangeRanking => {
+            "Meilleur *temps delta {{ 'moyen' if aggregate == 'avg' else 'médian' }}* des jours {{ first }} à {{ last }} de l'événement {{ year }} (au {{timestamp}}) :\n\
+            {%- for (prefix, name, time, n_days) in ranking %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}*{{ ' (' ~ n_days ~ '/' ~ n_total ~ ' jours)' if n_days < n_total }}
+            {%- else %}\n\
+                Personne n'a encore terminé les deux parties d'un de ces jours.
+            {%- endfor %}"
+        }
+        MessageTemplate::SeasonDelta => {
+            "⚖️ Les membres les plus réguliers de l'événement {{ year }}, par *temps delta {{ 'moyen' if aggregate == 'avg' else 'médian' }}* \
+            sur au moins {{ min_days }} jour{{ 's' if min_days > 1 }} (au {{timestamp}}) :\n\
+            {%- for (prefix, name, time, n_days) in ranking %}\n\
+                {{prefix}} en ⏱️ {{time}} 👉🏻 *{{name}}* ({{ n_days }} jour{{ 's' if n_days > 1 }})
+            {%- else %}\n\
+                Personne n'a encore terminé les deux parties de {{ min_days }} jour{{ 's' if min_days > 1 }}.
+            {%- endfor %}"
+        }
+        MessageTemplate::Hero => {
+            "{{ theme.celebration }} 🥳 Notre *{{ name }}* est entré dans le classement mondial sur la partie *{{ part }}* ! (*{{ rank }}*) 🙌"
+        }
+        MessageTemplate::Overtakes => {
+            "{%- for (overtaker, overtaken, position) in overtakes %}\n\
+                🏎️ *{{ overtaker }}* dépasse *{{ overtaken }}* pour la *{{ position }}* place !
+             {%- endfor %}"
+        }
+        MessageTemplate::StandingsShuffle => {
+            "🔀 *Remaniement du classement* de l'événement {{ year }} : des résolutions tardives de jours passés ont changé le classement général des résumés quotidiens
+            {%- for (name, previous, current, is_up) in changes %}\n\
+                \x20 • *{{ name }}* : {{ previous }} → *{{ current }}* {{ '⬆️' if is_up else '⬇️' }}
+            {%- endfor %}"
+        }
+        MessageTemplate::StageWinner => {
+            "🏁 *Vainqueur de l'étape {{ day }}* : *{{ name }}* est le premier à avoir récolté les deux étoiles du jour, en {{ time }} (delta {{ delta }}) ! 🏆"
+        }
+        MessageTemplate::FullCompletion => {
+            "🌟 *{{ name }}* a récolté les 50 étoiles de {{ year }}, {{ time }} après la publication du dernier puzzle ! \
+            {%- if first %} Le premier d'entre nous à terminer l'événement ! 🏆{% else %} {{ rank }} membre à terminer l'événement. 🎉{% endif %}"
+        }
+        MessageTemplate::NextEvent => {
+            "📣 *L'Advent of Code {{ year }} arrive !* Rejoignez notre leaderboard privé avec le code `{{ join_code }}` sur {{ url }} et préparez-vous pour le 1er décembre ! 🎄"
+        }
+        MessageTemplate::ReminderRegistration => {
+            "{%- if member -%}
+                ⏰ C'est noté ! Je vous enverrai un message privé chaque soir avec les jours où *{{ member }}* a encore des étoiles à récolter.
+            {%- else -%}
+                🔕 Entendu, plus de rappels pour vous. Profitez du calme !
+            {%- endif %}"
+        }
+        MessageTemplate::WatchRegistration => {
+            "{%- if watching -%}
+                👀 C'est noté ! Je vous enverrai un message privé chaque fois que *{{ member }}* récoltera une étoile ou vous dépassera au classement.
+            {%- elif member -%}
+                🙈 Entendu, plus de nouvelles de *{{ member }}*.
+            {%- else -%}
+                🙈 Entendu, plus de nouvelles des membres que vous suiviez.
+            {%- endif %}"
+        }
+        MessageTemplate::MemberLinked => {
+            "🤝 Enchanté *{{ member }}* ! Je ne manquerai pas de vous mentionner quand vous brillerez."
+        }
+        MessageTemplate::Absence => {
+            "🏖️ Bonne pause *{{ member }}* ! \
+            {% if days|length == 1 %}Le jour *{{ days|first }}*{% else %}Les jours *{{ days|first }}* à *{{ days|last }}*{% endif %} \
+            de l'événement {{ year }} ne {{ 'sera' if days|length == 1 else 'seront' }} pas pénalisé{{ 's' if days|length > 1 }} pour le 🟡 maillot jaune."
+        }
+        MessageTemplate::Exclusion => {
+            "{% if excluded -%}
+                🚫 *{{ member }}* est désormais retiré des classements, et seulement listé en bas du tableau.
+            {%- else -%}
+                ✅ *{{ member }}* est de nouveau compté dans les classements.
+            {%- endif %}"
+        }
+        MessageTemplate::YearInReview => {
+            "🎁 *Bilan {{ review.year }}* de *{{ review.name }}*\n\
+            L'événement {{ review.year }} est terminé, voici comment il s'est passé pour vous :\n\
+                \x20 • {{ theme.star }} *{{ review.n_stars }}* étoiles récoltées, pour un total de *{{ review.score }}* points\n\
+                \x20 • 🏁 Position finale : *{{ rank }}* sur {{ review.n_members }}
+            {%- if review.best_day %}\n\
+                \x20 • 🚀 Meilleur jour : *jour {{ review.best_day[0] }}*, avec *{{ review.best_day[1] }}* points gagnés
+            {%- endif %}
+            {%- if review.average_delta %}\n\
+                \x20 • ⏱️ Temps delta moyen : *{{ review.average_delta }}*
+            {%- endif %}\n\
+                \x20 • 📈 Évolution de la position : `{{ review.rank_chart }}`
+            {%- if review.badges %}\n\
+                Badges obtenus :
+                {%- for badge in review.badges %}\n\
+                    \x20 {{ badge }}
+                {%- endfor %}
+            {%- endif %}\n\
+            À l'année prochaine ! {{ theme.season }}"
+        }
+        MessageTemplate::YearInReviewOptIn => {
+            "{%- if opt_in -%}
+                🎁 Parfait, vous recevrez votre bilan personnel de l'année une fois l'événement terminé !
+            {%- else -%}
+                🙈 Pas de problème, votre bilan de l'année restera secret, même pour vous.
+            {%- endif %}"
+        }
+        MessageTemplate::PersonalSummary => {
+            "📬 *Jour {{ review.day }}* de {{ review.year }} pour *{{ review.name }}*\n\
+            {%- if review.p1_time %}\n\
+                \x20 • {{ theme.star }} Partie 1 en *{{ review.p1_time }}*
+            {%- endif %}
+            {%- if review.p2_time %}\n\
+                \x20 • {{ theme.star }}{{ theme.star }} Partie 2 en *{{ review.p2_time }}*
+            {%- endif %}
+            {%- if review.delta %}\n\
+                \x20 • ⏱️ Temps delta : *{{ review.delta }}*
+            {%- endif %}\n\
+                \x20 • 🎯 *{{ review.points }}* points gagnés aujourd'hui\n\
+                \x20 • 🏁 Position : *{{ rank }}* sur {{ review.n_members }}
+            {%- if previous_rank %}
+            {%- if review.rank < review.previous_rank %} (📈 en hausse depuis la {{ previous_rank }})
+            {%- elif review.rank > review.previous_rank %} (📉 en baisse depuis la {{ previous_rank }})
+            {%- else %} (inchangée)
+            {%- endif %}
+            {%- endif %}"
+        }
+        MessageTemplate::PersonalSummaryOptIn => {
+            "{%- if opt_in -%}
+                📬 Marché conclu ! Vous recevrez vos propres résultats du jour après chaque résumé quotidien.
+            {%- else -%}
+                🔕 Compris, plus de résumés quotidiens personnels pour vous.
+            {%- endif %}"
+        }
+        MessageTemplate::OptOut => {
+            "{%- if opt_out -%}
+                🥷 Entendu ! Vos étoiles comptent toujours, mais vous n'apparaîtrez plus que comme un *lutin anonyme* dans les messages.
+            {%- else -%}
+                📣 De retour sous les projecteurs ! Votre nom apparaîtra à nouveau dans les messages.
+            {%- endif %}"
+        }
+        MessageTemplate::WeeklyRecap => {
+            "🗞️ *Récap de la semaine* de l'événement {{ recap.year }}, voici ce qui s'est passé ces 7 derniers jours :\n\
+            {{ theme.star }} Étoiles récoltées :
+            {%- for (name, n_stars) in recap.stars %}\n\
+                \x20 • *{{ name }}* : {{ n_stars }} étoile{{ 's' if n_stars > 1 }}
+            {%- endfor %}
+            {%- if recap.climbs %}\n\
+                📈 Plus belles remontées au classement général :
+                {%- for (name, previous, current) in recap.climbs %}\n\
+                    \x20 • *{{ name }}* : de la position {{ previous }} à la position {{ current }}
+                {%- endfor %}
+            {%- endif %}
+            {%- if recap.jerseys %}\n\
+                🚴 Maillots :
+                {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                    \x20 • Maillot {{ {'yellow': 'jaune', 'green': 'vert', 'combative': 'combatif'}[jersey] }} : *{{ holder }}*
+                    {%- if previous and previous != holder %} (pris à {{ previous }})
+                    {%- elif previous %} (conservé)
+                    {%- endif %}
+                {%- endfor %}
+            {%- endif %}
+            {%- if recap.fastest_delta %}\n\
+                ⚡ Delta le plus rapide de la semaine : *{{ recap.fastest_delta[0] }}* le jour {{ recap.fastest_delta[1] }}, en *{{ recap.fastest_delta[2] }}*
+            {%- endif %}"
+        }
+        MessageTemplate::DailySummaryEmail => {
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head><meta charset='utf-8'><title>{{day}} décembre {{year}} - Résumé du jour</title></head>\n\
+            <body style='font-family: sans-serif;'>\n\
+            <h2>🥁 Résumé du {{day}} décembre {{year}}</h2>\n\
+            <p>Voici comment les choses se sont passées en tête du peloton aujourd'hui :</p>\n\
+            {%- for (heading, ranking) in [('Top 5 de la partie 1', ranking_p1), ('Top 5 de la partie 2', ranking_p2), ('Top 5 du delta', ranking_delta)] %}\n\
+                <h3>{{heading}} 🏁</h3>\n\
+                <table>\n\
+                {%- for (prefix, name, time) in ranking %}\n\
+                    <tr><td>{{prefix}}</td><td style='font-family: monospace;'>{{time|trim}}</td><td><b>{{name}}</b></td></tr>\n\
+                {%- else %}\n\
+                    <tr><td>Personne pour l'instant</td></tr>\n\
+                {%- endfor %}\n\
+                </table>\n\
+            {%- endfor %}\n\
+            {%- if negative_splits %}\n\
+                <h3>⚡ Negative splits (partie 2 plus rapide que la partie 1)</h3>\n\
+                <ul>\n\
+                {%- for (name, part1, delta) in negative_splits %}\n\
+                    <li><b>{{name}}</b> : partie 1 en {{part1}}, partie 2 en {{delta}}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            </body>\n\
+            </html>"
+        }
+        MessageTemplate::WeeklyRecapEmail => {
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head><meta charset='utf-8'><title>Récap de la semaine de l'événement {{ recap.year }}</title></head>\n\
+            <body style='font-family: sans-serif;'>\n\
+            <h2>🗞️ Récap de la semaine de l'événement {{ recap.year }}</h2>\n\
+            <p>Voici ce qui s'est passé ces 7 derniers jours.</p>\n\
+            <h3>⭐ Étoiles récoltées</h3>\n\
+            <ul>\n\
+            {%- for (name, n_stars) in recap.stars %}\n\
+                <li><b>{{ name }}</b> : {{ n_stars }} étoile{{ 's' if n_stars > 1 }}</li>\n\
+            {%- endfor %}\n\
+            </ul>\n\
+            {%- if recap.climbs %}\n\
+                <h3>📈 Plus belles remontées au classement général</h3>\n\
+                <ul>\n\
+                {%- for (name, previous, current) in recap.climbs %}\n\
+                    <li><b>{{ name }}</b> : de la position {{ previous }} à la position {{ current }}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            {%- if recap.jerseys %}\n\
+                <h3>🚴 Maillots</h3>\n\
+                <ul>\n\
+                {%- for (jersey, previous, holder) in recap.jerseys %}\n\
+                    <li>Maillot {{ {'yellow': 'jaune', 'green': 'vert', 'combative': 'combatif'}[jersey] }} : <b>{{ holder }}</b>\n\
+                    {%- if previous and previous != holder %} (pris à {{ previous }})\n\
+                    {%- elif previous %} (conservé)\n\
+                    {%- endif %}</li>\n\
+                {%- endfor %}\n\
+                </ul>\n\
+            {%- endif %}\n\
+            {%- if recap.fastest_delta %}\n\
+                <p>⚡ Delta le plus rapide de la semaine : <b>{{ recap.fastest_delta[0] }}</b> le jour {{ recap.fastest_delta[1] }}, en <b>{{ recap.fastest_delta[2] }}</b></p>\n\
+            {%- endif %}\n\
+            </body>\n\
+            </html>"
+        }
+        MessageTemplate::AwardsCeremony => {
+            "🏆 *Cérémonie de remise des prix* de l'événement {{ awards.year }} ! Le rideau tombe sur les puzzles, place aux champions de la saison :
+            {%- if awards.jerseys %}\n\
+                🚴 Maillots :
+                {%- for (jersey, winner) in awards.jerseys %}\n\
+                    \x20 • Maillot {% if jersey == 'yellow' %}jaune{% elif jersey == 'green' %}vert{% else %}{{ jersey }}{% endif %} : *{{ winner }}*
+                {%- endfor %}
+            {%- endif %}
+            {%- if awards.most_combative %}\n\
+                🥋 Le plus combatif : *{{ awards.most_combative[0] }}*, avec {{ awards.most_combative[1] }} points gagnés juste avant les déblocages suivants
+            {%- endif %}\n\
+            {{ theme.star }} Le plus d'étoiles :
+            {%- for (name, n_stars) in awards.most_stars %}\n\
+                \x20 {{ ['🥇', '🥈', '🥉'][loop.index0] }} *{{ name }}* : {{ n_stars }} étoile{{ 's' if n_stars > 1 }}
+            {%- endfor %}
+            {%- if awards.best_delta %}\n\
+                ⚡ Meilleur delta de la saison : *{{ awards.best_delta[0] }}* le jour {{ awards.best_delta[1] }}, en *{{ awards.best_delta[2] }}*
+            {%- endif %}
+            {%- if awards.comeback %}\n\
+                📈 Plus belle remontée : *{{ awards.comeback[0] }}*, de la position {{ awards.comeback[1] }} à la position {{ awards.comeback[2] }} du classement général
+            {%- endif %}\n\
+            Merci à tous d'avoir joué, rendez-vous en décembre prochain 🎄 !"
+        }
+        MessageTemplate::Guess => {
+            "🔮 Pari enregistré ! Vous misez sur *{{ guess }}* pour le 100e finisseur de la partie 2 du classement mondial \
+            du *jour {{ day }}*. Vous pouvez changer d'avis jusqu'à l'ouverture de l'énigme."
+        }
+        MessageTemplate::GuessResults => {
+            "🔮 Le 100e finisseur de la partie 2 du classement mondial du *jour {{ day }}* l'a fait en *{{ cutoff }}*. \
+            {{ 'Paris les plus proches' if winners|length > 1 else 'Pari le plus proche' }} sur {{ n_guesses }} :
+            {%- for (user, guess, error) in winners %}\n\
+                \x20 • {{ user }} avec *{{ guess }}* ({{ error }} d'écart)
+            {%- endfor %}"
+        }
+        MessageTemplate::Predictions => {
+            "{%- if standings -%}
+                🔮 Classement du jeu de pronostics de l'événement {{ year }} :
+                {%- for (user, wins, n_guesses, average_error) in standings %}\n\
+                    \x20 {{ loop.index }}. {{ user }} : *{{ wins }}* victoire{{ 's' if wins > 1 }} \
+                    sur {{ n_guesses }} pari{{ 's' if n_guesses > 1 }}, {{ average_error }} d'écart en moyenne
+                {%- endfor %}
+            {%- else -%}
+                🤷 Personne n'a encore joué au jeu de pronostics de l'événement {{ year }}. \
+                Pariez sur le temps du 100e finisseur de la partie 2 du classement mondial avec `{{ command_prefix }}guess <mm:ss>` !
+            {%- endif %}"
+        }
+        MessageTemplate::WhatIf => {
+            "🧮 Et si *{{ projection.name }}* terminait le jour {{ projection.day }} de l'événement {{ projection.year }} maintenant ?\n\
+                \x20 • {{ theme.star }} *{{ projection.n_stars }}* étoile{{ 's' if projection.n_stars > 1 }} de plus, pour *{{ projection.points }}* points\n\
+                \x20 • 🏁 Position : {% if projection.rank[0] == projection.rank[1] %}*{{ new_rank }}* (inchangée){% else %}de {{ rank }} à *{{ new_rank }}*{% endif %}
+            {%- if projection.overtaken %}, en dépassant {{ projection.overtaken|join(', ') }}{% endif %}
+            {%- for (jersey, before, after) in jerseys %}{% if after %}\n\
+                \x20 • 🚴 Maillot {{ {'yellow': 'jaune', 'green': 'vert', 'combative': 'combatif'}[jersey] }} : {% if before and before != after %}de {{ before }} à *{{ after }}*{% elif before %}*{{ after }}* (inchangée){% else %}*{{ after }}*{% endif %}
+            {%- endif %}{% endfor %}"
+        }
+        MessageTemplate::Jobs => {
+            "⚙️ *Tâches planifiées*
+            {%- for job in jobs %}\n\
+                \x20 • {% if job.running_since %}⏳{% elif job.succeeded %}✅{% else %}❌{% endif %} `{{ job.name }}` : \
+                {{ job.runs }} exécution{{ 's' if job.runs > 1 }} ({{ job.failed_runs }} en échec)
+                {%- if job.last_run_at %}, la dernière le {{ job.last_run_at }} en {{ job.last_run_ms }}ms{% endif %}
+                {%- if job.running_since %}, en cours depuis le {{ job.running_since }}{% endif %}
+                {%- if job.last_error %}\n\
+                    \x20     ⚠️ Dernière erreur le {{ job.last_error_at }} : `{{ job.last_error }}`
+                {%- endif %}
+            {%- else %}\n\
+                💤 Aucune tâche n'a été exécutée depuis le démarrage du bot.
+            {%- endfor %}"
+        }
+        MessageTemplate::Missing => {
+            "{%- if members -%}
+                🐢 *{{ members|length }}* membre{{ 's' if members|length > 1 }} sans aucune étoile le jour {{ day }}{% if not current_year %} de {{ year }}{% endif %}, \
+                les habitués en premier :
+                {%- for (name, n_days) in members %}\n\
+                    \x20 • {{ name }}{% if day > 1 %} ({{ n_days }}/{{ day - 1 }} jour{{ 's' if day > 2 }} précédent{{ 's' if day > 2 }} joué{{ 's' if day > 2 }}){% endif %}
+                {%- endfor %}
+            {%- else -%}
+                🎉 Tout le monde a récolté au moins une étoile le jour {{ day }}{% if not current_year %} de {{ year }}{% endif %} !
+            {%- endif %}"
+        }
+        MessageTemplate::Heroes => {
+            "{%- if heroes -%}
+                🦸 Apparitions de nos membres dans le classement mondial, \
+                sur les {{ n_scanned }} classement{{ 's' if n_scanned != 1 }} mondia{{ 'ux' if n_scanned != 1 else 'l' }} enregistré{{ 's' if n_scanned != 1 }} :
+                {%- for hero in heroes %}\n\
+                    \x20 {{ loop.index }}. *{{ hero.name }}* : *{{ hero.appearances }}* apparition{{ 's' if hero.appearances > 1 }} \
+                    sur {{ hero.years|length }} événement{{ 's' if hero.years|length > 1 }}, \
+                    meilleur rang *{{ hero.rank }}* (partie {{ hero.part }} du jour {{ hero.day }} {{ hero.year }})
+                {%- endfor %}
+            {%- else -%}
+                🦸 Aucun de nos membres n'est apparu dans les {{ n_scanned }} classement{{ 's' if n_scanned != 1 }} mondia{{ 'ux' if n_scanned != 1 else 'l' }} enregistré{{ 's' if n_scanned != 1 }} pour l'instant.
+            {%- endif %}"
+        }
+        MessageTemplate::AppHome => {
+            "🎄 *Advent of Code {{ year }}*\n\
+            {%- if review %}\n\
+                Votre événement jusqu'ici, *{{ review.name }}* :\n\
+                \x20 • {{ theme.star }} *{{ review.n_stars }}* étoiles récoltées, pour un total de *{{ review.score }}* points\n\
+                \x20 • 🏁 Position : *{{ rank }}* sur {{ review.n_members }}
+                {%- if review.best_day %}\n\
+                    \x20 • 🚀 Meilleur jour : *jour {{ review.best_day[0] }}*, avec *{{ review.best_day[1] }}* points gagnés
+                {%- endif %}
+                {%- if review.average_delta %}\n\
+                    \x20 • ⏱️ Temps delta moyen : *{{ review.average_delta }}*
+                {%- endif %}\n\
+                \x20 • 📈 Évolution du classement : `{{ review.rank_chart }}`
+            {%- elif linked %}\n\
+                Vous n'avez encore récolté aucune étoile pendant l'événement {{ year }}, les puzzles vous attendent ! {{ theme.star }}
+            {%- else %}\n\
+                🔗 Associez-vous à un membre du classement avec `{{ command_prefix }}iam <nom>` pour voir vos propres statistiques ici.
+            {%- endif %}\n\n\
+            🚴 *Maillots*
+            {%- for (jersey, leaders) in jerseys %}\n\
+                \x20 • Maillot {{ {'yellow': 'jaune', 'green': 'vert', 'combative': 'combatif'}[jersey] }} : {% for (name, value) in leaders %}{{ loop.index }}. *{{ name }}* ({{ value }}){{ ', ' if not loop.last }}{% else %}personne pour l'instant{% endfor %}
+            {%- endfor %}"
+        }
+        MessageTemplate::Burndown => {
+            "{%- if chart -%}
+                📉 Étoiles restant à récolter le jour {{ day }}{% if not current_year %} de {{ year }}{% endif %}, \
+                heure par heure après la publication du puzzle :\n\
+                ```{{ chart }}```
+            {%- else -%}
+                🤷 Personne n'a récolté d'étoile dans les 24 heures suivant la publication du jour {{ day }}{% if not current_year %} de {{ year }}{% endif %}.
+            {%- endif %}"
+        }
+        MessageTemplate::Heatmap => {
+            "{%- if chart -%}
+                🌡️ Étoiles récoltées chaque jour de l'événement {{ year }}, \
+                {%- if hours %} par heure après la publication du puzzle
+                {%- elif n_members > max_members %} par les {{ max_members }} membres ayant le plus d'étoiles
+                {%- else %} par membre{% endif %} (plus c'est foncé, plus c'est animé) :\n\
+                ```{{ chart }}```
+            {%- else -%}
+                🤷 Personne n'a encore récolté d'étoile dans l'événement {{ year }}.
+            {%- endif %}"
+        }
+        MessageTemplate::FullCompletions => {
+            "{%- if completions -%}
+                🌟 *Panthéon {{ year }}*, les membres ayant récolté les 50 étoiles :
+                {%- for c in completions %}\n\
+                    \x20 {{ loop.index }}. *{{ c.name }}*, {{ c.time }} après la dernière publication ({{ c.date }})
+                {%- endfor %}
+            {%- else -%}
+                🤷 Personne n'a encore récolté les 50 étoiles de {{ year }}.
+            {%- endif %}"
+        }
+        MessageTemplate::Gaps => {
+            "{%- if gaps -%}
+                ⏱️ *Écarts {{ 'du maillot jaune' if yellow else 'du score local' }} {{ year }}*, avec le membre précédent (et avec le leader) :\n\
+                ```{{ gaps }}```
+                {%- if closest %}\n\
+                    🔥 Duel le plus serré : *{{ closest.behind }}* est à \
+                    {%- if yellow %} {{ closest.gap }}{% else %} {{ closest.points }} point{{ 's' if closest.points != 1 }}{% endif %} de *{{ closest.ahead }}*.
+                {%- endif %}
+            {%- else -%}
+                🤷 Personne n'est encore dans la course de {{ year }}.
+            {%- endif %}"
+        }
+        MessageTemplate::AuditLog => {
+            "📜 *{{ messages|length }} dernier{{ 's' if messages|length != 1 }} message{{ 's' if messages|length != 1 }} publié{{ 's' if messages|length != 1 }}*, le plus récent en premier
+            {%- for m in messages %}\n\
+                \x20 • {{ '✅' if m.ts else ('🖼️' if not m.error else '❌') }} {{ m.at }} `{{ m.event }}` dans <#{{ m.channel }}>{% if m.ts %} (ts {{ m.ts }}){% endif %} : `{{ m.excerpt }}`
+                {%- if m.error %}\n\
+                    \x20     ⚠️ `{{ m.error }}`
+                {%- endif %}
+            {%- else %}\n\
+                📭 Aucun message publié depuis le démarrage du journal.
+            {%- endfor %}"
+        }
+        MessageTemplate::Practice => {
+            "{%- if day -%}
+                🎯 Pourquoi pas le *jour {{ day }} de {{ puzzle_year }}* ? {{ 'Les deux étoiles vous attendent' if missing_stars == 2 else 'La deuxième étoile vous attend' }} encore : {{ url }}
+                {%- if difficulty %}\n\
+                    \x20 • Indice de difficulté : *{{ difficulty }}*, plus difficile que {{ percentile }} % des {{ n_days }} autres jours enregistrés sur le classement mondial
+                {%- endif %}
+            {%- else -%}
+                🏆 Vous avez terminé tous les puzzles{{ ' de ' ~ year if year }} publiés jusqu'ici, plus rien à pratiquer !
+            {%- endif %}"
+        }
+        MessageTemplate::Splits => {
+            "⚡ *Negative splits* de l'événement {{ year }}, partie 2 résolue plus vite que la partie 1
+            {%- for (name, n_splits, n_days) in members %}\n\
+                {{ loop.index }}. *{{ name }}* : {{ n_splits }} negative split{{ 's' if n_splits > 1 }} sur {{ n_days }} jour{{ 's' if n_days > 1 }} terminé{{ 's' if n_days > 1 }}
+            {%- else %}\n\
+                Personne n'a encore résolu une partie 2 plus vite que sa partie 1.
+            {%- endfor %}"
+        }
+        MessageTemplate::Rank => {
+            "🏁 *{{ rank.name }}* dans l'événement {{ rank.year }} : *{{ position }}* sur {{ rank.n_members }}, \
+            avec *{{ rank.score }}* points et {{ rank.n_stars }} {{ theme.star }}
+            {%- if rank.ahead %}\n\
+                \x20 • ⬆️ {{ rank.ahead[1] }} point{{ 's' if rank.ahead[1] > 1 }} derrière *{{ rank.ahead[0] }}*
+            {%- endif %}
+            {%- if rank.behind %}\n\
+                \x20 • ⬇️ {{ rank.behind[1] }} point{{ 's' if rank.behind[1] > 1 }} d'avance sur *{{ rank.behind[0] }}*
+            {%- endif %}
+            {%- for (jersey, position) in jerseys %}{% if position %}\n\
+                \x20 • 🚴 Maillot {{ {'yellow': 'jaune', 'green': 'vert', 'combative': 'combatif'}[jersey] }} : *{{ position }}*
+            {%- endif %}{% endfor %}"
+        }
+        MessageTemplate::LanguagePoll => {
+            "🗳️ Quel langage avez-vous utilisé aujourd'hui ? Votez avec les réactions ci-dessous :
+            {%- for (reaction, language) in languages %} {{ reaction }} {{ language }}{{ ',' if not loop.last }}{% endfor %}"
+        }
+        MessageTemplate::Languages => {
+            "🗳️ *Langages* de l'événement {{ year }}, d'après les sondages quotidiens des fils des solutions
+            {%- for (language, votes, n_days) in languages %}\n\
+                {{ loop.index }}. *{{ language }}* : {{ votes }} vote{{ 's' if votes > 1 }} sur {{ n_days }} jour{{ 's' if n_days > 1 }}
+            {%- else %}\n\
+                Personne n'a encore voté dans les sondages.
+            {%- endfor %}"
+        }
+        MessageTemplate::Latency => {
+            "{%- if latency -%}
+                ⏱️ *{{ latency.n_stars }}* étoile{{ 's' if latency.n_stars > 1 }} de l'événement {{ year }} détectée{{ 's' if latency.n_stars > 1 }} par le bot, en moyenne *{{ latency.average }}* après leur obtention (médiane {{ latency.median }}, au plus {{ latency.max }}).\n\
+                {{ latency.within_refresh }} % d'entre elles ont été détectées en moins de {{ latency.refresh_min }} minutes, l'intervalle entre deux actualisations du leaderboard privé.
+            {%- else -%}
+                ⏱️ Aucune étoile de l'événement {{ year }} n'a encore été détectée par le bot.
+            {%- endif %}"
+        }
+        MessageTemplate::Query => {
+            "🔎 *{{ result.n_matches }}* étoile{{ 's' if result.n_matches > 1 }} de l'événement {{ result.year }}\
+            {%- if result.query %} correspondant à `{{ result.query }}`{% endif %}\
+            {%- if result.rows|length < result.n_matches %} ({{ result.rows|length }} premières listées){% endif %}{{ ' :' if result.rows }}
+            {%- for row in result.rows %}\n\
+                \x20 {{ loop.index }}. *{{ row.name }}* : jour {{ row.day }} partie {{ row.part }} en {{ row.time }}
+            {%- endfor %}"
+        }
+        MessageTemplate::Preview => {
+            "🧪 Aperçu de `{{ template }}` publié dans le canal de monitoring\
+            {%- if failed %}, mais il n'a pas pu être affiché ⚠️{% endif %}."
+        }
+        MessageTemplate::TemplatePreview => {
+            "{%- if error -%}
+                ⚠️ `{{ template }}` n'a pas pu être affiché avec les valeurs d'exemple :\n```{{ error }}```
+            {%- else -%}
+                🧪 Aperçu de `{{ template }}` avec des valeurs d'exemple :\n{{ rendered }}
+            {%- endif %}"
+        }
+        MessageTemplate::CommandCooldown => {
+            "⏳ Doucement ! `{{ command_prefix }}{{ command }}` a été beaucoup demandée dans la dernière minute, merci de réessayer dans {{ wait_sec }} seconde{{ 's' if wait_sec != 1 }} 🙏."
+        }
+        MessageTemplate::InvalidOption => {
+            "🤔 `{{ option }}` n'est pas une option de `{{ command_prefix }}{{ command }}`\
+            {%- if suggestion %}, vouliez-vous dire `{{ suggestion }}` ?{% else %}.{% endif %} \
+            Les options valides sont {% for value in valid %}`{{ value }}`{{ ', ' if not loop.last }}{% endfor %}."
+        }
+        MessageTemplate::PersonalReminder => {
+            "⏰ Petit rappel de votre lutin préféré, des étoiles vous attendent encore dans l'événement {{ year }} :\n\
+            {%- for (day, n_missing) in missing %}\n\
+                \x20 • Jour {{ day }} : {{ n_missing }} étoile{{ 's' if n_missing > 1 }} à récolter
+            {%- endfor %}"
+        }
+        MessageTemplate::WatchAlerts => {
+            "👀 Des nouvelles des membres que vous suivez dans l'événement {{ year }} :\n\
+            {%- for (name, day, is_first, position) in alerts %}\n\
+                \x20 • {% if position %}🏎️ *{{ name }}* vous a dépassé au classement, désormais {{ position }}\
+                {%- else %}⭐ *{{ name }}* a récolté la {{ 'première' if is_first else 'deuxième' }} étoile du jour {{ day }}{% endif %}
+            {%- endfor %}"
+        }
+        MessageTemplate::LeaderboardDisplay => {
+            "{%- if current_year -%}
+                📓 Classement actuel par {% if scoring_method == 'local' %}*score local*{% elif scoring_method == 'percentile' %}*score par percentile*{% else %}*nombre d’étoiles*{% endif %} au {{timestamp}}{{ ' (page ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }} :
+            {%- else -%}
+                📓 Classement par {% if scoring_method == 'local' %}*score local*{% elif scoring_method == 'percentile' %}*score par percentile*{% else %}*nombre d’étoiles*{% endif %} de l'événement {{ year }}{{ ' (page ' ~ page ~ '/' ~ n_pages ~ ')' if n_pages > 1 }} :
+            {%- endif %}
+            {%- if detailed %}\n\
+                _Dernières colonnes : temps médian de la partie 2 et delta moyen sur l'événement._
+            {%- endif %}\n\
+            ```{{ leaderboard }}```"
+        }
+        MessageTemplate::Wins => {
+            "{%- if current_year -%}
+                🏅 Jours gagnés par chaque membre au {{timestamp}} :
+            {%- else -%}
+                🏅 Jours gagnés par chaque membre pendant l'événement *{{year}}* :
+            {%- endif %}\n\
+            ```{{ wins }}```"
+        }
+        MessageTemplate::AllTime => {
+            "🏛️ Classement de tous les temps sur {{ years|length }} événement{{ 's' if years|length > 1 }} en cache\
+            {% if years %} ({{ years|first }}-{{ years|last }}){% endif %}, par {{ 'étoiles' if scoring == 'stars' else 'score' }} au {{timestamp}} :\n\
+            ```{{ standings }}```\n\
+            _Les scores sont normalisés par événement, le meilleur score de chaque événement valant 100 points._"
+        }
+        MessageTemplate::Faq => {
+            "{%- if answer -%}
+                ❓ *{{ answer.question }}*\n\
+                {{ answer.answer }}
+            {%- elif requested -%}
+                🤷 Je n'ai trouvé aucune réponse sur *{{ requested }}*
+                {%- if entries %}, vouliez-vous dire l'un de ceux-ci ?
+                    {%- for entry in entries %}\n\
+                        \x20 • `{{ entry.topic }}` : {{ entry.question }}
+                    {%- endfor %}
+                {%- else %}. Utilisez `{{ command_prefix }}faq` pour lister les sujets disponibles.
+                {%- endif %}
+            {%- elif entries -%}
+                📚 Voici les sujets sur lesquels je peux vous aider (`{{ command_prefix }}faq <topic>`) :
+                {%- for entry in entries %}\n\
+                    \x20 • `{{ entry.topic }}` : {{ entry.question }}
+                {%- endfor %}
+            {%- else -%}
+                📚 Aucune FAQ n'a encore été configurée pour cet événement.
+            {%- endif %}"
+        }
+        MessageTemplate::Link => {
+            "🔗 Tout ce qu'il vous faut pour le *jour {{day}}* de l'événement *{{year}}* :\n\
+                \x20 • 🧩 <{{puzzle}}|Puzzle>\n\
+                \x20 • 🎄 <{{private_leaderboard}}|Classement privé>\n\
+                \x20 • 🌍 <{{global_leaderboard}}|Classement mondial>"
+        }
+        MessageTemplate::BestSolutions => {
+            "{%- if solutions -%}
+                🗳️ Réponses les plus appréciées du fil des solutions du *jour {{day}}* (événement {{year}}) :
+                {%- for (permalink, author, votes) in solutions %}\n\
+                    \x20 {{ loop.index }}. <{{ permalink }}|Solution> de {{ author }} ({{ votes }} réaction{{ 's' if votes > 1 }})
+                {%- endfor %}
+            {%- else -%}
+                🤷 Aucune réponse du fil des solutions du *jour {{day}}* (événement {{year}}) n'a encore reçu de réaction. \
+                Réagissez aux solutions que vous aimez pour voter pour elles !
+            {%- endif %}"
+        }
+        MessageTemplate::TdfStandings => {
+            "{%- if current_year and not day -%}
+                🚴 Classement actuel du maillot {{ '🟡 Jaune 🛵' if jersey=='yellow' else ('🟢 Vert 🍏' if jersey=='green' else ('⚫Combatif 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} au {{timestamp}} :
+            {%- elif not day -%}
+                🚴 Classement du maillot {{ '🟡 Jaune 🛵' if jersey=='yellow' else ('🟢 Vert 🍏' if jersey=='green' else ('⚫Combatif 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} de l'événement *{{year}}* :
+            {%- else -%}
+                🚴 Classement du maillot {{ '🟡 Jaune 🛵' if jersey=='yellow' else ('🟢 Vert 🍏' if jersey=='green' else ('⚫Combatif 🥋' if jersey=='combative' else '🎽 ' ~ jersey|capitalize))}} du *jour {{day}}* de l'événement {{year}} :
+            {%- endif %}
+            {%- if drop_worst %} _(sans les {{drop_worst}} pire{{ 's' if drop_worst > 1 }} jour{{ 's' if drop_worst > 1 }} de chaque membre)_{% endif %}\n\
+            ```{{ standings }}```"
+        }
+        MessageTemplate::TdfStage => {
+            "{%- if current_day -%}
+                🚴 Résultats de l'*étape {{day}}* du maillot 🟡 Jaune 🛵 au {{timestamp}} :
+            {%- else -%}
+                🚴 Résultats de l'*étape {{day}}* du maillot 🟡 Jaune 🛵 de l'événement {{year}} :
+            {%- endif %}
+            {%- if winner %}\n\
+                🏆 Vainqueur de l'étape : *{{ winner }}*
+            {%- endif %}
+            {%- if stage %}\n\
+                ```{{ stage }}```
+            {%- else %}\n\
+                Personne n'a encore pris le départ de cette étape.
+            {%- endif %}"
+        }
+    }
+}
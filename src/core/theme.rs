@@ -0,0 +1,87 @@
+use crate::error::{BotError, BotResult};
+use figment::{
+    providers::{Format, Yaml},
+    Figment,
+};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Theme of the messages, registered once at startup before anything is rendered.
+static THEME: OnceCell<Theme> = OnceCell::new();
+
+/// Emoji set used in the messages. A theme pack (yaml file) only needs to define the emojis it
+/// overrides, Slack custom emojis (e.g. `:party-parrot:`) being accepted as well.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    // prefixes of the top ranks of the `!fast` rankings
+    pub medals: Vec<String>,
+    // prefixes of the top ranks of the daily summary
+    pub trophies: Vec<String>,
+    pub celebration: String,
+    pub star: String,
+    pub announcement: String,
+    pub season: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            medals: ["🥇", "🥈", "🥉"].map(String::from).to_vec(),
+            trophies: ["🏆", "🥈", "🥉", "🍫", "🍬"].map(String::from).to_vec(),
+            celebration: "🎉".to_string(),
+            star: "⭐".to_string(),
+            announcement: "📣".to_string(),
+            season: "🎄".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    /// Load a theme pack, the emojis it does not define being kept from the default theme.
+    pub fn from_file(path: &str) -> BotResult<Theme> {
+        if !Path::new(path).exists() {
+            return Err(BotError::Config(format!("theme pack '{path}' not found")));
+        };
+        Theme::from_figment(Figment::from(Yaml::file(path)))
+    }
+
+    fn from_figment(figment: Figment) -> BotResult<Theme> {
+        Ok(figment.extract()?)
+    }
+}
+
+/// Use a theme for all the messages. Only the first registered theme is kept, the templates
+/// environment capturing the theme the first time a message is rendered.
+pub fn register(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// Theme of the messages, the default one if none was registered.
+pub fn current() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_packs_override_part_of_the_default_theme() {
+        let theme = Theme::from_figment(Figment::from(Yaml::string(
+            "
+medals: [':gold:', ':silver:', ':bronze:']
+celebration: ':party-parrot:'
+",
+        )))
+        .unwrap();
+
+        assert_eq!(theme.medals, vec![":gold:", ":silver:", ":bronze:"]);
+        assert_eq!(theme.celebration, ":party-parrot:");
+        assert_eq!(theme.trophies, Theme::default().trophies);
+        assert_eq!(theme.star, "⭐");
+
+        assert!(Theme::from_file("does/not/exist.yaml").is_err());
+    }
+}
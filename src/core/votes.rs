@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use slack_morphism::{SlackChannelId, SlackTs, SlackUserId};
+use std::collections::HashMap;
+
+// Number of replies listed by the `!bestsolutions` command
+pub const BEST_SOLUTIONS_COUNT: usize = 5;
+
+/// Reply posted by a user in a daily solutions thread, with the reactions it received from the
+/// other users.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolutionReply {
+    pub year: i32,
+    pub day: u8,
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+    pub author: SlackUserId,
+    // number of reactions of each user, a user voting once whatever their number of reactions
+    #[serde(default)]
+    pub voters: HashMap<SlackUserId, u32>,
+}
+
+impl SolutionReply {
+    /// Number of users who reacted to the reply.
+    pub fn votes(&self) -> u32 {
+        self.voters.len() as u32
+    }
+}
+
+/// Count a reaction added to (or removed from) a reply. Reactions on other messages, and the
+/// ones of the authors on their own replies, are ignored. Returns whether a vote was counted.
+pub fn record_reaction(
+    replies: &mut [SolutionReply],
+    channel: &SlackChannelId,
+    ts: &SlackTs,
+    user: &SlackUserId,
+    added: bool,
+) -> bool {
+    match replies
+        .iter_mut()
+        .find(|r| r.channel == *channel && r.ts == *ts && r.author != *user)
+    {
+        Some(reply) => {
+            let reactions = reply.voters.entry(user.clone()).or_default();
+            *reactions = match added {
+                true => *reactions + 1,
+                false => reactions.saturating_sub(1),
+            };
+            if *reactions == 0 {
+                reply.voters.remove(user);
+            };
+            true
+        }
+        None => false,
+    }
+}
+
+/// Most upvoted replies of the day, the earliest reply first in case of a tie.
+pub fn best_solutions(replies: &[SolutionReply], year: i32, day: u8) -> Vec<SolutionReply> {
+    let mut best = replies
+        .iter()
+        .filter(|r| r.year == year && r.day == day && r.votes() > 0)
+        .cloned()
+        .collect::<Vec<SolutionReply>>();
+    best.sort_by(|a, b| b.votes().cmp(&a.votes()).then_with(|| a.ts.0.cmp(&b.ts.0)));
+    best.truncate(BEST_SOLUTIONS_COUNT);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(day: u8, ts: &str, author: &str) -> SolutionReply {
+        SolutionReply {
+            year: 2022,
+            day,
+            channel: SlackChannelId("C000X9X0XX".to_string()),
+            ts: SlackTs(ts.to_string()),
+            author: SlackUserId(author.to_string()),
+            voters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn replies_are_ranked_on_the_votes_of_the_other_users() {
+        let mut replies = vec![
+            reply(3, "1701580000.000100", "U0DASHER"),
+            reply(3, "1701580000.000200", "U0DANCER"),
+            reply(3, "1701580000.000300", "U0PRANCER"),
+            reply(4, "1701670000.000100", "U0DASHER"),
+        ];
+        let channel = SlackChannelId("C000X9X0XX".to_string());
+        let mut vote = |ts: &str, user: &str, added: bool| {
+            record_reaction(
+                &mut replies,
+                &channel,
+                &SlackTs(ts.to_string()),
+                &SlackUserId(user.to_string()),
+                added,
+            )
+        };
+
+        assert!(vote("1701580000.000200", "U0DASHER", true));
+        assert!(vote("1701580000.000100", "U0DANCER", true));
+        assert!(vote("1701580000.000100", "U0PRANCER", true));
+        assert!(vote("1701580000.000300", "U0DASHER", true));
+        assert!(vote("1701580000.000300", "U0DASHER", false));
+        // several reactions of a user are a single vote, until all of them are removed
+        assert!(vote("1701580000.000100", "U0PRANCER", true));
+        assert!(vote("1701580000.000100", "U0PRANCER", false));
+        // own reply and unknown message
+        assert!(!vote("1701580000.000200", "U0DANCER", true));
+        assert!(!vote("1701580000.000999", "U0DANCER", true));
+
+        let best = best_solutions(&replies, 2022, 3)
+            .into_iter()
+            .map(|r| (r.author.0.clone(), r.votes()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            best,
+            vec![("U0DASHER".to_string(), 2), ("U0DANCER".to_string(), 1)]
+        );
+        assert!(best_solutions(&replies, 2022, 4).is_empty());
+    }
+}
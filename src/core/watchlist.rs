@@ -0,0 +1,155 @@
+use crate::core::leaderboard::{Identifier, Leaderboard, MemberId, ProblemPart};
+use itertools::Itertools;
+use slack_morphism::SlackUserId;
+use std::collections::{HashMap, HashSet};
+
+/// Members watched by the users with the `!watch` command.
+pub type Watchlists = HashMap<SlackUserId, HashSet<MemberId>>;
+
+/// News about a watched member, sent to the watching user as a direct message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchAlert {
+    // (member, day, part)
+    Star(Identifier, u8, ProblemPart),
+    // (member, new position of the member) of a member overtaking the user in the overall standings
+    Overtake(Identifier, usize),
+}
+
+/// Alerts of each user about the members they watch, from the entries of the year newly merged
+/// into the leaderboard (see `ScrapedLeaderboard::merge_with`) and the overall standings before
+/// and after the merge. The overtakes are only known for the users linked to a member.
+pub fn watch_alerts(
+    watchlists: &Watchlists,
+    slack_users: &HashMap<MemberId, SlackUserId>,
+    new_entries: &Leaderboard,
+    positions: (&[Identifier], &[Identifier]),
+    year: i32,
+) -> Vec<(SlackUserId, Vec<WatchAlert>)> {
+    let new_stars = new_entries
+        .iter()
+        .filter(|e| e.year == year)
+        .sorted_unstable_by_key(|e| e.timestamp)
+        .collect::<Vec<_>>();
+
+    let (previous_positions, current_positions) = positions;
+    let position = |positions: &[Identifier], member: MemberId| {
+        positions.iter().position(|id| id.numeric == member)
+    };
+    let users = slack_users
+        .iter()
+        .map(|(member, user)| (user, *member))
+        .collect::<HashMap<&SlackUserId, MemberId>>();
+
+    watchlists
+        .iter()
+        .sorted_unstable_by_key(|(user, _watched)| user.to_string())
+        .filter_map(|(user, watched)| {
+            let stars = new_stars
+                .iter()
+                .filter(|e| watched.contains(&e.id.numeric))
+                .map(|e| WatchAlert::Star(e.id.clone(), e.day, e.part));
+            // members ranked behind the user before the merge, and ahead of them after it
+            let overtakes = users.get(user).into_iter().flat_map(|me| {
+                let before = position(previous_positions, *me);
+                let after = position(current_positions, *me);
+                current_positions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_pos, id)| watched.contains(&id.numeric) && id.numeric != *me)
+                    .filter(move |(pos, id)| {
+                        let was_behind = match (position(previous_positions, id.numeric), before) {
+                            (Some(other), Some(me)) => other > me,
+                            _ => false,
+                        };
+                        was_behind && after.is_some_and(|me| *pos < me)
+                    })
+                    .map(|(pos, id)| WatchAlert::Overtake(id.clone(), pos + 1))
+            });
+            let alerts = stars.chain(overtakes).collect::<Vec<_>>();
+            (!alerts.is_empty()).then(|| (user.clone(), alerts))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{
+            leaderboard::ScrapedLeaderboard,
+            standings::{overall_positions, Scoring},
+        },
+        fixtures::{private_leaderboard, MemberFixture},
+    };
+    use chrono::Utc;
+
+    #[test]
+    fn watched_members_stars_and_overtakes_are_alerted() {
+        let year = 2000;
+        let mut cache = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                year,
+                &[
+                    MemberFixture::new(3001, "Frosty").with_day(1, 600, 60),
+                    MemberFixture::new(3002, "Sleet").with_part1(1, 900),
+                ],
+            ),
+        };
+        let previous_positions = overall_positions(&Scoring::LOCAL, &cache.leaderboard, year);
+        let scraped = private_leaderboard(
+            year,
+            &[
+                MemberFixture::new(3001, "Frosty")
+                    .with_day(1, 600, 60)
+                    .with_part1(2, 3600),
+                MemberFixture::new(3002, "Sleet")
+                    .with_day(1, 900, 60)
+                    .with_day(2, 600, 60),
+            ],
+        );
+        let new_entries = cache.merge_with(ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: scraped,
+        });
+        let current_positions = overall_positions(&Scoring::LOCAL, &cache.leaderboard, year);
+
+        let (frosty, sleet) = (
+            SlackUserId::new("U3001".into()),
+            SlackUserId::new("U3009".into()),
+        );
+        let watchlists = Watchlists::from([
+            (frosty.clone(), HashSet::from([MemberId(3002)])),
+            (sleet.clone(), HashSet::from([MemberId(3001)])),
+        ]);
+        // only Frosty is linked to a member
+        let slack_users = HashMap::from([(MemberId(3001), frosty.clone())]);
+
+        let alerts = watch_alerts(
+            &watchlists,
+            &slack_users,
+            &new_entries,
+            (&previous_positions, &current_positions),
+            year,
+        );
+        let (sleet_id, frosty_id) = (current_positions[0].clone(), current_positions[1].clone());
+        assert_eq!(
+            alerts,
+            vec![
+                (
+                    frosty,
+                    vec![
+                        WatchAlert::Star(sleet_id.clone(), 1, ProblemPart::SECOND),
+                        WatchAlert::Star(sleet_id.clone(), 2, ProblemPart::FIRST),
+                        WatchAlert::Star(sleet_id.clone(), 2, ProblemPart::SECOND),
+                        WatchAlert::Overtake(sleet_id, 1),
+                    ]
+                ),
+                (
+                    sleet,
+                    vec![WatchAlert::Star(frosty_id, 2, ProblemPart::FIRST)]
+                ),
+            ]
+        );
+    }
+}
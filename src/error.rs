@@ -1,58 +1,137 @@
-use std::{error::Error, fmt};
+use reqwest::StatusCode;
+use slack_morphism::errors::SlackClientError;
+use thiserror::Error;
+use tokio::sync::mpsc::error::SendError;
 use tokio_cron_scheduler::JobSchedulerError;
 
 /// Custom Error and Result types to unify errors from all sources.
 pub type BotResult<T> = Result<T, BotError>;
 
-#[derive(Debug)]
+// Large source errors are boxed to keep BotResult small.
+#[derive(Debug, Error)]
 pub enum BotError {
+    #[error("Configuration Error: {0}")]
     Config(String),
-    Http(String),
-    IO(String),
-    Scheduler(String),
-    AOC(String),
-    ChannelSend(String),
-    Slack(String),
-    Compute(String),
+    #[error("Configuration Error: could not load settings. {0}")]
+    Settings(#[source] Box<figment::Error>),
+    #[error("HTTP Error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("HTTP Error: {0}")]
+    HttpStatus(StatusCode),
+    #[error("IO Error: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Scheduler Error: {0}")]
+    Scheduler(#[from] JobSchedulerError),
+    #[error("Scheduler Error: {0}")]
+    JobFailed(String),
+    #[error("AOC Error: {0}")]
+    AOC(&'static str),
+    #[error("MPSC Error: could not send message to MPSC channel, {0}.")]
+    ChannelSend(#[source] SendError<()>),
+    #[error("Slack Communication Error: {0} {1}")]
+    Slack(&'static str, #[source] Box<SlackClientError>),
+    #[error("Slack Communication Error: {0}")]
+    SlackListener(String),
+    #[error("Telegram Communication Error: {0}")]
+    Telegram(String),
+    #[error("Email Error: {0}")]
+    Email(String),
+    #[error("Template Error: {0}")]
+    Template(#[from] minijinja::Error),
+    #[error("Serialization Error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Snapshot Error: {0}")]
+    Snapshot(String),
+    #[error("Rendering Error: {0} {1}")]
+    Render(
+        &'static str,
+        #[source] Box<dyn std::error::Error + Send + Sync>,
+    ),
+    #[error("Computation Error: {0}")]
+    Compute(&'static str),
+    #[error("Parsing Error")]
     Parse,
+    #[error("Parsing Error: {0}")]
+    IncompleteParse(String),
 }
 
-impl fmt::Display for BotError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl BotError {
+    /// Stable code of the error, used to identify it in the monitoring alerts.
+    /// 1xx: configuration, 2xx: AoC/HTTP, 3xx: Slack/Telegram/messaging, 4xx: scheduling,
+    /// 5xx: storage, 6xx: rendering, 7xx: computation/parsing.
+    pub fn code(&self) -> &'static str {
         match self {
-            BotError::Config(s) => write!(f, "Configuration Error: {}", s),
-            BotError::Http(s) => write!(f, "HTTP Error: {}", s),
-            BotError::IO(s) => write!(f, "IO Error: {}", s),
-            BotError::Scheduler(s) => write!(f, "Scheduler Error: {}", s),
-            BotError::AOC(s) => write!(f, "AOC Error: {}", s),
-            BotError::ChannelSend(s) => write!(f, "MPSC Error: {}", s),
-            BotError::Slack(s) => write!(f, "Slack Communication Error: {}", s),
-            BotError::Compute(s) => write!(f, "Computation Error: {}", s),
-            BotError::Parse => write!(f, "Parsing Error"),
+            BotError::Config(_) => "CEO-100",
+            BotError::Settings(_) => "CEO-101",
+            BotError::Http(_) => "CEO-200",
+            BotError::HttpStatus(_) => "CEO-201",
+            BotError::AOC(_) => "CEO-202",
+            BotError::Slack(..) => "CEO-300",
+            BotError::SlackListener(_) => "CEO-301",
+            BotError::ChannelSend(_) => "CEO-302",
+            BotError::Telegram(_) => "CEO-303",
+            BotError::Email(_) => "CEO-304",
+            BotError::Scheduler(_) => "CEO-400",
+            BotError::JobFailed(_) => "CEO-401",
+            BotError::IO(_) => "CEO-500",
+            BotError::Json(_) => "CEO-501",
+            BotError::Snapshot(_) => "CEO-502",
+            BotError::Template(_) => "CEO-600",
+            BotError::Render(..) => "CEO-601",
+            BotError::Compute(_) => "CEO-700",
+            BotError::Parse => "CEO-701",
+            BotError::IncompleteParse(_) => "CEO-702",
         }
     }
-}
 
-impl Error for BotError {}
+    /// Slack error, with the context of the failed call.
+    pub fn slack(context: &'static str) -> impl FnOnce(SlackClientError) -> BotError {
+        move |e| BotError::Slack(context, Box::new(e))
+    }
 
-impl From<reqwest::Error> for BotError {
-    fn from(error: reqwest::Error) -> Self {
-        BotError::Http(error.to_string())
+    /// Rendering error, with the context of the failed step.
+    pub fn render<E: std::error::Error + Send + Sync + 'static>(
+        context: &'static str,
+    ) -> impl FnOnce(E) -> BotError {
+        move |e| BotError::Render(context, Box::new(e))
     }
 }
 
-impl From<std::io::Error> for BotError {
-    fn from(error: std::io::Error) -> Self {
-        BotError::IO(error.to_string())
+impl From<figment::Error> for BotError {
+    fn from(error: figment::Error) -> Self {
+        BotError::Settings(Box::new(error))
     }
 }
 
-impl From<JobSchedulerError> for BotError {
-    fn from(error: JobSchedulerError) -> Self {
-        BotError::Scheduler(error.to_string())
+impl<T> From<SendError<T>> for BotError {
+    fn from(_error: SendError<T>) -> Self {
+        // The unsent message is dropped, only the failure is kept
+        BotError::ChannelSend(SendError(()))
     }
 }
 
 pub fn convert_err(e: reqwest::Error) -> std::io::Error {
     std::io::Error::new(std::io::ErrorKind::Other, e)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_keep_their_source_and_code() {
+        use std::error::Error;
+
+        let json = serde_json::from_str::<u64>("not json").unwrap_err();
+        let error = BotError::from(json);
+        assert_eq!(error.code(), "CEO-501");
+        assert!(error.source().is_some());
+
+        let error = BotError::from(SendError(42));
+        assert_eq!(error.code(), "CEO-302");
+        assert_eq!(
+            error.to_string(),
+            "MPSC Error: could not send message to MPSC channel, channel closed."
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Test fixtures generating realistic AoC private leaderboard JSON responses.
+
+use crate::{
+    client::aoc::AoC,
+    core::leaderboard::{Entry, Leaderboard},
+};
+use itertools::Itertools;
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use serde_json::{json, Map, Value};
+use std::{collections::HashMap, ops::Range};
+
+pub mod aoc_server;
+
+pub const YEAR: i32 = 2023;
+// Ids of the members of the random boards, kept apart from the ids of the other fixtures as the
+// registries of names and absences are shared by the tests
+pub const RANDOM_IDS: Range<u64> = 9000..10_000;
+// Number of random boards generated to check the invariants of the computations
+pub const RANDOM_BOARDS: u32 = 64;
+
+#[derive(Debug, Clone)]
+pub struct MemberFixture {
+    pub id: u64,
+    /// anonymous users appear with null names in the AoC API
+    pub name: Option<String>,
+    /// (day, part, seconds after the puzzle release)
+    pub stars: Vec<(u8, u8, i64)>,
+}
+
+impl MemberFixture {
+    pub fn new(id: u64, name: &str) -> Self {
+        MemberFixture {
+            id,
+            name: Some(name.to_string()),
+            stars: vec![],
+        }
+    }
+
+    pub fn anonymous(id: u64) -> Self {
+        MemberFixture {
+            id,
+            name: None,
+            stars: vec![],
+        }
+    }
+
+    /// Both parts of the day, part 2 being completed `delta` seconds after part 1.
+    pub fn with_day(mut self, day: u8, part1: i64, delta: i64) -> Self {
+        self.stars.push((day, 1, part1));
+        self.stars.push((day, 2, part1 + delta));
+        self
+    }
+
+    /// Only the first part of the day.
+    pub fn with_part1(mut self, day: u8, part1: i64) -> Self {
+        self.stars.push((day, 1, part1));
+        self
+    }
+}
+
+/// Timestamp of a star completed `offset` seconds after the release of the puzzle.
+pub fn star_ts(year: i32, day: u8, offset: i64) -> i64 {
+    Entry::puzzle_unlock(year, day).unwrap().timestamp() + offset
+}
+
+/// Private leaderboard JSON, as returned by the AoC API.
+pub fn private_leaderboard_json(year: i32, members: &[MemberFixture]) -> String {
+    // star indexes are increasing with the completion time, as on AoC, so a star keeps its index
+    // as stars are added to the members (ties broken in the order the members are listed)
+    let star_indexes = members
+        .iter()
+        .enumerate()
+        .flat_map(|(m, member)| {
+            member
+                .stars
+                .iter()
+                .map(move |(day, part, offset)| (star_ts(year, *day, *offset), m, *day, *part))
+        })
+        .sorted()
+        .enumerate()
+        .map(|(index, (_ts, m, day, part))| ((m, day, part), index + 1))
+        .collect::<HashMap<_, _>>();
+    let members = members
+        .iter()
+        .enumerate()
+        .map(|(m, member)| {
+            let mut completion_day_level = Map::new();
+            for (day, part, offset) in member.stars.iter() {
+                let star_index = star_indexes[&(m, *day, *part)];
+                let day_entry = completion_day_level
+                    .entry(day.to_string())
+                    .or_insert_with(|| json!({}));
+                day_entry[part.to_string()] = json!({
+                    "get_star_ts": star_ts(year, *day, *offset),
+                    "star_index": star_index,
+                });
+            }
+            let last_star_ts = member
+                .stars
+                .iter()
+                .map(|(day, _part, offset)| star_ts(year, *day, *offset))
+                .max()
+                .unwrap_or(0);
+            (
+                member.id.to_string(),
+                json!({
+                    "id": member.id,
+                    "name": member.name,
+                    "stars": member.stars.len(),
+                    "local_score": 0,
+                    "global_score": 0,
+                    "last_star_ts": last_star_ts,
+                    "completion_day_level": Value::Object(completion_day_level),
+                }),
+            )
+        })
+        .collect::<Map<String, Value>>();
+
+    json!({
+        "owner_id": members.keys().next().and_then(|id| id.parse::<u64>().ok()).unwrap_or(0),
+        "event": year.to_string(),
+        "members": Value::Object(members),
+    })
+    .to_string()
+}
+
+/// Global leaderboard HTML page for the day, as served by AoC. Entries are (id, name, seconds
+/// after the puzzle release), in rank order, anonymous users having no name.
+pub fn global_leaderboard_html(
+    day: u8,
+    both_parts: &[(u64, Option<&str>, i64)],
+    first_part: &[(u64, Option<&str>, i64)],
+) -> String {
+    let entries = |entries: &[(u64, Option<&str>, i64)]| {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(rank_minus_one, (id, name, offset))| {
+                let name = name.map_or(
+                    format!(r#"<span class="leaderboard-anon">(anonymous user #{id})</span>"#),
+                    |name| format!(r#"<a href="https://github.com/{name}" target="_blank">{name}</a>"#),
+                );
+                format!(
+                    r#"<div class="leaderboard-entry" data-user-id="{id}"><span class="leaderboard-position">{:>3})</span> <span class="leaderboard-time">Dec {day:02}  {:02}:{:02}:{:02}</span> {name}</div>"#,
+                    rank_minus_one + 1,
+                    offset / 3600,
+                    (offset / 60) % 60,
+                    offset % 60,
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    format!(
+        r#"<html><body><main>
+<p>First hundred users to get <span class="leaderboard-daydesc-both">both stars</span> on Day {day}:</p>
+{}
+<p>First hundred users to get the <span class="leaderboard-daydesc-first">first star</span> on Day {day}:</p>
+{}
+</main></body></html>"#,
+        entries(both_parts),
+        entries(first_part),
+    )
+}
+
+/// Daily challenge HTML page, as served by AoC.
+pub fn daily_challenge_html(day: u8, title: &str) -> String {
+    format!(
+        r#"<html><body><main><article class="day-desc"><h2>--- Day {day}: {title} ---</h2><p>...</p></article></main></body></html>"#
+    )
+}
+
+/// Landing page of the event of the year, as served by AoC.
+pub fn event_page_html(year: i32) -> String {
+    format!(
+        r#"<html><head><title>Advent of Code {year}</title></head><body><main><p>...</p></main></body></html>"#
+    )
+}
+
+/// Parsed private leaderboard.
+pub fn private_leaderboard(year: i32, members: &[MemberFixture]) -> Leaderboard {
+    AoC::parse_private_leaderboard(&private_leaderboard_json(year, members)).unwrap()
+}
+
+/// A small leaderboard with the usual suspects:
+/// - a member who completed everything,
+/// - an anonymous member,
+/// - a member without any star,
+/// - a member who only completed the first part of day 25.
+pub fn edge_case_members() -> Vec<MemberFixture> {
+    let completionist = (1..=25).fold(MemberFixture::new(1, "Santa"), |m, day| {
+        m.with_day(day, 600 + day as i64 * 10, 300)
+    });
+    let anonymous = MemberFixture::anonymous(2)
+        .with_day(1, 900, 120)
+        .with_part1(2, 1500);
+    let no_stars = MemberFixture::new(3, "Lazy Elf");
+    let last_day_single_part = (1..=24)
+        .fold(MemberFixture::new(4, "Rudolph"), |m, day| {
+            m.with_day(day, 1200, 600)
+        })
+        .with_part1(25, 3600);
+    vec![completionist, anonymous, no_stars, last_day_single_part]
+}
+
+/// A board with `n_members` members with deterministic pseudo-random completion times, some
+/// members skipping days or only completing the first part.
+pub fn huge_board_members(n_members: usize) -> Vec<MemberFixture> {
+    let days = prop::collection::vec(prop::collection::vec(day_strategy(), 25), n_members);
+    let days = days
+        .new_tree(&mut TestRunner::deterministic())
+        .unwrap()
+        .current();
+    (1..).zip(days).map(|(id, days)| member(id, days)).collect()
+}
+
+/// Boards of 1 to `max_members` members with random ids and completion times, to check the
+/// invariants of the computations on many different boards.
+pub fn random_board(max_members: usize) -> impl Strategy<Value = Vec<MemberFixture>> {
+    prop::collection::btree_map(
+        RANDOM_IDS,
+        prop::collection::vec(day_strategy(), 25),
+        1..=max_members,
+    )
+    .prop_map(|members| {
+        members
+            .into_iter()
+            .map(|(id, days)| member(id, days))
+            .collect()
+    })
+}
+
+// Completion of a day: skipped, only the first part, or both parts (part 1, delta of part 2).
+fn day_strategy() -> impl Strategy<Value = Option<(i64, Option<i64>)>> {
+    prop_oneof![
+        1 => Just(None),
+        1 => (60..86_460i64).prop_map(|part1| Some((part1, None))),
+        8 => (60..86_460i64, 1..7_201i64).prop_map(|(part1, delta)| Some((part1, Some(delta)))),
+    ]
+}
+
+fn member(id: u64, days: Vec<Option<(i64, Option<i64>)>>) -> MemberFixture {
+    (1..=25).zip(days).fold(
+        MemberFixture::new(id, &format!("Elf #{id}")),
+        |m, (day, completion)| match completion {
+            None => m,
+            Some((part1, None)) => m.with_part1(day, part1),
+            Some((part1, Some(delta))) => m.with_day(day, part1, delta),
+        },
+    )
+}
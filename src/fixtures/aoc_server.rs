@@ -0,0 +1,177 @@
+//! Mock AoC server serving canned leaderboards and challenges, for the integration tests.
+//!
+//! The server is shared by all the tests of the process and the bot settings are initialized to
+//! target it, so `mock_aoc()` must be called before anything reading `config::SETTINGS`.
+
+use crate::{
+    config,
+    fixtures::{private_leaderboard_json, MemberFixture},
+};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::get,
+    Router,
+};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+pub const SESSION_COOKIE: &str = "mock-session-cookie";
+pub const PRIVATE_LEADERBOARD_ID: u64 = 424242;
+
+static MOCK_AOC: Lazy<MockAoC> = Lazy::new(MockAoC::start);
+
+/// Shared mock AoC server. Tests sharing a year must not run concurrently, so each test should
+/// use its own year whenever possible.
+pub fn mock_aoc() -> &'static MockAoC {
+    &MOCK_AOC
+}
+
+#[derive(Default)]
+struct Pages {
+    events: HashMap<i32, String>,
+    private_leaderboards: HashMap<i32, String>,
+    global_leaderboards: HashMap<(i32, u8), String>,
+    challenges: HashMap<(i32, u8), String>,
+}
+
+type SharedPages = Arc<Mutex<Pages>>;
+
+pub struct MockAoC {
+    pub base_url: String,
+    pages: SharedPages,
+}
+
+impl MockAoC {
+    fn start() -> MockAoC {
+        let pages = SharedPages::default();
+        let app = Router::new()
+            .route("/{year}", get(event))
+            .route(
+                "/{year}/leaderboard/private/view/{file}",
+                get(private_leaderboard),
+            )
+            .route("/{year}/leaderboard/day/{day}", get(global_leaderboard))
+            .route("/{year}/day/{day}", get(challenge))
+            .with_state(pages.clone());
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        // Each test has its own runtime, so the server gets a dedicated one.
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                axum::serve(listener, app).await.unwrap();
+            });
+        });
+
+        for (key, value) in [
+            ("AOC__BASE_URL", base_url.as_str()),
+            ("AOC__SESSION_COOKIE", SESSION_COOKIE),
+            (
+                "AOC__PRIVATE_LEADERBOARD_ID",
+                &PRIVATE_LEADERBOARD_ID.to_string(),
+            ),
+            ("SLACK__TOKEN", "xoxb-mock"),
+            ("SLACK__APP_TOKEN", "xapp-mock"),
+            ("SLACK__DEFAULT_CHANNEL", "C0000MOCK0"),
+        ] {
+            std::env::set_var(key, value);
+        }
+        config::load().unwrap();
+
+        MockAoC { base_url, pages }
+    }
+
+    pub fn set_event_page(&self, year: i32, html: String) {
+        let mut pages = self.pages.lock().unwrap();
+        pages.events.insert(year, html);
+    }
+
+    pub fn set_private_leaderboard(&self, year: i32, members: &[MemberFixture]) {
+        let mut pages = self.pages.lock().unwrap();
+        pages
+            .private_leaderboards
+            .insert(year, private_leaderboard_json(year, members));
+    }
+
+    pub fn set_global_leaderboard(&self, year: i32, day: u8, html: String) {
+        let mut pages = self.pages.lock().unwrap();
+        pages.global_leaderboards.insert((year, day), html);
+    }
+
+    pub fn set_challenge(&self, year: i32, day: u8, html: String) {
+        let mut pages = self.pages.lock().unwrap();
+        pages.challenges.insert((year, day), html);
+    }
+}
+
+async fn event(
+    State(pages): State<SharedPages>,
+    Path(year): Path<i32>,
+) -> Result<String, StatusCode> {
+    let pages = pages.lock().unwrap();
+    pages
+        .events
+        .get(&year)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn private_leaderboard(
+    State(pages): State<SharedPages>,
+    Path((year, file)): Path<(i32, String)>,
+    headers: HeaderMap,
+) -> Result<String, StatusCode> {
+    // AoC responds with an internal server error when the session cookie is invalid
+    let authenticated = headers
+        .get("cookie")
+        .and_then(|cookie| cookie.to_str().ok())
+        .is_some_and(|cookie| cookie == format!("session={SESSION_COOKIE}"));
+    if !authenticated {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    if file != format!("{PRIVATE_LEADERBOARD_ID}.json") {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let pages = pages.lock().unwrap();
+    pages
+        .private_leaderboards
+        .get(&year)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn global_leaderboard(
+    State(pages): State<SharedPages>,
+    Path((year, day)): Path<(i32, u8)>,
+) -> Result<String, StatusCode> {
+    let pages = pages.lock().unwrap();
+    pages
+        .global_leaderboards
+        .get(&(year, day))
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn challenge(
+    State(pages): State<SharedPages>,
+    Path((year, day)): Path<(i32, u8)>,
+) -> Result<String, StatusCode> {
+    let pages = pages.lock().unwrap();
+    pages
+        .challenges
+        .get(&(year, day))
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,427 @@
+//! End to end tests of the AoC client and the scheduler jobs, against the mock AoC server.
+//! Each test uses its own year (or its own members ids) so they can run concurrently.
+
+use crate::{
+    client::{aoc::AoC, provider::EventProvider, self_check},
+    core::{
+        commands::Command,
+        events::Event,
+        leaderboard::{
+            Entry, GlobalParseReport, Identifier, Leaderboard, MemberId, ProblemPart,
+            ScrapedLeaderboard,
+        },
+    },
+    error::BotResult,
+    fixtures::{
+        aoc_server::{mock_aoc, PRIVATE_LEADERBOARD_ID},
+        daily_challenge_html, edge_case_members, event_page_html, global_leaderboard_html,
+        private_leaderboard, MemberFixture,
+    },
+    scheduler::{
+        announce_next_event, pipeline, send_daily_summary, send_reminders,
+        update_private_leaderboard,
+    },
+    storage::{precomputed::Precomputed, MemoryCache},
+    utils::{current_aoc_year_day, merge_with_highlights},
+};
+use chrono::{Datelike, TimeZone, Utc};
+use itertools::Itertools;
+use slack_morphism::SlackUserId;
+use tokio::sync::mpsc::{self, Receiver};
+
+fn received_events(rx: &mut Receiver<Event>) -> Vec<Event> {
+    let mut events = vec![];
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn private_leaderboard_is_fetched_and_parsed() {
+    let aoc = mock_aoc();
+    aoc.set_private_leaderboard(2016, &edge_case_members());
+
+    let scraped = AoC::new().private_leaderboard(2016).await.unwrap();
+
+    // Santa: 50 stars, anonymous: 3 stars, Rudolph: 49 stars, Lazy Elf: none.
+    assert_eq!(scraped.leaderboard.len(), 102);
+    assert_eq!(scraped.leaderboard.members_ids().len(), 3);
+    assert!(scraped.leaderboard.iter().all(|e| e.year == 2016));
+}
+
+#[tokio::test]
+async fn missing_private_leaderboard_is_an_error() {
+    mock_aoc();
+
+    assert!(AoC::new().private_leaderboard(2010).await.is_err());
+}
+
+#[tokio::test]
+async fn session_cookie_is_checked_against_the_private_leaderboard() {
+    let aoc = mock_aoc();
+    aoc.set_private_leaderboard(
+        2012,
+        &[MemberFixture::new(7151, "Pepper").with_part1(1, 60)],
+    );
+
+    assert!(self_check::check_aoc(&AoC::new(), 2012).await.is_empty());
+    let problems = self_check::check_aoc(&AoC::new(), 2013).await;
+    assert!(problems[0].starts_with("aoc.session_cookie: could not fetch the 2013"));
+}
+
+#[tokio::test]
+async fn global_leaderboard_is_fetched_and_parsed() {
+    let aoc = mock_aoc();
+    aoc.set_global_leaderboard(
+        2017,
+        3,
+        global_leaderboard_html(
+            3,
+            &[(7001, Some("Comet"), 125), (7002, None, 300)],
+            &[(7001, Some("Comet"), 60), (7003, Some("Prancer"), 90)],
+        ),
+    );
+
+    let (scraped, report) = AoC::new().global_leaderboard(2017, 3).await.unwrap();
+    assert_eq!((report.parsed, report.expected), (4, 4));
+    assert!(report.failures.is_empty());
+    let entries = scraped
+        .leaderboard
+        .iter()
+        .map(|e| (e.id.numeric, e.id.name.as_str(), e.part, e.rank))
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries.len(), 4);
+    for expected in [
+        (MemberId(7001), "Comet", ProblemPart::FIRST, Some(1)),
+        (MemberId(7003), "Prancer", ProblemPart::FIRST, Some(2)),
+        (MemberId(7001), "Comet", ProblemPart::SECOND, Some(1)),
+        (
+            MemberId(7002),
+            "anonymous user #7002",
+            ProblemPart::SECOND,
+            Some(2),
+        ),
+    ] {
+        assert!(entries.contains(&expected), "missing {expected:?}");
+    }
+    let comet_p2 = scraped
+        .leaderboard
+        .iter()
+        .find(|e| e.id.numeric == MemberId(7001) && e.part == ProblemPart::SECOND)
+        .unwrap();
+    assert_eq!(
+        comet_p2.duration_since_release().unwrap().num_seconds(),
+        125
+    );
+}
+
+#[tokio::test]
+async fn daily_challenge_title_is_parsed() {
+    let aoc = mock_aoc();
+    aoc.set_challenge(2018, 5, daily_challenge_html(5, "Alchemical Reduction"));
+
+    let title = AoC::new().daily_challenge(2018, 5).await.unwrap();
+
+    assert_eq!(title, "--- Day 5: Alchemical Reduction ---");
+}
+
+#[tokio::test]
+async fn next_event_is_announced_once_its_page_is_live() {
+    let aoc = mock_aoc();
+    let cache = MemoryCache::new();
+    let (tx, mut rx) = mpsc::channel(8);
+
+    // before the announcement, AoC redirects to the page of the last event
+    aoc.set_event_page(2014, event_page_html(2013));
+    assert!(
+        !announce_next_event(&AoC::new(), &cache, &tx, 2014, "4242-c0de")
+            .await
+            .unwrap()
+    );
+    assert!(received_events(&mut rx).is_empty());
+
+    aoc.set_event_page(2014, event_page_html(2014));
+    assert!(
+        announce_next_event(&AoC::new(), &cache, &tx, 2014, "4242-c0de")
+            .await
+            .unwrap()
+    );
+    match &received_events(&mut rx)[..] {
+        [Event::NextEventAnnounced(2014, join_code, url)] => {
+            assert_eq!(join_code, "4242-c0de");
+            assert_eq!(url, &format!("{}/2014/leaderboard/private", aoc.base_url));
+        }
+        events => panic!("unexpected events: {events:?}"),
+    };
+    assert_eq!(
+        cache.announced_event.read(|announced| *announced),
+        Some(2014)
+    );
+
+    assert!(!AoC::new().event_is_live(2015).await.unwrap());
+}
+
+#[test]
+fn links_target_the_configured_private_leaderboard() {
+    let aoc = mock_aoc();
+
+    let command = Command::build_from(
+        "!link 3 2018".to_string(),
+        &ScrapedLeaderboard::new(),
+        &Precomputed::default(),
+    );
+
+    match command {
+        Some(Command::Link(2018, 3, puzzle, private_leaderboard, global_leaderboard)) => {
+            assert_eq!(puzzle, format!("{}/2018/day/3", aoc.base_url));
+            assert_eq!(
+                private_leaderboard,
+                format!(
+                    "{}/2018/leaderboard/private/view/{PRIVATE_LEADERBOARD_ID}",
+                    aoc.base_url
+                )
+            );
+            assert_eq!(
+                global_leaderboard,
+                format!("{}/2018/leaderboard/day/3", aoc.base_url)
+            );
+        }
+        command => panic!("unexpected command: {command:?}"),
+    }
+}
+
+#[tokio::test]
+async fn highlights_are_computed_between_two_scrapes() {
+    let aoc = mock_aoc();
+    let client = AoC::new();
+    let before = vec![MemberFixture::new(7101, "Donner").with_part1(1, 300)];
+    let after = vec![MemberFixture::new(7101, "Donner")
+        .with_day(1, 300, 200)
+        .with_day(2, 400, 100)];
+
+    aoc.set_private_leaderboard(2019, &before);
+    let mut cached = client.private_leaderboard(2019).await.unwrap();
+    aoc.set_private_leaderboard(2019, &after);
+    let new = client.private_leaderboard(2019).await.unwrap();
+
+    let (_new_entries, highlights) = merge_with_highlights(&mut cached, new);
+    let highlights = highlights
+        .into_iter()
+        .map(|h| (h.day, h.n_stars))
+        .collect::<Vec<_>>();
+    assert_eq!(highlights.len(), 2);
+    assert!(highlights.contains(&(1, 1)));
+    assert!(highlights.contains(&(2, 2)));
+}
+
+#[tokio::test]
+async fn private_leaderboard_update_triggers_announcements() {
+    let aoc = mock_aoc();
+    let (year, _day) = current_aoc_year_day();
+    let cache = MemoryCache::new();
+    let (tx, mut rx) = mpsc::channel(64);
+
+    aoc.set_private_leaderboard(
+        year,
+        &[
+            MemberFixture::new(7201, "Dasher").with_day(1, 60, 60),
+            MemberFixture::new(7202, "Dancer").with_part1(1, 120),
+        ],
+    );
+    update_private_leaderboard(&AoC::new(), &cache, &tx).await;
+    let events = received_events(&mut rx);
+
+    assert!(matches!(
+        &events[..],
+        [
+            Event::PrivateLeaderboardNewMembers(members),
+            Event::PrivateLeaderboardNewEntries(_),
+            Event::PrivateLeaderboardUpdated,
+        ] if members.len() == 2
+    ));
+
+    // Dancer catches up and takes the lead
+    aoc.set_private_leaderboard(
+        year,
+        &[
+            MemberFixture::new(7201, "Dasher").with_day(1, 60, 60),
+            MemberFixture::new(7202, "Dancer")
+                .with_day(1, 120, 600)
+                .with_day(2, 60, 60),
+        ],
+    );
+    update_private_leaderboard(&AoC::new(), &cache, &tx).await;
+    let events = received_events(&mut rx);
+
+    assert!(matches!(
+        &events[..],
+        [
+            Event::PrivateLeaderboardNewEntries(highlights),
+            Event::PrivateLeaderboardOvertakes(overtakes),
+            Event::PrivateLeaderboardUpdated,
+        ] if highlights.len() == 2
+            && overtakes.len() == 1
+            && overtakes[0].0.numeric == MemberId(7202)
+            && overtakes[0].1.numeric == MemberId(7201)
+    ));
+    let cached_members = cache.data.read().unwrap().leaderboard.members_ids();
+    assert!(cached_members.contains(&MemberId(7202)));
+}
+
+#[tokio::test]
+async fn failing_year_does_not_stop_the_update_pipeline() {
+    let aoc = mock_aoc();
+    let cache = MemoryCache::new();
+    let (tx, mut rx) = mpsc::channel(64);
+
+    aoc.set_private_leaderboard(
+        2020,
+        &[MemberFixture::new(7401, "Blitzen").with_day(1, 60, 60)],
+    );
+    aoc.set_private_leaderboard(
+        2021,
+        &[MemberFixture::new(7402, "Prancer").with_part1(1, 60)],
+    );
+    // no leaderboard for 2011
+    pipeline::run(&AoC::new(), &cache, &tx, vec![2020, 2011, 2021], false).await;
+    let events = received_events(&mut rx);
+
+    // only the failure is reported, nothing being announced at initialization
+    assert!(matches!(&events[..], [Event::MonitoringAlert(..)]));
+    let cached_members = cache.data.read().unwrap().leaderboard.members_ids();
+    assert!(cached_members.contains(&MemberId(7401)) && cached_members.contains(&MemberId(7402)));
+    let stats = cache.pipeline_stats.lock().unwrap().clone();
+    assert_eq!(stats.runs, 1);
+    assert_eq!(stats.fetch.failures, 1);
+    assert_eq!(stats.parse.failures + stats.merge.failures, 0);
+}
+
+// Provider of another event, with its own API: one `member_id,name,day,part,timestamp` line per star.
+struct CsvProvider;
+
+impl EventProvider for CsvProvider {
+    async fn fetch_private_leaderboard(&self, year: i32) -> BotResult<String> {
+        let unlock = Entry::puzzle_unlock(year, 1)?.timestamp();
+        Ok(format!(
+            "7501,Sparkle,1,1,{}\n7501,Sparkle,1,2,{}",
+            unlock + 60,
+            unlock + 90
+        ))
+    }
+
+    fn parse_private_leaderboard(response: &str) -> BotResult<(Leaderboard, Vec<Identifier>)> {
+        let mut leaderboard = Leaderboard::new();
+        for line in response.lines() {
+            let fields = line.split(',').collect::<Vec<&str>>();
+            let number = |i: usize| fields[i].parse::<i64>().unwrap();
+            let timestamp = Utc.timestamp_opt(number(4), 0).unwrap();
+            leaderboard.insert(Entry {
+                timestamp,
+                star_index: None,
+                year: timestamp.year(),
+                day: number(2) as u8,
+                part: ProblemPart::from(number(3) as usize),
+                id: Identifier {
+                    name: fields[1].to_string(),
+                    numeric: MemberId(number(0) as u64),
+                },
+                rank: None,
+            });
+        }
+        let members = leaderboard.iter().map(|e| e.id.clone()).unique().collect();
+        Ok((leaderboard, members))
+    }
+
+    async fn global_leaderboard(
+        &self,
+        _year: i32,
+        _day: u8,
+    ) -> BotResult<(ScrapedLeaderboard, GlobalParseReport)> {
+        Ok((ScrapedLeaderboard::new(), GlobalParseReport::default()))
+    }
+
+    async fn daily_challenge(&self, _year: i32, day: u8) -> BotResult<String> {
+        Ok(format!("Quest {day}"))
+    }
+
+    async fn event_is_live(&self, _year: i32) -> BotResult<bool> {
+        Ok(true)
+    }
+
+    fn daily_challenge_url(&self, year: i32, day: u8) -> String {
+        format!("https://example.com/{year}/quests/{day}")
+    }
+
+    fn private_leaderboard_url(&self, year: i32) -> String {
+        format!("https://example.com/{year}/leaderboard")
+    }
+
+    fn global_leaderboard_url(&self, year: i32, _day: u8) -> String {
+        format!("https://example.com/{year}/leaderboard")
+    }
+
+    fn join_private_leaderboard_url(&self, year: i32) -> String {
+        format!("https://example.com/{year}/leaderboard/join")
+    }
+}
+
+#[tokio::test]
+async fn other_event_providers_plug_into_the_update_pipeline() {
+    let cache = MemoryCache::new();
+    let (tx, mut rx) = mpsc::channel(64);
+
+    pipeline::run(&CsvProvider, &cache, &tx, vec![2022], false).await;
+
+    assert!(received_events(&mut rx).is_empty());
+    let data = cache.data.read().unwrap();
+    assert_eq!(data.leaderboard.len(), 2);
+    assert!(data.leaderboard.members_ids().contains(&MemberId(7501)));
+}
+
+#[tokio::test]
+async fn daily_summary_and_reminders_are_computed_from_cache() {
+    mock_aoc();
+    let (year, day) = current_aoc_year_day();
+    let cache = MemoryCache::new();
+    let (tx, mut rx) = mpsc::channel(64);
+
+    {
+        let mut data = cache.data.write().unwrap();
+        data.merge_with(ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                year,
+                &[
+                    MemberFixture::new(7301, "Vixen").with_day(day, 60, 60),
+                    MemberFixture::new(7302, "Cupid").with_part1(day, 3600),
+                ],
+            ),
+        });
+    }
+    let user = SlackUserId("U0CUPID".to_string());
+    cache
+        .reminders
+        .update(|reminders| reminders.insert(user.clone(), MemberId(7302)));
+
+    send_daily_summary(&cache, &tx).await;
+    send_reminders(&cache, &tx).await;
+    let events = received_events(&mut rx);
+
+    match &events[..] {
+        [Event::DailySummary(summary_year, summary_day, p1, p2, delta, splits, _difficulty), Event::PersonalReminder(reminded, reminder_year, missing)] =>
+        {
+            assert_eq!((*summary_year, *summary_day), (year, day));
+            assert_eq!((p1.len(), p2.len(), delta.len()), (2, 1, 1));
+            // part 2 as fast as part 1, not a negative split
+            assert!(splits.is_empty());
+            assert_eq!(reminded, &user);
+            assert_eq!(*reminder_year, year);
+            // nothing done on the previous days, and part 2 missing today
+            assert_eq!(missing.len(), day as usize);
+            assert_eq!(missing.last(), Some(&(day, 1)));
+        }
+        events => panic!("unexpected events: {events:?}"),
+    }
+}
@@ -0,0 +1,13 @@
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod core;
+pub mod error;
+#[cfg(test)]
+pub mod fixtures;
+#[cfg(test)]
+mod integration_tests;
+pub mod scheduler;
+pub mod server;
+pub mod storage;
+pub mod utils;
@@ -1,28 +1,35 @@
 use chrono::{Timelike, Utc};
+use clap::Parser;
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::info;
-
-use client::slack::AoCSlackClient;
-use core::events::Event;
-use scheduler::{JobProcess, Scheduler};
-use storage::MemoryCache;
-
-pub mod cli;
-pub mod client;
-pub mod config;
-pub mod core;
-pub mod error;
-pub mod scheduler;
-pub mod storage;
-pub mod utils;
+use tracing::{error, info, warn};
+
+use ceo::{
+    cli,
+    client::{aoc::AoC, self_check, slack::AoCSlackClient, telegram::AoCTelegramClient},
+    config::{self, MessagingBackend},
+    core::{
+        absences, commands,
+        events::Event,
+        exclusions,
+        leaderboard::{MemberId, AOC_PUZZLE_UTC_STARTING_HOUR},
+        names, standings, templates, theme,
+    },
+    error::BotError,
+    scheduler::{
+        JobProcess, Scheduler, COUNTDOWN_MINUTES, HIGHLIGHTS_FLUSH_SCHEDULE,
+        NEXT_EVENT_WATCH_SCHEDULE, PRIVATE_LEADERBOARD_REFRESH_MIN, SEASON_WATCH_SCHEDULE,
+    },
+    server,
+    storage::MemoryCache,
+};
 
 #[tokio::main]
 // async fn main() -> Result<(), Box<dyn std::error::Error>> {
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
 
-    let settings = &config::SETTINGS;
+    let settings = config::load()?;
 
     let subscriber = tracing_subscriber::FmtSubscriber::builder()
         .with_max_level(settings.get_trace_level())
@@ -30,6 +37,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     tracing::subscriber::set_global_default(subscriber).expect("Setting default subscriber failed");
 
+    for deprecation in settings.deprecations() {
+        warn!("{deprecation}");
+    }
+    info!("Effective configuration:\n{settings}");
+
+    // Registered before any message is rendered, the theme file being validated with the settings.
+    theme::register(settings.get_theme()?);
+    // Templates of the language loaded in the templating engine environment at the first rendering.
+    templates::register_language(settings.language);
+    // Templates overrides are loaded (and reloaded when modified) when the messages are rendered.
+    if let Some(dir) = &settings.templates_dir {
+        templates::register_dir(dir);
+    }
+    // Jerseys points curves used by the standings, before any standing is computed.
+    standings::register_jersey_points(settings.jerseys.clone());
+    // Commands prefix and aliases compiled in the commands parser, before any message is parsed.
+    commands::register(settings.get_command_syntax());
+
     // Silencing the warning, as removing the mut here would actually break compilation.
     #[allow(unused_mut)]
     // Capacity of 64 should be more than plenty to handle all the messages
@@ -41,22 +66,127 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let now_minute = now.minute();
     let now_second = now.second();
 
-    // At every 15th minute from (now_minute % 15) through 59.
-    let private_leaderboard_schedule = format!("{} {}/15 * * 12,1 *", now_second, now_minute % 15);
+    // At every 15th minute from (now_minute % 15) through 59, idling off-season.
+    let private_leaderboard_schedule = format!(
+        "{} {}/{PRIVATE_LEADERBOARD_REFRESH_MIN} * * * *",
+        now_second,
+        now_minute % PRIVATE_LEADERBOARD_REFRESH_MIN
+    );
+
+    // Created before the cache, so the state can be persisted on a fresh deployment.
+    if let Some(dir) = &settings.storage_dir {
+        std::fs::create_dir_all(dir)?;
+    }
 
     // Initialize global cache
     let cache = MemoryCache::new();
 
-    let sched = Scheduler::new(cache.clone(), Arc::new(tx.clone())).await?;
+    // Warm start from the last leaderboard snapshot, before the first scrape.
+    cache.load_snapshot();
+
+    // Link members to their Slack users, links made with `!iam` taking precedence.
+    names::register_slack_users(settings.get_aoc_slack_users());
+    names::register_slack_users(cache.slack_users.read(|users| users.clone()));
+
+    // Settings checked against the messaging platform (and AoC with `--check`, the private
+    // leaderboard being scraped at startup anyway), every problem being reported at once. The
+    // bot still starts, the services might only be unavailable for a moment.
+    let check = cli::Cli::parse().check;
+    let problems = self_check::run(&cache, check).await;
+    if check {
+        match problems.is_empty() {
+            true => println!("Self-check passed."),
+            false => eprintln!("Self-check failed:\n  - {}", problems.join("\n  - ")),
+        };
+        std::process::exit(if problems.is_empty() { 0 } else { 1 });
+    };
+    if !problems.is_empty() {
+        let error = BotError::Config(format!("\n  - {}", problems.join("\n  - ")));
+        eprintln!("Self-check failed: {error}");
+        tx.send(Event::MonitoringAlert("Startup self-check failed.", error))
+            .await?;
+    };
 
-    let jobs = vec![
+    // Absences declared with `!tdf absence`, not penalized for the yellow jersey.
+    absences::register(cache.absences.read(|absences| absences.clone()));
+
+    // Members left out of the standings, in the settings or with `!exclude`.
+    exclusions::register(settings.aoc.excluded_members.iter().map(|id| MemberId(*id)));
+    exclusions::register(cache.exclusions.read(|exclusions| exclusions.clone()));
+
+    // Members never named in the messages, in the settings or with `!optout`.
+    names::register_anonymous_members(
+        settings
+            .aoc
+            .anonymous_members
+            .iter()
+            .map(|id| MemberId(*id)),
+    );
+    names::register_opt_outs(cache.opt_outs.read(|opt_outs| opt_outs.clone()));
+
+    let sched = Scheduler::new(AoC::new(), cache.clone(), Arc::new(tx.clone())).await?;
+
+    // Countdown before each puzzle unlock, e.g. T-10min is posted at 04:50 UTC.
+    let countdown_schedules = COUNTDOWN_MINUTES
+        .iter()
+        .map(|minutes| {
+            let minute_of_day = AOC_PUZZLE_UTC_STARTING_HOUR * 60 - minutes;
+            let schedule = format!("0 {} {} 1-25 12 *", minute_of_day % 60, minute_of_day / 60);
+            (schedule, *minutes)
+        })
+        .collect::<Vec<(String, u32)>>();
+
+    let quiet_hours_schedule = settings.quiet_hours().map(|hours| hours.end_schedule());
+    let difficulty_poll_schedule = settings.difficulty_poll_schedule();
+
+    let schedules = &settings.scheduling;
+    let mut jobs = vec![
         JobProcess::InitializePrivateLeaderboard, // only ran once, at startup.
+        JobProcess::WatchSeason(SEASON_WATCH_SCHEDULE),
         JobProcess::UpdatePrivateLeaderboard(&private_leaderboard_schedule),
-        JobProcess::InitializeDailySolutionsThread("0 30 7 1-25 12 *"),
-        JobProcess::WatchGlobalLeaderboard("0 0 5 1-25 12 *"),
-        JobProcess::ParseDailyChallenge("1 0 5 1-25 12 *"),
-        JobProcess::SendDailySummary("0 30 16 1-25 12 *"),
+        JobProcess::InitializeDailySolutionsThread(&schedules.daily_solutions_thread),
+        JobProcess::WatchGlobalLeaderboard(&schedules.global_leaderboard),
+        JobProcess::ResumeGlobalLeaderboardWatch, // only ran once, at startup.
+        JobProcess::ParseDailyChallenge(&schedules.daily_challenge),
+        JobProcess::SendDailySummary(&schedules.daily_summary),
+        JobProcess::SendReminders(&schedules.reminders),
+        JobProcess::SummarizeDailySolutionsThread(&schedules.solutions_thread_summary),
     ];
+    if let Some(join_code) = &settings.aoc.join_code {
+        jobs.push(JobProcess::WatchNextEvent(
+            NEXT_EVENT_WATCH_SCHEDULE,
+            join_code,
+        ));
+    };
+    if settings.features.heroes_history {
+        jobs.push(JobProcess::ScanGlobalHeroes); // only ran once, after the startup scrape.
+    };
+    if settings.features.year_in_review {
+        jobs.push(JobProcess::SendYearInReview(&schedules.year_in_review));
+    };
+    if settings.features.weekly_recap {
+        jobs.push(JobProcess::SendWeeklyRecap(&schedules.weekly_recap));
+    };
+    if settings.features.awards_ceremony {
+        jobs.push(JobProcess::SendAwardsCeremony(&schedules.awards_ceremony));
+    };
+    if settings.features.heartbeat {
+        jobs.push(JobProcess::SendHeartbeat(&schedules.heartbeat));
+    };
+    if let Some(schedule) = &quiet_hours_schedule {
+        jobs.push(JobProcess::EndQuietHours(schedule));
+    };
+    if settings.highlights_debounce().is_some() {
+        jobs.push(JobProcess::FlushHighlights(HIGHLIGHTS_FLUSH_SCHEDULE));
+    };
+    if let Some(schedule) = &difficulty_poll_schedule {
+        jobs.push(JobProcess::SendDifficultyPoll(schedule));
+    };
+    if settings.features.countdown {
+        for (schedule, minutes) in &countdown_schedules {
+            jobs.push(JobProcess::SendCountdown(schedule, *minutes));
+        }
+    };
     for job in jobs {
         sched.add_job(job).await?;
     }
@@ -64,11 +194,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     info!("Starting scheduler.");
     sched.start().await?;
 
+    let (refresh, refresh_requests) = mpsc::channel(1);
+    sched.serve_refreshes(refresh_requests);
+
+    if let Some(port) = settings.server.http_port {
+        info!("Starting HTTP server.");
+        let (cache, sender) = (cache.clone(), Arc::new(tx.clone()));
+        tokio::spawn(async move {
+            if let Err(e) = server::serve(port, cache, sender, refresh).await {
+                error!("{e}");
+            };
+        });
+    };
+
     info!("Initializing messaging engine.");
 
-    let slack_client = AoCSlackClient::new().expect("Slack client could not be initialized");
-    slack_client
-        .handle_messages_and_events(cache, tx, rx)
-        .await?;
+    match settings.messaging_backend {
+        MessagingBackend::Slack => {
+            let slack_client =
+                AoCSlackClient::new().expect("Slack client could not be initialized");
+            slack_client
+                .handle_messages_and_events(cache, tx, rx)
+                .await?;
+        }
+        MessagingBackend::Telegram => {
+            AoCTelegramClient::new()?
+                .handle_messages_and_events(cache, tx, rx)
+                .await?;
+        }
+    };
     Ok(())
 }
@@ -1,39 +1,120 @@
 use crate::{
-    client::aoc::AoC,
+    client::provider::EventProvider,
     config,
     core::{
+        awards::season_awards,
+        commands::Command,
+        difficulty::{day_difficulty, DayDifficulty, GlobalCompletion},
         events::Event,
-        standings::{Ranking, Standing},
+        leaderboard::{Entry, Identifier, LeaderboardStatistics, MemberId},
+        names,
+        predictions::closest_guesses,
+        recap::weekly_recap,
+        review::{day_review, year_review},
+        splits::{self, NegativeSplit},
+        standings::{Ranking, Scoring},
     },
     error::{BotError, BotResult},
     storage::MemoryCache,
-    utils::{compute_highlights, current_aoc_year_day, get_new_members},
+    utils::{current_aoc_year_day, season_at, Season},
+};
+use chrono::{DateTime, Datelike, Local, Utc};
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{
+        mpsc::{Receiver, Sender},
+        oneshot,
+    },
+    time,
 };
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::mpsc::Sender, time};
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info};
 
-pub struct Scheduler {
+pub mod heartbeat;
+pub mod history;
+pub mod pipeline;
+pub mod watch;
+
+use history::TrackedRun;
+use watch::GlobalWatch;
+
+// Only overtakes happening in the top positions of the overall standings are announced.
+const OVERTAKES_TOP_POSITIONS: usize = 5;
+// Maximum number of overtakes announced after a single update.
+const OVERTAKES_MAX_PER_UPDATE: usize = 3;
+// Minimum time before announcing again an overtake between the same two members.
+const OVERTAKES_COOLDOWN_MIN: i64 = 60;
+// Minutes before the puzzle unlock at which a countdown message is posted.
+pub const COUNTDOWN_MINUTES: [u32; 3] = [60, 10, 1];
+// Daily check of the season, right after the puzzle unlock hour at which the event window opens.
+pub const SEASON_WATCH_SCHEDULE: &str = "30 0 5 * * *";
+// Check, every minute, of the highlights held back once their debounce window has elapsed.
+pub const HIGHLIGHTS_FLUSH_SCHEDULE: &str = "0 * * * * *";
+// Hourly check of the page of the next event in the months before its first puzzle unlock.
+pub const NEXT_EVENT_WATCH_SCHEDULE: &str = "0 0 * * 10-12 *";
+// Interval between two refreshes of the private leaderboard, AoC asking not to fetch it more often.
+pub const PRIVATE_LEADERBOARD_REFRESH_MIN: u32 = 15;
+// Delay before scanning the past global leaderboards, for the startup scrape to load the years.
+const GLOBAL_HEROES_SCAN_DELAY_SEC: u64 = 120;
+// Pause between two requests to the past global leaderboards, not to hammer the AoC servers.
+const GLOBAL_HEROES_SCAN_THROTTLE_SEC: u64 = 5;
+// Pause between two scrapes of the past events already loaded from the snapshot at startup.
+const PAST_YEARS_RESCRAPE_THROTTLE_SEC: u64 = 30;
+// Longest pause between two polls of the global leaderboard while AoC keeps failing.
+const GLOBAL_WATCH_MAX_BACKOFF_SEC: u64 = 1800;
+
+// On-demand refresh of the private leaderboard (e.g. through the admin API), answered once done.
+pub type RefreshRequest = oneshot::Sender<()>;
+
+pub struct Scheduler<P> {
     scheduler: JobScheduler,
+    // source of the leaderboards and puzzles (e.g. AoC)
+    provider: Arc<P>,
     cache: MemoryCache,
     sender: Arc<Sender<Event>>, // communication to messaging service
 }
 
 pub enum JobProcess<'schedule> {
     InitializePrivateLeaderboard,
+    WatchSeason(&'schedule str),
+    // (schedule, join code of the private leaderboard)
+    WatchNextEvent(&'schedule str, &'schedule str),
+    ScanGlobalHeroes,
     InitializeDailySolutionsThread(&'schedule str),
     UpdatePrivateLeaderboard(&'schedule str),
     WatchGlobalLeaderboard(&'schedule str),
+    ResumeGlobalLeaderboardWatch,
     ParseDailyChallenge(&'schedule str),
     SendDailySummary(&'schedule str),
+    SendReminders(&'schedule str),
+    SummarizeDailySolutionsThread(&'schedule str),
+    SendYearInReview(&'schedule str),
+    SendWeeklyRecap(&'schedule str),
+    SendAwardsCeremony(&'schedule str),
+    SendHeartbeat(&'schedule str),
+    // (schedule, minutes before the puzzle unlock)
+    SendCountdown(&'schedule str, u32),
+    SendDifficultyPoll(&'schedule str),
+    // schedule in the local time of the bot
+    EndQuietHours(&'schedule str),
+    FlushHighlights(&'schedule str),
 }
 
-impl Scheduler {
-    pub async fn new(cache: MemoryCache, sender: Arc<Sender<Event>>) -> BotResult<Self> {
+impl<P: EventProvider + Send + 'static> Scheduler<P> {
+    pub async fn new(
+        provider: P,
+        cache: MemoryCache,
+        sender: Arc<Sender<Event>>,
+    ) -> BotResult<Self> {
         let scheduler = JobScheduler::new().await?;
         Ok(Scheduler {
             scheduler,
+            provider: Arc::new(provider),
             cache,
             sender,
         })
@@ -42,25 +123,122 @@ impl Scheduler {
     pub async fn add_job(&self, job_process: JobProcess<'_>) -> BotResult<uuid::Uuid> {
         let job = match job_process {
             JobProcess::InitializePrivateLeaderboard => {
-                initialize_private_leaderboard_job(self.cache.clone()).await?
+                initialize_private_leaderboard_job(
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
+            }
+            JobProcess::WatchSeason(schedule) => {
+                watch_season_job(
+                    schedule,
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
+            }
+            JobProcess::WatchNextEvent(schedule, join_code) => {
+                watch_next_event_job(
+                    schedule,
+                    join_code.to_string(),
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
+            }
+            JobProcess::ScanGlobalHeroes => {
+                scan_global_heroes_job(
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
             }
             JobProcess::InitializeDailySolutionsThread(schedule) => {
-                initialize_daily_solutions_thread_job(schedule, self.sender.clone()).await?
+                initialize_daily_solutions_thread_job(
+                    schedule,
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
             }
             JobProcess::UpdatePrivateLeaderboard(schedule) => {
-                update_private_leaderboard_job(schedule, self.cache.clone(), self.sender.clone())
-                    .await?
+                update_private_leaderboard_job(
+                    schedule,
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
             }
             JobProcess::WatchGlobalLeaderboard(schedule) => {
-                watch_global_leaderboard_job(schedule, self.cache.clone(), self.sender.clone())
-                    .await?
+                watch_global_leaderboard_job(
+                    schedule,
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
+            }
+            JobProcess::ResumeGlobalLeaderboardWatch => {
+                resume_global_leaderboard_watch_job(
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
             }
             JobProcess::ParseDailyChallenge(schedule) => {
-                parse_daily_challenge_job(schedule, self.sender.clone()).await?
+                parse_daily_challenge_job(
+                    schedule,
+                    self.provider.clone(),
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
             }
             JobProcess::SendDailySummary(schedule) => {
                 send_daily_summary_job(schedule, self.cache.clone(), self.sender.clone()).await?
             }
+            JobProcess::SummarizeDailySolutionsThread(schedule) => {
+                summarize_daily_solutions_thread_job(
+                    schedule,
+                    self.cache.clone(),
+                    self.sender.clone(),
+                )
+                .await?
+            }
+            JobProcess::SendYearInReview(schedule) => {
+                send_year_in_review_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::SendWeeklyRecap(schedule) => {
+                send_weekly_recap_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::SendAwardsCeremony(schedule) => {
+                send_awards_ceremony_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::SendHeartbeat(schedule) => {
+                send_heartbeat_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::SendReminders(schedule) => {
+                send_reminders_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::SendCountdown(schedule, minutes) => {
+                send_countdown_job(schedule, minutes, self.cache.clone(), self.sender.clone())
+                    .await?
+            }
+            JobProcess::SendDifficultyPoll(schedule) => {
+                send_difficulty_poll_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::EndQuietHours(schedule) => {
+                end_quiet_hours_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
+            JobProcess::FlushHighlights(schedule) => {
+                flush_highlights_job(schedule, self.cache.clone(), self.sender.clone()).await?
+            }
         };
         Ok(self.scheduler.add(job).await?)
     }
@@ -69,8 +247,22 @@ impl Scheduler {
         Ok(self.scheduler.start().await?)
     }
 
+    /// Refresh the private leaderboard from the provider of the scheduler on each request, one
+    /// refresh at a time.
+    pub fn serve_refreshes(&self, mut requests: Receiver<RefreshRequest>) {
+        let provider = self.provider.clone();
+        let cache = self.cache.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            while let Some(done) = requests.recv().await {
+                update_private_leaderboard(&*provider, &cache, &sender).await;
+                let _ = done.send(());
+            }
+        });
+    }
+
     // pub fn cache_size(&self) -> usize {
-    //     let data = self.cache.data.lock().unwrap();
+    //     let data = self.cache.data.read().unwrap();
     //     data.leaderboard.len()
     // }
 
@@ -83,31 +275,137 @@ impl Scheduler {
 // Jobs definition
 //////////////////
 
-async fn initialize_private_leaderboard_job(cache: MemoryCache) -> BotResult<Job> {
+/// Log the error and forward it to the monitoring channel.
+async fn alert(sender: &Sender<Event>, context: &'static str, error: BotError) {
+    error!("{context} {error}");
+    if let Err(e) = sender.send(Event::MonitoringAlert(context, error)).await {
+        let error = BotError::from(e);
+        error!("{error}");
+    };
+}
+
+async fn initialize_private_leaderboard_job<P: EventProvider + Send + 'static>(
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
     let job = Job::new_one_shot_async(Duration::from_secs(0), move |_uuid, _l| {
+        let provider = provider.clone();
         let cache = cache.clone();
+        let sender = sender.clone();
         Box::pin(async move {
-            let aoc_client = AoC::new();
+            let mut run = TrackedRun::start(&cache, "initialize_private_leaderboard");
             let settings = &config::SETTINGS;
 
             let (current_year, _day) = current_aoc_year_day();
+            let is_cached = {
+                let data = cache.data.read().unwrap();
+                data.leaderboard.iter().any(|e| e.year == current_year)
+            };
+            // Off-season, the leaderboard loaded from the snapshot is enough to answer commands.
+            if is_cached && season_at(Utc::now()) == Season::OffSeason {
+                info!("Off-season: AoC is not scraped until the next event window.");
+                run.finish(&sender).await;
+                return;
+            };
+
             let mut live_years = vec![current_year];
-            if settings.all_years {
-                live_years.extend(2015..current_year)
+            let mut cached_past_years = vec![];
+            if settings.aoc.all_years {
+                // Past events already loaded from the snapshot are scraped last, the commands
+                // being answered from the snapshot in the meantime.
+                let cached_years = {
+                    let data = cache.data.read().unwrap();
+                    data.leaderboard
+                        .iter()
+                        .map(|e| e.year)
+                        .collect::<HashSet<i32>>()
+                };
+                let (cached, missing): (Vec<i32>, Vec<i32>) =
+                    (2015..current_year).partition(|y| cached_years.contains(y));
+                live_years.extend(missing);
+                cached_past_years = cached;
             };
 
-            for year in live_years {
-                match aoc_client.private_leaderboard(year).await {
-                    Ok(scraped_leaderboard) => {
-                        let mut data = cache.data.lock().unwrap();
-                        data.merge_with(scraped_leaderboard);
+            if !pipeline::run(&*provider, &cache, &sender, live_years, false)
+                .await
+                .succeeded
+            {
+                run.error(&BotError::AOC(
+                    "could not scrape all the private leaderboards",
+                ));
+            };
+
+            // Stars of the past events can still be earned (e.g. by members joining later), so
+            // the cached years are refreshed too, one at a time not to hammer the AoC servers.
+            for year in cached_past_years {
+                time::sleep(Duration::from_secs(PAST_YEARS_RESCRAPE_THROTTLE_SEC)).await;
+                if !pipeline::run(&*provider, &cache, &sender, vec![year], false)
+                    .await
+                    .succeeded
+                {
+                    run.error(&BotError::AOC(
+                        "could not scrape all the private leaderboards",
+                    ));
+                };
+            }
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+/// Record the appearances of the members on the past global leaderboards of the cached years,
+/// without announcing them.
+async fn scan_global_heroes_job<P: EventProvider + Send + 'static>(
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let delay = Duration::from_secs(GLOBAL_HEROES_SCAN_DELAY_SEC);
+    let job = Job::new_one_shot_async(delay, move |_uuid, _l| {
+        let provider = provider.clone();
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "scan_global_heroes");
+
+            let cached_years = {
+                let data = cache.data.read().unwrap();
+                data.leaderboard
+                    .iter()
+                    .map(|e| e.year)
+                    .unique()
+                    .collect::<Vec<i32>>()
+            };
+            let days = cache
+                .global_heroes
+                .read(|heroes| heroes.days_to_scan(&cached_years, current_aoc_year_day()));
+            info!("Scanning {} past global leaderboards.", days.len());
+
+            let mut n_appearances = 0;
+            for (year, day) in days {
+                match provider.global_leaderboard(year, day).await {
+                    Ok((global_leaderboard, _report)) => {
+                        let is_complete = global_leaderboard.leaderboard.is_global_complete();
+                        let private_leaderboard = cache.data.read().unwrap();
+                        let hero_entries = global_leaderboard
+                            .leaderboard
+                            .get_common_members_with(&private_leaderboard.leaderboard);
+                        n_appearances += cache
+                            .global_heroes
+                            .update(|heroes| heroes.record(year, day, &hero_entries, is_complete));
                     }
                     Err(e) => {
-                        let error = BotError::AOC(format!("Could not scrape leaderboard. {e}"));
-                        error!("{error}");
+                        // not marked as scanned, retried at the next startup
+                        error!("Could not scrape global leaderboard of day {day} {year}. {e}");
+                        run.error(&e);
                     }
                 };
+                time::sleep(Duration::from_secs(GLOBAL_HEROES_SCAN_THROTTLE_SEC)).await;
             }
+            info!("Recorded {n_appearances} new global leaderboard appearances.");
+            run.finish(&sender).await;
         })
     })?;
     Ok(job)
@@ -115,97 +413,187 @@ async fn initialize_private_leaderboard_job(cache: MemoryCache) -> BotResult<Job
 
 async fn initialize_daily_solutions_thread_job(
     schedule: &str,
+    cache: MemoryCache,
     sender: Arc<Sender<Event>>,
 ) -> BotResult<Job> {
     let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
         let sender = sender.clone();
         Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "initialize_daily_solutions_thread");
             let (_year, day) = current_aoc_year_day();
             if let Err(e) = sender
                 .send(Event::DailySolutionsThreadToInitialize(day))
                 .await
             {
-                let error =
-                    BotError::ChannelSend(format!("Could not send message to MPSC channel. {e}"));
+                let error = BotError::from(e);
                 error!("{error}");
+                run.error(&error);
             };
+            run.finish(&sender).await;
         })
     })?;
     Ok(job)
 }
 
-async fn update_private_leaderboard_job(
+/// Announce the season changes in the monitoring channel. When the event window opens, the private
+/// leaderboard of the upcoming event is loaded without announcing anything, the scheduled jobs
+/// resuming by themselves.
+async fn watch_season_job<P: EventProvider + Send + 'static>(
     schedule: &str,
+    provider: Arc<P>,
     cache: MemoryCache,
     sender: Arc<Sender<Event>>,
 ) -> BotResult<Job> {
-    let job = Job::new_async(schedule, move |uuid, mut l| {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let provider = provider.clone();
         let cache = cache.clone();
         let sender = sender.clone();
         Box::pin(async move {
-            let aoc_client = AoC::new();
-            let settings = &config::SETTINGS;
-
-            let (year, _day) = current_aoc_year_day();
-            match aoc_client.private_leaderboard(year).await {
-                Ok(scraped_leaderboard) => {
-                    // Scoped to force 'current_leaderboard' to drop before 'await' so future can be Send.
-                    let (highlights, new_members) = {
-                        let mut current_leaderboard = cache.data.lock().unwrap();
-
-                        // Check for new parts completions
-                        let highlights = compute_highlights(
-                            &current_leaderboard.leaderboard,
-                            &scraped_leaderboard.leaderboard,
-                        );
+            let now = Utc::now();
+            let season = season_at(now);
+            if season == season_at(now - chrono::Duration::days(1)) {
+                return;
+            };
+            let mut run = TrackedRun::start(&cache, "watch_season");
+            info!("Season changed: {season:?}.");
+            let year = match season {
+                Season::Event => {
+                    let (year, _day) = current_aoc_year_day();
+                    if !pipeline::run(&*provider, &cache, &sender, vec![year], false)
+                        .await
+                        .succeeded
+                    {
+                        run.error(&BotError::AOC("could not scrape the private leaderboard"));
+                    };
+                    year
+                }
+                // the next event is the one of the current year
+                Season::OffSeason => now.year(),
+            };
+            if let Err(e) = sender.send(Event::SeasonChanged(season, year)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
 
-                        // Check for new members
-                        let new_members = get_new_members(
-                            &current_leaderboard.leaderboard,
-                            &scraped_leaderboard.leaderboard,
-                        );
+async fn watch_next_event_job<P: EventProvider + Send + 'static>(
+    schedule: &str,
+    join_code: String,
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let provider = provider.clone();
+        let cache = cache.clone();
+        let sender = sender.clone();
+        let join_code = join_code.clone();
+        Box::pin(async move {
+            let now = Utc::now();
+            // the next event is the one of the current year, until its first puzzle unlocks
+            let year = now.year();
+            let is_started = Entry::puzzle_unlock(year, 1).is_ok_and(|unlock| now >= unlock);
+            if is_started
+                || cache
+                    .announced_event
+                    .read(|announced| *announced == Some(year))
+            {
+                return;
+            };
+            let mut run = TrackedRun::start(&cache, "watch_next_event");
+            if let Err(e) = announce_next_event(&*provider, &cache, &sender, year, &join_code).await
+            {
+                run.error(&e);
+                alert(&sender, "Could not check the page of the next event.", e).await;
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
 
-                        // Update leadearboard in cache.
-                        current_leaderboard.merge_with(scraped_leaderboard);
+/// Announce the event of the year with the join code of the private leaderboard, once its page is
+/// live. Whether the event was announced is persisted, so it is only announced once.
+pub async fn announce_next_event(
+    provider: &impl EventProvider,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+    year: i32,
+    join_code: &str,
+) -> BotResult<bool> {
+    if !provider.event_is_live(year).await? {
+        info!("The {year} event is not announced yet.");
+        return Ok(false);
+    };
+    info!("The {year} event is announced.");
+    let url = provider.join_private_leaderboard_url(year);
+    sender
+        .send(Event::NextEventAnnounced(year, join_code.to_string(), url))
+        .await?;
+    cache
+        .announced_event
+        .update(|announced| *announced = Some(year));
+    Ok(true)
+}
 
-                        (highlights, new_members)
-                    };
+async fn send_heartbeat_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            // Posted even off-season: a silent monitoring channel means the bot is down.
+            let mut run = TrackedRun::start(&cache, "send_heartbeat");
+            let timestamp = cache.data.read().unwrap().timestamp;
+            let n_members = cache.membership.read(|history| history.members.len());
+            let last_scrape = cache.pipeline_stats.lock().unwrap().last_scrape;
+            let status = {
+                let jobs = cache.jobs_history.lock().unwrap();
+                heartbeat::heartbeat(timestamp, n_members, last_scrape, &jobs, Utc::now())
+            };
+            if let Err(e) = sender.send(Event::Heartbeat(status)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
 
-                    // Conditionnally trigger internal events, base on leaderboard processing.
-                    if !settings.summary_events_only && !new_members.is_empty() {
-                        if let Err(e) = sender
-                            .send(Event::PrivateLeaderboardNewMembers(new_members))
-                            .await
-                        {
-                            let error = BotError::ChannelSend(format!(
-                                "Could not send message to MPSC channel. {e}"
-                            ));
-                            error!("{error}");
-                        };
-                    }
-                    if !settings.summary_events_only && !highlights.is_empty() {
-                        if let Err(e) = sender
-                            .send(Event::PrivateLeaderboardNewEntries(highlights))
-                            .await
-                        {
-                            let error = BotError::ChannelSend(format!(
-                                "Could not send message to MPSC channel. {e}"
-                            ));
-                            error!("{error}");
-                        };
-                    }
-                    if let Err(e) = sender.send(Event::PrivateLeaderboardUpdated).await {
-                        let error = BotError::ChannelSend(format!(
-                            "Could not send message to MPSC channel. {e}"
-                        ));
-                        error!("{error}");
-                    };
-                }
-                Err(e) => {
-                    let error = BotError::AOC(format!("Could not scrape leaderboard. {e}"));
-                    error!("{error}");
-                }
+async fn update_private_leaderboard_job<P: EventProvider + Send + 'static>(
+    schedule: &str,
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |uuid, mut l| {
+        let provider = provider.clone();
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            // Scraping idles off-season, and resumes by itself once the event window opens.
+            if season_at(Utc::now()) == Season::OffSeason {
+                return;
             };
+            let mut run = TrackedRun::start(&cache, "update_private_leaderboard");
+            if !update_private_leaderboard(&*provider, &cache, &sender)
+                .await
+                .succeeded
+            {
+                run.error(&BotError::AOC("could not scrape the private leaderboard"));
+            };
+            run.finish(&sender).await;
 
             // Query the next execution time for this job
             let next_tick = l.next_tick_for_job(uuid).await;
@@ -218,157 +606,504 @@ async fn update_private_leaderboard_job(
     Ok(job)
 }
 
-async fn watch_global_leaderboard_job(
+/// Scrape the private leaderboard of the current year, merge it into the cache and trigger the
+/// related events. Used by the scheduled update job, but can also be triggered on demand.
+pub async fn update_private_leaderboard<P: EventProvider + 'static>(
+    provider: &P,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+) -> pipeline::LastScrape {
+    let (year, _day) = current_aoc_year_day();
+    let scrape = pipeline::run(provider, cache, sender, vec![year], true).await;
+    if scrape.succeeded {
+        send_live_scoreboard(cache, sender, year).await;
+    };
+    scrape
+}
+
+/// Send the board of the year, to refresh the message pinned in the scoreboard channels (if any).
+async fn send_live_scoreboard(cache: &MemoryCache, sender: &Sender<Event>, year: i32) {
+    let settings = &config::SETTINGS;
+    if settings
+        .slack
+        .scoreboard_channels
+        .iter()
+        .flatten()
+        .next()
+        .is_none()
+    {
+        return;
+    };
+    let board = {
+        let data = cache.data.read().unwrap();
+        Command::board_page(&data, &cache.precomputed, year, Scoring::LOCAL, false, 0)
+    };
+    if let Err(e) = sender.send(Event::LiveScoreboard(board)).await {
+        let error = BotError::from(e);
+        error!("{error}");
+    };
+}
+
+/// Compute the most requested outputs from the freshly merged leaderboard in a background task,
+/// so the next commands and the daily summary do not have to.
+fn precompute_outputs(cache: &MemoryCache) {
+    let cache = cache.clone();
+    tokio::task::spawn_blocking(move || {
+        let (year, day) = current_aoc_year_day();
+        let data = cache.data.read().unwrap();
+        cache.precomputed.warm(&data, year, day);
+    });
+}
+
+/// Filter out overtakes between members that were recently announced (in any direction), to avoid
+/// noise when members keep exchanging positions, and cap the number of announced overtakes.
+fn throttle_overtakes(
+    announced: &Mutex<HashMap<(MemberId, MemberId), DateTime<Utc>>>,
+    overtakes: Vec<(Identifier, Identifier, u8)>,
+    now: DateTime<Utc>,
+) -> Vec<(Identifier, Identifier, u8)> {
+    let cooldown = chrono::Duration::minutes(OVERTAKES_COOLDOWN_MIN);
+    let mut announced = announced.lock().unwrap();
+
+    let overtakes = overtakes
+        .into_iter()
+        .filter(|(overtaker, overtaken, _position)| {
+            let pair = (overtaker.numeric, overtaken.numeric);
+            let reversed_pair = (overtaken.numeric, overtaker.numeric);
+            ![pair, reversed_pair].iter().any(|p| {
+                announced
+                    .get(p)
+                    .is_some_and(|last_time| now - *last_time < cooldown)
+            })
+        })
+        .take(OVERTAKES_MAX_PER_UPDATE)
+        .collect::<Vec<_>>();
+
+    overtakes
+        .iter()
+        .for_each(|(overtaker, overtaken, _position)| {
+            announced.insert((overtaker.numeric, overtaken.numeric), now);
+        });
+    overtakes
+}
+
+async fn watch_global_leaderboard_job<P: EventProvider + Send + 'static>(
     schedule: &str,
+    provider: Arc<P>,
     cache: MemoryCache,
     sender: Arc<Sender<Event>>,
 ) -> BotResult<Job> {
     let job = Job::new_async(schedule, move |_uuid, _l| {
+        let provider = provider.clone();
         let cache = cache.clone();
         let sender = sender.clone();
 
         Box::pin(async move {
-            let settings = &config::SETTINGS;
-            let aoc_client = AoC::new();
+            let mut run = TrackedRun::start(&cache, "watch_global_leaderboard");
+            let (year, day) = current_aoc_year_day();
+            let watch = cache
+                .global_watch
+                .read(|watch| GlobalWatch::resume(watch.as_ref(), year, day));
+            watch_global_leaderboard(&*provider, &cache, &sender, &mut run, watch).await;
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
 
-            let mut interval = time::interval(Duration::from_secs(
-                settings.global_leaderboard_polling_interval_sec,
-            ));
+async fn resume_global_leaderboard_watch_job<P: EventProvider + Send + 'static>(
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_one_shot_async(Duration::from_secs(0), move |_uuid, _l| {
+        let provider = provider.clone();
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let (year, day) = current_aoc_year_day();
+            // Only the watch of the day interrupted by a restart is resumed.
+            let watch = cache.global_watch.read(|watch| {
+                watch
+                    .clone()
+                    .filter(|w| w.year == year && w.day == day && !w.is_complete)
+            });
+            if let Some(watch) = watch {
+                info!("Resuming the watch of the Global Leaderboard for day {day}.");
+                let mut run = TrackedRun::start(&cache, "watch_global_leaderboard");
+                watch_global_leaderboard(&*provider, &cache, &sender, &mut run, watch).await;
+                run.finish(&sender).await;
+            };
+        })
+    })?;
+    Ok(job)
+}
 
-            // Note: the first interval tick ticks immediately, so we trigger it
-            // to ensure the counter reflects interval time multiples.
-            interval.tick().await;
+/// Poll the global leaderboard of the day until it is complete, announcing the heroes found on
+/// it. The progress of the watch is persisted after each poll.
+async fn watch_global_leaderboard(
+    provider: &impl EventProvider,
+    cache: &MemoryCache,
+    sender: &Arc<Sender<Event>>,
+    run: &mut TrackedRun,
+    mut watch: GlobalWatch,
+) {
+    let settings = &config::SETTINGS;
+    let (year, day) = (watch.year, watch.day);
+    let max_backoff = Duration::from_secs(GLOBAL_WATCH_MAX_BACKOFF_SEC);
 
-            let (year, day) = current_aoc_year_day();
+    let mut interval = time::interval(Duration::from_secs(
+        settings.scheduling.global_leaderboard_polling_interval_sec,
+    ));
 
-            let mut known_hero_hashes: Vec<String> = vec![];
-
-            info!("Starting polling Global Leaderboard for day {day}.");
-            let mut is_global_leaderboard_complete = false;
-            let mut counter = 0;
-
-            while !is_global_leaderboard_complete {
-                match aoc_client.global_leaderboard(year, day).await {
-                    Ok(global_leaderboard) => {
-                        is_global_leaderboard_complete =
-                            global_leaderboard.leaderboard.is_global_complete();
-
-                        // Scoped to not held data across .await
-                        let hero_entries = {
-                            // check if private members made it to the global leaderboard
-                            let private_leaderboard = cache.data.lock().unwrap();
-                            global_leaderboard
-                                .leaderboard
-                                .get_common_members_with(&private_leaderboard.leaderboard)
-                        };
+    // Note: the first interval tick ticks immediately, so we trigger it
+    // to ensure the counter reflects interval time multiples.
+    interval.tick().await;
 
-                        for entry in hero_entries {
-                            let entry_hash = entry.to_key();
-                            // If not already known, send shoutout to hero
-                            if !known_hero_hashes.contains(&entry_hash) {
-                                // let (name, part, rank) = &hero_hit;
-                                let (name, part, rank) = (
-                                    entry.id.name.clone(),
-                                    entry.part,
-                                    entry.rank.unwrap_or_default(),
-                                );
-                                if let Err(e) = sender
-                                    .send(Event::GlobalLeaderboardHeroFound((name, part, rank)))
-                                    .await
-                                {
-                                    let error = BotError::ChannelSend(format!(
-                                        "Could not send message to MPSC channel. {e}"
-                                    ));
-                                    error!("{error}");
-                                } else {
-                                    // Announcement successful, let's register the hero.
-                                    known_hero_hashes.push(entry_hash);
-                                };
-                            }
-                        }
+    match watch.counter {
+        0 => info!("Starting polling Global Leaderboard for day {day}."),
+        n => info!("Resuming polling Global Leaderboard for day {day} after {n} polls."),
+    };
 
-                        if is_global_leaderboard_complete {
-                            info!("Global Leaderboard for day {day} is now complete!");
-                            match global_leaderboard
-                                .leaderboard
-                                .statistics_for_year_day(year, day)
-                            {
-                                Ok(stats) => {
-                                    if let Err(e) = sender
-                                        .send(Event::GlobalLeaderboardComplete((day, stats)))
-                                        .await
-                                    {
-                                        let error = BotError::ChannelSend(format!(
-                                            "Could not send message to MPSC channel. {e}"
-                                        ));
-                                        error!("{error}");
-                                    };
-                                }
-                                Err(e) => {
-                                    let error = BotError::Compute(format!(
-                                        "Could not compute global statistics. {e}"
-                                    ));
-                                    error!("{error}");
-                                }
-                            }
+    while !watch.is_complete {
+        watch.poll(Utc::now());
+        match provider.global_leaderboard(year, day).await {
+            Ok((global_leaderboard, report)) => {
+                watch.consecutive_failures = 0;
+                // Entries dropped by the parser would prevent the completion of the
+                // global leaderboard from ever being detected, most likely because AoC
+                // changed its DOM. Reported once per day.
+                if report.is_incomplete() && !watch.is_parse_alert_sent {
+                    watch.is_parse_alert_sent = true;
+                    let error = BotError::IncompleteParse(report.to_string());
+                    run.error(&error);
+                    alert(
+                        sender,
+                        "Global leaderboard entries dropped by the parser.",
+                        error,
+                    )
+                    .await;
+                }
+                cache.pipeline_stats.lock().unwrap().global_parse = Some(report);
+                watch.is_complete = global_leaderboard.leaderboard.is_global_complete();
+
+                // Scoped to not held data across .await
+                let hero_entries = {
+                    // check if private members made it to the global leaderboard
+                    let private_leaderboard = cache.data.read().unwrap();
+                    let hero_entries = global_leaderboard
+                        .leaderboard
+                        .get_common_members_with(&private_leaderboard.leaderboard);
+                    cache.global_heroes.update(|heroes| {
+                        heroes.record(year, day, &hero_entries, watch.is_complete)
+                    });
+                    hero_entries
+                };
+
+                for entry in hero_entries {
+                    let entry_hash = entry.to_key();
+                    // If not already announced (possibly before a restart), send shoutout
+                    // to hero
+                    let is_announced = cache
+                        .global_heroes
+                        .read(|heroes| heroes.is_announced(year, day, &entry_hash));
+                    if !is_announced {
+                        // let (name, part, rank) = &hero_hit;
+                        let (name, part, rank) = (
+                            names::mention(&entry.id),
+                            entry.part,
+                            entry.rank.unwrap_or_default(),
+                        );
+                        if let Err(e) = sender
+                            .send(Event::GlobalLeaderboardHeroFound((name, part, rank)))
+                            .await
+                        {
+                            let error = BotError::from(e);
+                            error!("{error}");
                         } else {
-                            info!("Global Leaderboard for day {day} not complete yet.");
-                            if [5, 8, 11, 14].contains(&counter) {
-                                let num_sec = interval.period().as_secs() * counter;
-                                if let Err(e) = sender
-                                    .send(Event::GlobalLeaderboardUpdateMessage(counter, num_sec))
-                                    .await
-                                {
-                                    let error = BotError::ChannelSend(format!(
-                                        "Could not send message to MPSC channel. {e}"
-                                    ));
-                                    error!("{error}");
-                                };
+                            // Announcement successful, let's register the hero.
+                            cache
+                                .global_heroes
+                                .update(|heroes| heroes.mark_announced(year, day, entry_hash));
+                        };
+                    }
+                }
+
+                if watch.is_complete {
+                    info!("Global Leaderboard for day {day} is now complete!");
+                    match global_leaderboard
+                        .leaderboard
+                        .statistics_for_year_day(year, day)
+                    {
+                        Ok(stats) => {
+                            let difficulty = record_global_completion(cache, year, day, &stats);
+                            let p2_slow_sec = stats.p2_slow.map(|d| d.num_seconds());
+                            if let Err(e) = sender
+                                .send(Event::GlobalLeaderboardComplete((day, stats, difficulty)))
+                                .await
+                            {
+                                let error = BotError::from(e);
+                                error!("{error}");
+                            };
+                            if let Some(p2_slow_sec) = p2_slow_sec {
+                                send_guess_results(cache, sender, year, day, p2_slow_sec).await;
                             }
                         }
+                        Err(e) => {
+                            error!("Could not compute global statistics. {e}");
+                            run.error(&e);
+                        }
                     }
-                    Err(e) => {
-                        let error =
-                            BotError::AOC(format!("Could not scrape global leaderboard. {e}"));
-                        error!("{error}");
+                } else {
+                    info!("Global Leaderboard for day {day} not complete yet.");
+                    let counter = watch.counter;
+                    if [5, 8, 11, 14].contains(&counter) {
+                        let num_sec = watch.elapsed().as_secs();
+                        if let Err(e) = sender
+                            .send(Event::GlobalLeaderboardUpdateMessage(counter, num_sec))
+                            .await
+                        {
+                            let error = BotError::from(e);
+                            error!("{error}");
+                        };
                     }
-                };
+                }
+            }
+            Err(e) => {
+                run.error(&e);
+                watch.consecutive_failures += 1;
+                // Only the first failure of a streak is forwarded, not to flood the
+                // monitoring channel while AoC is down.
+                match watch.consecutive_failures {
+                    1 => alert(sender, "Could not scrape global leaderboard.", e).await,
+                    _ => error!("Could not scrape global leaderboard. {e}"),
+                }
+            }
+        };
 
-                counter += 1;
+        watch.counter += 1;
+        cache
+            .global_watch
+            .update(|stored| *stored = Some(watch.clone()));
+        if watch.is_complete {
+            break;
+        };
+        match watch.consecutive_failures {
+            0 => {
                 interval.tick().await;
             }
+            _ => {
+                let delay = watch.delay(interval.period(), max_backoff);
+                info!(
+                    "Next poll of the Global Leaderboard in {}s.",
+                    delay.as_secs()
+                );
+                time::sleep(delay).await;
+                // the ticks missed while backing off are not burst
+                interval.reset();
+            }
+        };
+    }
+}
+
+/// Record the global leaderboard completion time of the day, and compute how hard the day was
+/// compared to the previously recorded days.
+fn record_global_completion(
+    cache: &MemoryCache,
+    year: i32,
+    day: u8,
+    stats: &LeaderboardStatistics,
+) -> Option<DayDifficulty> {
+    let p2_slow_sec = stats.p2_slow?.num_seconds();
+    cache.global_completions.update(|completions| {
+        completions.retain(|c| !(c.year == year && c.day == day));
+        let difficulty = day_difficulty(p2_slow_sec, completions);
+        completions.push(GlobalCompletion {
+            year,
+            day,
+            p2_slow_sec,
+        });
+        difficulty
+    })
+}
+
+/// Announce the closest guesses of the global leaderboard cutoff, if anybody played.
+async fn send_guess_results(
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+    year: i32,
+    day: u8,
+    p2_slow_sec: i64,
+) {
+    let (winners, n_guesses) = cache.guesses.read(|guesses| {
+        (
+            closest_guesses(guesses, year, day, p2_slow_sec),
+            guesses
+                .iter()
+                .filter(|g| g.year == year && g.day == day)
+                .count(),
+        )
+    });
+    if winners.is_empty() {
+        return;
+    };
+
+    if let Err(e) = sender
+        .send(Event::GuessResults(day, p2_slow_sec, winners, n_guesses))
+        .await
+    {
+        let error = BotError::from(e);
+        error!("{error}");
+    };
+}
+
+async fn summarize_daily_solutions_thread_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "summarize_daily_solutions_thread");
+            let (year, day) = current_aoc_year_day();
+            if let Err(e) = sender
+                .send(Event::DailySolutionsThreadToSummarize(year, day))
+                .await
+            {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
         })
     })?;
     Ok(job)
 }
 
-async fn parse_daily_challenge_job(schedule: &str, sender: Arc<Sender<Event>>) -> BotResult<Job> {
+async fn send_countdown_job(
+    schedule: &str,
+    minutes: u32,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
     let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
         let sender = sender.clone();
         Box::pin(async move {
-            let aoc_client = AoC::new();
+            let mut run = TrackedRun::start(&cache, "send_countdown");
+            // The job runs the day of the unlock, before the release hour.
+            let day = (Utc::now() + chrono::Duration::minutes(minutes.into())).day() as u8;
+            if let Err(e) = sender.send(Event::Countdown(day, minutes)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
 
+async fn send_difficulty_poll_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "send_difficulty_poll");
             let (year, day) = current_aoc_year_day();
-            let challenge_url = aoc_client.daily_challenge_url(year, day);
+            if let Err(e) = sender.send(Event::DifficultyPoll(year, day)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+async fn end_quiet_hours_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async_tz(schedule, Local, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "end_quiet_hours");
+            if let Err(e) = sender.send(Event::QuietHoursEnded).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+async fn flush_highlights_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "flush_highlights");
+            if let Err(e) = sender.send(Event::HighlightsDebounceTick).await {
+                let error = BotError::from(e);
+                error!("{error}");
+                run.error(&error);
+            };
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+async fn parse_daily_challenge_job<P: EventProvider + Send + 'static>(
+    schedule: &str,
+    provider: Arc<P>,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let provider = provider.clone();
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let mut run = TrackedRun::start(&cache, "parse_daily_challenge");
+
+            let (year, day) = current_aoc_year_day();
+            let challenge_url = provider.daily_challenge_url(year, day);
 
             info!("Retrieving challenge title for day {day}.");
-            match aoc_client.daily_challenge(year, day).await {
+            match provider.daily_challenge(year, day).await {
                 Ok(title) => {
                     if let Err(e) = sender
                         .send(Event::DailyChallengeIsUp(day, title.clone(), challenge_url))
                         .await
                     {
-                        let error = BotError::ChannelSend(format!(
-                            "Could not send message to MPSC channel. {e}"
-                        ));
+                        let error = BotError::from(e);
                         error!("{error}");
+                        run.error(&error);
                     };
                 }
                 Err(e) => {
-                    let error = BotError::AOC(format!("Could not scrape global leaderboard. {e}"));
-                    error!("{error}");
+                    run.error(&e);
+                    alert(&sender, "Could not retrieve daily challenge.", e).await;
                 }
             };
+            run.finish(&sender).await;
         })
     })?;
     Ok(job)
@@ -383,24 +1118,9 @@ async fn send_daily_summary_job(
         let cache = cache.clone();
         let sender = sender.clone();
         Box::pin(async move {
-            let (year, day) = current_aoc_year_day();
-            let (p1, p2, delta) = {
-                let leaderboard = cache.data.lock().unwrap();
-                let standings = Standing::new(&leaderboard.leaderboard);
-                let p1 = standings.by_time(&Ranking::PART1, year, day);
-                let p2 = standings.by_time(&Ranking::PART2, year, day);
-                let delta = standings.by_time(&Ranking::DELTA, year, day);
-                (p1, p2, delta)
-            };
-
-            if let Err(e) = sender
-                .send(Event::DailySummary(year, day, p1, p2, delta))
-                .await
-            {
-                let error =
-                    BotError::ChannelSend(format!("Could not send message to MPSC channel. {e}"));
-                error!("{error}");
-            };
+            let run = TrackedRun::start(&cache, "send_daily_summary");
+            send_daily_summary(&cache, &sender).await;
+            run.finish(&sender).await;
 
             // Query the next execution time for this job
             let next_tick = l.next_tick_for_job(uuid).await;
@@ -412,3 +1132,269 @@ async fn send_daily_summary_job(
     })?;
     Ok(job)
 }
+
+/// Compute the rankings of the current day from the cache and send the daily summary, followed by
+/// the personal summaries if enabled.
+pub async fn send_daily_summary(cache: &MemoryCache, sender: &Sender<Event>) {
+    let (year, day) = current_aoc_year_day();
+    let (p1, p2, delta, splits) = {
+        let leaderboard = cache.data.read().unwrap();
+        let precomputed = &cache.precomputed;
+        let p1 = precomputed.ranking(&leaderboard, &Ranking::PART1, year, day);
+        let p2 = precomputed.ranking(&leaderboard, &Ranking::PART2, year, day);
+        let delta = precomputed.ranking(&leaderboard, &Ranking::DELTA, year, day);
+        let splits = splits::negative_splits(&leaderboard.leaderboard, year)
+            .into_iter()
+            .filter(|split| split.day == day)
+            .collect::<Vec<NegativeSplit>>();
+        (p1, p2, delta, splits)
+    };
+
+    if let Err(e) = sender
+        // The community difficulty is tallied from the poll of the day by the messaging service.
+        .send(Event::DailySummary(year, day, p1, p2, delta, splits, None))
+        .await
+    {
+        let error = BotError::from(e);
+        error!("{error}");
+    };
+
+    if config::SETTINGS.features.personal_summary {
+        send_personal_summaries(cache, sender, year, day).await;
+    }
+}
+
+/// Send their own results of the day to the members linked to a Slack user who opted in, if they
+/// collected any star that day.
+async fn send_personal_summaries(cache: &MemoryCache, sender: &Sender<Event>, year: i32, day: u8) {
+    let opt_ins = cache
+        .personal_summary_opt_ins
+        .read(|opt_ins| opt_ins.clone());
+    let summaries = {
+        let leaderboard = cache.data.read().unwrap();
+        names::slack_users()
+            .into_iter()
+            .filter(|(_member, user)| opt_ins.contains(user))
+            .filter_map(|(member, user)| {
+                day_review(&leaderboard.leaderboard, member, year, day).map(|review| (user, review))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    info!("Sending {} personal summary(ies).", summaries.len());
+    for (user, review) in summaries {
+        if let Err(e) = sender.send(Event::PersonalSummary(user, review)).await {
+            let error = BotError::from(e);
+            error!("{error}");
+        };
+    }
+}
+
+async fn send_reminders_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let run = TrackedRun::start(&cache, "send_reminders");
+            send_reminders(&cache, &sender).await;
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+/// Send a direct message to each user who asked for reminders, listing the days of the event
+/// their member still has stars to collect.
+pub async fn send_reminders(cache: &MemoryCache, sender: &Sender<Event>) {
+    let (year, day) = current_aoc_year_day();
+    let reminders = {
+        let leaderboard = cache.data.read().unwrap();
+        cache.reminders.read(|reminders| {
+            reminders
+                .iter()
+                .map(|(user, member)| {
+                    let missing = leaderboard
+                        .leaderboard
+                        .missing_stars_for_member(*member, year, day);
+                    (user.clone(), missing)
+                })
+                .filter(|(_user, missing)| !missing.is_empty())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    info!("Sending {} reminder(s).", reminders.len());
+    for (user, missing) in reminders {
+        if let Err(e) = sender
+            .send(Event::PersonalReminder(user, year, missing))
+            .await
+        {
+            let error = BotError::from(e);
+            error!("{error}");
+        };
+    }
+}
+
+async fn send_weekly_recap_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let run = TrackedRun::start(&cache, "send_weekly_recap");
+            send_weekly_recap(&cache, &sender).await;
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+/// Send the recap of the stars collected over the last 7 days, if any.
+pub async fn send_weekly_recap(cache: &MemoryCache, sender: &Sender<Event>) {
+    let (year, _day) = current_aoc_year_day();
+    let end = Utc::now();
+    let recap = {
+        let leaderboard = cache.data.read().unwrap();
+        weekly_recap(
+            &leaderboard.leaderboard,
+            year,
+            end - chrono::Duration::days(7),
+            end,
+        )
+    };
+
+    match recap {
+        Some(recap) => {
+            if let Err(e) = sender.send(Event::WeeklyRecap(recap)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+            };
+        }
+        None => info!("No star collected this week, no weekly recap to send."),
+    }
+}
+
+async fn send_awards_ceremony_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let run = TrackedRun::start(&cache, "send_awards_ceremony");
+            send_awards_ceremony(&cache, &sender).await;
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+/// Send the awards of the event that just ended, if any star was collected.
+pub async fn send_awards_ceremony(cache: &MemoryCache, sender: &Sender<Event>) {
+    let (year, _day) = current_aoc_year_day();
+    let awards = {
+        let leaderboard = cache.data.read().unwrap();
+        season_awards(&leaderboard.leaderboard, year)
+    };
+
+    match awards {
+        Some(awards) => {
+            if let Err(e) = sender.send(Event::SeasonAwards(awards)).await {
+                let error = BotError::from(e);
+                error!("{error}");
+            };
+        }
+        None => info!("No star collected during the {year} event, no awards ceremony."),
+    }
+}
+
+async fn send_year_in_review_job(
+    schedule: &str,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+) -> BotResult<Job> {
+    let job = Job::new_async(schedule, move |_uuid, _l| {
+        let cache = cache.clone();
+        let sender = sender.clone();
+        Box::pin(async move {
+            let run = TrackedRun::start(&cache, "send_year_in_review");
+            send_year_in_review(&cache, &sender).await;
+            run.finish(&sender).await;
+        })
+    })?;
+    Ok(job)
+}
+
+/// Send their personal year in review to the members linked to a Slack user, unless they
+/// opted out.
+pub async fn send_year_in_review(cache: &MemoryCache, sender: &Sender<Event>) {
+    let (year, _day) = current_aoc_year_day();
+    let opt_outs = cache.review_opt_outs.read(|opt_outs| opt_outs.clone());
+    let reviews = {
+        let leaderboard = cache.data.read().unwrap();
+        names::slack_users()
+            .into_iter()
+            .filter(|(_member, user)| !opt_outs.contains(user))
+            .filter_map(|(member, user)| {
+                year_review(&leaderboard.leaderboard, member, year).map(|review| (user, review))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    info!("Sending {} year in review(s).", reviews.len());
+    for (user, review) in reviews {
+        if let Err(e) = sender.send(Event::YearInReview(user, review)).await {
+            let error = BotError::from(e);
+            error!("{error}");
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overtakes_are_throttled() {
+        let member = |id: u64| Identifier {
+            name: format!("Elf {id}"),
+            numeric: MemberId(id),
+        };
+        let overtake = |overtaker: u64, overtaken: u64| (member(overtaker), member(overtaken), 1);
+        let announced = Mutex::new(HashMap::new());
+        let now = Utc::now();
+
+        // capped per update
+        let overtakes = (1..=5).map(|id| overtake(id, id + 10)).collect::<Vec<_>>();
+        assert_eq!(
+            throttle_overtakes(&announced, overtakes.clone(), now).len(),
+            OVERTAKES_MAX_PER_UPDATE
+        );
+
+        // the same pairs, in any direction, are not announced again during the cooldown
+        let later = now + chrono::Duration::minutes(OVERTAKES_COOLDOWN_MIN - 1);
+        assert_eq!(
+            throttle_overtakes(&announced, vec![overtake(1, 11), overtake(12, 2)], later),
+            vec![]
+        );
+        assert_eq!(
+            throttle_overtakes(&announced, overtakes.clone(), later),
+            vec![overtake(4, 14), overtake(5, 15)]
+        );
+
+        let after_cooldown = now + chrono::Duration::minutes(OVERTAKES_COOLDOWN_MIN);
+        assert_eq!(
+            throttle_overtakes(&announced, vec![overtake(11, 1)], after_cooldown),
+            vec![overtake(11, 1)]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use super::{history::JobsHistory, pipeline::LastScrape};
+use chrono::{DateTime, Utc};
+
+/// Status of the bot, posted periodically in the monitoring channel so that a missing heartbeat
+/// reveals a bot down or wedged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    // age of the cached leaderboard
+    pub cache_age_sec: i64,
+    pub n_members: usize,
+    pub last_scrape: Option<LastScrape>,
+    pub n_jobs: usize,
+    // jobs whose last run failed
+    pub failing_jobs: Vec<&'static str>,
+}
+
+pub fn heartbeat(
+    timestamp: DateTime<Utc>,
+    n_members: usize,
+    last_scrape: Option<LastScrape>,
+    jobs: &JobsHistory,
+    now: DateTime<Utc>,
+) -> Heartbeat {
+    Heartbeat {
+        cache_age_sec: (now - timestamp).num_seconds(),
+        n_members,
+        last_scrape,
+        n_jobs: jobs.len(),
+        failing_jobs: jobs
+            .iter()
+            .filter(|(_job, history)| history.last_run.is_some_and(|run| !run.succeeded()))
+            .map(|(job, _history)| *job)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::history::{JobHistory, JobRun};
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn jobs_whose_last_run_failed_are_reported() {
+        let now = Utc.with_ymd_and_hms(2023, 12, 10, 12, 0, 0).unwrap();
+        let run = |errors: u64| JobHistory {
+            runs: 3,
+            last_run: Some(JobRun {
+                at: now - Duration::hours(1),
+                duration_ms: 1500,
+                errors,
+            }),
+            ..JobHistory::default()
+        };
+        let jobs = JobsHistory::from([
+            ("send_daily_summary", run(0)),
+            ("update_private_leaderboard", run(2)),
+            // running for the first time
+            ("watch_global_leaderboard", JobHistory::default()),
+        ]);
+
+        let status = heartbeat(now - Duration::minutes(4), 42, None, &jobs, now);
+        assert_eq!(status.cache_age_sec, 240);
+        assert_eq!(status.n_members, 42);
+        assert_eq!(status.n_jobs, 3);
+        assert_eq!(status.failing_jobs, vec!["update_private_leaderboard"]);
+    }
+}
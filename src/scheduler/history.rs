@@ -0,0 +1,144 @@
+use super::alert;
+use crate::{core::events::Event, error::BotError, storage::MemoryCache};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tokio::sync::mpsc::Sender;
+
+/// Execution history of the scheduler jobs, by job name.
+pub type JobsHistory = BTreeMap<&'static str, JobHistory>;
+
+/// Outcome of a run of a scheduler job.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct JobRun {
+    pub at: DateTime<Utc>,
+    pub duration_ms: u64,
+    // errors reported during the run, the run failed if any
+    pub errors: u64,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct JobHistory {
+    pub runs: u64,
+    pub failed_runs: u64,
+    // start of the run in progress (the global leaderboard watch runs until it is complete)
+    pub running_since: Option<DateTime<Utc>>,
+    pub last_run: Option<JobRun>,
+    // (time, error) of the last error reported by the job
+    pub last_error: Option<(DateTime<Utc>, String)>,
+}
+
+impl JobRun {
+    pub fn succeeded(&self) -> bool {
+        self.errors == 0
+    }
+}
+
+impl JobHistory {
+    fn start(&mut self, at: DateTime<Utc>) {
+        self.running_since = Some(at);
+    }
+
+    fn error(&mut self, at: DateTime<Utc>, error: &BotError) {
+        self.last_error = Some((at, error.to_string()));
+    }
+
+    /// Record the end of the run started at `at`. Returns whether the run is the first failed
+    /// one of a streak.
+    fn finish(&mut self, at: DateTime<Utc>, now: DateTime<Utc>, errors: u64) -> bool {
+        let was_failing = self.last_run.is_some_and(|run| !run.succeeded());
+        let run = JobRun {
+            at,
+            duration_ms: (now - at).num_milliseconds().max(0) as u64,
+            errors,
+        };
+        self.running_since = None;
+        self.runs += 1;
+        if !run.succeeded() {
+            self.failed_runs += 1;
+        };
+        self.last_run = Some(run);
+        !run.succeeded() && !was_failing
+    }
+}
+
+/// Run of a job in progress, recorded in the jobs history of the cache.
+pub struct TrackedRun {
+    cache: MemoryCache,
+    job: &'static str,
+    at: DateTime<Utc>,
+    errors: u64,
+}
+
+impl TrackedRun {
+    pub fn start(cache: &MemoryCache, job: &'static str) -> TrackedRun {
+        let at = Utc::now();
+        let mut history = cache.jobs_history.lock().unwrap();
+        history.entry(job).or_default().start(at);
+        TrackedRun {
+            cache: cache.clone(),
+            job,
+            at,
+            errors: 0,
+        }
+    }
+
+    /// Record an error reported during the run, the job handling it (e.g. by retrying later).
+    pub fn error(&mut self, error: &BotError) {
+        self.errors += 1;
+        let mut history = self.cache.jobs_history.lock().unwrap();
+        history
+            .entry(self.job)
+            .or_default()
+            .error(Utc::now(), error);
+    }
+
+    /// Record the end of the run. Only the first failed run of a streak is forwarded to the
+    /// monitoring channel, the errors themselves being alerted by the job if needed.
+    pub async fn finish(self, sender: &Sender<Event>) {
+        let is_new_failure = {
+            let mut history = self.cache.jobs_history.lock().unwrap();
+            history
+                .entry(self.job)
+                .or_default()
+                .finish(self.at, Utc::now(), self.errors)
+        };
+        if is_new_failure {
+            let error = BotError::JobFailed(format!(
+                "{} reported {} error(s) during its last run.",
+                self.job, self.errors
+            ));
+            alert(sender, "Scheduler job run failed.", error).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn failed_runs_are_counted_and_reported_once_per_streak() {
+        let at = Utc.with_ymd_and_hms(2022, 12, 5, 5, 0, 0).unwrap();
+        let mut history = JobHistory::default();
+
+        history.start(at);
+        assert_eq!(history.running_since, Some(at));
+        history.error(at, &BotError::AOC("global leaderboard unavailable"));
+        assert!(history.finish(at, at + Duration::seconds(90), 1));
+        assert_eq!(history.running_since, None);
+        assert_eq!(history.last_run.map(|run| run.duration_ms), Some(90_000));
+
+        // still failing, already reported
+        assert!(!history.finish(at, at, 2));
+        assert!(!history.finish(at, at, 0));
+        assert!(history.finish(at, at, 1));
+
+        assert_eq!((history.runs, history.failed_runs), (4, 3));
+        assert_eq!(
+            history.last_error,
+            Some((at, "AOC Error: global leaderboard unavailable".to_string()))
+        );
+    }
+}
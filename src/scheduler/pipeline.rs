@@ -0,0 +1,459 @@
+use super::{alert, precompute_outputs, throttle_overtakes, OVERTAKES_TOP_POSITIONS};
+use crate::{
+    client::provider::EventProvider,
+    config,
+    core::{
+        completions::{new_full_completions, FullCompletion},
+        events::Event,
+        latency::new_detections,
+        leaderboard::{GlobalParseReport, Identifier, Leaderboard, ScrapedLeaderboard},
+        membership::MembershipChange,
+        names,
+        standings::{
+            late_entries_shuffle, new_stage_winner, overall_positions, overtakes_between,
+            RankChange, Scoring, StageWinner,
+        },
+        watchlist::{watch_alerts, WatchAlert},
+    },
+    error::{BotError, BotResult},
+    storage::MemoryCache,
+    utils::{current_aoc_year_day, get_new_members, merge_with_highlights, DayHighlight},
+};
+use chrono::{DateTime, Utc};
+use futures::{stream, StreamExt};
+use serde::Serialize;
+use slack_morphism::SlackUserId;
+use std::time::{Duration, Instant};
+use tokio::{
+    sync::mpsc::{self, Receiver, Sender},
+    task::JoinSet,
+};
+use tracing::{error, info};
+
+// Number of leaderboards fetched concurrently, few enough to stay under the AoC rate limits.
+const FETCH_WORKERS: usize = 3;
+// Number of leaderboards parsed concurrently on the blocking pool.
+const PARSE_WORKERS: usize = 4;
+// Capacity of the channels connecting the stages.
+const STAGE_CAPACITY: usize = 8;
+
+// Raw private leaderboard response of the event provider API.
+struct Fetched {
+    year: i32,
+    response: String,
+    timestamp: DateTime<Utc>,
+}
+
+// Parsed private leaderboard, with all the members (with or without stars).
+struct Parsed {
+    year: i32,
+    scraped: ScrapedLeaderboard,
+    members: Vec<Identifier>,
+}
+
+// Changes brought by a merged leaderboard.
+struct Changes {
+    year: i32,
+    new_members: Vec<Identifier>,
+    membership: Option<MembershipChange>,
+    highlights: Vec<DayHighlight>,
+    overtakes: Vec<(Identifier, Identifier, u8)>,
+    // overall standings changed by late completions of past days
+    shuffle: Vec<RankChange>,
+    // first completion of both parts of the current day
+    stage_winner: Option<StageWinner>,
+    // members having just collected all the stars of the year
+    full_completions: Vec<FullCompletion>,
+    // news about the watched members, for each watching user
+    watch_alerts: Vec<(SlackUserId, Vec<WatchAlert>)>,
+}
+
+// Outcome of a stage over a pipeline run.
+#[derive(Default)]
+struct StageReport {
+    // time spent processing the items (waiting for the previous stage excluded)
+    busy: Duration,
+    processed: usize,
+    failures: u64,
+}
+
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct StageStats {
+    pub last_ms: u64,
+    pub max_ms: u64,
+    pub failures: u64,
+}
+
+/// Outcome of the last scrape of the private leaderboards.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct LastScrape {
+    pub at: DateTime<Utc>,
+    // whether all the requested leaderboards were fetched and parsed
+    pub succeeded: bool,
+    pub duration_ms: u64,
+}
+
+/// Timings of the stages of the update pipeline, over all the runs.
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct PipelineStats {
+    pub runs: u64,
+    pub last_scrape: Option<LastScrape>,
+    pub fetch: StageStats,
+    pub parse: StageStats,
+    pub merge: StageStats,
+    pub announce: StageStats,
+    // last parsing of the global leaderboard
+    pub global_parse: Option<GlobalParseReport>,
+}
+
+impl StageStats {
+    fn record(&mut self, report: &StageReport) {
+        let ms = report.busy.as_millis() as u64;
+        self.last_ms = ms;
+        self.max_ms = self.max_ms.max(ms);
+        self.failures += report.failures;
+    }
+}
+
+/// Update the cache with the private leaderboards of the given years, fetched from the provider.
+/// Fetching, parsing (on the blocking pool), merging and announcing run as separate stages
+/// connected by channels, so the years (fetched a few at a time) are merged as they arrive, and a
+/// year failing at any stage does not prevent the others from being merged. Changes are only
+/// announced if requested (i.e. not when the cache is initialized).
+pub async fn run<P: EventProvider + 'static>(
+    provider: &P,
+    cache: &MemoryCache,
+    sender: &Sender<Event>,
+    years: Vec<i32>,
+    announce: bool,
+) -> LastScrape {
+    let start = Instant::now();
+    let (fetched_tx, fetched_rx) = mpsc::channel(STAGE_CAPACITY);
+    let (parsed_tx, parsed_rx) = mpsc::channel(STAGE_CAPACITY);
+    let (changes_tx, changes_rx) = mpsc::channel(STAGE_CAPACITY);
+
+    let (fetch, parse, merge, announcement) = tokio::join!(
+        fetch_stage(provider, years, fetched_tx, sender),
+        parse_stage::<P>(fetched_rx, parsed_tx, sender),
+        merge_stage(parsed_rx, changes_tx, cache, announce),
+        announce_stage(changes_rx, sender),
+    );
+
+    if merge.processed > 0 {
+        cache.save_snapshot();
+        precompute_outputs(cache);
+    };
+
+    info!(
+        "Update pipeline: {} leaderboard(s) merged (fetch {}ms, parse {}ms, merge {}ms, announce {}ms).",
+        merge.processed,
+        fetch.busy.as_millis(),
+        parse.busy.as_millis(),
+        merge.busy.as_millis(),
+        announcement.busy.as_millis(),
+    );
+    let last_scrape = LastScrape {
+        at: Utc::now(),
+        succeeded: fetch.failures == 0 && parse.failures == 0,
+        duration_ms: start.elapsed().as_millis() as u64,
+    };
+    let mut stats = cache.pipeline_stats.lock().unwrap();
+    stats.runs += 1;
+    stats.last_scrape = Some(last_scrape);
+    stats.fetch.record(&fetch);
+    stats.parse.record(&parse);
+    stats.merge.record(&merge);
+    stats.announce.record(&announcement);
+    last_scrape
+}
+
+async fn fetch_stage(
+    provider: &impl EventProvider,
+    years: Vec<i32>,
+    output: Sender<Fetched>,
+    sender: &Sender<Event>,
+) -> StageReport {
+    let mut report = StageReport::default();
+    let mut responses = stream::iter(years)
+        .map(|year| async move {
+            let start = Instant::now();
+            let response = provider.fetch_private_leaderboard(year).await;
+            (year, response, start.elapsed())
+        })
+        .buffer_unordered(FETCH_WORKERS);
+
+    while let Some((year, response, busy)) = responses.next().await {
+        report.busy += busy;
+        match response {
+            Ok(response) => {
+                report.processed += 1;
+                let fetched = Fetched {
+                    year,
+                    response,
+                    timestamp: Utc::now(),
+                };
+                if output.send(fetched).await.is_err() {
+                    break;
+                };
+            }
+            Err(e) => {
+                report.failures += 1;
+                alert(sender, "Could not scrape leaderboard.", e).await;
+            }
+        };
+    }
+    report
+}
+
+async fn parse_stage<P: EventProvider + 'static>(
+    mut input: Receiver<Fetched>,
+    output: Sender<Parsed>,
+    sender: &Sender<Event>,
+) -> StageReport {
+    let mut report = StageReport::default();
+    let mut workers = JoinSet::new();
+    let mut input_open = true;
+
+    loop {
+        tokio::select! {
+            fetched = input.recv(), if input_open && workers.len() < PARSE_WORKERS => match fetched {
+                Some(fetched) => {
+                    workers.spawn_blocking(move || parse::<P>(fetched));
+                }
+                None => input_open = false,
+            },
+            Some(parsed) = workers.join_next(), if !workers.is_empty() => {
+                let parsed = match parsed {
+                    Ok((parsed, busy)) => {
+                        report.busy += busy;
+                        parsed
+                    }
+                    Err(_) => Err(BotError::Compute("Leaderboard parsing worker crashed.")),
+                };
+                match parsed {
+                    Ok(parsed) => {
+                        report.processed += 1;
+                        if output.send(parsed).await.is_err() {
+                            break;
+                        };
+                    }
+                    Err(e) => {
+                        report.failures += 1;
+                        alert(sender, "Could not parse leaderboard.", e).await;
+                    }
+                };
+            },
+            else => break,
+        }
+    }
+    report
+}
+
+// (parsed leaderboard, time spent parsing)
+fn parse<P: EventProvider>(fetched: Fetched) -> (BotResult<Parsed>, Duration) {
+    let start = Instant::now();
+    let parsed =
+        P::parse_private_leaderboard(&fetched.response).map(|(leaderboard, members)| Parsed {
+            year: fetched.year,
+            scraped: ScrapedLeaderboard {
+                timestamp: fetched.timestamp,
+                leaderboard,
+            },
+            members,
+        });
+    (parsed, start.elapsed())
+}
+
+async fn merge_stage(
+    mut input: Receiver<Parsed>,
+    output: Sender<Changes>,
+    cache: &MemoryCache,
+    announce: bool,
+) -> StageReport {
+    let mut report = StageReport::default();
+
+    while let Some(parsed) = input.recv().await {
+        let start = Instant::now();
+        let changes = merge(cache, parsed, announce);
+        report.busy += start.elapsed();
+        report.processed += 1;
+        if let Some(changes) = changes {
+            if output.send(changes).await.is_err() {
+                break;
+            };
+        };
+    }
+    report
+}
+
+/// Merge the scraped leaderboard into the cache, with the resulting changes if they are to be
+/// announced.
+fn merge(cache: &MemoryCache, parsed: Parsed, announce: bool) -> Option<Changes> {
+    let Parsed {
+        year,
+        scraped: scraped_leaderboard,
+        members,
+    } = parsed;
+    // The changes are computed under the write lock, so two merges (e.g. the scheduled refresh
+    // and an admin refresh) cannot both diff against the same leaderboard and announce twice.
+    let mut current_leaderboard = cache.data.write().unwrap();
+    if !announce {
+        current_leaderboard.merge_with(scraped_leaderboard);
+        cache
+            .membership
+            .update(|history| history.track(members, Utc::now()));
+        return None;
+    };
+
+    // Snapshot of the overall standings before the update
+    let previous_positions =
+        overall_positions(&Scoring::LOCAL, &current_leaderboard.leaderboard, year);
+    let mut previous_entries = Leaderboard::new();
+    previous_entries.extend(
+        current_leaderboard
+            .leaderboard
+            .iter()
+            .filter(|e| e.year == year)
+            .cloned(),
+    );
+
+    // Check for new members
+    let new_members = get_new_members(
+        &current_leaderboard.leaderboard,
+        &scraped_leaderboard.leaderboard,
+    );
+
+    // Check for members joining or leaving the private leaderboard, and for its cap
+    let membership = cache
+        .membership
+        .update(|history| history.update(members, Utc::now()));
+
+    // Update leaderboard in cache, checking for new parts completions. The other changes are
+    // computed from the new entries returned by the merge.
+    let (previous_scrape, scrape) = (current_leaderboard.timestamp, scraped_leaderboard.timestamp);
+    let (new_entries, highlights) =
+        merge_with_highlights(&mut current_leaderboard, scraped_leaderboard);
+
+    // Record when the new stars are first seen, the scrape time being compared to their completion.
+    let detections = new_detections(&new_entries, previous_scrape, scrape);
+    if !detections.is_empty() {
+        cache.detections.update(|log| log.extend(detections));
+    };
+
+    // Check for overtakes at the top of the overall standings
+    let current_positions =
+        overall_positions(&Scoring::LOCAL, &current_leaderboard.leaderboard, year);
+    let overtakes = throttle_overtakes(
+        &cache.announced_overtakes,
+        overtakes_between(
+            &previous_positions,
+            &current_positions,
+            OVERTAKES_TOP_POSITIONS,
+        ),
+        Utc::now(),
+    );
+
+    // Check for late completions making the posted daily summaries stale
+    let shuffle = late_entries_shuffle(&previous_entries, &current_leaderboard.leaderboard, year);
+
+    // Check for the winner of the current day, announced as soon as part 2 is first completed
+    let (current_year, current_day) = current_aoc_year_day();
+    let stage_winner = (year == current_year)
+        .then(|| {
+            new_stage_winner(
+                &previous_entries,
+                &current_leaderboard.leaderboard,
+                year,
+                current_day,
+            )
+        })
+        .flatten();
+
+    // Check for the members collecting all the stars of the year
+    let full_completions =
+        new_full_completions(&previous_entries, &current_leaderboard.leaderboard, year);
+
+    // Check for the news about the members watched by the users
+    let watch_alerts = cache.watchlists.read(|watchlists| {
+        watch_alerts(
+            watchlists,
+            &names::slack_users(),
+            &new_entries,
+            (&previous_positions, &current_positions),
+            year,
+        )
+    });
+
+    Some(Changes {
+        year,
+        new_members,
+        membership,
+        highlights,
+        overtakes,
+        shuffle,
+        stage_winner,
+        full_completions,
+        watch_alerts,
+    })
+}
+
+async fn announce_stage(mut input: Receiver<Changes>, sender: &Sender<Event>) -> StageReport {
+    let settings = &config::SETTINGS;
+    let mut report = StageReport::default();
+
+    while let Some(changes) = input.recv().await {
+        let start = Instant::now();
+        // Conditionnally trigger internal events, base on leaderboard processing.
+        let mut events = vec![];
+        if !settings.features.summary_events_only {
+            if !changes.new_members.is_empty() {
+                events.push(Event::PrivateLeaderboardNewMembers(changes.new_members));
+            };
+            if !changes.highlights.is_empty() {
+                events.push(Event::PrivateLeaderboardNewEntries(changes.highlights));
+            };
+            if !changes.overtakes.is_empty() {
+                events.push(Event::PrivateLeaderboardOvertakes(changes.overtakes));
+            };
+            events.extend(
+                changes
+                    .full_completions
+                    .into_iter()
+                    .map(Event::FullCompletion),
+            );
+        };
+        if !changes.shuffle.is_empty() {
+            events.push(Event::PrivateLeaderboardShuffle(
+                changes.year,
+                changes.shuffle,
+            ));
+        };
+        if let Some(winner) = changes
+            .stage_winner
+            .filter(|_| settings.features.stage_winner)
+        {
+            events.push(Event::StageWinner(winner));
+        };
+        if let Some(membership) = changes.membership {
+            events.push(Event::PrivateLeaderboardMembership(membership));
+        };
+        events.extend(
+            changes
+                .watch_alerts
+                .into_iter()
+                .map(|(user, alerts)| Event::WatchAlerts(user, changes.year, alerts)),
+        );
+        events.push(Event::PrivateLeaderboardUpdated);
+
+        for event in events {
+            if let Err(e) = sender.send(event).await {
+                report.failures += 1;
+                let error = BotError::from(e);
+                error!("{error}");
+            };
+        }
+        report.busy += start.elapsed();
+        report.processed += 1;
+    }
+    report
+}
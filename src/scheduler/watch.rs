@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Progress of the watch of the global leaderboard of a day, persisted after each poll so the
+/// watch resumes where it stopped after a restart. The heroes already announced are kept with
+/// the appearances of the members (see `HeroesHistory`).
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct GlobalWatch {
+    pub year: i32,
+    pub day: u8,
+    // polls of the global leaderboard since the start of the watch
+    pub counter: u64,
+    pub is_complete: bool,
+    // polls that failed since the last successful one
+    pub consecutive_failures: u32,
+    pub is_parse_alert_sent: bool,
+    // times of the first and the last polls, the delay between the polls varying with the
+    // backoff
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_poll: Option<DateTime<Utc>>,
+}
+
+impl GlobalWatch {
+    /// Watch of the day, resumed from the stored one if it was started for the same day.
+    pub fn resume(stored: Option<&GlobalWatch>, year: i32, day: u8) -> GlobalWatch {
+        match stored {
+            Some(watch) if watch.year == year && watch.day == day => watch.clone(),
+            _ => GlobalWatch {
+                year,
+                day,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Record a poll of the global leaderboard.
+    pub fn poll(&mut self, now: DateTime<Utc>) {
+        self.started_at.get_or_insert(now);
+        self.last_poll = Some(now);
+    }
+
+    /// Time between the first and the last polls of the watch.
+    pub fn elapsed(&self) -> Duration {
+        match (self.started_at, self.last_poll) {
+            (Some(start), Some(last)) => (last - start).to_std().unwrap_or_default(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Delay before the next poll, doubled after each consecutive failure (up to `max`) not to
+    /// hammer AoC while it is down.
+    pub fn delay(&self, period: Duration, max: Duration) -> Duration {
+        match self.consecutive_failures {
+            0 => period,
+            failures => period
+                .saturating_mul(2u32.saturating_pow(failures.min(16)))
+                .min(max.max(period)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_resumes_the_same_day_and_backs_off_on_failures() {
+        let stored = GlobalWatch {
+            year: 2007,
+            day: 3,
+            counter: 6,
+            consecutive_failures: 2,
+            ..Default::default()
+        };
+        let watch = GlobalWatch::resume(Some(&stored), 2007, 3);
+        assert_eq!(watch, stored);
+        assert_eq!(
+            GlobalWatch::resume(Some(&stored), 2007, 4),
+            GlobalWatch {
+                year: 2007,
+                day: 4,
+                ..Default::default()
+            }
+        );
+
+        let (period, max) = (Duration::from_secs(60), Duration::from_secs(600));
+        assert_eq!(watch.delay(period, max), Duration::from_secs(240));
+        let watch = GlobalWatch {
+            consecutive_failures: 0,
+            ..watch
+        };
+        assert_eq!(watch.delay(period, max), period);
+        let watch = GlobalWatch {
+            consecutive_failures: 40,
+            ..watch
+        };
+        assert_eq!(watch.delay(period, max), max);
+
+        let mut watch = GlobalWatch::default();
+        let start = Utc::now();
+        watch.poll(start);
+        assert_eq!(watch.elapsed(), Duration::ZERO);
+        // backed off polls
+        watch.poll(start + chrono::Duration::seconds(60));
+        watch.poll(start + chrono::Duration::seconds(300));
+        assert_eq!(watch.started_at, Some(start));
+        assert_eq!(watch.elapsed(), Duration::from_secs(300));
+    }
+}
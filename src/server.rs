@@ -0,0 +1,114 @@
+use crate::{
+    config, core::events::Event, error::BotResult, scheduler::RefreshRequest, storage::MemoryCache,
+};
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::{net::TcpListener, sync::mpsc::Sender};
+use tracing::info;
+
+pub mod admin;
+pub mod bot_api;
+pub mod data_api;
+pub mod health;
+pub mod oauth;
+
+// State shared by all the HTTP handlers.
+#[derive(Clone)]
+pub struct ServerState {
+    pub cache: MemoryCache,
+    pub sender: Arc<Sender<Event>>, // communication to messaging service
+    pub refresh: Sender<RefreshRequest>, // refreshes run by the scheduler, with its provider
+}
+
+pub async fn serve(
+    port: u16,
+    cache: MemoryCache,
+    sender: Arc<Sender<Event>>,
+    refresh: Sender<RefreshRequest>,
+) -> BotResult<()> {
+    let settings = &config::SETTINGS;
+    let state = ServerState {
+        cache,
+        sender,
+        refresh,
+    };
+
+    let mut app = health::router(state.clone());
+    if let Some(token) = &settings.server.admin_api_token {
+        app = app.nest("/admin", admin::router(state.clone(), token.to_string()));
+    };
+    if let Some(token) = &settings.server.bot_api_token {
+        app = app.nest(
+            "/api",
+            bot_api::router(
+                state.clone(),
+                token.to_string(),
+                settings.server.bot_api_max_announcements_per_hour,
+            ),
+        );
+    };
+
+    if let Some(token) = &settings.server.data_api_token {
+        app = app.nest("/data", data_api::router(state.clone(), token.to_string()));
+    };
+
+    // the installation is started with the admin token (required along with the OAuth settings)
+    if let (Some(oauth), Some(token)) = (&settings.slack.oauth, &settings.server.admin_api_token) {
+        app = app.nest(
+            "/slack",
+            oauth::router(state.cache.clone(), oauth, token.to_string()),
+        );
+    };
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    info!("HTTP server listening on port {port}.");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// Middleware rejecting the requests not providing the expected bearer token.
+pub async fn authenticate(
+    State(token): State<Arc<String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match is_authorized(request.headers(), &token) {
+        true => next.run(request).await,
+        false => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Whether the bearer token of the request is the expected one, compared in constant time so
+/// the token can not be guessed from the response times.
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|bearer| bearer.as_bytes().ct_eq(token.as_bytes()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_authorized_with_the_expected_bearer_token_only() {
+        let token = "s3cr3t-t0ken";
+        let headers = |authorization: &str| {
+            HeaderMap::from_iter([(header::AUTHORIZATION, authorization.parse().unwrap())])
+        };
+
+        assert!(is_authorized(&headers("Bearer s3cr3t-t0ken"), token));
+        assert!(!is_authorized(&HeaderMap::new(), token));
+        assert!(!is_authorized(&headers("Bearer wrong-token"), token));
+        assert!(!is_authorized(&headers("Bearer s3cr3t"), token));
+        assert!(!is_authorized(&headers("s3cr3t-t0ken"), token));
+    }
+}
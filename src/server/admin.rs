@@ -0,0 +1,92 @@
+use crate::{
+    scheduler::{pipeline::PipelineStats, send_daily_summary},
+    server::{authenticate, ServerState},
+    storage::precomputed::PrecomputedStats,
+};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::Serialize;
+use std::sync::{atomic::Ordering, Arc};
+use tokio::sync::oneshot;
+use tracing::info;
+
+#[derive(Serialize, Debug)]
+struct CacheStats {
+    timestamp: DateTime<Utc>,
+    entries: usize,
+    members: usize,
+    years: Vec<i32>,
+    muted: bool,
+    // lookups of the outputs precomputed after each leaderboard merge
+    precomputed: PrecomputedStats,
+    // stages timings of the leaderboard update pipeline
+    pipeline: PipelineStats,
+}
+
+/// Admin endpoints, all protected by the configured bearer token.
+pub fn router(state: ServerState, token: String) -> Router {
+    Router::new()
+        .route("/refresh", post(refresh))
+        .route("/summary", post(resend_summary))
+        .route("/cache", get(cache_stats))
+        .route("/mute", post(mute))
+        .route("/unmute", post(unmute))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(token),
+            authenticate,
+        ))
+        .with_state(state)
+}
+
+async fn refresh(State(state): State<ServerState>) -> StatusCode {
+    info!("Private leaderboard refresh requested through admin API.");
+    let (done, refreshed) = oneshot::channel();
+    if state.refresh.send(done).await.is_err() || refreshed.await.is_err() {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    };
+    StatusCode::OK
+}
+
+async fn resend_summary(State(state): State<ServerState>) -> StatusCode {
+    info!("Daily summary requested through admin API.");
+    send_daily_summary(&state.cache, &state.sender).await;
+    StatusCode::OK
+}
+
+async fn cache_stats(State(state): State<ServerState>) -> Json<CacheStats> {
+    let data = state.cache.data.read().unwrap();
+    Json(CacheStats {
+        timestamp: data.timestamp,
+        entries: data.leaderboard.len(),
+        members: data.leaderboard.members_ids().len(),
+        years: data
+            .leaderboard
+            .iter()
+            .map(|e| e.year)
+            .unique()
+            .sorted()
+            .collect(),
+        muted: state.cache.muted.load(Ordering::Relaxed),
+        precomputed: state.cache.precomputed.stats(),
+        pipeline: state.cache.pipeline_stats.lock().unwrap().clone(),
+    })
+}
+
+async fn mute(State(state): State<ServerState>) -> StatusCode {
+    info!("Announcements muted through admin API.");
+    state.cache.muted.store(true, Ordering::Relaxed);
+    StatusCode::OK
+}
+
+async fn unmute(State(state): State<ServerState>) -> StatusCode {
+    info!("Announcements unmuted through admin API.");
+    state.cache.muted.store(false, Ordering::Relaxed);
+    StatusCode::OK
+}
@@ -0,0 +1,128 @@
+use crate::{
+    core::events::Event,
+    server::{authenticate, ServerState},
+};
+use axum::{extract::State, http::StatusCode, middleware, routing::post, Json, Router};
+use serde::Deserialize;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(3600);
+
+#[derive(Deserialize, Debug)]
+struct Announcement {
+    message: String,
+    // 📣 if not set
+    emoji: Option<String>,
+}
+
+#[derive(Clone)]
+struct BotApiState {
+    server: ServerState,
+    limiter: Arc<RateLimiter>,
+}
+
+/// Bot API endpoints, letting scripts (e.g. CI pipelines) post announcements. All protected by
+/// the configured bearer token.
+pub fn router(state: ServerState, token: String, max_per_hour: usize) -> Router {
+    let state = BotApiState {
+        server: state,
+        limiter: Arc::new(RateLimiter::new(max_per_hour, RATE_LIMIT_WINDOW)),
+    };
+    Router::new()
+        .route("/announce", post(announce))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(token),
+            authenticate,
+        ))
+        .with_state(state)
+}
+
+async fn announce(
+    State(state): State<BotApiState>,
+    Json(announcement): Json<Announcement>,
+) -> StatusCode {
+    let message = announcement.message.trim();
+    if message.is_empty() {
+        return StatusCode::UNPROCESSABLE_ENTITY;
+    };
+    if !state.limiter.try_acquire(Instant::now()) {
+        warn!("Announcement rejected by the bot API rate limiting.");
+        return StatusCode::TOO_MANY_REQUESTS;
+    };
+
+    info!("Announcement requested through bot API.");
+    let event = Event::CustomAnnouncement(message.to_string(), announcement.emoji);
+    match state.server.sender.send(event).await {
+        Ok(_) => StatusCode::ACCEPTED,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+/// Sliding window rate limiting: at most `max` acquisitions over any `window` long period.
+struct RateLimiter {
+    max: usize,
+    window: Duration,
+    acquired: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(max: usize, window: Duration) -> RateLimiter {
+        RateLimiter {
+            max,
+            window,
+            acquired: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn try_acquire(&self, now: Instant) -> bool {
+        let mut acquired = self.acquired.lock().unwrap();
+        while acquired
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= self.window)
+        {
+            acquired.pop_front();
+        }
+        match acquired.len() < self.max {
+            true => {
+                acquired.push_back(now);
+                true
+            }
+            false => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn announcements_are_limited_over_a_sliding_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(limiter.try_acquire(at(0)));
+        assert!(limiter.try_acquire(at(30)));
+        assert!(!limiter.try_acquire(at(59)));
+        // the first announcement is out of the window
+        assert!(limiter.try_acquire(at(60)));
+        assert!(!limiter.try_acquire(at(89)));
+        assert!(limiter.try_acquire(at(90)));
+    }
+
+    #[test]
+    fn announcements_go_through_the_custom_message_template() {
+        let with_emoji =
+            Event::CustomAnnouncement("Solutions repo updated".to_string(), Some("🚀".to_string()));
+        let without_emoji = Event::CustomAnnouncement("Solutions repo updated".to_string(), None);
+
+        assert_eq!(with_emoji.to_string(), "🚀 Solutions repo updated");
+        assert_eq!(without_emoji.to_string(), "📣 Solutions repo updated");
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    core::{
+        leaderboard::{Leaderboard, MemberId, ProblemPart},
+        names,
+        standings::{standings_board, Jersey, Scoring, Standing},
+    },
+    server::{authenticate, ServerState},
+    utils::current_aoc_year_day,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::sse::{self, KeepAlive, Sse},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::info;
+
+#[derive(Deserialize, Debug)]
+struct Params {
+    // current event if not set
+    year: Option<i32>,
+    // `local` or `stars`, for the standings
+    scoring: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct Response<T: Serialize> {
+    // last update of the cached leaderboard
+    timestamp: DateTime<Utc>,
+    year: i32,
+    data: Vec<T>,
+}
+
+#[derive(Serialize, Debug)]
+struct Star {
+    member_id: MemberId,
+    name: String,
+    day: u8,
+    part: ProblemPart,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct MemberStanding {
+    rank: usize,
+    member_id: MemberId,
+    name: String,
+    stars: usize,
+    score: usize,
+    // (stars, points) earned each day
+    days: Vec<(u8, usize)>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Classification {
+    Time {
+        total_time_sec: i64,
+        // days not finished (or finished after the penalty time)
+        penalties: i64,
+    },
+    Points {
+        points: i64,
+        scored_days: i64,
+    },
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct TdfStanding {
+    rank: usize,
+    member_id: MemberId,
+    name: String,
+    #[serde(flatten)]
+    classification: Classification,
+}
+
+/// Read-only endpoints exposing the scraped data, to build dashboards on top of the bot. All
+/// protected by the configured bearer token.
+pub fn router(state: ServerState, token: String) -> Router {
+    Router::new()
+        .route("/leaderboard", get(leaderboard))
+        .route("/standings", get(standings))
+        .route("/tdf/{jersey}", get(tdf))
+        .route("/events", get(events))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(token),
+            authenticate,
+        ))
+        .with_state(state)
+}
+
+async fn leaderboard(
+    State(state): State<ServerState>,
+    Query(params): Query<Params>,
+) -> Json<Response<Star>> {
+    let year = params.year.unwrap_or(current_aoc_year_day().0);
+    let data = state.cache.data.read().unwrap();
+    let mut stars = data
+        .leaderboard
+        .iter()
+        .filter(|e| e.year == year)
+        .collect::<Vec<_>>();
+    stars.sort();
+
+    Json(Response {
+        timestamp: data.timestamp,
+        year,
+        data: stars
+            .into_iter()
+            .map(|e| Star {
+                member_id: e.id.numeric,
+                name: names::resolve(&e.id),
+                day: e.day,
+                part: e.part,
+                timestamp: e.timestamp,
+            })
+            .collect(),
+    })
+}
+
+async fn standings(
+    State(state): State<ServerState>,
+    Query(params): Query<Params>,
+) -> Result<Json<Response<MemberStanding>>, StatusCode> {
+    let year = params.year.unwrap_or(current_aoc_year_day().0);
+    let scoring = Scoring::from_string(
+        params
+            .scoring
+            .as_deref()
+            .unwrap_or(Scoring::get_default_str()),
+    )
+    .ok_or(StatusCode::BAD_REQUEST)?;
+    let data = state.cache.data.read().unwrap();
+
+    Ok(Json(Response {
+        timestamp: data.timestamp,
+        year,
+        data: member_standings(&data.leaderboard, year, &scoring),
+    }))
+}
+
+async fn tdf(
+    State(state): State<ServerState>,
+    Path(jersey): Path<String>,
+    Query(params): Query<Params>,
+) -> Result<Json<Response<TdfStanding>>, StatusCode> {
+    let year = params.year.unwrap_or(current_aoc_year_day().0);
+    let jersey = Jersey::from_string(&jersey).ok_or(StatusCode::NOT_FOUND)?;
+    let data = state.cache.data.read().unwrap();
+
+    Ok(Json(Response {
+        timestamp: data.timestamp,
+        year,
+        data: tdf_standings(&data.leaderboard, year, &jersey),
+    }))
+}
+
+/// Announcements streamed as Server-Sent Events while they are dispatched, the event name being
+/// the variant of the event.
+async fn events(
+    State(state): State<ServerState>,
+) -> Sse<impl Stream<Item = Result<sse::Event, axum::Error>>> {
+    let receiver = state.cache.event_stream.subscribe();
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(streamed) => {
+                    let event = sse::Event::default()
+                        .event(streamed.event)
+                        .json_data(&streamed);
+                    return Some((event, receiver));
+                }
+                // a slow subscriber misses the oldest events, but keeps the stream
+                Err(RecvError::Lagged(n_skipped)) => {
+                    info!("Events stream subscriber lagging, {n_skipped} events skipped.");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+fn member_standings(
+    leaderboard: &Leaderboard,
+    year: i32,
+    scoring: &Scoring,
+) -> Vec<MemberStanding> {
+    standings_board(scoring, leaderboard, year)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (id, days, _total))| MemberStanding {
+            rank: i + 1,
+            member_id: id.numeric,
+            name: names::resolve(id),
+            stars: days.iter().map(|(stars, _score)| *stars as usize).sum(),
+            score: days.iter().map(|(_stars, score)| score).sum(),
+            days: days.to_vec(),
+        })
+        .collect()
+}
+
+fn tdf_standings(leaderboard: &Leaderboard, year: i32, jersey: &Jersey) -> Vec<TdfStanding> {
+    Standing::new(leaderboard)
+        .tdf_season(jersey, year)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (id, value, n_days))| TdfStanding {
+            rank: i + 1,
+            member_id: id.numeric,
+            name: names::resolve(id),
+            classification: match jersey {
+                Jersey::YELLOW => Classification::Time {
+                    total_time_sec: value,
+                    penalties: n_days,
+                },
+                Jersey::CUSTOM(custom) if !custom.is_points() => Classification::Time {
+                    total_time_sec: value,
+                    penalties: n_days,
+                },
+                Jersey::GREEN | Jersey::COMBATIVE | Jersey::CUSTOM(_) => Classification::Points {
+                    points: value,
+                    scored_days: n_days,
+                },
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    #[test]
+    fn standings_expose_the_daily_stars_and_points() {
+        let members = vec![
+            MemberFixture::new(31, "Rudolph").with_day(1, 200, 60),
+            MemberFixture::new(32, "Olive").with_day(1, 300, 600),
+            MemberFixture::new(33, "Clarice").with_part1(2, 900),
+        ];
+        let leaderboard = private_leaderboard(2019, &members);
+
+        let by_score = member_standings(&leaderboard, 2019, &Scoring::LOCAL)
+            .into_iter()
+            .map(|s| (s.rank, s.name, s.stars, s.score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            by_score,
+            vec![
+                (1, "Rudolph".to_string(), 2, 6),
+                (2, "Olive".to_string(), 2, 4),
+                (3, "Clarice".to_string(), 1, 3),
+            ]
+        );
+        let olive = &member_standings(&leaderboard, 2019, &Scoring::LOCAL)[1];
+        assert_eq!(olive.days[0], (2, 4));
+        assert_eq!(olive.days[1], (0, 0));
+
+        let green = tdf_standings(&leaderboard, 2019, &Jersey::GREEN);
+        assert_eq!(green[0].member_id, MemberId(31));
+        assert!(matches!(
+            green[0].classification,
+            Classification::Points { scored_days: 1, .. }
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    config,
+    scheduler::pipeline::LastScrape,
+    server::ServerState,
+    utils::{season_at, Season},
+};
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq)]
+struct Health {
+    healthy: bool,
+    last_scrape: Option<LastScrape>,
+    // age of the cached leaderboard
+    cache_age_sec: i64,
+    max_age_sec: u64,
+}
+
+/// Unauthenticated health check, so orchestrators can restart a wedged bot.
+pub fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .with_state(state)
+}
+
+async fn healthz(State(state): State<ServerState>) -> (StatusCode, Json<Health>) {
+    let settings = &config::SETTINGS;
+    let timestamp = state.cache.data.read().unwrap().timestamp;
+    let last_scrape = state.cache.pipeline_stats.lock().unwrap().last_scrape;
+
+    let health = health(
+        timestamp,
+        last_scrape,
+        Utc::now(),
+        settings.server.healthcheck_max_age_sec,
+    );
+    let status = match health.healthy {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(health))
+}
+
+// The cache is only expected to be fresh while the private leaderboard is scraped.
+fn health(
+    timestamp: DateTime<Utc>,
+    last_scrape: Option<LastScrape>,
+    now: DateTime<Utc>,
+    max_age_sec: u64,
+) -> Health {
+    let cache_age_sec = (now - timestamp).num_seconds();
+    let is_scraped = season_at(now) == Season::Event;
+
+    Health {
+        healthy: !is_scraped || cache_age_sec <= max_age_sec as i64,
+        last_scrape,
+        cache_age_sec,
+        max_age_sec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn stale_cache_is_unhealthy_while_the_leaderboard_is_scraped() {
+        let during_event = Utc.with_ymd_and_hms(2023, 12, 10, 12, 0, 0).unwrap();
+        let off_season = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let failed = Some(LastScrape {
+            at: during_event,
+            succeeded: false,
+            duration_ms: 4200,
+        });
+
+        let fresh = health(
+            during_event - Duration::minutes(20),
+            failed,
+            during_event,
+            3600,
+        );
+        assert!(fresh.healthy);
+        assert_eq!(fresh.cache_age_sec, 1200);
+
+        let stale = health(
+            during_event - Duration::hours(2),
+            failed,
+            during_event,
+            3600,
+        );
+        assert!(!stale.healthy);
+
+        let old = health(during_event, None, off_season, 3600);
+        assert!(old.healthy);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    config::SlackOAuthSettings,
+    error::{BotError, BotResult},
+    server::authenticate,
+    storage::{MemoryCache, SlackInstallation},
+};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use reqwest::Url;
+use serde::Deserialize;
+use slack_morphism::prelude::*;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::{error, info};
+
+const AUTHORIZE_URL: &str = "https://slack.com/oauth/v2/authorize";
+// Bot scopes of the Slack app manifest (see README).
+const BOT_SCOPES: [&str; 9] = [
+    "channels:join",
+    "channels:read",
+    "groups:read",
+    "chat:write",
+    "chat:write.customize",
+    "files:write",
+    "im:write",
+    "channels:history",
+    "reactions:read",
+];
+// Time given to the user to approve the installation on the Slack side.
+const PENDING_INSTALLATION_TTL: Duration = Duration::from_secs(600);
+
+#[derive(Clone)]
+struct OAuthState {
+    cache: MemoryCache,
+    settings: &'static SlackOAuthSettings,
+    pending: Arc<PendingInstallations>,
+}
+
+/// Installations waiting for the user approval, identified by the random `state` sent to Slack
+/// and expected back in the callback (CSRF protection).
+#[derive(Default)]
+struct PendingInstallations(Mutex<HashMap<String, Instant>>);
+
+#[derive(Deserialize)]
+struct Callback {
+    code: Option<String>,
+    state: Option<String>,
+    // set if the user cancelled the installation
+    error: Option<String>,
+}
+
+/// OAuth installation endpoints: `/install`, protected by the admin token, gives the URL of the
+/// Slack approval page, which then redirects to `/oauth/callback` where the code is exchanged for
+/// the bot token. The callback only completes the installations started with `/install`.
+pub fn router(
+    cache: MemoryCache,
+    settings: &'static SlackOAuthSettings,
+    admin_token: String,
+) -> Router {
+    Router::new()
+        .route("/install", get(install))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(admin_token),
+            authenticate,
+        ))
+        .route("/oauth/callback", get(callback))
+        .with_state(OAuthState {
+            cache,
+            settings,
+            pending: Arc::new(PendingInstallations::default()),
+        })
+}
+
+impl PendingInstallations {
+    fn start(&self, now: Instant) -> String {
+        let mut pending = self.0.lock().unwrap();
+        pending.retain(|_state, started| now.duration_since(*started) < PENDING_INSTALLATION_TTL);
+        let state = uuid::Uuid::new_v4().to_string();
+        pending.insert(state.clone(), now);
+        state
+    }
+
+    /// Whether the state matches an installation in progress, which can only be completed once.
+    fn complete(&self, state: &str, now: Instant) -> bool {
+        let mut pending = self.0.lock().unwrap();
+        pending
+            .remove(state)
+            .is_some_and(|started| now.duration_since(started) < PENDING_INSTALLATION_TTL)
+    }
+}
+
+fn authorize_url(settings: &SlackOAuthSettings, state: &str) -> Url {
+    Url::parse_with_params(
+        AUTHORIZE_URL,
+        [
+            ("client_id", settings.client_id.as_str()),
+            ("scope", &BOT_SCOPES.join(",")),
+            ("redirect_uri", settings.redirect_url.as_str()),
+            ("state", state),
+        ],
+    )
+    .unwrap()
+}
+
+async fn install(State(state): State<OAuthState>) -> String {
+    let pending = state.pending.start(Instant::now());
+    info!("Slack installation started through the admin token.");
+    authorize_url(state.settings, &pending).to_string()
+}
+
+/// Replace the current installation, unless the new one is for another workspace: the bot can
+/// only be installed again (e.g. to renew its token) in the workspace it is installed in.
+fn replace_installation(
+    current: &mut Option<SlackInstallation>,
+    installation: SlackInstallation,
+) -> Result<(), SlackTeamId> {
+    match current {
+        Some(installed) if installed.team_id != installation.team_id => {
+            Err(installed.team_id.clone())
+        }
+        _ => {
+            *current = Some(installation);
+            Ok(())
+        }
+    }
+}
+
+async fn callback(State(state): State<OAuthState>, Query(callback): Query<Callback>) -> Response {
+    if let Some(error) = callback.error {
+        info!("Slack installation cancelled: {error}.");
+        return (StatusCode::BAD_REQUEST, "Installation cancelled.").into_response();
+    };
+    let (Some(code), Some(pending)) = (callback.code, callback.state) else {
+        return (StatusCode::BAD_REQUEST, "Missing OAuth code or state.").into_response();
+    };
+    if !state.pending.complete(&pending, Instant::now()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Unknown or expired installation, please start again.",
+        )
+            .into_response();
+    };
+
+    match exchange_code(state.settings, code).await {
+        Ok(installation) => {
+            let team = installation
+                .team_name
+                .clone()
+                .unwrap_or_else(|| installation.team_id.to_string());
+            let replaced = state
+                .cache
+                .slack_installation
+                .update(|current| replace_installation(current, installation));
+            match replaced {
+                Ok(()) => {
+                    info!("Bot installed in the {team} workspace.");
+                    (
+                        StatusCode::OK,
+                        format!("The bot is now installed in the {team} workspace 🎄"),
+                    )
+                        .into_response()
+                }
+                Err(installed) => {
+                    error!("Installation in the {team} workspace rejected, the bot is installed in {installed}.");
+                    (
+                        StatusCode::FORBIDDEN,
+                        "The bot is already installed in another workspace.",
+                    )
+                        .into_response()
+                }
+            }
+        }
+        Err(error) => {
+            error!("{error}");
+            (
+                StatusCode::BAD_GATEWAY,
+                "Could not complete the installation.",
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn exchange_code(
+    settings: &SlackOAuthSettings,
+    code: String,
+) -> BotResult<SlackInstallation> {
+    let connector = SlackClientHyperConnector::new()
+        .map_err(|e| BotError::SlackListener(format!("Could not create Slack client. {e}")))?;
+    let client = SlackClient::new(connector);
+    let request = SlackOAuthV2AccessTokenRequest::new(
+        settings.client_id.clone().into(),
+        settings.client_secret.clone().into(),
+        code.into(),
+    )
+    .opt_redirect_uri(Url::parse(&settings.redirect_url).ok());
+
+    let response = client
+        .oauth2_access(&request)
+        .await
+        .map_err(BotError::slack("Could not exchange the OAuth code."))?;
+    Ok(SlackInstallation {
+        team_id: response.team.id,
+        team_name: response.team.name,
+        bot_user_id: response.bot_user_id,
+        bot_token: response.access_token,
+        installed_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn installations_can_only_be_completed_once_and_in_time() {
+        let pending = PendingInstallations::default();
+        let now = Instant::now();
+
+        let state = pending.start(now);
+        assert!(!pending.complete("forged", now));
+        assert!(pending.complete(&state, now + Duration::from_secs(30)));
+        assert!(!pending.complete(&state, now + Duration::from_secs(31)));
+
+        let expired = pending.start(now);
+        assert!(!pending.complete(&expired, now + PENDING_INSTALLATION_TTL));
+    }
+
+    #[test]
+    fn installations_in_another_workspace_are_rejected() {
+        let installation = |team: &str, token: &str| SlackInstallation {
+            team_id: team.into(),
+            team_name: None,
+            bot_user_id: None,
+            bot_token: token.into(),
+            installed_at: Utc::now(),
+        };
+        let mut current = None;
+
+        assert!(replace_installation(&mut current, installation("T01", "xoxb-1")).is_ok());
+        assert_eq!(
+            replace_installation(&mut current, installation("T02", "xoxb-2")),
+            Err("T01".into())
+        );
+        assert!(replace_installation(&mut current, installation("T01", "xoxb-3")).is_ok());
+        assert_eq!(
+            current.map(|i| i.bot_token.to_string()),
+            Some("xoxb-3".to_string())
+        );
+    }
+
+    #[test]
+    fn authorize_url_requests_the_bot_scopes() {
+        let settings = SlackOAuthSettings {
+            client_id: "1234.5678".to_string(),
+            client_secret: "s3cr3t".to_string(),
+            redirect_url: "https://ceo.example.com/slack/oauth/callback".to_string(),
+        };
+
+        let url = authorize_url(&settings, "some-state");
+        let params = url.query_pairs().into_owned().collect::<HashMap<_, _>>();
+
+        assert_eq!(params["client_id"], "1234.5678");
+        assert_eq!(params["scope"], BOT_SCOPES.join(","));
+        assert_eq!(params["redirect_uri"], settings.redirect_url);
+        assert_eq!(params["state"], "some-state");
+        assert!(!url.as_str().contains("s3cr3t"));
+    }
+}
@@ -1,11 +1,145 @@
-use crate::core::leaderboard::ScrapedLeaderboard;
-use std::sync::{Arc, Mutex};
+use crate::{
+    config,
+    core::{
+        absences::Absences,
+        difficulty::{GlobalCompletion, PollVotes},
+        exclusions::Exclusions,
+        heroes::HeroesHistory,
+        latency::Detection,
+        leaderboard::{MemberId, ScrapedLeaderboard},
+        membership::MembershipHistory,
+        names::OptOuts,
+        predictions::Guess,
+        votes::SolutionReply,
+        watchlist::Watchlists,
+    },
+    error::{BotError, BotResult},
+    scheduler::{history::JobsHistory, pipeline::PipelineStats, watch::GlobalWatch},
+};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use slack_morphism::{SlackApiTokenValue, SlackChannelId, SlackTeamId, SlackTs, SlackUserId};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
+};
+use tracing::{error, info};
 
-type SharedLeaderboard = Arc<Mutex<ScrapedLeaderboard>>;
+pub mod audit;
+pub mod precomputed;
+pub mod snapshot;
+pub mod stream;
+
+use audit::AuditStore;
+use precomputed::Precomputed;
+use snapshot::LeaderboardSnapshot;
+use stream::EventStream;
+
+// Name of the cached leaderboard snapshot file in the storage directory
+const LEADERBOARD_SNAPSHOT: &str = "leaderboard";
+
+// Read by the commands and most jobs, only written when merging a scraped leaderboard.
+type SharedLeaderboard = Arc<RwLock<ScrapedLeaderboard>>;
+type AnnouncedOvertakes = Arc<Mutex<HashMap<(MemberId, MemberId), DateTime<Utc>>>>;
 
 #[derive(Clone)]
 pub struct MemoryCache {
     pub data: SharedLeaderboard,
+    // Most requested outputs computed from the cached leaderboard.
+    pub precomputed: Arc<Precomputed>,
+    // Timings of the stages of the leaderboard update pipeline.
+    pub pipeline_stats: Arc<Mutex<PipelineStats>>,
+    // Last runs and errors of the scheduler jobs.
+    pub jobs_history: Arc<Mutex<JobsHistory>>,
+    // When muted, announcements are not posted (commands responses are still sent).
+    pub muted: Arc<AtomicBool>,
+    // Announcements published to the subscribers of the data API events endpoint.
+    pub event_stream: EventStream,
+    // Last time an overtake between two members (overtaker, overtaken) was announced.
+    pub announced_overtakes: AnnouncedOvertakes,
+    // Slack users who asked to be reminded of the days a member still has stars to collect.
+    pub reminders: Store<HashMap<SlackUserId, MemberId>>,
+    // Live scoreboard message pinned by the bot in each scoreboard channel.
+    pub live_scoreboards: Store<HashMap<SlackChannelId, SlackTs>>,
+    // Daily solutions threads posted by the bot.
+    pub solution_threads: Store<Vec<SolutionThread>>,
+    // Replies posted in the daily solutions threads, with the reactions they received.
+    pub solution_replies: Store<Vec<SolutionReply>>,
+    // Difficulty polls posted by the bot after the puzzle release.
+    pub difficulty_polls: Store<Vec<DifficultyPoll>>,
+    // Members of the private leaderboard and their number over time, to report the churn and
+    // warn before the cap is reached.
+    pub membership: Store<MembershipHistory>,
+    // Slack users who linked themselves to a member with the `!iam` command.
+    pub slack_users: Store<HashMap<MemberId, SlackUserId>>,
+    // Slack users who do not want to receive their year in review.
+    pub review_opt_outs: Store<HashSet<SlackUserId>>,
+    // Slack users who asked to receive their personal summary after the daily summary.
+    pub personal_summary_opt_ins: Store<HashSet<SlackUserId>>,
+    // Global leaderboard completion times of the past days, to rate the difficulty of a day.
+    pub global_completions: Store<Vec<GlobalCompletion>>,
+    // Appearances of the members on the global leaderboards, watched daily or scanned at startup.
+    pub global_heroes: Store<HeroesHistory>,
+    // Progress of the watch of the global leaderboard of the day, resumed after a restart.
+    pub global_watch: Store<Option<GlobalWatch>>,
+    // Year of the last event announced with the join code of the private leaderboard.
+    pub announced_event: Store<Option<i32>>,
+    // Stars of the private leaderboard with the time they were first seen by the bot, to measure
+    // the detection latency reported by the `!latency` command.
+    pub detections: Store<Vec<Detection>>,
+    // Guesses of the global leaderboard cutoff submitted with the `!guess` command.
+    pub guesses: Store<Vec<Guess>>,
+    // Days of absence declared by the admins with the `!tdf absence` command.
+    pub absences: Store<Absences>,
+    // Members left out of the standings by the admins with the `!exclude` command.
+    pub exclusions: Store<Exclusions>,
+    // Members watched by the users with the `!watch` command, to alert them of their progress.
+    pub watchlists: Store<Watchlists>,
+    // Members who asked not to be named in the messages with the `!optout` command.
+    pub opt_outs: Store<OptOuts>,
+    // Last messages posted by the bot, listed by the admins with the `!auditlog` command.
+    pub audit_log: AuditStore,
+    // Messages of the announcements held back during the quiet hours, posted once they end.
+    pub quiet_hours_held: Store<Vec<String>>,
+    // Installation of the bot in the workspace through OAuth, if not configured with a bot token.
+    pub slack_installation: Store<Option<SlackInstallation>>,
+}
+
+/// Bot token (and workspace) obtained when installing the bot through the Slack OAuth flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackInstallation {
+    pub team_id: SlackTeamId,
+    pub team_name: Option<String>,
+    pub bot_user_id: Option<SlackUserId>,
+    // redacted from the debug output
+    pub bot_token: SlackApiTokenValue,
+    pub installed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolutionThread {
+    pub year: i32,
+    pub day: u8,
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+    // languages poll posted in the thread, if it could be posted
+    #[serde(default)]
+    pub poll_ts: Option<SlackTs>,
+}
+
+/// Difficulty poll of a day posted by the bot, its votes being tallied for the daily summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyPoll {
+    pub year: i32,
+    pub day: u8,
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+    // latest rating of each user, recorded as the reactions are added
+    #[serde(default)]
+    pub votes: PollVotes,
 }
 
 impl Default for MemoryCache {
@@ -17,7 +151,178 @@ impl Default for MemoryCache {
 impl MemoryCache {
     pub fn new() -> MemoryCache {
         MemoryCache {
-            data: Arc::new(Mutex::new(ScrapedLeaderboard::new())),
+            data: Arc::new(RwLock::new(ScrapedLeaderboard::new())),
+            precomputed: Arc::new(Precomputed::default()),
+            pipeline_stats: Arc::new(Mutex::new(PipelineStats::default())),
+            jobs_history: Arc::new(Mutex::new(JobsHistory::new())),
+            muted: Arc::new(AtomicBool::new(false)),
+            event_stream: EventStream::new(),
+            announced_overtakes: Arc::new(Mutex::new(HashMap::new())),
+            reminders: Store::persisted("reminders"),
+            live_scoreboards: Store::persisted("live_scoreboards"),
+            solution_threads: Store::persisted("solution_threads"),
+            solution_replies: Store::persisted("solution_replies"),
+            difficulty_polls: Store::persisted("difficulty_polls"),
+            membership: Store::persisted("membership"),
+            slack_users: Store::persisted("slack_users"),
+            review_opt_outs: Store::persisted("review_opt_outs"),
+            personal_summary_opt_ins: Store::persisted("personal_summary_opt_ins"),
+            global_completions: Store::persisted("global_completions"),
+            global_heroes: Store::persisted("global_heroes"),
+            global_watch: Store::persisted("global_watch"),
+            announced_event: Store::persisted("announced_event"),
+            detections: Store::persisted("detections"),
+            guesses: Store::persisted("guesses"),
+            absences: Store::persisted("absences"),
+            exclusions: Store::persisted("exclusions"),
+            watchlists: Store::persisted("watchlists"),
+            opt_outs: Store::persisted("opt_outs"),
+            audit_log: AuditStore::persisted("audit_log"),
+            quiet_hours_held: Store::persisted("quiet_hours_held"),
+            slack_installation: Store::secret("slack_installation"),
         }
     }
+
+    /// Merge the leaderboard snapshot saved in the storage directory (if any) into the cache.
+    /// A snapshot that can not be read is set aside, so it is not overwritten by the next one.
+    pub fn load_snapshot(&self) {
+        let Some(path) = storage_path(LEADERBOARD_SNAPSHOT).filter(|path| path.exists()) else {
+            return;
+        };
+        let snapshot = fs::read_to_string(&path)
+            .map_err(BotError::from)
+            .and_then(|content| snapshot::decode(&content));
+        match snapshot {
+            Ok(snapshot) => {
+                info!(
+                    "Loaded leaderboard snapshot from {} ({} entries).",
+                    snapshot.timestamp,
+                    snapshot.leaderboard.len()
+                );
+                let mut data = self.data.write().unwrap();
+                data.merge_with(snapshot);
+            }
+            Err(e) => {
+                error!("Could not load {}: {e}", path.display());
+                let aside = path.with_extension("json.unreadable");
+                match fs::rename(&path, &aside) {
+                    Ok(()) => info!("Leaderboard snapshot kept as {}.", aside.display()),
+                    Err(e) => error!("Could not set {} aside: {e}", path.display()),
+                };
+            }
+        };
+    }
+
+    /// Save the cached leaderboard in the storage directory (if configured), in the current
+    /// version of the snapshot format.
+    pub fn save_snapshot(&self) {
+        if let Some(path) = storage_path(LEADERBOARD_SNAPSHOT) {
+            let snapshot = LeaderboardSnapshot::from(&*self.data.read().unwrap());
+            write_json(&path, &snapshot, false);
+        };
+    }
+}
+
+/// Shared state, persisted as a JSON file in the storage directory (if configured) each time
+/// it is updated, and reloaded from it at startup.
+#[derive(Clone, Default)]
+pub struct Store<T> {
+    data: Arc<Mutex<T>>,
+    path: Option<PathBuf>,
+    // whether the file is only readable by the bot user
+    secret: bool,
+}
+
+impl<T: Serialize + DeserializeOwned + Default> Store<T> {
+    /// Store persisted under `<storage_dir>/<name>.json`. Only kept in memory if no storage
+    /// directory is configured.
+    pub fn persisted(name: &str) -> Self {
+        let path = storage_path(name);
+        let data = path
+            .as_ref()
+            .and_then(|p| read_json::<T>(p))
+            .inspect(|_| info!("Loaded {name} from storage."))
+            .unwrap_or_default();
+
+        Store {
+            data: Arc::new(Mutex::new(data)),
+            path,
+            secret: false,
+        }
+    }
+
+    /// Store persisted as `persisted`, its file being only readable by the bot user (e.g. to
+    /// keep tokens).
+    pub fn secret(name: &str) -> Self {
+        Store {
+            secret: true,
+            ..Store::persisted(name)
+        }
+    }
+
+    pub fn read<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let data = self.data.lock().unwrap();
+        f(&data)
+    }
+
+    /// Apply the update to the stored data, and persist the result.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut data = self.data.lock().unwrap();
+        let result = f(&mut data);
+        if let Some(path) = &self.path {
+            write_json(path, &*data, self.secret);
+        };
+        result
+    }
+}
+
+/// Path of the `<storage_dir>/<name>.json` file, if a storage directory is configured.
+fn storage_path(name: &str) -> Option<PathBuf> {
+    let settings = &config::SETTINGS;
+    settings
+        .storage_dir
+        .as_ref()
+        .map(|dir| PathBuf::from(dir).join(format!("{name}.json")))
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    if !path.exists() {
+        return None;
+    };
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<T>(&content)
+            .inspect_err(|e| error!("Could not parse {}: {e}", path.display()))
+            .ok(),
+        Err(e) => {
+            error!("Could not read {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+// Written to a temporary file renamed over the previous one, so a crash while writing does not
+// leave a truncated file behind.
+fn write_json<T: Serialize>(path: &Path, data: &T, secret: bool) {
+    let persist = || -> BotResult<()> {
+        let tmp = path.with_extension("json.tmp");
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if secret {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // a temporary file left over by a crash keeps its permissions otherwise
+            if tmp.exists() {
+                fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+            };
+        };
+        let mut file = options.open(&tmp)?;
+        file.write_all(serde_json::to_string(data)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    };
+    if let Err(e) = persist() {
+        error!("Could not persist {}: {e}", path.display());
+    };
 }
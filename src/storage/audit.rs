@@ -0,0 +1,196 @@
+use crate::{
+    core::chat::{ChatId, MessageId},
+    error::BotResult,
+    storage::storage_path,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+use tracing::{error, info};
+
+// Number of posted messages kept in the audit log, the oldest ones being dropped first
+const AUDIT_LOG_MAX_ENTRIES: usize = 500;
+
+/// Last messages posted by the bot, oldest first.
+pub type AuditLog = VecDeque<PostedMessage>;
+
+/// Message posted (or attempted to be posted) by the bot, kept to investigate missing or
+/// duplicated messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostedMessage {
+    pub at: DateTime<Utc>,
+    // variant of the event the message was rendered from
+    pub event: String,
+    pub channel: ChatId,
+    // id of the message, None if it could not be posted or was uploaded as an image
+    pub ts: Option<MessageId>,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+/// Audit log kept in memory, each recorded message being appended to
+/// `<storage_dir>/<name>.jsonl` (if configured) by a background thread, so the posts never wait
+/// for the disk. The file is compacted to the capacity of the log when loaded at startup.
+#[derive(Clone, Default)]
+pub struct AuditStore {
+    log: Arc<Mutex<AuditLog>>,
+    appends: Option<mpsc::Sender<PostedMessage>>,
+}
+
+impl AuditStore {
+    pub fn persisted(name: &str) -> Self {
+        let Some(path) = storage_path(name).map(|path| path.with_extension("jsonl")) else {
+            return AuditStore::default();
+        };
+        let log = load(&path);
+        if !log.is_empty() {
+            info!("Loaded {name} from storage.");
+        };
+        if let Err(e) = compact(&path, &log) {
+            error!("Could not compact {}: {e}", path.display());
+        };
+
+        let (appends, received) = mpsc::channel::<PostedMessage>();
+        thread::spawn(move || {
+            for message in received {
+                if let Err(e) = append(&path, &message) {
+                    error!("Could not persist {}: {e}", path.display());
+                };
+            }
+        });
+        AuditStore {
+            log: Arc::new(Mutex::new(log)),
+            appends: Some(appends),
+        }
+    }
+
+    /// Record the message, and have it appended to the file of the log.
+    pub fn record(&self, message: PostedMessage) {
+        if let Some(appends) = &self.appends {
+            // only fails if the writing thread panicked, the message still being kept in memory
+            let _ = appends.send(message.clone());
+        };
+        record(&mut self.log.lock().unwrap(), message);
+    }
+
+    /// Last `n` messages of the log, most recent first.
+    pub fn last(&self, n: usize) -> Vec<PostedMessage> {
+        last(&self.log.lock().unwrap(), n)
+    }
+}
+
+// Messages of the file of the log, one JSON object per line, the unreadable lines being skipped.
+fn load(path: &Path) -> AuditLog {
+    let Ok(content) = fs::read_to_string(path) else {
+        return AuditLog::new();
+    };
+    let mut log = AuditLog::new();
+    content
+        .lines()
+        .filter_map(|line| {
+            serde_json::from_str::<PostedMessage>(line)
+                .inspect_err(|e| error!("Could not parse a line of {}: {e}", path.display()))
+                .ok()
+        })
+        .for_each(|message| record(&mut log, message));
+    log
+}
+
+// Rewrite the file with the messages kept in memory, through a temporary file renamed over it.
+fn compact(path: &Path, log: &AuditLog) -> BotResult<()> {
+    let mut lines = String::new();
+    for message in log {
+        lines.push_str(&serde_json::to_string(message)?);
+        lines.push('\n');
+    }
+    let tmp = PathBuf::from(format!("{}.tmp", path.display()));
+    fs::write(&tmp, lines)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn append(path: &Path, message: &PostedMessage) -> BotResult<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(format!("{}\n", serde_json::to_string(message)?).as_bytes())?;
+    Ok(())
+}
+
+/// Record the message, dropping the oldest messages beyond the capacity of the log.
+pub fn record(log: &mut AuditLog, message: PostedMessage) {
+    log.push_back(message);
+    while log.len() > AUDIT_LOG_MAX_ENTRIES {
+        log.pop_front();
+    }
+}
+
+/// Last `n` messages of the log, most recent first.
+pub fn last(log: &AuditLog, n: usize) -> Vec<PostedMessage> {
+    log.iter().rev().take(n).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn posted(n: usize) -> PostedMessage {
+        PostedMessage {
+            at: Utc::now(),
+            event: "GlobalLeaderboardUpdateMessage".to_string(),
+            channel: ChatId("C0GENERAL".to_string()),
+            ts: Some(MessageId(format!("1700000000.{n:06}"))),
+            text: format!("message #{n}"),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn appended_messages_are_loaded_back_up_to_the_capacity() {
+        let path = std::env::temp_dir().join(format!("ceo-audit-{}.jsonl", std::process::id()));
+        (0..AUDIT_LOG_MAX_ENTRIES + 2).for_each(|n| append(&path, &posted(n)).unwrap());
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"truncated\n")
+            .unwrap();
+
+        let log = load(&path);
+        compact(&path, &log).unwrap();
+        let compacted = load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(log.len(), AUDIT_LOG_MAX_ENTRIES);
+        assert_eq!(log.front().map(|m| m.text.as_str()), Some("message #2"));
+        assert_eq!(compacted, log);
+    }
+
+    #[test]
+    fn oldest_messages_are_dropped_beyond_the_capacity() {
+        let mut log = AuditLog::new();
+        (0..AUDIT_LOG_MAX_ENTRIES + 2).for_each(|n| record(&mut log, posted(n)));
+
+        assert_eq!(log.len(), AUDIT_LOG_MAX_ENTRIES);
+        assert_eq!(log.front().map(|m| m.text.as_str()), Some("message #2"));
+        let recent = last(&log, 2)
+            .into_iter()
+            .map(|m| m.text)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            recent,
+            vec![
+                format!("message #{}", AUDIT_LOG_MAX_ENTRIES + 1),
+                format!("message #{AUDIT_LOG_MAX_ENTRIES}")
+            ]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::core::{
+    commands::Command,
+    display,
+    leaderboard::ScrapedLeaderboard,
+    standings::{solve_times, standings_board, Jersey, Ranking, Scoring, Standing},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+// Time the results of the commands are served from memory, as they may also depend on the
+// current time (e.g. the time left before the next puzzle).
+const COMMAND_RESULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Most requested outputs (board, yellow jersey, fast rankings of the day), computed in the
+/// background after each leaderboard merge so commands and the daily summary are simple lookups.
+///
+/// Outputs are tied to the timestamp of the leaderboard they were computed from, and are all
+/// invalidated as soon as an output is requested for a more recent leaderboard. Lookups are
+/// made while holding the leaderboard lock, so an output can not be stored for a stale leaderboard.
+#[derive(Default)]
+pub struct Precomputed {
+    outputs: Mutex<Outputs>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct Outputs {
+    // timestamp of the leaderboard the outputs were computed from
+    timestamp: DateTime<Utc>,
+    // keyed by (year, scoring, detailed)
+    boards: HashMap<(i32, Scoring, bool), String>,
+    yellow_jerseys: HashMap<i32, String>,
+    rankings: HashMap<(i32, u8, Ranking), Vec<(String, String)>>,
+    // keyed by (current AoC year and day, command as written), with the time it was built
+    commands: HashMap<((i32, u8), String), (Instant, Command)>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PrecomputedStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Precomputed {
+    /// Formatted board (all pages) of the year, with the solve times columns if detailed.
+    pub fn board(
+        &self,
+        data: &ScrapedLeaderboard,
+        year: i32,
+        scoring: &Scoring,
+        detailed: bool,
+    ) -> String {
+        self.counted(self.lookup_board(data, year, scoring, detailed))
+    }
+
+    /// Formatted yellow jersey standings of the year.
+    pub fn yellow_jersey(&self, data: &ScrapedLeaderboard, year: i32) -> String {
+        self.counted(self.lookup_yellow_jersey(data, year))
+    }
+
+    /// Fast ranking of the day.
+    pub fn ranking(
+        &self,
+        data: &ScrapedLeaderboard,
+        ranking: &Ranking,
+        year: i32,
+        day: u8,
+    ) -> Vec<(String, String)> {
+        self.counted(self.lookup_ranking(data, ranking, year, day))
+    }
+
+    /// Result of a command, built again only if the leaderboard was updated since it was last
+    /// built with the same arguments, or if it is older than the TTL. The build is made without
+    /// holding the outputs, as it may look up the other outputs. Invalid commands are not kept,
+    /// so the error replies are never served from memory.
+    pub fn command(
+        &self,
+        data: &ScrapedLeaderboard,
+        key: ((i32, u8), String),
+        build: impl FnOnce() -> Option<Command>,
+    ) -> Option<Command> {
+        let now = Instant::now();
+        let memoized = self
+            .current(data.timestamp)
+            .commands
+            .get(&key)
+            .filter(|(built, _)| now.duration_since(*built) < COMMAND_RESULT_TTL)
+            .map(|(_, command)| command.clone());
+        if memoized.is_some() {
+            return self.counted((memoized, true));
+        };
+        let command = build();
+        if let Some(command) = command
+            .as_ref()
+            .filter(|c| !matches!(c, Command::NotValid(_)))
+        {
+            self.current(data.timestamp)
+                .commands
+                .insert(key, (now, command.clone()));
+        };
+        self.counted((command, false))
+    }
+
+    /// Compute the most requested outputs of the day, without affecting the hit/miss metric.
+    pub fn warm(&self, data: &ScrapedLeaderboard, year: i32, day: u8) {
+        for scoring in [Scoring::LOCAL, Scoring::STARS] {
+            self.lookup_board(data, year, &scoring, false);
+        }
+        self.lookup_yellow_jersey(data, year);
+        for ranking in [Ranking::PART1, Ranking::PART2, Ranking::DELTA] {
+            self.lookup_ranking(data, &ranking, year, day);
+        }
+    }
+
+    /// Drop all the outputs, e.g. when the standings depend on something else than the
+    /// leaderboard that changed.
+    pub fn invalidate(&self) {
+        *self.outputs.lock().unwrap() = Outputs::default();
+    }
+
+    pub fn stats(&self) -> PrecomputedStats {
+        PrecomputedStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn counted<V>(&self, (value, hit): (V, bool)) -> V {
+        let counter = match hit {
+            true => &self.hits,
+            false => &self.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    fn lookup_board(
+        &self,
+        data: &ScrapedLeaderboard,
+        year: i32,
+        scoring: &Scoring,
+        detailed: bool,
+    ) -> (String, bool) {
+        self.lookup(
+            data.timestamp,
+            |outputs| &mut outputs.boards,
+            (year, scoring.clone(), detailed),
+            || {
+                let times = detailed.then(|| solve_times(&data.leaderboard, year));
+                display::board(
+                    standings_board(scoring, &data.leaderboard, year),
+                    data.leaderboard.excluded_stars_for_year(year),
+                    times.as_ref(),
+                )
+            },
+        )
+    }
+
+    fn lookup_yellow_jersey(&self, data: &ScrapedLeaderboard, year: i32) -> (String, bool) {
+        self.lookup(
+            data.timestamp,
+            |outputs| &mut outputs.yellow_jerseys,
+            year,
+            || {
+                let standings = Standing::new(&data.leaderboard);
+                display::tdf_time_yearly(&standings.tdf_season(&Jersey::YELLOW, year))
+            },
+        )
+    }
+
+    fn lookup_ranking(
+        &self,
+        data: &ScrapedLeaderboard,
+        ranking: &Ranking,
+        year: i32,
+        day: u8,
+    ) -> (Vec<(String, String)>, bool) {
+        self.lookup(
+            data.timestamp,
+            |outputs| &mut outputs.rankings,
+            (year, day, ranking.clone()),
+            || Standing::new(&data.leaderboard).by_time(ranking, year, day),
+        )
+    }
+
+    // (output, whether it was already computed)
+    fn lookup<K: Eq + Hash, V: Clone>(
+        &self,
+        timestamp: DateTime<Utc>,
+        select: impl FnOnce(&mut Outputs) -> &mut HashMap<K, V>,
+        key: K,
+        compute: impl FnOnce() -> V,
+    ) -> (V, bool) {
+        let mut outputs = self.current(timestamp);
+        let computed = select(&mut outputs);
+        match computed.get(&key) {
+            Some(value) => (value.clone(), true),
+            None => {
+                let value = compute();
+                computed.insert(key, value.clone());
+                (value, false)
+            }
+        }
+    }
+
+    // Outputs of the leaderboard of the timestamp, dropped if computed from another one.
+    fn current(&self, timestamp: DateTime<Utc>) -> MutexGuard<'_, Outputs> {
+        let mut outputs = self.outputs.lock().unwrap();
+        if outputs.timestamp != timestamp {
+            *outputs = Outputs {
+                timestamp,
+                ..Default::default()
+            };
+        };
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{edge_case_members, private_leaderboard, YEAR};
+    use chrono::Duration;
+    use std::cell::Cell;
+
+    #[test]
+    fn outputs_are_invalidated_by_a_new_leaderboard() {
+        let precomputed = Precomputed::default();
+        let mut data = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(YEAR, &edge_case_members()),
+        };
+
+        precomputed.warm(&data, YEAR, 25);
+        let board = precomputed.board(&data, YEAR, &Scoring::LOCAL, false);
+        precomputed.ranking(&data, &Ranking::DELTA, YEAR, 25);
+        assert_eq!(precomputed.stats(), PrecomputedStats { hits: 2, misses: 0 });
+
+        data.timestamp += Duration::minutes(15);
+        assert_eq!(
+            precomputed.board(&data, YEAR, &Scoring::LOCAL, false),
+            board
+        );
+        assert_eq!(precomputed.stats(), PrecomputedStats { hits: 2, misses: 1 });
+    }
+
+    #[test]
+    fn command_results_are_memoized_until_the_next_scrape() {
+        let precomputed = Precomputed::default();
+        let mut data = ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(YEAR, &edge_case_members()),
+        };
+        let builds = Cell::new(0);
+        let build = |command: Option<Command>| {
+            builds.set(builds.get() + 1);
+            command
+        };
+        let key = |input: &str| ((YEAR, 25), input.to_string());
+
+        for _ in 0..2 {
+            precomputed.command(&data, key("!wins"), || build(Some(Command::Help)));
+        }
+        assert_eq!(builds.get(), 1);
+        precomputed.command(&data, key("!wins 2015"), || build(Some(Command::Help)));
+        assert_eq!(builds.get(), 2);
+
+        // commands not recognized are not memoized
+        for _ in 0..2 {
+            assert!(precomputed
+                .command(&data, key("!fifty"), || build(None))
+                .is_none());
+        }
+        assert_eq!(builds.get(), 4);
+        for _ in 0..2 {
+            precomputed.command(&data, key("!wins 1999"), || {
+                build(Some(Command::NotValid("Invalid year".to_string())))
+            });
+        }
+        assert_eq!(builds.get(), 6);
+
+        data.timestamp += Duration::minutes(15);
+        precomputed.command(&data, key("!wins"), || build(Some(Command::Help)));
+        assert_eq!(builds.get(), 7);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    core::leaderboard::{
+        Entry, Identifier, Leaderboard, MemberId, ProblemPart, ScrapedLeaderboard,
+    },
+    error::{BotError, BotResult},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Version of the snapshot format written by this release. Bumped whenever the format changes,
+/// along with a migration of the snapshots written in the previous versions.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Persisted leaderboard snapshot. Decoupled from the leaderboard types, so changing them does
+/// not break the snapshots written by the previous releases.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeaderboardSnapshot {
+    pub version: u32,
+    // time of the scrape the snapshot was taken from
+    pub timestamp: DateTime<Utc>,
+    pub stars: Vec<SnapshotStar>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotStar {
+    pub member_id: u64,
+    pub member_name: String,
+    pub year: i32,
+    pub day: u8,
+    // 1 or 2
+    pub part: u8,
+    pub timestamp: DateTime<Utc>,
+    // only known for the stars scraped from the private leaderboard API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub star_index: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u8>,
+}
+
+impl From<&ScrapedLeaderboard> for LeaderboardSnapshot {
+    fn from(scraped: &ScrapedLeaderboard) -> Self {
+        // chronological order, so successive snapshots can be compared
+        let mut entries = scraped.leaderboard.iter().collect::<Vec<&Entry>>();
+        entries.sort_unstable();
+        LeaderboardSnapshot {
+            version: SNAPSHOT_VERSION,
+            timestamp: scraped.timestamp,
+            stars: entries
+                .into_iter()
+                .map(|e| SnapshotStar {
+                    member_id: e.id.numeric.0,
+                    member_name: e.id.name.clone(),
+                    year: e.year,
+                    day: e.day,
+                    part: match e.part {
+                        ProblemPart::FIRST => 1,
+                        ProblemPart::SECOND => 2,
+                    },
+                    timestamp: e.timestamp,
+                    star_index: e.star_index,
+                    rank: e.rank,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<LeaderboardSnapshot> for ScrapedLeaderboard {
+    type Error = BotError;
+
+    fn try_from(snapshot: LeaderboardSnapshot) -> BotResult<Self> {
+        let mut leaderboard = Leaderboard::new();
+        for star in snapshot.stars {
+            let part = match star.part {
+                1 => ProblemPart::FIRST,
+                2 => ProblemPart::SECOND,
+                part => {
+                    return Err(BotError::Snapshot(format!(
+                        "invalid part {part} for day {} of {}",
+                        star.day, star.year
+                    )))
+                }
+            };
+            leaderboard.insert(Entry {
+                timestamp: star.timestamp,
+                star_index: star.star_index,
+                year: star.year,
+                day: star.day,
+                part,
+                id: Identifier {
+                    name: star.member_name,
+                    numeric: MemberId(star.member_id),
+                },
+                rank: star.rank,
+            });
+        }
+        Ok(ScrapedLeaderboard {
+            timestamp: snapshot.timestamp,
+            leaderboard,
+        })
+    }
+}
+
+/// Leaderboard of a snapshot written in any version of the format, the snapshots written before
+/// the format was versioned (leaderboard types serialized as is) being migrated.
+pub fn decode(content: &str) -> BotResult<ScrapedLeaderboard> {
+    let value = serde_json::from_str::<serde_json::Value>(content)?;
+    match value.get("version").map(|version| version.as_u64()) {
+        None => {
+            info!("Migrating leaderboard snapshot from the unversioned format.");
+            Ok(serde_json::from_value::<ScrapedLeaderboard>(value)?)
+        }
+        Some(Some(version)) if version <= SNAPSHOT_VERSION as u64 => {
+            serde_json::from_value::<LeaderboardSnapshot>(value)?.try_into()
+        }
+        Some(Some(version)) => Err(BotError::Snapshot(format!(
+            "version {version} was written by a newer release (this one reads up to version {SNAPSHOT_VERSION})"
+        ))),
+        Some(None) => Err(BotError::Snapshot("version is not a number".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{private_leaderboard, MemberFixture};
+
+    fn scraped() -> ScrapedLeaderboard {
+        ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: private_leaderboard(
+                2021,
+                &[
+                    MemberFixture::new(5201, "Gingerbread").with_day(1, 600, 60),
+                    MemberFixture::new(5202, "Marzipan").with_part1(2, 900),
+                ],
+            ),
+        }
+    }
+
+    #[test]
+    fn snapshots_are_decoded_in_any_version() {
+        let leaderboard = scraped();
+
+        let versioned = serde_json::to_string(&LeaderboardSnapshot::from(&leaderboard)).unwrap();
+        assert!(versioned.starts_with(r#"{"version":1,"#));
+        let restored = decode(&versioned).unwrap();
+        assert_eq!(restored.timestamp, leaderboard.timestamp);
+        assert_eq!(*restored.leaderboard, *leaderboard.leaderboard);
+
+        // written by the releases before the versioned format
+        let unversioned = serde_json::to_string(&leaderboard).unwrap();
+        let migrated = decode(&unversioned).unwrap();
+        assert_eq!(*migrated.leaderboard, *leaderboard.leaderboard);
+    }
+
+    #[test]
+    fn snapshots_of_newer_releases_are_rejected() {
+        let newer = r#"{"version":99,"timestamp":"2021-12-01T05:00:00Z","stars":[]}"#;
+        let error = decode(newer).unwrap_err().to_string();
+        assert!(error.contains("version 99"), "{error}");
+
+        let invalid = r#"{"version":1,"timestamp":"2021-12-01T05:00:00Z","stars":[
+            {"member_id":1,"member_name":"Elf","year":2021,"day":1,"part":3,"timestamp":"2021-12-01T05:10:00Z"}
+        ]}"#;
+        assert!(decode(invalid).is_err());
+    }
+}
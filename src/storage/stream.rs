@@ -0,0 +1,92 @@
+use crate::core::events::Event;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+// Number of events buffered for the slowest subscriber, the oldest ones being skipped beyond
+const EVENT_STREAM_CAPACITY: usize = 64;
+
+/// Announcement streamed to the subscribers of the events endpoint (e.g. stream overlays).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamedEvent {
+    pub at: DateTime<Utc>,
+    // variant of the event the text was rendered from
+    pub event: &'static str,
+    pub text: String,
+}
+
+/// Announcements published as they are dispatched, to any number of subscribers.
+#[derive(Debug, Clone)]
+pub struct EventStream(broadcast::Sender<StreamedEvent>);
+
+impl Default for EventStream {
+    fn default() -> Self {
+        EventStream::new()
+    }
+}
+
+impl EventStream {
+    pub fn new() -> EventStream {
+        let (sender, _receiver) = broadcast::channel(EVENT_STREAM_CAPACITY);
+        EventStream(sender)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StreamedEvent> {
+        self.0.subscribe()
+    }
+
+    /// Publish the event if it is an announcement, only rendered if somebody is listening.
+    pub fn publish(&self, event: &Event) {
+        // the activity of the thread is gathered (and rendered) by the messaging service, and the
+        // live scoreboard is an edit of an already posted message
+        let is_streamed = event.is_announcement()
+            && !matches!(
+                event,
+                Event::DailySolutionsThreadToSummarize(..) | Event::LiveScoreboard(_)
+            );
+        if !is_streamed || self.0.receiver_count() == 0 {
+            return;
+        };
+        // only fails if all the subscribers left in the meantime
+        let _ = self.0.send(StreamedEvent {
+            at: Utc::now(),
+            event: event.into(),
+            text: event.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        chat::{ChatId, MessageId},
+        commands::Command,
+        events::Reply,
+    };
+
+    #[test]
+    fn only_announcements_are_streamed() {
+        let stream = EventStream::new();
+        // nothing is buffered without subscribers
+        stream.publish(&Event::GlobalLeaderboardUpdateMessage(5, 300));
+
+        let mut receiver = stream.subscribe();
+        stream.publish(&Event::CommandReceived(
+            ChatId("C0GENERAL".to_string()),
+            MessageId("1700000000.000100".to_string()),
+            Reply::Thread,
+            Command::Help,
+        ));
+        stream.publish(&Event::PrivateLeaderboardUpdated);
+        stream.publish(&Event::GlobalLeaderboardUpdateMessage(8, 480));
+
+        let streamed = receiver.try_recv().unwrap();
+        assert_eq!(streamed.event, "GlobalLeaderboardUpdateMessage");
+        assert_eq!(
+            streamed.text,
+            Event::GlobalLeaderboardUpdateMessage(8, 480).to_string()
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+}
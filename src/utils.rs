@@ -1,5 +1,10 @@
-use crate::core::leaderboard::{Entry, Leaderboard};
-use chrono::{Datelike, Duration, Utc};
+use crate::core::{
+    leaderboard::{Entry, Identifier, Leaderboard, MemberId, ScrapedLeaderboard},
+    names,
+    standings::{delta_percentile, deltas_for_year_day},
+    templates::{self, Language},
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
 use itertools::Itertools;
 use serde::Serialize;
 use std::{
@@ -7,11 +12,74 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+// Days before the first puzzle unlock from which the upcoming event is taken into account
+const EVENT_WINDOW_DAYS_BEFORE: i64 = 15;
+
+// Languages looked for in the solutions threads replies (lowercase). Go is only detected as
+// `golang`, to avoid counting every "let's go".
+const LANGUAGES: [&str; 19] = [
+    "rust",
+    "python",
+    "golang",
+    "haskell",
+    "ocaml",
+    "elixir",
+    "kotlin",
+    "java",
+    "scala",
+    "c++",
+    "c#",
+    "zig",
+    "julia",
+    "typescript",
+    "javascript",
+    "ruby",
+    "clojure",
+    "excel",
+    "apl",
+];
+
+/// (language, number of messages mentioning it), most mentioned languages first.
+pub fn languages_mentioned<'a>(messages: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    messages
+        .flat_map(|message| {
+            let message = message.to_lowercase();
+            // Tokens are split on anything that can't be part of a language name (e.g. `c++`)
+            let tokens = message
+                .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+                .collect::<HashSet<&str>>();
+            LANGUAGES
+                .iter()
+                .filter(|language| tokens.contains(*language))
+                .map(|language| match *language {
+                    "golang" => "go",
+                    language => language,
+                })
+                .unique()
+                .collect::<Vec<&str>>()
+        })
+        .counts()
+        .into_iter()
+        .sorted_by_key(|(language, count)| (Reverse(*count), *language))
+        .map(|(language, count)| (language.to_string(), count))
+        .collect()
+}
+
 pub fn exponential_decay(max: f32, decay_rate: f32, time: i32) -> usize {
     (max * (1.0 - decay_rate).powi(time)).round() as usize
 }
 
-pub fn ordinal_number_suffix(num: u8) -> &'static str {
+/// Ordinal suffix in the language of the messages.
+pub fn ordinal_number_suffix(num: usize) -> &'static str {
+    match templates::language() {
+        Language::En => english_ordinal_suffix(num),
+        Language::Fr if num == 1 => "er",
+        Language::Fr => "e",
+        Language::De => ".",
+    }
+}
+
+fn english_ordinal_suffix(num: usize) -> &'static str {
     let s = num.to_string();
     if s.ends_with('1') && !s.ends_with("11") {
         "st"
@@ -24,32 +92,50 @@ pub fn ordinal_number_suffix(num: u8) -> &'static str {
     }
 }
 
-pub fn format_rank(rank: u8) -> String {
+pub fn format_rank(rank: usize) -> String {
     format!("{}{}", rank, ordinal_number_suffix(rank))
 }
 
 /// Get the last valid AOC (year, day) combo.
 /// If AOC is ongoing, returns the current year and current day.
+/// If AOC is about to start, returns the current year, first day.
 /// If AOC is over, returns the previous year, last day (25).
 pub fn current_aoc_year_day() -> (i32, u8) {
-    let now = Utc::now();
+    aoc_year_day_at(Utc::now())
+}
+
+fn aoc_year_day_at(now: DateTime<Utc>) -> (i32, u8) {
     let year = now.year();
+    match (now >= event_window_start(year), now.month()) {
+        (true, 12) => (year, now.day().min(25) as u8),
+        (true, _) => (year, 1),
+        (false, _) => (year - 1, 25),
+    }
+}
 
-    // We start taking the current year into account 15 days before the first puzzle unlocks.
-    let start_aoc_period = Entry::puzzle_unlock(year, 1)
-        .ok()
+// We start taking the current year into account 15 days before the first puzzle unlocks.
+fn event_window_start(year: i32) -> DateTime<Utc> {
+    Entry::puzzle_unlock(year, 1)
         // if something wrong happen in the parsing, we won't take the current year into account
-        .map_or_else(|| now + Duration::minutes(10), |t| t - Duration::days(15));
+        .map_or(DateTime::<Utc>::MAX_UTC, |t| {
+            t - Duration::days(EVENT_WINDOW_DAYS_BEFORE)
+        })
+}
 
-    let year = match start_aoc_period <= now {
-        true => year,
-        false => year - 1,
-    };
-    let day = match (start_aoc_period <= now, now.day() <= 25) {
-        (true, true) => now.day() as u8,
-        _ => 25,
-    };
-    (year, day)
+/// Period of the year, AoC being only scraped during the event window.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    // from 15 days before the first puzzle unlock until the end of January
+    Event,
+    // until the next event window, the commands being answered from the cached leaderboard
+    OffSeason,
+}
+
+pub fn season_at(now: DateTime<Utc>) -> Season {
+    match now >= event_window_start(now.year()) || now.month() == 1 {
+        true => Season::Event,
+        false => Season::OffSeason,
+    }
 }
 
 pub fn format_duration(duration: Duration) -> String {
@@ -70,10 +156,39 @@ pub fn format_duration_with_days(duration: Duration) -> String {
     )
 }
 
-pub fn get_new_members(cur: &Leaderboard, new: &Leaderboard) -> Vec<String> {
-    let cur = cur.iter().map(|e| &e.id.name).collect::<HashSet<&String>>();
-    let new = new.iter().map(|e| &e.id.name).collect::<HashSet<&String>>();
-    new.difference(&cur).map(|n| n.to_string()).collect()
+/// Duration written as numbers followed by their unit (`d`, `h`, `m` or `s`), e.g. `7d` or
+/// `1d12h`.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let mut seconds: i64 = 0;
+    let mut number = String::new();
+    for c in input.chars() {
+        let unit_sec = match c {
+            '0'..='9' => {
+                number.push(c);
+                continue;
+            }
+            'd' => 24 * 3600,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let value = number.parse::<i64>().ok()?.checked_mul(unit_sec)?;
+        seconds = seconds.checked_add(value)?;
+        number.clear();
+    }
+    (!input.is_empty() && number.is_empty()).then(|| Duration::seconds(seconds))
+}
+
+/// Members of the new leaderboard not present in the current one, based on their numeric id.
+pub fn get_new_members(cur: &Leaderboard, new: &Leaderboard) -> Vec<Identifier> {
+    let cur = cur.members_ids();
+    new.iter()
+        .filter(|e| !cur.contains(&e.id.numeric))
+        .map(|e| e.id.clone())
+        .unique()
+        .collect()
 }
 
 #[derive(Serialize, Debug)]
@@ -84,18 +199,49 @@ pub struct DayHighlight {
     pub n_stars: usize,
     pub name: String,
     pub delta: Option<String>,
+    // share (in percent) of the deltas of the day at least as fast as the delta
+    pub delta_percentile: Option<usize>,
     pub new_points: usize,
 }
 
-/// Retrieve needed info to compute highlights statistics
-pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHighlight> {
-    let new_entries = new.difference(current).collect::<HashSet<_>>();
+/// Merge the scraped leaderboard into the cached one, returning the new entries along with their
+/// highlights. Only the years of the scraped leaderboard are scored, before and after the merge.
+pub fn merge_with_highlights(
+    cached: &mut ScrapedLeaderboard,
+    scraped: ScrapedLeaderboard,
+) -> (Leaderboard, Vec<DayHighlight>) {
+    let years = scraped
+        .leaderboard
+        .iter()
+        .map(|e| e.year)
+        .unique()
+        .collect::<Vec<i32>>();
+    // daily scores of the members for the given years
+    let scores_for_years = |leaderboard: &Leaderboard| {
+        years
+            .iter()
+            .flat_map(|year| {
+                leaderboard
+                    .daily_stars_and_scores_per_member_for_year(*year)
+                    .into_iter()
+                    .map(|(id, days)| ((*year, id.numeric), days.map(|(_stars, score)| score)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashMap<(i32, MemberId), [usize; 25]>>()
+    };
+
+    // scores before the merge, the points of the members shifting as new members join
+    let previous_scores = scores_for_years(&cached.leaderboard);
+    let new_entries = cached.merge_with(scraped);
+    let leaderboard = &cached.leaderboard;
+    let new_scores = scores_for_years(leaderboard);
 
     // buffers
     let mut target_days_per_member = HashMap::new();
     let mut target_year_day_combinations = HashSet::new();
 
-    new_entries.iter().for_each(|e| {
+    // the stars of the excluded members are not highlighted, as they do not score
+    new_entries.ranked_entries().for_each(|e| {
         target_days_per_member
             .entry((e.year, &e.id))
             .or_insert(vec![])
@@ -104,13 +250,11 @@ pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHi
     });
 
     // We can now compute the points changes for each id for the year/day
-    let current_scores = current.daily_scores_per_year_member();
-    let new_scores = new.daily_scores_per_year_member();
     let entries_of_interest =
         target_year_day_combinations
             .iter()
             .fold(HashMap::new(), |mut acc, (year, day)| {
-                let year_day_member = new
+                let year_day_member = leaderboard
                     .entries_per_member_for_year_day(*year, *day)
                     .into_iter()
                     .map(|(id, entries)| ((year, day, id), entries));
@@ -118,6 +262,12 @@ pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHi
                 acc
             });
 
+    // deltas of the whole leaderboard on the days with new entries, to rank the new deltas
+    let deltas = target_year_day_combinations
+        .iter()
+        .map(|(year, day)| ((*year, *day), deltas_for_year_day(leaderboard, *year, *day)))
+        .collect::<HashMap<_, _>>();
+
     let highlights = target_days_per_member
         .iter()
         .flat_map(|((year, id), days)| {
@@ -127,12 +277,12 @@ pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHi
                     // Difference in score
                     let day_index = *d as usize - 1; // arrays are zero-indexed
                     let score_increase = new_scores
-                        .get(&(*year, id))
+                        .get(&(*year, id.numeric))
                         .map(|days| days[day_index])
                         // we know there is a score, unwrap safely
                         .unwrap()
-                        - current_scores
-                            .get(&(*year, id))
+                        - previous_scores
+                            .get(&(*year, id.numeric))
                             .map(|days| days[day_index])
                             // if first star for new member, no previous result
                             .unwrap_or(0);
@@ -147,17 +297,20 @@ pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHi
                         .sorted()
                         .collect::<Vec<Duration>>();
                     let delta = match durations.len() > 1 {
-                        true => Some(format_duration(durations[1] - durations[0])),
+                        true => Some(durations[1] - durations[0]),
                         false => None,
                     };
+                    let percentile = delta
+                        .and_then(|delta| delta_percentile(deltas.get(&(*year, *day))?, &delta));
 
                     DayHighlight {
                         parts_duration: durations.iter().map(|d| format_duration(*d)).collect(),
                         year: *year,
                         day: *day,
-                        name: id.name.clone(),
+                        name: names::mention(id),
                         n_stars: days.iter().filter(|d| d == &day).count(),
-                        delta,
+                        delta: delta.map(format_duration),
+                        delta_percentile: percentile,
                         new_points: score_increase,
                     }
                 })
@@ -166,5 +319,194 @@ pub fn compute_highlights(current: &Leaderboard, new: &Leaderboard) -> Vec<DayHi
         .sorted_by_key(|h| Reverse(h.new_points))
         .collect::<Vec<DayHighlight>>();
 
-    highlights
+    (new_entries, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::leaderboard::MemberId,
+        fixtures::{
+            private_leaderboard, random_board, star_ts, MemberFixture, RANDOM_BOARDS, YEAR,
+        },
+    };
+    use chrono::TimeZone;
+    use proptest::prelude::*;
+
+    #[test]
+    fn ranks_are_not_capped() {
+        let suffixes = [1, 2, 3, 11, 112, 256, 301]
+            .map(|rank| format!("{rank}{}", english_ordinal_suffix(rank)));
+        assert_eq!(
+            suffixes,
+            ["1st", "2nd", "3rd", "11th", "112th", "256th", "301st"]
+        );
+    }
+
+    #[test]
+    fn event_window_opens_two_weeks_before_the_first_puzzle() {
+        let at = |month, day| Utc.with_ymd_and_hms(2025, month, day, 12, 0, 0).unwrap();
+
+        assert_eq!(aoc_year_day_at(at(12, 3)), (2025, 3));
+        assert_eq!(aoc_year_day_at(at(12, 28)), (2025, 25));
+        assert_eq!(aoc_year_day_at(at(11, 20)), (2025, 1));
+        assert_eq!(aoc_year_day_at(at(11, 10)), (2024, 25));
+        assert_eq!(aoc_year_day_at(at(1, 15)), (2024, 25));
+
+        assert_eq!(season_at(at(11, 20)), Season::Event);
+        assert_eq!(season_at(at(1, 31)), Season::Event);
+        assert_eq!(season_at(at(2, 1)), Season::OffSeason);
+        assert_eq!(season_at(at(11, 10)), Season::OffSeason);
+    }
+
+    #[test]
+    fn durations_are_parsed_from_their_units() {
+        assert_eq!(parse_duration("7d"), Some(Duration::days(7)));
+        assert_eq!(parse_duration("1d12h"), Some(Duration::hours(36)));
+        assert_eq!(parse_duration(" 90m30s "), Some(Duration::seconds(5430)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("7"), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("7 days"), None);
+    }
+
+    #[test]
+    fn new_members_are_detected_by_id() {
+        let current =
+            private_leaderboard(YEAR, &[MemberFixture::new(1, "Santa").with_part1(1, 10)]);
+        // Same name, but different member
+        let new = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(1, "Santa").with_part1(1, 10),
+                MemberFixture::new(2, "Santa").with_part1(1, 20),
+            ],
+        );
+
+        let new_members = get_new_members(&current, &new);
+        assert_eq!(new_members.len(), 1);
+        assert_eq!(new_members[0].numeric, MemberId(2));
+    }
+
+    #[test]
+    fn renamed_members_are_not_new_members() {
+        let current =
+            private_leaderboard(YEAR, &[MemberFixture::new(1, "Santa").with_part1(1, 10)]);
+        let new = private_leaderboard(
+            YEAR,
+            &[MemberFixture::new(1, "Père Noël").with_part1(1, 10)],
+        );
+
+        assert!(get_new_members(&current, &new).is_empty());
+    }
+
+    fn highlights_between(current: Leaderboard, new: Leaderboard) -> Vec<DayHighlight> {
+        let mut cached = ScrapedLeaderboard::new();
+        cached.merge_with(ScrapedLeaderboard {
+            timestamp: Utc::now(),
+            leaderboard: current,
+        });
+        let (_new_entries, highlights) = merge_with_highlights(
+            &mut cached,
+            ScrapedLeaderboard {
+                timestamp: Utc::now(),
+                leaderboard: new,
+            },
+        );
+        highlights
+    }
+
+    #[test]
+    fn highlights_report_new_stars_and_delta() {
+        let current =
+            private_leaderboard(YEAR, &[MemberFixture::new(2001, "Santa").with_part1(1, 60)]);
+        let new = private_leaderboard(
+            YEAR,
+            &[
+                MemberFixture::new(2001, "Santa").with_day(1, 60, 30),
+                MemberFixture::new(2002, "Vixen").with_day(1, 120, 3600),
+            ],
+        );
+
+        let highlights = highlights_between(current, new);
+        assert_eq!(highlights.len(), 2);
+
+        let santa = highlights.iter().find(|h| h.name == "Santa").unwrap();
+        assert_eq!(santa.n_stars, 1);
+        assert_eq!(santa.delta, Some("00:00:30".to_string()));
+        // 1st on part 2 (2 pts), and 1 more pt on part 1 as a new member joined
+        assert_eq!(santa.new_points, 3);
+
+        let vixen = highlights.iter().find(|h| h.name == "Vixen").unwrap();
+        assert_eq!(vixen.n_stars, 2);
+        assert_eq!(vixen.parts_duration, vec!["00:02:00", "01:02:00"]);
+        // 2nd on both parts
+        assert_eq!(vixen.new_points, 2);
+    }
+
+    #[test]
+    fn no_highlights_without_new_entries() {
+        let members = [MemberFixture::new(1, "Santa").with_day(1, 60, 30)];
+        let current = private_leaderboard(YEAR, &members);
+        let new = private_leaderboard(YEAR, &members);
+
+        assert!(highlights_between(current, new).is_empty());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(RANDOM_BOARDS))]
+
+        #[test]
+        fn random_boards_highlight_every_new_star(members in random_board(15), day in 1..=25u8) {
+            let year = 2003;
+            let new = private_leaderboard(year, &members);
+            // the stars collected before the scrape time are already cached
+            let scrape = star_ts(year, day, 3600);
+            let mut current = Leaderboard::new();
+            current.extend(
+                new.iter()
+                    .filter(|e| e.timestamp.timestamp() < scrape)
+                    .cloned(),
+            );
+            let n_new_stars = new.len() - current.len();
+
+            let highlights = highlights_between(current, new);
+            prop_assert_eq!(
+                highlights.iter().map(|h| h.n_stars).sum::<usize>(),
+                n_new_stars
+            );
+            prop_assert!(highlights
+                .windows(2)
+                .all(|w| w[0].new_points >= w[1].new_points));
+            for highlight in highlights.iter() {
+                // new stars are ranked after the cached ones, and always earn points
+                prop_assert!(highlight.new_points >= highlight.n_stars);
+                prop_assert!((1..=2).contains(&highlight.n_stars));
+                prop_assert_eq!(
+                    highlight.delta.is_some(),
+                    highlight.parts_duration.len() == 2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn languages_are_counted_once_per_message() {
+        let messages = [
+            "Rust again, rust forever",
+            "python one-liner :snake:",
+            "Did it in golang, then Rust",
+            "let's go!",
+        ];
+
+        assert_eq!(
+            languages_mentioned(messages.into_iter()),
+            vec![
+                ("rust".to_string(), 2),
+                ("go".to_string(), 1),
+                ("python".to_string(), 1)
+            ]
+        );
+    }
 }